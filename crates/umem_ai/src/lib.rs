@@ -4,6 +4,7 @@ mod model_impl;
 pub mod models;
 mod providers;
 mod response_generators;
+mod spend;
 mod utils;
 use lazy_static::lazy_static;
 
@@ -11,6 +12,7 @@ pub use model_impl::*;
 pub use models::*;
 pub use providers::*;
 pub use response_generators::*;
+pub use spend::{SpendTotals, SpendTracker};
 
 pub type HashMap<K, V> = rustc_hash::FxHashMap<K, V>;
 
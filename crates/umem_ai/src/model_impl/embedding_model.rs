@@ -1,53 +1,36 @@
+use super::{provider_from_config, with_circuit_breaker, with_fixtures, with_metering};
 use crate::{
-    AIProvider, AIProviderError, AmazonBedrockProviderBuilder, OpenAIProvider,
-    models::{EmbeddingModel, EmbeddingModelError},
+    Prices, SpendTracker,
+    models::{EmbeddingModel, EmbeddingModelError, EmbeddingModelSpec},
 };
 use std::sync::Arc;
-use tokio::sync::OnceCell;
-use umem_config::CONFIG;
-
-pub static EMBEDDING_MODEL: OnceCell<Arc<EmbeddingModel>> = OnceCell::const_new();
 
 impl EmbeddingModel {
-    pub async fn get_model() -> Result<Arc<EmbeddingModel>, EmbeddingModelError> {
-        EMBEDDING_MODEL
-            .get_or_try_init(|| async {
-                match CONFIG.embedding_model.provider.clone() {
-                    umem_config::Provider::OpenAI(open_ai) => {
-                        let openai_provider = OpenAIProvider::builder()
-                            .api_key(open_ai.api_key)
-                            .base_url(open_ai.base_url)
-                            .default_headers(open_ai.default_headers.unwrap_or_default())
-                            .project(open_ai.project)
-                            .organization(open_ai.organization)
-                            .build();
-
-                        let provider = Arc::new(AIProvider::from(openai_provider));
-
-                        Ok(Arc::new(EmbeddingModel {
-                            provider,
-                            model_name: CONFIG.embedding_model.model.clone(),
-                        }))
-                    }
-                    umem_config::Provider::AmazonBedrock(config) => {
-                        let provider = AmazonBedrockProviderBuilder::default()
-                            .region(config.region)
-                            .access_key_id(config.key_id)
-                            .secret_access_key(config.access_key)
-                            .build()
-                            .await
-                            .map_err(|e| AIProviderError::ProviderBuilderError(e.into()))?;
-
-                        let provider = Arc::new(AIProvider::from(provider));
-
-                        Ok(Arc::new(EmbeddingModel {
-                            provider,
-                            model_name: CONFIG.embedding_model.model.clone(),
-                        }))
-                    }
-                }
-            })
-            .await
-            .cloned()
+    pub async fn from_config(
+        config: &umem_config::EmbeddingModel,
+        spend: &Arc<SpendTracker>,
+    ) -> Result<Arc<EmbeddingModel>, EmbeddingModelError> {
+        let price = config.price_per_million_tokens.or_else(|| {
+            EmbeddingModelSpec::lookup(&config.model).map(|spec| spec.price_per_million_tokens)
+        });
+        let provider = with_circuit_breaker(
+            with_fixtures(
+                with_metering(
+                    provider_from_config(config.provider.clone()).await?,
+                    Prices {
+                        input: price.unwrap_or_default(),
+                        output: 0.0,
+                    },
+                    spend,
+                ),
+                config.fixtures.clone(),
+            ),
+            &config.model,
+            config.circuit_breaker.clone(),
+        );
+        Ok(Arc::new(EmbeddingModel::new(
+            provider,
+            config.model.clone(),
+        )))
     }
 }
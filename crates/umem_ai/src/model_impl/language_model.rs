@@ -1,53 +1,27 @@
+use super::{provider_from_config, with_circuit_breaker, with_metering};
 use crate::{
-    AIProvider, AIProviderError, AmazonBedrockProviderBuilder, OpenAIProvider,
+    Prices, SpendTracker,
     models::{LanguageModel, LanguageModelError},
 };
 use std::sync::Arc;
-use tokio::sync::OnceCell;
-use umem_config::CONFIG;
-
-pub static LANGUAGE_MODEL: OnceCell<Arc<LanguageModel>> = OnceCell::const_new();
 
 impl LanguageModel {
-    pub async fn get_model() -> Result<Arc<LanguageModel>, LanguageModelError> {
-        LANGUAGE_MODEL
-            .get_or_try_init(|| async {
-                match CONFIG.language_model.provider.clone() {
-                    umem_config::Provider::OpenAI(open_ai) => {
-                        let openai_provider = OpenAIProvider::builder()
-                            .api_key(open_ai.api_key)
-                            .base_url(open_ai.base_url)
-                            .default_headers(open_ai.default_headers.unwrap_or_default())
-                            .project(open_ai.project)
-                            .organization(open_ai.organization)
-                            .build();
-
-                        let provider = Arc::new(AIProvider::from(openai_provider));
-
-                        Ok(Arc::new(LanguageModel {
-                            provider,
-                            model_name: CONFIG.language_model.model.clone(),
-                        }))
-                    }
-                    umem_config::Provider::AmazonBedrock(config) => {
-                        let provider = AmazonBedrockProviderBuilder::default()
-                            .region(config.region)
-                            .access_key_id(config.key_id)
-                            .secret_access_key(config.access_key)
-                            .build()
-                            .await
-                            .map_err(|e| AIProviderError::ProviderBuilderError(e.into()))?;
-
-                        let provider = Arc::new(AIProvider::from(provider));
-
-                        Ok(Arc::new(LanguageModel {
-                            provider,
-                            model_name: CONFIG.language_model.model.clone(),
-                        }))
-                    }
-                }
-            })
-            .await
-            .cloned()
+    pub async fn from_config(
+        config: &umem_config::LanguageModel,
+        spend: &Arc<SpendTracker>,
+    ) -> Result<Arc<LanguageModel>, LanguageModelError> {
+        let provider = with_circuit_breaker(
+            with_metering(
+                provider_from_config(config.provider.clone()).await?,
+                Prices {
+                    input: config.input_price_per_million_tokens.unwrap_or_default(),
+                    output: config.output_price_per_million_tokens.unwrap_or_default(),
+                },
+                spend,
+            ),
+            &config.model,
+            config.circuit_breaker.clone(),
+        );
+        Ok(Arc::new(LanguageModel::new(provider, config.model.clone())))
     }
 }
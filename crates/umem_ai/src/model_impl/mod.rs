@@ -5,3 +5,83 @@ mod reranking_model;
 pub use embedding_model::*;
 pub use language_model::*;
 pub use reranking_model::*;
+
+use crate::{
+    AIProvider, AIProviderError, AmazonBedrockProviderBuilder, CircuitBreakerProvider,
+    FixtureProvider, MeteredProvider, OpenAIProvider, Prices, SpendTracker,
+};
+use std::sync::Arc;
+
+pub(crate) async fn provider_from_config(
+    provider: umem_config::Provider,
+) -> Result<Arc<AIProvider>, AIProviderError> {
+    match provider {
+        umem_config::Provider::OpenAI(open_ai) => {
+            let openai_provider = OpenAIProvider::builder()
+                .api_key(open_ai.api_key)
+                .base_url(open_ai.base_url)
+                .default_headers(open_ai.default_headers.unwrap_or_default())
+                .project(open_ai.project)
+                .organization(open_ai.organization)
+                .build();
+
+            Ok(Arc::new(AIProvider::from(openai_provider)))
+        }
+        umem_config::Provider::AmazonBedrock(config) => {
+            let provider = AmazonBedrockProviderBuilder::default()
+                .region(config.region)
+                .access_key_id(config.key_id)
+                .secret_access_key(config.access_key)
+                .build()
+                .await
+                .map_err(|e| AIProviderError::ProviderBuilderError(e.into()))?;
+
+            Ok(Arc::new(AIProvider::from(provider)))
+        }
+    }
+}
+
+/// Puts `provider` behind a `MeteredProvider` recording into `spend` when the model has
+/// a price.
+pub(crate) fn with_metering(
+    provider: Arc<AIProvider>,
+    prices: Prices,
+    spend: &Arc<SpendTracker>,
+) -> Arc<AIProvider> {
+    match prices.input > 0.0 || prices.output > 0.0 {
+        true => Arc::new(AIProvider::from(MeteredProvider::new(
+            provider,
+            prices,
+            spend.clone(),
+        ))),
+        false => provider,
+    }
+}
+
+/// Puts `provider` behind a `FixtureProvider` when `[*.fixtures]` is configured.
+pub(crate) fn with_fixtures(
+    provider: Arc<AIProvider>,
+    fixtures: Option<umem_config::Fixtures>,
+) -> Arc<AIProvider> {
+    match fixtures {
+        Some(fixtures) => Arc::new(AIProvider::from(FixtureProvider::new(provider, fixtures))),
+        None => provider,
+    }
+}
+
+/// Puts `provider` behind a `CircuitBreakerProvider` when `[*.circuit_breaker]` is
+/// configured.
+pub(crate) fn with_circuit_breaker(
+    provider: Arc<AIProvider>,
+    model: &str,
+    circuit_breaker: Option<umem_config::CircuitBreaker>,
+) -> Arc<AIProvider> {
+    match circuit_breaker {
+        Some(circuit_breaker) => Arc::new(AIProvider::from(CircuitBreakerProvider::new(
+            provider,
+            model,
+            circuit_breaker,
+        ))),
+        None => provider,
+    }
+}
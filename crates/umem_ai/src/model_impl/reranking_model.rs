@@ -1,53 +1,33 @@
+use super::{provider_from_config, with_circuit_breaker, with_fixtures, with_metering};
 use crate::{
-    AIProvider, AIProviderError, AmazonBedrockProviderBuilder, OpenAIProvider,
+    Prices, SpendTracker,
     models::{RerankingModel, RerankingModelError},
 };
 use std::sync::Arc;
-use tokio::sync::OnceCell;
-use umem_config::CONFIG;
-
-pub static RERANKING_MODEL: OnceCell<Arc<RerankingModel>> = OnceCell::const_new();
 
 impl RerankingModel {
-    pub async fn get_model() -> Result<Arc<RerankingModel>, RerankingModelError> {
-        RERANKING_MODEL
-            .get_or_try_init(|| async {
-                match CONFIG.reranking_model.provider.clone() {
-                    umem_config::Provider::OpenAI(open_ai) => {
-                        let openai_provider = OpenAIProvider::builder()
-                            .api_key(open_ai.api_key)
-                            .base_url(open_ai.base_url)
-                            .default_headers(open_ai.default_headers.unwrap_or_default())
-                            .project(open_ai.project)
-                            .organization(open_ai.organization)
-                            .build();
-
-                        let provider = Arc::new(AIProvider::from(openai_provider));
-
-                        Ok(Arc::new(RerankingModel {
-                            provider,
-                            model_name: CONFIG.reranking_model.model.clone(),
-                        }))
-                    }
-                    umem_config::Provider::AmazonBedrock(config) => {
-                        let provider = AmazonBedrockProviderBuilder::default()
-                            .region(config.region)
-                            .access_key_id(config.key_id)
-                            .secret_access_key(config.access_key)
-                            .build()
-                            .await
-                            .map_err(|e| AIProviderError::ProviderBuilderError(e.into()))?;
-
-                        let provider = Arc::new(AIProvider::from(provider));
-
-                        Ok(Arc::new(RerankingModel {
-                            provider,
-                            model_name: CONFIG.reranking_model.model.clone(),
-                        }))
-                    }
-                }
-            })
-            .await
-            .cloned()
+    pub async fn from_config(
+        config: &umem_config::RerankingModel,
+        spend: &Arc<SpendTracker>,
+    ) -> Result<Arc<RerankingModel>, RerankingModelError> {
+        let provider = with_circuit_breaker(
+            with_fixtures(
+                with_metering(
+                    provider_from_config(config.provider.clone()).await?,
+                    Prices {
+                        input: config.price_per_million_tokens.unwrap_or_default(),
+                        output: 0.0,
+                    },
+                    spend,
+                ),
+                config.fixtures.clone(),
+            ),
+            &config.model,
+            config.circuit_breaker.clone(),
+        );
+        Ok(Arc::new(RerankingModel::new(
+            provider,
+            config.model.clone(),
+        )))
     }
 }
@@ -15,10 +15,18 @@ pub struct EmbeddingModel {
 }
 
 impl EmbeddingModel {
-    fn new(provider: Arc<AIProvider>, model_name: String) -> Self {
+    pub(crate) fn new(provider: Arc<AIProvider>, model_name: String) -> Self {
         Self {
             provider,
             model_name,
         }
     }
+
+    /// Same provider, another model, e.g. a cheaper one for a single call.
+    pub fn with_model_name(&self, model_name: impl Into<String>) -> Self {
+        Self {
+            provider: Arc::clone(&self.provider),
+            model_name: model_name.into(),
+        }
+    }
 }
@@ -1,7 +1,9 @@
 pub mod embedding;
 pub mod language;
+pub mod registry;
 pub mod reranking;
 
 pub use embedding::*;
 pub use language::*;
+pub use registry::*;
 pub use reranking::*;
@@ -0,0 +1,148 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ModelRegistryError {
+    #[error(
+        "embedding model '{0}' is not in the model registry; set embedding_model_dimensions in [vector_store]"
+    )]
+    UnknownDimensions(String),
+
+    #[error(
+        "embedding_model_dimensions is {configured} but embedding model '{model}' produces {expected}"
+    )]
+    DimensionMismatch {
+        model: String,
+        configured: u16,
+        expected: u16,
+    },
+}
+
+/// What is known about an embedding model ahead of time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddingModelSpec {
+    pub name: &'static str,
+    pub dimensions: u16,
+    pub max_tokens: u32,
+    /// US dollars per million input tokens.
+    pub price_per_million_tokens: f64,
+    /// Whether the model can be asked for fewer than `dimensions` dimensions.
+    pub reducible: bool,
+}
+
+pub const EMBEDDING_MODEL_SPECS: &[EmbeddingModelSpec] = &[
+    EmbeddingModelSpec {
+        name: "text-embedding-3-small",
+        dimensions: 1536,
+        max_tokens: 8191,
+        price_per_million_tokens: 0.02,
+        reducible: true,
+    },
+    EmbeddingModelSpec {
+        name: "text-embedding-3-large",
+        dimensions: 3072,
+        max_tokens: 8191,
+        price_per_million_tokens: 0.13,
+        reducible: true,
+    },
+    EmbeddingModelSpec {
+        name: "text-embedding-ada-002",
+        dimensions: 1536,
+        max_tokens: 8191,
+        price_per_million_tokens: 0.10,
+        reducible: false,
+    },
+    EmbeddingModelSpec {
+        name: "amazon.titan-embed-text-v2:0",
+        dimensions: 1024,
+        max_tokens: 8192,
+        price_per_million_tokens: 0.02,
+        reducible: true,
+    },
+    EmbeddingModelSpec {
+        name: "amazon.titan-embed-text-v1",
+        dimensions: 1536,
+        max_tokens: 8192,
+        price_per_million_tokens: 0.10,
+        reducible: false,
+    },
+    EmbeddingModelSpec {
+        name: "cohere.embed-english-v3",
+        dimensions: 1024,
+        max_tokens: 512,
+        price_per_million_tokens: 0.10,
+        reducible: false,
+    },
+    EmbeddingModelSpec {
+        name: "cohere.embed-multilingual-v3",
+        dimensions: 1024,
+        max_tokens: 512,
+        price_per_million_tokens: 0.10,
+        reducible: false,
+    },
+    EmbeddingModelSpec {
+        name: "bge-m3",
+        dimensions: 1024,
+        max_tokens: 8192,
+        price_per_million_tokens: 0.012,
+        reducible: false,
+    },
+    EmbeddingModelSpec {
+        name: "bge-large-en-v1.5",
+        dimensions: 1024,
+        max_tokens: 512,
+        price_per_million_tokens: 0.204,
+        reducible: false,
+    },
+    EmbeddingModelSpec {
+        name: "bge-base-en-v1.5",
+        dimensions: 768,
+        max_tokens: 512,
+        price_per_million_tokens: 0.067,
+        reducible: false,
+    },
+    EmbeddingModelSpec {
+        name: "bge-small-en-v1.5",
+        dimensions: 384,
+        max_tokens: 512,
+        price_per_million_tokens: 0.02,
+        reducible: false,
+    },
+];
+
+impl EmbeddingModelSpec {
+    /// Finds a model by name, also accepting provider-prefixed names such as
+    /// `@cf/baai/bge-m3`.
+    pub fn lookup(model: &str) -> Option<&'static EmbeddingModelSpec> {
+        let short_name = model.rsplit('/').next().unwrap_or(model);
+        EMBEDDING_MODEL_SPECS
+            .iter()
+            .find(|spec| spec.name == model || spec.name == short_name)
+    }
+}
+
+/// Fills in `embedding_model_dimensions` from the registry when it is not configured,
+/// and rejects a configured value the embedding model cannot produce.
+pub fn resolve_vector_store(
+    vector_store: &umem_config::VectorStore,
+    embedding_model: &umem_config::EmbeddingModel,
+) -> Result<umem_config::VectorStore, ModelRegistryError> {
+    let spec = EmbeddingModelSpec::lookup(&embedding_model.model);
+
+    match (vector_store.embedding_model_dimensions(), spec) {
+        (Some(configured), Some(spec))
+            if configured != spec.dimensions
+                && !(spec.reducible && configured < spec.dimensions) =>
+        {
+            Err(ModelRegistryError::DimensionMismatch {
+                model: embedding_model.model.clone(),
+                configured,
+                expected: spec.dimensions,
+            })
+        }
+        (Some(_), _) => Ok(vector_store.clone()),
+        (None, Some(spec)) => Ok(vector_store.with_embedding_model_dimensions(spec.dimensions)),
+        (None, None) => Err(ModelRegistryError::UnknownDimensions(
+            embedding_model.model.clone(),
+        )),
+    }
+}
@@ -26,13 +26,12 @@ use aws_sdk_bedrockruntime::{
     },
 };
 use base64::Engine;
-use futures::future::join_all;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::Map;
 use std::sync::Arc;
 use thiserror::Error;
-use tokio::sync::Semaphore;
+use tokio::{sync::Semaphore, task::JoinSet};
 
 #[derive(Clone, Debug)]
 pub struct AmazonBedrockProvider {
@@ -469,9 +468,11 @@ impl Embeds for AmazonBedrockProvider {
         }
 
         let semaphore = Arc::new(Semaphore::new(request.max_parallels));
-        let mut embedding_invoke_handles = Vec::with_capacity(request.input.len());
+        // NOTE: tasks in a JoinSet are aborted when it is dropped, so an embed whose
+        // caller went away stops invoking the model
+        let mut embedding_invokes = JoinSet::new();
 
-        for data in request.input.into_iter() {
+        for (index, data) in request.input.into_iter().enumerate() {
             let permit = semaphore.clone().acquire_owned().await.map_err(|e| {
                 ResponseGeneratorError::InternalServerError(format!(
                     "Failed to acquire thread lock while making multiple requests. Details: {e}"
@@ -481,7 +482,7 @@ impl Embeds for AmazonBedrockProvider {
             let bedrockruntime_client = Arc::clone(&self.bedrockruntime_client);
             let model_name = request.model.model_name.clone();
 
-            let handle = tokio::spawn(async move {
+            embedding_invokes.spawn(async move {
                 let invoke_res = bedrockruntime_client
                     .invoke_model()
                     .model_id(&model_name)
@@ -497,29 +498,25 @@ impl Embeds for AmazonBedrockProvider {
                         ))
                     });
                 drop(permit);
-                invoke_res
+                (index, invoke_res)
             });
-
-            embedding_invoke_handles.push(handle)
         }
 
-        let results: Result<Vec<InvokeModelOutput>, ResponseGeneratorError> =
-            join_all(embedding_invoke_handles)
-                .await
-                .into_iter()
-                .map(|r| {
-                    r.map_err(|e| {
-                        ResponseGeneratorError::InternalServerError(format!(
-                            "Failed to acquire result from the relevant thread. Details: {e}"
-                        ))
-                    })
-                })
-                .map(|r| r.and_then(|inner| inner))
-                .collect();
+        let mut results: Vec<(usize, InvokeModelOutput)> =
+            Vec::with_capacity(embedding_invokes.len());
+        while let Some(joined) = embedding_invokes.join_next().await {
+            let (index, invoke_res) = joined.map_err(|e| {
+                ResponseGeneratorError::InternalServerError(format!(
+                    "Failed to acquire result from the relevant thread. Details: {e}"
+                ))
+            })?;
+            results.push((index, invoke_res?));
+        }
+        results.sort_by_key(|(index, _)| *index);
 
-        let embeddings = results?
+        let embeddings = results
             .into_iter()
-            .map(|r| {
+            .map(|(_, r)| {
                 serde_json::from_slice::<AmazonBedrockEmbeddingInvokeModelResponse>(
                     &r.body.into_inner(),
                 )
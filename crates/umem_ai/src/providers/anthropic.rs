@@ -1,13 +1,29 @@
+use crate::GeneratesObject;
 use crate::GeneratesText;
 use crate::ResponseGeneratorError;
+use crate::providers::OpenAIProvider;
+use crate::reqwest_client;
 use crate::response_generators::GenerateTextRequest;
 use crate::response_generators::GenerateTextResponse;
+use crate::response_generators::generate_object::{GenerateObjectRequest, GenerateObjectResponse};
+use crate::response_generators::messages::{FilePart, Message, UserMessagePart, UserModelMessage};
 use crate::utils;
 use anyhow::Result;
 use async_trait::async_trait;
+use base64::Engine;
 use reqwest::header::HeaderMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
 use typed_builder::TypedBuilder;
 
+/// The only tool offered to the model on `generate_object`; its input schema is the
+/// output schema, so the arguments of the forced call are the object.
+const EMIT_TOOL: &str = "emit";
+
+/// Property a non-object output is wrapped in, since tool inputs must be objects.
+const WRAPPED_OUTPUT: &str = "output";
+
 #[derive(TypedBuilder, Debug, Clone)]
 pub struct AnthropicProvider {
     #[builder(setter(transform = |value: impl Into<String>| value.into()))]
@@ -25,6 +41,123 @@ pub struct AnthropicProvider {
     pub headers: HeaderMap,
 }
 
+impl AnthropicProvider {
+    /// The Messages API body for `request`, and whether its output is wrapped in an
+    /// object under `WRAPPED_OUTPUT`.
+    pub fn normalize_generate_object_request<
+        T: Clone + JsonSchema + Serialize + Send + Sync + DeserializeOwned,
+    >(
+        &self,
+        request: &GenerateObjectRequest<T>,
+    ) -> (Value, bool) {
+        let system = OpenAIProvider::normalize_system_message(&request.messages);
+        let messages = Self::normalize_messages(&request.messages);
+        let (input_schema, wrapped) =
+            Self::emit_input_schema(request.output_schema.clone().to_value());
+
+        let mut body = json!({
+            "model": request.model.model_name,
+            "max_tokens": request.max_output_tokens.unwrap_or(8192),
+            "system": system,
+            "messages": messages,
+            "tools": [{
+                "name": EMIT_TOOL,
+                "description": "Emit the requested output. Call it exactly once with the complete output as its input.",
+                "input_schema": input_schema,
+            }],
+            "tool_choice": {
+                "type": "tool",
+                "name": EMIT_TOOL
+            }
+        });
+        if let Some(temperature) = request.temperature {
+            body["temperature"] = json!(temperature);
+        }
+        if let Some(top_p) = request.top_p {
+            body["top_p"] = json!(top_p);
+        }
+        if let Some(top_k) = request.top_k {
+            body["top_k"] = json!(top_k);
+        }
+        (body, wrapped)
+    }
+
+    /// The output schema as a tool input schema, and whether it had to be wrapped in an
+    /// object under `WRAPPED_OUTPUT`.
+    fn emit_input_schema(mut schema: Value) -> (Value, bool) {
+        let Some(object) = schema.as_object_mut() else {
+            return (schema, false);
+        };
+        object.remove("$schema");
+        if object.get("type").and_then(Value::as_str) == Some("object") {
+            return (schema, false);
+        }
+
+        // NOTE: `$defs` stay at the root so the `#/$defs/..` references still resolve
+        let defs = object.remove("$defs");
+        let mut wrapper = json!({
+            "type": "object",
+            "properties": { (WRAPPED_OUTPUT): schema },
+            "required": [WRAPPED_OUTPUT],
+        });
+        if let Some(defs) = defs {
+            wrapper["$defs"] = defs;
+        }
+        (wrapper, true)
+    }
+
+    /// One `user` turn per user message, in order. System messages are sent apart as
+    /// the system prompt.
+    pub(crate) fn normalize_messages(messages: &[Message]) -> Vec<Value> {
+        messages
+            .iter()
+            .filter_map(|message| match message {
+                Message::System(_) => None,
+                Message::User(user_message) => Some(json!({
+                    "role": "user",
+                    "content": Self::normalize_user_message(user_message),
+                })),
+            })
+            .collect()
+    }
+
+    fn normalize_user_message(message: &UserModelMessage) -> Vec<Value> {
+        match message {
+            UserModelMessage::Text(text) => vec![json!({"type": "text", "text": text})],
+            UserModelMessage::Parts(user_message_parts) => user_message_parts
+                .iter()
+                .map(|ump| match ump {
+                    UserMessagePart::Text(text) => json!({"type": "text", "text": text}),
+                    UserMessagePart::Image(image_part) => json!({
+                        "type": "image",
+                        "source": Self::normalize_source(image_part, mime::IMAGE_PNG),
+                    }),
+                    UserMessagePart::File(file_part) => json!({
+                        "type": "document",
+                        "source": Self::normalize_source(file_part, mime::APPLICATION_PDF),
+                    }),
+                })
+                .collect(),
+        }
+    }
+
+    fn normalize_source(part: &FilePart, default_media_type: mime::Mime) -> Value {
+        match part {
+            FilePart::Url(url, _) => json!({"type": "url", "url": url}),
+            FilePart::Base64(b64, media_type) => json!({
+                "type": "base64",
+                "media_type": media_type.clone().unwrap_or(default_media_type).to_string(),
+                "data": b64,
+            }),
+            FilePart::Buffer(buf, media_type) => json!({
+                "type": "base64",
+                "media_type": media_type.clone().unwrap_or(default_media_type).to_string(),
+                "data": base64::engine::general_purpose::STANDARD.encode(buf),
+            }),
+        }
+    }
+}
+
 #[async_trait]
 impl GeneratesText for AnthropicProvider {
     async fn generate_text(
@@ -35,9 +168,131 @@ impl GeneratesText for AnthropicProvider {
     }
 }
 
+#[async_trait]
+impl GeneratesObject for AnthropicProvider {
+    async fn generate_object<T>(
+        &self,
+        request: GenerateObjectRequest<T>,
+    ) -> Result<GenerateObjectResponse<T>, ResponseGeneratorError>
+    where
+        T: Clone + JsonSchema + Serialize + DeserializeOwned + Send + Sync,
+    {
+        let (request_body, wrapped) = self.normalize_generate_object_request(&request);
+
+        let response = reqwest_client
+            .post(format!("{}/messages", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", &self.api_version)
+            .header("Content-Type", "application/json")
+            .headers(self.headers.clone())
+            .headers(request.headers)
+            .body(request_body.to_string())
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<AnthropicMessagesResponse>()
+            .await?;
+
+        Ok(GenerateObjectResponse {
+            output: response.emitted_object(wrapped)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessagesResponse {
+    content: Vec<ContentBlock>,
+    stop_reason: Option<String>,
+}
+
+impl AnthropicMessagesResponse {
+    /// The arguments of the `emit` call, checked against `T`.
+    fn emitted_object<T: DeserializeOwned>(
+        self,
+        wrapped: bool,
+    ) -> Result<T, ResponseGeneratorError> {
+        // NOTE: a call cut off by the token limit has partial arguments that may still parse
+        if self.stop_reason.as_deref() == Some("max_tokens") {
+            return Err(ResponseGeneratorError::InvalidProviderResponse(
+                "output was truncated at max_tokens".into(),
+            ));
+        }
+
+        let mut input = self
+            .content
+            .into_iter()
+            .find_map(|block| match block {
+                ContentBlock::ToolUse { name, input } if name == EMIT_TOOL => Some(input),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                ResponseGeneratorError::InvalidProviderResponse(
+                    "was expecting the model to call the emit tool".into(),
+                )
+            })?;
+        if wrapped {
+            input = input
+                .get_mut(WRAPPED_OUTPUT)
+                .map(Value::take)
+                .unwrap_or(Value::Null);
+        }
+
+        serde_json::from_value::<T>(input.clone())
+            .map_err(|e| ResponseGeneratorError::Deserialization(e, input.to_string()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+#[serde(rename_all = "snake_case")]
+enum ContentBlock {
+    ToolUse {
+        name: String,
+        input: Value,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        AIProvider, models::LanguageModel,
+        response_generators::generate_object::GenerateObjectRequestBuilder,
+    };
+    use std::sync::Arc;
+
+    #[derive(Clone, JsonSchema, Serialize, Deserialize, Debug, PartialEq)]
+    struct Holiday {
+        name: String,
+        traditions: String,
+    }
+
+    fn request<T: Clone + JsonSchema + Serialize + Send + Sync + DeserializeOwned>()
+    -> GenerateObjectRequest<T> {
+        let provider = AnthropicProvider::builder()
+            .api_key("sk-some-api-key")
+            .build();
+        let model = Arc::new(LanguageModel {
+            provider: Arc::new(AIProvider::from(provider)),
+            model_name: "claude-sonnet-4-5".to_string(),
+        });
+        GenerateObjectRequestBuilder::<T>::new()
+            .model(model)
+            .system("You are a helpful assistant.".to_string())
+            .prompt("Invent a new holiday and describe its traditions.".to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn response(content: Value, stop_reason: &str) -> AnthropicMessagesResponse {
+        serde_json::from_value(json!({
+            "content": content,
+            "stop_reason": stop_reason,
+        }))
+        .unwrap()
+    }
 
     #[test]
     fn test_building_anthropic_provider() {
@@ -46,4 +301,83 @@ mod tests {
             .build();
         dbg!("Anthropic Provider: {:?}", provider);
     }
+
+    #[test]
+    fn test_generate_object_request_forces_emit_tool() {
+        let provider = AnthropicProvider::builder()
+            .api_key("sk-some-api-key")
+            .build();
+        let (body, wrapped) = provider.normalize_generate_object_request(&request::<Holiday>());
+
+        assert!(!wrapped);
+        assert_eq!(
+            body["messages"],
+            json!([{
+                "role": "user",
+                "content": [{"type": "text", "text": "Invent a new holiday and describe its traditions."}],
+            }])
+        );
+        assert_eq!(body["tool_choice"], json!({"type": "tool", "name": "emit"}));
+        assert_eq!(body["tools"][0]["name"], "emit");
+        let input_schema = &body["tools"][0]["input_schema"];
+        assert_eq!(input_schema["type"], "object");
+        assert!(input_schema.get("$schema").is_none());
+        assert!(input_schema["properties"]["traditions"].is_object());
+        assert!(body.get("temperature").is_none());
+    }
+
+    #[test]
+    fn test_non_object_output_is_wrapped() {
+        let provider = AnthropicProvider::builder()
+            .api_key("sk-some-api-key")
+            .build();
+        let (body, wrapped) =
+            provider.normalize_generate_object_request(&request::<Vec<Holiday>>());
+        assert!(wrapped);
+        let input_schema = &body["tools"][0]["input_schema"];
+        assert_eq!(input_schema["required"], json!(["output"]));
+        assert_eq!(input_schema["properties"]["output"]["type"], "array");
+        assert!(input_schema["$defs"]["Holiday"].is_object());
+
+        let holidays: Vec<Holiday> = response(
+            json!([{
+                "type": "tool_use",
+                "id": "toolu_01",
+                "name": "emit",
+                "input": {"output": [{"name": "Lantern Day", "traditions": "lanterns"}]},
+            }]),
+            "tool_use",
+        )
+        .emitted_object(true)
+        .unwrap();
+        assert_eq!(holidays[0].name, "Lantern Day");
+    }
+
+    #[test]
+    fn test_emitted_object_is_validated() {
+        let emitted = |input: Value| {
+            response(
+                json!([
+                    {"type": "text", "text": "Here is the holiday."},
+                    {"type": "tool_use", "id": "toolu_01", "name": "emit", "input": input},
+                ]),
+                "tool_use",
+            )
+        };
+
+        let holiday: Holiday = emitted(json!({"name": "Lantern Day", "traditions": "lanterns"}))
+            .emitted_object(false)
+            .unwrap();
+        assert_eq!(holiday.traditions, "lanterns");
+
+        assert!(matches!(
+            emitted(json!({"name": "Lantern Day"})).emitted_object::<Holiday>(false),
+            Err(ResponseGeneratorError::Deserialization(..))
+        ));
+        assert!(matches!(
+            response(json!([{"type": "text", "text": "{}"}]), "end_turn")
+                .emitted_object::<Holiday>(false),
+            Err(ResponseGeneratorError::InvalidProviderResponse(_))
+        ));
+    }
 }
@@ -0,0 +1,285 @@
+use crate::{
+    AIProvider, Embeds, GenerateObjectRequest, GenerateObjectResponse, GenerateTextRequest,
+    GenerateTextResponse, GeneratesObject, GeneratesText, ModerationResponse, RerankRequest,
+    RerankResponse, Reranks, ReranksStructuredData, ResponseGeneratorError,
+    StructuredRerankRequest, StructuredRerankResponse,
+    embed::{EmbeddingRequest, EmbeddingResponse},
+};
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Serialize, de::DeserializeOwned};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+const DEFAULT_FAILURE_RATE: f32 = 0.5;
+const DEFAULT_WINDOW: usize = 20;
+const DEFAULT_OPEN_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Circuit {
+    Closed,
+    Open {
+        until: Instant,
+    },
+    /// A probe call is in flight; everything else is failed until it settles.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Breaker {
+    failure_rate: f32,
+    window: usize,
+    open_for: Duration,
+    circuit: Circuit,
+    /// Whether each of the latest calls failed, oldest first.
+    outcomes: VecDeque<bool>,
+}
+
+impl Breaker {
+    fn new(config: umem_config::CircuitBreaker) -> Self {
+        Self {
+            failure_rate: config.failure_rate.unwrap_or(DEFAULT_FAILURE_RATE),
+            window: config.window.unwrap_or(DEFAULT_WINDOW).max(1),
+            open_for: Duration::from_secs(config.open_secs.unwrap_or(DEFAULT_OPEN_SECS)),
+            circuit: Circuit::Closed,
+            outcomes: VecDeque::new(),
+        }
+    }
+
+    /// Whether a call may go through at `now`.
+    fn admit(&mut self, now: Instant) -> bool {
+        match self.circuit {
+            Circuit::Closed => true,
+            Circuit::Open { until } if now >= until => {
+                self.circuit = Circuit::HalfOpen;
+                true
+            }
+            Circuit::Open { .. } | Circuit::HalfOpen => false,
+        }
+    }
+
+    fn record(&mut self, failed: bool, now: Instant) {
+        match self.circuit {
+            Circuit::HalfOpen if failed => self.open(now),
+            Circuit::HalfOpen => {
+                tracing::info!("circuit breaker closed after a successful probe");
+                self.circuit = Circuit::Closed;
+                self.outcomes.clear();
+            }
+            Circuit::Closed => {
+                self.outcomes.push_back(failed);
+                if self.outcomes.len() > self.window {
+                    self.outcomes.pop_front();
+                }
+                let failures = self.outcomes.iter().filter(|failed| **failed).count();
+                if self.outcomes.len() == self.window
+                    && failures as f32 >= self.failure_rate * self.window as f32
+                {
+                    self.open(now);
+                }
+            }
+            // NOTE: calls admitted before the breaker opened still settle afterwards
+            Circuit::Open { .. } => {}
+        }
+    }
+
+    fn open(&mut self, now: Instant) {
+        tracing::warn!(
+            "circuit breaker opened for {:?} after failed provider calls",
+            self.open_for
+        );
+        self.circuit = Circuit::Open {
+            until: now + self.open_for,
+        };
+        self.outcomes.clear();
+    }
+}
+
+/// Counts against the provider unless settled with a success, so calls abandoned on a
+/// timeout are recorded as failures too.
+struct Permit<'a> {
+    breaker: &'a Mutex<Breaker>,
+    settled: bool,
+}
+
+impl Permit<'_> {
+    fn settle(mut self, failed: bool) {
+        self.settled = true;
+        lock(self.breaker).record(failed, Instant::now());
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if !self.settled {
+            lock(self.breaker).record(true, Instant::now());
+        }
+    }
+}
+
+fn lock(breaker: &Mutex<Breaker>) -> std::sync::MutexGuard<'_, Breaker> {
+    breaker
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Whether `error` says the provider is unhealthy, rather than that the request was bad.
+fn is_provider_failure(error: &ResponseGeneratorError) -> bool {
+    !matches!(
+        error,
+        ResponseGeneratorError::InvalidArgumentsProvided(_)
+            | ResponseGeneratorError::StructuredRerankDocumentsSerializationError(_)
+            | ResponseGeneratorError::FixtureError(_)
+            | ResponseGeneratorError::CircuitOpen(_)
+    )
+}
+
+/// Calls another provider until too many of its calls fail, then fails every call
+/// straight away for a while instead of letting callers wait out their timeouts.
+#[derive(Debug)]
+pub struct CircuitBreakerProvider {
+    inner: Arc<AIProvider>,
+    /// Names the provider in errors and logs.
+    name: String,
+    breaker: Mutex<Breaker>,
+}
+
+impl CircuitBreakerProvider {
+    pub fn new(
+        inner: Arc<AIProvider>,
+        name: impl Into<String>,
+        config: umem_config::CircuitBreaker,
+    ) -> Self {
+        Self {
+            inner,
+            name: name.into(),
+            breaker: Mutex::new(Breaker::new(config)),
+        }
+    }
+
+    pub(crate) fn inner(&self) -> &AIProvider {
+        &self.inner
+    }
+
+    async fn call<R>(
+        &self,
+        call: impl Future<Output = Result<R, ResponseGeneratorError>>,
+    ) -> Result<R, ResponseGeneratorError> {
+        if !lock(&self.breaker).admit(Instant::now()) {
+            return Err(ResponseGeneratorError::CircuitOpen(self.name.clone()));
+        }
+        let permit = Permit {
+            breaker: &self.breaker,
+            settled: false,
+        };
+
+        let result = call.await;
+        permit.settle(result.as_ref().is_err_and(is_provider_failure));
+        result
+    }
+
+    pub(crate) async fn moderate(
+        &self,
+        model: &str,
+        inputs: Vec<String>,
+    ) -> Result<Vec<ModerationResponse>, ResponseGeneratorError> {
+        self.call(self.inner.do_moderate(model, inputs)).await
+    }
+}
+
+#[async_trait]
+impl GeneratesText for CircuitBreakerProvider {
+    async fn generate_text(
+        &self,
+        request: GenerateTextRequest,
+    ) -> Result<GenerateTextResponse, ResponseGeneratorError> {
+        self.call(self.inner.do_generate_text(request)).await
+    }
+}
+
+#[async_trait]
+impl GeneratesObject for CircuitBreakerProvider {
+    async fn generate_object<T: Clone + JsonSchema + Serialize + Send + Sync + DeserializeOwned>(
+        &self,
+        request: GenerateObjectRequest<T>,
+    ) -> Result<GenerateObjectResponse<T>, ResponseGeneratorError> {
+        self.call(self.inner.do_generate_object(request)).await
+    }
+}
+
+#[async_trait]
+impl Embeds for CircuitBreakerProvider {
+    async fn embed(
+        &self,
+        request: EmbeddingRequest,
+    ) -> Result<EmbeddingResponse, ResponseGeneratorError> {
+        self.call(self.inner.do_embed(request)).await
+    }
+}
+
+#[async_trait]
+impl Reranks for CircuitBreakerProvider {
+    async fn rerank(
+        &self,
+        request: RerankRequest,
+    ) -> Result<RerankResponse, ResponseGeneratorError> {
+        self.call(self.inner.do_reranking(request)).await
+    }
+}
+
+#[async_trait]
+impl ReranksStructuredData for CircuitBreakerProvider {
+    async fn rerank_structured<T>(
+        &self,
+        request: StructuredRerankRequest<T>,
+    ) -> Result<StructuredRerankResponse<T>, ResponseGeneratorError>
+    where
+        T: Serialize + Clone + Send + Sync,
+    {
+        self.call(self.inner.do_structured_reranking(request)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_opens_on_failure_rate_and_closes_after_a_probe() {
+        let mut breaker = Breaker::new(umem_config::CircuitBreaker {
+            failure_rate: Some(0.5),
+            window: Some(4),
+            open_secs: Some(10),
+        });
+        let now = Instant::now();
+
+        for failed in [true, false, true] {
+            assert!(breaker.admit(now));
+            breaker.record(failed, now);
+        }
+        assert_eq!(breaker.circuit, Circuit::Closed);
+        breaker.record(false, now);
+        assert_eq!(
+            breaker.circuit,
+            Circuit::Open {
+                until: now + Duration::from_secs(10)
+            }
+        );
+        assert!(!breaker.admit(now + Duration::from_secs(9)));
+
+        let later = now + Duration::from_secs(10);
+        assert!(breaker.admit(later));
+        assert!(!breaker.admit(later), "only one probe at a time");
+        breaker.record(true, later);
+        assert!(!breaker.admit(later + Duration::from_secs(1)));
+
+        let probe = later + Duration::from_secs(10);
+        assert!(breaker.admit(probe));
+        breaker.record(false, probe);
+        assert_eq!(breaker.circuit, Circuit::Closed);
+        assert!(breaker.admit(probe));
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    AIProvider, Embeds, Ranking, RerankRequest, RerankResponse, Reranks, ResponseGeneratorError,
+    embed::{EmbeddingRequest, EmbeddingResponse},
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Map;
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use umem_config::FixtureMode;
+
+#[derive(Serialize, Deserialize)]
+struct EmbeddingFixture {
+    model: String,
+    input: Vec<String>,
+    embeddings: Vec<Vec<f32>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RerankFixture {
+    model: String,
+    query: String,
+    documents: Vec<String>,
+    top_k: usize,
+    /// `(original_index, score)` of each ranked document, best first.
+    rankings: Vec<(usize, f32)>,
+}
+
+/// Embeds and reranks through another provider, saving each response to a fixture file
+/// named after the request, or answers from those files without calling it.
+///
+/// Fixture names hash the model and the request, so any change to either misses the
+/// recorded response instead of replaying a stale one.
+#[derive(Debug)]
+pub struct FixtureProvider {
+    inner: Arc<AIProvider>,
+    directory: PathBuf,
+    mode: FixtureMode,
+}
+
+impl FixtureProvider {
+    pub fn new(inner: Arc<AIProvider>, fixtures: umem_config::Fixtures) -> Self {
+        Self {
+            inner,
+            directory: fixtures.directory,
+            mode: fixtures.mode,
+        }
+    }
+
+    fn path_of(&self, kind: &str, request: impl Hash) -> PathBuf {
+        let mut hasher = rustc_hash::FxHasher::default();
+        request.hash(&mut hasher);
+        self.directory
+            .join(format!("{}-{:016x}.json", kind, hasher.finish()))
+    }
+
+    fn replay<R: DeserializeOwned>(&self, path: &Path) -> Result<R, ResponseGeneratorError> {
+        let fixture = std::fs::read(path).map_err(|e| {
+            ResponseGeneratorError::FixtureError(format!(
+                "no recorded response at {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        serde_json::from_slice(&fixture)
+            .map_err(|e| ResponseGeneratorError::FixtureError(e.to_string()))
+    }
+
+    fn record<R: Serialize>(&self, path: &Path, fixture: &R) -> Result<(), ResponseGeneratorError> {
+        let fixture = serde_json::to_vec_pretty(fixture)
+            .map_err(|e| ResponseGeneratorError::FixtureError(e.to_string()))?;
+        std::fs::create_dir_all(&self.directory)
+            .and_then(|_| std::fs::write(path, fixture))
+            .map_err(|e| {
+                ResponseGeneratorError::FixtureError(format!(
+                    "recording {} failed with: {}",
+                    path.display(),
+                    e
+                ))
+            })
+    }
+}
+
+#[async_trait]
+impl Embeds for FixtureProvider {
+    async fn embed(
+        &self,
+        request: EmbeddingRequest,
+    ) -> Result<EmbeddingResponse, ResponseGeneratorError> {
+        let model = request.model.model_name.clone();
+        let input = request.input.clone();
+        let path = self.path_of("embed", (&model, &input, request.dimensions));
+
+        if self.mode == FixtureMode::Replay {
+            let fixture: EmbeddingFixture = self.replay(&path)?;
+            return Ok(EmbeddingResponse {
+                embeddings: fixture.embeddings,
+            });
+        }
+
+        let response = self.inner.do_embed(request).await?;
+        self.record(
+            &path,
+            &EmbeddingFixture {
+                model,
+                input,
+                embeddings: response.embeddings.clone(),
+            },
+        )?;
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Reranks for FixtureProvider {
+    async fn rerank(
+        &self,
+        request: RerankRequest,
+    ) -> Result<RerankResponse, ResponseGeneratorError> {
+        let model = request.model.model_name.clone();
+        let path = self.path_of(
+            "rerank",
+            (&model, &request.query, &request.documents, request.top_k),
+        );
+
+        if self.mode == FixtureMode::Replay {
+            let fixture: RerankFixture = self.replay(&path)?;
+            let rankings = fixture
+                .rankings
+                .into_iter()
+                .map(|(original_index, score)| {
+                    let document = fixture.documents.get(original_index).ok_or_else(|| {
+                        ResponseGeneratorError::FixtureError(format!(
+                            "{} ranks a document it does not hold",
+                            path.display()
+                        ))
+                    })?;
+                    Ok(Ranking {
+                        original_index,
+                        score,
+                        document: document.clone(),
+                    })
+                })
+                .collect::<Result<Vec<_>, ResponseGeneratorError>>()?;
+            return Ok(RerankResponse {
+                ranked_documents: rankings
+                    .iter()
+                    .map(|ranking| ranking.document.clone())
+                    .collect(),
+                rankings,
+                raw_fields: Map::new(),
+            });
+        }
+
+        let response = self.inner.do_reranking(request.clone()).await?;
+        self.record(
+            &path,
+            &RerankFixture {
+                model,
+                query: request.query,
+                documents: request.documents,
+                top_k: request.top_k,
+                rankings: response
+                    .rankings
+                    .iter()
+                    .map(|ranking| (ranking.original_index, ranking.score))
+                    .collect(),
+            },
+        )?;
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockProvider, embed::embed, models::EmbeddingModel};
+
+    #[tokio::test]
+    async fn test_fixture_provider_replays_recorded_embeddings() {
+        let directory = std::env::temp_dir().join(format!("umem-fixtures-{}", std::process::id()));
+        let model = |mode, inner: MockProvider| {
+            let fixtures = umem_config::Fixtures {
+                directory: directory.clone(),
+                mode,
+            };
+            Arc::new(EmbeddingModel {
+                provider: Arc::new(AIProvider::from(FixtureProvider::new(
+                    Arc::new(AIProvider::from(inner)),
+                    fixtures,
+                ))),
+                model_name: "mock".to_string(),
+            })
+        };
+        let request = |model| {
+            EmbeddingRequest::builder()
+                .model(model)
+                .input(vec!["a memory".to_string()])
+                .max_retries(0)
+                .build()
+        };
+
+        let recorded = embed(request(model(FixtureMode::Record, MockProvider::new())))
+            .await
+            .unwrap();
+        // NOTE: other dimensions would embed differently if the inner provider were called
+        let replayed = embed(request(model(
+            FixtureMode::Replay,
+            MockProvider::new().with_dimensions(2),
+        )))
+        .await
+        .unwrap();
+        assert_eq!(replayed.embeddings, recorded.embeddings);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert!(
+            embed(request(model(FixtureMode::Replay, MockProvider::new())))
+                .await
+                .is_err()
+        );
+    }
+}
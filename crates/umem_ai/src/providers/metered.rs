@@ -0,0 +1,161 @@
+use crate::{
+    AIProvider, Embeds, GenerateObjectRequest, GenerateObjectResponse, GenerateTextRequest,
+    GenerateTextResponse, GeneratesObject, GeneratesText, Message, RerankRequest, RerankResponse,
+    Reranks, ReranksStructuredData, ResponseGeneratorError, SpendTracker, StructuredRerankRequest,
+    StructuredRerankResponse, UserMessagePart, UserModelMessage,
+    embed::{EmbeddingRequest, EmbeddingResponse},
+    spend::estimated_tokens,
+};
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Serialize, de::DeserializeOwned};
+use std::sync::Arc;
+
+/// Prices in US dollars per million tokens.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Prices {
+    pub input: f64,
+    pub output: f64,
+}
+
+/// Calls another provider and records the estimated cost of every successful call with
+/// its `SpendTracker`. Batches, billed apart from live calls, are not metered.
+#[derive(Debug)]
+pub struct MeteredProvider {
+    inner: Arc<AIProvider>,
+    prices: Prices,
+    spend: Arc<SpendTracker>,
+}
+
+impl MeteredProvider {
+    pub fn new(inner: Arc<AIProvider>, prices: Prices, spend: Arc<SpendTracker>) -> Self {
+        Self {
+            inner,
+            prices,
+            spend,
+        }
+    }
+
+    pub(crate) fn inner(&self) -> &AIProvider {
+        &self.inner
+    }
+
+    fn record(&self, input_tokens: usize, output_tokens: usize) {
+        self.spend.record(
+            (input_tokens as f64 * self.prices.input + output_tokens as f64 * self.prices.output)
+                / 1_000_000.0,
+        );
+    }
+}
+
+fn prompt_tokens(messages: &[Message]) -> usize {
+    messages
+        .iter()
+        .map(|message| match message {
+            Message::System(text) | Message::User(UserModelMessage::Text(text)) => {
+                estimated_tokens(text)
+            }
+            Message::User(UserModelMessage::Parts(parts)) => parts
+                .iter()
+                .map(|part| match part {
+                    UserMessagePart::Text(text) => estimated_tokens(text),
+                    // NOTE: attachments are billed by the provider's own rules, not metered
+                    UserMessagePart::Image(_) | UserMessagePart::File(_) => 0,
+                })
+                .sum(),
+        })
+        .sum()
+}
+
+/// Rerankers score the query against every document.
+fn rerank_tokens(query: &str, documents: impl Iterator<Item = usize>) -> usize {
+    let query_tokens = estimated_tokens(query);
+    documents
+        .map(|document_tokens| query_tokens + document_tokens)
+        .sum()
+}
+
+#[async_trait]
+impl GeneratesText for MeteredProvider {
+    async fn generate_text(
+        &self,
+        request: GenerateTextRequest,
+    ) -> Result<GenerateTextResponse, ResponseGeneratorError> {
+        let input_tokens = prompt_tokens(&request.messages);
+        let response = self.inner.do_generate_text(request).await?;
+        self.record(input_tokens, estimated_tokens(&response.text));
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl GeneratesObject for MeteredProvider {
+    async fn generate_object<T: Clone + JsonSchema + Serialize + Send + Sync + DeserializeOwned>(
+        &self,
+        request: GenerateObjectRequest<T>,
+    ) -> Result<GenerateObjectResponse<T>, ResponseGeneratorError> {
+        let input_tokens = prompt_tokens(&request.messages)
+            + estimated_tokens(&serde_json::to_string(&request.output_schema).unwrap_or_default());
+        let response = self.inner.do_generate_object(request).await?;
+        let output = serde_json::to_string(&response.output).unwrap_or_default();
+        self.record(input_tokens, estimated_tokens(&output));
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Embeds for MeteredProvider {
+    async fn embed(
+        &self,
+        request: EmbeddingRequest,
+    ) -> Result<EmbeddingResponse, ResponseGeneratorError> {
+        let input_tokens = request
+            .input
+            .iter()
+            .map(|input| estimated_tokens(input))
+            .sum();
+        let response = self.inner.do_embed(request).await?;
+        self.record(input_tokens, 0);
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Reranks for MeteredProvider {
+    async fn rerank(
+        &self,
+        request: RerankRequest,
+    ) -> Result<RerankResponse, ResponseGeneratorError> {
+        let input_tokens = rerank_tokens(
+            &request.query,
+            request
+                .documents
+                .iter()
+                .map(|document| estimated_tokens(document)),
+        );
+        let response = self.inner.do_reranking(request).await?;
+        self.record(input_tokens, 0);
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl ReranksStructuredData for MeteredProvider {
+    async fn rerank_structured<T>(
+        &self,
+        request: StructuredRerankRequest<T>,
+    ) -> Result<StructuredRerankResponse<T>, ResponseGeneratorError>
+    where
+        T: Serialize + Clone + Send + Sync,
+    {
+        let input_tokens = rerank_tokens(
+            &request.query,
+            request.documents.iter().map(|document| {
+                estimated_tokens(&serde_json::to_string(document).unwrap_or_default())
+            }),
+        );
+        let response = self.inner.do_structured_reranking(request).await?;
+        self.record(input_tokens, 0);
+        Ok(response)
+    }
+}
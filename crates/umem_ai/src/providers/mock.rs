@@ -0,0 +1,244 @@
+use crate::{
+    Embeds, GenerateObjectRequest, GenerateObjectResponse, GenerateTextRequest,
+    GenerateTextResponse, GeneratesObject, GeneratesText, Message, Ranking, RerankRequest,
+    RerankResponse, Reranks, ResponseGeneratorError, UserMessagePart, UserModelMessage,
+    embed::{EmbeddingRequest, EmbeddingResponse},
+};
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Map;
+use std::{
+    collections::VecDeque,
+    hash::{Hash, Hasher},
+    sync::Mutex,
+};
+
+const DEFAULT_MOCK_DIMENSIONS: usize = 8;
+
+/// Answers from canned responses instead of a remote api, so flows built on language,
+/// embedding and reranking models can be tested without network access.
+///
+/// Text and object generation return the scripted responses in order, then the fixed
+/// response once the script runs out. Objects are parsed from the response as JSON.
+/// Embeddings are derived from the hash of each input, so equal inputs embed equally,
+/// and reranking scores documents by the share of query words they contain.
+#[derive(Debug, Default)]
+pub struct MockProvider {
+    script: Mutex<VecDeque<String>>,
+    fixed: Option<String>,
+    dimensions: usize,
+    /// The text of every generation request received, in order.
+    prompts: Mutex<Vec<String>>,
+}
+
+impl MockProvider {
+    /// Answers every generation request with `response`.
+    pub fn fixed(response: impl Into<String>) -> Self {
+        Self {
+            fixed: Some(response.into()),
+            ..Self::new()
+        }
+    }
+
+    /// Answers generation requests with `responses` in order, failing once they run out.
+    pub fn scripted<I>(responses: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            script: Mutex::new(responses.into_iter().map(Into::into).collect()),
+            ..Self::new()
+        }
+    }
+
+    pub fn new() -> Self {
+        Self {
+            dimensions: DEFAULT_MOCK_DIMENSIONS,
+            ..Default::default()
+        }
+    }
+
+    /// Queues `response` after the scripted responses not yet returned.
+    pub fn push_response(&self, response: impl Into<String>) {
+        self.script.lock().unwrap().push_back(response.into());
+    }
+
+    /// Sets the number of dimensions of the embeddings returned.
+    pub fn with_dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = dimensions;
+        self
+    }
+
+    /// The text of every generation request received so far, messages joined by newlines.
+    pub fn prompts(&self) -> Vec<String> {
+        self.prompts.lock().unwrap().clone()
+    }
+
+    fn respond(&self, messages: &[Message]) -> Result<String, ResponseGeneratorError> {
+        self.prompts.lock().unwrap().push(prompt_of(messages));
+        self.script
+            .lock()
+            .unwrap()
+            .pop_front()
+            .or_else(|| self.fixed.clone())
+            .ok_or(ResponseGeneratorError::EmptyProviderResponse)
+    }
+
+    fn embedding_of(&self, input: &str) -> Vec<f32> {
+        let values: Vec<f32> = (0..self.dimensions)
+            .map(|dimension| {
+                let mut hasher = rustc_hash::FxHasher::default();
+                (input, dimension).hash(&mut hasher);
+                (hasher.finish() % 2001) as f32 / 1000.0 - 1.0
+            })
+            .collect();
+
+        let norm = values.iter().map(|value| value * value).sum::<f32>().sqrt();
+        if norm == 0.0 {
+            return values;
+        }
+        values.into_iter().map(|value| value / norm).collect()
+    }
+}
+
+fn prompt_of(messages: &[Message]) -> String {
+    messages
+        .iter()
+        .flat_map(|message| match message {
+            Message::System(text) | Message::User(UserModelMessage::Text(text)) => {
+                vec![text.as_str()]
+            }
+            Message::User(UserModelMessage::Parts(parts)) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    UserMessagePart::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn overlap(query: &str, document: &str) -> f32 {
+    let document = document.to_lowercase();
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if words.is_empty() {
+        return 0.0;
+    }
+    let found = words
+        .iter()
+        .filter(|word| document.contains(word.as_str()))
+        .count();
+    found as f32 / words.len() as f32
+}
+
+#[async_trait]
+impl GeneratesText for MockProvider {
+    async fn generate_text(
+        &self,
+        request: GenerateTextRequest,
+    ) -> Result<GenerateTextResponse, ResponseGeneratorError> {
+        Ok(GenerateTextResponse {
+            text: self.respond(&request.messages)?,
+        })
+    }
+}
+
+#[async_trait]
+impl GeneratesObject for MockProvider {
+    async fn generate_object<T: Clone + JsonSchema + Serialize + Send + Sync + DeserializeOwned>(
+        &self,
+        request: GenerateObjectRequest<T>,
+    ) -> Result<GenerateObjectResponse<T>, ResponseGeneratorError> {
+        let response = self.respond(&request.messages)?;
+        let output = serde_json::from_str(&response)
+            .map_err(|e| ResponseGeneratorError::Deserialization(e, response))?;
+        Ok(GenerateObjectResponse { output })
+    }
+}
+
+#[async_trait]
+impl Embeds for MockProvider {
+    async fn embed(
+        &self,
+        request: EmbeddingRequest,
+    ) -> Result<EmbeddingResponse, ResponseGeneratorError> {
+        Ok(EmbeddingResponse {
+            embeddings: request
+                .input
+                .iter()
+                .map(|input| self.embedding_of(input))
+                .collect(),
+        })
+    }
+}
+
+#[async_trait]
+impl Reranks for MockProvider {
+    async fn rerank(
+        &self,
+        request: RerankRequest,
+    ) -> Result<RerankResponse, ResponseGeneratorError> {
+        let mut rankings: Vec<Ranking> = request
+            .documents
+            .iter()
+            .enumerate()
+            .map(|(original_index, document)| Ranking {
+                original_index,
+                score: overlap(&request.query, document),
+                document: document.clone(),
+            })
+            .collect();
+        // NOTE: a stable sort keeps documents with equal scores in their original order
+        rankings.sort_by(|a, b| b.score.total_cmp(&a.score));
+        rankings.truncate(request.top_k);
+
+        Ok(RerankResponse {
+            ranked_documents: rankings
+                .iter()
+                .map(|ranking| ranking.document.clone())
+                .collect(),
+            rankings,
+            raw_fields: Map::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AIProvider, GenerateTextRequestBuilder, generate_text, models::LanguageModel};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_mock_provider_follows_its_script() {
+        let provider = Arc::new(AIProvider::from(MockProvider::scripted(["first"])));
+        let model = Arc::new(LanguageModel {
+            provider: Arc::clone(&provider),
+            model_name: "mock".to_string(),
+        });
+        let request = || {
+            GenerateTextRequestBuilder::new()
+                .model(Arc::clone(&model))
+                .system("You are a helpful assistant.".to_string())
+                .prompt("Say something.".to_string())
+                .max_retries(0)
+                .build()
+                .unwrap()
+        };
+
+        assert_eq!(generate_text(request()).await.unwrap().text, "first");
+        assert!(generate_text(request()).await.is_err());
+
+        let AIProvider::Mock(mock) = provider.as_ref() else {
+            unreachable!()
+        };
+        assert_eq!(
+            mock.prompts(),
+            vec!["You are a helpful assistant.\nSay something."; 2]
+        );
+    }
+}
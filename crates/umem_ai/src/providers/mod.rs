@@ -1,22 +1,30 @@
 mod amazon_bedrock;
 mod anthropic;
 mod azure_openai;
+mod circuit_breaker;
 mod cohere;
+mod fixture;
 mod google_vertex;
+mod metered;
+mod mock;
 mod openai;
 mod xai;
 use crate::{
     Embeds, GenerateObjectRequest, GenerateObjectResponse, GenerateTextRequest,
-    GenerateTextResponse, GeneratesObject, GeneratesText, RerankRequest, RerankResponse, Reranks,
-    ReranksStructuredData, ResponseGeneratorError, StructuredRerankRequest,
-    StructuredRerankResponse,
+    GenerateTextResponse, GeneratesObject, GeneratesText, ModerationResponse, ObjectBatch,
+    RerankRequest, RerankResponse, Reranks, ReranksStructuredData, ResponseGeneratorError,
+    StructuredRerankRequest, StructuredRerankResponse,
     embed::{EmbeddingRequest, EmbeddingResponse},
 };
 pub use amazon_bedrock::*;
 pub use anthropic::AnthropicProvider;
 pub use azure_openai::AzureOpenAIProvider;
+pub use circuit_breaker::CircuitBreakerProvider;
 pub use cohere::CohereProvider;
+pub use fixture::FixtureProvider;
 pub use google_vertex::GoogleVertexAIProvider;
+pub use metered::{MeteredProvider, Prices};
+pub use mock::MockProvider;
 pub use openai::OpenAIProvider;
 use schemars::JsonSchema;
 use serde::{Serialize, de::DeserializeOwned};
@@ -44,6 +52,10 @@ pub enum AIProvider {
     XAI(XAIProvider),
     AmazonBedrock(AmazonBedrockProvider),
     Cohere(CohereProvider),
+    Mock(MockProvider),
+    Fixture(FixtureProvider),
+    CircuitBreaker(CircuitBreakerProvider),
+    Metered(MeteredProvider),
 }
 
 impl AIProvider {
@@ -54,6 +66,9 @@ impl AIProvider {
         match self {
             AIProvider::OpenAI(provider) => provider.generate_text(request),
             AIProvider::AmazonBedrock(provider) => provider.generate_text(request),
+            AIProvider::Mock(provider) => provider.generate_text(request),
+            AIProvider::CircuitBreaker(provider) => provider.generate_text(request),
+            AIProvider::Metered(provider) => provider.generate_text(request),
             _ => unimplemented!(),
         }
         .await
@@ -68,6 +83,10 @@ impl AIProvider {
         match self {
             AIProvider::OpenAI(provider) => provider.generate_object(request),
             AIProvider::AmazonBedrock(provider) => provider.generate_object(request),
+            AIProvider::Anthropic(provider) => provider.generate_object(request),
+            AIProvider::Mock(provider) => provider.generate_object(request),
+            AIProvider::CircuitBreaker(provider) => provider.generate_object(request),
+            AIProvider::Metered(provider) => provider.generate_object(request),
             _ => unimplemented!(),
         }
         .await
@@ -80,6 +99,10 @@ impl AIProvider {
         match self {
             AIProvider::Cohere(provider) => provider.rerank(request),
             AIProvider::AmazonBedrock(provider) => provider.rerank(request),
+            AIProvider::Mock(provider) => provider.rerank(request),
+            AIProvider::Fixture(provider) => provider.rerank(request),
+            AIProvider::CircuitBreaker(provider) => provider.rerank(request),
+            AIProvider::Metered(provider) => provider.rerank(request),
             _ => unimplemented!(),
         }
         .await
@@ -95,16 +118,99 @@ impl AIProvider {
         match self {
             AIProvider::Cohere(provider) => provider.rerank_structured(request).await,
             AIProvider::AmazonBedrock(provider) => provider.rerank_structured(request).await,
+            AIProvider::CircuitBreaker(provider) => provider.rerank_structured(request).await,
+            AIProvider::Metered(provider) => provider.rerank_structured(request).await,
             _ => unimplemented!(),
         }
     }
 
+    pub(crate) async fn do_submit_object_batch<
+        T: Clone + JsonSchema + Serialize + Send + Sync + DeserializeOwned,
+    >(
+        &self,
+        requests: &[(String, GenerateObjectRequest<T>)],
+    ) -> Result<ObjectBatch, ResponseGeneratorError> {
+        match self {
+            AIProvider::OpenAI(provider) => provider.submit_object_batch(requests).await,
+            // NOTE: batches are polled for hours, so they bypass the breaker
+            AIProvider::CircuitBreaker(provider) => {
+                Box::pin(provider.inner().do_submit_object_batch(requests)).await
+            }
+            AIProvider::Metered(provider) => {
+                Box::pin(provider.inner().do_submit_object_batch(requests)).await
+            }
+            _ => Err(Self::batches_unsupported()),
+        }
+    }
+
+    pub(crate) async fn do_poll_object_batch(
+        &self,
+        batch_id: &str,
+    ) -> Result<ObjectBatch, ResponseGeneratorError> {
+        match self {
+            AIProvider::OpenAI(provider) => provider.poll_object_batch(batch_id).await,
+            AIProvider::CircuitBreaker(provider) => {
+                Box::pin(provider.inner().do_poll_object_batch(batch_id)).await
+            }
+            AIProvider::Metered(provider) => {
+                Box::pin(provider.inner().do_poll_object_batch(batch_id)).await
+            }
+            _ => Err(Self::batches_unsupported()),
+        }
+    }
+
+    pub(crate) async fn do_object_batch_results<T: DeserializeOwned>(
+        &self,
+        batch: &ObjectBatch,
+    ) -> Result<Vec<(String, Result<T, ResponseGeneratorError>)>, ResponseGeneratorError> {
+        match self {
+            AIProvider::OpenAI(provider) => provider.object_batch_results(batch).await,
+            AIProvider::CircuitBreaker(provider) => {
+                Box::pin(provider.inner().do_object_batch_results(batch)).await
+            }
+            AIProvider::Metered(provider) => {
+                Box::pin(provider.inner().do_object_batch_results(batch)).await
+            }
+            _ => Err(Self::batches_unsupported()),
+        }
+    }
+
+    fn batches_unsupported() -> ResponseGeneratorError {
+        ResponseGeneratorError::InvalidArgumentsProvided(
+            "batch generation is only supported by the openai provider".to_string(),
+        )
+    }
+
+    pub(crate) async fn do_moderate(
+        &self,
+        model: &str,
+        inputs: Vec<String>,
+    ) -> Result<Vec<ModerationResponse>, ResponseGeneratorError> {
+        match self {
+            AIProvider::OpenAI(provider) => provider.moderate(model, inputs).await,
+            AIProvider::CircuitBreaker(provider) => {
+                Box::pin(provider.moderate(model, inputs)).await
+            }
+            // NOTE: moderation is free, so it is not metered
+            AIProvider::Metered(provider) => {
+                Box::pin(provider.inner().do_moderate(model, inputs)).await
+            }
+            _ => Err(ResponseGeneratorError::InvalidArgumentsProvided(
+                "moderation is only supported by the openai provider".to_string(),
+            )),
+        }
+    }
+
     pub(crate) async fn do_embed(
         &self,
         request: EmbeddingRequest,
     ) -> Result<EmbeddingResponse, ResponseGeneratorError> {
         match self {
             AIProvider::AmazonBedrock(provider) => provider.embed(request),
+            AIProvider::Mock(provider) => provider.embed(request),
+            AIProvider::Fixture(provider) => provider.embed(request),
+            AIProvider::CircuitBreaker(provider) => provider.embed(request),
+            AIProvider::Metered(provider) => provider.embed(request),
             _ => unimplemented!(),
         }
         .await
@@ -146,3 +252,27 @@ impl From<GoogleVertexAIProvider> for AIProvider {
         AIProvider::GoogleVertexAI(config)
     }
 }
+
+impl From<MockProvider> for AIProvider {
+    fn from(config: MockProvider) -> Self {
+        AIProvider::Mock(config)
+    }
+}
+
+impl From<FixtureProvider> for AIProvider {
+    fn from(config: FixtureProvider) -> Self {
+        AIProvider::Fixture(config)
+    }
+}
+
+impl From<CircuitBreakerProvider> for AIProvider {
+    fn from(config: CircuitBreakerProvider) -> Self {
+        AIProvider::CircuitBreaker(config)
+    }
+}
+
+impl From<MeteredProvider> for AIProvider {
+    fn from(config: MeteredProvider) -> Self {
+        AIProvider::Metered(config)
+    }
+}
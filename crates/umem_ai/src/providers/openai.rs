@@ -1,5 +1,6 @@
 use crate::{
-    GeneratesObject, GeneratesText, reqwest_client,
+    GeneratesObject, GeneratesText, ModerationResponse, ObjectBatch, ObjectBatchStatus,
+    reqwest_client,
     response_generators::{
         GenerateTextRequest, GenerateTextResponse, ResponseGeneratorError,
         generate_object::{GenerateObjectRequest, GenerateObjectResponse},
@@ -9,10 +10,14 @@ use crate::{
 };
 use async_trait::async_trait;
 use base64::Engine;
-use reqwest::header::HeaderMap;
+use reqwest::{
+    header::HeaderMap,
+    multipart::{Form, Part},
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::{Map, Value};
+use std::collections::BTreeMap;
 use typed_builder::TypedBuilder;
 
 #[derive(TypedBuilder, Debug)]
@@ -207,23 +212,7 @@ impl GeneratesText for OpenAIProvider {
             .json::<OpenAIResponsesApiResponse>()
             .await?;
 
-        let output_text = response
-            .output
-            .iter()
-            .find_map(|item| match item {
-                OutputItem::Message { content, .. } => {
-                    let texts: Vec<String> = content
-                        .iter()
-                        .filter_map(|mc| match mc {
-                            MessageContent::OutputText { text, .. } => Some(text.clone()),
-                            _ => None,
-                        })
-                        .collect();
-                    Some(texts.join("\n"))
-                }
-                _ => None,
-            })
-            .unwrap_or_default();
+        let output_text = response.output_text();
 
         return Ok(GenerateTextResponse { text: output_text });
     }
@@ -258,8 +247,22 @@ impl GeneratesObject for OpenAIProvider {
             .json::<OpenAIResponsesApiResponse>()
             .await?;
 
-        let output_text = response
-            .output
+        Ok(GenerateObjectResponse {
+            output: response.output_object()?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OpenAIResponsesApiResponse {
+    pub output: Vec<OutputItem>,
+    #[serde(flatten)]
+    pub response_metadata: Map<String, Value>,
+}
+
+impl OpenAIResponsesApiResponse {
+    fn output_text(&self) -> String {
+        self.output
             .iter()
             .find_map(|item| match item {
                 OutputItem::Message { content, .. } => {
@@ -274,20 +277,14 @@ impl GeneratesObject for OpenAIProvider {
                 }
                 _ => None,
             })
-            .unwrap_or_default();
-
-        let output: T = serde_json::from_str(&output_text)
-            .map_err(|e| ResponseGeneratorError::Deserialization(e, output_text))?;
-
-        Ok(GenerateObjectResponse { output })
+            .unwrap_or_default()
     }
-}
 
-#[derive(Debug, Serialize, Deserialize)]
-struct OpenAIResponsesApiResponse {
-    pub output: Vec<OutputItem>,
-    #[serde(flatten)]
-    pub response_metadata: Map<String, Value>,
+    fn output_object<T: DeserializeOwned>(&self) -> Result<T, ResponseGeneratorError> {
+        let output_text = self.output_text();
+        serde_json::from_str(&output_text)
+            .map_err(|e| ResponseGeneratorError::Deserialization(e, output_text))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -354,6 +351,283 @@ enum MessageContent {
     },
 }
 
+/// Endpoint every line of a submitted batch is sent to.
+const BATCH_ENDPOINT: &str = "/v1/responses";
+/// The only completion window the Batch API offers.
+const BATCH_COMPLETION_WINDOW: &str = "24h";
+
+#[derive(Debug, Deserialize)]
+struct OpenAIFile {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIBatch {
+    id: String,
+    status: String,
+    output_file_id: Option<String>,
+    error_file_id: Option<String>,
+    errors: Option<OpenAIBatchErrors>,
+    request_counts: Option<OpenAIBatchRequestCounts>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIBatchErrors {
+    #[serde(default)]
+    data: Vec<OpenAIBatchError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIBatchError {
+    message: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenAIBatchRequestCounts {
+    total: u64,
+    completed: u64,
+    failed: u64,
+}
+
+/// One line of a batch's output or error file.
+#[derive(Debug, Deserialize)]
+struct OpenAIBatchResult {
+    custom_id: String,
+    response: Option<OpenAIBatchResponse>,
+    error: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIBatchResponse {
+    status_code: u16,
+    body: Value,
+}
+
+impl From<OpenAIBatch> for ObjectBatch {
+    fn from(batch: OpenAIBatch) -> Self {
+        let status = match batch.status.as_str() {
+            "completed" => ObjectBatchStatus::Completed {
+                output_file_id: batch.output_file_id,
+                error_file_id: batch.error_file_id,
+            },
+            "failed" | "expired" | "cancelled" => {
+                let reasons: Vec<String> = batch
+                    .errors
+                    .map(|errors| errors.data.into_iter().map(|e| e.message).collect())
+                    .unwrap_or_default();
+                ObjectBatchStatus::Failed(if reasons.is_empty() {
+                    batch.status
+                } else {
+                    format!("{}: {}", batch.status, reasons.join("; "))
+                })
+            }
+            _ => ObjectBatchStatus::InProgress,
+        };
+        let counts = batch.request_counts.unwrap_or_default();
+
+        ObjectBatch {
+            id: batch.id,
+            status,
+            total: counts.total,
+            completed: counts.completed,
+            failed: counts.failed,
+        }
+    }
+}
+
+impl OpenAIProvider {
+    fn authorized(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        request
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header(
+                "OpenAI-Organization",
+                self.organization.clone().unwrap_or_default(),
+            )
+            .header("OpenAI-Project", self.project.clone().unwrap_or_default())
+            .headers(self.default_headers.clone())
+    }
+
+    /// Uploads the requests as a JSONL file and starts a batch over it.
+    pub(crate) async fn submit_object_batch<
+        T: Clone + JsonSchema + Serialize + Send + Sync + DeserializeOwned,
+    >(
+        &self,
+        requests: &[(String, GenerateObjectRequest<T>)],
+    ) -> Result<ObjectBatch, ResponseGeneratorError> {
+        let mut lines = String::new();
+        for (custom_id, request) in requests {
+            let body = self.normalize_generate_object_request(request);
+            let body: Value = serde_json::from_str(&body)
+                .map_err(|e| ResponseGeneratorError::Deserialization(e, body))?;
+            lines.push_str(
+                &serde_json::json!({
+                    "custom_id": custom_id,
+                    "method": "POST",
+                    "url": BATCH_ENDPOINT,
+                    "body": body,
+                })
+                .to_string(),
+            );
+            lines.push('\n');
+        }
+
+        let form = Form::new().text("purpose", "batch").part(
+            "file",
+            Part::bytes(lines.into_bytes())
+                .file_name("batch.jsonl")
+                .mime_str("application/jsonl")?,
+        );
+        let file = self
+            .authorized(reqwest_client.post(format!("{}/files", self.base_url)))
+            .multipart(form)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<OpenAIFile>()
+            .await?;
+
+        let batch = self
+            .authorized(reqwest_client.post(format!("{}/batches", self.base_url)))
+            .json(&serde_json::json!({
+                "input_file_id": file.id,
+                "endpoint": BATCH_ENDPOINT,
+                "completion_window": BATCH_COMPLETION_WINDOW,
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<OpenAIBatch>()
+            .await?;
+
+        Ok(batch.into())
+    }
+
+    pub(crate) async fn poll_object_batch(
+        &self,
+        batch_id: &str,
+    ) -> Result<ObjectBatch, ResponseGeneratorError> {
+        let batch = self
+            .authorized(reqwest_client.get(format!("{}/batches/{batch_id}", self.base_url)))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<OpenAIBatch>()
+            .await?;
+
+        Ok(batch.into())
+    }
+
+    /// Reads the output and error files of a completed batch.
+    pub(crate) async fn object_batch_results<T: DeserializeOwned>(
+        &self,
+        batch: &ObjectBatch,
+    ) -> Result<Vec<(String, Result<T, ResponseGeneratorError>)>, ResponseGeneratorError> {
+        let ObjectBatchStatus::Completed {
+            output_file_id,
+            error_file_id,
+        } = &batch.status
+        else {
+            return Err(ResponseGeneratorError::InvalidArgumentsProvided(format!(
+                "batch {} has not completed",
+                batch.id
+            )));
+        };
+
+        let mut results = Vec::new();
+        for file_id in output_file_id.iter().chain(error_file_id) {
+            let content = self
+                .authorized(
+                    reqwest_client.get(format!("{}/files/{file_id}/content", self.base_url)),
+                )
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+
+            for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                let result: OpenAIBatchResult = serde_json::from_str(line)
+                    .map_err(|e| ResponseGeneratorError::Deserialization(e, line.to_string()))?;
+                let output = match (result.response, result.error) {
+                    (Some(response), _) if response.status_code == 200 => {
+                        serde_json::from_value::<OpenAIResponsesApiResponse>(response.body)
+                            .map_err(|e| {
+                                ResponseGeneratorError::Deserialization(e, line.to_string())
+                            })
+                            .and_then(|response| response.output_object())
+                    }
+                    (Some(response), _) => {
+                        Err(ResponseGeneratorError::InvalidProviderResponse(format!(
+                            "batch request failed with status {}: {}",
+                            response.status_code, response.body
+                        )))
+                    }
+                    (None, error) => Err(ResponseGeneratorError::InvalidProviderResponse(format!(
+                        "batch request failed: {}",
+                        error.unwrap_or_default()
+                    ))),
+                };
+                results.push((result.custom_id, output));
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIModerationResponse {
+    results: Vec<OpenAIModerationResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIModerationResult {
+    flagged: bool,
+    #[serde(default)]
+    categories: BTreeMap<String, bool>,
+}
+
+impl OpenAIProvider {
+    pub(crate) async fn moderate(
+        &self,
+        model: &str,
+        inputs: Vec<String>,
+    ) -> Result<Vec<ModerationResponse>, ResponseGeneratorError> {
+        let expected = inputs.len();
+        let response = self
+            .authorized(reqwest_client.post(format!("{}/moderations", self.base_url)))
+            .json(&serde_json::json!({
+                "model": model,
+                "input": inputs,
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<OpenAIModerationResponse>()
+            .await?;
+        if response.results.len() != expected {
+            return Err(ResponseGeneratorError::InvalidProviderResponse(format!(
+                "expected {expected} moderation results, got {}",
+                response.results.len()
+            )));
+        }
+
+        Ok(response
+            .results
+            .into_iter()
+            .map(|result| ModerationResponse {
+                flagged: result.flagged,
+                categories: result
+                    .categories
+                    .into_iter()
+                    .filter(|(_, flagged)| *flagged)
+                    .map(|(category, _)| category)
+                    .collect(),
+            })
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
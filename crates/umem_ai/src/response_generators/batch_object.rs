@@ -0,0 +1,76 @@
+use crate::models::LanguageModel;
+use crate::{GenerateObjectRequest, ResponseGeneratorError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::sync::Arc;
+
+/// `generate_object` requests handed to a provider's batch API as one job. Batches cost
+/// about half as much as the same requests made one by one, but are answered within
+/// hours rather than seconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectBatch {
+    pub id: String,
+    pub status: ObjectBatchStatus,
+    pub total: u64,
+    pub completed: u64,
+    pub failed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectBatchStatus {
+    InProgress,
+    /// Results can be fetched; requests that failed inside the batch are reported with
+    /// their error.
+    Completed {
+        output_file_id: Option<String>,
+        error_file_id: Option<String>,
+    },
+    /// Failed, expired or cancelled as a whole, with the provider's reason.
+    Failed(String),
+}
+
+/// Submits `requests` as one batch. Every request must use the same model; the ids are
+/// handed back with the results and must be unique within the batch.
+pub async fn submit_object_batch<T>(
+    requests: Vec<(String, GenerateObjectRequest<T>)>,
+) -> Result<ObjectBatch, ResponseGeneratorError>
+where
+    T: Clone + JsonSchema + Send + Sync + Serialize + DeserializeOwned,
+{
+    let Some((_, first)) = requests.first() else {
+        return Err(ResponseGeneratorError::InvalidArgumentsProvided(
+            "a batch needs at least one request".to_string(),
+        ));
+    };
+    let model = Arc::clone(&first.model);
+    if requests
+        .iter()
+        .any(|(_, request)| request.model.model_name != model.model_name)
+    {
+        return Err(ResponseGeneratorError::InvalidArgumentsProvided(
+            "every request of a batch must use the same model".to_string(),
+        ));
+    }
+
+    model.provider.do_submit_object_batch(&requests).await
+}
+
+/// The current state of a batch submitted with `model`'s provider.
+pub async fn poll_object_batch(
+    model: &LanguageModel,
+    batch_id: &str,
+) -> Result<ObjectBatch, ResponseGeneratorError> {
+    model.provider.do_poll_object_batch(batch_id).await
+}
+
+/// The answer to every request of a completed batch, keyed by the id it was submitted
+/// with.
+pub async fn object_batch_results<T>(
+    model: &LanguageModel,
+    batch: &ObjectBatch,
+) -> Result<Vec<(String, Result<T, ResponseGeneratorError>)>, ResponseGeneratorError>
+where
+    T: DeserializeOwned,
+{
+    model.provider.do_object_batch_results(batch).await
+}
@@ -1,14 +1,18 @@
+pub mod batch_object;
 pub mod embed;
 pub mod generate_object;
 pub mod generate_text;
 pub mod messages;
+pub mod moderate;
 pub mod rerank;
 pub mod structured_rerank;
 
+pub use batch_object::*;
 pub use embed::*;
 pub use generate_object::*;
 pub use generate_text::*;
 pub use messages::*;
+pub use moderate::*;
 pub use rerank::*;
 pub use structured_rerank::*;
 use thiserror::Error;
@@ -39,4 +43,8 @@ pub enum ResponseGeneratorError {
     Transient(#[from] anyhow::Error),
     #[error("yaml serialization error: {0}")]
     StructuredRerankDocumentsSerializationError(String),
+    #[error("fixture error: {0}")]
+    FixtureError(String),
+    #[error("circuit breaker for {0} is open after repeated failures")]
+    CircuitOpen(String),
 }
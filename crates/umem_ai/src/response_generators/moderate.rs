@@ -0,0 +1,24 @@
+use crate::ResponseGeneratorError;
+use crate::models::LanguageModel;
+
+/// A moderation model's verdict on one input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModerationResponse {
+    pub flagged: bool,
+    /// Categories the input was flagged for, as the provider names them, e.g.
+    /// "self-harm/intent".
+    pub categories: Vec<String>,
+}
+
+/// Classifies every input with `model`, which has to be one of its provider's
+/// moderation models, e.g. OpenAI's "omni-moderation-latest". Verdicts come back in
+/// the order of `inputs`.
+pub async fn moderate(
+    model: &LanguageModel,
+    inputs: Vec<String>,
+) -> Result<Vec<ModerationResponse>, ResponseGeneratorError> {
+    if inputs.is_empty() {
+        return Ok(vec![]);
+    }
+    model.provider.do_moderate(&model.model_name, inputs).await
+}
@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+/// Tokens in `text`, estimated at about four bytes each; providers bill somewhat
+/// differently, so spend is an estimate too.
+pub(crate) fn estimated_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+/// Estimated US dollars spent on priced provider calls in the current UTC day and
+/// month.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpendTotals {
+    /// `YYYY-MM-DD` that `day_usd` was spent on.
+    pub day: String,
+    pub day_usd: f64,
+    /// `YYYY-MM` that `month_usd` was spent in.
+    pub month: String,
+    pub month_usd: f64,
+}
+
+impl SpendTotals {
+    /// The totals as of `now`, started over on a new day or month.
+    fn at(mut self, now: DateTime<Utc>) -> Self {
+        let day = now.format("%Y-%m-%d").to_string();
+        if self.day != day {
+            self.day = day;
+            self.day_usd = 0.0;
+        }
+        let month = now.format("%Y-%m").to_string();
+        if self.month != month {
+            self.month = month;
+            self.month_usd = 0.0;
+        }
+        self
+    }
+
+    /// Whether the daily or monthly cap of `budget` is used up.
+    pub fn exceeds(&self, budget: &umem_config::Budget) -> bool {
+        budget.daily_usd.is_some_and(|cap| self.day_usd >= cap)
+            || budget.monthly_usd.is_some_and(|cap| self.month_usd >= cap)
+    }
+}
+
+#[derive(Debug, Default)]
+struct SpendState {
+    totals: SpendTotals,
+    path: Option<PathBuf>,
+}
+
+/// Adds up what the priced models built with it spend.
+#[derive(Debug, Default)]
+pub struct SpendTracker {
+    state: Mutex<SpendState>,
+}
+
+impl SpendTracker {
+    /// Continues from the totals saved at `path`, if any, and saves them there after
+    /// every recorded call.
+    pub fn persist_to(&self, path: &Path) -> io::Result<()> {
+        let totals = match std::fs::read(path) {
+            Ok(saved) => serde_json::from_slice(&saved).map_err(io::Error::other)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => SpendTotals::default(),
+            Err(e) => return Err(e),
+        };
+        let mut state = self.lock();
+        state.totals = totals;
+        state.path = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    pub fn totals(&self) -> SpendTotals {
+        self.lock().totals.clone().at(Utc::now())
+    }
+
+    pub(crate) fn record(&self, usd: f64) {
+        if usd <= 0.0 {
+            return;
+        }
+        let mut state = self.lock();
+        let mut totals = std::mem::take(&mut state.totals).at(Utc::now());
+        totals.day_usd += usd;
+        totals.month_usd += usd;
+        state.totals = totals;
+
+        if let Some(path) = state.path.as_ref() {
+            let saved = serde_json::to_vec(&state.totals)
+                .map_err(io::Error::other)
+                .and_then(|totals| std::fs::write(path, totals));
+            if let Err(e) = saved {
+                tracing::warn!("could not save spend to {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SpendState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_spend_totals_start_over_each_day_and_month() {
+        let budget = umem_config::Budget {
+            daily_usd: Some(1.0),
+            monthly_usd: Some(10.0),
+            path: None,
+        };
+        let totals = SpendTotals {
+            day: "2026-01-31".to_string(),
+            day_usd: 1.5,
+            month: "2026-01".to_string(),
+            month_usd: 4.0,
+        };
+        assert!(totals.exceeds(&budget));
+
+        let next_day = totals
+            .clone()
+            .at(Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!(next_day.day_usd, 0.0);
+        assert_eq!(next_day.month_usd, 0.0);
+        assert!(!next_day.exceeds(&budget));
+
+        let same_day = totals.at(Utc.with_ymd_and_hms(2026, 1, 31, 23, 0, 0).unwrap());
+        assert_eq!(same_day.day_usd, 1.5);
+    }
+}
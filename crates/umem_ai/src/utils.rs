@@ -74,6 +74,14 @@ pub fn is_retryable_error(e: &ResponseGeneratorError) -> bool {
             tracing::error!("Internal Server Error: {}", e);
             true
         }
+        ResponseGeneratorError::FixtureError(e) => {
+            tracing::error!("Fixture error: {}", e);
+            false
+        }
+        ResponseGeneratorError::CircuitOpen(provider) => {
+            tracing::warn!("Circuit breaker for {} is open, not retrying", provider);
+            false
+        }
     }
 }
 
@@ -0,0 +1,51 @@
+use crate::AnnotationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use umem_ai::{GenerateObjectRequestBuilder, LanguageModel};
+
+const AMBIGUITY_PROMPT: &str = r#"
+You are a review system for a memory store. You are given content an agent wants to remember about a user, before it is stored.
+
+## Output
+
+### ambiguous
+- true when the content states no clear fact (e.g. "it went fine", "change that one"), refers to things it does not name, or contradicts itself (e.g. "User is vegetarian and loves steak")
+- false when at least one fact about the user can be read from it without guessing, even if it is short or casual
+
+### question
+- When ambiguous: one short question that, once answered, would make the content clear (e.g. "Which project does 'it' refer to?")
+- When not ambiguous: null
+"#;
+
+#[derive(Debug, Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct LLMAmbiguity {
+    pub ambiguous: bool,
+    pub question: Option<String>,
+}
+
+pub struct AmbiguityCheck;
+
+impl AmbiguityCheck {
+    /// Returns a clarifying question when `content` is too ambiguous to store as is.
+    pub async fn generate(
+        content: &str,
+        model: Arc<LanguageModel>,
+    ) -> Result<Option<String>, AnnotationError> {
+        let request = GenerateObjectRequestBuilder::<LLMAmbiguity>::new()
+            .model(model)
+            .system(AMBIGUITY_PROMPT)
+            .prompt(content)
+            .max_output_tokens(200)
+            .temperature(0.0)
+            .build()?;
+
+        let ambiguity = umem_ai::generate_object(request).await?.output;
+        Ok(match ambiguity.ambiguous {
+            true => ambiguity
+                .question
+                .map(|question| question.trim().to_string())
+                .filter(|question| !question.is_empty()),
+            false => None,
+        })
+    }
+}
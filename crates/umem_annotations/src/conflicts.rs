@@ -0,0 +1,59 @@
+use crate::AnnotationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use umem_ai::{GenerateObjectRequestBuilder, LanguageModel};
+
+const CONFLICT_PROMPT: &str = r#"
+You are a conflict detection system for a memory store. You are given a NEW memory and a numbered list of EXISTING memories about the same user.
+
+## Output
+
+### contradicted
+- The numbers of the existing memories that the new memory directly contradicts
+- A contradiction means both memories cannot be true at the same time (e.g. "User lives in Paris" vs "User moved to Berlin")
+- Memories that merely add detail, refine, or talk about different things are NOT contradictions
+- Return an empty list if nothing is contradicted
+"#;
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct LLMConflicts {
+    pub contradicted: Vec<usize>,
+}
+
+pub struct ConflictDetection;
+
+impl ConflictDetection {
+    /// Returns the indices of `existing` that the `summary` contradicts.
+    pub async fn generate(
+        summary: &str,
+        existing: &[String],
+        model: Arc<LanguageModel>,
+    ) -> Result<Vec<usize>, AnnotationError> {
+        if existing.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let existing_list = existing
+            .iter()
+            .enumerate()
+            .map(|(index, memory)| format!("{index}. {memory}"))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let request = GenerateObjectRequestBuilder::<LLMConflicts>::new()
+            .model(model)
+            .system(CONFLICT_PROMPT)
+            .prompt(format!(
+                "NEW memory:\n{summary}\n\nEXISTING memories:\n{existing_list}"
+            ))
+            .max_output_tokens(500)
+            .temperature(0.0)
+            .build()?;
+
+        let mut contradicted = umem_ai::generate_object(request).await?.output.contradicted;
+        contradicted.retain(|index| *index < existing.len());
+        contradicted.sort_unstable();
+        contradicted.dedup();
+        Ok(contradicted)
+    }
+}
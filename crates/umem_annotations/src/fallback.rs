@@ -0,0 +1,66 @@
+use crate::{Annotated, AnnotationError, MAX_SUMMARY_LENGTH, MAX_TAGS, MAX_TAG_LENGTH};
+use rustc_hash::{FxHashMap, FxHashSet};
+use stopwords::{Language, Stopwords, NLTK};
+use umem_core::{credence::Credence, MemoryContent, MemoryKind, MemorySignals, Provenance};
+
+/// Words shorter than this make poor tags.
+const MIN_KEYWORD_LENGTH: usize = 3;
+
+const FALLBACK_CERTAINTY: f32 = 1.0;
+const FALLBACK_SALIENCE: f32 = 0.5;
+
+impl crate::Annotation {
+    /// Annotates `raw_content` without the language model: the content itself is the
+    /// summary and its most frequent keywords are the tags. Used when the language model
+    /// must not be called, e.g. once the spend budget is used up.
+    pub fn fallback(raw_content: &str) -> Result<Annotated, AnnotationError> {
+        let summary = match raw_content.char_indices().nth(MAX_SUMMARY_LENGTH) {
+            Some((end, _)) => &raw_content[..end],
+            None => raw_content,
+        };
+
+        Ok(Annotated {
+            content: MemoryContent::new(summary, keywords(raw_content))?,
+            kind: MemoryKind::default(),
+            signals: MemorySignals::new(
+                Credence::new(FALLBACK_CERTAINTY)?,
+                Credence::new(FALLBACK_SALIENCE)?,
+            )?,
+            provenance: Provenance::direct_user(),
+            language: None,
+            schedule: None,
+        })
+    }
+}
+
+/// The `MAX_TAGS` most frequent words of `text` that are not stopwords, earliest first
+/// among equally frequent ones.
+fn keywords(text: &str) -> Vec<String> {
+    let stopwords: FxHashSet<&str> = NLTK::stopwords(Language::English)
+        .map(|stopwords| stopwords.iter().copied().collect())
+        .unwrap_or_default();
+
+    let mut counts: FxHashMap<String, (usize, usize)> = FxHashMap::default();
+    for (position, word) in text
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| {
+            (MIN_KEYWORD_LENGTH..=MAX_TAG_LENGTH).contains(&word.chars().count())
+                && !word.chars().all(char::is_numeric)
+                && !stopwords.contains(word.as_str())
+        })
+        .enumerate()
+    {
+        counts.entry(word).or_insert((0, position)).0 += 1;
+    }
+
+    let mut keywords: Vec<(String, (usize, usize))> = counts.into_iter().collect();
+    keywords.sort_by(|(_, (a_count, a_first)), (_, (b_count, b_first))| {
+        b_count.cmp(a_count).then(a_first.cmp(b_first))
+    });
+    keywords
+        .into_iter()
+        .take(MAX_TAGS)
+        .map(|(word, _)| word)
+        .collect()
+}
@@ -0,0 +1,127 @@
+use crate::LLMAnnotations;
+use rustc_hash::FxHashSet;
+use thiserror::Error;
+use umem_core::{MemoryKind, MemorySchedule};
+
+pub const MAX_SUMMARY_LENGTH: usize = 1000;
+pub const MIN_TAGS: usize = 3;
+pub const MAX_TAGS: usize = 7;
+pub const MAX_TAG_LENGTH: usize = 40;
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum GuardrailViolation {
+    #[error("memory {index}: summary is empty")]
+    EmptySummary { index: usize },
+
+    #[error("memory {index}: summary is {length} characters long, the maximum is {max}", max = MAX_SUMMARY_LENGTH)]
+    SummaryTooLong { index: usize, length: usize },
+
+    #[error("memory {index}: has {count} tags, expected between {min} and {max}", min = MIN_TAGS, max = MAX_TAGS)]
+    TagCount { index: usize, count: usize },
+
+    #[error("memory {index}: tag \"{tag}\" must be a trimmed lowercase keyword of at most {max} characters", max = MAX_TAG_LENGTH)]
+    InvalidTag { index: usize, tag: String },
+
+    #[error("memory {index}: tag \"{tag}\" is repeated")]
+    DuplicateTag { index: usize, tag: String },
+
+    #[error("memory {index}: {signal} must be between 0.0 and 1.0, got {value}")]
+    SignalOutOfRange {
+        index: usize,
+        signal: &'static str,
+        value: f32,
+    },
+
+    #[error("memory {index}: certainty and salience cannot both be zero")]
+    DeadSignals { index: usize },
+
+    #[error("memory {index}: due_at \"{due_at}\" must be an RFC 3339 timestamp")]
+    InvalidDueAt { index: usize, due_at: String },
+
+    #[error("memory {index}: only prospective memories can have due_at or recurrence")]
+    ScheduleOnNonProspective { index: usize },
+}
+
+fn is_valid_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag.trim() == tag
+        && tag.chars().count() <= MAX_TAG_LENGTH
+        && !tag.chars().any(|c| c.is_uppercase() || c.is_control())
+}
+
+fn is_valid_signal(value: f32) -> bool {
+    value.is_finite() && (0.0..=1.0).contains(&value)
+}
+
+impl LLMAnnotations {
+    pub fn violations(&self) -> Vec<GuardrailViolation> {
+        let mut violations = Vec::new();
+
+        for (index, annotated) in self.memories.iter().enumerate() {
+            let summary = annotated.content.summary().trim();
+            let length = summary.chars().count();
+
+            if summary.is_empty() {
+                violations.push(GuardrailViolation::EmptySummary { index });
+            } else if length > MAX_SUMMARY_LENGTH {
+                violations.push(GuardrailViolation::SummaryTooLong { index, length });
+            }
+
+            let tags = annotated.content.tags();
+            if !(MIN_TAGS..=MAX_TAGS).contains(&tags.len()) {
+                violations.push(GuardrailViolation::TagCount {
+                    index,
+                    count: tags.len(),
+                });
+            }
+
+            let mut seen = FxHashSet::default();
+            for tag in tags {
+                if !is_valid_tag(tag) {
+                    violations.push(GuardrailViolation::InvalidTag {
+                        index,
+                        tag: tag.clone(),
+                    });
+                } else if !seen.insert(tag.as_str()) {
+                    violations.push(GuardrailViolation::DuplicateTag {
+                        index,
+                        tag: tag.clone(),
+                    });
+                }
+            }
+
+            let signals = annotated.signals;
+            for (signal, value) in [
+                ("certainty", signals.certainty),
+                ("salience", signals.salience),
+            ] {
+                if !is_valid_signal(value) {
+                    violations.push(GuardrailViolation::SignalOutOfRange {
+                        index,
+                        signal,
+                        value,
+                    });
+                }
+            }
+
+            if signals.certainty == 0.0 && signals.salience == 0.0 {
+                violations.push(GuardrailViolation::DeadSignals { index });
+            }
+
+            if annotated.kind != MemoryKind::Prospective
+                && (annotated.due_at.is_some() || annotated.recurrence.is_some())
+            {
+                violations.push(GuardrailViolation::ScheduleOnNonProspective { index });
+            } else if let Some(due_at) = &annotated.due_at {
+                if MemorySchedule::parse(due_at, annotated.recurrence).is_err() {
+                    violations.push(GuardrailViolation::InvalidDueAt {
+                        index,
+                        due_at: due_at.clone(),
+                    });
+                }
+            }
+        }
+
+        violations
+    }
+}
@@ -1,14 +1,36 @@
+mod ambiguity;
+mod conflicts;
+mod fallback;
+mod guardrails;
+mod prompt;
+mod relations;
+mod session;
+mod topics;
+mod translation;
+
+pub use ambiguity::*;
+pub use conflicts::*;
+pub use guardrails::*;
+pub use prompt::*;
+pub use relations::*;
+pub use session::*;
+pub use topics::*;
+pub use translation::*;
+
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use thiserror::Error;
+use tracing::warn;
 use umem_core::{
-    MemoryContent,
-    MemoryKind,
-    // MemorySignals, Provenance
+    credence::{Credence, CredenceError},
+    MemoryContent, MemoryContentError, MemoryKind, MemoryLanguage, MemoryLanguageError,
+    MemorySchedule, MemoryScheduleError, MemorySignals, MemorySignalsError, ParseMemoryKindError,
+    Provenance, ProvenanceMethod, ProvenanceOrigin, Recurrence,
 };
 
 use umem_ai::{
-    GenerateObjectRequestBuilder, GenerateObjectRequestBuilderError,
+    GenerateObjectRequest, GenerateObjectRequestBuilder, GenerateObjectRequestBuilderError,
     GenerateTextRequestBuilderError, LanguageModel, ResponseGeneratorError,
 };
 
@@ -24,63 +46,237 @@ pub enum AnnotationError {
 
     #[error("llm response generate_object failed: {0}")]
     GenerateObjectRequestBuilderError(#[from] GenerateObjectRequestBuilderError),
+
+    #[error("llm annotated credence is invalid: {0}")]
+    CredenceError(#[from] CredenceError),
+
+    #[error("llm annotated signals are invalid: {0}")]
+    MemorySignalsError(#[from] MemorySignalsError),
+
+    #[error("annotation prompt could not be read: {0}")]
+    PromptReadError(#[from] std::io::Error),
+
+    #[error("annotation prompt kind definition is invalid: {0}")]
+    KindDefinitionError(#[from] ParseMemoryKindError),
+
+    #[error("annotation prompt and prompt_path cannot both be set")]
+    ConflictingPromptSources,
+
+    #[error("unknown storage language: {0}")]
+    UnknownLanguage(String),
+
+    #[error("translated memory language is invalid: {0}")]
+    MemoryLanguageError(#[from] MemoryLanguageError),
+
+    #[error("translated memory content is invalid: {0}")]
+    MemoryContentError(#[from] MemoryContentError),
+
+    #[error("llm annotation violated quality guardrails: {}", format_violations(.0))]
+    GuardrailViolations(Vec<GuardrailViolation>),
+
+    #[error("llm annotated schedule is invalid: {0}")]
+    MemoryScheduleError(#[from] MemoryScheduleError),
+
+    #[error("llm annotation could not be serialized: {0}")]
+    SerdeError(#[from] serde_json::Error),
+}
+
+fn format_violations(violations: &[GuardrailViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join("; ")
 }
 
-const ANNOTATION_PROMPT: &str = r#"
-You are a memory annotation system. Your task is to analyze a chat session between a user and an AI agent, then extract structured memory metadata that can be stored and retrieved efficiently.
-
-## Input
-A conversation transcript containing user messages and agent responses. Focus on extracting what the user learned, decided, asked about, or expressed preferences for—not the back-and-forth dialogue itself.
-
-## Output
-
-### content.summary
-Extract the key points from the conversation as a concise, information-dense summary. Requirements:
-- Preserve specific details: names, dates, numbers, URLs, technical terms, and concrete values
-- Focus on actionable information, facts, preferences, and decisions made by the user
-- Capture the resolution or answer, not the process of arriving at it
-- Omit filler words, pleasantries, and redundant back-and-forth
-- Use clear, direct language
-
-### content.tags
-Extract 3-7 lowercase keywords that categorize and index this memory:
-- Use singular forms (e.g., "project" not "projects")
-- Include domain-specific terms, proper nouns (lowercased), and action verbs where relevant
-- Prioritize terms useful for future retrieval
-
-### kind
-Classify the memory into exactly one type:
-- **Semantic**: General knowledge, facts, concepts, definitions, explanations
-- **Episodic**: Specific events, experiences, occurrences with temporal or spatial context
-- **Procedural**: How-to knowledge, workflows, step-by-step processes, techniques, habits
-- **Instruction**: Explicit directives, user preferences, rules, constraints, configurations
-- **Relational**: Information about people, organizations, entities, and their relationships
-- **Working**: Temporary context relevant only to an ongoing task or session
-- **Prospective**: Future intentions, goals, plans, reminders, scheduled commitments
+const MAX_CORRECTIONS: usize = 2;
+
+const CORRECTION_PROMPT: &str = r#"
+## Corrections
+Your previous answer broke the annotation rules. Fix every issue listed below and answer again with the complete list of memories.
+
+### Issues
+{{violations}}
+
+### Previous answer
+{{previous_answer}}
 "#;
 
+#[derive(Clone, Copy, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct LLMSignals {
+    pub certainty: f32,
+    pub salience: f32,
+}
+
 #[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
 pub struct LLMAnnotated {
     pub content: MemoryContent,
     pub kind: MemoryKind,
-    // pub signals: MemorySignals,
-    // pub provenance: Provenance,
+    pub signals: LLMSignals,
+    pub origin: ProvenanceOrigin,
+    #[serde(default)]
+    pub due_at: Option<String>,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct LLMAnnotations {
+    pub memories: Vec<LLMAnnotated>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Annotated {
+    pub content: MemoryContent,
+    pub kind: MemoryKind,
+    pub signals: MemorySignals,
+    pub provenance: Provenance,
+    pub language: Option<MemoryLanguage>,
+    pub schedule: Option<MemorySchedule>,
+}
+
+impl LLMAnnotated {
+    pub fn validate(self, method: ProvenanceMethod) -> Result<Annotated, AnnotationError> {
+        let signals = MemorySignals::new(
+            Credence::new(self.signals.certainty)?,
+            Credence::new(self.signals.salience)?,
+        )?;
+
+        let schedule = match (self.kind, self.due_at) {
+            (MemoryKind::Prospective, Some(due_at)) => {
+                Some(MemorySchedule::parse(&due_at, self.recurrence)?)
+            }
+            _ => None,
+        };
+
+        Ok(Annotated {
+            content: self.content,
+            kind: self.kind,
+            signals,
+            provenance: Provenance::new(self.origin, method),
+            language: None,
+            schedule,
+        })
+    }
 }
 
 impl Annotation {
     pub async fn generate(
         raw_content: impl Into<String>,
         model: Arc<LanguageModel>,
-    ) -> Result<LLMAnnotated, AnnotationError> {
-        let request = GenerateObjectRequestBuilder::<LLMAnnotated>::new()
+        prompt: &AnnotationPrompt,
+        existing_memories: &[String],
+    ) -> Result<Vec<Annotated>, AnnotationError> {
+        let method = Self::method(&model, prompt);
+        let raw_content = raw_content.into();
+        let system = Self::system(prompt, existing_memories);
+        let mut user_prompt = raw_content.clone();
+        let mut corrections = 0;
+
+        loop {
+            let request = Self::object_request(Arc::clone(&model), system.clone(), user_prompt)?;
+
+            let annotations = umem_ai::generate_object(request).await?.output;
+            let violations = annotations.violations();
+
+            if violations.is_empty() {
+                return annotations
+                    .memories
+                    .into_iter()
+                    .map(|annotated| annotated.validate(method.clone()))
+                    .collect();
+            }
+
+            if corrections == MAX_CORRECTIONS {
+                return Err(AnnotationError::GuardrailViolations(violations));
+            }
+
+            corrections += 1;
+            warn!(
+                "annotation violated {} guardrails, retrying with corrections ({}/{})",
+                violations.len(),
+                corrections,
+                MAX_CORRECTIONS
+            );
+
+            let issues = violations
+                .iter()
+                .map(|violation| format!("- {violation}"))
+                .collect::<Vec<String>>()
+                .join("\n");
+            let correction = CORRECTION_PROMPT
+                .replace("{{violations}}", &issues)
+                .replace("{{previous_answer}}", &serde_json::to_string(&annotations)?);
+            user_prompt = format!("{raw_content}\n{correction}");
+        }
+    }
+
+    /// The first request `generate` makes, for answering through a batch API instead.
+    pub fn request(
+        raw_content: impl Into<String>,
+        model: Arc<LanguageModel>,
+        prompt: &AnnotationPrompt,
+        existing_memories: &[String],
+    ) -> Result<GenerateObjectRequest<LLMAnnotations>, AnnotationError> {
+        Self::object_request(
+            model,
+            Self::system(prompt, existing_memories),
+            raw_content.into(),
+        )
+    }
+
+    /// Validates the answer to a `request`. An answer that breaks the guardrails is
+    /// thrown away and annotated again with `generate`, which corrects it call by call.
+    pub async fn from_output(
+        annotations: LLMAnnotations,
+        raw_content: impl Into<String>,
+        model: Arc<LanguageModel>,
+        prompt: &AnnotationPrompt,
+        existing_memories: &[String],
+    ) -> Result<Vec<Annotated>, AnnotationError> {
+        let violations = annotations.violations();
+        if !violations.is_empty() {
+            warn!(
+                "batched annotation violated {} guardrails, annotating again",
+                violations.len()
+            );
+            return Self::generate(raw_content, model, prompt, existing_memories).await;
+        }
+
+        let method = Self::method(&model, prompt);
+        annotations
+            .memories
+            .into_iter()
+            .map(|annotated| annotated.validate(method.clone()))
+            .collect()
+    }
+
+    fn method(model: &LanguageModel, prompt: &AnnotationPrompt) -> ProvenanceMethod {
+        ProvenanceMethod::Extracted {
+            model: model.model_name.clone(),
+            prompt: prompt.name().to_string(),
+        }
+    }
+
+    fn system(prompt: &AnnotationPrompt, existing_memories: &[String]) -> String {
+        format!(
+            "{}\n\nCurrent time (UTC): {}",
+            prompt.render(existing_memories),
+            Utc::now().to_rfc3339()
+        )
+    }
+
+    fn object_request(
+        model: Arc<LanguageModel>,
+        system: String,
+        user_prompt: String,
+    ) -> Result<GenerateObjectRequest<LLMAnnotations>, AnnotationError> {
+        Ok(GenerateObjectRequestBuilder::<LLMAnnotations>::new()
             .model(model)
-            .system(ANNOTATION_PROMPT)
-            .prompt(raw_content)
+            .system(system)
+            .prompt(user_prompt)
             .max_output_tokens(10000)
             .temperature(0.7)
-            .build()?;
-
-        let annotations = umem_ai::generate_object(request).await?;
-        Ok(annotations.output)
+            .build()?)
     }
 }
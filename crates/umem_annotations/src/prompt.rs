@@ -0,0 +1,193 @@
+use crate::AnnotationError;
+use std::str::FromStr;
+use umem_core::MemoryKind;
+
+pub const DEFAULT_ANNOTATION_PROMPT_NAME: &str = "memory_annotation";
+
+const KIND_DEFINITIONS_VARIABLE: &str = "{{kind_definitions}}";
+const EXISTING_MEMORIES_VARIABLE: &str = "{{existing_memories}}";
+
+const ANNOTATION_PROMPT: &str = r#"
+You are a memory annotation system. Your task is to analyze a chat session between a user and an AI agent, then extract structured memory metadata that can be stored and retrieved efficiently.
+
+## Input
+A conversation transcript containing user messages and agent responses. Focus on extracting what the user learned, decided, asked about, or expressed preferences for—not the back-and-forth dialogue itself.
+
+## Existing memories
+Memories already stored for this user. Do not extract information that is already captured here; only extract new or changed information.
+
+{{existing_memories}}
+
+## Output
+
+### memories
+A list with one entry per distinct fact, preference, decision, event, or plan found in the conversation:
+- Split unrelated pieces of information into separate memories
+- Keep closely related details together in a single memory
+- Do not repeat the same information across memories
+- Return an empty list if the conversation contains nothing worth remembering
+
+Every memory has the following fields.
+
+### content.summary
+Extract the key points of this memory as a concise, information-dense summary. Requirements:
+- Preserve specific details: names, dates, numbers, URLs, technical terms, and concrete values
+- Focus on actionable information, facts, preferences, and decisions made by the user
+- Capture the resolution or answer, not the process of arriving at it
+- Omit filler words, pleasantries, and redundant back-and-forth
+- Use clear, direct language
+
+### content.tags
+Extract 3-7 lowercase keywords that categorize and index this memory:
+- Use singular forms (e.g., "project" not "projects")
+- Include domain-specific terms, proper nouns (lowercased), and action verbs where relevant
+- Prioritize terms useful for future retrieval
+
+### kind
+Classify the memory into exactly one type:
+{{kind_definitions}}
+
+### signals.certainty
+A number between 0.0 and 1.0 estimating how confident you are that the memory is true and correctly captured:
+- 1.0 for facts the user stated explicitly and unambiguously
+- Around 0.5 for reasonable inferences from the conversation
+- Below 0.3 for speculation, hypotheticals, or sarcasm
+
+### signals.salience
+A number between 0.0 and 1.0 estimating how important the memory is for future interactions with the user:
+- High for durable preferences, identity, relationships, and commitments
+- Low for passing remarks and one-off details
+- Certainty and salience must not both be 0.0
+
+### origin
+Who the information came from:
+- **User**: Stated or expressed by the user
+- **Agent**: Produced by the AI agent (e.g. an answer or recommendation the user accepted)
+
+### due_at
+Only for Prospective memories with a concrete time (e.g. "remind me next Tuesday at 9"), otherwise null:
+- An RFC 3339 timestamp such as "2025-03-04T09:00:00Z"
+- Resolve relative times ("tomorrow", "next Tuesday") against the current time given at the end of these instructions
+- Use 09:00 in the user's timezone (UTC if unknown) when only a date is given
+
+### recurrence
+Only for Prospective memories that repeat, otherwise null: one of "daily", "weekly", "monthly", "yearly"
+"#;
+
+const DEFAULT_KIND_DEFINITIONS: &[(MemoryKind, &str)] = &[
+    (
+        MemoryKind::Semantic,
+        "General knowledge, facts, concepts, definitions, explanations",
+    ),
+    (
+        MemoryKind::Episodic,
+        "Specific events, experiences, occurrences with temporal or spatial context",
+    ),
+    (
+        MemoryKind::Procedural,
+        "How-to knowledge, workflows, step-by-step processes, techniques, habits",
+    ),
+    (
+        MemoryKind::Instruction,
+        "Explicit directives, user preferences, rules, constraints, configurations",
+    ),
+    (
+        MemoryKind::Relational,
+        "Information about people, organizations, entities, and their relationships",
+    ),
+    (
+        MemoryKind::Working,
+        "Temporary context relevant only to an ongoing task or session",
+    ),
+    (
+        MemoryKind::Prospective,
+        "Future intentions, goals, plans, reminders, scheduled commitments",
+    ),
+];
+
+#[derive(Debug, Clone)]
+pub struct AnnotationPrompt {
+    name: String,
+    template: String,
+    kind_definitions: Vec<(MemoryKind, String)>,
+}
+
+impl Default for AnnotationPrompt {
+    fn default() -> Self {
+        Self::new(DEFAULT_ANNOTATION_PROMPT_NAME, ANNOTATION_PROMPT)
+    }
+}
+
+impl AnnotationPrompt {
+    pub fn new(name: impl Into<String>, template: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            template: template.into(),
+            kind_definitions: DEFAULT_KIND_DEFINITIONS
+                .iter()
+                .map(|(kind, definition)| (*kind, definition.to_string()))
+                .collect(),
+        }
+    }
+
+    pub fn from_config(config: &umem_config::Annotation) -> Result<Self, AnnotationError> {
+        let mut prompt = match (&config.prompt, &config.prompt_path) {
+            (Some(_), Some(_)) => return Err(AnnotationError::ConflictingPromptSources),
+            (Some(template), None) => Self::new("inline", template),
+            (None, Some(path)) => {
+                let name = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or(DEFAULT_ANNOTATION_PROMPT_NAME);
+                Self::new(name, std::fs::read_to_string(path)?)
+            }
+            (None, None) => Self::default(),
+        };
+
+        for (kind, definition) in &config.kind_definitions {
+            prompt = prompt.with_kind_definition(MemoryKind::from_str(kind)?, definition);
+        }
+
+        Ok(prompt)
+    }
+
+    pub fn with_kind_definition(mut self, kind: MemoryKind, definition: impl Into<String>) -> Self {
+        let definition = definition.into();
+        match self.kind_definitions.iter_mut().find(|(k, _)| *k == kind) {
+            Some((_, existing)) => *existing = definition,
+            None => self.kind_definitions.push((kind, definition)),
+        }
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn uses_existing_memories(&self) -> bool {
+        self.template.contains(EXISTING_MEMORIES_VARIABLE)
+    }
+
+    pub fn render(&self, existing_memories: &[String]) -> String {
+        let kind_definitions = self
+            .kind_definitions
+            .iter()
+            .map(|(kind, definition)| format!("- **{}**: {}", kind.as_str(), definition))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let existing_memories = if existing_memories.is_empty() {
+            "None".to_string()
+        } else {
+            existing_memories
+                .iter()
+                .map(|summary| format!("- {summary}"))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+
+        self.template
+            .replace(KIND_DEFINITIONS_VARIABLE, &kind_definitions)
+            .replace(EXISTING_MEMORIES_VARIABLE, &existing_memories)
+    }
+}
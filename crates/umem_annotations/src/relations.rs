@@ -0,0 +1,48 @@
+use crate::AnnotationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use umem_ai::{GenerateObjectRequestBuilder, LanguageModel};
+
+const RELATION_PROMPT: &str = r#"
+You are a relation extraction system for a memory store. Given a memory about people, organizations, or other entities, extract the relationships it states as subject-predicate-object triples.
+
+## Output
+
+### relations
+- subject and object are entity names exactly as written in the memory (e.g. "Alice", "Acme Corp")
+- predicate is a short lowercase verb phrase describing the relationship (e.g. "works with", "manages", "is married to")
+- Use "user" as the entity name when the memory refers to the user
+- Only extract relationships that are explicitly stated; do not infer new ones
+- Return an empty list if the memory states no relationships
+"#;
+
+#[derive(Debug, Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct LLMRelation {
+    pub subject: String,
+    pub predicate: String,
+    pub object: String,
+}
+
+#[derive(Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct LLMRelations {
+    pub relations: Vec<LLMRelation>,
+}
+
+pub struct RelationExtraction;
+
+impl RelationExtraction {
+    pub async fn generate(
+        summary: impl Into<String>,
+        model: Arc<LanguageModel>,
+    ) -> Result<Vec<LLMRelation>, AnnotationError> {
+        let request = GenerateObjectRequestBuilder::<LLMRelations>::new()
+            .model(model)
+            .system(RELATION_PROMPT)
+            .prompt(summary)
+            .max_output_tokens(2000)
+            .temperature(0.0)
+            .build()?;
+
+        Ok(umem_ai::generate_object(request).await?.output.relations)
+    }
+}
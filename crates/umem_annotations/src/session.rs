@@ -0,0 +1,50 @@
+use crate::AnnotationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use umem_ai::{GenerateObjectRequestBuilder, LanguageModel};
+
+const SESSION_SUMMARY_PROMPT: &str = r#"
+You are a session summarization system for a memory store. You are given the working memories an agent collected during a single session, in the order they were recorded.
+
+## Output
+
+### summary
+- One consolidated, self-contained account of what happened in the session (max 1000 characters)
+- Keep decisions, outcomes, open questions, and facts about the user that were learned
+- Drop scratch notes and intermediate steps that no longer matter
+- Write in third person ("User...", "The agent...")
+
+### tags
+- 3-7 lowercase keywords covering the main topics of the session
+"#;
+
+#[derive(Debug, Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct LLMSessionSummary {
+    pub summary: String,
+    pub tags: Vec<String>,
+}
+
+pub struct SessionSummary;
+
+impl SessionSummary {
+    pub async fn generate(
+        memories: &[String],
+        model: Arc<LanguageModel>,
+    ) -> Result<LLMSessionSummary, AnnotationError> {
+        let session = memories
+            .iter()
+            .map(|memory| format!("- {memory}"))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let request = GenerateObjectRequestBuilder::<LLMSessionSummary>::new()
+            .model(model)
+            .system(SESSION_SUMMARY_PROMPT)
+            .prompt(session)
+            .max_output_tokens(2000)
+            .temperature(0.0)
+            .build()?;
+
+        Ok(umem_ai::generate_object(request).await?.output)
+    }
+}
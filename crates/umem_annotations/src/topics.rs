@@ -0,0 +1,50 @@
+use crate::AnnotationError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use umem_ai::{GenerateObjectRequestBuilder, LanguageModel};
+
+const TOPIC_LABEL_PROMPT: &str = r#"
+You are a topic labeling system for a memory store. You are given memories about one user that were grouped together because they are about similar things.
+
+## Output
+
+### label
+- A short lowercase name for what the memories have in common (1-4 words, e.g. "cooking", "job search", "family health")
+- Name the subject, not the kind of memory ("travel plans", not "user preferences")
+- Prefer a broader label that fits every memory over a precise one that fits only some
+"#;
+
+#[derive(Debug, Clone, schemars::JsonSchema, Serialize, Deserialize)]
+pub struct LLMTopicLabel {
+    pub label: String,
+}
+
+pub struct TopicLabel;
+
+impl TopicLabel {
+    pub async fn generate(
+        memories: &[String],
+        model: Arc<LanguageModel>,
+    ) -> Result<String, AnnotationError> {
+        let cluster = memories
+            .iter()
+            .map(|memory| format!("- {memory}"))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let request = GenerateObjectRequestBuilder::<LLMTopicLabel>::new()
+            .model(model)
+            .system(TOPIC_LABEL_PROMPT)
+            .prompt(cluster)
+            .max_output_tokens(100)
+            .temperature(0.0)
+            .build()?;
+
+        Ok(umem_ai::generate_object(request)
+            .await?
+            .output
+            .label
+            .trim()
+            .to_lowercase())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{Annotated, AnnotationError};
+use std::sync::Arc;
+use umem_ai::{GenerateTextRequestBuilder, LanguageModel};
+use umem_core::{MemoryContent, MemoryLanguage};
+use whatlang::Lang;
+
+const TRANSLATION_PROMPT: &str = r#"
+You are a translation system for a memory store. Translate the memory summary given by the user into {{language}}.
+
+Requirements:
+- Preserve every specific detail: names, dates, numbers, URLs, technical terms, and concrete values
+- Do not translate proper nouns, code, or identifiers
+- Do not add, remove, or explain information
+- Respond with the translated summary only
+"#;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Translator {
+    storage_language: Lang,
+}
+
+impl Translator {
+    pub fn new(storage_language: &str) -> Result<Self, AnnotationError> {
+        let storage_language = Lang::from_code(storage_language.trim().to_ascii_lowercase())
+            .ok_or_else(|| AnnotationError::UnknownLanguage(storage_language.to_string()))?;
+
+        Ok(Self { storage_language })
+    }
+
+    pub fn from_config(config: &umem_config::Annotation) -> Result<Option<Self>, AnnotationError> {
+        config
+            .storage_language
+            .as_deref()
+            .map(Self::new)
+            .transpose()
+    }
+
+    pub fn storage_language(&self) -> Lang {
+        self.storage_language
+    }
+
+    pub fn detect(text: &str) -> Option<Lang> {
+        whatlang::detect(text)
+            .filter(|info| info.is_reliable())
+            .map(|info| info.lang())
+    }
+
+    pub async fn translate(
+        &self,
+        annotated: Annotated,
+        model: Arc<LanguageModel>,
+    ) -> Result<Annotated, AnnotationError> {
+        let summary = annotated.content.summary();
+
+        let Some(language) = Self::detect(summary) else {
+            return Ok(annotated);
+        };
+
+        if language == self.storage_language {
+            return Ok(Annotated {
+                language: Some(MemoryLanguage::new(language.code())?),
+                ..annotated
+            });
+        }
+
+        let request = GenerateTextRequestBuilder::new()
+            .model(model)
+            .system(TRANSLATION_PROMPT.replace("{{language}}", self.storage_language.eng_name()))
+            .prompt(summary.clone())
+            .temperature(0.0)
+            .build()?;
+
+        let translated = umem_ai::generate_text(request).await?.text;
+        let language = MemoryLanguage::translated(language.code(), summary.clone())?;
+        let content = MemoryContent::new(translated, annotated.content.tags().clone())?;
+
+        Ok(Annotated {
+            content,
+            language: Some(language),
+            ..annotated
+        })
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{AuthError, Result};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use umem_core::MemoryContext;
+use uuid::Uuid;
+
+/// Prefix of every issued key, so servers can tell api keys apart from OAuth tokens.
+pub const API_KEY_PREFIX: &str = "enf_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Access {
+    Read,
+    ReadWrite,
+}
+
+impl Access {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Access::Read => "read",
+            Access::ReadWrite => "read_write",
+        }
+    }
+}
+
+impl FromStr for Access {
+    type Err = AuthError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "read" => Ok(Access::Read),
+            "read_write" => Ok(Access::ReadWrite),
+            other => Err(AuthError::UnknownAccess(other.to_string())),
+        }
+    }
+}
+
+/// Contexts a key may touch. `None` leaves that identifier unrestricted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyScope {
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    /// Organization whose shared pool the key may also read and publish to. Keys
+    /// restricted to a user or agent reach no pool without it.
+    #[serde(default)]
+    pub org_id: Option<String>,
+}
+
+impl KeyScope {
+    pub fn is_unrestricted(&self) -> bool {
+        self.user_id.is_none() && self.agent_id.is_none() && self.org_id.is_none()
+    }
+
+    fn is_member_of(&self, org_id: &str) -> bool {
+        self.is_unrestricted() || self.org_id.as_deref() == Some(org_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub scope: KeyScope,
+    pub access: Access,
+    pub created_at: i64,
+    pub revoked_at: Option<i64>,
+}
+
+impl ApiKey {
+    /// A read-write principal limited to `user_id`, for callers that signed in some other
+    /// way, such as an OAuth session. It is never stored and cannot be verified.
+    pub fn for_user(user_id: impl Into<String>) -> Self {
+        ApiKey {
+            id: Uuid::nil(),
+            name: "session".to_string(),
+            scope: KeyScope {
+                user_id: Some(user_id.into()),
+                agent_id: None,
+                org_id: None,
+            },
+            access: Access::ReadWrite,
+            created_at: chrono::Utc::now().timestamp(),
+            revoked_at: None,
+        }
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn can_write(&self) -> bool {
+        self.access == Access::ReadWrite
+    }
+
+    /// Checks that `context` lies inside the key's scope and that the key grants `access`.
+    pub fn authorize(&self, context: &MemoryContext, access: Access) -> Result<()> {
+        if access == Access::ReadWrite && !self.can_write() {
+            return Err(AuthError::ReadOnly);
+        }
+
+        if let Some(org_id) = context.org_id() {
+            if !self.scope.is_member_of(org_id) {
+                return Err(AuthError::OutOfScope {
+                    field: "org_id",
+                    value: org_id.to_string(),
+                });
+            }
+            if context.is_org_pool() {
+                return Ok(());
+            }
+        }
+
+        if let Some(user_id) = &self.scope.user_id {
+            if context.user_id() != Some(user_id.as_str()) {
+                return Err(AuthError::OutOfScope {
+                    field: "user_id",
+                    value: context.user_id().unwrap_or_default().to_string(),
+                });
+            }
+        }
+
+        if let Some(agent_id) = &self.scope.agent_id {
+            if context.agent_id() != Some(agent_id.as_str()) {
+                return Err(AuthError::OutOfScope {
+                    field: "agent_id",
+                    value: context.agent_id().unwrap_or_default().to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(access: Access) -> ApiKey {
+        ApiKey {
+            id: Uuid::new_v4(),
+            name: "ci".to_string(),
+            scope: KeyScope {
+                user_id: Some("alice".to_string()),
+                agent_id: Some("planner".to_string()),
+                org_id: Some("acme".to_string()),
+            },
+            access,
+            created_at: 0,
+            revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn enforces_scope_and_access() {
+        let inside = MemoryContext::new(
+            Some("alice".to_string()),
+            Some("planner".to_string()),
+            Some("run-1".to_string()),
+        )
+        .unwrap();
+        let other_agent =
+            MemoryContext::new(Some("alice".to_string()), Some("coder".to_string()), None).unwrap();
+        let user_only = MemoryContext::for_user("alice").unwrap();
+
+        assert!(key(Access::ReadWrite)
+            .authorize(&inside, Access::ReadWrite)
+            .is_ok());
+        assert!(key(Access::Read).authorize(&inside, Access::Read).is_ok());
+        assert!(matches!(
+            key(Access::Read).authorize(&inside, Access::ReadWrite),
+            Err(AuthError::ReadOnly)
+        ));
+        assert!(matches!(
+            key(Access::ReadWrite).authorize(&other_agent, Access::Read),
+            Err(AuthError::OutOfScope {
+                field: "agent_id",
+                ..
+            })
+        ));
+        assert!(key(Access::ReadWrite)
+            .authorize(&user_only, Access::Read)
+            .is_err());
+    }
+
+    #[test]
+    fn session_principal_is_limited_to_its_user() {
+        let session = ApiKey::for_user("alice");
+        let bob = MemoryContext::for_user("bob").unwrap();
+
+        assert!(session
+            .authorize(
+                &MemoryContext::for_user("alice").unwrap(),
+                Access::ReadWrite
+            )
+            .is_ok());
+        assert!(matches!(
+            session.authorize(&bob, Access::Read),
+            Err(AuthError::OutOfScope {
+                field: "user_id",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn org_pool_is_limited_to_members() {
+        let acme = MemoryContext::for_org("acme").unwrap();
+        let globex = MemoryContext::for_org("globex").unwrap();
+        let merged =
+            MemoryContext::new(Some("alice".to_string()), Some("planner".to_string()), None)
+                .unwrap()
+                .with_org(Some("globex".to_string()))
+                .unwrap();
+
+        assert!(key(Access::ReadWrite)
+            .authorize(&acme, Access::ReadWrite)
+            .is_ok());
+        assert!(matches!(
+            key(Access::Read).authorize(&acme, Access::ReadWrite),
+            Err(AuthError::ReadOnly)
+        ));
+        assert!(matches!(
+            key(Access::Read).authorize(&globex, Access::Read),
+            Err(AuthError::OutOfScope {
+                field: "org_id",
+                ..
+            })
+        ));
+        assert!(key(Access::Read).authorize(&merged, Access::Read).is_err());
+        assert!(ApiKey::for_user("alice")
+            .authorize(&acme, Access::Read)
+            .is_err());
+    }
+}
@@ -0,0 +1,30 @@
+mod api_key;
+mod store;
+
+pub use api_key::*;
+pub use store::*;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AuthError {
+    #[error("api key store failed with: {0}")]
+    StoreError(#[from] sqlx::Error),
+
+    #[error("api key is invalid or revoked")]
+    InvalidKey,
+
+    #[error("api key is read-only")]
+    ReadOnly,
+
+    #[error("api key is not allowed to access {field} '{value}'")]
+    OutOfScope { field: &'static str, value: String },
+
+    #[error("unknown api key access: {0}")]
+    UnknownAccess(String),
+
+    #[error("stored api key id is not a valid uuid: {0}")]
+    UuidError(#[from] uuid::Error),
+}
+
+type Result<T> = std::result::Result<T, AuthError>;
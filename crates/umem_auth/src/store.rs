@@ -0,0 +1,156 @@
+use crate::{Access, ApiKey, AuthError, KeyScope, Result, API_KEY_PREFIX};
+use chrono::Utc;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::{
+    query,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteRow},
+    Pool, Row, Sqlite,
+};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Api keys in a small sqlite database. Only a SHA-256 digest of each key is stored;
+/// keys carry 256 random bits, so a fast hash is enough to make the table useless to
+/// an attacker who reads it.
+pub struct ApiKeyStore {
+    client: Pool<Sqlite>,
+}
+
+impl ApiKeyStore {
+    pub async fn from_config(config: &umem_config::ApiKeys) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(&config.database_url)?.create_if_missing(true);
+        let client = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
+            .await?;
+
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS api_keys (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                key_hash TEXT NOT NULL UNIQUE,
+                user_id TEXT,
+                agent_id TEXT,
+                org_id TEXT,
+                access TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                revoked_at INTEGER
+            )
+            "#,
+        )
+        .execute(&client)
+        .await?;
+
+        // Databases created before keys carried an organization lack the column.
+        let has_org_id: bool = query(
+            r#"SELECT count(*) > 0 FROM pragma_table_info('api_keys') WHERE name = 'org_id'"#,
+        )
+        .fetch_one(&client)
+        .await?
+        .try_get(0)?;
+        if !has_org_id {
+            query(r#"ALTER TABLE api_keys ADD COLUMN org_id TEXT"#)
+                .execute(&client)
+                .await?;
+        }
+
+        Ok(Self { client })
+    }
+
+    /// Issues a new key. The returned secret is shown once and cannot be recovered.
+    pub async fn create(
+        &self,
+        name: impl Into<String>,
+        scope: KeyScope,
+        access: Access,
+    ) -> Result<(ApiKey, String)> {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let secret = format!("{}{}", API_KEY_PREFIX, hex::encode(bytes));
+
+        let key = ApiKey {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            scope,
+            access,
+            created_at: Utc::now().timestamp(),
+            revoked_at: None,
+        };
+
+        query(
+            r#"INSERT INTO api_keys
+                (id, name, key_hash, user_id, agent_id, org_id, access, created_at)
+                VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+        )
+        .bind(key.id.to_string())
+        .bind(&key.name)
+        .bind(hash(&secret))
+        .bind(&key.scope.user_id)
+        .bind(&key.scope.agent_id)
+        .bind(&key.scope.org_id)
+        .bind(key.access.as_str())
+        .bind(key.created_at)
+        .execute(&self.client)
+        .await?;
+
+        Ok((key, secret))
+    }
+
+    /// Resolves a presented secret to its key, rejecting unknown and revoked keys.
+    pub async fn verify(&self, secret: &str) -> Result<ApiKey> {
+        let row = query(r#"SELECT * FROM api_keys WHERE key_hash = $1"#)
+            .bind(hash(secret))
+            .fetch_optional(&self.client)
+            .await?
+            .ok_or(AuthError::InvalidKey)?;
+
+        let key = Self::map_row(&row)?;
+        if key.is_revoked() {
+            return Err(AuthError::InvalidKey);
+        }
+        Ok(key)
+    }
+
+    pub async fn list(&self) -> Result<Vec<ApiKey>> {
+        query(r#"SELECT * FROM api_keys ORDER BY created_at"#)
+            .fetch_all(&self.client)
+            .await?
+            .iter()
+            .map(Self::map_row)
+            .collect()
+    }
+
+    /// Revokes a key, returning whether an active key with that id existed.
+    pub async fn revoke(&self, id: &Uuid) -> Result<bool> {
+        let result =
+            query(r#"UPDATE api_keys SET revoked_at = $1 WHERE id = $2 AND revoked_at IS NULL"#)
+                .bind(Utc::now().timestamp())
+                .bind(id.to_string())
+                .execute(&self.client)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn map_row(row: &SqliteRow) -> Result<ApiKey> {
+        Ok(ApiKey {
+            id: Uuid::parse_str(row.try_get("id")?)?,
+            name: row.try_get("name")?,
+            scope: KeyScope {
+                user_id: row.try_get("user_id")?,
+                agent_id: row.try_get("agent_id")?,
+                org_id: row.try_get("org_id")?,
+            },
+            access: Access::from_str(row.try_get("access")?)?,
+            created_at: row.try_get("created_at")?,
+            revoked_at: row.try_get("revoked_at")?,
+        })
+    }
+}
+
+fn hash(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
@@ -0,0 +1,79 @@
+mod object;
+
+pub use object::*;
+
+use async_trait::async_trait;
+use object_store::{aws::AmazonS3Builder, local::LocalFileSystem};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Raw content up to this many bytes stays in the memory unless configured otherwise.
+const DEFAULT_INLINE_LIMIT: usize = 64 * 1024;
+
+#[derive(Debug, Error)]
+pub enum BlobStoreError {
+    #[error("invalid blob store bucket: {0} (expected s3:// or file://)")]
+    InvalidBucket(String),
+
+    #[error("blob uri {0} is not in this blob store")]
+    ForeignUri(String),
+
+    #[error("object store action failed with: {0}")]
+    ObjectStoreError(#[from] object_store::Error),
+
+    #[error("io action failed with: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+type Result<T> = std::result::Result<T, BlobStoreError>;
+
+pub struct BlobStore;
+
+impl BlobStore {
+    /// Opens the bucket named by `[blob_store] bucket`.
+    pub fn from_config(
+        config: &umem_config::BlobStore,
+    ) -> Result<Arc<dyn BlobStoreBase + Send + Sync>> {
+        let prefix = config
+            .prefix
+            .as_deref()
+            .unwrap_or_default()
+            .trim_matches('/')
+            .to_string();
+        let inline_limit = config.inline_limit.unwrap_or(DEFAULT_INLINE_LIMIT);
+
+        let store: Arc<dyn object_store::ObjectStore> = match config.bucket.split_once("://") {
+            Some(("s3", name)) => {
+                Arc::new(AmazonS3Builder::from_env().with_bucket_name(name).build()?)
+            }
+            Some(("file", directory)) => {
+                std::fs::create_dir_all(directory)?;
+                Arc::new(LocalFileSystem::new_with_prefix(directory)?)
+            }
+            _ => return Err(BlobStoreError::InvalidBucket(config.bucket.clone())),
+        };
+
+        Ok(Arc::new(ObjectBlobStore::new(
+            store,
+            config.bucket.trim_end_matches('/'),
+            prefix,
+            inline_limit,
+        )))
+    }
+}
+
+/// Large raw transcripts, documents and images kept outside the vector store. Blobs are
+/// addressed by their content, so storing the same bytes twice yields the same uri and
+/// one blob can be shared by every memory made from it.
+#[async_trait]
+pub trait BlobStoreBase {
+    /// Stores `bytes`, returning the uri memories refer to them by.
+    async fn put(&self, bytes: Vec<u8>) -> Result<String>;
+
+    async fn get(&self, uri: &str) -> Result<Vec<u8>>;
+
+    async fn delete(&self, uri: &str) -> Result<()>;
+
+    /// Content longer than this many bytes should be put here rather than kept inline.
+    fn inline_limit(&self) -> usize;
+}
@@ -0,0 +1,97 @@
+use super::{BlobStoreBase, BlobStoreError, Result};
+use async_trait::async_trait;
+use object_store::{path::Path, ObjectStore, PutPayload};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Blobs kept in an object store, e.g. an S3 bucket or a local directory.
+pub struct ObjectBlobStore {
+    store: Arc<dyn ObjectStore>,
+    /// The bucket uri, e.g. `s3://memories`, that blob uris start with.
+    root: String,
+    prefix: String,
+    inline_limit: usize,
+}
+
+impl ObjectBlobStore {
+    pub fn new(
+        store: Arc<dyn ObjectStore>,
+        root: impl Into<String>,
+        prefix: impl Into<String>,
+        inline_limit: usize,
+    ) -> Self {
+        Self {
+            store,
+            root: root.into(),
+            prefix: prefix.into(),
+            inline_limit,
+        }
+    }
+
+    fn key_of(&self, bytes: &[u8]) -> String {
+        let id = Uuid::new_v5(&Uuid::NAMESPACE_OID, bytes);
+        if self.prefix.is_empty() {
+            id.to_string()
+        } else {
+            format!("{}/{}", self.prefix, id)
+        }
+    }
+
+    fn path_of(&self, uri: &str) -> Result<Path> {
+        uri.strip_prefix(&self.root)
+            .and_then(|key| key.strip_prefix('/'))
+            .filter(|key| !key.is_empty())
+            .map(Path::from)
+            .ok_or_else(|| BlobStoreError::ForeignUri(uri.to_string()))
+    }
+}
+
+#[async_trait]
+impl BlobStoreBase for ObjectBlobStore {
+    async fn put(&self, bytes: Vec<u8>) -> Result<String> {
+        let key = self.key_of(&bytes);
+        self.store
+            .put(&Path::from(key.as_str()), PutPayload::from(bytes))
+            .await?;
+        Ok(format!("{}/{}", self.root, key))
+    }
+
+    async fn get(&self, uri: &str) -> Result<Vec<u8>> {
+        let path = self.path_of(uri)?;
+        Ok(self.store.get(&path).await?.bytes().await?.to_vec())
+    }
+
+    async fn delete(&self, uri: &str) -> Result<()> {
+        let path = self.path_of(uri)?;
+        self.store.delete(&path).await?;
+        Ok(())
+    }
+
+    fn inline_limit(&self) -> usize {
+        self.inline_limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+
+    #[tokio::test]
+    async fn blobs_are_addressed_by_their_content() {
+        let blobs = ObjectBlobStore::new(Arc::new(InMemory::new()), "s3://memories", "raw", 0);
+
+        let uri = blobs.put(b"a long transcript".to_vec()).await.unwrap();
+        assert!(uri.starts_with("s3://memories/raw/"));
+        assert_eq!(blobs.put(b"a long transcript".to_vec()).await.unwrap(), uri);
+        assert_eq!(blobs.get(&uri).await.unwrap(), b"a long transcript");
+
+        assert!(matches!(
+            blobs.get("s3://other/raw/blob").await,
+            Err(BlobStoreError::ForeignUri(_))
+        ));
+
+        blobs.delete(&uri).await.unwrap();
+        assert!(blobs.get(&uri).await.is_err());
+    }
+}
@@ -1,7 +1,11 @@
-use config::{Config, File};
+use config::{Config, ConfigError, File, FileFormat};
 use lazy_static::lazy_static;
 use serde::Deserialize;
-use std::net::SocketAddr;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct OpenAI {
@@ -32,25 +36,115 @@ pub enum Provider {
 pub struct EmbeddingModel {
     pub provider: Provider,
     pub model: String,
+    pub fixtures: Option<Fixtures>,
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// US dollars per million input tokens, counted against `[budget]`. Looked up from
+    /// the model registry when unset.
+    pub price_per_million_tokens: Option<f64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct LanguageModel {
     pub provider: Provider,
     pub model: String,
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// US dollars per million prompt tokens, counted against `[budget]` (free by default).
+    pub input_price_per_million_tokens: Option<f64>,
+    /// US dollars per million generated tokens, counted against `[budget]` (free by
+    /// default).
+    pub output_price_per_million_tokens: Option<f64>,
+}
+
+/// Fails calls to a provider straight away while too many of its recent calls failed,
+/// instead of letting each caller wait out its timeout. Once `open_secs` passed, one
+/// probe call is let through; it closes the breaker again if it succeeds.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct CircuitBreaker {
+    /// Share of the last `window` calls that must have failed to open the breaker (0.5
+    /// by default).
+    pub failure_rate: Option<f32>,
+    /// How many of the latest calls the failure rate is computed over; fewer calls never
+    /// open the breaker (20 by default).
+    pub window: Option<usize>,
+    /// How long the breaker stays open before a probe call (30 by default).
+    pub open_secs: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Grpc {
     pub server_addr: SocketAddr,
+    /// More addresses served next to `server_addr`, e.g. an IPv6 one or one per network
+    /// interface.
+    #[serde(default)]
+    pub extra_addrs: Vec<SocketAddr>,
+    /// Unix socket to serve on instead of the addresses above, e.g. for a sidecar that
+    /// shares its pod with the agent. A socket left by an earlier run is replaced.
+    pub socket_path: Option<PathBuf>,
+    /// Longest a call may run before it is cancelled. A shorter `grpc-timeout` sent by
+    /// the client wins. Unbounded when unset.
+    pub request_timeout_ms: Option<u64>,
+    /// How often the status of each service reported by `grpc.health.v1.Health` is
+    /// refreshed from the vector store and embedder readiness (10 by default).
+    pub health_interval_secs: Option<u64>,
+    pub keepalive: Option<GrpcKeepalive>,
+}
+
+/// Keeps connections through load balancers and NATs alive, and bounds how long one
+/// connection is kept, so clients spread out again when instances are added.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct GrpcKeepalive {
+    /// How often an HTTP/2 ping is sent on an idle connection; no pings when unset.
+    pub interval_secs: Option<u64>,
+    /// How long a ping may go unanswered before the connection is closed (20 by
+    /// default).
+    pub timeout_secs: Option<u64>,
+    /// How often TCP keepalive probes are sent; none when unset.
+    pub tcp_secs: Option<u64>,
+    /// How long a connection is kept before the server closes it gracefully; kept for
+    /// good when unset.
+    pub max_connection_age_secs: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Mcp {
     pub server_addr: SocketAddr,
+    /// Unix socket to serve on instead of `server_addr`. A socket left by an earlier run
+    /// is replaced.
+    pub socket_path: Option<PathBuf>,
     pub remote_url: String,
     pub jwks_url: String,
     pub work_os: WorkOs,
+    /// How tools return memories unless a call asks otherwise: `"full"` (default),
+    /// `"json"` for compact objects or `"markdown"`.
+    pub response_format: Option<String>,
+    /// Whether `add_memory` checks content for ambiguity first and asks the host model a
+    /// clarifying question through MCP sampling (false by default).
+    pub clarify_ambiguous: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Mem0 {
+    pub server_addr: SocketAddr,
+    /// Unix socket to serve on instead of `server_addr`. A socket left by an earlier run
+    /// is replaced.
+    pub socket_path: Option<PathBuf>,
+}
+
+/// The admin_v1 gRPC service for operational tasks, on a port of its own.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Admin {
+    pub server_addr: SocketAddr,
+    /// Bearer token every admin call must pass in its `authorization` metadata. The
+    /// admin server refuses to start when it is empty.
+    pub token: String,
+}
+
+/// Liveness and readiness endpoints for orchestrators.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Health {
+    pub server_addr: SocketAddr,
+    /// How long each readiness check may take (2000 by default).
+    pub timeout_ms: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -66,14 +160,22 @@ pub struct Qdrant {
     pub key: String,
     pub collection_name: String,
     pub chunk_size: u16,
-    pub embedding_model_dimensions: u16,
+    /// Looked up from the embedding model when unset.
+    pub embedding_model_dimensions: Option<u16>,
+    /// Whether memory payloads are stored zstd-compressed, except the fields queries
+    /// read (false by default).
+    pub compress_payloads: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct PgVector {
     pub url: String,
-    pub embedding_model_dimensions: u16,
+    /// Looked up from the embedding model when unset.
+    pub embedding_model_dimensions: Option<u16>,
     pub collection_name: String,
+    /// Whether memory payloads are stored zstd-compressed, except the fields queries
+    /// read (false by default).
+    pub compress_payloads: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -84,20 +186,353 @@ pub enum VectorStore {
     PgVector(PgVector),
 }
 
+impl VectorStore {
+    pub fn collection_name(&self) -> &str {
+        match self {
+            VectorStore::Qdrant(qdrant) => &qdrant.collection_name,
+            VectorStore::PgVector(pgvector) => &pgvector.collection_name,
+        }
+    }
+
+    pub fn embedding_model_dimensions(&self) -> Option<u16> {
+        match self {
+            VectorStore::Qdrant(qdrant) => qdrant.embedding_model_dimensions,
+            VectorStore::PgVector(pgvector) => pgvector.embedding_model_dimensions,
+        }
+    }
+
+    pub fn with_embedding_model_dimensions(&self, dimensions: u16) -> Self {
+        let mut config = self.clone();
+        match &mut config {
+            VectorStore::Qdrant(qdrant) => qdrant.embedding_model_dimensions = Some(dimensions),
+            VectorStore::PgVector(pgvector) => {
+                pgvector.embedding_model_dimensions = Some(dimensions)
+            }
+        }
+        config
+    }
+
+    pub fn with_collection_name(&self, collection_name: impl Into<String>) -> Self {
+        let mut config = self.clone();
+        match &mut config {
+            VectorStore::Qdrant(qdrant) => qdrant.collection_name = collection_name.into(),
+            VectorStore::PgVector(pgvector) => pgvector.collection_name = collection_name.into(),
+        }
+        config
+    }
+}
+
+/// Live migration to another vector store: writes go to both stores while reads are
+/// served by one of them.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DualWrite {
+    /// The store being migrated to.
+    pub vector_store: VectorStore,
+    /// Whether reads are served by `vector_store` instead of `[vector_store]` (false by
+    /// default).
+    pub read_from_target: Option<bool>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct RerankingModel {
     pub provider: Provider,
     pub model: String,
+    pub fixtures: Option<Fixtures>,
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// US dollars per million tokens of query and documents, counted against `[budget]`
+    /// (free by default).
+    pub price_per_million_tokens: Option<f64>,
+}
+
+/// Provider responses recorded to, or replayed from, `directory` instead of answered
+/// live, so tests of the search pipeline are deterministic and need no network access.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Fixtures {
+    pub directory: PathBuf,
+    pub mode: FixtureMode,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FixtureMode {
+    /// Answers live and saves every response.
+    Record,
+    /// Answers from saved responses only, failing for requests never recorded.
+    Replay,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Annotation {
+    pub prompt: Option<String>,
+    pub prompt_path: Option<PathBuf>,
+    #[serde(default)]
+    pub kind_definitions: HashMap<String, String>,
+    pub storage_language: Option<String>,
+    /// Whether memories keep the content they were annotated from. Defaults to true.
+    pub keep_raw_content: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub enum Graph {
+    #[serde(rename = "in_memory")]
+    InMemory,
+}
+
+/// Fields hidden from callers per api key access level (`field = "strip" | "mask"`).
+/// Callers without an api key see everything.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Redaction {
+    #[serde(default)]
+    pub read: HashMap<String, String>,
+    #[serde(default)]
+    pub read_write: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ApiKeys {
+    pub database_url: String,
+}
+
+/// An RSS, Atom or Substack feed polled into the embed engine. With `distill`, new items
+/// also become memories of `user_id`/`agent_id`, tagged with `tag` (the feed's host by
+/// default).
+#[derive(Debug, Deserialize, Clone)]
+pub struct Feed {
+    pub url: String,
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub distill: bool,
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Feeds {
+    pub poll_interval_secs: u64,
+    #[serde(default)]
+    pub sources: Vec<Feed>,
+}
+
+/// How searches behave when parts of the pipeline are slow or down.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Search {
+    /// Return vector-ranked results, flagged as not reranked, instead of failing the
+    /// search when the reranker errors or times out.
+    #[serde(default)]
+    pub rerank_fallback: bool,
+    /// Give up on the reranker after this long. Unbounded when unset.
+    pub rerank_timeout_ms: Option<u64>,
+    /// Budget for a whole search. When it runs out, outstanding vector queries are
+    /// cancelled and the memories found so far are returned. Callers' own deadlines,
+    /// such as gRPC deadlines, can only shorten it.
+    pub timeout_ms: Option<u64>,
+    /// Sub-queries of a multi-search run against the vector store at once (8 by default).
+    pub concurrency: Option<usize>,
+    /// Memories fetched per sub-query (5 by default).
+    pub sub_query_limit: Option<u32>,
+    /// Most candidates a multi-search sends to the reranker. Unbounded when unset.
+    pub candidate_cap: Option<usize>,
+    /// Orders results by a weighted sum of scores instead of the reranker's order.
+    pub fusion: Option<ScoreFusion>,
+}
+
+/// Weights of the scores summed into a search result's final score. Every score lies
+/// in [0, 1]; unset weights are zero.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ScoreFusion {
+    #[serde(default)]
+    pub rerank: f32,
+    /// Cosine similarity between the query and the memory.
+    #[serde(default)]
+    pub vector: f32,
+    #[serde(default)]
+    pub salience: f32,
+    #[serde(default)]
+    pub certainty: f32,
+    /// Halves every `recency_half_life_days` since the memory's content was last written.
+    #[serde(default)]
+    pub recency: f32,
+    /// 30 days by default, also used in place of a value that is not positive.
+    pub recency_half_life_days: Option<f64>,
+}
+
+/// How agent feedback moves memory salience: `mark_used` raises it, and memories left
+/// unused for `decay_after_days` lose some on every decay run.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Salience {
+    /// Share of the remaining headroom to 1.0 gained per use (0.1 by default).
+    pub use_boost: Option<f32>,
+    /// Share of salience lost per decay run (0.05 by default).
+    pub decay_rate: Option<f32>,
+    /// 30 by default.
+    pub decay_after_days: Option<u64>,
+    /// How often `MemoryMachine::run_salience_decay` runs (daily by default).
+    pub decay_interval_secs: Option<u64>,
+}
+
+/// A local append-only file that create requests are written to before the stores
+/// see them, so memories can still be recorded while the vector store is down.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Journal {
+    pub path: String,
+    /// How often `MemoryMachine::run_journal_flush` replays the journal (every 30
+    /// seconds by default).
+    pub flush_interval_secs: Option<u64>,
+}
+
+/// Where saved views, named searches kept per context, are stored.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Views {
+    pub database_url: String,
+}
+
+/// How long deleted memories stay in the trash, restorable, before they are purged.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Trash {
+    /// 30 by default.
+    pub retention_days: Option<u64>,
+    /// How often `MemoryMachine::run_trash_purge` runs (hourly by default).
+    pub purge_interval_secs: Option<u64>,
+}
+
+/// Caps on the estimated cost of provider calls. Once a cap is reached, memories are
+/// tagged by keyword instead of annotated, conflict and relation detection and
+/// reranking are skipped, and optional language model operations are refused until the
+/// UTC day or month is over.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Budget {
+    /// US dollars per UTC day; uncapped when unset.
+    pub daily_usd: Option<f64>,
+    /// US dollars per UTC month; uncapped when unset.
+    pub monthly_usd: Option<f64>,
+    /// File the spend is kept in across restarts; kept in memory only when unset.
+    pub path: Option<PathBuf>,
+}
+
+/// How each user's memories are grouped into topics, labeled by the language model and
+/// stored on the memories, by `MemoryMachine::run_topic_clustering`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Topics {
+    /// Most topics per user (8 by default).
+    pub max_topics: Option<usize>,
+    /// Users with fewer active memories are not clustered (10 by default).
+    pub min_memories: Option<usize>,
+    /// Most memories clustered per user (1000 by default).
+    pub max_memories: Option<u32>,
+    /// How often `MemoryMachine::run_topic_clustering` runs (daily by default).
+    pub interval_secs: Option<u64>,
+}
+
+/// Periodic exports of changed memories to object storage, for analytics that should
+/// not query the vector store.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Export {
+    /// `s3://<bucket>`, `gs://<bucket>` or `file://<directory>`. Credentials are read
+    /// from the environment (`AWS_*` or `GOOGLE_*`).
+    pub bucket: String,
+    /// Key prefix the files and the export state are written under.
+    pub prefix: Option<String>,
+    /// "jsonl" (default) or "parquet".
+    pub format: Option<String>,
+    /// How often `MemoryMachine::run_export` runs (hourly by default).
+    pub interval_secs: Option<u64>,
+    /// Memories per file (1000 by default).
+    pub batch_size: Option<u32>,
+}
+
+/// Where raw content too large for vector store payloads is kept. Memories refer to it
+/// by uri.
+#[derive(Debug, Deserialize, Clone)]
+pub struct BlobStore {
+    /// `s3://<bucket>` or `file://<directory>`. Credentials are read from the
+    /// environment (`AWS_*`).
+    pub bucket: String,
+    /// Key prefix blobs are written under.
+    pub prefix: Option<String>,
+    /// Raw content longer than this many bytes goes to the blob store (64 KiB by
+    /// default); shorter content stays in the memory.
+    pub inline_limit: Option<usize>,
+}
+
+/// Checks memories before they are stored. Content flagged in a category is rejected
+/// or stored with the flag recorded in its provenance, per `actions`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Moderation {
+    /// Moderation model of the language model's provider (openai only), e.g.
+    /// "omni-moderation-latest". Only `rules` are checked when unset.
+    pub model: Option<String>,
+    /// `category = "reject" | "flag" | "allow"`. A category also matches the
+    /// subcategories of the model, e.g. "self-harm" covers "self-harm/intent".
+    #[serde(default)]
+    pub actions: HashMap<String, String>,
+    /// Action for flagged categories missing from `actions` ("flag" by default).
+    pub default_action: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<ModerationRule>,
+}
+
+/// Flags content matching `pattern`, a regular expression, in `category`, e.g.
+/// credentials or secrets the moderation model does not look for.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ModerationRule {
+    pub category: String,
+    pub pattern: String,
+}
+
+/// One field of a `[templates.<kind>]` template.
+#[derive(Debug, Deserialize, Clone)]
+pub struct TemplateField {
+    pub name: String,
+    #[serde(default)]
+    pub required: bool,
+    pub description: Option<String>,
+}
+
+/// Fields a structured memory of one kind carries. Replaces the built-in template of
+/// that kind; an empty `fields` list removes it.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Template {
+    #[serde(default)]
+    pub fields: Vec<TemplateField>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
     pub vector_store: VectorStore,
+    pub dual_write: Option<DualWrite>,
     pub embedding_model: EmbeddingModel,
     pub language_model: LanguageModel,
     pub reranking_model: RerankingModel,
+    #[serde(default)]
+    pub annotation: Annotation,
+    pub graph: Option<Graph>,
+    pub api_keys: Option<ApiKeys>,
+    #[serde(default)]
+    pub redaction: Redaction,
     pub mcp: Mcp,
     pub grpc: Grpc,
+    pub mem0: Option<Mem0>,
+    pub health: Option<Health>,
+    pub admin: Option<Admin>,
+    pub feeds: Option<Feeds>,
+    #[serde(default)]
+    pub search: Search,
+    #[serde(default)]
+    pub salience: Salience,
+    #[serde(default)]
+    pub trash: Trash,
+    #[serde(default)]
+    pub topics: Topics,
+    #[serde(default)]
+    pub budget: Budget,
+    pub journal: Option<Journal>,
+    pub views: Option<Views>,
+    pub export: Option<Export>,
+    pub moderation: Option<Moderation>,
+    pub blob_store: Option<BlobStore>,
+    /// Templates by memory kind name.
+    #[serde(default)]
+    pub templates: HashMap<String, Template>,
 }
 
 impl Default for AppConfig {
@@ -110,16 +545,26 @@ impl AppConfig {
     pub fn new() -> Self {
         let home = dirs::home_dir().expect("Could not determine home directory");
         let config_path = home.join(".config/enfinyte/enfinyte.toml");
-        let config_path = config_path
-            .to_str()
-            .expect("Config path contains invalid UTF-8");
+
+        Self::from_path(config_path).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let config_path = path.as_ref().to_str().ok_or_else(|| {
+            ConfigError::Message("Config path contains invalid UTF-8".to_string())
+        })?;
 
         Config::builder()
             .add_source(File::with_name(config_path))
-            .build()
-            .unwrap_or_else(|e| panic!("{e}"))
+            .build()?
+            .try_deserialize::<Self>()
+    }
+
+    pub fn from_toml(source: &str) -> Result<Self, ConfigError> {
+        Config::builder()
+            .add_source(File::from_str(source, FileFormat::Toml))
+            .build()?
             .try_deserialize::<Self>()
-            .unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
@@ -0,0 +1,142 @@
+use super::{JournalError, MemoryController, MemoryControllerError, TenancyError};
+use rustc_hash::FxHashMap;
+use thiserror::Error;
+use tracing::{info, warn};
+use umem_vector_store::VectorStoreError;
+
+const DRIFT_SCROLL_BATCH_SIZE: u32 = 256;
+
+#[derive(Debug, Error)]
+pub enum AdminError {
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("journal action failed with: {0}")]
+    JournalError(#[from] JournalError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+/// Counters an operator reads off a running instance.
+#[derive(Debug, Clone, Copy)]
+pub struct AdminMetrics {
+    /// Memories in the collection, including archived ones and ones in the trash.
+    pub memory_count: usize,
+    /// Create requests waiting in the journal; unset when `[journal]` is not configured.
+    pub pending_journal_entries: Option<usize>,
+}
+
+/// Which embedding models produced the stored vectors, against the one this controller
+/// embeds with now.
+#[derive(Debug, Clone)]
+pub struct EmbeddingDrift {
+    pub current_model: String,
+    pub total: usize,
+    /// Vectors from another model, or embedded before models were recorded.
+    pub stale: usize,
+    /// Vectors per model, most first; `None` counts the unrecorded ones.
+    pub models: Vec<(Option<String>, usize)>,
+}
+
+impl EmbeddingDrift {
+    /// Share of the stored vectors that `reindex --new-embedder` would replace.
+    pub fn stale_fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.stale as f64 / self.total as f64
+    }
+}
+
+impl MemoryController {
+    /// Drops every memory in the collection and recreates it empty. Global only.
+    pub async fn reset_collection(&self) -> Result<(), MemoryControllerError> {
+        Ok(self.reset_collection_impl().await?)
+    }
+
+    /// Migrates every stored payload to the current schema version, returning how many
+    /// were upgraded. Global only.
+    pub async fn reindex(&self) -> Result<usize, MemoryControllerError> {
+        Ok(self.reindex_impl().await?)
+    }
+
+    pub async fn metrics(&self) -> Result<AdminMetrics, MemoryControllerError> {
+        Ok(self.metrics_impl().await?)
+    }
+
+    /// Counts the stored vectors by the embedding model that produced them. Global only.
+    pub async fn embedding_drift(&self) -> Result<EmbeddingDrift, MemoryControllerError> {
+        Ok(self.embedding_drift_impl().await?)
+    }
+
+    async fn reset_collection_impl(&self) -> Result<(), AdminError> {
+        self.guard_global()?;
+        warn!("resetting the memory collection");
+        self.vector_store.reset().await?;
+        Ok(())
+    }
+
+    async fn reindex_impl(&self) -> Result<usize, AdminError> {
+        self.guard_global()?;
+        let upgraded = self.vector_store.reindex().await?;
+        info!("reindex upgraded {upgraded} payloads");
+        Ok(upgraded)
+    }
+
+    async fn metrics_impl(&self) -> Result<AdminMetrics, AdminError> {
+        self.guard_global()?;
+        let memory_count = self.vector_store.count_documents().await?;
+        let pending_journal_entries = match self.journal.as_ref() {
+            Some(journal) => Some(journal.pending().await?),
+            None => None,
+        };
+
+        Ok(AdminMetrics {
+            memory_count,
+            pending_journal_entries,
+        })
+    }
+
+    async fn embedding_drift_impl(&self) -> Result<EmbeddingDrift, AdminError> {
+        self.guard_global()?;
+        let current_model = self.embedding_model.model_name.clone();
+
+        let mut models: FxHashMap<Option<String>, usize> = FxHashMap::default();
+        let mut cursor = None;
+        loop {
+            let (memories, next) = self
+                .vector_store
+                .scroll(cursor, DRIFT_SCROLL_BATCH_SIZE)
+                .await?;
+            for memory in &memories {
+                *models
+                    .entry(memory.embedding_model().map(str::to_string))
+                    .or_default() += 1;
+            }
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        let total = models.values().sum();
+        let stale = total
+            - models
+                .get(&Some(current_model.clone()))
+                .copied()
+                .unwrap_or_default();
+        let mut models: Vec<_> = models.into_iter().collect();
+        models.sort_by(|(a_model, a), (b_model, b)| b.cmp(a).then_with(|| a_model.cmp(b_model)));
+
+        if stale > 0 {
+            warn!("{stale} of {total} stored vectors were not embedded with {current_model}");
+        }
+        Ok(EmbeddingDrift {
+            current_model,
+            total,
+            stale,
+            models,
+        })
+    }
+}
@@ -0,0 +1,14 @@
+use super::MemoryController;
+use umem_ai::SpendTotals;
+
+impl MemoryController {
+    /// Estimated spend on priced provider calls so far today and this month.
+    pub fn spend(&self) -> SpendTotals {
+        self.spend.totals()
+    }
+
+    /// Whether `[budget]` is used up, so optional model calls should be left out.
+    pub fn over_budget(&self) -> bool {
+        self.spend().exceeds(&self.budget)
+    }
+}
@@ -0,0 +1,166 @@
+use super::MemoryController;
+use chrono::Utc;
+use std::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+use tracing::warn;
+use umem_core::{Memory, MemoryId};
+use umem_vector_store::VectorStoreError;
+use uuid::Uuid;
+
+const INSERT_ATTEMPTS: u32 = 3;
+const INSERT_BACKOFF: Duration = Duration::from_millis(200);
+
+/// The writes one create call has made so far. When a later step fails they are undone
+/// newest first, so a failed call does not leave half-stored memories behind.
+#[derive(Debug, Default)]
+pub(crate) struct Compensation {
+    inserted: Vec<Uuid>,
+    /// Stored memories that were marked as contradicting one of `inserted`.
+    contradicted: Vec<Uuid>,
+    /// Memories whose relations were written to the graph.
+    linked: Vec<Uuid>,
+}
+
+impl Compensation {
+    pub(crate) fn inserted(&mut self, memories: &[Memory]) {
+        self.inserted
+            .extend(memories.iter().map(|memory| *memory.get_id()));
+    }
+
+    pub(crate) fn contradicted(&mut self, memory: &Memory) {
+        self.contradicted.push(*memory.get_id());
+    }
+
+    pub(crate) fn linked(&mut self, memory: &Memory) {
+        self.linked.push(*memory.get_id());
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.contradicted.is_empty() && self.linked.is_empty()
+    }
+}
+
+/// A `Compensation` that is undone if it is dropped before the call settles. A call is
+/// cancelled by dropping its future, e.g. when a gRPC client hangs up or its deadline
+/// passes, so without this a cancelled create could leave half-stored memories behind.
+pub(crate) struct CompensationGuard<'a> {
+    controller: &'a MemoryController,
+    compensation: Compensation,
+    settled: bool,
+}
+
+impl<'a> CompensationGuard<'a> {
+    pub(crate) fn new(controller: &'a MemoryController) -> Self {
+        Self {
+            controller,
+            compensation: Compensation::default(),
+            settled: false,
+        }
+    }
+
+    /// Keeps the writes: the call succeeded.
+    pub(crate) fn commit(mut self) {
+        self.settled = true;
+    }
+
+    /// Undoes the writes before returning the error the call failed with.
+    pub(crate) async fn rollback(mut self) {
+        self.settled = true;
+        let compensation = std::mem::take(&mut self.compensation);
+        self.controller.compensate(compensation).await;
+    }
+}
+
+impl Deref for CompensationGuard<'_> {
+    type Target = Compensation;
+
+    fn deref(&self) -> &Compensation {
+        &self.compensation
+    }
+}
+
+impl DerefMut for CompensationGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Compensation {
+        &mut self.compensation
+    }
+}
+
+impl Drop for CompensationGuard<'_> {
+    fn drop(&mut self) {
+        if self.settled || self.compensation.is_empty() {
+            return;
+        }
+        // NOTE: the cancelled future is gone, so the undo runs on its own task
+        warn!("create was cancelled part way, undoing its writes");
+        let controller = self.controller.clone();
+        let compensation = std::mem::take(&mut self.compensation);
+        tokio::spawn(async move { controller.compensate(compensation).await });
+    }
+}
+
+impl MemoryController {
+    /// Inserts are idempotent, so a failed attempt is retried with the same ids.
+    pub(crate) async fn insert_with_retry(
+        &self,
+        vectors: &[&[f32]],
+        payloads: &[&Memory],
+    ) -> Result<(), VectorStoreError> {
+        let mut attempt = 1;
+        loop {
+            match self.vector_store.insert(vectors, payloads).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < INSERT_ATTEMPTS => {
+                    warn!("insert attempt {attempt} failed, retrying: {}", e);
+                    tokio::time::sleep(INSERT_BACKOFF * attempt).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Undoes the writes in `compensation`. The caller is already returning the error
+    /// that caused this, so failures here are only logged and the remaining steps
+    /// still run.
+    pub(crate) async fn compensate(&self, compensation: Compensation) {
+        if let Some(graph) = self.graph.as_ref() {
+            for id in &compensation.linked {
+                if let Err(e) = graph.delete_memory(id).await {
+                    warn!("could not remove relations of memory {id}: {}", e);
+                }
+            }
+        }
+
+        for id in &compensation.contradicted {
+            if let Err(e) = self.unmark_contradictions(id, &compensation.inserted).await {
+                warn!("could not undo contradictions on memory {id}: {}", e);
+            }
+        }
+
+        for id in &compensation.inserted {
+            if let Err(e) = self.vector_store.delete(&MemoryId::from(*id)).await {
+                warn!("could not remove memory {id}: {}", e);
+            }
+        }
+    }
+
+    async fn unmark_contradictions(
+        &self,
+        id: &Uuid,
+        others: &[Uuid],
+    ) -> Result<(), VectorStoreError> {
+        let id = MemoryId::from(*id);
+        let mut memory = self.vector_store.get(&id).await?;
+        let now = Utc::now();
+        let mut changed = false;
+        for other in others {
+            changed |= memory.unmark_contradiction(other, now);
+        }
+        if changed {
+            self.vector_store.update(&id, None, Some(&memory)).await?;
+        }
+        Ok(())
+    }
+}
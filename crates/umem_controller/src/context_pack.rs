@@ -0,0 +1,188 @@
+use super::{search_memory::take_ranked, MemoryController, MemoryControllerError, TenancyError};
+use chrono::DateTime;
+use rustc_hash::FxHashSet;
+use serde::Serialize;
+use std::sync::Arc;
+use thiserror::Error;
+use typed_builder::TypedBuilder;
+use umem_ai::{
+    embed::{embed, EmbeddingRequest},
+    rerank, EmbeddingModel, EmbeddingModelError, RerankRequest, RerankRequestBuilderError,
+    RerankingModelError,
+};
+use umem_auth::Access;
+use umem_core::{Memory, MemoryContext, Query};
+use umem_vector_store::VectorStoreError;
+
+const CONTEXT_SEARCH_LIMIT: u32 = 50;
+const CONTEXT_RERANK_TOP_K: usize = 20;
+
+#[derive(Debug, Error)]
+pub enum ContextPackError {
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("embedding action failed with: {0}")]
+    EmbeddingModelError(#[from] EmbeddingModelError),
+
+    #[error("rerank action failed with: {0}")]
+    RerankingModelError(#[from] RerankingModelError),
+
+    #[error("rerank builder action failed with: {0}")]
+    RerankingModelBuilderError(#[from] RerankRequestBuilderError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum ContextPolicy {
+    /// Keep the reranker order, most relevant first.
+    #[default]
+    Relevance,
+    /// Newest memories first.
+    Recency,
+    /// Highest certainty x salience first.
+    Importance,
+}
+
+#[derive(TypedBuilder, Default)]
+pub struct BuildContextOptions {
+    #[builder(default)]
+    pub policy: ContextPolicy,
+    #[builder(default = None)]
+    pub embedding_model: Option<Arc<EmbeddingModel>>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ContextPack {
+    pub text: String,
+    pub tokens: usize,
+    pub memories: Vec<Memory>,
+}
+
+impl ContextPack {
+    /// Packs `memories` in order into `token_budget` tokens (cl100k), each costing the
+    /// tokens of the text `render` writes for it.
+    pub fn fit(
+        memories: Vec<Memory>,
+        token_budget: usize,
+        render: impl Fn(&Memory) -> String,
+    ) -> Self {
+        let tokenizer = tiktoken_rs::cl100k_base_singleton();
+        let mut pack = ContextPack::default();
+        for memory in memories {
+            let line = render(&memory);
+            let tokens = tokenizer.encode_ordinary(&line).len();
+
+            // NOTE: skip rather than stop so shorter memories can still fill the budget
+            if pack.tokens + tokens > token_budget {
+                continue;
+            }
+
+            pack.text.push_str(&line);
+            pack.tokens += tokens;
+            pack.memories.push(memory);
+        }
+
+        pack
+    }
+}
+
+fn format_memory(memory: &Memory) -> String {
+    let date = DateTime::from_timestamp(memory.temporal().created_at(), 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let conflict = if memory.contradicts().is_empty() {
+        ""
+    } else {
+        " (conflicts with another stored memory)"
+    };
+
+    format!(
+        "- [{:?}, {date}] {}{conflict}\n",
+        memory.kind(),
+        memory.get_summary().trim()
+    )
+}
+
+impl MemoryController {
+    /// Searches, reranks and deduplicates memories, orders them by the given policy and
+    /// formats as many as fit into `token_budget` tokens (cl100k) as a prompt-ready list.
+    pub async fn build_context(
+        &self,
+        context: MemoryContext,
+        query: String,
+        token_budget: usize,
+        options: Option<BuildContextOptions>,
+    ) -> Result<ContextPack, MemoryControllerError> {
+        Ok(self
+            .build_context_impl(context, query, token_budget, options)
+            .await?)
+    }
+
+    async fn build_context_impl(
+        &self,
+        context: MemoryContext,
+        query: String,
+        token_budget: usize,
+        options: Option<BuildContextOptions>,
+    ) -> Result<ContextPack, ContextPackError> {
+        self.guard(&context, Access::Read)?;
+        let options = options.unwrap_or_default();
+        let embedding_model = options
+            .embedding_model
+            .unwrap_or_else(|| Arc::clone(&self.embedding_model));
+
+        let request = EmbeddingRequest::builder()
+            .model(embedding_model)
+            .input(vec![query.clone()])
+            .build();
+        let embedding_response = embed(request).await?;
+
+        let vector_query = Query::builder()
+            .vector(embedding_response.embeddings[0].clone())
+            .context(context)
+            .limit(CONTEXT_SEARCH_LIMIT)
+            .build();
+        let candidates = self.vector_store.search_scored(vector_query).await?;
+
+        if candidates.is_empty() || token_budget == 0 {
+            return Ok(ContextPack::default());
+        }
+
+        let documents: Vec<String> = candidates
+            .iter()
+            .map(|(memory, _)| memory.get_summary().clone())
+            .collect();
+        let request = RerankRequest::builder()
+            .model(Arc::clone(&self.reranking_model))
+            .documents(documents)
+            .query(query)
+            .top_k(CONTEXT_RERANK_TOP_K)
+            .build()?;
+        let rerank_response = rerank(request).await?;
+
+        let rankings = rerank_response
+            .rankings
+            .iter()
+            .map(|row| (row.original_index, row.score))
+            .collect();
+        let mut seen = FxHashSet::default();
+        let mut memories: Vec<Memory> = take_ranked(candidates, rankings)
+            .into_iter()
+            .map(|candidate| candidate.memory)
+            .filter(|memory| seen.insert(memory.get_summary().trim().to_lowercase()))
+            .collect();
+
+        match options.policy {
+            ContextPolicy::Relevance => {}
+            ContextPolicy::Recency => {
+                memories.sort_by_key(|memory| std::cmp::Reverse(memory.temporal().created_at()))
+            }
+            ContextPolicy::Importance => memories.sort_by(|a, b| b.score().total_cmp(&a.score())),
+        }
+
+        Ok(ContextPack::fit(memories, token_budget, format_memory))
+    }
+}
@@ -1,20 +1,36 @@
-use super::{MemoryController, MemoryControllerError};
+use super::{
+    Compensation, CompensationGuard, MemoryController, MemoryControllerError, MemoryTemplateError,
+    ModerationError, TenancyError, DUE_AT_FIELD,
+};
 use chrono::Utc;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
 use thiserror::Error;
+use tracing::{info, warn};
 use typed_builder::TypedBuilder;
 use umem_ai::{
     embed::{embed, EmbeddingRequest},
-    AIProviderError, EmbeddingModel, LanguageModel, ResponseGeneratorError,
+    AIProviderError, EmbeddingModel, GenerateObjectRequest, LanguageModel, ResponseGeneratorError,
+};
+use umem_annotations::{
+    AmbiguityCheck, Annotated, Annotation, AnnotationError, AnnotationPrompt, ConflictDetection,
+    LLMAnnotations, RelationExtraction, Translator,
 };
-use umem_annotations::{Annotation, AnnotationError, LLMAnnotated};
+use umem_auth::Access;
+use umem_blob::BlobStoreError;
 use umem_core::{
-    LifecycleState, Memory, MemoryContentError, MemoryContext, MemoryContextError, MemoryError,
+    credence::Credence, scrub_secrets, LifecycleState, Memory, MemoryContent, MemoryContentError,
+    MemoryContext, MemoryContextError, MemoryError, MemoryKind, MemoryLink, MemorySchedule,
+    MemoryScheduleError, MemorySignals, MemoryVisibility, Provenance, Query, RawContent,
     TemporalMetadata,
 };
+use umem_graph::{GraphError, Relation};
 use umem_vector_store::VectorStoreError;
 use uuid::Uuid;
 
+const EXISTING_MEMORIES_LIMIT: u32 = 50;
+const CONFLICT_CANDIDATES_LIMIT: u32 = 5;
+
 #[derive(Debug, Error)]
 pub enum CreateMemoryError {
     #[error("memory request --build-- failed with: {0}")]
@@ -28,6 +44,24 @@ pub enum CreateMemoryError {
 
     #[error("response generator action failed with: {0}")]
     ResponseGeneratorError(#[from] ResponseGeneratorError),
+
+    #[error("relation or conflict detection failed with: {0}")]
+    AnnotationError(#[from] AnnotationError),
+
+    #[error("graph action failed with: {0}")]
+    GraphError(#[from] GraphError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+
+    #[error("memory does not match its template: {0}")]
+    MemoryTemplateError(#[from] MemoryTemplateError),
+
+    #[error("moderation failed with: {0}")]
+    ModerationError(#[from] ModerationError),
+
+    #[error("blob store action failed with: {0}")]
+    BlobStoreError(#[from] BlobStoreError),
 }
 
 #[derive(Debug, Error)]
@@ -49,9 +83,12 @@ pub enum CreateMemoryRequestError {
 
     #[error("memory context errored with: {0}")]
     MemoryContextError(#[from] MemoryContextError),
+
+    #[error("memory schedule errored with: {0}")]
+    MemoryScheduleError(#[from] MemoryScheduleError),
 }
 
-#[derive(TypedBuilder)]
+#[derive(TypedBuilder, Clone, Serialize, Deserialize)]
 pub struct CreateMemoryRequest {
     #[builder(default = None)]
     user_id: Option<String>,
@@ -59,7 +96,33 @@ pub struct CreateMemoryRequest {
     agent_id: Option<String>,
     #[builder(default = None)]
     run_id: Option<String>,
+    /// Secrets are replaced with placeholders as soon as it is set, so they reach
+    /// neither the language model nor the stores.
+    #[builder(setter(transform = |raw_content: String| scrubbed(raw_content)))]
     raw_content: String,
+    /// Document chunk ids recorded on every memory built from this request.
+    #[builder(default)]
+    #[serde(default)]
+    sources: Vec<String>,
+    /// Tags added to every memory built from this request, next to the annotated ones.
+    #[builder(default)]
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Whether the memories built from this request may be read by the user's other
+    /// agents.
+    #[builder(default)]
+    #[serde(default)]
+    visibility: MemoryVisibility,
+}
+
+/// `text` with its secrets redacted.
+fn scrubbed(text: String) -> String {
+    let scrubbed = scrub_secrets(&text);
+    if scrubbed.secrets.is_empty() {
+        return text;
+    }
+    info!("redacted {} secrets before storing", scrubbed.secrets.len());
+    scrubbed.text
 }
 
 impl CreateMemoryRequest {
@@ -78,44 +141,165 @@ impl CreateMemoryRequest {
     pub async fn build(
         self,
         model: Arc<LanguageModel>,
-    ) -> Result<Memory, CreateMemoryRequestError> {
+        prompt: &AnnotationPrompt,
+        existing_memories: &[String],
+        translator: Option<Translator>,
+    ) -> Result<Vec<Memory>, CreateMemoryRequestError> {
         self.validate()?;
-        let annotations = self.annotations(model).await?;
-
-        Ok(Memory::builder()
-            .id(Uuid::new_v4())
-            .content(annotations.content)
-            .context(self.context()?)
-            .kind(annotations.kind)
-            // .signals(annotations.signals)
-            // .provenance(annotations.provenance)
-            .lifecycle(LifecycleState::Active)
-            .temporal(TemporalMetadata::new(Utc::now()))
-            .build())
+        let annotations = self
+            .annotations(Arc::clone(&model), prompt, existing_memories)
+            .await?;
+        self.build_from(annotations, model, translator).await
     }
 
-    fn context(&self) -> Result<MemoryContext, MemoryContextError> {
-        if let Some(ref user_id) = self.user_id {
-            return MemoryContext::for_user(user_id);
-        }
+    /// Builds memories from annotations made for this request elsewhere, e.g. by a
+    /// batch.
+    pub async fn build_from(
+        self,
+        mut annotations: Vec<Annotated>,
+        model: Arc<LanguageModel>,
+        translator: Option<Translator>,
+    ) -> Result<Vec<Memory>, CreateMemoryRequestError> {
+        self.validate()?;
+        let context = self.context()?;
 
-        if let Some(ref agent_id) = self.agent_id {
-            return MemoryContext::for_agent(agent_id);
+        if let Some(translator) = translator {
+            let mut translated = Vec::with_capacity(annotations.len());
+            for annotated in annotations {
+                translated.push(translator.translate(annotated, Arc::clone(&model)).await?);
+            }
+            annotations = translated;
         }
+        let now = Utc::now();
 
-        if let Some(ref run_id) = self.run_id {
-            return MemoryContext::for_run(run_id);
-        }
+        annotations
+            .into_iter()
+            .map(|annotated| {
+                let mut content = annotated.content;
+                for tag in &self.tags {
+                    match content.add_tag(tag.as_str()) {
+                        Ok(()) | Err(MemoryContentError::DuplicateTag(_)) => {}
+                        Err(e) => return Err(e.into()),
+                    }
+                }
 
-        // NOTE: unreachable because validated before
-        unreachable!()
+                Ok(Memory::builder()
+                    .id(Uuid::new_v4())
+                    .content(content)
+                    .context(context.clone())
+                    .kind(annotated.kind)
+                    .signals(annotated.signals)
+                    .provenance(annotated.provenance)
+                    .language(annotated.language)
+                    .schedule(annotated.schedule)
+                    .sources(self.sources.clone())
+                    .visibility(self.visibility)
+                    .lifecycle(LifecycleState::Active)
+                    .temporal(TemporalMetadata::new(now))
+                    .build()
+                    .finish()?)
+            })
+            .collect()
+    }
+
+    pub(crate) fn context(&self) -> Result<MemoryContext, MemoryContextError> {
+        MemoryContext::new(
+            self.user_id.clone(),
+            self.agent_id.clone(),
+            self.run_id.clone(),
+        )
     }
 
     async fn annotations(
         &self,
         model: Arc<LanguageModel>,
-    ) -> Result<LLMAnnotated, CreateMemoryRequestError> {
-        Ok(Annotation::generate(&self.raw_content, model).await?)
+        prompt: &AnnotationPrompt,
+        existing_memories: &[String],
+    ) -> Result<Vec<Annotated>, CreateMemoryRequestError> {
+        Ok(Annotation::generate(&self.raw_content, model, prompt, existing_memories).await?)
+    }
+}
+
+#[derive(TypedBuilder)]
+pub struct CreateStructuredMemoryRequest {
+    #[builder(default = None)]
+    user_id: Option<String>,
+    #[builder(default = None)]
+    agent_id: Option<String>,
+    #[builder(default = None)]
+    run_id: Option<String>,
+    summary: String,
+    #[builder(default)]
+    tags: Vec<String>,
+    #[builder(default)]
+    kind: MemoryKind,
+    #[builder(default = 1.0)]
+    certainty: f32,
+    #[builder(default = 1.0)]
+    salience: f32,
+    #[builder(default = Provenance::direct_user())]
+    provenance: Provenance,
+    #[builder(default = None)]
+    schedule: Option<MemorySchedule>,
+    /// Values for the fields of the kind's template. `due_at` sets the schedule when
+    /// `schedule` is not given.
+    #[builder(default)]
+    fields: BTreeMap<String, String>,
+    #[builder(default)]
+    visibility: MemoryVisibility,
+}
+
+impl CreateStructuredMemoryRequest {
+    pub fn validate(&self) -> Result<(), CreateMemoryRequestError> {
+        if self.user_id.is_none() && self.agent_id.is_none() && self.run_id.is_none() {
+            return Err(CreateMemoryRequestError::MissingContext);
+        }
+
+        if self.summary.trim().is_empty() {
+            return Err(CreateMemoryRequestError::MissingContent);
+        }
+
+        Ok(())
+    }
+
+    pub fn build(mut self) -> Result<Memory, CreateMemoryRequestError> {
+        self.validate()?;
+
+        let due_at = self.fields.remove(DUE_AT_FIELD);
+        for value in self.fields.values_mut() {
+            *value = scrubbed(std::mem::take(value));
+        }
+        let schedule = match (self.schedule, due_at) {
+            (Some(schedule), _) => Some(schedule),
+            (None, Some(due_at)) => Some(MemorySchedule::parse(&due_at, None)?),
+            (None, None) => None,
+        };
+
+        let signals = MemorySignals::new(
+            Credence::new(self.certainty).map_err(MemoryError::from)?,
+            Credence::new(self.salience).map_err(MemoryError::from)?,
+        )
+        .map_err(MemoryError::from)?;
+
+        let memory = Memory::builder()
+            .id(Uuid::new_v4())
+            .content(MemoryContent::new(scrubbed(self.summary), self.tags)?)
+            .context(MemoryContext::new(
+                self.user_id,
+                self.agent_id,
+                self.run_id,
+            )?)
+            .kind(self.kind)
+            .signals(signals)
+            .provenance(self.provenance)
+            .schedule(schedule)
+            .fields(self.fields)
+            .visibility(self.visibility)
+            .lifecycle(LifecycleState::Active)
+            .temporal(TemporalMetadata::new(Utc::now()))
+            .build()
+            .finish()?;
+        Ok(memory)
     }
 }
 
@@ -125,35 +309,367 @@ pub struct CreateMemoryOptions {
     pub embedding_model: Option<Arc<EmbeddingModel>>,
     #[builder(default = None)]
     pub language_model: Option<Arc<LanguageModel>>,
+    /// Model of the configured embedding provider to use instead of the default one,
+    /// e.g. for a bulk import. Ignored when `embedding_model` is set.
+    #[builder(default = None, setter(into))]
+    pub embedding_model_name: Option<String>,
+    /// Model of the configured language provider to annotate with, e.g. a stronger
+    /// one for important memories. Ignored when `language_model` is set.
+    #[builder(default = None, setter(into))]
+    pub language_model_name: Option<String>,
 }
 
 impl MemoryController {
+    pub(crate) fn models_for(
+        &self,
+        options: Option<CreateMemoryOptions>,
+    ) -> (Arc<EmbeddingModel>, Arc<LanguageModel>) {
+        let options = options.unwrap_or_default();
+        let embedding_model = match (options.embedding_model, options.embedding_model_name) {
+            (Some(embedding_model), _) => embedding_model,
+            (None, Some(model_name)) => Arc::new(self.embedding_model.with_model_name(model_name)),
+            (None, None) => Arc::clone(&self.embedding_model),
+        };
+        let language_model = match (options.language_model, options.language_model_name) {
+            (Some(language_model), _) => language_model,
+            (None, Some(model_name)) => Arc::new(self.language_model.with_model_name(model_name)),
+            (None, None) => Arc::clone(&self.language_model),
+        };
+        (embedding_model, language_model)
+    }
+
     pub async fn create(
         &self,
         request: CreateMemoryRequest,
         options: Option<CreateMemoryOptions>,
-    ) -> Result<Memory, MemoryControllerError> {
+    ) -> Result<Vec<Memory>, MemoryControllerError> {
         Ok(self.create_impl(request, options).await?)
     }
 
     async fn create_impl(
         &self,
         request: CreateMemoryRequest,
-        _options: Option<CreateMemoryOptions>,
-    ) -> Result<Memory, CreateMemoryError> {
-        let vector_store = Arc::clone(&self.vector_store);
-        let embedding_model = Arc::clone(&self.embedding_model);
-        let language_model = Arc::clone(&self.language_model);
+        options: Option<CreateMemoryOptions>,
+    ) -> Result<Vec<Memory>, CreateMemoryError> {
+        request.validate()?;
+        self.guard(
+            &request.context().map_err(CreateMemoryRequestError::from)?,
+            Access::ReadWrite,
+        )?;
+        let (embedding_model, language_model) = self.models_for(options);
+
+        let raw_content = self.raw_content_of(&request).await?;
+        let mut memories = if self.over_budget() {
+            warn!("spend budget is used up, tagging the memory by keyword");
+            let annotated = Annotation::fallback(&request.raw_content)?;
+            request
+                .build_from(vec![annotated], Arc::clone(&language_model), None)
+                .await?
+        } else {
+            let existing_memories = if self.annotation_prompt.uses_existing_memories() {
+                self.existing_summaries(&request).await?
+            } else {
+                vec![]
+            };
+            request
+                .build(
+                    Arc::clone(&language_model),
+                    &self.annotation_prompt,
+                    &existing_memories,
+                    self.translator,
+                )
+                .await?
+        };
+        for memory in &mut memories {
+            memory.set_raw_content(raw_content.clone());
+        }
+
+        self.store_and_link(memories, embedding_model, language_model)
+            .await
+    }
+
+    /// A question to ask before storing `raw_content` when it states no clear fact or
+    /// contradicts itself. Not checked once the spend budget is used up.
+    pub async fn clarifying_question(
+        &self,
+        raw_content: &str,
+    ) -> Result<Option<String>, MemoryControllerError> {
+        if self.over_budget() {
+            return Ok(None);
+        }
+        Ok(
+            AmbiguityCheck::generate(raw_content, Arc::clone(&self.language_model))
+                .await
+                .map_err(CreateMemoryError::from)?,
+        )
+    }
+
+    /// The annotation call `create` would make for `request`, to be answered through a
+    /// batch API and handed to `create_annotated`.
+    pub(crate) async fn annotation_request(
+        &self,
+        request: &CreateMemoryRequest,
+        language_model: Arc<LanguageModel>,
+    ) -> Result<GenerateObjectRequest<LLMAnnotations>, CreateMemoryError> {
+        request.validate()?;
+        self.guard(
+            &request.context().map_err(CreateMemoryRequestError::from)?,
+            Access::ReadWrite,
+        )?;
+        let existing_memories = if self.annotation_prompt.uses_existing_memories() {
+            self.existing_summaries(request).await?
+        } else {
+            vec![]
+        };
+
+        Ok(Annotation::request(
+            request.raw_content.clone(),
+            language_model,
+            &self.annotation_prompt,
+            &existing_memories,
+        )?)
+    }
+
+    /// Finishes a `create` whose annotation call was answered elsewhere.
+    pub(crate) async fn create_annotated(
+        &self,
+        request: CreateMemoryRequest,
+        annotations: LLMAnnotations,
+        options: Option<CreateMemoryOptions>,
+    ) -> Result<Vec<Memory>, CreateMemoryError> {
+        request.validate()?;
+        self.guard(
+            &request.context().map_err(CreateMemoryRequestError::from)?,
+            Access::ReadWrite,
+        )?;
+        let (embedding_model, language_model) = self.models_for(options);
+
+        let existing_memories = if self.annotation_prompt.uses_existing_memories() {
+            self.existing_summaries(&request).await?
+        } else {
+            vec![]
+        };
+        let annotations = Annotation::from_output(
+            annotations,
+            request.raw_content.clone(),
+            Arc::clone(&language_model),
+            &self.annotation_prompt,
+            &existing_memories,
+        )
+        .await?;
 
-        let memory = request.build(language_model).await?;
+        let raw_content = self.raw_content_of(&request).await?;
+        let mut memories = request
+            .build_from(annotations, Arc::clone(&language_model), self.translator)
+            .await?;
+        for memory in &mut memories {
+            memory.set_raw_content(raw_content.clone());
+        }
+
+        self.store_and_link(memories, embedding_model, language_model)
+            .await
+    }
+
+    /// What the memories built from `request` keep of the content they were annotated
+    /// from.
+    /// Content longer than the blob store's inline limit is put there, keeping vector
+    /// store payloads small.
+    async fn raw_content_of(
+        &self,
+        request: &CreateMemoryRequest,
+    ) -> Result<Option<RawContent>, CreateMemoryError> {
+        if !self.keep_raw_content {
+            return Ok(None);
+        }
+
+        match self.blob_store.as_ref() {
+            Some(blob_store) if request.raw_content.len() > blob_store.inline_limit() => {
+                let uri = blob_store
+                    .put(request.raw_content.as_bytes().to_vec())
+                    .await?;
+                Ok(Some(RawContent::Blob(uri)))
+            }
+            _ => Ok(Some(RawContent::new(&request.raw_content))),
+        }
+    }
+
+    /// Stores memories built by `create` and writes their relations to the graph.
+    async fn store_and_link(
+        &self,
+        memories: Vec<Memory>,
+        embedding_model: Arc<EmbeddingModel>,
+        language_model: Arc<LanguageModel>,
+    ) -> Result<Vec<Memory>, CreateMemoryError> {
+        let mut compensation = CompensationGuard::new(self);
+        let mut memories = match self
+            .store_steps(
+                memories,
+                embedding_model,
+                Arc::clone(&language_model),
+                &mut compensation,
+            )
+            .await
+        {
+            Ok(memories) => memories,
+            Err(e) => {
+                compensation.rollback().await;
+                return Err(e);
+            }
+        };
+        if let Err(e) = self
+            .link_relations(&mut memories, language_model, &mut compensation)
+            .await
+        {
+            compensation.rollback().await;
+            return Err(e);
+        }
+        compensation.commit();
+        Ok(memories)
+    }
+
+    /// Extracts the relations of relational memories, keeps them on the memories as
+    /// links, from which `rebuild_graph` restores the graph, and writes them to the graph.
+    async fn link_relations(
+        &self,
+        memories: &mut [Memory],
+        model: Arc<LanguageModel>,
+        compensation: &mut Compensation,
+    ) -> Result<(), CreateMemoryError> {
+        let Some(graph) = self.graph.as_ref() else {
+            return Ok(());
+        };
+        if self.over_budget() {
+            return Ok(());
+        }
+
+        let mut relations = Vec::new();
+        for memory in memories
+            .iter_mut()
+            .filter(|memory| matches!(memory.kind(), MemoryKind::Relational))
+        {
+            let links: Vec<MemoryLink> =
+                RelationExtraction::generate(memory.get_summary(), Arc::clone(&model))
+                    .await?
+                    .into_iter()
+                    .map(|relation| MemoryLink {
+                        subject: relation.subject,
+                        predicate: relation.predicate,
+                        object: relation.object,
+                    })
+                    .collect();
+            if links.is_empty() {
+                continue;
+            }
+            for link in &links {
+                relations.push(Relation::new(
+                    memory.context(),
+                    link.subject.clone(),
+                    link.predicate.clone(),
+                    link.object.clone(),
+                    *memory.get_id(),
+                )?);
+            }
+
+            memory.set_links(links, Utc::now());
+            self.vector_store
+                .update(&memory.memory_id(), None, Some(&*memory))
+                .await?;
+            compensation.linked(memory);
+        }
+
+        if !relations.is_empty() {
+            graph.insert(&relations).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn create_structured(
+        &self,
+        requests: Vec<CreateStructuredMemoryRequest>,
+        options: Option<CreateMemoryOptions>,
+    ) -> Result<Vec<Memory>, MemoryControllerError> {
+        Ok(self.create_structured_impl(requests, options).await?)
+    }
+
+    async fn create_structured_impl(
+        &self,
+        requests: Vec<CreateStructuredMemoryRequest>,
+        options: Option<CreateMemoryOptions>,
+    ) -> Result<Vec<Memory>, CreateMemoryError> {
+        let (embedding_model, language_model) = self.models_for(options);
+
+        let memories = requests
+            .into_iter()
+            .map(CreateStructuredMemoryRequest::build)
+            .collect::<Result<Vec<Memory>, CreateMemoryRequestError>>()?;
+        for memory in &memories {
+            self.templates.validate(memory)?;
+        }
+
+        self.store(memories, embedding_model, language_model).await
+    }
+
+    /// Embeds and inserts `memories` and marks the stored memories they contradict.
+    /// Nothing is left behind when a step fails.
+    pub(crate) async fn store(
+        &self,
+        memories: Vec<Memory>,
+        embedding_model: Arc<EmbeddingModel>,
+        language_model: Arc<LanguageModel>,
+    ) -> Result<Vec<Memory>, CreateMemoryError> {
+        let mut compensation = CompensationGuard::new(self);
+        let result = self
+            .store_steps(memories, embedding_model, language_model, &mut compensation)
+            .await;
+        match result {
+            Ok(_) => compensation.commit(),
+            Err(_) => compensation.rollback().await,
+        }
+        result
+    }
+
+    async fn store_steps(
+        &self,
+        mut memories: Vec<Memory>,
+        embedding_model: Arc<EmbeddingModel>,
+        language_model: Arc<LanguageModel>,
+        compensation: &mut Compensation,
+    ) -> Result<Vec<Memory>, CreateMemoryError> {
+        if memories.is_empty() {
+            return Ok(memories);
+        }
+        for memory in &memories {
+            self.guard(memory.context(), Access::ReadWrite)?;
+        }
+        self.moderate(&mut memories).await?;
+        for memory in &mut memories {
+            memory.set_embedding_model(embedding_model.model_name.as_str());
+        }
 
         let request = EmbeddingRequest::builder()
             .model(embedding_model)
-            .input(vec![memory.get_summary().to_owned()])
+            .input(
+                memories
+                    .iter()
+                    .map(|memory| memory.get_summary().to_owned())
+                    .collect(),
+            )
             .build();
 
         let embedding_response = embed(request).await?;
 
+        let contradicted = if self.over_budget() {
+            vec![]
+        } else {
+            self.detect_conflicts(
+                &mut memories,
+                &embedding_response.embeddings,
+                language_model,
+            )
+            .await?
+        };
+
         //NOTE: change this later, just didin't want to fight with the drilled types
         let slices: Vec<&[f32]> = embedding_response
             .embeddings
@@ -161,8 +677,86 @@ impl MemoryController {
             .map(|inner| inner.as_slice())
             .collect();
         let slice_of_slices: &[&[f32]] = &slices;
+        let payloads: Vec<&Memory> = memories.iter().collect();
 
-        vector_store.insert(slice_of_slices, &[&memory]).await?;
-        Ok(memory)
+        compensation.inserted(&memories);
+        self.insert_with_retry(slice_of_slices, &payloads).await?;
+
+        for memory in contradicted {
+            self.vector_store
+                .update(&memory.memory_id(), None, Some(&memory))
+                .await?;
+            compensation.contradicted(&memory);
+        }
+
+        Ok(memories)
+    }
+
+    /// Compares each new memory against its nearest stored neighbours and marks
+    /// both sides of every contradiction. Returns the stored memories that were
+    /// marked so the caller can persist them.
+    async fn detect_conflicts(
+        &self,
+        memories: &mut [Memory],
+        embeddings: &[Vec<f32>],
+        model: Arc<LanguageModel>,
+    ) -> Result<Vec<Memory>, CreateMemoryError> {
+        let mut contradicted: Vec<Memory> = Vec::new();
+
+        for (memory, vector) in memories.iter_mut().zip(embeddings) {
+            let query = Query::builder()
+                .vector(vector.clone())
+                .context(memory.context().clone())
+                .limit(CONFLICT_CANDIDATES_LIMIT)
+                .build();
+
+            let mut candidates = self.vector_store.search(query).await?;
+            let summaries: Vec<String> = candidates
+                .iter()
+                .map(|candidate| candidate.get_summary().to_owned())
+                .collect();
+
+            for index in
+                ConflictDetection::generate(memory.get_summary(), &summaries, Arc::clone(&model))
+                    .await?
+            {
+                let now = Utc::now();
+                let candidate = std::mem::take(&mut candidates[index]);
+                memory.mark_contradiction(*candidate.get_id(), now);
+
+                match contradicted
+                    .iter_mut()
+                    .find(|existing| existing.get_id() == candidate.get_id())
+                {
+                    Some(existing) => existing.mark_contradiction(*memory.get_id(), now),
+                    None => {
+                        let mut candidate = candidate;
+                        candidate.mark_contradiction(*memory.get_id(), now);
+                        contradicted.push(candidate);
+                    }
+                }
+            }
+        }
+
+        Ok(contradicted)
+    }
+
+    async fn existing_summaries(
+        &self,
+        request: &CreateMemoryRequest,
+    ) -> Result<Vec<String>, CreateMemoryError> {
+        request.validate()?;
+        let query = Query::builder()
+            .context(request.context().map_err(CreateMemoryRequestError::from)?)
+            .limit(EXISTING_MEMORIES_LIMIT)
+            .build();
+
+        Ok(self
+            .vector_store
+            .list(query)
+            .await?
+            .iter()
+            .map(|memory| memory.get_summary().to_owned())
+            .collect())
     }
 }
@@ -1,22 +1,160 @@
 use std::sync::Arc;
 
-use super::{MemoryController, MemoryControllerError};
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use chrono::{DateTime, Utc};
 use thiserror::Error;
+use tracing::info;
+use umem_auth::Access;
+use umem_core::{Memory, MemoryError, MemoryId};
+use umem_graph::GraphError;
 use umem_vector_store::VectorStoreError;
 
+const DEFAULT_RETENTION_DAYS: u64 = 30;
+const PURGE_BATCH_SIZE: u32 = 256;
+
 #[derive(Debug, Error)]
 pub enum DeleteMemoryError {
     #[error("vector store action failed with: {0}")]
     VectorStoreError(#[from] VectorStoreError),
+
+    #[error("graph action failed with: {0}")]
+    GraphError(#[from] GraphError),
+
+    #[error("memory action failed with: {0}")]
+    MemoryError(#[from] MemoryError),
+
+    #[error("memory {0} is not in the trash")]
+    NotDeleted(MemoryId),
+
+    #[error("memory {0} was deleted more than {1} days ago and can no longer be restored")]
+    RestoreWindowExpired(MemoryId, u64),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
 }
 
 impl MemoryController {
-    pub async fn delete(&self, id: String) -> Result<(), MemoryControllerError> {
+    /// Moves the memory to the trash. It disappears from every query but can be brought
+    /// back with `restore_deleted` for `[trash] retention_days`.
+    pub async fn delete(&self, id: MemoryId) -> Result<(), MemoryControllerError> {
         Ok(self.delete_impl(id).await?)
     }
 
-    async fn delete_impl(&self, id: String) -> Result<(), DeleteMemoryError> {
+    /// Takes a memory out of the trash in the lifecycle state it had before.
+    pub async fn restore_deleted(&self, id: MemoryId) -> Result<Memory, MemoryControllerError> {
+        Ok(self.restore_deleted_impl(id, Utc::now()).await?)
+    }
+
+    /// Permanently removes a memory that is already in the trash.
+    pub async fn purge(&self, id: MemoryId) -> Result<(), MemoryControllerError> {
+        Ok(self.purge_impl(id).await?)
+    }
+
+    /// Permanently removes memories that have been in the trash for longer than
+    /// `[trash] retention_days`, returning how many. Meant to run periodically.
+    pub async fn purge_expired(&self, now: DateTime<Utc>) -> Result<usize, MemoryControllerError> {
+        Ok(self.purge_expired_impl(now).await?)
+    }
+
+    async fn delete_impl(&self, id: MemoryId) -> Result<(), DeleteMemoryError> {
         let vector_store = Arc::clone(&self.vector_store);
-        Ok(vector_store.delete(id.as_str()).await?)
+        let mut memory = vector_store.get(&id).await?;
+        self.guard(memory.context(), Access::ReadWrite)?;
+        if memory.is_deleted() {
+            return Ok(());
+        }
+
+        memory.soft_delete(Utc::now());
+        vector_store.update(&id, None, Some(&memory)).await?;
+
+        Ok(())
+    }
+
+    async fn restore_deleted_impl(
+        &self,
+        id: MemoryId,
+        now: DateTime<Utc>,
+    ) -> Result<Memory, DeleteMemoryError> {
+        let mut memory = self.vector_store.get(&id).await?;
+        self.guard(memory.context(), Access::ReadWrite)?;
+
+        let Some(deleted_at) = memory.temporal().deleted_at() else {
+            return Err(DeleteMemoryError::NotDeleted(id));
+        };
+        let retention_days = self.retention_days();
+        if deleted_at < self.retention_cutoff(now) {
+            return Err(DeleteMemoryError::RestoreWindowExpired(id, retention_days));
+        }
+
+        memory.restore(now);
+        memory.validate()?;
+        self.vector_store.update(&id, None, Some(&memory)).await?;
+
+        Ok(memory)
+    }
+
+    async fn purge_impl(&self, id: MemoryId) -> Result<(), DeleteMemoryError> {
+        let memory = self.vector_store.get(&id).await?;
+        self.guard(memory.context(), Access::ReadWrite)?;
+        if !memory.is_deleted() {
+            return Err(DeleteMemoryError::NotDeleted(id));
+        }
+
+        self.remove(&id).await
+    }
+
+    async fn purge_expired_impl(&self, now: DateTime<Utc>) -> Result<usize, DeleteMemoryError> {
+        self.guard_global()?;
+        let cutoff = self.retention_cutoff(now);
+
+        let mut expired = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (memories, next) = self.vector_store.scroll(cursor, PURGE_BATCH_SIZE).await?;
+            expired.extend(
+                memories
+                    .iter()
+                    .filter(|memory| {
+                        memory.is_deleted()
+                            && memory
+                                .temporal()
+                                .deleted_at()
+                                .is_some_and(|deleted_at| deleted_at < cutoff)
+                    })
+                    .map(Memory::memory_id),
+            );
+
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        for id in &expired {
+            self.remove(id).await?;
+        }
+
+        info!("purged {} memories from the trash", expired.len());
+        Ok(expired.len())
+    }
+
+    async fn remove(&self, id: &MemoryId) -> Result<(), DeleteMemoryError> {
+        self.vector_store.delete(id).await?;
+
+        if let Some(graph) = self.graph.as_ref() {
+            graph.delete_memory(id.as_uuid()).await?;
+        }
+
+        Ok(())
+    }
+
+    fn retention_days(&self) -> u64 {
+        self.trash_config
+            .retention_days
+            .unwrap_or(DEFAULT_RETENTION_DAYS)
+    }
+
+    fn retention_cutoff(&self, now: DateTime<Utc>) -> i64 {
+        now.timestamp() - (self.retention_days() * 86_400) as i64
     }
 }
@@ -0,0 +1,283 @@
+use std::{str::FromStr, sync::Arc};
+
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use arrow_array::RecordBatch;
+use arrow_json::ReaderBuilder;
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use chrono::{DateTime, Utc};
+use object_store::{path::Path, ObjectStore, PutPayload};
+use parquet::{arrow::ArrowWriter, errors::ParquetError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info;
+use typed_builder::TypedBuilder;
+use umem_core::Memory;
+use umem_vector_store::VectorStoreError;
+
+/// Object under the export prefix that records how far the last export got.
+const EXPORT_STATE_FILE: &str = "_export_state.json";
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("object store action failed with: {0}")]
+    ObjectStoreError(#[from] object_store::Error),
+
+    #[error("serde action failed with: {0}")]
+    SerdeError(#[from] serde_json::Error),
+
+    #[error("arrow encoding failed with: {0}")]
+    ArrowError(#[from] ArrowError),
+
+    #[error("parquet encoding failed with: {0}")]
+    ParquetError(#[from] ParquetError),
+
+    #[error("invalid export format: {0} (expected jsonl or parquet)")]
+    InvalidFormat(String),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One memory per line, as stored.
+    #[default]
+    Jsonl,
+    /// One row per memory with its fields as columns and the stored memory in `memory`.
+    Parquet,
+}
+
+impl FromStr for ExportFormat {
+    type Err = ExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "jsonl" | "json" => Ok(Self::Jsonl),
+            "parquet" => Ok(Self::Parquet),
+            s => Err(ExportError::InvalidFormat(s.to_string())),
+        }
+    }
+}
+
+impl ExportFormat {
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    fn encode(&self, memories: &[Memory]) -> Result<Vec<u8>, ExportError> {
+        match self {
+            Self::Jsonl => {
+                let mut buffer = Vec::new();
+                for memory in memories {
+                    serde_json::to_writer(&mut buffer, memory)?;
+                    buffer.push(b'\n');
+                }
+                Ok(buffer)
+            }
+            Self::Parquet => {
+                let schema = Arc::new(export_schema());
+                let rows = memories
+                    .iter()
+                    .map(ExportRow::new)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut decoder = ReaderBuilder::new(Arc::clone(&schema)).build_decoder()?;
+                decoder.serialize(&rows)?;
+                let batch = decoder
+                    .flush()?
+                    .unwrap_or_else(|| RecordBatch::new_empty(Arc::clone(&schema)));
+
+                let mut buffer = Vec::new();
+                let mut writer = ArrowWriter::try_new(&mut buffer, schema, None)?;
+                writer.write(&batch)?;
+                writer.close()?;
+                Ok(buffer)
+            }
+        }
+    }
+}
+
+/// A memory flattened into the columns of a Parquet export.
+#[derive(Serialize)]
+struct ExportRow<'a> {
+    id: String,
+    user_id: Option<&'a str>,
+    agent_id: Option<&'a str>,
+    run_id: Option<&'a str>,
+    org_id: Option<&'a str>,
+    kind: &'static str,
+    lifecycle: &'a str,
+    summary: &'a str,
+    tags: &'a [String],
+    certainty: f32,
+    salience: f32,
+    created_at: i64,
+    updated_at: Option<i64>,
+    archived_at: Option<i64>,
+    deleted_at: Option<i64>,
+    suppressed_at: Option<i64>,
+    memory: String,
+}
+
+impl<'a> ExportRow<'a> {
+    fn new(memory: &'a Memory) -> Result<Self, serde_json::Error> {
+        let context = memory.context();
+        let temporal = memory.temporal();
+        Ok(Self {
+            id: memory.get_id().to_string(),
+            user_id: context.user_id(),
+            agent_id: context.agent_id(),
+            run_id: context.run_id(),
+            org_id: context.org_id(),
+            kind: memory.kind().as_str(),
+            lifecycle: memory.lifecycle().as_str(),
+            summary: memory.get_summary(),
+            tags: memory.content().tags(),
+            certainty: memory.signals().get_certainty(),
+            salience: memory.signals().get_salience(),
+            created_at: temporal.created_at(),
+            updated_at: temporal.updated_at(),
+            archived_at: temporal.archived_at(),
+            deleted_at: temporal.deleted_at(),
+            suppressed_at: temporal.suppressed_at(),
+            memory: serde_json::to_string(memory)?,
+        })
+    }
+}
+
+fn export_schema() -> Schema {
+    let text = |name: &str, nullable: bool| Field::new(name, DataType::Utf8, nullable);
+    let time = |name: &str, nullable: bool| Field::new(name, DataType::Int64, nullable);
+    Schema::new(vec![
+        text("id", false),
+        text("user_id", true),
+        text("agent_id", true),
+        text("run_id", true),
+        text("org_id", true),
+        text("kind", false),
+        text("lifecycle", false),
+        text("summary", false),
+        Field::new(
+            "tags",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, false))),
+            false,
+        ),
+        Field::new("certainty", DataType::Float32, false),
+        Field::new("salience", DataType::Float32, false),
+        time("created_at", false),
+        time("updated_at", true),
+        time("archived_at", true),
+        time("deleted_at", true),
+        time("suppressed_at", true),
+        text("memory", false),
+    ])
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ExportState {
+    /// Memories changed after this time (unix seconds) are not exported yet.
+    exported_until: i64,
+}
+
+#[derive(TypedBuilder)]
+pub struct ExportRequest {
+    /// Bucket the files and the export state are written to.
+    target: Arc<dyn ObjectStore>,
+    /// Key prefix within `target`, without a trailing slash.
+    #[builder(default)]
+    prefix: String,
+    #[builder(default)]
+    format: ExportFormat,
+    /// Memories per file.
+    #[builder(default = 1000)]
+    batch_size: u32,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ExportProgress {
+    pub exported: usize,
+    pub files: usize,
+}
+
+impl MemoryController {
+    /// Writes every memory changed since the previous export to `target`, one file per
+    /// `batch_size` memories under `<prefix>/dt=<date>/`. The first export writes every
+    /// memory. Memories changed while an export runs are written again by the next one,
+    /// so readers should keep the row with the latest change per id.
+    pub async fn export_changed(
+        &self,
+        request: ExportRequest,
+        now: DateTime<Utc>,
+    ) -> Result<ExportProgress, MemoryControllerError> {
+        Ok(self.export_changed_impl(request, now).await?)
+    }
+
+    async fn export_changed_impl(
+        &self,
+        request: ExportRequest,
+        now: DateTime<Utc>,
+    ) -> Result<ExportProgress, ExportError> {
+        self.guard_global()?;
+        let ExportRequest {
+            target,
+            prefix,
+            format,
+            batch_size,
+        } = request;
+
+        let state_path = Path::from(format!("{prefix}/{EXPORT_STATE_FILE}"));
+        let state = match target.get(&state_path).await {
+            Ok(state) => serde_json::from_slice(&state.bytes().await?)?,
+            Err(object_store::Error::NotFound { .. }) => ExportState::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let until = now.timestamp();
+        let directory = format!("{prefix}/dt={}", now.format("%Y-%m-%d"));
+        let mut progress = ExportProgress::default();
+        let mut cursor = None;
+
+        loop {
+            let (memories, next) = self
+                .vector_store
+                .scroll_changed(None, state.exported_until, cursor, batch_size)
+                .await?;
+
+            if !memories.is_empty() {
+                let path = Path::from(format!(
+                    "{directory}/{until}-{:05}.{}",
+                    progress.files,
+                    format.extension()
+                ));
+                target
+                    .put(&path, PutPayload::from(format.encode(&memories)?))
+                    .await?;
+                progress.exported += memories.len();
+                progress.files += 1;
+            }
+
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        let state = ExportState {
+            exported_until: until,
+        };
+        target
+            .put(&state_path, PutPayload::from(serde_json::to_vec(&state)?))
+            .await?;
+        info!(
+            "exported {} changed memories in {} files to {directory}",
+            progress.exported, progress.files
+        );
+
+        Ok(progress)
+    }
+}
@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
-use super::{MemoryController, MemoryControllerError};
+use super::{MemoryController, MemoryControllerError, TenancyError};
 use thiserror::Error;
-use umem_core::Memory;
+use umem_auth::Access;
+use umem_core::{Memory, MemoryId};
 use umem_vector_store::VectorStoreError;
 
 #[derive(Debug, Error)]
@@ -10,17 +11,25 @@ pub enum GetMemoryError {
     #[error("vector store action failed with: {0}")]
     VectorStoreError(#[from] VectorStoreError),
 
-    #[error("vector id cannot be empty or whitespace")]
-    EmptyVectorId,
+    #[error("memory {0} is in the trash")]
+    Deleted(MemoryId),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
 }
 
 impl MemoryController {
-    pub async fn get(&self, id: String) -> Result<Memory, MemoryControllerError> {
+    pub async fn get(&self, id: MemoryId) -> Result<Memory, MemoryControllerError> {
         Ok(self.get_impl(id).await?)
     }
 
-    async fn get_impl(&self, id: String) -> Result<Memory, GetMemoryError> {
+    async fn get_impl(&self, id: MemoryId) -> Result<Memory, GetMemoryError> {
         let vector_store = Arc::clone(&self.vector_store);
-        Ok(vector_store.get(id.as_str()).await?)
+        let memory = vector_store.get(&id).await?;
+        self.guard_memory(&memory, Access::Read)?;
+        if memory.is_deleted() {
+            return Err(GetMemoryError::Deleted(id));
+        }
+        Ok(memory)
     }
 }
@@ -0,0 +1,149 @@
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use rustc_hash::FxHashSet;
+use serde::Serialize;
+use thiserror::Error;
+use umem_auth::Access;
+use umem_core::{Memory, MemoryContext, MemoryId};
+use umem_graph::{GraphError, Relation};
+use umem_vector_store::VectorStoreError;
+
+const REBUILD_BATCH_SIZE: u32 = 256;
+
+#[derive(Debug, Error)]
+pub enum GraphQueryError {
+    #[error("graph store is not configured")]
+    GraphNotConfigured,
+
+    #[error("graph action failed with: {0}")]
+    GraphError(#[from] GraphError),
+
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct GraphQueryResponse {
+    pub relations: Vec<Relation>,
+    pub memories: Vec<Memory>,
+}
+
+fn mentions(question: &str, entity: &str) -> bool {
+    question.match_indices(entity).any(|(start, _)| {
+        let end = start + entity.len();
+        let before = question[..start].chars().next_back();
+        let after = question[end..].chars().next();
+
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+impl MemoryController {
+    pub async fn graph_query(
+        &self,
+        context: MemoryContext,
+        question: String,
+        hops: usize,
+    ) -> Result<GraphQueryResponse, MemoryControllerError> {
+        Ok(self.graph_query_impl(context, question, hops).await?)
+    }
+
+    /// Restores the graph from the links kept on the stored memories, e.g. after a
+    /// restart emptied an in-memory graph. Returns how many relations were inserted.
+    pub async fn rebuild_graph(&self) -> Result<usize, MemoryControllerError> {
+        Ok(self.rebuild_graph_impl().await?)
+    }
+
+    async fn graph_query_impl(
+        &self,
+        context: MemoryContext,
+        question: String,
+        hops: usize,
+    ) -> Result<GraphQueryResponse, GraphQueryError> {
+        self.guard(&context, Access::Read)?;
+        let graph = self
+            .graph
+            .as_ref()
+            .ok_or(GraphQueryError::GraphNotConfigured)?;
+
+        let question = question.to_lowercase();
+        let entities: Vec<String> = graph
+            .entities(&context)
+            .await?
+            .into_iter()
+            .filter(|entity| mentions(&question, entity))
+            .collect();
+
+        if entities.is_empty() {
+            return Ok(GraphQueryResponse::default());
+        }
+
+        let relations = graph.traverse(&context, &entities, hops.max(1)).await?;
+
+        let mut seen = FxHashSet::default();
+        let mut hidden = FxHashSet::default();
+        let mut memories = Vec::new();
+        for relation in &relations {
+            if seen.insert(*relation.memory_id()) {
+                let memory = self
+                    .vector_store
+                    .get(&MemoryId::from(*relation.memory_id()))
+                    .await?;
+                if memory.is_deleted() || memory.is_suppressed() {
+                    hidden.insert(*relation.memory_id());
+                } else {
+                    memories.push(memory);
+                }
+            }
+        }
+        let relations = relations
+            .into_iter()
+            .filter(|relation| !hidden.contains(relation.memory_id()))
+            .collect();
+
+        Ok(GraphQueryResponse {
+            relations,
+            memories,
+        })
+    }
+
+    async fn rebuild_graph_impl(&self) -> Result<usize, GraphQueryError> {
+        self.guard_global()?;
+        let graph = self
+            .graph
+            .as_ref()
+            .ok_or(GraphQueryError::GraphNotConfigured)?;
+
+        let mut inserted = 0;
+        let mut cursor = None;
+        loop {
+            let (memories, next) = self.vector_store.scroll(cursor, REBUILD_BATCH_SIZE).await?;
+
+            let mut relations = Vec::new();
+            for memory in memories.iter().filter(|memory| !memory.is_deleted()) {
+                for link in memory.links() {
+                    relations.push(Relation::new(
+                        memory.context(),
+                        link.subject.clone(),
+                        link.predicate.clone(),
+                        link.object.clone(),
+                        *memory.get_id(),
+                    )?);
+                }
+            }
+            if !relations.is_empty() {
+                graph.insert(&relations).await?;
+                inserted += relations.len();
+            }
+
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(inserted)
+    }
+}
@@ -0,0 +1,95 @@
+use std::{sync::Arc, time::Duration};
+
+use super::MemoryController;
+use serde::Serialize;
+use umem_ai::embed::{embed, EmbeddingRequest};
+
+/// Input embedded to check the embedder can be reached.
+const PROBE_INPUT: &str = "readiness probe";
+
+/// One dependency checked by `readiness`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessCheck {
+    pub name: &'static str,
+    /// Why the check failed; unset when it passed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ReadinessCheck {
+    pub fn new(name: &'static str, result: Result<(), String>) -> Self {
+        Self {
+            name,
+            error: result.err(),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Readiness {
+    pub ready: bool,
+    pub checks: Vec<ReadinessCheck>,
+}
+
+impl Readiness {
+    pub fn new(checks: Vec<ReadinessCheck>) -> Self {
+        Self {
+            ready: checks.iter().all(ReadinessCheck::passed),
+            checks,
+        }
+    }
+}
+
+impl MemoryController {
+    /// Checks that the vector store and the embedder answer within `timeout`, and that
+    /// embeddings have `dimensions` entries when given, so traffic is only routed to an
+    /// instance that can serve it.
+    pub async fn readiness(&self, timeout: Duration, dimensions: Option<usize>) -> Readiness {
+        let (vector_store, embedder) = tokio::join!(
+            self.check_vector_store(timeout),
+            self.check_embedder(timeout, dimensions)
+        );
+
+        Readiness::new(vec![
+            ReadinessCheck::new("vector_store", vector_store),
+            ReadinessCheck::new("embedder", embedder),
+        ])
+    }
+
+    async fn check_vector_store(&self, timeout: Duration) -> Result<(), String> {
+        match tokio::time::timeout(timeout, self.vector_store.ping()).await {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(_) => Err(format!("no answer within {}ms", timeout.as_millis())),
+        }
+    }
+
+    async fn check_embedder(
+        &self,
+        timeout: Duration,
+        dimensions: Option<usize>,
+    ) -> Result<(), String> {
+        let request = EmbeddingRequest::builder()
+            .model(Arc::clone(&self.embedding_model))
+            .input(vec![PROBE_INPUT.to_string()])
+            .max_retries(0)
+            .build();
+        let response = match tokio::time::timeout(timeout, embed(request)).await {
+            Ok(response) => response.map_err(|e| e.to_string())?,
+            Err(_) => return Err(format!("no answer within {}ms", timeout.as_millis())),
+        };
+
+        match (response.embeddings.first(), dimensions) {
+            (None, _) => Err("no embedding returned".to_string()),
+            (Some(embedding), Some(dimensions)) if embedding.len() != dimensions => Err(format!(
+                "embeddings have {} dimensions, the vector store expects {}",
+                embedding.len(),
+                dimensions
+            )),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use std::{sync::Arc, time::Duration};
+
+use super::{
+    ConversationImportError, DrainImportQueueRequest, ImportQueue, ImportQueueError,
+    ImportQueueProgress, MemoryController, MemoryControllerError, QueuedItem, QueuedWork,
+};
+use thiserror::Error;
+use tracing::{info, warn};
+use typed_builder::TypedBuilder;
+use umem_ai::{
+    object_batch_results, poll_object_batch, submit_object_batch, ObjectBatch, ObjectBatchStatus,
+    ResponseGeneratorError,
+};
+use umem_annotations::LLMAnnotations;
+
+#[derive(Debug, Error)]
+pub enum BatchImportError {
+    #[error("import queue action failed with: {0}")]
+    ImportQueueError(#[from] ImportQueueError),
+
+    #[error("conversation import failed with: {0}")]
+    ConversationImportError(#[from] ConversationImportError),
+
+    #[error("batch api action failed with: {0}")]
+    ResponseGeneratorError(#[from] ResponseGeneratorError),
+}
+
+#[derive(TypedBuilder)]
+pub struct BatchImportRequest {
+    /// Sessions submitted as one provider batch. Each batch's memories are stored as
+    /// soon as it completes, so smaller batches show results sooner.
+    #[builder(default = 500)]
+    sessions_per_batch: u32,
+    /// How often submitted batches are checked on.
+    #[builder(default = Duration::from_secs(60))]
+    poll_interval: Duration,
+    /// Queued memories, which need no annotation, are stored this many at a time.
+    #[builder(default = 64)]
+    batch_size: u32,
+}
+
+impl MemoryController {
+    /// Works through `queue` like `drain_import_queue`, but annotates its sessions
+    /// through the language model provider's batch API, at about half the price and
+    /// within a day instead of right away. Batches submitted by an earlier, interrupted
+    /// run are picked up again instead of being submitted twice.
+    pub async fn drain_import_queue_batched(
+        &self,
+        queue: &ImportQueue,
+        request: BatchImportRequest,
+    ) -> Result<ImportQueueProgress, MemoryControllerError> {
+        Ok(self.drain_import_queue_batched_impl(queue, request).await?)
+    }
+
+    async fn drain_import_queue_batched_impl(
+        &self,
+        queue: &ImportQueue,
+        request: BatchImportRequest,
+    ) -> Result<ImportQueueProgress, BatchImportError> {
+        let (_, language_model) = self.models_for(None);
+        let mut progress = ImportQueueProgress::default();
+
+        loop {
+            let items = queue
+                .pending_sessions(request.sessions_per_batch.max(1))
+                .await?;
+            if items.is_empty() {
+                break;
+            }
+
+            let mut requests = Vec::new();
+            for QueuedItem { key, work } in items {
+                let QueuedWork::Session { user_id, session } = work else {
+                    queue
+                        .fail(&key, "only sessions can be annotated in a batch")
+                        .await?;
+                    progress.failed += 1;
+                    continue;
+                };
+                if self.session_imported(&user_id, &session).await? {
+                    queue.complete(&key).await?;
+                    progress.done += 1;
+                    continue;
+                }
+                match self
+                    .annotation_request(&session.request(&user_id), Arc::clone(&language_model))
+                    .await
+                {
+                    Ok(annotation) => requests.push((key, annotation)),
+                    Err(e) => {
+                        warn!("preparing queued session {} failed: {}", key, e);
+                        queue.fail(&key, &e.to_string()).await?;
+                        progress.failed += 1;
+                    }
+                }
+            }
+            if requests.is_empty() {
+                continue;
+            }
+
+            let keys: Vec<String> = requests.iter().map(|(key, _)| key.clone()).collect();
+            let batch = submit_object_batch(requests).await?;
+            queue.submit(&keys, &batch.id).await?;
+            info!("submitted {} sessions as batch {}", keys.len(), batch.id);
+        }
+
+        // NOTE: memories need no annotation, so they are stored while the batches run
+        let memories = self
+            .drain_import_queue_impl(
+                queue,
+                DrainImportQueueRequest::builder()
+                    .batch_size(request.batch_size)
+                    .build(),
+            )
+            .await?;
+        progress.done += memories.done;
+        progress.failed += memories.failed;
+        progress.memories += memories.memories;
+
+        loop {
+            let batch_ids = queue.submitted_batches().await?;
+            if batch_ids.is_empty() {
+                break;
+            }
+
+            let mut waiting = false;
+            for batch_id in batch_ids {
+                let batch = poll_object_batch(&language_model, &batch_id).await?;
+                match &batch.status {
+                    ObjectBatchStatus::InProgress => {
+                        waiting = true;
+                        info!(
+                            "batch {}: {} of {} sessions answered",
+                            batch.id,
+                            batch.completed + batch.failed,
+                            batch.total
+                        );
+                    }
+                    ObjectBatchStatus::Failed(reason) => {
+                        warn!("batch {} failed: {}", batch.id, reason);
+                        progress.failed += queue.fail_batch(&batch.id, reason).await? as usize;
+                    }
+                    ObjectBatchStatus::Completed { .. } => {
+                        self.store_batch(queue, &batch, &mut progress).await?;
+                    }
+                }
+            }
+
+            info!(
+                "import queue: {} done, {} failed, {} memories stored",
+                progress.done, progress.failed, progress.memories
+            );
+            if waiting {
+                tokio::time::sleep(request.poll_interval).await;
+            }
+        }
+
+        Ok(progress)
+    }
+
+    /// Stores the memories annotated by a completed batch and settles its sessions.
+    async fn store_batch(
+        &self,
+        queue: &ImportQueue,
+        batch: &ObjectBatch,
+        progress: &mut ImportQueueProgress,
+    ) -> Result<(), BatchImportError> {
+        let (_, language_model) = self.models_for(None);
+
+        for (key, output) in object_batch_results::<LLMAnnotations>(&language_model, batch).await? {
+            let Some(QueuedItem {
+                work: QueuedWork::Session { user_id, session },
+                ..
+            }) = queue.get(&key).await?
+            else {
+                warn!("batch {} answered unknown session {}", batch.id, key);
+                continue;
+            };
+            // a run interrupted while storing a batch leaves some of it stored already
+            if self.session_imported(&user_id, &session).await? {
+                queue.complete(&key).await?;
+                progress.done += 1;
+                continue;
+            }
+
+            let stored = match output {
+                Ok(annotations) => self
+                    .create_annotated(session.request(&user_id), annotations, None)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            match stored {
+                Ok(memories) => {
+                    queue.complete(&key).await?;
+                    progress.done += 1;
+                    progress.memories += memories.len();
+                }
+                Err(e) => {
+                    warn!("importing batched session {} failed: {}", key, e);
+                    queue.fail(&key, &e).await?;
+                    progress.failed += 1;
+                }
+            }
+        }
+
+        let missing = queue
+            .fail_batch(&batch.id, "the batch returned no answer")
+            .await?;
+        progress.failed += missing as usize;
+        info!("stored the answers of batch {}", batch.id);
+        Ok(())
+    }
+}
@@ -0,0 +1,427 @@
+use std::{collections::HashMap, future::Future, str::FromStr, time::Duration};
+
+use super::{CreateMemoryRequest, MemoryController, MemoryControllerError};
+use chrono::DateTime;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::{sync::Mutex, time::Interval};
+use tracing::{info, warn};
+use typed_builder::TypedBuilder;
+use umem_core::{scrub_secrets, MemoryContext, MemoryContextError, Query};
+use umem_vector_store::VectorStoreError;
+
+/// Transcript length a session is cut at, well below what one annotation call takes.
+const DEFAULT_SESSION_CHARS: usize = 12_000;
+
+#[derive(Debug, Error)]
+pub enum ConversationImportError {
+    #[error("invalid conversation format: {0} (expected chatgpt or claude)")]
+    InvalidFormat(String),
+
+    #[error("{0} export could not be parsed: {1}")]
+    ParseError(&'static str, serde_json::Error),
+
+    #[error("memory context action failed with: {0}")]
+    MemoryContextError(#[from] MemoryContextError),
+
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+}
+
+/// Chat assistant data exports that `parse` understands; both are `conversations.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversationFormat {
+    ChatGpt,
+    Claude,
+}
+
+impl FromStr for ConversationFormat {
+    type Err = ConversationImportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "chatgpt" | "openai" => Ok(Self::ChatGpt),
+            "claude" | "anthropic" => Ok(Self::Claude),
+            s => Err(ConversationImportError::InvalidFormat(s.to_string())),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ChatGptConversation {
+    id: Option<String>,
+    conversation_id: Option<String>,
+    title: Option<String>,
+    create_time: Option<f64>,
+    current_node: Option<String>,
+    #[serde(default)]
+    mapping: HashMap<String, ChatGptNode>,
+}
+
+#[derive(Deserialize)]
+struct ChatGptNode {
+    parent: Option<String>,
+    message: Option<ChatGptMessage>,
+}
+
+#[derive(Deserialize)]
+struct ChatGptMessage {
+    author: ChatGptAuthor,
+    content: ChatGptContent,
+}
+
+#[derive(Deserialize)]
+struct ChatGptAuthor {
+    role: String,
+}
+
+#[derive(Deserialize)]
+struct ChatGptContent {
+    #[serde(default)]
+    parts: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ClaudeConversation {
+    uuid: String,
+    name: Option<String>,
+    created_at: Option<String>,
+    #[serde(default)]
+    chat_messages: Vec<ClaudeMessage>,
+}
+
+#[derive(Deserialize)]
+struct ClaudeMessage {
+    sender: String,
+    #[serde(default)]
+    text: String,
+}
+
+/// A slice of one conversation, annotated in one call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationSession {
+    /// Stored as the run_id of the session's memories.
+    pub id: String,
+    pub transcript: String,
+}
+
+impl ConversationSession {
+    /// The create request that annotates this session into memories of `user_id`.
+    pub(crate) fn request(&self, user_id: &str) -> CreateMemoryRequest {
+        CreateMemoryRequest::builder()
+            .user_id(Some(user_id.to_string()))
+            .run_id(Some(self.id.clone()))
+            .raw_content(self.transcript.clone())
+            .build()
+    }
+}
+
+/// Cuts `turns` into transcripts of at most about `max_chars`, each headed by `header`
+/// and split between turns, so no session starts in the middle of a message.
+fn slice(
+    conversation_id: &str,
+    header: &str,
+    turns: Vec<(&'static str, String)>,
+    max_chars: usize,
+) -> Vec<ConversationSession> {
+    let mut transcripts = Vec::new();
+    let mut transcript = String::new();
+    for (speaker, text) in turns {
+        // NOTE: sessions wait in the import queue, so secrets are scrubbed before that
+        let text = scrub_secrets(text.trim()).text;
+        if text.is_empty() {
+            continue;
+        }
+        let turn = format!("{speaker}: {text}\n");
+        if !transcript.is_empty() && transcript.len() + turn.len() > max_chars {
+            transcripts.push(std::mem::take(&mut transcript));
+        }
+        transcript.push_str(&turn);
+    }
+    if !transcript.is_empty() {
+        transcripts.push(transcript);
+    }
+
+    transcripts
+        .into_iter()
+        .enumerate()
+        .map(|(index, transcript)| ConversationSession {
+            id: format!("{conversation_id}/{index}"),
+            transcript: format!("{header}\n{transcript}"),
+        })
+        .collect()
+}
+
+fn header(title: Option<&str>, created_at: Option<i64>) -> String {
+    let date = created_at
+        .and_then(|time| DateTime::from_timestamp(time, 0))
+        .map(|time| format!(" ({})", time.format("%Y-%m-%d")))
+        .unwrap_or_default();
+    format!("Conversation: {}{date}", title.unwrap_or("untitled").trim())
+}
+
+fn speaker(role: &str) -> Option<&'static str> {
+    match role {
+        "user" | "human" => Some("User"),
+        "assistant" => Some("Assistant"),
+        _ => None,
+    }
+}
+
+impl ConversationFormat {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ChatGpt => "chatgpt",
+            Self::Claude => "claude",
+        }
+    }
+
+    /// Reads a `conversations.json` export into sessions of at most about `max_chars`
+    /// (12000 by default) of user and assistant turns. System and tool messages are
+    /// left out.
+    pub fn parse(
+        &self,
+        input: &str,
+        max_chars: Option<usize>,
+    ) -> Result<Vec<ConversationSession>, ConversationImportError> {
+        let source = self.as_str();
+        let max_chars = max_chars.unwrap_or(DEFAULT_SESSION_CHARS);
+        let parse_error = |e| ConversationImportError::ParseError(source, e);
+
+        let sessions = match self {
+            Self::ChatGpt => {
+                let conversations: Vec<ChatGptConversation> =
+                    serde_json::from_str(input).map_err(parse_error)?;
+                conversations
+                    .into_iter()
+                    .flat_map(|conversation| {
+                        let id = conversation
+                            .conversation_id
+                            .or(conversation.id)
+                            .unwrap_or_default();
+                        let header = header(
+                            conversation.title.as_deref(),
+                            conversation.create_time.map(|time| time as i64),
+                        );
+                        // NOTE: the mapping is a tree of edits and regenerations; the
+                        // branch the user last saw runs from current_node up to the root
+                        let mut turns = Vec::new();
+                        let mut node = conversation.current_node;
+                        // a malformed export could link parents in a cycle
+                        let mut steps = conversation.mapping.len();
+                        while let Some(current) = node
+                            .and_then(|id| conversation.mapping.get(&id))
+                            .filter(|_| steps > 0)
+                        {
+                            steps -= 1;
+                            if let Some(message) = &current.message {
+                                if let Some(speaker) = speaker(&message.author.role) {
+                                    let text = message
+                                        .content
+                                        .parts
+                                        .iter()
+                                        .filter_map(|part| part.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join("\n");
+                                    turns.push((speaker, text));
+                                }
+                            }
+                            node = current.parent.clone();
+                        }
+                        turns.reverse();
+                        slice(&format!("{source}:{id}"), &header, turns, max_chars)
+                    })
+                    .collect()
+            }
+            Self::Claude => {
+                let conversations: Vec<ClaudeConversation> =
+                    serde_json::from_str(input).map_err(parse_error)?;
+                conversations
+                    .into_iter()
+                    .flat_map(|conversation| {
+                        let created_at = conversation
+                            .created_at
+                            .as_deref()
+                            .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                            .map(|time| time.timestamp());
+                        let header = header(conversation.name.as_deref(), created_at);
+                        let turns = conversation
+                            .chat_messages
+                            .into_iter()
+                            .filter_map(|message| {
+                                speaker(&message.sender).map(|speaker| (speaker, message.text))
+                            })
+                            .collect();
+                        slice(
+                            &format!("{source}:{}", conversation.uuid),
+                            &header,
+                            turns,
+                            max_chars,
+                        )
+                    })
+                    .collect()
+            }
+        };
+
+        Ok(sessions)
+    }
+}
+
+#[derive(TypedBuilder)]
+pub struct ConversationImportRequest {
+    user_id: String,
+    sessions: Vec<ConversationSession>,
+    /// Sessions annotated concurrently.
+    #[builder(default = 4)]
+    batch_size: usize,
+    /// Upper bound on annotation calls per minute, for the language model's rate limit.
+    #[builder(default = None)]
+    requests_per_minute: Option<u32>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConversationImportProgress {
+    pub sessions: usize,
+    pub memories: usize,
+    /// Sessions that already had memories, from an earlier run.
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+/// Paces annotation calls to `requests_per_minute`, when one is given. Shared by the
+/// calls in flight, see `paced`.
+pub(crate) fn rate_limiter(requests_per_minute: Option<u32>) -> Option<Mutex<Interval>> {
+    requests_per_minute.map(|requests| {
+        Mutex::new(tokio::time::interval(Duration::from_secs_f64(
+            60.0 / f64::from(requests.max(1)),
+        )))
+    })
+}
+
+/// Runs `call` on every item concurrently, each call starting only once `limiter`
+/// allows another, and returns the results in order.
+pub(crate) async fn paced<I, F, Fut>(
+    limiter: Option<&Mutex<Interval>>,
+    items: I,
+    call: F,
+) -> Vec<Fut::Output>
+where
+    I: IntoIterator,
+    F: Fn(I::Item) -> Fut,
+    Fut: Future,
+{
+    let call = &call;
+    join_all(items.into_iter().map(|item| async move {
+        if let Some(limiter) = limiter {
+            limiter.lock().await.tick().await;
+        }
+        call(item).await
+    }))
+    .await
+}
+
+impl MemoryController {
+    /// Annotates every session into memories of `user_id`, with the session id as
+    /// their run_id. Sessions that already have memories are skipped, so an interrupted
+    /// import can be run again; sessions whose annotation fails are logged and counted.
+    pub async fn import_conversations(
+        &self,
+        request: ConversationImportRequest,
+    ) -> Result<ConversationImportProgress, MemoryControllerError> {
+        Ok(self.import_conversations_impl(request).await?)
+    }
+
+    async fn import_conversations_impl(
+        &self,
+        request: ConversationImportRequest,
+    ) -> Result<ConversationImportProgress, ConversationImportError> {
+        let ConversationImportRequest {
+            user_id,
+            sessions,
+            batch_size,
+            requests_per_minute,
+        } = request;
+        let total = sessions.len();
+        let limiter = rate_limiter(requests_per_minute);
+        let mut progress = ConversationImportProgress::default();
+
+        for batch in sessions.chunks(batch_size.max(1)) {
+            let mut pending = Vec::new();
+            for session in batch {
+                if self.session_imported(&user_id, session).await? {
+                    progress.skipped += 1;
+                } else {
+                    pending.push(session);
+                }
+            }
+
+            let results = paced(limiter.as_ref(), &pending, |session| {
+                self.annotate_session(&user_id, session)
+            })
+            .await;
+
+            for (session, result) in pending.iter().zip(results) {
+                match result {
+                    Ok(memories) => progress.memories += memories,
+                    Err(e) => {
+                        warn!("importing session {} failed: {}", session.id, e);
+                        progress.failed += 1;
+                    }
+                }
+            }
+            progress.sessions += batch.len();
+            info!(
+                "imported {}/{total} sessions into {} memories ({} skipped, {} failed)",
+                progress.sessions, progress.memories, progress.skipped, progress.failed
+            );
+        }
+
+        Ok(progress)
+    }
+
+    /// Whether `session` already has memories of `user_id`, from an earlier import.
+    pub(crate) async fn session_imported(
+        &self,
+        user_id: &str,
+        session: &ConversationSession,
+    ) -> Result<bool, ConversationImportError> {
+        let context =
+            MemoryContext::new(Some(user_id.to_string()), None, Some(session.id.clone()))?;
+        let query = Query::builder().context(context).limit(1).build();
+        Ok(!self.vector_store.list(query).await?.is_empty())
+    }
+
+    /// Runs annotation over the transcript of `session`, returning how many memories
+    /// were created.
+    pub(crate) async fn annotate_session(
+        &self,
+        user_id: &str,
+        session: &ConversationSession,
+    ) -> Result<usize, MemoryControllerError> {
+        Ok(self.create(session.request(user_id), None).await?.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::Instant;
+
+    #[tokio::test(start_paused = true)]
+    async fn paced_calls_start_one_rate_limit_period_apart() {
+        let limiter = rate_limiter(Some(60));
+        let start = Instant::now();
+
+        let started = paced(limiter.as_ref(), 0..3, |_| async {
+            let at = Instant::now();
+            // calls overlap, so only the limiter spaces them
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            at
+        })
+        .await;
+
+        let offsets: Vec<u64> = started.iter().map(|at| (*at - start).as_secs()).collect();
+        assert_eq!(offsets, vec![0, 1, 2]);
+    }
+}
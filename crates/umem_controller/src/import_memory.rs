@@ -0,0 +1,421 @@
+use std::{str::FromStr, sync::Arc};
+
+use super::{CreateMemoryError, MemoryController, MemoryControllerError, TenancyError};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::info;
+use umem_auth::Access;
+use umem_core::{
+    credence::Credence, scrub_secrets, LifecycleState, Memory, MemoryContent, MemoryContext,
+    MemoryError, MemoryKind, MemorySignals, Provenance, ProvenanceMethod, ProvenanceOrigin,
+    TemporalMetadata,
+};
+use umem_vector_store::VectorStoreError;
+use uuid::Uuid;
+
+/// Imported memories were not vetted by this deployment's extraction, so they start
+/// below the certainty of memories created here.
+const IMPORTED_CERTAINTY: f32 = 0.8;
+const IMPORTED_SALIENCE: f32 = 0.5;
+
+/// Phrases that mark a rule for the agent rather than a fact about the user.
+const INSTRUCTION_PREFIXES: &[&str] = &[
+    "always ",
+    "never ",
+    "don't ",
+    "do not ",
+    "make sure ",
+    "please ",
+    "prefer ",
+];
+const PROCEDURAL_MARKERS: &[&str] = &["how to ", "step 1", "steps to ", "first, ", "then run "];
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("invalid import format: {0} (expected mem0, zep or langchain)")]
+    InvalidFormat(String),
+
+    #[error("{0} export could not be parsed: {1}")]
+    ParseError(&'static str, serde_json::Error),
+
+    #[error("{0} export names no user; pass one to import into")]
+    MissingUser(&'static str),
+
+    #[error("memory validation failed with: {0}")]
+    MemoryError(#[from] MemoryError),
+
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("storing imported memories failed with: {0}")]
+    CreateMemoryError(#[from] CreateMemoryError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+/// Export formats of other memory stores that `parse` understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// The `get_all` response of mem0: `{"results": [...]}` or the bare list.
+    Mem0,
+    /// Zep sessions with their `facts` and `messages`, as one object or a list.
+    Zep,
+    /// LangChain chat history as written by `messages_to_dict`.
+    LangChain,
+}
+
+impl FromStr for ImportFormat {
+    type Err = ImportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mem0" => Ok(Self::Mem0),
+            "zep" => Ok(Self::Zep),
+            "langchain" => Ok(Self::LangChain),
+            s => Err(ImportError::InvalidFormat(s.to_string())),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Mem0Export {
+    Results { results: Vec<Mem0Memory> },
+    List(Vec<Mem0Memory>),
+}
+
+#[derive(Deserialize)]
+struct Mem0Memory {
+    id: Option<String>,
+    memory: String,
+    user_id: Option<String>,
+    agent_id: Option<String>,
+    run_id: Option<String>,
+    #[serde(default)]
+    categories: Option<Vec<String>>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ZepExport {
+    Sessions(Vec<ZepSession>),
+    Session(ZepSession),
+}
+
+#[derive(Deserialize)]
+struct ZepSession {
+    user_id: Option<String>,
+    session_id: Option<String>,
+    #[serde(default)]
+    facts: Vec<ZepFact>,
+    #[serde(default)]
+    messages: Vec<ZepMessage>,
+}
+
+#[derive(Deserialize)]
+struct ZepFact {
+    uuid: Option<String>,
+    fact: String,
+    created_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ZepMessage {
+    uuid: Option<String>,
+    role_type: Option<String>,
+    role: Option<String>,
+    content: String,
+    created_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LangChainMessage {
+    #[serde(rename = "type")]
+    kind: String,
+    data: LangChainMessageData,
+}
+
+#[derive(Deserialize)]
+struct LangChainMessageData {
+    content: String,
+    id: Option<String>,
+}
+
+/// A memory read from an export, before it is mapped to a `Memory`.
+struct Imported {
+    id: Option<String>,
+    summary: String,
+    tags: Vec<String>,
+    context: MemoryContext,
+    kind: MemoryKind,
+    origin: ProvenanceOrigin,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+}
+
+/// The kind the other store would not tell us: instructions and procedures are
+/// recognized by their phrasing, everything else keeps `default`.
+fn infer_kind(text: &str, default: MemoryKind) -> MemoryKind {
+    let text = text.trim_start().to_lowercase();
+    if INSTRUCTION_PREFIXES
+        .iter()
+        .any(|prefix| text.starts_with(prefix))
+    {
+        MemoryKind::Instruction
+    } else if PROCEDURAL_MARKERS
+        .iter()
+        .any(|marker| text.contains(marker))
+    {
+        MemoryKind::Procedural
+    } else {
+        default
+    }
+}
+
+fn timestamp(value: Option<&str>) -> Option<i64> {
+    value
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .map(|time| time.timestamp())
+}
+
+impl ImportFormat {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mem0 => "mem0",
+            Self::Zep => "zep",
+            Self::LangChain => "langchain",
+        }
+    }
+
+    /// Maps an export into memories marked as imported from this format. `user_id`
+    /// fills in for exports that do not name one, and is required for LangChain
+    /// histories, which never do. Ids that are uuids are kept, so importing the same
+    /// export twice does not store anything twice.
+    pub fn parse(&self, input: &str, user_id: Option<&str>) -> Result<Vec<Memory>, ImportError> {
+        let source = self.as_str();
+        let parse_error = |e| ImportError::ParseError(source, e);
+        let context = |user: Option<String>, agent: Option<String>, run: Option<String>| {
+            let user = user.or_else(|| user_id.map(str::to_owned));
+            if user.is_none() && agent.is_none() {
+                return Err(ImportError::MissingUser(source));
+            }
+            MemoryContext::new(user, agent, run).map_err(|e| MemoryError::from(e).into())
+        };
+
+        let imported = match self {
+            Self::Mem0 => {
+                let memories = match serde_json::from_str(input).map_err(parse_error)? {
+                    Mem0Export::Results { results } => results,
+                    Mem0Export::List(memories) => memories,
+                };
+                memories
+                    .into_iter()
+                    .map(|memory| {
+                        Ok(Imported {
+                            kind: infer_kind(&memory.memory, MemoryKind::Semantic),
+                            context: context(memory.user_id, memory.agent_id, memory.run_id)?,
+                            id: memory.id,
+                            summary: memory.memory,
+                            tags: memory.categories.unwrap_or_default(),
+                            origin: ProvenanceOrigin::User,
+                            created_at: memory.created_at,
+                            updated_at: memory.updated_at,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, ImportError>>()?
+            }
+            Self::Zep => {
+                let sessions = match serde_json::from_str(input).map_err(parse_error)? {
+                    ZepExport::Sessions(sessions) => sessions,
+                    ZepExport::Session(session) => vec![session],
+                };
+                let mut imported = Vec::new();
+                for session in sessions {
+                    let user = session.user_id;
+                    for fact in session.facts {
+                        imported.push(Imported {
+                            kind: infer_kind(&fact.fact, MemoryKind::Semantic),
+                            context: context(user.clone(), None, None)?,
+                            id: fact.uuid,
+                            summary: fact.fact,
+                            tags: Vec::new(),
+                            origin: ProvenanceOrigin::User,
+                            created_at: fact.created_at,
+                            updated_at: None,
+                        });
+                    }
+                    for message in session.messages {
+                        let role = message.role_type.or(message.role).unwrap_or_default();
+                        if role == "system" {
+                            continue;
+                        }
+                        imported.push(Imported {
+                            kind: infer_kind(&message.content, MemoryKind::Episodic),
+                            context: context(user.clone(), None, session.session_id.clone())?,
+                            id: message.uuid,
+                            summary: message.content,
+                            tags: Vec::new(),
+                            origin: match role.as_str() {
+                                "assistant" | "tool" => ProvenanceOrigin::Agent,
+                                _ => ProvenanceOrigin::User,
+                            },
+                            created_at: message.created_at,
+                            updated_at: None,
+                        });
+                    }
+                }
+                imported
+            }
+            Self::LangChain => {
+                let messages: Vec<LangChainMessage> =
+                    serde_json::from_str(input).map_err(parse_error)?;
+                messages
+                    .into_iter()
+                    .filter(|message| message.kind != "system")
+                    .map(|message| {
+                        Ok(Imported {
+                            kind: infer_kind(&message.data.content, MemoryKind::Episodic),
+                            context: context(None, None, None)?,
+                            id: message.data.id,
+                            summary: message.data.content,
+                            tags: Vec::new(),
+                            origin: match message.kind.as_str() {
+                                "ai" | "tool" | "function" => ProvenanceOrigin::Agent,
+                                _ => ProvenanceOrigin::User,
+                            },
+                            created_at: None,
+                            updated_at: None,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, ImportError>>()?
+            }
+        };
+
+        imported
+            .into_iter()
+            .filter(|imported| !imported.summary.trim().is_empty())
+            .map(|imported| imported.into_memory(source))
+            .collect()
+    }
+}
+
+impl Imported {
+    fn into_memory(self, source: &str) -> Result<Memory, ImportError> {
+        let created_at =
+            timestamp(self.created_at.as_deref()).unwrap_or_else(|| Utc::now().timestamp());
+        let updated_at = timestamp(self.updated_at.as_deref()).filter(|time| *time >= created_at);
+        let signals = MemorySignals::new(
+            Credence::new(IMPORTED_CERTAINTY).map_err(MemoryError::from)?,
+            Credence::new(IMPORTED_SALIENCE).map_err(MemoryError::from)?,
+        )
+        .map_err(MemoryError::from)?;
+
+        let memory = Memory::builder()
+            .id(self
+                .id
+                .and_then(|id| Uuid::parse_str(&id).ok())
+                .unwrap_or_else(Uuid::new_v4))
+            .content(
+                MemoryContent::new(scrub_secrets(&self.summary).text, self.tags)
+                    .map_err(MemoryError::from)?,
+            )
+            .context(self.context)
+            .kind(self.kind)
+            .signals(signals)
+            .provenance(Provenance::new(
+                self.origin,
+                ProvenanceMethod::Imported {
+                    source: source.to_string(),
+                },
+            ))
+            .lifecycle(LifecycleState::Active)
+            .temporal(
+                TemporalMetadata::with_times(created_at, updated_at, None)
+                    .map_err(MemoryError::from)?,
+            )
+            .build()
+            .finish()?;
+        Ok(memory)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImportProgress {
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+impl MemoryController {
+    /// Stores `memories` read by `ImportFormat::parse`, `batch_size` at a time, like
+    /// newly created ones. Memories whose id is already stored are skipped, so an
+    /// interrupted import can be run again. Nothing is stored unless the principal may
+    /// write every memory's context.
+    pub async fn import(
+        &self,
+        memories: Vec<Memory>,
+        batch_size: usize,
+    ) -> Result<ImportProgress, MemoryControllerError> {
+        Ok(self.import_impl(memories, batch_size).await?)
+    }
+
+    pub(crate) async fn import_impl(
+        &self,
+        memories: Vec<Memory>,
+        batch_size: usize,
+    ) -> Result<ImportProgress, ImportError> {
+        for memory in &memories {
+            self.guard(memory.context(), Access::ReadWrite)?;
+        }
+
+        let (embedding_model, language_model) = self.models_for(None);
+        let mut progress = ImportProgress::default();
+
+        for batch in memories.chunks(batch_size.max(1)) {
+            let ids: Vec<Uuid> = batch.iter().map(|memory| *memory.get_id()).collect();
+            let existing = self.vector_store.existing_ids(&ids).await?;
+            let pending: Vec<Memory> = batch
+                .iter()
+                .filter(|memory| !existing.contains(memory.get_id()))
+                .cloned()
+                .collect();
+            progress.skipped += batch.len() - pending.len();
+            progress.imported += pending.len();
+
+            self.store(
+                pending,
+                Arc::clone(&embedding_model),
+                Arc::clone(&language_model),
+            )
+            .await?;
+            info!(
+                "imported {} memories ({} already present)",
+                progress.imported, progress.skipped
+            );
+        }
+
+        Ok(progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+    use umem_auth::ApiKey;
+
+    #[tokio::test]
+    async fn import_rejects_memories_outside_the_principals_scope() {
+        let controller = testing::controller().for_principal(Some(&ApiKey::for_user("alice")));
+        let foreign = Memory::gen_dummy().unwrap();
+        let id = foreign.memory_id();
+
+        let result = controller.import_impl(vec![foreign], 10).await;
+
+        assert!(matches!(result, Err(ImportError::TenancyError(_))));
+        assert!(controller.vector_store.get(&id).await.is_err());
+    }
+}
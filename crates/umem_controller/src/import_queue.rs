@@ -0,0 +1,438 @@
+use std::str::FromStr;
+
+use super::{
+    paced, rate_limiter, ConversationImportError, ConversationSession, MemoryController,
+    MemoryControllerError,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    query,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    Pool, Row, Sqlite,
+};
+use thiserror::Error;
+use tracing::{info, warn};
+use typed_builder::TypedBuilder;
+use umem_core::Memory;
+
+#[derive(Debug, Error)]
+pub enum ImportQueueError {
+    #[error("import queue database action failed with: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    #[error("queued work could not be encoded: {0}")]
+    SerdeError(#[from] serde_json::Error),
+
+    #[error("conversation import failed with: {0}")]
+    ConversationImportError(#[from] ConversationImportError),
+}
+
+/// One unit of import work: a memory to store, or a session to annotate.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueuedWork {
+    Memory(Memory),
+    Session {
+        user_id: String,
+        session: ConversationSession,
+    },
+}
+
+impl QueuedWork {
+    /// Identifies the work across runs, so queueing the same export twice adds nothing.
+    fn key(&self) -> String {
+        match self {
+            Self::Memory(memory) => format!("memory:{}", memory.get_id()),
+            Self::Session { user_id, session } => format!("session:{user_id}:{}", session.id),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct QueuedItem {
+    pub key: String,
+    pub work: QueuedWork,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImportQueueStatus {
+    pub pending: i64,
+    /// Sessions waiting on a provider batch.
+    pub submitted: i64,
+    pub done: i64,
+    pub failed: i64,
+}
+
+/// Import work kept in a sqlite database until it is done, so a large import holds one
+/// batch in memory at a time and carries on where it stopped after a crash. Work is
+/// marked done after it reached the stores; whatever was in flight during a crash runs
+/// again, and the importers skip what they already stored.
+pub struct ImportQueue {
+    client: Pool<Sqlite>,
+}
+
+impl ImportQueue {
+    pub async fn open(database_url: &str) -> Result<Self, ImportQueueError> {
+        let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+        let client = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await?;
+
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS import_queue (
+                key TEXT PRIMARY KEY,
+                work TEXT NOT NULL,
+                state TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                error TEXT,
+                batch_id TEXT,
+                enqueued_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&client)
+        .await?;
+
+        // Queues created before batch annotation lack the column.
+        let has_batch_id: bool = query(
+            r#"SELECT count(*) > 0 FROM pragma_table_info('import_queue') WHERE name = 'batch_id'"#,
+        )
+        .fetch_one(&client)
+        .await?
+        .try_get(0)?;
+        if !has_batch_id {
+            query(r#"ALTER TABLE import_queue ADD COLUMN batch_id TEXT"#)
+                .execute(&client)
+                .await?;
+        }
+        query(r#"CREATE INDEX IF NOT EXISTS import_queue_state ON import_queue (state)"#)
+            .execute(&client)
+            .await?;
+
+        Ok(Self { client })
+    }
+
+    /// Adds `work` as pending, returning how much of it was not queued before.
+    pub async fn enqueue(
+        &self,
+        work: impl IntoIterator<Item = QueuedWork>,
+    ) -> Result<u64, ImportQueueError> {
+        let now = Utc::now().timestamp();
+        let mut transaction = self.client.begin().await?;
+        let mut queued = 0;
+        for work in work {
+            queued += query(
+                r#"INSERT OR IGNORE INTO import_queue
+                    (key, work, state, enqueued_at, updated_at)
+                    VALUES
+                    ($1, $2, 'pending', $3, $3)"#,
+            )
+            .bind(work.key())
+            .bind(serde_json::to_string(&work)?)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
+        }
+        transaction.commit().await?;
+        Ok(queued)
+    }
+
+    /// The oldest pending work, up to `limit` items.
+    pub async fn pending(&self, limit: u32) -> Result<Vec<QueuedItem>, ImportQueueError> {
+        query(
+            r#"SELECT key, work FROM import_queue WHERE state = 'pending' ORDER BY rowid LIMIT $1"#,
+        )
+        .bind(limit)
+        .fetch_all(&self.client)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok(QueuedItem {
+                key: row.try_get("key")?,
+                work: serde_json::from_str(row.try_get("work")?)?,
+            })
+        })
+        .collect()
+    }
+
+    /// The oldest pending sessions, up to `limit`, leaving queued memories aside.
+    pub async fn pending_sessions(&self, limit: u32) -> Result<Vec<QueuedItem>, ImportQueueError> {
+        query(
+            r#"SELECT key, work FROM import_queue
+                WHERE state = 'pending' AND key LIKE 'session:%' ORDER BY rowid LIMIT $1"#,
+        )
+        .bind(limit)
+        .fetch_all(&self.client)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok(QueuedItem {
+                key: row.try_get("key")?,
+                work: serde_json::from_str(row.try_get("work")?)?,
+            })
+        })
+        .collect()
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Option<QueuedItem>, ImportQueueError> {
+        query(r#"SELECT key, work FROM import_queue WHERE key = $1"#)
+            .bind(key)
+            .fetch_optional(&self.client)
+            .await?
+            .map(|row| {
+                Ok(QueuedItem {
+                    key: row.try_get("key")?,
+                    work: serde_json::from_str(row.try_get("work")?)?,
+                })
+            })
+            .transpose()
+    }
+
+    /// Records that `keys` are being answered by the provider batch `batch_id`.
+    pub async fn submit(&self, keys: &[String], batch_id: &str) -> Result<(), ImportQueueError> {
+        let now = Utc::now().timestamp();
+        let mut transaction = self.client.begin().await?;
+        for key in keys {
+            query(
+                r#"UPDATE import_queue SET state = 'submitted', batch_id = $1, updated_at = $2
+                    WHERE key = $3"#,
+            )
+            .bind(batch_id)
+            .bind(now)
+            .bind(key)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Provider batches that still have work waiting on them.
+    pub async fn submitted_batches(&self) -> Result<Vec<String>, ImportQueueError> {
+        query(
+            r#"SELECT DISTINCT batch_id FROM import_queue
+                WHERE state = 'submitted' AND batch_id IS NOT NULL"#,
+        )
+        .fetch_all(&self.client)
+        .await?
+        .iter()
+        .map(|row| Ok(row.try_get("batch_id")?))
+        .collect()
+    }
+
+    /// Fails the work of `batch_id` that has no result yet, returning how much there was.
+    pub async fn fail_batch(&self, batch_id: &str, error: &str) -> Result<u64, ImportQueueError> {
+        let result = query(
+            r#"UPDATE import_queue
+                SET state = 'failed', attempts = attempts + 1, error = $1, updated_at = $2
+                WHERE batch_id = $3 AND state = 'submitted'"#,
+        )
+        .bind(error)
+        .bind(Utc::now().timestamp())
+        .bind(batch_id)
+        .execute(&self.client)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn complete(&self, key: &str) -> Result<(), ImportQueueError> {
+        query(r#"UPDATE import_queue SET state = 'done', error = NULL, updated_at = $1 WHERE key = $2"#)
+            .bind(Utc::now().timestamp())
+            .bind(key)
+            .execute(&self.client)
+            .await?;
+        Ok(())
+    }
+
+    /// Sets work aside with the error it failed with, until `retry_failed`.
+    pub async fn fail(&self, key: &str, error: &str) -> Result<(), ImportQueueError> {
+        query(
+            r#"UPDATE import_queue
+                SET state = 'failed', attempts = attempts + 1, error = $1, updated_at = $2
+                WHERE key = $3"#,
+        )
+        .bind(error)
+        .bind(Utc::now().timestamp())
+        .bind(key)
+        .execute(&self.client)
+        .await?;
+        Ok(())
+    }
+
+    /// Makes failed work pending again, returning how much there was.
+    pub async fn retry_failed(&self) -> Result<u64, ImportQueueError> {
+        let result = query(
+            r#"UPDATE import_queue SET state = 'pending', updated_at = $1 WHERE state = 'failed'"#,
+        )
+        .bind(Utc::now().timestamp())
+        .execute(&self.client)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn status(&self) -> Result<ImportQueueStatus, ImportQueueError> {
+        let mut status = ImportQueueStatus::default();
+        let rows = query(r#"SELECT state, count(*) AS count FROM import_queue GROUP BY state"#)
+            .fetch_all(&self.client)
+            .await?;
+        for row in rows {
+            let count: i64 = row.try_get("count")?;
+            match row.try_get::<&str, _>("state")? {
+                "pending" => status.pending = count,
+                "submitted" => status.submitted = count,
+                "done" => status.done = count,
+                _ => status.failed = count,
+            }
+        }
+        Ok(status)
+    }
+
+    /// The most recent failures as (key, attempts, error).
+    pub async fn failures(
+        &self,
+        limit: u32,
+    ) -> Result<Vec<(String, i64, String)>, ImportQueueError> {
+        query(
+            r#"SELECT key, attempts, error FROM import_queue
+                WHERE state = 'failed' ORDER BY updated_at DESC LIMIT $1"#,
+        )
+        .bind(limit)
+        .fetch_all(&self.client)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok((
+                row.try_get("key")?,
+                row.try_get("attempts")?,
+                row.try_get::<Option<String>, _>("error")?
+                    .unwrap_or_default(),
+            ))
+        })
+        .collect()
+    }
+}
+
+#[derive(TypedBuilder)]
+pub struct DrainImportQueueRequest {
+    /// Items taken from the queue at a time; sessions in a batch are annotated
+    /// concurrently.
+    #[builder(default = 16)]
+    batch_size: u32,
+    /// Upper bound on annotation calls per minute, for the language model's rate limit.
+    #[builder(default = None)]
+    requests_per_minute: Option<u32>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImportQueueProgress {
+    pub done: usize,
+    pub failed: usize,
+    /// Memories stored, including those annotated from sessions.
+    pub memories: usize,
+}
+
+impl MemoryController {
+    /// Works through the pending work of `queue` until none is left. Work that fails is
+    /// marked failed with its error and the rest carries on.
+    pub async fn drain_import_queue(
+        &self,
+        queue: &ImportQueue,
+        request: DrainImportQueueRequest,
+    ) -> Result<ImportQueueProgress, MemoryControllerError> {
+        Ok(self.drain_import_queue_impl(queue, request).await?)
+    }
+
+    pub(crate) async fn drain_import_queue_impl(
+        &self,
+        queue: &ImportQueue,
+        request: DrainImportQueueRequest,
+    ) -> Result<ImportQueueProgress, ImportQueueError> {
+        let limiter = rate_limiter(request.requests_per_minute);
+        let mut progress = ImportQueueProgress::default();
+
+        loop {
+            let items = queue.pending(request.batch_size.max(1)).await?;
+            if items.is_empty() {
+                break;
+            }
+
+            let mut memory_keys = Vec::new();
+            let mut memories = Vec::new();
+            let mut sessions = Vec::new();
+            for item in items {
+                match item.work {
+                    QueuedWork::Memory(memory) => {
+                        memory_keys.push(item.key);
+                        memories.push(memory);
+                    }
+                    QueuedWork::Session { user_id, session } => {
+                        sessions.push((item.key, user_id, session));
+                    }
+                }
+            }
+
+            if !memories.is_empty() {
+                let batch_size = memories.len();
+                match self.import_impl(memories, batch_size).await {
+                    Ok(imported) => {
+                        for key in &memory_keys {
+                            queue.complete(key).await?;
+                        }
+                        progress.done += memory_keys.len();
+                        progress.memories += imported.imported;
+                    }
+                    Err(e) => {
+                        warn!("importing {} queued memories failed: {}", batch_size, e);
+                        for key in &memory_keys {
+                            queue.fail(key, &e.to_string()).await?;
+                        }
+                        progress.failed += memory_keys.len();
+                    }
+                }
+            }
+
+            let mut pending = Vec::new();
+            for (key, user_id, session) in &sessions {
+                if self.session_imported(user_id, session).await? {
+                    queue.complete(key).await?;
+                    progress.done += 1;
+                } else {
+                    pending.push((key, user_id, session));
+                }
+            }
+
+            let results = paced(limiter.as_ref(), &pending, |(_, user_id, session)| {
+                self.annotate_session(user_id, session)
+            })
+            .await;
+
+            for ((key, ..), result) in pending.iter().zip(results) {
+                match result {
+                    Ok(memories) => {
+                        queue.complete(key).await?;
+                        progress.done += 1;
+                        progress.memories += memories;
+                    }
+                    Err(e) => {
+                        warn!("importing queued session {} failed: {}", key, e);
+                        queue.fail(key, &e.to_string()).await?;
+                        progress.failed += 1;
+                    }
+                }
+            }
+
+            let status = queue.status().await?;
+            info!(
+                "import queue: {} done, {} failed, {} pending",
+                progress.done, progress.failed, status.pending
+            );
+        }
+
+        Ok(progress)
+    }
+}
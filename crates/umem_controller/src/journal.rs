@@ -0,0 +1,211 @@
+use super::{
+    CreateMemoryRequest, CreateMemoryRequestError, MemoryController, MemoryControllerError,
+    TenancyError,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+use tokio::{fs, io::AsyncWriteExt, sync::Mutex};
+use tracing::{info, warn};
+use umem_auth::Access;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum JournalError {
+    #[error("journal is not configured")]
+    NotConfigured,
+
+    #[error("journal file action failed with: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("journal entry could not be encoded: {0}")]
+    SerdeError(#[from] serde_json::Error),
+
+    #[error("memory request --build-- failed with: {0}")]
+    CreateMemoryRequestError(#[from] CreateMemoryRequestError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: Uuid,
+    pub recorded_at: i64,
+    pub request: CreateMemoryRequest,
+}
+
+/// Append-only file of create requests that have not reached the stores yet, one JSON
+/// entry per line. Entries are replayed at least once: a crash during a flush replays
+/// the whole batch again.
+#[derive(Debug)]
+pub struct Journal {
+    path: PathBuf,
+    /// Serializes appends with the swap a flush starts with.
+    lock: Mutex<()>,
+}
+
+impl Journal {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn from_config(config: &umem_config::Journal) -> Arc<Journal> {
+        Arc::new(Self::new(&config.path))
+    }
+
+    /// The batch being replayed. It only exists during a flush, or after one was
+    /// interrupted.
+    fn flushing_path(&self) -> PathBuf {
+        self.path.with_extension("flushing")
+    }
+
+    /// Writes `entries` to the end of the journal and syncs them to disk.
+    async fn append(&self, entries: &[JournalEntry]) -> Result<(), JournalError> {
+        let mut lines = Vec::new();
+        for entry in entries {
+            serde_json::to_writer(&mut lines, entry)?;
+            lines.push(b'\n');
+        }
+
+        let _guard = self.lock.lock().await;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(&lines).await?;
+        file.sync_data().await?;
+        Ok(())
+    }
+
+    /// Moves the journal aside and returns its entries, or `None` when there is
+    /// nothing to flush. A batch left over from an interrupted flush is returned
+    /// first, before new entries are taken.
+    async fn take(&self) -> Result<Option<Vec<JournalEntry>>, JournalError> {
+        let _guard = self.lock.lock().await;
+        let flushing = self.flushing_path();
+        if !fs::try_exists(&flushing).await? {
+            match fs::rename(&self.path, &flushing).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(Some(Self::read(&flushing).await?))
+    }
+
+    async fn read(path: &Path) -> Result<Vec<JournalEntry>, JournalError> {
+        let contents = fs::read_to_string(path).await?;
+        let mut entries = Vec::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                // NOTE: a torn last line from a crash mid-append
+                Err(e) => warn!("skipping unreadable journal entry: {}", e),
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn finish(&self) -> Result<(), JournalError> {
+        Ok(fs::remove_file(self.flushing_path()).await?)
+    }
+
+    /// How many requests wait for a flush, counting a batch left over from an
+    /// interrupted one.
+    pub(crate) async fn pending(&self) -> Result<usize, JournalError> {
+        let mut pending = 0;
+        for path in [self.path.clone(), self.flushing_path()] {
+            match fs::read_to_string(&path).await {
+                Ok(contents) => {
+                    pending += contents
+                        .lines()
+                        .filter(|line| !line.trim().is_empty())
+                        .count()
+                }
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(pending)
+    }
+}
+
+impl MemoryController {
+    /// Records a create request in the journal and returns its entry id without
+    /// touching the stores; `flush_journal` creates the memories later. The request
+    /// is validated and authorized now, so only store failures are left to retry.
+    pub async fn enqueue_create(
+        &self,
+        request: CreateMemoryRequest,
+    ) -> Result<Uuid, MemoryControllerError> {
+        Ok(self.enqueue_create_impl(request).await?)
+    }
+
+    /// Creates the memories for every journaled request, keeping the ones that fail
+    /// for the next flush. Returns how many requests were flushed.
+    pub async fn flush_journal(&self) -> Result<usize, MemoryControllerError> {
+        Ok(self.flush_journal_impl().await?)
+    }
+
+    async fn enqueue_create_impl(
+        &self,
+        request: CreateMemoryRequest,
+    ) -> Result<Uuid, JournalError> {
+        let journal = self.journal.as_ref().ok_or(JournalError::NotConfigured)?;
+        request.validate()?;
+        self.guard(
+            &request.context().map_err(CreateMemoryRequestError::from)?,
+            Access::ReadWrite,
+        )?;
+
+        let entry = JournalEntry {
+            id: Uuid::new_v4(),
+            recorded_at: Utc::now().timestamp(),
+            request,
+        };
+        journal.append(std::slice::from_ref(&entry)).await?;
+        Ok(entry.id)
+    }
+
+    async fn flush_journal_impl(&self) -> Result<usize, JournalError> {
+        let journal = self.journal.as_ref().ok_or(JournalError::NotConfigured)?;
+        self.guard_global()?;
+
+        let Some(entries) = journal.take().await? else {
+            return Ok(0);
+        };
+
+        let mut flushed = 0;
+        let mut failed = Vec::new();
+        for entry in entries {
+            match self.create(entry.request.clone(), None).await {
+                Ok(_) => flushed += 1,
+                Err(e) => {
+                    warn!("journal entry {} could not be flushed: {}", entry.id, e);
+                    failed.push(entry);
+                }
+            }
+        }
+
+        if !failed.is_empty() {
+            journal.append(&failed).await?;
+        }
+        journal.finish().await?;
+
+        info!(
+            "flushed {flushed} journaled requests, {} left for the next flush",
+            failed.len()
+        );
+        Ok(flushed)
+    }
+}
@@ -2,19 +2,77 @@ use std::sync::Arc;
 
 use thiserror::Error;
 
+mod admin;
+mod budget;
+mod compensation;
+mod context_pack;
 mod create_memory;
 mod delete_memory;
+mod export_memory;
 mod get_memory;
+mod graph_query;
+mod health;
+mod import_batch;
+mod import_conversations;
+mod import_memory;
+mod import_queue;
+mod journal;
 mod list_memory;
+mod memory_changes;
+mod memory_stats;
+mod memory_template;
+mod memory_usage;
+mod moderation;
+mod publish_memory;
+mod reembed;
+mod regenerate_memory;
+mod response_policy;
+mod saved_view;
+mod score_fusion;
 mod search_memory;
+mod summarize_session;
+mod tag_memory;
+mod tenancy;
+#[cfg(test)]
+mod testing;
+mod topic_clustering;
 mod update_memory;
 
+pub use admin::*;
+pub(crate) use compensation::{Compensation, CompensationGuard};
+pub use context_pack::*;
 pub use create_memory::*;
 pub use delete_memory::*;
+pub use export_memory::*;
 pub use get_memory::*;
+pub use graph_query::*;
+pub use health::*;
+pub use import_batch::*;
+pub use import_conversations::*;
+pub use import_memory::*;
+pub use import_queue::*;
+pub use journal::*;
 pub use list_memory::*;
+pub use memory_changes::*;
+pub use memory_stats::*;
+pub use memory_template::*;
+pub use memory_usage::*;
+pub use moderation::*;
+pub use publish_memory::*;
+pub use reembed::*;
+pub use regenerate_memory::*;
+pub use response_policy::*;
+pub use saved_view::*;
 pub use search_memory::*;
-use umem_ai::{EmbeddingModel, LanguageModel, RerankingModel};
+pub use summarize_session::*;
+pub use tag_memory::*;
+pub use tenancy::*;
+pub use topic_clustering::*;
+use umem_ai::{EmbeddingModel, LanguageModel, RerankingModel, SpendTracker};
+use umem_annotations::{AnnotationPrompt, Translator};
+use umem_auth::ApiKey;
+use umem_blob::BlobStoreBase;
+use umem_graph::GraphStoreBase;
 use umem_vector_store::VectorStoreBase;
 pub use update_memory::*;
 
@@ -37,6 +95,63 @@ pub enum MemoryControllerError {
 
     #[error("search memory failed with: {0}")]
     SearchMemoryError(#[from] SearchMemoryError),
+
+    #[error("graph query failed with: {0}")]
+    GraphQueryError(#[from] GraphQueryError),
+
+    #[error("summarize session failed with: {0}")]
+    SummarizeSessionError(#[from] SummarizeSessionError),
+
+    #[error("build context failed with: {0}")]
+    ContextPackError(#[from] ContextPackError),
+
+    #[error("re-embedding failed with: {0}")]
+    ReembedError(#[from] ReembedError),
+
+    #[error("regenerate memory failed with: {0}")]
+    RegenerateMemoryError(#[from] RegenerateMemoryError),
+
+    #[error("tag management failed with: {0}")]
+    TagMemoryError(#[from] TagMemoryError),
+
+    #[error("memory usage update failed with: {0}")]
+    MemoryUsageError(#[from] MemoryUsageError),
+
+    #[error("journal action failed with: {0}")]
+    JournalError(#[from] JournalError),
+
+    #[error("publish memory failed with: {0}")]
+    PublishMemoryError(#[from] PublishMemoryError),
+
+    #[error("export failed with: {0}")]
+    ExportError(#[from] ExportError),
+
+    #[error("import failed with: {0}")]
+    ImportError(#[from] ImportError),
+
+    #[error("conversation import failed with: {0}")]
+    ConversationImportError(#[from] ConversationImportError),
+
+    #[error("import queue action failed with: {0}")]
+    ImportQueueError(#[from] ImportQueueError),
+
+    #[error("batch import failed with: {0}")]
+    BatchImportError(#[from] BatchImportError),
+
+    #[error("memory stats failed with: {0}")]
+    MemoryStatsError(#[from] MemoryStatsError),
+
+    #[error("admin action failed with: {0}")]
+    AdminError(#[from] AdminError),
+
+    #[error("topic clustering failed with: {0}")]
+    TopicClusteringError(#[from] TopicClusteringError),
+
+    #[error("saved view action failed with: {0}")]
+    SavedViewError(#[from] SavedViewError),
+
+    #[error("memory changes failed with: {0}")]
+    MemoryChangesError(#[from] MemoryChangesError),
 }
 
 #[derive(Clone)]
@@ -45,4 +160,33 @@ pub struct MemoryController {
     pub embedding_model: Arc<EmbeddingModel>,
     pub reranking_model: Arc<RerankingModel>,
     pub language_model: Arc<LanguageModel>,
+    pub annotation_prompt: Arc<AnnotationPrompt>,
+    pub translator: Option<Translator>,
+    /// Whether created memories keep the content they were annotated from, for
+    /// `regenerate` and audits.
+    pub keep_raw_content: bool,
+    /// Where raw content too large to keep in the memory goes. Unset keeps all of it
+    /// inline.
+    pub blob_store: Option<Arc<dyn BlobStoreBase + Send + Sync>>,
+    pub graph: Option<Arc<dyn GraphStoreBase + Send + Sync>>,
+    pub response_policy: Arc<ResponsePolicy>,
+    pub search_config: Arc<umem_config::Search>,
+    pub salience_config: Arc<umem_config::Salience>,
+    pub trash_config: Arc<umem_config::Trash>,
+    pub topics_config: Arc<umem_config::Topics>,
+    /// Caps on estimated provider spend, past which memories are stored with fewer
+    /// model calls.
+    pub budget: Arc<umem_config::Budget>,
+    /// What the priced models of this controller have spent, checked against `budget`.
+    pub spend: Arc<SpendTracker>,
+    /// Checked against structured memories before they are stored.
+    pub templates: Arc<MemoryTemplates>,
+    /// Checked against memories before they are stored. Unset stores them unmoderated.
+    pub moderation: Option<Arc<ModerationPolicy>>,
+    /// Where create requests wait when they are queued with `enqueue_create`.
+    pub journal: Option<Arc<Journal>>,
+    /// Where `save_view` keeps named searches. Unset refuses saved views.
+    pub views: Option<Arc<ViewStore>>,
+    /// The caller every operation is checked against. Set with `for_principal`.
+    pub principal: Option<Arc<ApiKey>>,
 }
@@ -1,8 +1,13 @@
 use std::sync::Arc;
 
-use super::{MemoryController, MemoryControllerError};
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use chrono::{DateTime, Utc};
 use thiserror::Error;
-use umem_core::{Memory, MemoryContext, MemoryContextError, Query};
+use umem_auth::Access;
+use umem_core::{
+    Memory, MemoryContext, MemoryContextError, MemoryFacets, MemoryId, MemoryKind,
+    MemoryScheduleError, Query, UserId,
+};
 use umem_vector_store::VectorStoreError;
 
 #[derive(Debug, Error)]
@@ -12,22 +17,28 @@ pub enum ListMemoryError {
 
     #[error("memory context action failed with: {0}")]
     MemoryContextError(#[from] MemoryContextError),
+
+    #[error("memory schedule action failed with: {0}")]
+    MemoryScheduleError(#[from] MemoryScheduleError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
 }
 
 impl MemoryController {
     pub async fn list_for_user(
         &self,
-        user_id: String,
+        user_id: UserId,
     ) -> Result<Vec<Memory>, MemoryControllerError> {
         Ok(self.list_for_user_impl(user_id).await?)
     }
 
-    async fn list_for_user_impl(&self, user_id: String) -> Result<Vec<Memory>, ListMemoryError> {
+    async fn list_for_user_impl(&self, user_id: UserId) -> Result<Vec<Memory>, ListMemoryError> {
+        let context = MemoryContext::for_user(user_id)?;
+        self.guard(&context, Access::Read)?;
+
         let vector_store = Arc::clone(&self.vector_store);
-        let query = Query::builder()
-            .context(MemoryContext::for_user(user_id)?)
-            .limit(1000)
-            .build();
+        let query = Query::builder().context(context).limit(1000).build();
 
         Ok(vector_store.list(query).await?)
     }
@@ -43,9 +54,106 @@ impl MemoryController {
         &self,
         context: MemoryContext,
     ) -> Result<Vec<Memory>, ListMemoryError> {
+        self.guard(&context, Access::Read)?;
         let vector_store = Arc::clone(&self.vector_store);
         let query = Query::builder().context(context).limit(1000).build();
 
         Ok(vector_store.list(query).await?)
     }
+
+    /// Counts the memories in `context` by kind, tag and creation month, to accompany
+    /// list and search results.
+    pub async fn facets_with_context(
+        &self,
+        context: MemoryContext,
+    ) -> Result<MemoryFacets, MemoryControllerError> {
+        Ok(self.facets_with_context_impl(context).await?)
+    }
+
+    async fn facets_with_context_impl(
+        &self,
+        context: MemoryContext,
+    ) -> Result<MemoryFacets, ListMemoryError> {
+        self.guard(&context, Access::Read)?;
+        let vector_store = Arc::clone(&self.vector_store);
+        let query = Query::builder().context(context).limit(1000).build();
+
+        Ok(vector_store.facets(query).await?)
+    }
+
+    /// Returns the scheduled Prospective memories that are due at `now`, soonest first.
+    /// Recurring ones stay due until `acknowledge_due` advances them; one-off reminders
+    /// stay due until they are archived or deleted.
+    pub async fn due_prospective(
+        &self,
+        context: MemoryContext,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Memory>, MemoryControllerError> {
+        Ok(self.due_prospective_impl(context, now).await?)
+    }
+
+    /// Advances the recurring schedules among `ids` that are due at `now` to their next
+    /// occurrence, so `due_prospective` returns each occurrence once. Memories that are
+    /// not due, e.g. because another caller acknowledged them first, are left alone.
+    pub async fn acknowledge_due(
+        &self,
+        ids: Vec<MemoryId>,
+        now: DateTime<Utc>,
+    ) -> Result<(), MemoryControllerError> {
+        Ok(self.acknowledge_due_impl(ids, now).await?)
+    }
+
+    async fn due_prospective_impl(
+        &self,
+        context: MemoryContext,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Memory>, ListMemoryError> {
+        self.guard(&context, Access::Read)?;
+        let vector_store = Arc::clone(&self.vector_store);
+        let query = Query::builder()
+            .context(context)
+            .kinds(vec![MemoryKind::Prospective])
+            .limit(1000)
+            .build();
+
+        let mut due: Vec<Memory> = vector_store
+            .list(query)
+            .await?
+            .into_iter()
+            .filter(|memory| {
+                memory
+                    .schedule()
+                    .is_some_and(|schedule| schedule.is_due(now))
+            })
+            .collect();
+        due.sort_by_key(|memory| memory.schedule().map(|schedule| schedule.due_at()));
+
+        Ok(due)
+    }
+
+    async fn acknowledge_due_impl(
+        &self,
+        ids: Vec<MemoryId>,
+        now: DateTime<Utc>,
+    ) -> Result<(), ListMemoryError> {
+        for id in ids {
+            let mut memory = self.vector_store.get(&id).await?;
+            self.guard_memory(&memory, Access::ReadWrite)?;
+            let Some(schedule) = memory
+                .schedule()
+                .copied()
+                .filter(|schedule| schedule.is_due(now))
+            else {
+                continue;
+            };
+            let Some(next) = schedule.next_after(now)? else {
+                continue;
+            };
+
+            memory.reschedule(Some(next), now);
+            self.vector_store.update(&id, None, Some(&memory)).await?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,72 @@
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use chrono::Utc;
+use serde::Serialize;
+use thiserror::Error;
+use umem_auth::Access;
+use umem_core::{Memory, MemoryContext};
+use umem_vector_store::VectorStoreError;
+
+/// Memories read per page of the store's change scroll.
+const CHANGES_PAGE_SIZE: u32 = 256;
+
+#[derive(Debug, Error)]
+pub enum MemoryChangesError {
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+/// What changed in a context since a client last synced, so it can update a local copy
+/// instead of listing everything again.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MemoryChanges {
+    /// Memories created, updated, archived, deleted or suppressed after `since`, in no
+    /// particular order. Deleted and suppressed ones carry `deleted_at` or
+    /// `suppressed_at`, so caches can drop them; memories deleted without `[trash]` are
+    /// gone from the store and not reported.
+    pub memories: Vec<Memory>,
+    /// Pass as `since` next time. Memories changed during that second may be returned
+    /// again, so apply changes idempotently.
+    pub until: i64,
+}
+
+impl MemoryController {
+    /// Memories of `context` that changed after `since` (unix seconds); 0 returns every
+    /// memory, including archived and trashed ones.
+    pub async fn changes(
+        &self,
+        context: MemoryContext,
+        since: i64,
+    ) -> Result<MemoryChanges, MemoryControllerError> {
+        Ok(self.changes_impl(context, since).await?)
+    }
+
+    async fn changes_impl(
+        &self,
+        context: MemoryContext,
+        since: i64,
+    ) -> Result<MemoryChanges, MemoryChangesError> {
+        self.guard(&context, Access::Read)?;
+
+        // NOTE: a change later in the current second can land behind the scroll, so the
+        // next sync starts again from the second before
+        let until = Utc::now().timestamp() - 1;
+        let mut memories = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (page, next) = self
+                .vector_store
+                .scroll_changed(Some(&context), since, cursor, CHANGES_PAGE_SIZE)
+                .await?;
+            memories.extend(page);
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(MemoryChanges { memories, until })
+    }
+}
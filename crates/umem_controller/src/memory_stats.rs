@@ -0,0 +1,117 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use serde::Serialize;
+use thiserror::Error;
+use umem_auth::Access;
+use umem_core::{FacetCount, Memory, MemoryContext, Query};
+use umem_vector_store::VectorStoreError;
+
+/// Most memories `stats` reads; counts of larger contexts stop there.
+const STATS_MEMORIES_LIMIT: u32 = 10_000;
+
+#[derive(Debug, Error)]
+pub enum MemoryStatsError {
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("memory could not be measured: {0}")]
+    SerdeError(#[from] serde_json::Error),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+/// What is stored for a context, shown back to its user. Deleted and suppressed
+/// memories are not counted.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MemoryStats {
+    pub total: u64,
+    /// Most frequent first.
+    pub kinds: Vec<FacetCount>,
+    /// Most frequent first.
+    pub lifecycles: Vec<FacetCount>,
+    /// Size of the stored memories, not counting embeddings or blob store content.
+    pub storage_bytes: u64,
+    /// Memories created per month as `YYYY-MM` (UTC), newest first.
+    pub months: Vec<FacetCount>,
+    /// When a memory was last created, updated, archived or used, in unix seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_activity_at: Option<i64>,
+}
+
+impl MemoryStats {
+    fn of(memories: &[Memory]) -> Result<Self, MemoryStatsError> {
+        let mut kinds = HashMap::new();
+        let mut lifecycles = HashMap::new();
+        let mut months = HashMap::new();
+        let mut storage_bytes = 0;
+        let mut last_activity_at = None;
+
+        for memory in memories {
+            *kinds.entry(memory.kind().as_str().to_string()).or_insert(0) += 1;
+            *lifecycles
+                .entry(memory.lifecycle().as_str().to_string())
+                .or_insert(0) += 1;
+            *months.entry(memory.temporal().created_month()).or_insert(0) += 1;
+            storage_bytes += serde_json::to_vec(memory)?.len() as u64;
+
+            let temporal = memory.temporal();
+            let activity = [
+                Some(temporal.created_at()),
+                temporal.updated_at(),
+                temporal.archived_at(),
+                memory.usage().last_used_at(),
+            ]
+            .into_iter()
+            .flatten()
+            .max();
+            last_activity_at = last_activity_at.max(activity);
+        }
+
+        let mut months = counts(months);
+        months.sort_by(|a, b| b.value.cmp(&a.value));
+
+        Ok(Self {
+            total: memories.len() as u64,
+            kinds: counts(kinds),
+            lifecycles: counts(lifecycles),
+            storage_bytes,
+            months,
+            last_activity_at,
+        })
+    }
+}
+
+/// Most frequent first, ties by value.
+fn counts(counts: HashMap<String, u64>) -> Vec<FacetCount> {
+    let mut counts: Vec<FacetCount> = counts
+        .into_iter()
+        .map(|(value, count)| FacetCount { value, count })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    counts
+}
+
+impl MemoryController {
+    /// Counts the memories in `context` by kind, lifecycle state and creation month,
+    /// with their size and when they were last touched.
+    pub async fn stats(
+        &self,
+        context: MemoryContext,
+    ) -> Result<MemoryStats, MemoryControllerError> {
+        Ok(self.stats_impl(context).await?)
+    }
+
+    async fn stats_impl(&self, context: MemoryContext) -> Result<MemoryStats, MemoryStatsError> {
+        self.guard(&context, Access::Read)?;
+        let vector_store = Arc::clone(&self.vector_store);
+        let query = Query::builder()
+            .context(context)
+            .include_archived(true)
+            .limit(STATS_MEMORIES_LIMIT)
+            .build();
+
+        MemoryStats::of(&vector_store.list(query).await?)
+    }
+}
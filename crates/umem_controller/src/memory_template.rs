@@ -0,0 +1,158 @@
+use std::{collections::HashMap, str::FromStr};
+
+use thiserror::Error;
+use umem_core::{Memory, MemoryKind, ParseMemoryKindError};
+
+/// The template field set through a memory's schedule rather than its fields.
+pub const DUE_AT_FIELD: &str = "due_at";
+
+#[derive(Debug, Error)]
+pub enum MemoryTemplateError {
+    #[error("template kind is invalid: {0}")]
+    MemoryKindError(#[from] ParseMemoryKindError),
+
+    #[error("{kind} memories need the {field} field")]
+    MissingField { kind: &'static str, field: String },
+
+    #[error("{kind} memories have no {field} field")]
+    UnknownField { kind: &'static str, field: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateField {
+    pub name: String,
+    pub required: bool,
+    pub description: String,
+}
+
+impl TemplateField {
+    fn new(name: &str, required: bool, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            required,
+            description: description.to_string(),
+        }
+    }
+}
+
+/// Fields a structured memory of `kind` is created with, so clients know what to
+/// supply and incomplete memories are rejected before they are stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryTemplate {
+    pub kind: MemoryKind,
+    pub fields: Vec<TemplateField>,
+}
+
+impl MemoryTemplate {
+    pub fn validate(&self, memory: &Memory) -> Result<(), MemoryTemplateError> {
+        let kind = self.kind.as_str();
+        for field in self.fields.iter().filter(|field| field.required) {
+            let present = match field.name.as_str() {
+                DUE_AT_FIELD => memory.schedule().is_some(),
+                name => memory
+                    .fields()
+                    .get(name)
+                    .is_some_and(|value| !value.trim().is_empty()),
+            };
+            if !present {
+                return Err(MemoryTemplateError::MissingField {
+                    kind,
+                    field: field.name.clone(),
+                });
+            }
+        }
+
+        if let Some(name) = memory
+            .fields()
+            .keys()
+            .find(|name| !self.fields.iter().any(|field| &field.name == *name))
+        {
+            return Err(MemoryTemplateError::UnknownField {
+                kind,
+                field: name.clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Templates by kind. Kinds without one take any fields.
+#[derive(Debug, Clone)]
+pub struct MemoryTemplates(Vec<MemoryTemplate>);
+
+impl Default for MemoryTemplates {
+    fn default() -> Self {
+        Self(vec![
+            MemoryTemplate {
+                kind: MemoryKind::Prospective,
+                fields: vec![TemplateField::new(
+                    DUE_AT_FIELD,
+                    true,
+                    "When the plan or reminder is due, as an RFC 3339 timestamp",
+                )],
+            },
+            MemoryTemplate {
+                kind: MemoryKind::Relational,
+                fields: vec![
+                    TemplateField::new("subject", true, "Who or what the relation starts from"),
+                    TemplateField::new("predicate", false, "How the subject relates to the object"),
+                    TemplateField::new("object", true, "Who or what the subject relates to"),
+                ],
+            },
+        ])
+    }
+}
+
+impl MemoryTemplates {
+    /// The built-in templates with `[templates]` applied on top.
+    pub fn from_config(
+        config: &HashMap<String, umem_config::Template>,
+    ) -> Result<Self, MemoryTemplateError> {
+        config
+            .iter()
+            .try_fold(Self::default(), |templates, (kind, template)| {
+                Ok(templates.with_template(MemoryTemplate {
+                    kind: MemoryKind::from_str(kind)?,
+                    fields: template
+                        .fields
+                        .iter()
+                        .map(|field| TemplateField {
+                            name: field.name.trim().to_string(),
+                            required: field.required,
+                            description: field.description.clone().unwrap_or_default(),
+                        })
+                        .collect(),
+                }))
+            })
+    }
+
+    /// Replaces the template of the same kind; a template without fields removes it.
+    pub fn with_template(mut self, template: MemoryTemplate) -> Self {
+        self.0.retain(|existing| existing.kind != template.kind);
+        if !template.fields.is_empty() {
+            self.0.push(template);
+        }
+        self.0.sort_by_key(|template| {
+            MemoryKind::all()
+                .iter()
+                .position(|kind| *kind == template.kind)
+        });
+        self
+    }
+
+    pub fn get(&self, kind: &MemoryKind) -> Option<&MemoryTemplate> {
+        self.0.iter().find(|template| template.kind == *kind)
+    }
+
+    pub fn all(&self) -> &[MemoryTemplate] {
+        &self.0
+    }
+
+    pub fn validate(&self, memory: &Memory) -> Result<(), MemoryTemplateError> {
+        match self.get(memory.kind()) {
+            Some(template) => template.validate(memory),
+            None => Ok(()),
+        }
+    }
+}
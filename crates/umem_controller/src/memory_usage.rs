@@ -0,0 +1,182 @@
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use tracing::info;
+use umem_auth::Access;
+use umem_core::{Memory, MemoryError, MemoryId};
+use umem_vector_store::VectorStoreError;
+
+const DEFAULT_USE_BOOST: f32 = 0.1;
+const DEFAULT_DECAY_RATE: f32 = 0.05;
+const DEFAULT_DECAY_AFTER_DAYS: u64 = 30;
+/// Decay never pushes salience below this, so unused memories fade but stay findable.
+const MIN_SALIENCE: f32 = 0.05;
+const DECAY_BATCH_SIZE: u32 = 256;
+
+#[derive(Debug, Error)]
+pub enum MemoryUsageError {
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("memory action failed with: {0}")]
+    MemoryError(#[from] MemoryError),
+
+    #[error("feedback query cannot be empty or whitespace")]
+    EmptyQuery,
+
+    #[error("signal adjustments must be finite and within [-1.0, 1.0], got {0}")]
+    AdjustmentOutOfRange(f32),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+impl MemoryController {
+    /// Records that an agent used the memories with `ids` after retrieving them,
+    /// raising their salience by `[salience] use_boost`.
+    pub async fn mark_used(
+        &self,
+        ids: Vec<MemoryId>,
+        now: DateTime<Utc>,
+    ) -> Result<(), MemoryControllerError> {
+        Ok(self.mark_used_impl(ids, now).await?)
+    }
+
+    /// Stores whether the memory `memory_id` helped answer `query` on the memory itself,
+    /// as training data for fusion weights and rerankers.
+    pub async fn feedback(
+        &self,
+        memory_id: MemoryId,
+        query: String,
+        helpful: bool,
+    ) -> Result<(), MemoryControllerError> {
+        Ok(self.feedback_impl(memory_id, query, helpful).await?)
+    }
+
+    /// Strengthens or weakens the memory `id` on new evidence, moving its certainty and
+    /// salience by the given deltas. Both stay within [0.0, 1.0], and the adjustment is
+    /// recorded on the memory with the api key that made it.
+    pub async fn adjust_signals(
+        &self,
+        id: MemoryId,
+        delta_certainty: f32,
+        delta_salience: f32,
+    ) -> Result<Memory, MemoryControllerError> {
+        Ok(self
+            .adjust_signals_impl(id, delta_certainty, delta_salience)
+            .await?)
+    }
+
+    /// Lowers the salience of active memories nobody used for `[salience]
+    /// decay_after_days`, returning how many changed. Meant to run periodically.
+    pub async fn decay_unused(&self, now: DateTime<Utc>) -> Result<usize, MemoryControllerError> {
+        Ok(self.decay_unused_impl(now).await?)
+    }
+
+    async fn mark_used_impl(
+        &self,
+        ids: Vec<MemoryId>,
+        now: DateTime<Utc>,
+    ) -> Result<(), MemoryUsageError> {
+        let boost = self.salience_config.use_boost.unwrap_or(DEFAULT_USE_BOOST);
+
+        for id in ids {
+            let mut memory = self.vector_store.get(&id).await?;
+            self.guard_memory(&memory, Access::ReadWrite)?;
+            memory.mark_used(now, boost)?;
+            self.vector_store.update(&id, None, Some(&memory)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn feedback_impl(
+        &self,
+        memory_id: MemoryId,
+        query: String,
+        helpful: bool,
+    ) -> Result<(), MemoryUsageError> {
+        if query.trim().is_empty() {
+            return Err(MemoryUsageError::EmptyQuery);
+        }
+
+        let mut memory = self.vector_store.get(&memory_id).await?;
+        self.guard_memory(&memory, Access::ReadWrite)?;
+        memory.add_feedback(query, helpful, Utc::now());
+        self.vector_store
+            .update(&memory_id, None, Some(&memory))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn adjust_signals_impl(
+        &self,
+        id: MemoryId,
+        delta_certainty: f32,
+        delta_salience: f32,
+    ) -> Result<Memory, MemoryUsageError> {
+        for delta in [delta_certainty, delta_salience] {
+            if !delta.is_finite() || !(-1.0..=1.0).contains(&delta) {
+                return Err(MemoryUsageError::AdjustmentOutOfRange(delta));
+            }
+        }
+
+        let mut memory = self.vector_store.get(&id).await?;
+        self.guard_memory(&memory, Access::ReadWrite)?;
+        let by = self
+            .principal
+            .as_ref()
+            .map(|principal| principal.name.clone());
+        memory.adjust_signals(delta_certainty, delta_salience, by, Utc::now())?;
+        self.vector_store.update(&id, None, Some(&memory)).await?;
+
+        Ok(memory)
+    }
+
+    async fn decay_unused_impl(&self, now: DateTime<Utc>) -> Result<usize, MemoryUsageError> {
+        self.guard_global()?;
+        let rate = self
+            .salience_config
+            .decay_rate
+            .unwrap_or(DEFAULT_DECAY_RATE);
+        let decay_after_days = self
+            .salience_config
+            .decay_after_days
+            .unwrap_or(DEFAULT_DECAY_AFTER_DAYS);
+        let cutoff = now.timestamp() - (decay_after_days * 86_400) as i64;
+
+        let mut decayed = 0;
+        let mut cursor = None;
+        loop {
+            let (memories, next) = self.vector_store.scroll(cursor, DECAY_BATCH_SIZE).await?;
+
+            for mut memory in memories {
+                let last_used = memory
+                    .usage()
+                    .last_used_at()
+                    .unwrap_or(memory.temporal().created_at());
+                if !memory.is_active() || last_used > cutoff {
+                    continue;
+                }
+
+                let salience = memory.signals().get_salience();
+                memory.decay_salience(rate, MIN_SALIENCE, now)?;
+                if memory.signals().get_salience() < salience {
+                    self.vector_store
+                        .update(&memory.memory_id(), None, Some(&memory))
+                        .await?;
+                    decayed += 1;
+                }
+            }
+
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        info!("decayed the salience of {decayed} unused memories");
+        Ok(decayed)
+    }
+}
@@ -0,0 +1,168 @@
+use std::{collections::HashMap, str::FromStr};
+
+use super::MemoryController;
+use regex::Regex;
+use thiserror::Error;
+use umem_ai::{moderate, ResponseGeneratorError};
+use umem_core::{Memory, Moderation};
+
+/// Moderator recorded on memories when no moderation model is configured.
+const RULES_MODERATOR: &str = "rules";
+
+#[derive(Debug, Error)]
+pub enum ModerationError {
+    #[error("invalid moderation action: {0} (expected reject, flag or allow)")]
+    InvalidAction(String),
+
+    #[error("moderation rule for {0} has an invalid pattern: {1}")]
+    InvalidPattern(String, regex::Error),
+
+    #[error("content was rejected by moderation for: {}", .0.join(", "))]
+    Rejected(Vec<String>),
+
+    #[error("moderation model failed with: {0}")]
+    ResponseGeneratorError(#[from] ResponseGeneratorError),
+}
+
+/// What happens to content flagged in a category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationAction {
+    /// Nothing is stored.
+    Reject,
+    /// Stored, with the category recorded in the memory's provenance.
+    Flag,
+    Allow,
+}
+
+impl FromStr for ModerationAction {
+    type Err = ModerationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "reject" => Ok(Self::Reject),
+            "flag" => Ok(Self::Flag),
+            "allow" => Ok(Self::Allow),
+            _ => Err(ModerationError::InvalidAction(s.to_string())),
+        }
+    }
+}
+
+/// Which content may be stored. Categories are found by the configured rules and, when
+/// one is set, the provider's moderation model.
+#[derive(Debug, Clone)]
+pub struct ModerationPolicy {
+    model: Option<String>,
+    rules: Vec<(String, Regex)>,
+    actions: HashMap<String, ModerationAction>,
+    default_action: ModerationAction,
+}
+
+impl ModerationPolicy {
+    pub fn from_config(config: &umem_config::Moderation) -> Result<Self, ModerationError> {
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .map(|pattern| (rule.category.clone(), pattern))
+                    .map_err(|e| ModerationError::InvalidPattern(rule.category.clone(), e))
+            })
+            .collect::<Result<_, _>>()?;
+        let actions = config
+            .actions
+            .iter()
+            .map(|(category, action)| Ok((category.clone(), ModerationAction::from_str(action)?)))
+            .collect::<Result<_, ModerationError>>()?;
+
+        Ok(Self {
+            model: config.model.clone(),
+            rules,
+            actions,
+            default_action: match config.default_action.as_deref() {
+                Some(action) => ModerationAction::from_str(action)?,
+                None => ModerationAction::Flag,
+            },
+        })
+    }
+
+    /// The action for `category`, falling back from a subcategory such as
+    /// "self-harm/intent" to its parent.
+    pub fn action_for(&self, category: &str) -> ModerationAction {
+        self.actions
+            .get(category)
+            .or_else(|| {
+                category
+                    .split_once('/')
+                    .and_then(|(parent, _)| self.actions.get(parent))
+            })
+            .copied()
+            .unwrap_or(self.default_action)
+    }
+
+    fn matching_rules(&self, text: &str) -> Vec<String> {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| pattern.is_match(text))
+            .map(|(category, _)| category.clone())
+            .collect()
+    }
+}
+
+/// The text of a memory that is moderated: its summary and field values.
+fn moderated_text(memory: &Memory) -> String {
+    let mut text = memory.get_summary().clone();
+    for value in memory.fields().values() {
+        text.push('\n');
+        text.push_str(value);
+    }
+    text
+}
+
+impl MemoryController {
+    /// Moderates memories about to be stored and records the decision in their
+    /// provenance. Fails when any of them is rejected, so a request is stored whole or
+    /// not at all.
+    pub(crate) async fn moderate(&self, memories: &mut [Memory]) -> Result<(), ModerationError> {
+        let Some(policy) = self.moderation.as_ref() else {
+            return Ok(());
+        };
+        if memories.is_empty() {
+            return Ok(());
+        }
+
+        let texts: Vec<String> = memories.iter().map(moderated_text).collect();
+        let mut categories: Vec<Vec<String>> = texts
+            .iter()
+            .map(|text| policy.matching_rules(text))
+            .collect();
+        if let Some(model) = &policy.model {
+            let model = self.language_model.with_model_name(model);
+            for (found, verdict) in categories.iter_mut().zip(moderate(&model, texts).await?) {
+                found.extend(verdict.categories);
+            }
+        }
+        let moderator = policy.model.as_deref().unwrap_or(RULES_MODERATOR);
+
+        let mut decisions = Vec::with_capacity(memories.len());
+        for mut found in categories {
+            found.sort();
+            found.dedup();
+            let (rejected, flagged): (Vec<String>, Vec<String>) = found
+                .into_iter()
+                .filter(|category| policy.action_for(category) != ModerationAction::Allow)
+                .partition(|category| policy.action_for(category) == ModerationAction::Reject);
+            if !rejected.is_empty() {
+                return Err(ModerationError::Rejected(rejected));
+            }
+            decisions.push(Moderation {
+                moderator: moderator.to_string(),
+                flagged,
+            });
+        }
+        for (memory, decision) in memories.iter_mut().zip(decisions) {
+            memory.set_moderation(decision);
+        }
+
+        Ok(())
+    }
+}
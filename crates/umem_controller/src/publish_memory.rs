@@ -0,0 +1,63 @@
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use chrono::Utc;
+use thiserror::Error;
+use umem_auth::Access;
+use umem_core::{Memory, MemoryContext, MemoryContextError, MemoryId, MemoryKind};
+use umem_vector_store::VectorStoreError;
+
+/// Kinds that hold knowledge worth sharing with a whole organization; the others are
+/// about one person's history or plans.
+const PUBLISHABLE_KINDS: &[MemoryKind] = &[MemoryKind::Instruction, MemoryKind::Semantic];
+
+#[derive(Debug, Error)]
+pub enum PublishMemoryError {
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("memory context action failed with: {0}")]
+    MemoryContextError(#[from] MemoryContextError),
+
+    #[error("{0} memories cannot be published, only instruction and semantic ones")]
+    UnpublishableKind(&'static str),
+
+    #[error("memory {0} is already published")]
+    AlreadyPublished(MemoryId),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+impl MemoryController {
+    /// Moves a memory into the pool of `org_id`, where every member of the organization
+    /// finds it next to their own memories.
+    pub async fn publish(
+        &self,
+        id: MemoryId,
+        org_id: String,
+    ) -> Result<Memory, MemoryControllerError> {
+        Ok(self.publish_impl(id, org_id).await?)
+    }
+
+    async fn publish_impl(
+        &self,
+        id: MemoryId,
+        org_id: String,
+    ) -> Result<Memory, PublishMemoryError> {
+        let mut memory = self.vector_store.get(&id).await?;
+        self.guard_memory(&memory, Access::ReadWrite)?;
+        if memory.context().is_org_pool() {
+            return Err(PublishMemoryError::AlreadyPublished(id));
+        }
+        if !PUBLISHABLE_KINDS.contains(memory.kind()) {
+            return Err(PublishMemoryError::UnpublishableKind(
+                memory.kind().as_str(),
+            ));
+        }
+
+        let pool = MemoryContext::for_org(org_id)?;
+        self.guard(&pool, Access::ReadWrite)?;
+        memory.set_context(pool, Utc::now());
+        self.vector_store.update(&id, None, Some(&memory)).await?;
+        Ok(memory)
+    }
+}
@@ -0,0 +1,122 @@
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use rustc_hash::FxHashSet;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::info;
+use typed_builder::TypedBuilder;
+use umem_ai::{
+    embed::{embed, EmbeddingRequest},
+    EmbeddingModelError,
+};
+use umem_core::Memory;
+use umem_vector_store::{VectorStoreBase, VectorStoreError};
+
+#[derive(Debug, Error)]
+pub enum ReembedError {
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("embedding action failed with: {0}")]
+    EmbeddingModelError(#[from] EmbeddingModelError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+#[derive(TypedBuilder)]
+pub struct ReembedRequest {
+    /// Store for the new collection, created with the new embedder's dimensions.
+    target: Arc<dyn VectorStoreBase + Send + Sync>,
+    /// Name of the new collection, promoted in place of the current one when done.
+    target_collection: String,
+    #[builder(default = 64)]
+    batch_size: u32,
+    /// Whether `target_collection` replaces the current collection when done; a
+    /// `[dual_write]` backfill leaves both in place.
+    #[builder(default = true)]
+    promote: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReembedProgress {
+    pub embedded: usize,
+    pub skipped: usize,
+}
+
+impl MemoryController {
+    /// Streams every memory of the current collection, embeds its summary with this
+    /// controller's embedding model into `target`, then swaps `target` in. Memories
+    /// already present in `target` are skipped, so an interrupted run can be resumed.
+    pub async fn reembed(
+        &self,
+        request: ReembedRequest,
+    ) -> Result<ReembedProgress, MemoryControllerError> {
+        Ok(self.reembed_impl(request).await?)
+    }
+
+    async fn reembed_impl(&self, request: ReembedRequest) -> Result<ReembedProgress, ReembedError> {
+        self.guard_global()?;
+        let ReembedRequest {
+            target,
+            target_collection,
+            batch_size,
+            promote,
+        } = request;
+
+        let mut progress = ReembedProgress::default();
+        let mut cursor = None;
+
+        loop {
+            let (memories, next) = self.vector_store.scroll(cursor, batch_size).await?;
+
+            let ids: Vec<_> = memories.iter().map(|memory| *memory.get_id()).collect();
+            let existing: FxHashSet<_> = target.existing_ids(&ids).await?.into_iter().collect();
+            let (skipped, mut pending): (Vec<Memory>, Vec<Memory>) = memories
+                .into_iter()
+                .partition(|memory| existing.contains(memory.get_id()));
+            progress.skipped += skipped.len();
+
+            if !pending.is_empty() {
+                for memory in &mut pending {
+                    memory.set_embedding_model(self.embedding_model.model_name.as_str());
+                }
+                let request = EmbeddingRequest::builder()
+                    .model(Arc::clone(&self.embedding_model))
+                    .input(
+                        pending
+                            .iter()
+                            .map(|memory| memory.get_summary().to_owned())
+                            .collect(),
+                    )
+                    .build();
+                let embedding_response = embed(request).await?;
+
+                let vectors: Vec<&[f32]> = embedding_response
+                    .embeddings
+                    .iter()
+                    .map(|inner| inner.as_slice())
+                    .collect();
+                let payloads: Vec<&Memory> = pending.iter().collect();
+                target.insert(&vectors, &payloads).await?;
+                progress.embedded += pending.len();
+            }
+
+            info!(
+                "re-embedded {} memories into {target_collection} ({} already present)",
+                progress.embedded, progress.skipped
+            );
+
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        if promote {
+            self.vector_store.promote(&target_collection).await?;
+            info!("promoted {target_collection}");
+        }
+
+        Ok(progress)
+    }
+}
@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use super::{
+    CreateMemoryOptions, MemoryController, MemoryControllerError, ModerationError, TenancyError,
+};
+use chrono::Utc;
+use rustc_hash::FxHashSet;
+use thiserror::Error;
+use umem_ai::{
+    embed::{embed, EmbeddingRequest},
+    ResponseGeneratorError,
+};
+use umem_annotations::{Annotated, Annotation, AnnotationError};
+use umem_auth::Access;
+use umem_blob::BlobStoreError;
+use umem_core::{
+    credence::{Credence, CredenceError},
+    Memory, MemoryError, MemoryId, Query,
+};
+use umem_vector_store::VectorStoreError;
+
+const EXISTING_MEMORIES_LIMIT: u32 = 50;
+
+#[derive(Debug, Error)]
+pub enum RegenerateMemoryError {
+    #[error("annotating the memory's content produced no memory")]
+    NothingAnnotated,
+
+    #[error("raw content is kept at {0} but no blob store is configured")]
+    NoBlobStore(String),
+
+    #[error("blob store action failed with: {0}")]
+    BlobStoreError(#[from] BlobStoreError),
+
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("annotation failed with: {0}")]
+    AnnotationError(#[from] AnnotationError),
+
+    #[error("response generator action failed with: {0}")]
+    ResponseGeneratorError(#[from] ResponseGeneratorError),
+
+    #[error("regenerated memory is invalid: {0}")]
+    MemoryError(#[from] MemoryError),
+
+    #[error("regenerated certainty is invalid: {0}")]
+    CredenceError(#[from] CredenceError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+
+    #[error("moderation failed with: {0}")]
+    ModerationError(#[from] ModerationError),
+
+    #[error("spend budget is used up")]
+    BudgetExhausted,
+}
+
+/// Words `a` and `b` share, over the words in either.
+fn overlap(a: &str, b: &str) -> f32 {
+    let words = |text: &str| -> FxHashSet<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / union as f32
+}
+
+impl MemoryController {
+    /// Runs annotation again over the content memory `id` was made from, with the
+    /// current prompt and model (or those of `options`), and replaces its summary, tags,
+    /// kind, certainty and provenance with the result. For memories annotated before a
+    /// prompt improvement or by a weaker model.
+    pub async fn regenerate(
+        &self,
+        id: MemoryId,
+        options: Option<CreateMemoryOptions>,
+    ) -> Result<Memory, MemoryControllerError> {
+        Ok(self.regenerate_impl(id, options).await?)
+    }
+
+    async fn regenerate_impl(
+        &self,
+        id: MemoryId,
+        options: Option<CreateMemoryOptions>,
+    ) -> Result<Memory, RegenerateMemoryError> {
+        let mut memory = self.vector_store.get(&id).await?;
+        self.guard_memory(&memory, Access::ReadWrite)?;
+        if self.over_budget() {
+            return Err(RegenerateMemoryError::BudgetExhausted);
+        }
+        let (embedding_model, language_model) = self.models_for(options);
+
+        let source = self.annotation_source(&memory).await?;
+        let existing_memories = if self.annotation_prompt.uses_existing_memories() {
+            self.other_summaries(&memory).await?
+        } else {
+            vec![]
+        };
+        let annotations = Annotation::generate(
+            source,
+            Arc::clone(&language_model),
+            &self.annotation_prompt,
+            &existing_memories,
+        )
+        .await?;
+
+        // NOTE: a source can hold several memories; this one is the closest to the old
+        // summary
+        let annotated = annotations
+            .into_iter()
+            .map(|annotated| {
+                let score = overlap(annotated.content.summary(), memory.get_summary());
+                (annotated, score)
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(annotated, _)| annotated)
+            .ok_or(RegenerateMemoryError::NothingAnnotated)?;
+        let annotated = match self.translator {
+            Some(translator) => {
+                translator
+                    .translate(annotated, Arc::clone(&language_model))
+                    .await?
+            }
+            None => annotated,
+        };
+
+        let Annotated {
+            content,
+            kind,
+            signals,
+            provenance,
+            language,
+            schedule,
+        } = annotated;
+        let now = Utc::now();
+        memory.reannotate(
+            content,
+            kind,
+            Credence::new(signals.get_certainty())?,
+            provenance,
+            language,
+            schedule,
+            now,
+        )?;
+        memory.mark_updated(now)?;
+        self.moderate(std::slice::from_mut(&mut memory)).await?;
+        memory.set_embedding_model(embedding_model.model_name.as_str());
+
+        let request = EmbeddingRequest::builder()
+            .model(embedding_model)
+            .input(vec![memory.get_summary().to_owned()])
+            .build();
+        let embedding_response = embed(request).await?;
+        self.vector_store
+            .update(
+                &id,
+                embedding_response.embeddings.first().map(Vec::as_slice),
+                Some(&memory),
+            )
+            .await?;
+
+        Ok(memory)
+    }
+
+    /// The content `memory` was annotated from, as far as it is kept: its raw content,
+    /// the document chunks it came from, or else its summary before translation.
+    async fn annotation_source(&self, memory: &Memory) -> Result<String, RegenerateMemoryError> {
+        if let Some(raw_content) = memory.content().raw_content() {
+            let Some(uri) = raw_content.blob_uri() else {
+                return Ok(raw_content.text().map_err(MemoryError::from)?);
+            };
+            let blob_store = self
+                .blob_store
+                .as_ref()
+                .ok_or_else(|| RegenerateMemoryError::NoBlobStore(uri.to_string()))?;
+            return Ok(String::from_utf8_lossy(&blob_store.get(uri).await?).into_owned());
+        }
+        if !memory.sources().is_empty() {
+            let chunks = self
+                .vector_store
+                .get_documents(Some(memory.sources()), &[], None)
+                .await?;
+            if !chunks.is_empty() {
+                return Ok(chunks
+                    .into_iter()
+                    .map(|chunk| chunk.document)
+                    .collect::<Vec<_>>()
+                    .join("\n\n"));
+            }
+        }
+
+        Ok(memory
+            .language()
+            .and_then(|language| language.original_summary())
+            .unwrap_or(memory.get_summary())
+            .to_string())
+    }
+
+    async fn other_summaries(&self, memory: &Memory) -> Result<Vec<String>, RegenerateMemoryError> {
+        let query = Query::builder()
+            .context(memory.context().clone())
+            .limit(EXISTING_MEMORIES_LIMIT)
+            .build();
+
+        Ok(self
+            .vector_store
+            .list(query)
+            .await?
+            .iter()
+            .filter(|other| other.get_id() != memory.get_id())
+            .map(|other| other.get_summary().to_owned())
+            .collect())
+    }
+}
@@ -0,0 +1,88 @@
+use std::str::FromStr;
+
+use super::MemoryController;
+use thiserror::Error;
+use umem_auth::Access;
+use umem_core::{
+    Memory, ParseRedactedFieldError, ParseRedactionModeError, RedactedField, RedactionMode,
+};
+
+#[derive(Debug, Error)]
+pub enum ResponsePolicyError {
+    #[error("redacted field is invalid: {0}")]
+    RedactedFieldError(#[from] ParseRedactedFieldError),
+
+    #[error("redaction mode is invalid: {0}")]
+    RedactionModeError(#[from] ParseRedactionModeError),
+}
+
+type Rules = Vec<(RedactedField, RedactionMode)>;
+
+/// Which memory fields are stripped or masked in responses, by the caller's api key
+/// access level.
+#[derive(Debug, Clone, Default)]
+pub struct ResponsePolicy {
+    read: Rules,
+    read_write: Rules,
+}
+
+impl ResponsePolicy {
+    pub fn from_config(config: &umem_config::Redaction) -> Result<Self, ResponsePolicyError> {
+        Ok(Self {
+            read: parse_rules(&config.read)?,
+            read_write: parse_rules(&config.read_write)?,
+        })
+    }
+
+    pub fn with_rule(mut self, access: Access, field: RedactedField, mode: RedactionMode) -> Self {
+        let rules = match access {
+            Access::Read => &mut self.read,
+            Access::ReadWrite => &mut self.read_write,
+        };
+        match rules.iter_mut().find(|(f, _)| *f == field) {
+            Some((_, existing)) => *existing = mode,
+            None => rules.push((field, mode)),
+        }
+        self
+    }
+
+    /// Rules for a caller; `None` is a caller without an api key, who sees everything.
+    pub fn rules_for(&self, access: Option<Access>) -> &[(RedactedField, RedactionMode)] {
+        match access {
+            Some(Access::Read) => &self.read,
+            Some(Access::ReadWrite) => &self.read_write,
+            None => &[],
+        }
+    }
+}
+
+fn parse_rules(
+    config: &std::collections::HashMap<String, String>,
+) -> Result<Rules, ResponsePolicyError> {
+    config
+        .iter()
+        .map(|(field, mode)| {
+            Ok((
+                RedactedField::from_str(field)?,
+                RedactionMode::from_str(mode)?,
+            ))
+        })
+        .collect()
+}
+
+impl MemoryController {
+    /// Applies the response policy for a caller with `access` to memories about to be
+    /// returned. Redacted memories must not be written back to the store.
+    pub fn redact(&self, memories: &mut [Memory], access: Option<Access>) {
+        let rules = self.response_policy.rules_for(access);
+        if rules.is_empty() {
+            return;
+        }
+
+        for memory in memories {
+            for (field, mode) in rules {
+                memory.redact(*field, *mode);
+            }
+        }
+    }
+}
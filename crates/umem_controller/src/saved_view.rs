@@ -0,0 +1,300 @@
+use std::{str::FromStr, sync::Arc};
+
+use super::{
+    MemoryController, MemoryControllerError, SearchMemoryError, SearchMemoryOptions, SearchResults,
+    TenancyError,
+};
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::{
+    query,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    Pool, Row, Sqlite,
+};
+use thiserror::Error;
+use umem_auth::Access;
+use umem_core::{MemoryContext, ParsedQuery, QuerySyntaxError};
+
+const MAX_VIEW_NAME_CHARS: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum SavedViewError {
+    #[error("saved views are not configured")]
+    NotConfigured,
+
+    #[error("saved view database action failed with: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    #[error("view name must be 1 to {MAX_VIEW_NAME_CHARS} characters")]
+    InvalidName,
+
+    #[error("invalid view query: {0}")]
+    QuerySyntaxError(#[from] QuerySyntaxError),
+
+    #[error("no view named {0}")]
+    NotFound(String),
+
+    #[error("view search failed with: {0}")]
+    SearchMemoryError(#[from] SearchMemoryError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+/// A named search in the query language, kept for the context it was saved in so agents
+/// and dashboards can run the same retrieval again.
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedView {
+    pub name: String,
+    pub query: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Saved views in a sqlite database, keyed by the context's ids and the view's name.
+pub struct ViewStore {
+    client: Pool<Sqlite>,
+}
+
+impl ViewStore {
+    pub async fn open(database_url: &str) -> Result<Self, SavedViewError> {
+        let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+        let client = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
+            .await?;
+
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS saved_views (
+                user_id TEXT NOT NULL,
+                agent_id TEXT NOT NULL,
+                run_id TEXT NOT NULL,
+                org_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                query TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                PRIMARY KEY (user_id, agent_id, run_id, org_id, name)
+            )
+            "#,
+        )
+        .execute(&client)
+        .await?;
+
+        Ok(Self { client })
+    }
+
+    pub async fn from_config(config: &umem_config::Views) -> Result<Arc<Self>, SavedViewError> {
+        Ok(Arc::new(Self::open(&config.database_url).await?))
+    }
+
+    /// Saves `query` as `name`, replacing the query of a view saved under that name before.
+    pub async fn save(
+        &self,
+        context: &MemoryContext,
+        name: &str,
+        query_text: &str,
+    ) -> Result<SavedView, SavedViewError> {
+        let [user_id, agent_id, run_id, org_id] = Self::key(context);
+        let row = query(
+            r#"INSERT INTO saved_views
+                (user_id, agent_id, run_id, org_id, name, query, created_at, updated_at)
+                VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $7)
+                ON CONFLICT (user_id, agent_id, run_id, org_id, name)
+                DO UPDATE SET query = excluded.query, updated_at = excluded.updated_at
+                RETURNING name, query, created_at, updated_at"#,
+        )
+        .bind(user_id)
+        .bind(agent_id)
+        .bind(run_id)
+        .bind(org_id)
+        .bind(name)
+        .bind(query_text)
+        .bind(Utc::now().timestamp())
+        .fetch_one(&self.client)
+        .await?;
+        Self::view(&row)
+    }
+
+    pub async fn get(
+        &self,
+        context: &MemoryContext,
+        name: &str,
+    ) -> Result<Option<SavedView>, SavedViewError> {
+        let [user_id, agent_id, run_id, org_id] = Self::key(context);
+        let row = query(
+            r#"SELECT name, query, created_at, updated_at FROM saved_views
+                WHERE user_id = $1 AND agent_id = $2 AND run_id = $3 AND org_id = $4 AND name = $5"#,
+        )
+        .bind(user_id)
+        .bind(agent_id)
+        .bind(run_id)
+        .bind(org_id)
+        .bind(name)
+        .fetch_optional(&self.client)
+        .await?;
+        row.as_ref().map(Self::view).transpose()
+    }
+
+    /// The context's views, by name.
+    pub async fn list(&self, context: &MemoryContext) -> Result<Vec<SavedView>, SavedViewError> {
+        let [user_id, agent_id, run_id, org_id] = Self::key(context);
+        query(
+            r#"SELECT name, query, created_at, updated_at FROM saved_views
+                WHERE user_id = $1 AND agent_id = $2 AND run_id = $3 AND org_id = $4
+                ORDER BY name"#,
+        )
+        .bind(user_id)
+        .bind(agent_id)
+        .bind(run_id)
+        .bind(org_id)
+        .fetch_all(&self.client)
+        .await?
+        .iter()
+        .map(Self::view)
+        .collect()
+    }
+
+    /// Whether there was a view named `name` to delete.
+    pub async fn delete(
+        &self,
+        context: &MemoryContext,
+        name: &str,
+    ) -> Result<bool, SavedViewError> {
+        let [user_id, agent_id, run_id, org_id] = Self::key(context);
+        let deleted = query(
+            r#"DELETE FROM saved_views
+                WHERE user_id = $1 AND agent_id = $2 AND run_id = $3 AND org_id = $4 AND name = $5"#,
+        )
+        .bind(user_id)
+        .bind(agent_id)
+        .bind(run_id)
+        .bind(org_id)
+        .bind(name)
+        .execute(&self.client)
+        .await?
+        .rows_affected();
+        Ok(deleted > 0)
+    }
+
+    /// Unset ids are stored as empty strings, so they take part in the primary key.
+    fn key(context: &MemoryContext) -> [&str; 4] {
+        [
+            context.user_id().unwrap_or_default(),
+            context.agent_id().unwrap_or_default(),
+            context.run_id().unwrap_or_default(),
+            context.org_id().unwrap_or_default(),
+        ]
+    }
+
+    fn view(row: &sqlx::sqlite::SqliteRow) -> Result<SavedView, SavedViewError> {
+        Ok(SavedView {
+            name: row.try_get("name")?,
+            query: row.try_get("query")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+impl MemoryController {
+    /// Saves `query`, written in the query language, as the view `name` of `context`,
+    /// replacing an earlier view of that name. A query that does not parse is refused
+    /// here rather than when the view runs.
+    pub async fn save_view(
+        &self,
+        context: MemoryContext,
+        name: impl Into<String>,
+        query: impl Into<String>,
+    ) -> Result<SavedView, MemoryControllerError> {
+        Ok(self
+            .save_view_impl(context, name.into(), query.into())
+            .await?)
+    }
+
+    pub async fn list_views(
+        &self,
+        context: MemoryContext,
+    ) -> Result<Vec<SavedView>, MemoryControllerError> {
+        Ok(self.list_views_impl(context).await?)
+    }
+
+    /// Runs the view `name` of `context` like `parsed_search_with_context`.
+    pub async fn run_view(
+        &self,
+        context: MemoryContext,
+        name: &str,
+        options: Option<SearchMemoryOptions>,
+    ) -> Result<SearchResults, MemoryControllerError> {
+        Ok(self.run_view_impl(context, name, options).await?)
+    }
+
+    /// Whether there was a view named `name` to delete.
+    pub async fn delete_view(
+        &self,
+        context: MemoryContext,
+        name: &str,
+    ) -> Result<bool, MemoryControllerError> {
+        Ok(self.delete_view_impl(context, name).await?)
+    }
+
+    async fn save_view_impl(
+        &self,
+        context: MemoryContext,
+        name: String,
+        query: String,
+    ) -> Result<SavedView, SavedViewError> {
+        let views = self.views.as_ref().ok_or(SavedViewError::NotConfigured)?;
+        self.guard(&context, Access::ReadWrite)?;
+
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_VIEW_NAME_CHARS {
+            return Err(SavedViewError::InvalidName);
+        }
+        query.parse::<ParsedQuery>()?;
+
+        views.save(&context, name, query.trim()).await
+    }
+
+    async fn list_views_impl(
+        &self,
+        context: MemoryContext,
+    ) -> Result<Vec<SavedView>, SavedViewError> {
+        let views = self.views.as_ref().ok_or(SavedViewError::NotConfigured)?;
+        self.guard(&context, Access::Read)?;
+
+        views.list(&context).await
+    }
+
+    async fn run_view_impl(
+        &self,
+        context: MemoryContext,
+        name: &str,
+        options: Option<SearchMemoryOptions>,
+    ) -> Result<SearchResults, SavedViewError> {
+        let views = self.views.as_ref().ok_or(SavedViewError::NotConfigured)?;
+        self.guard(&context, Access::Read)?;
+
+        let view = views
+            .get(&context, name.trim())
+            .await?
+            .ok_or_else(|| SavedViewError::NotFound(name.to_string()))?;
+        let query: ParsedQuery = view.query.parse()?;
+        Ok(self
+            .parsed_search_with_context_impl(context, query, options)
+            .await?)
+    }
+
+    async fn delete_view_impl(
+        &self,
+        context: MemoryContext,
+        name: &str,
+    ) -> Result<bool, SavedViewError> {
+        let views = self.views.as_ref().ok_or(SavedViewError::NotConfigured)?;
+        self.guard(&context, Access::ReadWrite)?;
+
+        views.delete(&context, name.trim()).await
+    }
+}
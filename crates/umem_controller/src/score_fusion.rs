@@ -0,0 +1,81 @@
+use chrono::Utc;
+use umem_config::ScoreFusion;
+use umem_core::Memory;
+
+const DEFAULT_RECENCY_HALF_LIFE_DAYS: f64 = 30.0;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// A search result with the scores it was found with.
+pub(crate) struct Candidate {
+    pub memory: Memory,
+    pub vector_score: f32,
+    /// Unset when the reranker was skipped.
+    pub rerank_score: Option<f32>,
+}
+
+/// Orders `candidates` by their weighted score, best first. Without `fusion` the
+/// incoming order is kept.
+pub(crate) fn fuse(candidates: Vec<Candidate>, fusion: Option<&ScoreFusion>) -> Vec<Memory> {
+    let Some(fusion) = fusion else {
+        return candidates
+            .into_iter()
+            .map(|candidate| candidate.memory)
+            .collect();
+    };
+
+    let now = Utc::now().timestamp();
+    let mut scored: Vec<(f32, Memory)> = candidates
+        .into_iter()
+        .map(|candidate| (fused_score(&candidate, fusion, now), candidate.memory))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    scored.into_iter().map(|(_, memory)| memory).collect()
+}
+
+/// The score `fuse` orders `candidate` by; unset without `fusion`.
+pub(crate) fn score(candidate: &Candidate, fusion: Option<&ScoreFusion>) -> Option<f32> {
+    fusion.map(|fusion| fused_score(candidate, fusion, Utc::now().timestamp()))
+}
+
+fn fused_score(candidate: &Candidate, fusion: &ScoreFusion, now: i64) -> f32 {
+    let signals = candidate.memory.signals();
+    let age_days = (now - candidate.memory.content_changed_at()).max(0) as f64 / SECONDS_PER_DAY;
+    // NOTE: a half-life that is not positive would make every score 0, inf or NaN
+    let half_life_days = fusion
+        .recency_half_life_days
+        .filter(|days| *days > 0.0)
+        .unwrap_or(DEFAULT_RECENCY_HALF_LIFE_DAYS);
+    let recency = 0.5_f64.powf(age_days / half_life_days) as f32;
+
+    fusion.rerank * candidate.rerank_score.unwrap_or(0.0)
+        + fusion.vector * candidate.vector_score
+        + fusion.salience * signals.get_salience()
+        + fusion.certainty * signals.get_certainty()
+        + fusion.recency * recency
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn half_lives_that_are_not_positive_fall_back_to_the_default() {
+        let candidate = Candidate {
+            memory: Memory::gen_dummy().unwrap(),
+            vector_score: 0.5,
+            rerank_score: None,
+        };
+        let fusion = |recency_half_life_days| ScoreFusion {
+            recency: 1.0,
+            recency_half_life_days,
+            ..Default::default()
+        };
+        let now = Utc::now().timestamp() + 10 * SECONDS_PER_DAY as i64;
+        let expected = fused_score(&candidate, &fusion(None), now);
+
+        for days in [0.0, -1.0, f64::NAN] {
+            assert_eq!(fused_score(&candidate, &fusion(Some(days)), now), expected);
+        }
+    }
+}
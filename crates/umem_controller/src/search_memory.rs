@@ -1,18 +1,35 @@
-use super::{MemoryController, MemoryControllerError};
-use std::{sync::Arc, time::Instant};
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use crate::score_fusion::{fuse, score, Candidate};
+use chrono::{DateTime, Utc};
+use rustc_hash::FxHashSet;
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use thiserror::Error;
 use tokio::{sync::AcquireError, task::JoinError};
-use tracing::info;
+use tracing::{info, warn};
 use typed_builder::TypedBuilder;
 use umem_ai::{
     embed::{embed, EmbeddingRequest},
     rerank, EmbeddingModel, EmbeddingModelError, RerankRequest, RerankRequestBuilderError,
     RerankingModelError, ResponseGeneratorError,
 };
-use umem_core::{Memory, MemoryContext, MemoryContextError, Query};
+use umem_auth::Access;
+use umem_config::ScoreFusion;
+use umem_core::{
+    Memory, MemoryContext, MemoryContextError, ParsedQuery, Query, QueryError, UserId,
+};
 use umem_refine::{RefineError, Segmenter};
 use umem_vector_store::VectorStoreError;
 
+const DEFAULT_SEARCH_CONCURRENCY: usize = 8;
+const DEFAULT_SUB_QUERY_LIMIT: u32 = 5;
+const DEFAULT_PARSED_QUERY_TOP_K: u32 = 6;
+const PARSED_QUERY_CANDIDATES: u32 = 20;
+const PARSED_QUERY_LIST_LIMIT: u32 = 1000;
+
 #[derive(Debug, Error)]
 pub enum SearchMemoryError {
     #[error("vector store action failed with: {0}")]
@@ -41,32 +58,222 @@ pub enum SearchMemoryError {
 
     #[error("rerank response action failed with: {0}")]
     ResponseGeneratorError(#[from] ResponseGeneratorError),
+
+    #[error("rerank timed out")]
+    RerankTimeout,
+
+    #[error("spend budget is used up")]
+    BudgetExhausted,
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+
+    #[error("invalid query filters: {0}")]
+    QueryError(#[from] QueryError),
+}
+
+/// Memories found by a search, best first.
+#[derive(Debug, Default)]
+pub struct SearchResults {
+    pub memories: Vec<Memory>,
+    /// The reranker failed or timed out and `[search] rerank_fallback` kept the
+    /// memories in vector-similarity order instead.
+    pub rerank_skipped: bool,
+    /// The search budget ran out; `memories` is what was found in time.
+    pub timed_out: bool,
+    pub timings: SearchTimings,
+    /// Set when `SearchMemoryOptions::explain` was.
+    pub explanation: Option<SearchExplanation>,
+}
+
+/// How a search arrived at its results, for debugging why a memory was or wasn't
+/// retrieved. Timings are in `SearchResults::timings`.
+#[derive(Debug, Default, Clone)]
+pub struct SearchExplanation {
+    /// The queries that were embedded and searched; the original query is last.
+    pub sub_queries: Vec<String>,
+    /// The vector store's translation of the search's filters.
+    pub filter: String,
+    /// Everything the vector stage found, in the order it was found.
+    pub candidates: Vec<ExplainedCandidate>,
+}
+
+/// One memory the vector stage found, with its score at each later stage.
+#[derive(Debug, Clone)]
+pub struct ExplainedCandidate {
+    pub memory_id: String,
+    /// Index into `sub_queries` of the query that found it.
+    pub sub_query: usize,
+    pub vector_score: f32,
+    /// Unset when the memory was not reranked: it was already found by another
+    /// sub-query, cut by the candidate cap, or the reranker was skipped.
+    pub rerank_score: Option<f32>,
+    /// The score results were ordered by, when `[search.fusion]` is configured.
+    pub fused_score: Option<f32>,
+    /// Position in the results; unset when the memory was not returned.
+    pub rank: Option<usize>,
+}
+
+impl SearchExplanation {
+    fn new(sub_queries: Vec<String>, filter: String) -> Self {
+        Self {
+            sub_queries,
+            filter,
+            candidates: Vec::new(),
+        }
+    }
+
+    fn record_found(&mut self, found: &[(usize, Vec<(Memory, f32)>)]) {
+        for (sub_query, memories) in found {
+            self.candidates
+                .extend(
+                    memories
+                        .iter()
+                        .map(|(memory, vector_score)| ExplainedCandidate {
+                            memory_id: memory.get_id().to_string(),
+                            sub_query: *sub_query,
+                            vector_score: *vector_score,
+                            rerank_score: None,
+                            fused_score: None,
+                            rank: None,
+                        }),
+                );
+        }
+    }
+
+    /// The first sighting of a memory, which is the one deduplication keeps.
+    fn candidate_mut(&mut self, memory: &Memory) -> Option<&mut ExplainedCandidate> {
+        let memory_id = memory.get_id().to_string();
+        self.candidates
+            .iter_mut()
+            .find(|candidate| candidate.memory_id == memory_id)
+    }
+
+    fn record_scored(&mut self, candidates: &[Candidate], fusion: Option<&ScoreFusion>) {
+        for candidate in candidates {
+            if let Some(explained) = self.candidate_mut(&candidate.memory) {
+                explained.rerank_score = candidate.rerank_score;
+                explained.fused_score = score(candidate, fusion);
+            }
+        }
+    }
+
+    fn record_ranks(&mut self, memories: &[Memory]) {
+        for (rank, memory) in memories.iter().enumerate() {
+            if let Some(explained) = self.candidate_mut(memory) {
+                explained.rank = Some(rank);
+            }
+        }
+    }
+}
+
+/// Time spent in each stage of a search pipeline.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SearchTimings {
+    pub embed: Duration,
+    pub store: Duration,
+    pub rerank: Duration,
+}
+
+impl SearchResults {
+    fn timed_out(timings: SearchTimings) -> Self {
+        SearchResults {
+            timed_out: true,
+            timings,
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(TypedBuilder, Default)]
 pub struct SearchMemoryOptions {
     #[builder(default = None)]
     pub embedding_model: Option<Arc<EmbeddingModel>>,
+    /// Model of the configured embedding provider to embed the query with. It must
+    /// produce vectors comparable to the stored ones. Ignored when `embedding_model`
+    /// is set.
+    #[builder(default = None, setter(into))]
+    pub embedding_model_name: Option<String>,
+    /// Budget for the whole search, e.g. the caller's gRPC deadline. `[search] timeout_ms`
+    /// caps it further. When it runs out, outstanding vector queries are cancelled and
+    /// the memories found so far are returned.
+    #[builder(default = None)]
+    pub timeout: Option<Duration>,
+    /// Overrides `[search] concurrency` for this multi-search.
+    #[builder(default = None)]
+    pub concurrency: Option<usize>,
+    /// Overrides `[search] sub_query_limit` for this multi-search.
+    #[builder(default = None)]
+    pub sub_query_limit: Option<u32>,
+    /// Overrides `[search] candidate_cap` for this multi-search.
+    #[builder(default = None)]
+    pub candidate_cap: Option<usize>,
+    /// Also return a `SearchExplanation` of how the results were found.
+    #[builder(default = false)]
+    pub explain: bool,
+    /// Search the memories as they stood at this point in time, e.g. to audit what an
+    /// agent knew when it made a decision. Content is shown as it was then; signals are
+    /// current. See `Query::as_of`.
+    #[builder(default = None)]
+    pub as_of: Option<DateTime<Utc>>,
 }
 
 impl MemoryController {
+    fn embedding_model_for(&self, options: &SearchMemoryOptions) -> Arc<EmbeddingModel> {
+        match (&options.embedding_model, &options.embedding_model_name) {
+            (Some(embedding_model), _) => Arc::clone(embedding_model),
+            (None, Some(model_name)) => Arc::new(self.embedding_model.with_model_name(model_name)),
+            (None, None) => Arc::clone(&self.embedding_model),
+        }
+    }
+
+    fn deadline_for(&self, options: &SearchMemoryOptions) -> Option<tokio::time::Instant> {
+        let configured = self.search_config.timeout_ms.map(Duration::from_millis);
+        options
+            .timeout
+            .into_iter()
+            .chain(configured)
+            .min()
+            .map(|budget| tokio::time::Instant::now() + budget)
+    }
+
     pub async fn search_for_user(
         &self,
-        user_id: String,
+        user_id: UserId,
+        query: String,
+        options: Option<SearchMemoryOptions>,
+    ) -> Result<Vec<Memory>, MemoryControllerError> {
+        Ok(self
+            .search_for_user_impl(user_id, None, query, options)
+            .await?)
+    }
+
+    /// Like `search_for_user`, merging in the pool of the user's organization.
+    pub async fn search_for_member(
+        &self,
+        user_id: UserId,
+        org_id: Option<String>,
         query: String,
         options: Option<SearchMemoryOptions>,
     ) -> Result<Vec<Memory>, MemoryControllerError> {
-        Ok(self.search_for_user_impl(user_id, query, options).await?)
+        Ok(self
+            .search_for_user_impl(user_id, org_id, query, options)
+            .await?)
     }
 
     async fn search_for_user_impl(
         &self,
-        user_id: String,
+        user_id: UserId,
+        org_id: Option<String>,
         query: String,
-        _options: Option<SearchMemoryOptions>,
+        options: Option<SearchMemoryOptions>,
     ) -> Result<Vec<Memory>, SearchMemoryError> {
+        let context = MemoryContext::for_user(user_id)?.with_org(org_id)?;
+        self.guard(&context, Access::Read)?;
+
+        let options = options.unwrap_or_default();
         let request = EmbeddingRequest::builder()
-            .model(self.embedding_model.clone())
+            .model(self.embedding_model_for(&options))
             .input(vec![query])
             .build();
 
@@ -74,11 +281,14 @@ impl MemoryController {
 
         let query = Query::builder()
             .vector(embedding_response.embeddings[0].clone())
-            .context(MemoryContext::for_user(user_id)?)
+            .context(context)
             .limit(1000)
+            .as_of_opt(options.as_of)
             .build();
 
-        Ok(self.vector_store.search(query).await?)
+        let mut memories = self.vector_store.search(query).await?;
+        rewind(memories.iter_mut(), options.as_of);
+        Ok(memories)
     }
 
     pub async fn search_with_context(
@@ -87,6 +297,19 @@ impl MemoryController {
         query: String,
         options: Option<SearchMemoryOptions>,
     ) -> Result<Vec<Memory>, MemoryControllerError> {
+        Ok(self
+            .search_with_context_impl(context, query, options)
+            .await?
+            .memories)
+    }
+
+    /// Like `search_with_context`, but also reports whether reranking was skipped.
+    pub async fn search_with_context_results(
+        &self,
+        context: MemoryContext,
+        query: String,
+        options: Option<SearchMemoryOptions>,
+    ) -> Result<SearchResults, MemoryControllerError> {
         Ok(self
             .search_with_context_impl(context, query, options)
             .await?)
@@ -96,40 +319,190 @@ impl MemoryController {
         &self,
         context: MemoryContext,
         query: String,
-        _options: Option<SearchMemoryOptions>,
-    ) -> Result<Vec<Memory>, SearchMemoryError> {
+        options: Option<SearchMemoryOptions>,
+    ) -> Result<SearchResults, SearchMemoryError> {
+        self.guard(&context, Access::Read)?;
+        let options = options.unwrap_or_default();
+        let deadline = self.deadline_for(&options);
+        let mut timings = SearchTimings::default();
         let request = EmbeddingRequest::builder()
-            .model(self.embedding_model.clone())
+            .model(self.embedding_model_for(&options))
             .input(vec![query.clone()])
             .build();
 
-        let embedding_response = embed(request).await?;
+        let start = Instant::now();
+        let embedding_response = within(deadline, embed(request)).await;
+        timings.embed = start.elapsed();
+        let Some(embedding_response) = embedding_response else {
+            return Ok(SearchResults::timed_out(timings));
+        };
+        let embedding_response = embedding_response?;
 
         let vector_query = Query::builder()
             .vector(embedding_response.embeddings[0].clone())
             .context(context)
             .limit(20)
+            .as_of_opt(options.as_of)
             .build();
+        let mut explanation = options.explain.then(|| {
+            SearchExplanation::new(
+                vec![query.clone()],
+                self.vector_store.describe_filter(&vector_query),
+            )
+        });
 
-        let mut memories = self.vector_store.search(vector_query).await?;
+        let start = Instant::now();
+        let memories = within(deadline, self.vector_store.search_scored(vector_query)).await;
+        timings.store = start.elapsed();
+        let Some(memories) = memories else {
+            return Ok(SearchResults {
+                explanation,
+                ..SearchResults::timed_out(timings)
+            });
+        };
+        let mut found = vec![(0, memories?)];
+        rewind(found_memories(&mut found), options.as_of);
+        if let Some(explanation) = explanation.as_mut() {
+            explanation.record_found(&found);
+        }
+        let memories = found
+            .into_iter()
+            .flat_map(|(_, memories)| memories)
+            .collect();
 
-        let documents: Vec<String> = memories.iter().map(|m| m.get_summary().clone()).collect();
-        let request = RerankRequest::builder()
-            .model(Arc::clone(&self.reranking_model))
-            .documents(documents)
-            .query(query)
-            .top_k(6)
-            .build()?;
+        let start = Instant::now();
+        let mut results = self
+            .rerank_memories(memories, query, 6, deadline, explanation.as_mut())
+            .await?;
+        timings.rerank = start.elapsed();
+
+        results.timings = timings;
+        results.explanation = explanation;
+        Ok(results)
+    }
 
-        let rerank_response = rerank(request).await?;
+    pub async fn text_search_with_context(
+        &self,
+        context: MemoryContext,
+        text: String,
+    ) -> Result<Vec<Memory>, MemoryControllerError> {
+        Ok(self.text_search_with_context_impl(context, text).await?)
+    }
 
-        let memories: Vec<Memory> = rerank_response
-            .rankings
-            .iter()
-            .map(|row| std::mem::take(&mut memories[row.original_index]))
+    async fn text_search_with_context_impl(
+        &self,
+        context: MemoryContext,
+        text: String,
+    ) -> Result<Vec<Memory>, SearchMemoryError> {
+        self.guard(&context, Access::Read)?;
+        let query = Query::builder().context(context).limit(20).build();
+
+        Ok(self.vector_store.text_search(query, &text).await?)
+    }
+
+    /// Searches `context` for a query written in the query language, e.g. `kind:procedural
+    /// tag:deploy after:2024-01-01 "exact phrase" rollback`. Memories matching the filters
+    /// are ranked against the query's text and must contain its quoted phrases; a query
+    /// with only filters lists every memory that matches them.
+    pub async fn parsed_search_with_context(
+        &self,
+        context: MemoryContext,
+        query: ParsedQuery,
+        options: Option<SearchMemoryOptions>,
+    ) -> Result<SearchResults, MemoryControllerError> {
+        Ok(self
+            .parsed_search_with_context_impl(context, query, options)
+            .await?)
+    }
+
+    pub(crate) async fn parsed_search_with_context_impl(
+        &self,
+        context: MemoryContext,
+        mut parsed: ParsedQuery,
+        options: Option<SearchMemoryOptions>,
+    ) -> Result<SearchResults, SearchMemoryError> {
+        self.guard(&context, Access::Read)?;
+        let options = options.unwrap_or_default();
+        parsed.as_of = parsed.as_of.or(options.as_of);
+
+        if parsed.text.is_empty() {
+            let query = parsed.to_query(context, None, PARSED_QUERY_LIST_LIMIT)?;
+            let start = Instant::now();
+            let mut memories = self.vector_store.list(query).await?;
+            rewind(memories.iter_mut(), parsed.as_of);
+            return Ok(SearchResults {
+                memories,
+                timings: SearchTimings {
+                    store: start.elapsed(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        }
+
+        let deadline = self.deadline_for(&options);
+        let mut timings = SearchTimings::default();
+        let request = EmbeddingRequest::builder()
+            .model(self.embedding_model_for(&options))
+            .input(vec![parsed.text.clone()])
+            .build();
+
+        let start = Instant::now();
+        let embedding_response = within(deadline, embed(request)).await;
+        timings.embed = start.elapsed();
+        let Some(embedding_response) = embedding_response else {
+            return Ok(SearchResults::timed_out(timings));
+        };
+        let embedding_response = embedding_response?;
+
+        let top_k = parsed.limit.unwrap_or(DEFAULT_PARSED_QUERY_TOP_K);
+        let vector_query = parsed.to_query(
+            context,
+            Some(embedding_response.embeddings[0].clone()),
+            PARSED_QUERY_CANDIDATES,
+        )?;
+        let mut explanation = options.explain.then(|| {
+            SearchExplanation::new(
+                vec![parsed.text.clone()],
+                self.vector_store.describe_filter(&vector_query),
+            )
+        });
+
+        let start = Instant::now();
+        let memories = within(deadline, self.vector_store.search_scored(vector_query)).await;
+        timings.store = start.elapsed();
+        let Some(memories) = memories else {
+            return Ok(SearchResults {
+                explanation,
+                ..SearchResults::timed_out(timings)
+            });
+        };
+        let mut found = vec![(0, memories?)];
+        rewind(found_memories(&mut found), parsed.as_of);
+        if let Some(explanation) = explanation.as_mut() {
+            explanation.record_found(&found);
+        }
+        let memories = found
+            .into_iter()
+            .flat_map(|(_, memories)| memories)
+            .filter(|(memory, _)| parsed.matches_phrases(memory))
             .collect();
 
-        Ok(memories)
+        let start = Instant::now();
+        let mut results = self
+            .rerank_memories(
+                memories,
+                parsed.text,
+                top_k as usize,
+                deadline,
+                explanation.as_mut(),
+            )
+            .await?;
+        timings.rerank = start.elapsed();
+
+        results.timings = timings;
+        results.explanation = explanation;
+        Ok(results)
     }
 
     pub async fn multi_search_with_context(
@@ -138,6 +511,19 @@ impl MemoryController {
         query: String,
         options: Option<SearchMemoryOptions>,
     ) -> Result<Vec<Memory>, MemoryControllerError> {
+        Ok(self
+            .multi_search_with_context_impl(context, query, options)
+            .await?
+            .memories)
+    }
+
+    /// Like `multi_search_with_context`, but also reports whether reranking was skipped.
+    pub async fn multi_search_with_context_results(
+        &self,
+        context: MemoryContext,
+        query: String,
+        options: Option<SearchMemoryOptions>,
+    ) -> Result<SearchResults, MemoryControllerError> {
         Ok(self
             .multi_search_with_context_impl(context, query, options)
             .await?)
@@ -147,84 +533,315 @@ impl MemoryController {
         &self,
         context: MemoryContext,
         query: String,
-        _options: Option<SearchMemoryOptions>,
-    ) -> Result<Vec<Memory>, SearchMemoryError> {
-        use futures::stream::{FuturesUnordered, StreamExt};
-        use tokio::sync::Semaphore;
-        use tokio::task::JoinHandle;
-
-        let vector_store = Arc::clone(&self.vector_store);
+        options: Option<SearchMemoryOptions>,
+    ) -> Result<SearchResults, SearchMemoryError> {
+        self.guard(&context, Access::Read)?;
+        let options = options.unwrap_or_default();
+        let deadline = self.deadline_for(&options);
+        let concurrency = options
+            .concurrency
+            .or(self.search_config.concurrency)
+            .unwrap_or(DEFAULT_SEARCH_CONCURRENCY)
+            .max(1);
+        let sub_query_limit = options
+            .sub_query_limit
+            .or(self.search_config.sub_query_limit)
+            .unwrap_or(DEFAULT_SUB_QUERY_LIMIT);
+        let candidate_cap = options.candidate_cap.or(self.search_config.candidate_cap);
 
-        let semaphore = Arc::new(Semaphore::new(8)); // limit concurrency (tune this!)
-        let mut tasks: FuturesUnordered<JoinHandle<Result<Vec<Memory>, SearchMemoryError>>> =
-            FuturesUnordered::new();
         let mut sub_queries = Segmenter::process(&query)?;
         sub_queries.push(query.clone());
+        let explained_queries = options.explain.then(|| sub_queries.clone());
 
         // let sub_query_slices: Vec<&str> = sub_queries.iter().map(|s| s.as_str()).collect();
         // let vectors = embedder.generate_embeddings(&sub_query_slices).await?;
 
+        let mut timings = SearchTimings::default();
         let start = Instant::now();
 
         let request = EmbeddingRequest::builder()
-            .model(self.embedding_model.clone())
+            .model(self.embedding_model_for(&options))
             .input(sub_queries)
             .build();
 
-        let embedding_response = embed(request).await?;
-        let duration = start.elapsed();
-        info!("Embedder time : {:?}", duration);
+        let embedding_response = within(deadline, embed(request)).await;
+        timings.embed = start.elapsed();
+        info!("Embedder time : {:?}", timings.embed);
+        let Some(embedding_response) = embedding_response else {
+            return Ok(SearchResults::timed_out(timings));
+        };
+        let embedding_response = embedding_response?;
+
+        let queries: Vec<Query> = embedding_response
+            .embeddings
+            .into_iter()
+            .map(|vector| {
+                Query::builder()
+                    .vector(vector)
+                    .context(context.clone())
+                    .limit(sub_query_limit)
+                    .as_of_opt(options.as_of)
+                    .build()
+            })
+            .collect();
+        let mut explanation = explained_queries.map(|sub_queries| {
+            let filter = queries
+                .first()
+                .map(|query| self.vector_store.describe_filter(query))
+                .unwrap_or_default();
+            SearchExplanation::new(sub_queries, filter)
+        });
 
-        for vector in embedding_response.embeddings {
-            let permit = Arc::clone(&semaphore).acquire_owned().await?;
-            let vector_store = Arc::clone(&vector_store);
-            let context = context.clone();
+        let start = Instant::now();
+        let (mut found, timed_out) =
+            match within(deadline, self.vector_store.search_scored_batch(&queries)).await {
+                Some(Ok(batches)) => (batches.into_iter().enumerate().collect(), false),
+                Some(Err(VectorStoreError::Unsupported(_))) => {
+                    self.search_each(queries, concurrency, deadline).await?
+                }
+                Some(Err(e)) => return Err(e.into()),
+                None => {
+                    warn!("search budget ran out during the batch search");
+                    (Vec::new(), true)
+                }
+            };
+        timings.store = start.elapsed();
+        info!("Searching time : {:?}", timings.store);
+        rewind(found_memories(&mut found), options.as_of);
+        if let Some(explanation) = explanation.as_mut() {
+            explanation.record_found(&found);
+        }
 
-            tasks.push(tokio::spawn(async move {
-                let _permit = permit;
-                let q = Query::builder()
-                    .vector(vector)
-                    .context(context)
-                    .limit(5)
-                    .build();
+        let all_memories = found
+            .into_iter()
+            .flat_map(|(_, memories)| memories)
+            .collect();
+        let mut all_memories = dedup_by_id(all_memories);
+        if let Some(candidate_cap) = candidate_cap {
+            all_memories.truncate(candidate_cap);
+        }
 
-                Ok(vector_store.search(q).await?)
-            }));
+        let start = Instant::now();
+        let mut results = self
+            .rerank_memories(all_memories, query, 6, deadline, explanation.as_mut())
+            .await?;
+        timings.rerank = start.elapsed();
+        info!("Reranking time : {:?}", timings.rerank);
+
+        results.timed_out |= timed_out;
+        results.timings = timings;
+        results.explanation = explanation;
+        Ok(results)
+    }
+
+    /// Runs `queries` as separate searches, at most `concurrency` at a time, for stores
+    /// without a batch search. Returns what each sub-query that finished within the
+    /// deadline found, by its index, and whether any sub-query was cut off. Sub-queries
+    /// still running are aborted when this returns or is dropped, so a cancelled search
+    /// does not keep querying the store.
+    async fn search_each(
+        &self,
+        queries: Vec<Query>,
+        concurrency: usize,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<(Vec<(usize, Vec<(Memory, f32)>)>, bool), SearchMemoryError> {
+        use tokio::{sync::Semaphore, task::JoinSet};
+
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let mut tasks: JoinSet<Result<(usize, Vec<(Memory, f32)>), SearchMemoryError>> =
+            JoinSet::new();
+
+        for (index, q) in queries.into_iter().enumerate() {
+            let semaphore = Arc::clone(&semaphore);
+            let vector_store = Arc::clone(&self.vector_store);
+
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await?;
+                Ok((index, vector_store.search_scored(q).await?))
+            });
         }
 
         let mut all_memories = Vec::new();
+        loop {
+            match within(deadline, tasks.join_next()).await {
+                Some(Some(task)) => all_memories.push(task??),
+                Some(None) => return Ok((all_memories, false)),
+                None => {
+                    warn!(
+                        "search budget ran out, cancelling {} outstanding sub-queries",
+                        tasks.len()
+                    );
+                    return Ok((all_memories, true));
+                }
+            }
+        }
+    }
 
-        let start = Instant::now();
-        while let Some(task) = tasks.next().await {
-            let mut memories = task??;
-            all_memories.append(&mut memories);
+    /// Keeps the `top_k` memories the reranker ranks best. With `[search] rerank_fallback`,
+    /// when the search budget runs out, while the reranker's circuit breaker is open or
+    /// once the spend budget is used up, the first `top_k` are kept in vector order
+    /// instead. The scores and ranks are recorded in `explanation` when given.
+    async fn rerank_memories(
+        &self,
+        memories: Vec<(Memory, f32)>,
+        query: String,
+        top_k: usize,
+        deadline: Option<tokio::time::Instant>,
+        mut explanation: Option<&mut SearchExplanation>,
+    ) -> Result<SearchResults, SearchMemoryError> {
+        if memories.is_empty() {
+            return Ok(SearchResults::default());
         }
-        let duration = start.elapsed();
-        info!("Searching time : {:?}", duration);
 
-        let documents: Vec<String> = all_memories
+        let result = match self.over_budget() {
+            true => Err(SearchMemoryError::BudgetExhausted),
+            false => {
+                self.rerank_rankings(&memories, query, top_k, deadline)
+                    .await
+            }
+        };
+        let budget_spent = deadline.is_some_and(|deadline| tokio::time::Instant::now() >= deadline);
+        let circuit_open = matches!(
+            result,
+            Err(SearchMemoryError::ResponseGeneratorError(
+                ResponseGeneratorError::CircuitOpen(_)
+            ))
+        );
+        let over_budget = matches!(result, Err(SearchMemoryError::BudgetExhausted));
+        let fusion = self.search_config.fusion.as_ref();
+        match result {
+            Ok(rankings) => {
+                let candidates = take_ranked(memories, rankings);
+                if let Some(explanation) = explanation.as_deref_mut() {
+                    explanation.record_scored(&candidates, fusion);
+                }
+                let memories = fuse(candidates, fusion);
+                if let Some(explanation) = explanation {
+                    explanation.record_ranks(&memories);
+                }
+                Ok(SearchResults {
+                    memories,
+                    ..Default::default()
+                })
+            }
+            Err(e)
+                if budget_spent
+                    || circuit_open
+                    || over_budget
+                    || self.search_config.rerank_fallback =>
+            {
+                warn!("rerank failed, returning vector-ranked results: {}", e);
+                let candidates: Vec<Candidate> = memories
+                    .into_iter()
+                    .map(|(memory, vector_score)| Candidate {
+                        memory,
+                        vector_score,
+                        rerank_score: None,
+                    })
+                    .collect();
+                if let Some(explanation) = explanation.as_deref_mut() {
+                    explanation.record_scored(&candidates, fusion);
+                }
+                let mut memories = fuse(candidates, fusion);
+                memories.truncate(top_k);
+                if let Some(explanation) = explanation {
+                    explanation.record_ranks(&memories);
+                }
+                Ok(SearchResults {
+                    memories,
+                    rerank_skipped: true,
+                    timed_out: budget_spent,
+                    ..Default::default()
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the reranker's `(index, score)` rows, best first.
+    async fn rerank_rankings(
+        &self,
+        memories: &[(Memory, f32)],
+        query: String,
+        top_k: usize,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<Vec<(usize, f32)>, SearchMemoryError> {
+        let documents: Vec<String> = memories
             .iter()
-            .map(|m| m.get_summary().clone())
+            .map(|(m, _)| m.get_summary().clone())
             .collect();
         let request = RerankRequest::builder()
             .model(Arc::clone(&self.reranking_model))
             .documents(documents)
             .query(query)
-            .top_k(6)
+            .top_k(top_k)
             .build()?;
 
-        let start = Instant::now();
-        let rerank_response = rerank(request).await?;
-        let duration = start.elapsed();
-        info!("Reranking time : {:?}", duration);
-
-        // TODO: hybrid search with "row.score" + other metrics
-        let memories: Vec<Memory> = rerank_response
+        let rerank_timeout = self
+            .search_config
+            .rerank_timeout_ms
+            .map(|timeout_ms| tokio::time::Instant::now() + Duration::from_millis(timeout_ms));
+        let rerank_response = within(
+            rerank_timeout.into_iter().chain(deadline).min(),
+            rerank(request),
+        )
+        .await
+        .ok_or(SearchMemoryError::RerankTimeout)??;
+
+        Ok(rerank_response
             .rankings
             .iter()
-            .map(|row| std::mem::take(&mut all_memories[row.original_index]))
-            .collect();
+            .map(|row| (row.original_index, row.score))
+            .collect())
+    }
+}
 
-        Ok(memories)
+/// Shows `memories` as they stood at `as_of`, for searches of a past time.
+fn rewind<'a>(memories: impl Iterator<Item = &'a mut Memory>, as_of: Option<DateTime<Utc>>) {
+    if let Some(as_of) = as_of {
+        memories.for_each(|memory| memory.rewind(as_of));
+    }
+}
+
+fn found_memories(found: &mut [(usize, Vec<(Memory, f32)>)]) -> impl Iterator<Item = &mut Memory> {
+    found
+        .iter_mut()
+        .flat_map(|(_, memories)| memories.iter_mut().map(|(memory, _)| memory))
+}
+
+/// Keeps the first occurrence of every memory; sub-queries often find the same ones.
+fn dedup_by_id(memories: Vec<(Memory, f32)>) -> Vec<(Memory, f32)> {
+    let mut seen = FxHashSet::default();
+    memories
+        .into_iter()
+        .filter(|(memory, _)| seen.insert(*memory.get_id()))
+        .collect()
+}
+
+/// Orders `memories` by reranker `rankings`, skipping indices that are out of range or
+/// repeated.
+pub(crate) fn take_ranked(
+    memories: Vec<(Memory, f32)>,
+    rankings: Vec<(usize, f32)>,
+) -> Vec<Candidate> {
+    let mut slots: Vec<Option<(Memory, f32)>> = memories.into_iter().map(Some).collect();
+    rankings
+        .into_iter()
+        .filter_map(|(index, rerank_score)| {
+            let (memory, vector_score) = slots.get_mut(index)?.take()?;
+            Some(Candidate {
+                memory,
+                vector_score,
+                rerank_score: Some(rerank_score),
+            })
+        })
+        .collect()
+}
+
+/// Runs `future` to completion, or gives up at `deadline` and returns `None`.
+async fn within<F: Future>(deadline: Option<tokio::time::Instant>, future: F) -> Option<F::Output> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, future).await.ok(),
+        None => Some(future.await),
     }
 }
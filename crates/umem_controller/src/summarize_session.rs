@@ -0,0 +1,190 @@
+use super::{
+    CreateMemoryError, CreateMemoryOptions, MemoryController, MemoryControllerError, TenancyError,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use thiserror::Error;
+use typed_builder::TypedBuilder;
+use umem_annotations::{AnnotationError, SessionSummary};
+use umem_auth::Access;
+use umem_core::{
+    credence::Credence, LifecycleState, Memory, MemoryContent, MemoryContentError, MemoryContext,
+    MemoryContextError, MemoryError, MemoryKind, MemorySignals, Provenance, ProvenanceMethod,
+    ProvenanceOrigin, Query, TemporalMetadata,
+};
+use umem_vector_store::VectorStoreError;
+use uuid::Uuid;
+
+const SESSION_MEMORIES_LIMIT: u32 = 1000;
+
+#[derive(Debug, Error)]
+pub enum SummarizeSessionError {
+    #[error("memory context action failed with: {0}")]
+    MemoryContextError(#[from] MemoryContextError),
+
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("session summarization failed with: {0}")]
+    AnnotationError(#[from] AnnotationError),
+
+    #[error("memory content errored with: {0}")]
+    MemoryContentError(#[from] MemoryContentError),
+
+    #[error("memory validation failed with: {0}")]
+    MemoryError(#[from] MemoryError),
+
+    #[error("storing session summary failed with: {0}")]
+    CreateMemoryError(#[from] CreateMemoryError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+
+    #[error("spend budget is used up")]
+    BudgetExhausted,
+}
+
+#[derive(TypedBuilder)]
+pub struct SummarizeSessionRequest {
+    #[builder(default = None)]
+    user_id: Option<String>,
+    #[builder(default = None)]
+    agent_id: Option<String>,
+    run_id: String,
+}
+
+impl SummarizeSessionRequest {
+    fn context(&self) -> Result<MemoryContext, MemoryContextError> {
+        MemoryContext::new(
+            self.user_id.clone(),
+            self.agent_id.clone(),
+            Some(self.run_id.clone()),
+        )
+    }
+}
+
+fn session_signals(memories: &[Memory]) -> Result<MemorySignals, MemoryError> {
+    let certainty = memories
+        .iter()
+        .map(|memory| memory.signals().get_certainty())
+        .sum::<f32>()
+        / memories.len() as f32;
+    let salience = memories
+        .iter()
+        .map(|memory| memory.signals().get_salience())
+        .fold(0.0, f32::max);
+
+    Ok(MemorySignals::new(
+        Credence::new(certainty)?,
+        Credence::new(salience)?,
+    )?)
+}
+
+/// Consolidated memories keep the lineage shared by all of the session's memories,
+/// followed by the summarization itself.
+fn session_provenance(memories: &[Memory], model: &str) -> Provenance {
+    let origin = if memories
+        .iter()
+        .all(|memory| matches!(memory.provenance().origin, ProvenanceOrigin::User))
+    {
+        ProvenanceOrigin::User
+    } else {
+        ProvenanceOrigin::Agent
+    };
+
+    let mut chain = memories
+        .first()
+        .map(|memory| memory.provenance().chain.clone())
+        .unwrap_or_default();
+    for memory in memories.iter().skip(1) {
+        let shared = chain
+            .iter()
+            .zip(&memory.provenance().chain)
+            .take_while(|(a, b)| a == b)
+            .count();
+        chain.truncate(shared);
+    }
+    chain.push(ProvenanceMethod::Summarized {
+        model: model.to_string(),
+    });
+
+    Provenance {
+        origin,
+        chain,
+        moderation: None,
+    }
+}
+
+impl MemoryController {
+    /// Consolidates the Working memories of a run into a single Episodic memory
+    /// and archives them. Returns `None` when the run has no active Working memories.
+    pub async fn summarize_session(
+        &self,
+        request: SummarizeSessionRequest,
+        options: Option<CreateMemoryOptions>,
+    ) -> Result<Option<Memory>, MemoryControllerError> {
+        Ok(self.summarize_session_impl(request, options).await?)
+    }
+
+    async fn summarize_session_impl(
+        &self,
+        request: SummarizeSessionRequest,
+        options: Option<CreateMemoryOptions>,
+    ) -> Result<Option<Memory>, SummarizeSessionError> {
+        let options = options.unwrap_or_default();
+        let embedding_model = options
+            .embedding_model
+            .unwrap_or_else(|| Arc::clone(&self.embedding_model));
+        let language_model = options
+            .language_model
+            .unwrap_or_else(|| Arc::clone(&self.language_model));
+
+        let context = request.context()?;
+        self.guard(&context, Access::ReadWrite)?;
+        if self.over_budget() {
+            return Err(SummarizeSessionError::BudgetExhausted);
+        }
+        let query = Query::builder()
+            .context(context.clone())
+            .kinds(vec![MemoryKind::Working])
+            .limit(SESSION_MEMORIES_LIMIT)
+            .build();
+
+        let mut working = self.vector_store.list(query).await?;
+        if working.is_empty() {
+            return Ok(None);
+        }
+
+        let summaries: Vec<String> = working
+            .iter()
+            .map(|memory| memory.get_summary().to_owned())
+            .collect();
+        let session = SessionSummary::generate(&summaries, Arc::clone(&language_model)).await?;
+        let now = Utc::now();
+
+        let memory = Memory::builder()
+            .id(Uuid::new_v4())
+            .content(MemoryContent::new(session.summary, session.tags)?)
+            .context(context)
+            .kind(MemoryKind::Episodic)
+            .signals(session_signals(&working)?)
+            .provenance(session_provenance(&working, &language_model.model_name))
+            .lifecycle(LifecycleState::Active)
+            .temporal(TemporalMetadata::new(now))
+            .build()
+            .finish()?;
+
+        let mut stored = self
+            .store(vec![memory], embedding_model, language_model)
+            .await?;
+
+        for memory in working.iter_mut() {
+            memory.archive(now)?;
+            self.vector_store
+                .update(&memory.memory_id(), None, Some(&*memory))
+                .await?;
+        }
+
+        Ok(stored.pop())
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use thiserror::Error;
+use umem_auth::Access;
+use umem_core::{MemoryContext, TagCount};
+use umem_vector_store::VectorStoreError;
+
+#[derive(Debug, Error)]
+pub enum TagMemoryError {
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("tag must not be empty or whitespace")]
+    EmptyTag,
+
+    #[error("no source tags given")]
+    NoSourceTags,
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+impl MemoryController {
+    /// Renames `from` to `to` on every memory in `context`, returning how many
+    /// memories changed. Memories already tagged `to` keep a single copy.
+    pub async fn rename_tag(
+        &self,
+        context: MemoryContext,
+        from: String,
+        to: String,
+    ) -> Result<usize, MemoryControllerError> {
+        Ok(self.merge_tags_impl(context, vec![from], to).await?)
+    }
+
+    /// Folds every tag in `from` into `to` on the memories in `context`, returning
+    /// how many memories changed.
+    pub async fn merge_tags(
+        &self,
+        context: MemoryContext,
+        from: Vec<String>,
+        to: String,
+    ) -> Result<usize, MemoryControllerError> {
+        Ok(self.merge_tags_impl(context, from, to).await?)
+    }
+
+    /// Lists the tags used in `context` with how many memories carry each, most
+    /// used first.
+    pub async fn list_tags(
+        &self,
+        context: MemoryContext,
+    ) -> Result<Vec<TagCount>, MemoryControllerError> {
+        Ok(self.list_tags_impl(context).await?)
+    }
+
+    async fn merge_tags_impl(
+        &self,
+        context: MemoryContext,
+        from: Vec<String>,
+        to: String,
+    ) -> Result<usize, TagMemoryError> {
+        self.guard(&context, Access::ReadWrite)?;
+        let to = normalize_tag(&to)?;
+        let mut from = from
+            .iter()
+            .map(|tag| normalize_tag(tag))
+            .collect::<Result<Vec<_>, _>>()?;
+        from.sort();
+        from.dedup();
+        from.retain(|tag| tag != &to);
+
+        if from.is_empty() {
+            return Err(TagMemoryError::NoSourceTags);
+        }
+
+        let vector_store = Arc::clone(&self.vector_store);
+        Ok(vector_store.replace_tags(&context, &from, &to).await?)
+    }
+
+    async fn list_tags_impl(
+        &self,
+        context: MemoryContext,
+    ) -> Result<Vec<TagCount>, TagMemoryError> {
+        self.guard(&context, Access::Read)?;
+        let vector_store = Arc::clone(&self.vector_store);
+        Ok(vector_store.tag_counts(&context).await?)
+    }
+}
+
+fn normalize_tag(tag: &str) -> Result<String, TagMemoryError> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(TagMemoryError::EmptyTag);
+    }
+    Ok(tag.to_ascii_lowercase())
+}
@@ -0,0 +1,84 @@
+use super::MemoryController;
+use std::sync::Arc;
+use thiserror::Error;
+use umem_auth::{Access, ApiKey, AuthError};
+use umem_core::{Memory, MemoryContext, MemoryContextError, MemoryId};
+use umem_vector_store::VectorStoreError;
+
+#[derive(Debug, Error)]
+pub enum TenancyError {
+    #[error("operation needs a memory context: {0}")]
+    MemoryContextError(#[from] MemoryContextError),
+
+    #[error("principal is not allowed to do this: {0}")]
+    AuthError(#[from] AuthError),
+
+    #[error("operation spans every tenant and cannot run on behalf of an api key")]
+    CrossTenant,
+
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+}
+
+impl MemoryController {
+    /// A controller that checks every read and write against `principal`'s scope, so a
+    /// server cannot leak another tenant's memories by passing the wrong context.
+    /// `None` keeps the unrestricted controller, e.g. when api keys are not configured.
+    pub fn for_principal(&self, principal: Option<&ApiKey>) -> MemoryController {
+        MemoryController {
+            principal: principal.cloned().map(Arc::new),
+            ..self.clone()
+        }
+    }
+
+    /// Rejects empty contexts, which would match every tenant's memories, and contexts
+    /// outside the principal's scope.
+    pub(crate) fn guard(
+        &self,
+        context: &MemoryContext,
+        access: Access,
+    ) -> Result<(), TenancyError> {
+        context.validate()?;
+        if let Some(principal) = self.principal.as_ref() {
+            principal.authorize(context, access)?;
+        }
+        Ok(())
+    }
+
+    /// Like `guard`, for operations that only name a memory id. The memory is only
+    /// fetched when a principal is set.
+    pub(crate) async fn guard_id(&self, id: &MemoryId, access: Access) -> Result<(), TenancyError> {
+        if self.principal.is_none() {
+            return Ok(());
+        }
+        let memory = self.vector_store.get(id).await?;
+        self.guard_memory(&memory, access)
+    }
+
+    /// Like `guard`, for a stored memory. A shared memory may also be read by the other
+    /// agents of its user.
+    pub(crate) fn guard_memory(&self, memory: &Memory, access: Access) -> Result<(), TenancyError> {
+        let context = memory.context();
+        match self.principal.as_ref() {
+            Some(principal)
+                if access == Access::Read && memory.is_shared() && context.user_id().is_some() =>
+            {
+                let context = MemoryContext::new(
+                    context.user_id().map(str::to_string),
+                    principal.scope.agent_id.clone(),
+                    None,
+                )?;
+                self.guard(&context, access)
+            }
+            _ => self.guard(context, access),
+        }
+    }
+
+    /// Rejects maintenance operations that walk the whole store when a principal is set.
+    pub(crate) fn guard_global(&self) -> Result<(), TenancyError> {
+        match self.principal {
+            Some(_) => Err(TenancyError::CrossTenant),
+            None => Ok(()),
+        }
+    }
+}
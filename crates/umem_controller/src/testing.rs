@@ -0,0 +1,42 @@
+use super::{MemoryController, MemoryTemplates, ResponsePolicy};
+use std::sync::Arc;
+use umem_ai::{AIProvider, EmbeddingModel, LanguageModel, MockProvider, RerankingModel};
+use umem_annotations::AnnotationPrompt;
+use umem_vector_store::InMemoryVectorStore;
+
+/// A controller over an empty in-memory store, with mock models and default settings.
+pub(crate) fn controller() -> MemoryController {
+    let provider = Arc::new(AIProvider::Mock(MockProvider::new()));
+    MemoryController {
+        vector_store: Arc::new(InMemoryVectorStore::default()),
+        embedding_model: Arc::new(EmbeddingModel {
+            provider: Arc::clone(&provider),
+            model_name: "mock".to_string(),
+        }),
+        reranking_model: Arc::new(RerankingModel {
+            provider: Arc::clone(&provider),
+            model_name: "mock".to_string(),
+        }),
+        language_model: Arc::new(LanguageModel {
+            provider,
+            model_name: "mock".to_string(),
+        }),
+        annotation_prompt: Arc::new(AnnotationPrompt::default()),
+        translator: None,
+        keep_raw_content: false,
+        blob_store: None,
+        graph: None,
+        response_policy: Arc::new(ResponsePolicy::default()),
+        search_config: Arc::default(),
+        salience_config: Arc::default(),
+        trash_config: Arc::default(),
+        topics_config: Arc::default(),
+        budget: Arc::default(),
+        spend: Arc::default(),
+        templates: Arc::new(MemoryTemplates::default()),
+        moderation: None,
+        journal: None,
+        views: None,
+        principal: None,
+    }
+}
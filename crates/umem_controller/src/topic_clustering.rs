@@ -0,0 +1,281 @@
+use super::{MemoryController, MemoryControllerError, TenancyError};
+use chrono::Utc;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{info, warn};
+use umem_ai::{
+    embed::{embed, EmbeddingRequest},
+    EmbeddingModelError,
+};
+use umem_annotations::{AnnotationError, TopicLabel};
+use umem_auth::Access;
+use umem_core::{MemoryContext, MemoryContextError, MemoryId, Query};
+use umem_vector_store::VectorStoreError;
+
+const DEFAULT_MAX_TOPICS: usize = 8;
+const DEFAULT_MIN_MEMORIES: usize = 10;
+const DEFAULT_MAX_MEMORIES: u32 = 1000;
+const KMEANS_MAX_ITERATIONS: usize = 50;
+/// Summaries nearest to a cluster's centroid that the language model labels it from.
+const LABEL_SAMPLE_SIZE: usize = 20;
+const EMBED_BATCH_SIZE: usize = 256;
+const SCROLL_BATCH_SIZE: u32 = 256;
+
+#[derive(Debug, Error)]
+pub enum TopicClusteringError {
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("embedding action failed with: {0}")]
+    EmbeddingModelError(#[from] EmbeddingModelError),
+
+    #[error("annotation action failed with: {0}")]
+    AnnotationError(#[from] AnnotationError),
+
+    #[error("memory context action failed with: {0}")]
+    MemoryContextError(#[from] MemoryContextError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+}
+
+/// Memories of one user about the same subject, largest topic first.
+#[derive(Debug, Clone, Serialize)]
+pub struct Topic {
+    pub label: String,
+    pub memory_ids: Vec<MemoryId>,
+}
+
+impl MemoryController {
+    /// Groups the active memories of `context` by their embeddings, has the language
+    /// model label each group and stores the label as the memories' topic. Contexts
+    /// with fewer than `[topics] min_memories` memories are left alone, and so is every
+    /// context once the spend budget is used up.
+    pub async fn cluster_topics(
+        &self,
+        context: MemoryContext,
+    ) -> Result<Vec<Topic>, MemoryControllerError> {
+        Ok(self.cluster_topics_impl(context).await?)
+    }
+
+    /// Runs `cluster_topics` for every user in the store, returning how many users
+    /// were clustered. A user that fails is logged and skipped. Meant to run
+    /// periodically.
+    pub async fn cluster_all_topics(&self) -> Result<usize, MemoryControllerError> {
+        Ok(self.cluster_all_topics_impl().await?)
+    }
+
+    async fn cluster_all_topics_impl(&self) -> Result<usize, TopicClusteringError> {
+        self.guard_global()?;
+        if self.over_budget() {
+            info!("spend budget is used up, skipping topic clustering");
+            return Ok(0);
+        }
+
+        let mut users = FxHashSet::default();
+        let mut cursor = None;
+        loop {
+            let (memories, next) = self.vector_store.scroll(cursor, SCROLL_BATCH_SIZE).await?;
+            users.extend(
+                memories
+                    .iter()
+                    .filter_map(|memory| memory.context().user_id().map(str::to_string)),
+            );
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        let mut clustered = 0;
+        for user_id in users {
+            let context = MemoryContext::for_user(user_id.as_str())?;
+            match self.cluster_topics_impl(context).await {
+                Ok(topics) if !topics.is_empty() => clustered += 1,
+                Ok(_) => {}
+                Err(e) => warn!("could not cluster the topics of user {user_id}: {}", e),
+            }
+        }
+
+        info!("clustered the memories of {clustered} users into topics");
+        Ok(clustered)
+    }
+
+    async fn cluster_topics_impl(
+        &self,
+        context: MemoryContext,
+    ) -> Result<Vec<Topic>, TopicClusteringError> {
+        self.guard(&context, Access::ReadWrite)?;
+        if self.over_budget() {
+            return Ok(vec![]);
+        }
+        let max_topics = self
+            .topics_config
+            .max_topics
+            .unwrap_or(DEFAULT_MAX_TOPICS)
+            .max(1);
+        let min_memories = self
+            .topics_config
+            .min_memories
+            .unwrap_or(DEFAULT_MIN_MEMORIES)
+            .max(1);
+        let max_memories = self
+            .topics_config
+            .max_memories
+            .unwrap_or(DEFAULT_MAX_MEMORIES);
+
+        let query = Query::builder()
+            .context(context)
+            .limit(max_memories)
+            .build();
+        let memories = self.vector_store.list(query).await?;
+        if memories.len() < min_memories {
+            return Ok(vec![]);
+        }
+
+        let mut vectors = Vec::with_capacity(memories.len());
+        for batch in memories.chunks(EMBED_BATCH_SIZE) {
+            let request = EmbeddingRequest::builder()
+                .model(Arc::clone(&self.embedding_model))
+                .input(
+                    batch
+                        .iter()
+                        .map(|memory| memory.get_summary().to_owned())
+                        .collect(),
+                )
+                .build();
+            vectors.extend(embed(request).await?.embeddings);
+        }
+
+        let (assignments, centroids) = kmeans(&vectors, topic_count(memories.len(), max_topics));
+        let mut clusters = vec![vec![]; centroids.len()];
+        for (index, cluster) in assignments.iter().enumerate() {
+            clusters[*cluster].push(index);
+        }
+
+        // NOTE: clusters the model gives the same label are merged into one topic
+        let mut topics: FxHashMap<String, Vec<usize>> = FxHashMap::default();
+        for (cluster, mut members) in clusters.into_iter().enumerate() {
+            if members.is_empty() {
+                continue;
+            }
+            members.sort_by(|a, b| {
+                distance(&vectors[*a], &centroids[cluster])
+                    .total_cmp(&distance(&vectors[*b], &centroids[cluster]))
+            });
+            let sample: Vec<String> = members
+                .iter()
+                .take(LABEL_SAMPLE_SIZE)
+                .map(|index| memories[*index].get_summary().to_owned())
+                .collect();
+            let label = TopicLabel::generate(&sample, Arc::clone(&self.language_model)).await?;
+            topics.entry(label).or_default().extend(members);
+        }
+
+        let mut memories = memories;
+        let mut labeled = Vec::with_capacity(topics.len());
+        for (label, members) in topics {
+            let mut memory_ids = Vec::with_capacity(members.len());
+            for index in members {
+                let memory = &mut memories[index];
+                memory_ids.push(memory.memory_id());
+                if memory.topic() != Some(label.as_str()) {
+                    memory.set_topic(Some(label.clone()), Utc::now());
+                    self.vector_store
+                        .update(&memory.memory_id(), None, Some(&*memory))
+                        .await?;
+                }
+            }
+            labeled.push(Topic { label, memory_ids });
+        }
+        labeled.sort_by(|a, b| {
+            b.memory_ids
+                .len()
+                .cmp(&a.memory_ids.len())
+                .then_with(|| a.label.cmp(&b.label))
+        });
+
+        Ok(labeled)
+    }
+}
+
+/// About sqrt(n / 2) topics for `n` memories, the usual rule of thumb for k.
+fn topic_count(memories: usize, max_topics: usize) -> usize {
+    ((memories as f64 / 2.0).sqrt().round() as usize).clamp(1, max_topics)
+}
+
+fn normalized(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|value| value / norm).collect()
+}
+
+/// Cosine distance between `a` and the unit vector `b`.
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - normalized(a).iter().zip(b).map(|(a, b)| a * b).sum::<f32>()
+}
+
+fn nearest(vector: &[f32], centroids: &[Vec<f32>]) -> (usize, f32) {
+    centroids
+        .iter()
+        .enumerate()
+        .map(|(index, centroid)| (index, distance(vector, centroid)))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap_or((0, 0.0))
+}
+
+/// Spherical k-means: returns the cluster of each vector and the unit centroid of each
+/// cluster. Seeds are picked farthest-first from the first vector, so the same
+/// memories always cluster the same way.
+fn kmeans(vectors: &[Vec<f32>], k: usize) -> (Vec<usize>, Vec<Vec<f32>>) {
+    let Some(first) = vectors.first() else {
+        return (vec![], vec![]);
+    };
+
+    let mut centroids = vec![normalized(first)];
+    while centroids.len() < k.min(vectors.len()) {
+        let farthest = vectors
+            .iter()
+            .map(|vector| nearest(vector, &centroids).1)
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .unwrap_or_default();
+        centroids.push(normalized(&vectors[farthest]));
+    }
+
+    let mut assignments: Vec<usize> = vec![];
+    for _ in 0..KMEANS_MAX_ITERATIONS {
+        let next: Vec<usize> = vectors
+            .iter()
+            .map(|vector| nearest(vector, &centroids).0)
+            .collect();
+        if next == assignments {
+            break;
+        }
+        assignments = next;
+
+        for (cluster, centroid) in centroids.iter_mut().enumerate() {
+            let mut sum = vec![0.0; centroid.len()];
+            for (vector, _) in vectors
+                .iter()
+                .zip(&assignments)
+                .filter(|(_, assigned)| **assigned == cluster)
+            {
+                for (total, value) in sum.iter_mut().zip(normalized(vector)) {
+                    *total += value;
+                }
+            }
+            // NOTE: a cluster that lost all its vectors keeps its centroid
+            if sum.iter().any(|value| *value != 0.0) {
+                *centroid = normalized(&sum);
+            }
+        }
+    }
+
+    (assignments, centroids)
+}
@@ -1,19 +1,21 @@
 use std::sync::Arc;
 
-use super::{MemoryController, MemoryControllerError};
+use super::{MemoryController, MemoryControllerError, ModerationError, TenancyError};
+use chrono::Utc;
 use thiserror::Error;
 use typed_builder::TypedBuilder;
-use umem_ai::EmbeddingModelError;
-use umem_core::Memory;
+use umem_ai::{
+    embed::{embed, EmbeddingRequest},
+    EmbeddingModelError, ResponseGeneratorError,
+};
+use umem_auth::Access;
+use umem_core::{Memory, MemoryError, MemoryId, MemoryVisibility};
 use umem_vector_store::VectorStoreError;
 
 #[derive(Debug, Error)]
 pub enum UpdateMemoryRequestError {
     #[error("vector or memory should be passed")]
     EmptyUpdate,
-
-    #[error("vector id cannot be empty or whitespace")]
-    EmptyVectorId,
 }
 
 #[derive(Debug, Error)]
@@ -26,11 +28,30 @@ pub enum UpdateMemoryError {
 
     #[error("embedder action failed with: {0}")]
     EmbeddingModelError(#[from] EmbeddingModelError),
+
+    #[error("tenancy check failed with: {0}")]
+    TenancyError(#[from] TenancyError),
+
+    #[error("response generator action failed with: {0}")]
+    ResponseGeneratorError(#[from] ResponseGeneratorError),
+
+    #[error("edited memory is invalid: {0}")]
+    MemoryError(#[from] MemoryError),
+
+    #[error("moderation failed with: {0}")]
+    ModerationError(#[from] ModerationError),
+
+    #[error("memory {id} is at version {current}, not {expected}")]
+    VersionConflict {
+        id: MemoryId,
+        expected: u64,
+        current: u64,
+    },
 }
 
 #[derive(TypedBuilder)]
 pub struct UpdateMemoryRequest {
-    pub vector_id: String,
+    pub vector_id: MemoryId,
     #[builder(default = None)]
     pub vector: Option<Vec<f32>>,
     #[builder(default = None)]
@@ -42,9 +63,6 @@ impl UpdateMemoryRequest {
         if self.vector.is_none() && self.memory.is_none() {
             return Err(UpdateMemoryRequestError::EmptyUpdate);
         }
-        if self.vector_id.trim().is_empty() {
-            return Err(UpdateMemoryRequestError::EmptyVectorId);
-        }
         Ok(())
     }
 }
@@ -65,8 +83,159 @@ impl MemoryController {
             memory,
         } = request;
 
+        self.guard_id(&vector_id, Access::ReadWrite).await?;
+        if let Some(memory) = memory.as_ref() {
+            self.guard(memory.context(), Access::ReadWrite)?;
+        }
+
         Ok(vector_store
             .update(&vector_id, vector.as_deref(), memory.as_ref())
             .await?)
     }
+
+    /// Replaces the summary of memory `id` and embeds it again. With `expected_version`,
+    /// the edit is refused when the memory changed since the caller read it at that
+    /// version, so an edit made on a stale copy does not overwrite a newer one.
+    pub async fn edit(
+        &self,
+        id: MemoryId,
+        summary: impl Into<String>,
+        expected_version: Option<u64>,
+    ) -> Result<Memory, MemoryControllerError> {
+        Ok(self.edit_impl(id, summary.into(), expected_version).await?)
+    }
+
+    async fn edit_impl(
+        &self,
+        id: MemoryId,
+        summary: String,
+        expected_version: Option<u64>,
+    ) -> Result<Memory, UpdateMemoryError> {
+        let mut memory = self.vector_store.get(&id).await?;
+        self.guard_memory(&memory, Access::ReadWrite)?;
+        // NOTE: the write checks the version again; checking here spares embedding an
+        // edit that is already stale
+        if let Some(expected) = expected_version {
+            if memory.version() != expected {
+                return Err(UpdateMemoryError::VersionConflict {
+                    id,
+                    expected,
+                    current: memory.version(),
+                });
+            }
+        }
+
+        memory.edit_summary(summary, Utc::now())?;
+        self.moderate(std::slice::from_mut(&mut memory)).await?;
+        memory.set_embedding_model(self.embedding_model.model_name.as_str());
+
+        let request = EmbeddingRequest::builder()
+            .model(Arc::clone(&self.embedding_model))
+            .input(vec![memory.get_summary().to_owned()])
+            .build();
+        let embedding_response = embed(request).await?;
+        let vector = embedding_response.embeddings.first().map(Vec::as_slice);
+        match expected_version {
+            Some(expected) => {
+                self.update_if_version(&id, vector, &memory, expected)
+                    .await?
+            }
+            None => self.vector_store.update(&id, vector, Some(&memory)).await?,
+        }
+
+        Ok(memory)
+    }
+
+    /// Stores `memory` only while the stored copy is still at `expected`. Stores that
+    /// cannot make the write conditional fall back to the check made when the memory was
+    /// read, so two edits racing between read and write can both pass there.
+    async fn update_if_version(
+        &self,
+        id: &MemoryId,
+        vector: Option<&[f32]>,
+        memory: &Memory,
+        expected: u64,
+    ) -> Result<(), UpdateMemoryError> {
+        match self
+            .vector_store
+            .update_if_version(id, vector, memory, expected)
+            .await
+        {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(UpdateMemoryError::VersionConflict {
+                id: *id,
+                expected,
+                current: self.vector_store.get(id).await?.version(),
+            }),
+            Err(VectorStoreError::Unsupported(_)) => {
+                Ok(self.vector_store.update(id, vector, Some(memory)).await?)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Shares a memory with the other agents of its user, or makes it private again.
+    pub async fn set_visibility(
+        &self,
+        id: MemoryId,
+        visibility: MemoryVisibility,
+    ) -> Result<Memory, MemoryControllerError> {
+        Ok(self.set_visibility_impl(id, visibility).await?)
+    }
+
+    async fn set_visibility_impl(
+        &self,
+        id: MemoryId,
+        visibility: MemoryVisibility,
+    ) -> Result<Memory, UpdateMemoryError> {
+        let mut memory = self.vector_store.get(&id).await?;
+        self.guard_memory(&memory, Access::ReadWrite)?;
+        memory.set_visibility(visibility, Utc::now());
+        self.vector_store.update(&id, None, Some(&memory)).await?;
+        Ok(memory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[tokio::test]
+    async fn writes_on_a_stale_version_are_refused() {
+        let controller = testing::controller();
+        let mut memory = Memory::gen_dummy().unwrap();
+        let id = memory.memory_id();
+        let vector = [1.0; 8];
+        controller
+            .vector_store
+            .insert(&[vector.as_slice()], &[&memory])
+            .await
+            .unwrap();
+        let read_at = memory.version();
+
+        // another edit lands between reading the memory and writing this one
+        let mut concurrent = memory.clone();
+        concurrent
+            .edit_summary("User prefers oolong", Utc::now())
+            .unwrap();
+        controller
+            .update_if_version(&id, None, &concurrent, read_at)
+            .await
+            .unwrap();
+
+        memory
+            .edit_summary("User prefers green tea", Utc::now())
+            .unwrap();
+        let result = controller
+            .update_if_version(&id, None, &memory, read_at)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(UpdateMemoryError::VersionConflict { current, .. }) if current == read_at + 1
+        ));
+        let stored = controller.vector_store.get(&id).await.unwrap();
+        assert_eq!(stored.get_summary(), "User prefers oolong");
+    }
 }
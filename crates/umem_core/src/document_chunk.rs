@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A chunk of an ingested document, stored beside memories in the same vector store.
+/// `metadata` carries the ingestion bookkeeping (`doc_id`, `url`, `hash`, `app_id`,
+/// `data_type`, ...) and is what chunk filters match against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentChunk {
+    pub id: String,
+    pub document: String,
+    pub metadata: HashMap<String, String>,
+}
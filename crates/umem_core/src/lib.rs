@@ -1,3 +1,7 @@
+mod document_chunk;
 mod memory;
+mod secrets;
 
+pub use document_chunk::*;
 pub use memory::*;
+pub use secrets::*;
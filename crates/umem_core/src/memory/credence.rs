@@ -15,7 +15,9 @@ pub struct Credence(f32);
 
 impl Credence {
     pub fn new(value: f32) -> Result<Self, CredenceError> {
-        if !value.is_finite() {}
+        if !value.is_finite() {
+            return Err(CredenceError::NotFinite(value));
+        }
         if !(0.0..=1.0).contains(&value) {
             return Err(CredenceError::OutOfRange(value));
         }
@@ -14,6 +14,12 @@ pub enum LifecycleState {
     #[default]
     Active,
     Archived,
+    /// In the trash: hidden from every query until restored or purged.
+    Deleted,
+    /// Replaced by a newer memory; kept as history next to archived memories.
+    Superseded,
+    /// Hidden by the user: never retrieved, but kept so it is not re-learned.
+    Suppressed,
 }
 
 impl FromStr for LifecycleState {
@@ -23,6 +29,9 @@ impl FromStr for LifecycleState {
         match s.trim().to_ascii_lowercase().as_str() {
             "active" => Ok(Self::Active),
             "archived" => Ok(Self::Archived),
+            "deleted" => Ok(Self::Deleted),
+            "superseded" => Ok(Self::Superseded),
+            "suppressed" => Ok(Self::Suppressed),
             _ => Err(ParseLifecycleStateError {
                 input: s.to_string(),
             }),
@@ -31,10 +40,21 @@ impl FromStr for LifecycleState {
 }
 
 impl LifecycleState {
+    /// States queries return when they include archived memories. Deleted and
+    /// suppressed memories are never returned.
+    pub const RETRIEVABLE: [LifecycleState; 3] = [
+        LifecycleState::Active,
+        LifecycleState::Archived,
+        LifecycleState::Superseded,
+    ];
+
     pub fn as_str(&self) -> &str {
         match self {
             Self::Active => "Active",
             Self::Archived => "Archived",
+            Self::Deleted => "Deleted",
+            Self::Superseded => "Superseded",
+            Self::Suppressed => "Suppressed",
         }
     }
 
@@ -45,4 +65,16 @@ impl LifecycleState {
     pub fn is_archived(&self) -> bool {
         matches!(self, LifecycleState::Archived)
     }
+
+    pub fn is_deleted(&self) -> bool {
+        matches!(self, LifecycleState::Deleted)
+    }
+
+    pub fn is_superseded(&self) -> bool {
+        matches!(self, LifecycleState::Superseded)
+    }
+
+    pub fn is_suppressed(&self) -> bool {
+        matches!(self, LifecycleState::Suppressed)
+    }
 }
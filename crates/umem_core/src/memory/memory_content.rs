@@ -1,7 +1,13 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use rustc_hash::FxHashSet;
 use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
 use thiserror::Error;
 
+/// Raw content longer than this is stored gzipped.
+const COMPRESS_RAW_CONTENT_ABOVE: usize = 1024;
+
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum MemoryContentError {
     #[error("summary must not be empty or whitespace")]
@@ -15,12 +21,84 @@ pub enum MemoryContentError {
 
     #[error("tag not found: {0}")]
     TagNotFound(String),
+
+    #[error("raw content could not be decoded: {0}")]
+    InvalidRawContent(String),
+
+    #[error("raw content is kept in the blob store at {0}")]
+    RawContentInBlobStore(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: u64,
+}
+
+/// The content a memory was annotated from, kept so it can be annotated again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "encoding", content = "data", rename_all = "snake_case")]
+pub enum RawContent {
+    Plain(String),
+    /// Gzipped, then base64 encoded.
+    Gzip(String),
+    /// The uri of a blob holding the content, for content too large to keep inline.
+    Blob(String),
+}
+
+impl RawContent {
+    /// Keeps `text`, gzipped when it is long enough for that to pay off.
+    pub fn new(text: &str) -> Self {
+        if text.len() <= COMPRESS_RAW_CONTENT_ABOVE {
+            return Self::Plain(text.to_string());
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        match encoder
+            .write_all(text.as_bytes())
+            .and_then(|_| encoder.finish())
+        {
+            Ok(compressed) if compressed.len() < text.len() => {
+                Self::Gzip(STANDARD.encode(compressed))
+            }
+            _ => Self::Plain(text.to_string()),
+        }
+    }
+
+    /// The content, unless it is kept in a blob store.
+    pub fn text(&self) -> Result<String, MemoryContentError> {
+        match self {
+            Self::Plain(text) => Ok(text.clone()),
+            Self::Gzip(data) => {
+                let compressed = STANDARD
+                    .decode(data)
+                    .map_err(|e| MemoryContentError::InvalidRawContent(e.to_string()))?;
+                let mut text = String::new();
+                GzDecoder::new(compressed.as_slice())
+                    .read_to_string(&mut text)
+                    .map_err(|e| MemoryContentError::InvalidRawContent(e.to_string()))?;
+                Ok(text)
+            }
+            Self::Blob(uri) => Err(MemoryContentError::RawContentInBlobStore(uri.clone())),
+        }
+    }
+
+    pub fn blob_uri(&self) -> Option<&str> {
+        match self {
+            Self::Blob(uri) => Some(uri),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, schemars::JsonSchema, Clone, PartialEq, Default, Eq, Serialize, Deserialize)]
 pub struct MemoryContent {
     summary: String,
     tags: Vec<String>,
+    /// What the summary was annotated from, when that is kept.
+    #[schemars(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw_content: Option<RawContent>,
 }
 
 impl MemoryContent {
@@ -54,6 +132,7 @@ impl MemoryContent {
         Ok(Self {
             summary: summary.to_string(),
             tags: normalized_tags,
+            raw_content: None,
         })
     }
 
@@ -102,6 +181,34 @@ impl MemoryContent {
         }
     }
 
+    pub fn clear_tags(&mut self) {
+        self.tags.clear();
+    }
+
+    /// Replaces every tag in `from` with `to`, which must already be a normalized tag.
+    /// `to` keeps the position of the first replaced tag. Returns whether anything changed.
+    pub fn replace_tags(&mut self, from: &[String], to: &str) -> bool {
+        if !self.tags.iter().any(|tag| from.contains(tag)) {
+            return false;
+        }
+
+        let mut replaced = Vec::with_capacity(self.tags.len());
+        for tag in self.tags.drain(..) {
+            let tag = if from.contains(&tag) {
+                to.to_string()
+            } else {
+                tag
+            };
+
+            if !replaced.contains(&tag) {
+                replaced.push(tag);
+            }
+        }
+
+        self.tags = replaced;
+        true
+    }
+
     pub fn tags(&self) -> &Vec<String> {
         &self.tags
     }
@@ -109,4 +216,28 @@ impl MemoryContent {
     pub fn summary(&self) -> &String {
         &self.summary
     }
+
+    pub fn raw_content(&self) -> Option<&RawContent> {
+        self.raw_content.as_ref()
+    }
+
+    pub fn set_raw_content(&mut self, raw_content: Option<RawContent>) {
+        self.raw_content = raw_content;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_raw_content_is_gzipped_and_read_back() {
+        let short = "I moved to Lisbon last spring.";
+        assert_eq!(RawContent::new(short), RawContent::Plain(short.to_string()));
+
+        let long = "user: I moved to Lisbon last spring.\nassistant: How is it?\n".repeat(40);
+        let raw_content = RawContent::new(&long);
+        assert!(matches!(raw_content, RawContent::Gzip(_)));
+        assert_eq!(raw_content.text().unwrap(), long);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::{AgentId, MemoryScope, RunId, UserId};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -15,6 +16,10 @@ pub struct MemoryContext {
     user_id: Option<String>,
     agent_id: Option<String>,
     run_id: Option<String>,
+    /// On a personal context, the organization whose pool is searched next to it. On
+    /// its own, the organization pool.
+    #[serde(default)]
+    org_id: Option<String>,
 }
 
 impl MemoryContext {
@@ -35,17 +40,34 @@ impl MemoryContext {
             user_id,
             agent_id,
             run_id,
+            org_id: None,
         })
     }
 
     pub fn validate(&self) -> Result<(), MemoryContextError> {
-        if self.user_id.is_none() && self.agent_id.is_none() && self.run_id.is_none() {
+        if self.user_id.is_none()
+            && self.agent_id.is_none()
+            && self.run_id.is_none()
+            && self.org_id.is_none()
+        {
             return Err(MemoryContextError::EmptyContext);
         }
 
         Ok(())
     }
 
+    pub fn from_ids(
+        user_id: Option<UserId>,
+        agent_id: Option<AgentId>,
+        run_id: Option<RunId>,
+    ) -> Result<Self, MemoryContextError> {
+        Self::new(
+            user_id.map(String::from),
+            agent_id.map(String::from),
+            run_id.map(String::from),
+        )
+    }
+
     pub fn for_user(user_id: impl Into<String>) -> Result<Self, MemoryContextError> {
         Self::new(Some(user_id.into()), None, None)
     }
@@ -58,6 +80,20 @@ impl MemoryContext {
         Self::new(None, None, Some(run_id.into()))
     }
 
+    /// The shared pool of an organization.
+    pub fn for_org(org_id: impl Into<String>) -> Result<Self, MemoryContextError> {
+        Ok(Self {
+            org_id: normalize("org_id", Some(org_id.into()))?,
+            ..Self::default()
+        })
+    }
+
+    /// Also matches the pool of `org_id`, when set, next to this context's own memories.
+    pub fn with_org(mut self, org_id: Option<String>) -> Result<Self, MemoryContextError> {
+        self.org_id = normalize("org_id", org_id)?;
+        Ok(self)
+    }
+
     pub fn is_partial(&self) -> bool {
         let count = self.user_id.is_some() as u8
             + self.agent_id.is_some() as u8
@@ -78,6 +114,31 @@ impl MemoryContext {
         self.run_id.is_some()
     }
 
+    /// Whether this is an organization pool rather than a personal context.
+    pub fn is_org_pool(&self) -> bool {
+        self.org_id.is_some()
+            && self.user_id.is_none()
+            && self.agent_id.is_none()
+            && self.run_id.is_none()
+    }
+
+    pub fn scope(&self) -> MemoryScope {
+        if self.is_org_pool() {
+            MemoryScope::Org
+        } else {
+            MemoryScope::Personal
+        }
+    }
+
+    /// Overwrites every set identifier with `replacement`, or clears them all on `None`.
+    pub fn redact(&mut self, replacement: Option<&str>) {
+        for id in [&mut self.user_id, &mut self.agent_id, &mut self.run_id] {
+            if id.is_some() {
+                *id = replacement.map(str::to_owned);
+            }
+        }
+    }
+
     pub fn user_id(&self) -> Option<&str> {
         self.user_id.as_deref()
     }
@@ -89,6 +150,10 @@ impl MemoryContext {
     pub fn run_id(&self) -> Option<&str> {
         self.run_id.as_deref()
     }
+
+    pub fn org_id(&self) -> Option<&str> {
+        self.org_id.as_deref()
+    }
 }
 
 fn normalize(
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: u64,
+}
+
+/// Counts over every memory matching a query's filters, for dashboards and filter UIs.
+/// Each list is ordered most frequent first, except `months` which is newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MemoryFacets {
+    pub kinds: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+    /// Creation month as `YYYY-MM` (UTC).
+    pub months: Vec<FacetCount>,
+    /// Topic labels assigned by clustering; unlabeled memories are not counted.
+    #[serde(default)]
+    pub topics: Vec<FacetCount>,
+}
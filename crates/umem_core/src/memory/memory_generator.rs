@@ -0,0 +1,332 @@
+use crate::{
+    credence::Credence, LifecycleState, Memory, MemoryContent, MemoryContext, MemoryError,
+    MemoryKind, MemorySignals, Provenance, TemporalMetadata,
+};
+use chrono::{DateTime, Duration, Utc};
+use typed_builder::TypedBuilder;
+
+const NAMES: &[&str] = &[
+    "Alice", "Bilal", "Chen", "Dara", "Elena", "Farid", "Grace", "Hiro", "Ines", "Jonas",
+];
+const PLACES: &[&str] = &[
+    "Lisbon", "Kyoto", "Berlin", "Austin", "Nairobi", "Oslo", "Montreal", "Hanoi",
+];
+const FOODS: &[&str] = &[
+    "ramen",
+    "tacos",
+    "pho",
+    "sourdough pizza",
+    "falafel",
+    "dim sum",
+    "biryani",
+    "paella",
+];
+const TOOLS: &[&str] = &[
+    "Rust",
+    "Terraform",
+    "Docker",
+    "Postgres",
+    "Kubernetes",
+    "Figma",
+    "Python",
+    "Neovim",
+];
+const HOBBIES: &[&str] = &[
+    "running",
+    "bouldering",
+    "journaling",
+    "chess",
+    "cycling",
+    "yoga",
+    "piano",
+    "baking",
+];
+const PROJECTS: &[&str] = &[
+    "billing",
+    "search",
+    "onboarding",
+    "analytics",
+    "mobile app",
+    "data pipeline",
+];
+
+/// Summary templates per kind. `{name}`, `{place}`, `{food}`, `{tool}`, `{hobby}` and
+/// `{project}` are filled from the word lists above.
+const TEMPLATES: &[(MemoryKind, &str, &[&str])] = &[
+    (
+        MemoryKind::Semantic,
+        "Prefers {food} over most other cuisines",
+        &["food", "preferences"],
+    ),
+    (
+        MemoryKind::Semantic,
+        "Lives in {place} and works remotely",
+        &["location", "work"],
+    ),
+    (
+        MemoryKind::Semantic,
+        "Is allergic to peanuts but loves {food}",
+        &["food", "health"],
+    ),
+    (
+        MemoryKind::Semantic,
+        "Has been using {tool} professionally for five years",
+        &["work", "skills"],
+    ),
+    (
+        MemoryKind::Episodic,
+        "Visited {place} with {name} last month",
+        &["travel", "people"],
+    ),
+    (
+        MemoryKind::Episodic,
+        "Finished the {project} migration after a long weekend",
+        &["work"],
+    ),
+    (
+        MemoryKind::Episodic,
+        "Had {food} at a small place in {place} and loved it",
+        &["food", "travel"],
+    ),
+    (
+        MemoryKind::Procedural,
+        "Deploys the {project} service with {tool} every Friday",
+        &["work", "workflow"],
+    ),
+    (
+        MemoryKind::Procedural,
+        "Starts each morning with {hobby} before checking email",
+        &["routine", "hobbies"],
+    ),
+    (
+        MemoryKind::Procedural,
+        "Reviews pull requests in {tool} right after lunch",
+        &["work", "routine"],
+    ),
+    (
+        MemoryKind::Instruction,
+        "Wants code examples written in {tool}",
+        &["preferences", "work"],
+    ),
+    (
+        MemoryKind::Instruction,
+        "Asks for short answers without bullet points",
+        &["preferences"],
+    ),
+    (
+        MemoryKind::Relational,
+        "{name} is their manager on the {project} team",
+        &["people", "work"],
+    ),
+    (
+        MemoryKind::Relational,
+        "{name} is a close friend from {place}",
+        &["people"],
+    ),
+    (
+        MemoryKind::Relational,
+        "Goes {hobby} with {name} on weekends",
+        &["people", "hobbies"],
+    ),
+    (
+        MemoryKind::Working,
+        "Currently debugging a flaky test in the {project} service",
+        &["work"],
+    ),
+    (
+        MemoryKind::Working,
+        "Is drafting a {tool} proposal for {name}",
+        &["work", "people"],
+    ),
+    (
+        MemoryKind::Prospective,
+        "Plans to visit {place} next spring",
+        &["travel", "plans"],
+    ),
+    (
+        MemoryKind::Prospective,
+        "Wants to pick up {hobby} again after the {project} launch",
+        &["hobbies", "plans"],
+    ),
+];
+
+/// Questions an agent would ask about memories made from `TEMPLATES`, filled the same way.
+const QUERY_TEMPLATES: &[&str] = &[
+    "what food does the user like",
+    "does the user like {food}",
+    "where does the user live",
+    "who is {name}",
+    "when did they visit {place}",
+    "how do they deploy the {project} service",
+    "what does their morning routine look like",
+    "how should answers be formatted",
+    "what are they working on right now",
+    "what are their travel plans",
+    "which tools do they use at work",
+];
+
+fn default_kind_weights() -> Vec<(MemoryKind, u32)> {
+    vec![
+        (MemoryKind::Semantic, 30),
+        (MemoryKind::Episodic, 25),
+        (MemoryKind::Relational, 15),
+        (MemoryKind::Procedural, 10),
+        (MemoryKind::Working, 10),
+        (MemoryKind::Instruction, 5),
+        (MemoryKind::Prospective, 5),
+    ]
+}
+
+/// Produces synthetic memories for load and performance testing. The same `seed`
+/// always yields the same ids, users, kinds and summaries; only timestamps move with
+/// the current time.
+#[derive(TypedBuilder, Debug, Clone)]
+pub struct MemoryGenerator {
+    #[builder(default = 1000)]
+    pub count: usize,
+    /// Memories are spread over users `bench-user-0` to `bench-user-{users - 1}`.
+    #[builder(default = 10)]
+    pub users: usize,
+    /// Relative weight of each kind. Kinds left out are not generated.
+    #[builder(default = default_kind_weights())]
+    pub kind_weights: Vec<(MemoryKind, u32)>,
+    /// Creation times are spread over this many days before now.
+    #[builder(default = 365)]
+    pub days: u32,
+    #[builder(default = 0)]
+    pub seed: u64,
+}
+
+impl Default for MemoryGenerator {
+    fn default() -> Self {
+        MemoryGenerator::builder().build()
+    }
+}
+
+impl MemoryGenerator {
+    pub fn user_id(index: usize) -> String {
+        format!("bench-user-{index}")
+    }
+
+    pub fn generate(&self) -> Result<Vec<Memory>, MemoryError> {
+        self.iter().collect()
+    }
+
+    /// `count` `(user_id, query)` pairs for search workloads against memories from
+    /// this generator.
+    pub fn queries(&self, count: usize) -> Vec<(String, String)> {
+        let mut rng = SplitMix64(self.seed.rotate_left(32));
+        (0..count)
+            .map(|_| {
+                let user_id = Self::user_id(rng.below(self.users.max(1)));
+                let template = QUERY_TEMPLATES[rng.below(QUERY_TEMPLATES.len())];
+                (user_id, fill_template(template, &mut rng))
+            })
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Result<Memory, MemoryError>> + '_ {
+        let mut rng = SplitMix64(self.seed);
+        let now = Utc::now();
+        (0..self.count).map(move |_| self.next_memory(&mut rng, now))
+    }
+
+    fn next_memory(&self, rng: &mut SplitMix64, now: DateTime<Utc>) -> Result<Memory, MemoryError> {
+        let kind = self.pick_kind(rng);
+        let templates: Vec<_> = TEMPLATES
+            .iter()
+            .filter(|(template_kind, _, _)| *template_kind == kind)
+            .collect();
+        let (_, template, tags) = templates[rng.below(templates.len())];
+
+        let user_id = Self::user_id(rng.below(self.users.max(1)));
+        let context = match kind {
+            MemoryKind::Working => MemoryContext::new(
+                Some(user_id.clone()),
+                None,
+                Some(format!("{user_id}-run-{}", rng.below(5))),
+            )?,
+            _ => MemoryContext::for_user(user_id)?,
+        };
+
+        let created_at =
+            now - Duration::seconds(rng.below(self.days.max(1) as usize * 86_400) as i64);
+
+        let mut id = [0u8; 16];
+        id[..8].copy_from_slice(&rng.next_u64().to_le_bytes());
+        id[8..].copy_from_slice(&rng.next_u64().to_le_bytes());
+
+        Memory::builder()
+            .id(uuid::Builder::from_random_bytes(id).into_uuid())
+            .content(MemoryContent::new(
+                fill_template(template, rng),
+                tags.iter().map(|tag| tag.to_string()).collect(),
+            )?)
+            .context(context)
+            .kind(kind)
+            .signals(MemorySignals::new(
+                Credence::new(0.3 + 0.7 * rng.unit())?,
+                Credence::new(0.3 + 0.7 * rng.unit())?,
+            )?)
+            .provenance(Provenance::direct_user())
+            .lifecycle(LifecycleState::Active)
+            .temporal(TemporalMetadata::new(created_at))
+            .build()
+            .finish()
+    }
+
+    fn pick_kind(&self, rng: &mut SplitMix64) -> MemoryKind {
+        let total: u32 = self.kind_weights.iter().map(|(_, weight)| weight).sum();
+        if total == 0 {
+            return MemoryKind::Semantic;
+        }
+
+        let mut roll = rng.below(total as usize) as u32;
+        for (kind, weight) in &self.kind_weights {
+            if roll < *weight {
+                return *kind;
+            }
+            roll -= weight;
+        }
+        MemoryKind::Semantic
+    }
+}
+
+fn fill_template(template: &str, rng: &mut SplitMix64) -> String {
+    let mut summary = template.to_string();
+    for (slot, words) in [
+        ("{name}", NAMES),
+        ("{place}", PLACES),
+        ("{food}", FOODS),
+        ("{tool}", TOOLS),
+        ("{hobby}", HOBBIES),
+        ("{project}", PROJECTS),
+    ] {
+        if summary.contains(slot) {
+            summary = summary.replace(slot, words[rng.below(words.len())]);
+        }
+    }
+    summary
+}
+
+/// splitmix64, small and good enough for test data without pulling in `rand`.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn unit(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
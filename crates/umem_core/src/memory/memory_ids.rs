@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum MemoryIdError {
+    #[error("{field} must not be empty or whitespace")]
+    EmptyId { field: &'static str },
+
+    #[error("memory id is not a valid uuid: {0}")]
+    InvalidMemoryId(String),
+}
+
+/// The id of a stored memory, which is also the id of its vector.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct MemoryId(Uuid);
+
+impl MemoryId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn parse(id: &str) -> Result<Self, MemoryIdError> {
+        let id = id.trim();
+        if id.is_empty() {
+            return Err(MemoryIdError::EmptyId { field: "memory_id" });
+        }
+        Uuid::parse_str(id)
+            .map(Self)
+            .map_err(|_| MemoryIdError::InvalidMemoryId(id.to_string()))
+    }
+
+    pub fn as_uuid(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl From<Uuid> for MemoryId {
+    fn from(id: Uuid) -> Self {
+        Self(id)
+    }
+}
+
+impl From<MemoryId> for Uuid {
+    fn from(id: MemoryId) -> Self {
+        id.0
+    }
+}
+
+impl FromStr for MemoryId {
+    type Err = MemoryIdError;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        Self::parse(id)
+    }
+}
+
+impl fmt::Display for MemoryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An identifier of a memory context: trimmed, and never empty.
+macro_rules! context_id {
+    ($name:ident, $field:literal) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn parse(id: impl Into<String>) -> Result<Self, MemoryIdError> {
+                let id = id.into();
+                let trimmed = id.trim();
+                if trimmed.is_empty() {
+                    return Err(MemoryIdError::EmptyId { field: $field });
+                }
+                Ok(Self(trimmed.to_string()))
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = MemoryIdError;
+
+            fn try_from(id: String) -> Result<Self, Self::Error> {
+                Self::parse(id)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = MemoryIdError;
+
+            fn from_str(id: &str) -> Result<Self, Self::Err> {
+                Self::parse(id)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+context_id!(UserId, "user_id");
+context_id!(AgentId, "agent_id");
+context_id!(RunId, "run_id");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_validated_when_parsed() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            MemoryId::parse(&format!(" {id} ")).unwrap(),
+            MemoryId::from(id)
+        );
+        assert_eq!(
+            MemoryId::parse("not-a-uuid"),
+            Err(MemoryIdError::InvalidMemoryId("not-a-uuid".to_string()))
+        );
+
+        assert_eq!(UserId::parse(" alice ").unwrap().as_str(), "alice");
+        assert_eq!(
+            RunId::parse("  "),
+            Err(MemoryIdError::EmptyId { field: "run_id" })
+        );
+        assert!(AgentId::try_from(String::new()).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum MemoryLanguageError {
+    #[error("language code cannot be empty or whitespace")]
+    EmptyLanguage,
+
+    #[error("original summary cannot be empty or whitespace")]
+    EmptyOriginalSummary,
+}
+
+#[derive(Debug, schemars::JsonSchema, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct MemoryLanguage {
+    language: String,
+    original_summary: Option<String>,
+}
+
+impl MemoryLanguage {
+    pub fn new(language: impl Into<String>) -> Result<Self, MemoryLanguageError> {
+        let language = language.into().trim().to_ascii_lowercase();
+
+        if language.is_empty() {
+            return Err(MemoryLanguageError::EmptyLanguage);
+        }
+
+        Ok(Self {
+            language,
+            original_summary: None,
+        })
+    }
+
+    pub fn translated(
+        language: impl Into<String>,
+        original_summary: impl Into<String>,
+    ) -> Result<Self, MemoryLanguageError> {
+        let original_summary = original_summary.into();
+
+        if original_summary.trim().is_empty() {
+            return Err(MemoryLanguageError::EmptyOriginalSummary);
+        }
+
+        Ok(Self {
+            original_summary: Some(original_summary),
+            ..Self::new(language)?
+        })
+    }
+
+    pub fn is_translated(&self) -> bool {
+        self.original_summary.is_some()
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn original_summary(&self) -> Option<&str> {
+        self.original_summary.as_deref()
+    }
+}
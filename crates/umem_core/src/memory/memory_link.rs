@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// A subject-predicate-object relation extracted from a relational memory. Kept on the
+/// memory so the relation graph can be rebuilt from the store.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MemoryLink {
+    pub subject: String,
+    pub predicate: String,
+    pub object: String,
+}
@@ -0,0 +1,120 @@
+use crate::{Memory, ProvenanceMethod};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Placeholder written over masked string fields.
+pub const REDACTION_MASK: &str = "[redacted]";
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("invalid redacted field: {input}")]
+pub struct ParseRedactedFieldError {
+    pub input: String,
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("invalid redaction mode: {input}")]
+pub struct ParseRedactionModeError {
+    pub input: String,
+}
+
+/// Memory fields a response policy can hide from callers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactedField {
+    /// The extraction prompt, which embeds the raw source content.
+    ProvenancePrompt,
+    ProvenanceModel,
+    Context,
+    Tags,
+    Contradicts,
+    Language,
+}
+
+impl FromStr for RedactedField {
+    type Err = ParseRedactedFieldError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "provenance_prompt" => Ok(Self::ProvenancePrompt),
+            "provenance_model" => Ok(Self::ProvenanceModel),
+            "context" => Ok(Self::Context),
+            "tags" => Ok(Self::Tags),
+            "contradicts" => Ok(Self::Contradicts),
+            "language" => Ok(Self::Language),
+            s => Err(ParseRedactedFieldError {
+                input: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// `Strip` empties a field; `Mask` overwrites string fields with [`REDACTION_MASK`] so
+/// callers can tell a value exists. List and optional fields are emptied either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionMode {
+    Strip,
+    Mask,
+}
+
+impl RedactionMode {
+    fn replacement(&self) -> &'static str {
+        match self {
+            RedactionMode::Strip => "",
+            RedactionMode::Mask => REDACTION_MASK,
+        }
+    }
+}
+
+impl FromStr for RedactionMode {
+    type Err = ParseRedactionModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "strip" => Ok(Self::Strip),
+            "mask" => Ok(Self::Mask),
+            s => Err(ParseRedactionModeError {
+                input: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl Memory {
+    /// Hides `field` for presentation. The result is not meant to be stored again.
+    pub fn redact(&mut self, field: RedactedField, mode: RedactionMode) {
+        let replacement = mode.replacement();
+
+        match field {
+            RedactedField::ProvenancePrompt => {
+                for method in self.provenance.chain.iter_mut() {
+                    if let ProvenanceMethod::Extracted { prompt, .. } = method {
+                        *prompt = replacement.to_string();
+                    }
+                }
+            }
+            RedactedField::ProvenanceModel => {
+                for method in self.provenance.chain.iter_mut() {
+                    match method {
+                        ProvenanceMethod::Extracted { model, .. }
+                        | ProvenanceMethod::Summarized { model } => {
+                            *model = replacement.to_string()
+                        }
+                        ProvenanceMethod::Direct | ProvenanceMethod::Imported { .. } => {}
+                    }
+                }
+            }
+            RedactedField::Context => {
+                let replacement = match mode {
+                    RedactionMode::Strip => None,
+                    RedactionMode::Mask => Some(replacement),
+                };
+                self.context.redact(replacement);
+            }
+            RedactedField::Tags => self.content.clear_tags(),
+            RedactedField::Contradicts => self.contradicts.clear(),
+            RedactedField::Language => self.language = None,
+        }
+    }
+}
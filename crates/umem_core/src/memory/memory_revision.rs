@@ -0,0 +1,18 @@
+use crate::{MemoryContent, MemoryKind, MemoryLanguage, MemorySchedule};
+use serde::{Deserialize, Serialize};
+
+/// Most revisions kept per memory; older ones are dropped first.
+pub const MAX_MEMORY_REVISIONS: usize = 50;
+
+/// What a memory said before an edit or a new annotation replaced it, so a search for
+/// an earlier time can show the memory as it stood then.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemoryRevision {
+    /// The replaced content, without its raw content, which edits keep.
+    pub content: MemoryContent,
+    pub kind: MemoryKind,
+    pub language: Option<MemoryLanguage>,
+    pub schedule: Option<MemorySchedule>,
+    /// When the content was replaced.
+    pub until: i64,
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Days, Months, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum MemoryScheduleError {
+    #[error("due_at is not a valid RFC 3339 timestamp: {0}")]
+    InvalidDueAt(String),
+
+    #[error("due_at timestamp is out of range: {0}")]
+    OutOfRange(i64),
+}
+
+#[derive(Debug, schemars::JsonSchema, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Recurrence {
+    fn advance(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Recurrence::Daily => time.checked_add_days(Days::new(1)),
+            Recurrence::Weekly => time.checked_add_days(Days::new(7)),
+            Recurrence::Monthly => time.checked_add_months(Months::new(1)),
+            Recurrence::Yearly => time.checked_add_months(Months::new(12)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MemorySchedule {
+    due_at: i64,
+    #[serde(default)]
+    recurrence: Option<Recurrence>,
+}
+
+impl MemorySchedule {
+    pub fn new(due_at: DateTime<Utc>, recurrence: Option<Recurrence>) -> Self {
+        Self {
+            due_at: due_at.timestamp(),
+            recurrence,
+        }
+    }
+
+    pub fn parse(
+        due_at: &str,
+        recurrence: Option<Recurrence>,
+    ) -> Result<Self, MemoryScheduleError> {
+        let due_at = DateTime::parse_from_rfc3339(due_at.trim())
+            .map_err(|_| MemoryScheduleError::InvalidDueAt(due_at.to_string()))?;
+
+        Ok(Self::new(due_at.with_timezone(&Utc), recurrence))
+    }
+
+    pub fn due_at(&self) -> i64 {
+        self.due_at
+    }
+
+    pub fn recurrence(&self) -> Option<Recurrence> {
+        self.recurrence
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.due_at <= now.timestamp()
+    }
+
+    /// Returns the first occurrence strictly after `now`, or `None` for one-off schedules.
+    pub fn next_after(&self, now: DateTime<Utc>) -> Result<Option<Self>, MemoryScheduleError> {
+        let Some(recurrence) = self.recurrence else {
+            return Ok(None);
+        };
+
+        let mut due_at = DateTime::from_timestamp(self.due_at, 0)
+            .ok_or(MemoryScheduleError::OutOfRange(self.due_at))?;
+        while due_at <= now {
+            due_at = recurrence
+                .advance(due_at)
+                .ok_or(MemoryScheduleError::OutOfRange(due_at.timestamp()))?;
+        }
+
+        Ok(Some(Self::new(due_at, Some(recurrence))))
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Which pool a memory belongs to. Searches for a member of an organization return
+/// memories from both.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub enum MemoryScope {
+    /// Stored for a user, agent or run.
+    #[default]
+    Personal,
+    /// Published to an organization for all of its members.
+    Org,
+}
+
+impl MemoryScope {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Personal => "Personal",
+            Self::Org => "Org",
+        }
+    }
+}
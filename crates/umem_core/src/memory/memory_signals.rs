@@ -8,12 +8,25 @@ pub enum MemorySignalsError {
     DeadMemory,
 }
 
-#[derive(Serialize, Debug, schemars::JsonSchema, Clone, Default, Deserialize)]
+/// Signals of a memory stored without any: taken as stated, of middling salience.
+const DEFAULT_CERTAINTY: f32 = 1.0;
+const DEFAULT_SALIENCE: f32 = 0.5;
+
+#[derive(Serialize, Debug, schemars::JsonSchema, Clone, Deserialize)]
 pub struct MemorySignals {
     certainty: Credence,
     salience: Credence,
 }
 
+impl Default for MemorySignals {
+    fn default() -> Self {
+        Self {
+            certainty: Credence::new(DEFAULT_CERTAINTY).expect("default certainty is a credence"),
+            salience: Credence::new(DEFAULT_SALIENCE).expect("default salience is a credence"),
+        }
+    }
+}
+
 impl MemorySignals {
     pub fn new(certainty: Credence, salience: Credence) -> Result<Self, MemorySignalsError> {
         if certainty.get() == 0.0 && salience.get() == 0.0 {
@@ -26,6 +39,11 @@ impl MemorySignals {
         })
     }
 
+    /// Rejects signals that `new` would, e.g. ones read from an older payload.
+    pub fn validate(&self) -> Result<(), MemorySignalsError> {
+        Self::new(self.certainty, self.salience).map(|_| ())
+    }
+
     pub fn get_certainty(&self) -> f32 {
         self.certainty.get()
     }
@@ -34,6 +52,10 @@ impl MemorySignals {
         self.salience.get()
     }
 
+    pub fn with_salience(&self, salience: Credence) -> Result<Self, MemorySignalsError> {
+        Self::new(self.certainty, salience)
+    }
+
     pub fn is_weak(&self) -> bool {
         self.certainty.get() < 0.3 && self.salience.get() < 0.3
     }
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// How often agents reported a memory as used after retrieving it.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MemoryUsage {
+    use_count: u32,
+    #[serde(default)]
+    last_used_at: Option<i64>,
+}
+
+impl MemoryUsage {
+    pub fn record(&mut self, time: i64) {
+        self.use_count = self.use_count.saturating_add(1);
+        self.last_used_at = Some(time);
+    }
+
+    pub fn use_count(&self) -> u32 {
+        self.use_count
+    }
+
+    pub fn last_used_at(&self) -> Option<i64> {
+        self.last_used_at
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Which agents of the owning user can retrieve a memory. Memories without an agent
+/// already belong to the whole user.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub enum MemoryVisibility {
+    /// Only queries for the agent that created it return it.
+    #[default]
+    Private,
+    /// Queries for any agent of the same user return it.
+    Shared,
+}
+
+impl MemoryVisibility {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Private => "Private",
+            Self::Shared => "Shared",
+        }
+    }
+
+    pub fn is_shared(&self) -> bool {
+        matches!(self, MemoryVisibility::Shared)
+    }
+}
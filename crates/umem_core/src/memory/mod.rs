@@ -1,6 +1,6 @@
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use std::hash::Hash;
+use std::{collections::BTreeMap, hash::Hash};
 use thiserror::Error;
 use typed_builder::TypedBuilder;
 use uuid::Uuid;
@@ -9,20 +9,34 @@ pub mod credence;
 pub mod lifecycle_state;
 pub mod memory_content;
 pub mod memory_context;
+pub mod memory_facets;
+pub mod memory_generator;
+pub mod memory_ids;
 pub mod memory_kind;
+pub mod memory_language;
+pub mod memory_link;
+pub mod memory_redaction;
+pub mod memory_revision;
+pub mod memory_schedule;
+pub mod memory_scope;
 pub mod memory_signals;
+pub mod memory_usage;
+pub mod memory_visibility;
 pub mod provenance;
 pub mod query;
+pub mod query_syntax;
+pub mod retrieval_feedback;
+pub mod signal_adjustment;
 pub mod temporal_metadata;
 
-use crate::credence::{
-    // Credence,
-    CredenceError,
-};
+use crate::credence::{Credence, CredenceError};
 
 pub use crate::{
-    lifecycle_state::*, memory_content::*, memory_context::*, memory_kind::*, memory_signals::*,
-    provenance::*, query::*, temporal_metadata::*,
+    lifecycle_state::*, memory_content::*, memory_context::*, memory_facets::*,
+    memory_generator::*, memory_ids::*, memory_kind::*, memory_language::*, memory_link::*,
+    memory_redaction::*, memory_revision::*, memory_schedule::*, memory_scope::*,
+    memory_signals::*, memory_usage::*, memory_visibility::*, provenance::*, query::*,
+    query_syntax::*, retrieval_feedback::*, signal_adjustment::*, temporal_metadata::*,
 };
 
 #[derive(Debug, Error, Clone)]
@@ -36,6 +50,15 @@ pub enum MemoryError {
     #[error("invalid memory context: {0}")]
     ContextError(#[from] MemoryContextError),
 
+    #[error("invalid memory language: {0}")]
+    LanguageError(#[from] MemoryLanguageError),
+
+    #[error("invalid memory schedule: {0}")]
+    ScheduleError(#[from] MemoryScheduleError),
+
+    #[error("only prospective memories can have a schedule, got {0:?}")]
+    ScheduleOnNonProspective(MemoryKind),
+
     #[error("invalid memory signals: {0}")]
     SignalsError(#[from] MemorySignalsError),
 
@@ -56,6 +79,21 @@ pub enum MemoryError {
 
     #[error("lifecycle state is Active but archived_at timestamp is set")]
     ActiveWithArchivedTimestamp,
+
+    #[error("lifecycle state is Deleted but deleted_at timestamp is not set")]
+    DeletedWithoutTimestamp,
+
+    #[error("lifecycle state is Suppressed but suppressed_at timestamp is not set")]
+    SuppressedWithoutTimestamp,
+
+    #[error("lifecycle state is Superseded but superseded_by is not set")]
+    SupersededWithoutReplacement,
+
+    #[error("superseded_by is set but lifecycle state is {0:?}")]
+    ReplacementWithoutSuperseded(LifecycleState),
+
+    #[error("a memory cannot supersede itself")]
+    SupersededBySelf,
 }
 
 #[derive(TypedBuilder, Serialize, Debug, Default, Deserialize)]
@@ -65,9 +103,67 @@ pub struct Memory {
     lifecycle: LifecycleState,
     kind: MemoryKind,
     content: MemoryContent,
-    // signals: MemorySignals,
+    #[builder(default)]
+    #[serde(default)]
+    signals: MemorySignals,
     temporal: TemporalMetadata,
-    // provenance: Provenance,
+    #[builder(default)]
+    #[serde(default)]
+    provenance: Provenance,
+    #[builder(default)]
+    #[serde(default)]
+    language: Option<MemoryLanguage>,
+    #[builder(default)]
+    #[serde(default)]
+    contradicts: Vec<Uuid>,
+    #[builder(default)]
+    #[serde(default)]
+    schedule: Option<MemorySchedule>,
+    #[builder(default)]
+    #[serde(default)]
+    sources: Vec<String>,
+    #[builder(default)]
+    #[serde(default)]
+    usage: MemoryUsage,
+    #[builder(default)]
+    #[serde(default)]
+    feedback: Vec<RetrievalFeedback>,
+    #[builder(default)]
+    #[serde(default)]
+    adjustments: Vec<SignalAdjustment>,
+    /// The memory that replaced this one, set while it is Superseded.
+    #[builder(default)]
+    #[serde(default)]
+    superseded_by: Option<Uuid>,
+    #[builder(default)]
+    #[serde(default)]
+    visibility: MemoryVisibility,
+    /// Values of the kind's template fields, except `due_at`, which is the schedule.
+    #[builder(default)]
+    #[serde(default)]
+    fields: BTreeMap<String, String>,
+    /// Label of the topic cluster the memory was last assigned to.
+    #[builder(default)]
+    #[serde(default)]
+    topic: Option<String>,
+    /// The embedding model that produced the memory's vector; unset for memories
+    /// embedded before it was recorded.
+    #[builder(default)]
+    #[serde(default)]
+    embedding_model: Option<String>,
+    /// Bumped by every change that is stored, so a replica can tell whether its copy is
+    /// still the one stored. Memories stored before it was recorded start at 0.
+    #[builder(default)]
+    #[serde(default)]
+    version: u64,
+    /// Earlier content, oldest first, for showing the memory as it stood at a past time.
+    #[builder(default)]
+    #[serde(default)]
+    revisions: Vec<MemoryRevision>,
+    /// Relations extracted from the content, which the relation graph is rebuilt from.
+    #[builder(default)]
+    #[serde(default)]
+    links: Vec<MemoryLink>,
 }
 
 impl Hash for Memory {
@@ -79,10 +175,26 @@ impl Hash for Memory {
 type Result<T> = std::result::Result<T, MemoryError>;
 
 impl Memory {
+    /// Validates a memory fresh from `Memory::builder()`, so an inconsistent one is
+    /// rejected where it is constructed rather than wherever it is validated next.
+    pub fn finish(self) -> Result<Self> {
+        self.validate()?;
+        Ok(self)
+    }
+
     pub fn validate(&self) -> Result<()> {
         self.context.validate()?;
         // self.temporal.validate()?;
-        // self.provenance.validate()?;
+        self.provenance.validate()?;
+        self.signals.validate()?;
+
+        if self.content.summary().trim().is_empty() {
+            return Err(MemoryContentError::EmptySummary.into());
+        }
+
+        if self.schedule.is_some() && self.kind != MemoryKind::Prospective {
+            return Err(MemoryError::ScheduleOnNonProspective(self.kind));
+        }
 
         match (&self.lifecycle, self.temporal.archived_at()) {
             (LifecycleState::Archived, None) => {
@@ -94,20 +206,350 @@ impl Memory {
             _ => {}
         }
 
+        if self.lifecycle.is_deleted() && self.temporal.deleted_at().is_none() {
+            return Err(MemoryError::DeletedWithoutTimestamp);
+        }
+
+        if self.lifecycle.is_suppressed() && self.temporal.suppressed_at().is_none() {
+            return Err(MemoryError::SuppressedWithoutTimestamp);
+        }
+
+        match (&self.lifecycle, self.superseded_by) {
+            (LifecycleState::Superseded, None) => {
+                return Err(MemoryError::SupersededWithoutReplacement);
+            }
+            (_, Some(by)) if by == self.id => {
+                return Err(MemoryError::SupersededBySelf);
+            }
+            (LifecycleState::Active | LifecycleState::Archived, Some(_)) => {
+                return Err(MemoryError::ReplacementWithoutSuperseded(self.lifecycle));
+            }
+            _ => {}
+        }
+
         Ok(())
     }
 
     pub fn mark_updated(&mut self, time: chrono::DateTime<chrono::Utc>) -> Result<()> {
         self.temporal.mark_updated(time.timestamp())?;
+        self.version += 1;
         Ok(())
     }
 
+    /// Replaces the summary, keeping the tags and raw content, and marks the memory
+    /// updated.
+    pub fn edit_summary(
+        &mut self,
+        summary: impl Into<String>,
+        time: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let mut content = MemoryContent::new(summary, self.content.tags().clone())?;
+        content.set_raw_content(self.content.raw_content().cloned());
+        self.record_revision(time.timestamp());
+        self.content = content;
+        self.mark_updated(time)
+    }
+
     pub fn archive(&mut self, time: chrono::DateTime<chrono::Utc>) -> Result<()> {
         self.temporal.mark_archived(time.timestamp())?;
         self.lifecycle = LifecycleState::Archived;
+        self.version += 1;
+        Ok(())
+    }
+
+    /// Moves the memory to the trash. `restore` brings it back in the state it had.
+    pub fn soft_delete(&mut self, time: chrono::DateTime<chrono::Utc>) {
+        self.temporal.mark_deleted(time.timestamp());
+        self.lifecycle = LifecycleState::Deleted;
+        self.version += 1;
+    }
+
+    /// Takes the memory out of the trash in the state it was deleted from.
+    pub fn restore(&mut self, time: chrono::DateTime<chrono::Utc>) {
+        if !self.lifecycle.is_deleted() {
+            return;
+        }
+        self.temporal.clear_deleted();
+        self.lifecycle = self.resting_state();
+        self.touch(time);
+    }
+
+    /// Marks the memory as replaced by `by`. It stays retrievable next to archived
+    /// memories, pointing at its replacement.
+    pub fn supersede(&mut self, by: Uuid, time: chrono::DateTime<chrono::Utc>) -> Result<()> {
+        if by == self.id {
+            return Err(MemoryError::SupersededBySelf);
+        }
+        self.temporal.mark_superseded(time.timestamp());
+        self.superseded_by = Some(by);
+        self.lifecycle = LifecycleState::Superseded;
+        self.touch(time);
         Ok(())
     }
 
+    /// Hides the memory from every query on the user's request.
+    pub fn suppress(&mut self, time: chrono::DateTime<chrono::Utc>) {
+        self.temporal.mark_suppressed(time.timestamp());
+        self.lifecycle = LifecycleState::Suppressed;
+        self.version += 1;
+    }
+
+    pub fn unsuppress(&mut self, time: chrono::DateTime<chrono::Utc>) {
+        if !self.lifecycle.is_suppressed() {
+            return;
+        }
+        self.temporal.clear_suppressed();
+        self.lifecycle = self.resting_state();
+        self.touch(time);
+    }
+
+    /// The state the memory's own history points to, ignoring the trash.
+    fn resting_state(&self) -> LifecycleState {
+        if self.temporal.suppressed_at().is_some() {
+            LifecycleState::Suppressed
+        } else if self.superseded_by.is_some() {
+            LifecycleState::Superseded
+        } else if self.temporal.archived_at().is_some() {
+            LifecycleState::Archived
+        } else {
+            LifecycleState::Active
+        }
+    }
+
+    pub fn mark_contradiction(&mut self, other: Uuid, time: chrono::DateTime<chrono::Utc>) {
+        if other != self.id && !self.contradicts.contains(&other) {
+            self.contradicts.push(other);
+            self.touch(time);
+        }
+    }
+
+    /// Drops `other` from the contradictions, returning whether it was there.
+    pub fn unmark_contradiction(
+        &mut self,
+        other: &Uuid,
+        time: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        let before = self.contradicts.len();
+        self.contradicts.retain(|id| id != other);
+        let changed = self.contradicts.len() != before;
+        if changed {
+            self.touch(time);
+        }
+        changed
+    }
+
+    pub fn replace_tags(
+        &mut self,
+        from: &[String],
+        to: &str,
+        time: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        let changed = self.content.replace_tags(from, to);
+        if changed {
+            self.touch(time);
+        }
+        changed
+    }
+
+    /// Records that an agent used this memory and moves its salience `boost` of the way
+    /// towards 1.0, so memories that keep helping rise gradually.
+    pub fn mark_used(&mut self, time: chrono::DateTime<chrono::Utc>, boost: f32) -> Result<()> {
+        self.usage.record(time.timestamp());
+        let salience = self.signals.get_salience();
+        self.signals = self.signals.with_salience(Credence::new(
+            (salience + (1.0 - salience) * boost).min(1.0),
+        )?)?;
+        self.touch(time);
+        Ok(())
+    }
+
+    /// Records whether this memory helped answer `query`, keeping the most recent
+    /// `MAX_RETRIEVAL_FEEDBACK` entries.
+    pub fn add_feedback(
+        &mut self,
+        query: String,
+        helpful: bool,
+        time: chrono::DateTime<chrono::Utc>,
+    ) {
+        self.feedback.push(RetrievalFeedback {
+            query,
+            helpful,
+            at: time.timestamp(),
+        });
+        if self.feedback.len() > MAX_RETRIEVAL_FEEDBACK {
+            let excess = self.feedback.len() - MAX_RETRIEVAL_FEEDBACK;
+            self.feedback.drain(..excess);
+        }
+        self.touch(time);
+    }
+
+    /// Moves certainty and salience by the given deltas, clamped to [0.0, 1.0], and
+    /// records who adjusted them, keeping the most recent `MAX_SIGNAL_ADJUSTMENTS`.
+    pub fn adjust_signals(
+        &mut self,
+        delta_certainty: f32,
+        delta_salience: f32,
+        by: Option<String>,
+        time: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let certainty =
+            Credence::new((self.signals.get_certainty() + delta_certainty).clamp(0.0, 1.0))?;
+        let salience =
+            Credence::new((self.signals.get_salience() + delta_salience).clamp(0.0, 1.0))?;
+        self.signals = MemorySignals::new(certainty, salience)?;
+
+        self.adjustments.push(SignalAdjustment {
+            by,
+            delta_certainty,
+            delta_salience,
+            certainty: certainty.get(),
+            salience: salience.get(),
+            at: time.timestamp(),
+        });
+        if self.adjustments.len() > MAX_SIGNAL_ADJUSTMENTS {
+            let excess = self.adjustments.len() - MAX_SIGNAL_ADJUSTMENTS;
+            self.adjustments.drain(..excess);
+        }
+        self.touch(time);
+        Ok(())
+    }
+
+    /// Lowers salience by `rate` of its value, never below `floor`.
+    pub fn decay_salience(
+        &mut self,
+        rate: f32,
+        floor: f32,
+        time: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let salience = self.signals.get_salience();
+        if salience <= floor {
+            return Ok(());
+        }
+        self.signals = self
+            .signals
+            .with_salience(Credence::new((salience * (1.0 - rate)).max(floor))?)?;
+        self.touch(time);
+        Ok(())
+    }
+
+    pub fn reschedule(
+        &mut self,
+        schedule: Option<MemorySchedule>,
+        time: chrono::DateTime<chrono::Utc>,
+    ) {
+        self.schedule = schedule;
+        self.touch(time);
+    }
+
+    pub fn set_topic(&mut self, topic: Option<String>, time: chrono::DateTime<chrono::Utc>) {
+        self.topic = topic;
+        self.touch(time);
+    }
+
+    pub fn set_links(&mut self, links: Vec<MemoryLink>, time: chrono::DateTime<chrono::Utc>) {
+        self.links = links;
+        self.touch(time);
+    }
+
+    pub fn set_embedding_model(&mut self, model: impl Into<String>) {
+        self.embedding_model = Some(model.into());
+    }
+
+    pub fn set_visibility(
+        &mut self,
+        visibility: MemoryVisibility,
+        time: chrono::DateTime<chrono::Utc>,
+    ) {
+        self.visibility = visibility;
+        self.touch(time);
+    }
+
+    pub fn set_context(&mut self, context: MemoryContext, time: chrono::DateTime<chrono::Utc>) {
+        self.context = context;
+        self.touch(time);
+    }
+
+    /// Marks a change that is not an edit, such as new signals or visibility, so the
+    /// changes feed picks it up and a replica holding the previous version sees its copy
+    /// is stale. Edits go through `mark_updated`.
+    fn touch(&mut self, time: chrono::DateTime<chrono::Utc>) {
+        self.temporal.touch(time.timestamp());
+        self.version += 1;
+    }
+
+    /// Takes over a new annotation of this memory's content, keeping its id, context,
+    /// history, usage, links and raw content. Salience is kept as well, since it tracks
+    /// how the memory has been used since, and the new provenance is appended to the
+    /// old chain so the memory keeps its lineage.
+    #[allow(clippy::too_many_arguments)]
+    pub fn reannotate(
+        &mut self,
+        mut content: MemoryContent,
+        kind: MemoryKind,
+        certainty: Credence,
+        provenance: Provenance,
+        language: Option<MemoryLanguage>,
+        schedule: Option<MemorySchedule>,
+        time: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        self.record_revision(time.timestamp());
+        self.signals = MemorySignals::new(certainty, Credence::new(self.signals.get_salience())?)?;
+        if content.raw_content().is_none() {
+            content.set_raw_content(self.content.raw_content().cloned());
+        }
+        self.content = content;
+        self.kind = kind;
+        self.provenance = std::mem::take(&mut self.provenance).followed_by(provenance);
+        self.language = language;
+        self.schedule = schedule;
+        self.validate()
+    }
+
+    /// Keeps the current content as a revision replaced at `until`.
+    fn record_revision(&mut self, until: i64) {
+        let mut content = self.content.clone();
+        content.set_raw_content(None);
+        self.revisions.push(MemoryRevision {
+            content,
+            kind: self.kind,
+            language: self.language.clone(),
+            schedule: self.schedule,
+            until,
+        });
+        if self.revisions.len() > MAX_MEMORY_REVISIONS {
+            let excess = self.revisions.len() - MAX_MEMORY_REVISIONS;
+            self.revisions.drain(..excess);
+        }
+    }
+
+    /// Puts back the content, kind, language and schedule the memory had at `time`, to
+    /// show it as it stood then. Signals, usage and links are left as they are now.
+    /// Before the oldest kept revision, the memory shows that revision. The rewound
+    /// memory is a view of the past and must not be stored.
+    pub fn rewind(&mut self, time: chrono::DateTime<chrono::Utc>) {
+        let time = time.timestamp();
+        let Some(index) = self.revisions.iter().position(|rev| rev.until > time) else {
+            return;
+        };
+        let revision = self.revisions.swap_remove(index);
+        self.revisions.truncate(index);
+
+        let raw_content = self.content.raw_content().cloned();
+        self.content = revision.content;
+        self.content.set_raw_content(raw_content);
+        self.kind = revision.kind;
+        self.language = revision.language;
+        self.schedule = revision.schedule;
+    }
+
+    pub fn set_raw_content(&mut self, raw_content: Option<RawContent>) {
+        self.content.set_raw_content(raw_content);
+    }
+
+    pub fn set_moderation(&mut self, moderation: Moderation) {
+        self.provenance.moderation = Some(moderation);
+    }
+
     pub fn is_active(&self) -> bool {
         self.lifecycle.is_active()
     }
@@ -116,14 +558,34 @@ impl Memory {
         self.lifecycle.is_archived()
     }
 
-    // pub fn score(&self) -> u8 {
-    //     self.signals.get_certainty() * self.signals.get_salience()
-    // }
+    pub fn is_deleted(&self) -> bool {
+        self.lifecycle.is_deleted()
+    }
+
+    pub fn is_superseded(&self) -> bool {
+        self.lifecycle.is_superseded()
+    }
+
+    pub fn is_suppressed(&self) -> bool {
+        self.lifecycle.is_suppressed()
+    }
+
+    pub fn is_shared(&self) -> bool {
+        self.visibility.is_shared()
+    }
+
+    pub fn score(&self) -> f32 {
+        self.signals.get_certainty() * self.signals.get_salience()
+    }
 
     pub fn get_id(&self) -> &Uuid {
         &self.id
     }
 
+    pub fn memory_id(&self) -> MemoryId {
+        MemoryId::from(self.id)
+    }
+
     pub fn get_summary(&self) -> &String {
         self.content.summary()
     }
@@ -144,34 +606,231 @@ impl Memory {
         &self.content
     }
 
-    // pub fn signals(&self) -> &MemorySignals {
-    //     &self.signals
-    // }
+    pub fn signals(&self) -> &MemorySignals {
+        &self.signals
+    }
 
     pub fn temporal(&self) -> &TemporalMetadata {
         &self.temporal
     }
 
-    // pub fn provenance(&self) -> &Provenance {
-    //     &self.provenance
-    // }
+    pub fn provenance(&self) -> &Provenance {
+        &self.provenance
+    }
+
+    pub fn language(&self) -> Option<&MemoryLanguage> {
+        self.language.as_ref()
+    }
+
+    pub fn contradicts(&self) -> &[Uuid] {
+        &self.contradicts
+    }
+
+    pub fn superseded_by(&self) -> Option<&Uuid> {
+        self.superseded_by.as_ref()
+    }
+
+    /// Ids of the document chunks this memory was distilled from.
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    pub fn schedule(&self) -> Option<&MemorySchedule> {
+        self.schedule.as_ref()
+    }
+
+    pub fn usage(&self) -> &MemoryUsage {
+        &self.usage
+    }
+
+    pub fn feedback(&self) -> &[RetrievalFeedback] {
+        &self.feedback
+    }
+
+    pub fn adjustments(&self) -> &[SignalAdjustment] {
+        &self.adjustments
+    }
+
+    pub fn visibility(&self) -> MemoryVisibility {
+        self.visibility
+    }
+
+    pub fn scope(&self) -> MemoryScope {
+        self.context.scope()
+    }
+
+    pub fn topic(&self) -> Option<&str> {
+        self.topic.as_deref()
+    }
+
+    pub fn links(&self) -> &[MemoryLink] {
+        &self.links
+    }
+
+    pub fn embedding_model(&self) -> Option<&str> {
+        self.embedding_model.as_deref()
+    }
+
+    pub fn fields(&self) -> &BTreeMap<String, String> {
+        &self.fields
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn revisions(&self) -> &[MemoryRevision] {
+        &self.revisions
+    }
+
+    /// When the content was last written: its last edit or new annotation, else its
+    /// creation. Unlike `updated_at`, usage and signal changes do not move it.
+    pub fn content_changed_at(&self) -> i64 {
+        self.revisions
+            .last()
+            .map_or(self.temporal.created_at(), |revision| revision.until)
+    }
 
     pub fn gen_dummy() -> Result<Memory> {
-        Ok(Memory::builder()
+        Memory::builder()
             .id(Uuid::new_v4())
             .content(MemoryContent::new("content", vec![])?)
             .context(MemoryContext::for_user("test")?)
             .kind(MemoryKind::Working)
-            // .signals(MemorySignals::new(
-            //     Credence::new(0.2)?,
-            //     Credence::new(0.3)?,
-            // )?)
-            // .provenance(Provenance {
-            //     origin: ProvenanceOrigin::User,
-            //     method: ProvenanceMethod::Direct,
-            // })
+            .signals(MemorySignals::new(
+                Credence::new(0.2)?,
+                Credence::new(0.3)?,
+            )?)
+            .provenance(Provenance::direct_user())
             .lifecycle(LifecycleState::Active)
             .temporal(TemporalMetadata::new(Utc::now()))
-            .build())
+            .build()
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finish_rejects_inconsistent_memories() {
+        let archived = Memory::builder()
+            .id(Uuid::new_v4())
+            .content(MemoryContent::with_summary("User prefers tea").unwrap())
+            .context(MemoryContext::for_user("test").unwrap())
+            .kind(MemoryKind::Semantic)
+            .lifecycle(LifecycleState::Archived)
+            .temporal(TemporalMetadata::new(Utc::now()))
+            .build();
+        assert!(matches!(
+            archived.finish(),
+            Err(MemoryError::ArchivedWithoutTimestamp)
+        ));
+
+        let empty = Memory {
+            context: MemoryContext::for_user("test").unwrap(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            empty.finish(),
+            Err(MemoryError::ContentError(MemoryContentError::EmptySummary))
+        ));
+    }
+
+    #[test]
+    fn memories_built_without_signals_are_valid() {
+        let memory = Memory::builder()
+            .id(Uuid::new_v4())
+            .content(MemoryContent::with_summary("User prefers tea").unwrap())
+            .context(MemoryContext::for_user("test").unwrap())
+            .kind(MemoryKind::Semantic)
+            .lifecycle(LifecycleState::Active)
+            .temporal(TemporalMetadata::new(Utc::now()))
+            .build()
+            .finish()
+            .unwrap();
+        assert!(memory.signals().validate().is_ok());
+        assert!(memory.score() > 0.0);
+        assert!(!memory.signals().is_weak());
+    }
+
+    #[test]
+    fn adjust_signals_clamps_and_records_who_adjusted() {
+        let mut memory = Memory::gen_dummy().unwrap();
+        let now = Utc::now();
+
+        memory
+            .adjust_signals(2.0, -2.0, Some("agent-key".to_string()), now)
+            .unwrap();
+        assert_eq!(memory.signals().get_certainty(), 1.0);
+        assert_eq!(memory.signals().get_salience(), 0.0);
+        assert_eq!(memory.adjustments()[0].by.as_deref(), Some("agent-key"));
+
+        assert!(matches!(
+            memory.adjust_signals(-1.0, 0.0, None, now),
+            Err(MemoryError::SignalsError(MemorySignalsError::DeadMemory))
+        ));
+        assert_eq!(memory.adjustments().len(), 1);
+    }
+
+    #[test]
+    fn stored_changes_bump_the_version() {
+        let mut memory = Memory::gen_dummy().unwrap();
+        let now = Utc::now();
+        assert_eq!(memory.version(), 0);
+
+        memory.edit_summary("User prefers green tea", now).unwrap();
+        assert_eq!(memory.get_summary(), "User prefers green tea");
+        memory.soft_delete(now);
+        memory.restore(now);
+        assert_eq!(memory.version(), 3);
+
+        memory.mark_used(now, 0.1).unwrap();
+        memory.set_visibility(MemoryVisibility::Shared, now);
+        memory.adjust_signals(0.1, 0.0, None, now).unwrap();
+        memory.set_topic(Some("tea".to_string()), now);
+        memory.mark_contradiction(Uuid::new_v4(), now);
+        assert_eq!(memory.version(), 8);
+
+        assert!(matches!(
+            memory.edit_summary(" ", now),
+            Err(MemoryError::ContentError(MemoryContentError::EmptySummary))
+        ));
+        assert_eq!(memory.version(), 8);
+    }
+
+    #[test]
+    fn rewind_shows_the_content_at_that_time() {
+        let created = Utc::now();
+        let edited = created + chrono::Duration::hours(1);
+        let edited_twice = || {
+            let mut memory = Memory::gen_dummy().unwrap();
+            memory.set_raw_content(Some(RawContent::new("we talked about tea")));
+            memory
+                .edit_summary("User prefers green tea", edited)
+                .unwrap();
+            memory
+                .edit_summary("User prefers oolong", edited + chrono::Duration::hours(1))
+                .unwrap();
+            memory
+        };
+
+        let memory = edited_twice();
+        assert_eq!(memory.revisions().len(), 2);
+        assert!(memory.revisions()[0].content.raw_content().is_none());
+
+        let mut before_edits = edited_twice();
+        before_edits.rewind(created);
+        assert_eq!(before_edits.get_summary(), "content");
+        assert!(before_edits.content().raw_content().is_some());
+
+        let mut between = edited_twice();
+        between.rewind(edited);
+        assert_eq!(between.get_summary(), "User prefers green tea");
+
+        let mut now = edited_twice();
+        now.rewind(Utc::now() + chrono::Duration::days(1));
+        assert_eq!(now.get_summary(), "User prefers oolong");
     }
 }
@@ -2,29 +2,81 @@ use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use thiserror::Error;
 
-#[derive(Serialize, Debug, schemars::JsonSchema, Clone, Deserialize, Default, Hash)]
+#[derive(Serialize, Debug, schemars::JsonSchema, Clone, Deserialize, Hash)]
 pub struct Provenance {
     pub origin: ProvenanceOrigin,
-    pub method: ProvenanceMethod,
+    /// Every step that produced the memory's current content, oldest first, e.g. an
+    /// extraction by one model followed by a regeneration by another.
+    pub chain: Vec<ProvenanceMethod>,
+    /// Set when the memory was moderated before it was stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<Moderation>,
+}
+
+impl Default for Provenance {
+    fn default() -> Self {
+        Self::direct_user()
+    }
 }
 
 impl Provenance {
-    pub fn direct_user() -> Self {
+    pub fn new(origin: ProvenanceOrigin, method: ProvenanceMethod) -> Self {
         Self {
-            origin: ProvenanceOrigin::User,
-            method: ProvenanceMethod::Direct,
+            origin,
+            chain: vec![method],
+            moderation: None,
         }
     }
 
+    pub fn direct_user() -> Self {
+        Self::new(ProvenanceOrigin::User, ProvenanceMethod::Direct)
+    }
+
     pub fn direct_agent() -> Self {
+        Self::new(ProvenanceOrigin::Agent, ProvenanceMethod::Direct)
+    }
+
+    /// The last step of the chain, which produced the current content.
+    pub fn method(&self) -> &ProvenanceMethod {
+        const DIRECT: &ProvenanceMethod = &ProvenanceMethod::Direct;
+        self.chain.last().unwrap_or(DIRECT)
+    }
+
+    /// This provenance with the steps of `next` appended, for content reworked from a
+    /// memory that already had a lineage. Origin and moderation are taken from `next`.
+    pub fn followed_by(mut self, next: Provenance) -> Self {
+        self.chain.extend(next.chain);
         Self {
-            origin: ProvenanceOrigin::Agent,
-            method: ProvenanceMethod::Direct,
+            origin: next.origin,
+            chain: self.chain,
+            moderation: next.moderation,
         }
     }
 
     pub fn validate(&self) -> Result<(), ProvenanceMethodError> {
-        self.method.validate()
+        if self.chain.is_empty() {
+            return Err(ProvenanceMethodError::EmptyChain);
+        }
+        self.chain.iter().try_for_each(ProvenanceMethod::validate)
+    }
+}
+
+/// The moderation decision a memory was stored under.
+#[derive(
+    Serialize, schemars::JsonSchema, Debug, Clone, Default, Deserialize, Hash, PartialEq, Eq,
+)]
+pub struct Moderation {
+    /// What judged the content, e.g. "rules" or "omni-moderation-latest".
+    pub moderator: String,
+    /// Categories the content was flagged for but stored anyway; empty when it was
+    /// clean.
+    #[serde(default)]
+    pub flagged: Vec<String>,
+}
+
+impl Moderation {
+    pub fn is_flagged(&self) -> bool {
+        !self.flagged.is_empty()
     }
 }
 
@@ -55,7 +107,9 @@ impl FromStr for ProvenanceOrigin {
     }
 }
 
-#[derive(Serialize, schemars::JsonSchema, Debug, Clone, Default, Deserialize, Hash)]
+#[derive(
+    Serialize, schemars::JsonSchema, Debug, Clone, Default, Deserialize, Hash, PartialEq, Eq,
+)]
 pub enum ProvenanceMethod {
     #[default]
     Direct,
@@ -66,14 +120,22 @@ pub enum ProvenanceMethod {
     Summarized {
         model: String,
     },
+    /// Migrated from another memory store, e.g. "mem0".
+    Imported {
+        source: String,
+    },
 }
 
 #[derive(Debug, Error, Clone, PartialEq)]
 pub enum ProvenanceMethodError {
+    #[error("provenance chain cannot be empty")]
+    EmptyChain,
     #[error("model name cannot be empty")]
     EmptyModel,
     #[error("prompt cannot be empty for extracted provenance")]
     EmptyPrompt,
+    #[error("source cannot be empty for imported provenance")]
+    EmptySource,
 }
 
 impl ProvenanceMethod {
@@ -95,6 +157,12 @@ impl ProvenanceMethod {
                 }
                 Ok(())
             }
+            ProvenanceMethod::Imported { source } => {
+                if source.trim().is_empty() {
+                    return Err(ProvenanceMethodError::EmptySource);
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -152,16 +152,29 @@ pub struct Query {
     context: MemoryContext,
     #[builder(default = false)]
     include_archived: bool,
-    #[builder(default, setter(strip_option))]
+    /// For a context with both a user and an agent, also match memories other agents
+    /// of that user shared.
+    #[builder(default = true)]
+    include_shared: bool,
+    #[builder(default, setter(strip_option(fallback = vector_opt)))]
     vector: Option<Vec<f32>>,
-    #[builder(default, setter(strip_option))]
+    #[builder(default, setter(strip_option(fallback = kinds_opt)))]
     kinds: Option<Vec<MemoryKind>>,
-    #[builder(default, setter(strip_option))]
+    #[builder(default, setter(strip_option(fallback = tags_opt)))]
     tags: Option<Vec<String>>,
-    #[builder(default, setter(strip_option))]
+    /// Match memories assigned to any of these topics.
+    #[builder(default, setter(strip_option(fallback = topics_opt)))]
+    topics: Option<Vec<String>>,
+    #[builder(default, setter(strip_option(fallback = temporal_opt)))]
     temporal: Option<TemporalFilter>,
-    #[builder(default, setter(strip_option))]
+    #[builder(default, setter(strip_option(fallback = signals_opt)))]
     signals: Option<SignalFilter>,
+    /// Match the memories that were retrievable at this point in time: created by then
+    /// and not yet archived, superseded, deleted or suppressed, whatever their lifecycle
+    /// is now. Searches rewind the memories they return to that time, see
+    /// `Memory::rewind`.
+    #[builder(default, setter(strip_option(fallback = as_of_opt)))]
+    as_of: Option<DateTime<Utc>>,
 }
 
 impl Query {
@@ -221,6 +234,10 @@ impl Query {
         self.tags.as_deref()
     }
 
+    pub fn topics(&self) -> Option<&[String]> {
+        self.topics.as_deref()
+    }
+
     pub fn temporal(&self) -> Option<&TemporalFilter> {
         self.temporal.as_ref()
     }
@@ -229,10 +246,18 @@ impl Query {
         self.signals.as_ref()
     }
 
+    pub fn as_of(&self) -> Option<DateTime<Utc>> {
+        self.as_of
+    }
+
     pub fn include_archived(&self) -> bool {
         self.include_archived
     }
 
+    pub fn include_shared(&self) -> bool {
+        self.include_shared
+    }
+
     pub fn active_only() -> Self {
         Self {
             include_archived: false,
@@ -0,0 +1,301 @@
+use crate::{
+    Memory, MemoryContext, MemoryKind, ParseMemoryKindError, Query, QueryError, SignalFilter,
+    TemporalFilter,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum QuerySyntaxError {
+    #[error("unknown filter {0}: (quote it to search for it)")]
+    UnknownFilter(String),
+
+    #[error("{0}: needs a value")]
+    MissingValue(String),
+
+    #[error("unterminated quote")]
+    UnterminatedQuote,
+
+    #[error("{0}")]
+    InvalidKind(#[from] ParseMemoryKindError),
+
+    #[error("invalid date {0}, expected YYYY-MM-DD or an RFC 3339 timestamp")]
+    InvalidDate(String),
+
+    #[error("invalid number {0}")]
+    InvalidNumber(String),
+
+    #[error("unknown is: value {0}, expected archived")]
+    UnknownState(String),
+}
+
+/// A search written in the query language, e.g.
+/// `kind:procedural tag:deploy after:2024-01-01 "exact phrase" rollback`.
+///
+/// Filters are `name:value`, quoted when the value has spaces (`tag:"road trip"`):
+/// `kind:`, `tag:` and `topic:` match any of their values when repeated; `after:` and
+/// `before:` bound the creation date and `updated_after:` and `updated_before:` the last
+/// update, as `YYYY-MM-DD` (midnight UTC) or RFC 3339; `certainty:` and `salience:` are
+/// minimums in [0, 1]; `as_of:` searches the memories as they stood then; `limit:` caps
+/// the results and `is:archived` includes archived memories. Every other word is text
+/// to search for, and quoted phrases must also appear in a memory's summary.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedQuery {
+    /// The words and phrases, in order, to embed and rerank against.
+    pub text: String,
+    pub phrases: Vec<String>,
+    pub kinds: Vec<MemoryKind>,
+    pub tags: Vec<String>,
+    pub topics: Vec<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub updated_after: Option<DateTime<Utc>>,
+    pub updated_before: Option<DateTime<Utc>>,
+    pub min_certainty: Option<f32>,
+    pub min_salience: Option<f32>,
+    pub as_of: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+    pub include_archived: bool,
+}
+
+impl FromStr for ParsedQuery {
+    type Err = QuerySyntaxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parsed = ParsedQuery::default();
+        let mut text = Vec::new();
+
+        for token in tokenize(s)? {
+            let (name, value) = match token {
+                Token::Phrase(phrase) => {
+                    text.push(phrase.clone());
+                    parsed.phrases.push(phrase);
+                    continue;
+                }
+                Token::Word(word) => {
+                    text.push(word);
+                    continue;
+                }
+                Token::Filter(name, value) => (name, value),
+            };
+            if value.is_empty() {
+                return Err(QuerySyntaxError::MissingValue(name));
+            }
+
+            match name.as_str() {
+                "kind" => parsed.kinds.push(value.parse()?),
+                "tag" => parsed.tags.push(value),
+                "topic" => parsed.topics.push(value),
+                "after" => parsed.created_after = Some(parse_date(&value)?),
+                "before" => parsed.created_before = Some(parse_date(&value)?),
+                "updated_after" => parsed.updated_after = Some(parse_date(&value)?),
+                "updated_before" => parsed.updated_before = Some(parse_date(&value)?),
+                "certainty" => parsed.min_certainty = Some(parse_number(&value)?),
+                "salience" => parsed.min_salience = Some(parse_number(&value)?),
+                "as_of" => parsed.as_of = Some(parse_date(&value)?),
+                "limit" => parsed.limit = Some(parse_number(&value)?),
+                "is" if value == "archived" => parsed.include_archived = true,
+                "is" => return Err(QuerySyntaxError::UnknownState(value)),
+                _ => return Err(QuerySyntaxError::UnknownFilter(name)),
+            }
+        }
+
+        parsed.text = text.join(" ");
+        Ok(parsed)
+    }
+}
+
+impl ParsedQuery {
+    /// The `Query` for these filters in `context`, searching by `vector` when given.
+    /// `limit` applies unless the query sets its own.
+    pub fn to_query(
+        &self,
+        context: MemoryContext,
+        vector: Option<Vec<f32>>,
+        limit: u32,
+    ) -> Result<Query, QueryError> {
+        let temporal = [
+            self.created_after,
+            self.created_before,
+            self.updated_after,
+            self.updated_before,
+        ]
+        .iter()
+        .any(Option::is_some)
+        .then(|| {
+            TemporalFilter::new(
+                self.created_after,
+                self.created_before,
+                self.updated_after,
+                self.updated_before,
+            )
+        })
+        .transpose()?;
+        let signals = (self.min_certainty.is_some() || self.min_salience.is_some())
+            .then(|| SignalFilter::new(self.min_certainty, self.min_salience))
+            .transpose()?;
+
+        let query = Query::builder()
+            .context(context)
+            .limit(self.limit.unwrap_or(limit))
+            .include_archived(self.include_archived)
+            .vector_opt(vector)
+            .kinds_opt((!self.kinds.is_empty()).then(|| self.kinds.clone()))
+            .tags_opt((!self.tags.is_empty()).then(|| self.tags.clone()))
+            .topics_opt((!self.topics.is_empty()).then(|| self.topics.clone()))
+            .temporal_opt(temporal)
+            .signals_opt(signals)
+            .as_of_opt(self.as_of)
+            .build();
+        if query.limit() == 0 {
+            return Err(QueryError::InvalidLimit);
+        }
+        query.validate()?;
+        Ok(query)
+    }
+
+    /// Whether every quoted phrase appears in `memory`'s summary, ignoring case.
+    pub fn matches_phrases(&self, memory: &Memory) -> bool {
+        let summary = memory.get_summary().to_lowercase();
+        self.phrases
+            .iter()
+            .all(|phrase| summary.contains(&phrase.to_lowercase()))
+    }
+}
+
+enum Token {
+    Word(String),
+    Phrase(String),
+    Filter(String, String),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, QuerySyntaxError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if c == '"' {
+            chars.next();
+            let phrase = quoted(&mut chars)?;
+            if !phrase.trim().is_empty() {
+                tokens.push(Token::Phrase(phrase.trim().to_string()));
+            }
+            continue;
+        }
+
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            chars.next();
+            if c == ':' && is_filter_name(&word) {
+                let value = match chars.peek() {
+                    Some('"') => {
+                        chars.next();
+                        quoted(&mut chars)?.trim().to_string()
+                    }
+                    _ => chars.by_ref().take_while(|c| !c.is_whitespace()).collect(),
+                };
+                tokens.push(Token::Filter(std::mem::take(&mut word), value));
+                break;
+            }
+            word.push(c);
+        }
+        if !word.is_empty() {
+            tokens.push(Token::Word(word));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Reads up to the closing quote, which is consumed.
+fn quoted(chars: &mut impl Iterator<Item = char>) -> Result<String, QuerySyntaxError> {
+    let mut phrase = String::new();
+    for c in chars {
+        if c == '"' {
+            return Ok(phrase);
+        }
+        phrase.push(c);
+    }
+    Err(QuerySyntaxError::UnterminatedQuote)
+}
+
+fn is_filter_name(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+}
+
+fn parse_date(value: &str) -> Result<DateTime<Utc>, QuerySyntaxError> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.to_utc())
+        .map_err(|_| QuerySyntaxError::InvalidDate(value.to_string()))
+}
+
+fn parse_number<T: FromStr>(value: &str) -> Result<T, QuerySyntaxError> {
+    value
+        .parse()
+        .map_err(|_| QuerySyntaxError::InvalidNumber(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parses_filters_phrases_and_text() {
+        let parsed: ParsedQuery =
+            r#"kind:procedural tag:deploy tag:"road trip" after:2024-01-01 "exact phrase" rollback is:archived limit:5"#
+                .parse()
+                .unwrap();
+
+        assert_eq!(parsed.text, "exact phrase rollback");
+        assert_eq!(parsed.phrases, vec!["exact phrase"]);
+        assert_eq!(parsed.kinds, vec![MemoryKind::Procedural]);
+        assert_eq!(parsed.tags, vec!["deploy", "road trip"]);
+        assert_eq!(
+            parsed.created_after,
+            Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+        );
+        assert!(parsed.include_archived);
+
+        let query = parsed
+            .to_query(MemoryContext::for_user("alice").unwrap(), None, 20)
+            .unwrap();
+        assert_eq!(query.limit(), 5);
+        assert_eq!(query.kinds(), Some(&[MemoryKind::Procedural][..]));
+        assert!(query.temporal().unwrap().has_created_range());
+        assert!(query.signals().is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        assert_eq!(
+            "colour:blue".parse::<ParsedQuery>(),
+            Err(QuerySyntaxError::UnknownFilter("colour".to_string()))
+        );
+        assert_eq!(
+            "tag: deploy".parse::<ParsedQuery>(),
+            Err(QuerySyntaxError::MissingValue("tag".to_string()))
+        );
+        assert_eq!(
+            r#"deploy "rollback"#.parse::<ParsedQuery>(),
+            Err(QuerySyntaxError::UnterminatedQuote)
+        );
+
+        let parsed: ParsedQuery = "after:2025-01-01 before:2024-01-01".parse().unwrap();
+        assert!(matches!(
+            parsed.to_query(MemoryContext::for_user("alice").unwrap(), None, 20),
+            Err(QueryError::InvalidDateRange { .. })
+        ));
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Most feedback entries kept per memory; older ones are dropped first.
+pub const MAX_RETRIEVAL_FEEDBACK: usize = 100;
+
+/// An agent's verdict on whether a memory retrieved for `query` helped.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RetrievalFeedback {
+    pub query: String,
+    pub helpful: bool,
+    pub at: i64,
+}
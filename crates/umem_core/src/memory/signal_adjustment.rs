@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// Most signal adjustments kept per memory; older ones are dropped first.
+pub const MAX_SIGNAL_ADJUSTMENTS: usize = 100;
+
+/// A change to a memory's certainty and salience made on new evidence, with who made
+/// it and the values it left them at.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignalAdjustment {
+    /// The api key that made the adjustment; unset for unscoped callers.
+    pub by: Option<String>,
+    pub delta_certainty: f32,
+    pub delta_salience: f32,
+    pub certainty: f32,
+    pub salience: f32,
+    pub at: i64,
+}
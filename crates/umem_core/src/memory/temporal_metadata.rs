@@ -19,6 +19,12 @@ pub struct TemporalMetadata {
     created_at: i64,
     updated_at: Option<i64>,
     archived_at: Option<i64>,
+    #[serde(default)]
+    deleted_at: Option<i64>,
+    #[serde(default)]
+    suppressed_at: Option<i64>,
+    #[serde(default)]
+    superseded_at: Option<i64>,
 }
 
 impl TemporalMetadata {
@@ -27,6 +33,9 @@ impl TemporalMetadata {
             created_at: created_at.timestamp(),
             updated_at: None,
             archived_at: None,
+            deleted_at: None,
+            suppressed_at: None,
+            superseded_at: None,
         }
     }
 
@@ -63,6 +72,9 @@ impl TemporalMetadata {
             created_at,
             updated_at,
             archived_at,
+            deleted_at: None,
+            suppressed_at: None,
+            superseded_at: None,
         })
     }
     pub fn validate(&self) -> Result<(), TemporalMetadataError> {
@@ -114,6 +126,12 @@ impl TemporalMetadata {
         Ok(())
     }
 
+    /// Moves `updated_at` to `time` for a change that is not an edit, keeping it when it
+    /// is already later, so the memory shows up as changed since any earlier time.
+    pub fn touch(&mut self, time: i64) {
+        self.updated_at = Some(self.last_modified().max(time));
+    }
+
     pub fn mark_archived(&mut self, time: i64) -> Result<(), TemporalMetadataError> {
         if time < self.created_at {
             return Err(TemporalMetadataError::ArchivedBeforeCreated {
@@ -138,6 +156,26 @@ impl TemporalMetadata {
         self.archived_at.is_some()
     }
 
+    pub fn mark_deleted(&mut self, time: i64) {
+        self.deleted_at = Some(time);
+    }
+
+    pub fn clear_deleted(&mut self) {
+        self.deleted_at = None;
+    }
+
+    pub fn mark_suppressed(&mut self, time: i64) {
+        self.suppressed_at = Some(time);
+    }
+
+    pub fn clear_suppressed(&mut self) {
+        self.suppressed_at = None;
+    }
+
+    pub fn mark_superseded(&mut self, time: i64) {
+        self.superseded_at = Some(time);
+    }
+
     pub fn last_modified(&self) -> i64 {
         self.updated_at.unwrap_or(self.created_at)
     }
@@ -146,6 +184,14 @@ impl TemporalMetadata {
         self.created_at
     }
 
+    /// Creation month as `YYYY-MM` (UTC), the bucket used for month facets.
+    pub fn created_month(&self) -> String {
+        DateTime::from_timestamp(self.created_at, 0)
+            .unwrap_or_default()
+            .format("%Y-%m")
+            .to_string()
+    }
+
     pub fn updated_at(&self) -> Option<i64> {
         self.updated_at
     }
@@ -153,4 +199,16 @@ impl TemporalMetadata {
     pub fn archived_at(&self) -> Option<i64> {
         self.archived_at
     }
+
+    pub fn deleted_at(&self) -> Option<i64> {
+        self.deleted_at
+    }
+
+    pub fn suppressed_at(&self) -> Option<i64> {
+        self.suppressed_at
+    }
+
+    pub fn superseded_at(&self) -> Option<i64> {
+        self.superseded_at
+    }
 }
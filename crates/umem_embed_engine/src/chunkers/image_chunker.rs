@@ -0,0 +1,33 @@
+use super::Chunker;
+use crate::{
+    config::{AddConfig, ChunkerConfig},
+    data_type::DataType,
+};
+use anyhow::Result;
+use text_splitter::{ChunkConfig, TextSplitter};
+
+pub struct ImageChunker {
+    config: ChunkerConfig,
+}
+
+impl ImageChunker {
+    pub fn try_new(config: &AddConfig) -> Result<Self> {
+        Ok(ImageChunker {
+            config: config.chunker.clone().unwrap_or_default(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Chunker for ImageChunker {
+    fn get_data_type(&self) -> DataType {
+        DataType::Image
+    }
+
+    async fn get_chunks(&self, content: String) -> Result<Vec<String>> {
+        let splitter = TextSplitter::new(
+            ChunkConfig::new(self.config.chunk_size).with_sizer(self.config.clone()),
+        );
+        Ok(splitter.chunks(&content).map(|c| c.to_string()).collect())
+    }
+}
@@ -0,0 +1,35 @@
+use super::Chunker;
+use crate::{
+    config::{AddConfig, ChunkerConfig},
+    data_type::DataType,
+};
+use anyhow::Result;
+use text_splitter::{ChunkConfig, MarkdownSplitter};
+
+/// Splits a markdown section along its own structure (paragraphs, lists, code
+/// blocks), so a fenced code block is only broken when it alone exceeds a chunk.
+pub struct MdxChunker {
+    config: ChunkerConfig,
+}
+
+impl MdxChunker {
+    pub fn try_new(config: &AddConfig) -> Result<Self> {
+        Ok(MdxChunker {
+            config: config.chunker.clone().unwrap_or_default(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Chunker for MdxChunker {
+    fn get_data_type(&self) -> DataType {
+        DataType::Mdx
+    }
+
+    async fn get_chunks(&self, content: String) -> Result<Vec<String>> {
+        let splitter = MarkdownSplitter::new(
+            ChunkConfig::new(self.config.chunk_size).with_sizer(self.config.clone()),
+        );
+        Ok(splitter.chunks(&content).map(|c| c.to_string()).collect())
+    }
+}
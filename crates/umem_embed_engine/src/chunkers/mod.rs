@@ -1,7 +1,16 @@
 use crate::data_type::DataType;
+use anyhow::Result;
+pub mod docx_chunker;
+pub mod image_chunker;
+pub mod mdx_chunker;
 pub mod pdf_chunker;
+pub mod recursive_character_chunker;
+pub mod rss_feed_chunker;
+pub mod semantic_chunker;
+pub mod table_chunker;
 
+#[async_trait::async_trait]
 pub trait Chunker {
     fn get_data_type(&self) -> DataType;
-    fn get_chunks(&self, content: String) -> Vec<String>;
+    async fn get_chunks(&self, content: String) -> Result<Vec<String>>;
 }
@@ -18,15 +18,16 @@ impl PdfChunker {
     }
 }
 
+#[async_trait::async_trait]
 impl Chunker for PdfChunker {
     fn get_data_type(&self) -> DataType {
         DataType::PdfFile
     }
 
-    fn get_chunks(&self, content: String) -> Vec<String> {
-        let tokenizer = tiktoken_rs::cl100k_base_singleton();
-        let splitter =
-            TextSplitter::new(ChunkConfig::new(self.config.chunk_size).with_sizer(tokenizer));
-        splitter.chunks(&content).map(|c| c.to_string()).collect()
+    async fn get_chunks(&self, content: String) -> Result<Vec<String>> {
+        let splitter = TextSplitter::new(
+            ChunkConfig::new(self.config.chunk_size).with_sizer(self.config.clone()),
+        );
+        Ok(splitter.chunks(&content).map(|c| c.to_string()).collect())
     }
 }
@@ -0,0 +1,131 @@
+use super::Chunker;
+use crate::{config::ChunkerConfig, data_type::DataType};
+use anyhow::Result;
+use std::collections::VecDeque;
+
+pub struct RecursiveCharacterChunker {
+    config: ChunkerConfig,
+    separators: Vec<String>,
+    data_type: DataType,
+}
+
+impl RecursiveCharacterChunker {
+    pub fn new(data_type: DataType, config: ChunkerConfig, separators: Vec<String>) -> Self {
+        RecursiveCharacterChunker {
+            config,
+            separators,
+            data_type,
+        }
+    }
+
+    pub(crate) fn split(&self, text: &str) -> Vec<String> {
+        self.split_with(text, &self.separators)
+    }
+
+    fn length(&self, text: &str) -> usize {
+        self.config.length(text)
+    }
+
+    fn split_with(&self, text: &str, separators: &[String]) -> Vec<String> {
+        // the first separator present in the text wins; "" always matches
+        let position = separators
+            .iter()
+            .position(|s| s.is_empty() || text.contains(s.as_str()))
+            .unwrap_or(separators.len());
+        let separator = separators.get(position).map(String::as_str).unwrap_or("");
+        let remaining = separators.get(position + 1..).unwrap_or_default();
+
+        let pieces: Vec<String> = if separator.is_empty() {
+            text.chars().map(String::from).collect()
+        } else {
+            text.split(separator).map(String::from).collect()
+        };
+
+        let mut chunks = vec![];
+        let mut fitting = vec![];
+
+        for piece in pieces {
+            if self.length(&piece) < self.config.chunk_size {
+                fitting.push(piece);
+                continue;
+            }
+
+            if !fitting.is_empty() {
+                chunks.extend(self.merge(std::mem::take(&mut fitting), separator));
+            }
+
+            if remaining.is_empty() {
+                chunks.push(piece);
+            } else {
+                chunks.extend(self.split_with(&piece, remaining));
+            }
+        }
+
+        if !fitting.is_empty() {
+            chunks.extend(self.merge(fitting, separator));
+        }
+
+        chunks
+    }
+
+    /// Greedily joins pieces up to `chunk_size`, carrying up to `chunk_overlap` of
+    /// the previous chunk's tail into the next one.
+    fn merge(&self, pieces: Vec<String>, separator: &str) -> Vec<String> {
+        let separator_length = self.length(separator);
+        let mut chunks = vec![];
+        let mut current: VecDeque<String> = VecDeque::new();
+        let mut total = 0;
+
+        for piece in pieces {
+            let length = self.length(&piece);
+            let joined_separator = if current.is_empty() {
+                0
+            } else {
+                separator_length
+            };
+
+            if total + length + joined_separator > self.config.chunk_size && !current.is_empty() {
+                let chunk = current.iter().cloned().collect::<Vec<_>>().join(separator);
+                if !chunk.trim().is_empty() {
+                    chunks.push(chunk.trim().to_string());
+                }
+
+                while total > self.config.chunk_overlap
+                    || (total + length + separator_length > self.config.chunk_size && total > 0)
+                {
+                    let Some(front) = current.pop_front() else {
+                        break;
+                    };
+                    total -= self.length(&front);
+                    if !current.is_empty() {
+                        total -= separator_length;
+                    }
+                }
+            }
+
+            if !current.is_empty() {
+                total += separator_length;
+            }
+            total += length;
+            current.push_back(piece);
+        }
+
+        let chunk = current.into_iter().collect::<Vec<_>>().join(separator);
+        if !chunk.trim().is_empty() {
+            chunks.push(chunk.trim().to_string());
+        }
+
+        chunks
+    }
+}
+
+#[async_trait::async_trait]
+impl Chunker for RecursiveCharacterChunker {
+    fn get_data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    async fn get_chunks(&self, content: String) -> Result<Vec<String>> {
+        Ok(self.split(&content))
+    }
+}
@@ -0,0 +1,37 @@
+use super::Chunker;
+use crate::{
+    config::{AddConfig, ChunkerConfig},
+    data_type::DataType,
+};
+use anyhow::Result;
+use text_splitter::{ChunkConfig, TextSplitter};
+
+/// Chunks feed items, which the loader yields one record each, so chunks never span
+/// two items.
+pub struct RssFeedChunker {
+    config: ChunkerConfig,
+    data_type: DataType,
+}
+
+impl RssFeedChunker {
+    pub fn try_new(data_type: DataType, config: &AddConfig) -> Result<Self> {
+        Ok(RssFeedChunker {
+            config: config.chunker.clone().unwrap_or_default(),
+            data_type,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Chunker for RssFeedChunker {
+    fn get_data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    async fn get_chunks(&self, content: String) -> Result<Vec<String>> {
+        let splitter = TextSplitter::new(
+            ChunkConfig::new(self.config.chunk_size).with_sizer(self.config.clone()),
+        );
+        Ok(splitter.chunks(&content).map(|c| c.to_string()).collect())
+    }
+}
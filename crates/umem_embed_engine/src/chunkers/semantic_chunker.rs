@@ -0,0 +1,118 @@
+use super::{Chunker, recursive_character_chunker::RecursiveCharacterChunker};
+use crate::{
+    config::{ChunkerConfig, DEFAULT_SEPARATORS},
+    data_type::DataType,
+    embedder::EmbeddingModel,
+};
+use anyhow::{Result, anyhow};
+use std::sync::Arc;
+
+pub struct SemanticChunker {
+    embedder: Arc<dyn EmbeddingModel + Send + Sync>,
+    breakpoint_percentile: f32,
+    data_type: DataType,
+    /// Re-splits semantic groups that still exceed `chunk_size`.
+    fallback: RecursiveCharacterChunker,
+}
+
+impl SemanticChunker {
+    pub fn new(
+        data_type: DataType,
+        config: ChunkerConfig,
+        embedder: Arc<dyn EmbeddingModel + Send + Sync>,
+        breakpoint_percentile: f32,
+    ) -> Self {
+        let separators = DEFAULT_SEPARATORS.iter().map(|s| s.to_string()).collect();
+
+        SemanticChunker {
+            embedder,
+            breakpoint_percentile,
+            data_type,
+            fallback: RecursiveCharacterChunker::new(data_type, config, separators),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Chunker for SemanticChunker {
+    fn get_data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    async fn get_chunks(&self, content: String) -> Result<Vec<String>> {
+        let sentences = split_sentences(&content);
+        if sentences.len() < 3 {
+            return Ok(self.fallback.split(&content));
+        }
+
+        let embeddings = self.embedder.embed(&sentences).await?;
+        if embeddings.len() != sentences.len() {
+            return Err(anyhow!(
+                "embedder returned {} embeddings for {} sentences",
+                embeddings.len(),
+                sentences.len()
+            ));
+        }
+
+        let distances: Vec<f32> = embeddings
+            .windows(2)
+            .map(|pair| 1.0 - cosine_similarity(&pair[0], &pair[1]))
+            .collect();
+        let threshold = percentile(&distances, self.breakpoint_percentile);
+
+        let mut chunks = vec![];
+        let mut group: Vec<&str> = vec![sentences[0].as_str()];
+
+        for (sentence, distance) in sentences[1..].iter().zip(&distances) {
+            if *distance > threshold {
+                chunks.extend(self.fallback.split(&group.join(" ")));
+                group.clear();
+            }
+            group.push(sentence);
+        }
+        chunks.extend(self.fallback.split(&group.join(" ")));
+
+        Ok(chunks)
+    }
+}
+
+/// Splits after `.`, `!` or `?` when followed by whitespace.
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = vec![];
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let at_boundary = matches!(c, '.' | '!' | '?')
+            && chars.peek().is_some_and(|(_, next)| next.is_whitespace());
+        if at_boundary {
+            let end = index + c.len_utf8();
+            sentences.push(text[start..end].trim().to_string());
+            start = end;
+        }
+    }
+    sentences.push(text[start..].trim().to_string());
+
+    sentences.retain(|sentence| !sentence.is_empty());
+    sentences
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Nearest-rank percentile of `values`, which must not be empty.
+fn percentile(values: &[f32], percentile: f32) -> f32 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let rank = ((percentile / 100.0) * (sorted.len() - 1) as f32).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
@@ -0,0 +1,75 @@
+use super::Chunker;
+use crate::{
+    config::{AddConfig, ChunkerConfig},
+    data_type::DataType,
+    loaders::ROW_FIELD_SEPARATOR,
+};
+use anyhow::Result;
+use text_splitter::{ChunkConfig, TextSplitter};
+
+/// Chunks tabular sources, where the loader already yields one record per row. A row
+/// that fits is kept whole; an oversized row is cut between `header: value` fields,
+/// and only a single field larger than a chunk is split mid-text.
+pub struct TableChunker {
+    config: ChunkerConfig,
+    data_type: DataType,
+}
+
+impl TableChunker {
+    pub fn try_new(data_type: DataType, config: &AddConfig) -> Result<Self> {
+        Ok(TableChunker {
+            config: config.chunker.clone().unwrap_or_default(),
+            data_type,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Chunker for TableChunker {
+    fn get_data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    async fn get_chunks(&self, content: String) -> Result<Vec<String>> {
+        let splitter = TextSplitter::new(
+            ChunkConfig::new(self.config.chunk_size).with_sizer(self.config.clone()),
+        );
+        let fits = |text: &str| splitter.chunks(text).nth(1).is_none();
+
+        if fits(&content) {
+            return Ok(vec![content]);
+        }
+
+        let mut chunks = vec![];
+        let mut current = String::new();
+
+        for field in content.split(ROW_FIELD_SEPARATOR) {
+            let candidate = if current.is_empty() {
+                field.to_string()
+            } else {
+                format!("{}{}{}", current, ROW_FIELD_SEPARATOR, field)
+            };
+
+            if fits(&candidate) {
+                current = candidate;
+                continue;
+            }
+
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+
+            if fits(field) {
+                current = field.to_string();
+            } else {
+                chunks.extend(splitter.chunks(field).map(|c| c.to_string()));
+            }
+        }
+
+        if !current.is_empty() {
+            chunks.push(current);
+        }
+
+        Ok(chunks)
+    }
+}
@@ -1,11 +1,103 @@
+use crate::embedder::EmbeddingModel;
 use anyhow::{Result, anyhow};
+use std::sync::Arc;
+use text_splitter::ChunkSizer;
+use tiktoken_rs::CoreBPE;
+
+/// Separators tried in order by the recursive character chunker: paragraphs, lines,
+/// words, and finally single characters.
+pub const DEFAULT_SEPARATORS: [&str; 4] = ["\n\n", "\n", " ", ""];
+
+#[derive(Clone)]
+pub enum ChunkingStrategy {
+    /// The chunker built for the source's data type.
+    DataType,
+    /// Splits on the first separator present, recursing into pieces still larger
+    /// than `chunk_size` with the remaining separators, then merges the pieces back
+    /// up to `chunk_size` with `chunk_overlap`.
+    RecursiveCharacter { separators: Vec<String> },
+    /// Splits into sentences and cuts where the embedding distance between
+    /// neighbouring sentences is above the `breakpoint_percentile` of all such
+    /// distances in the document.
+    Semantic {
+        embedder: Arc<dyn EmbeddingModel + Send + Sync>,
+        breakpoint_percentile: f32,
+    },
+}
+
+impl ChunkingStrategy {
+    pub fn recursive_character() -> Self {
+        ChunkingStrategy::RecursiveCharacter {
+            separators: DEFAULT_SEPARATORS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    pub fn semantic(embedder: Arc<dyn EmbeddingModel + Send + Sync>) -> Self {
+        ChunkingStrategy::Semantic {
+            embedder,
+            breakpoint_percentile: 95.0,
+        }
+    }
+}
+
+/// BPE encodings; pick the one the embedding model was trained with so chunk limits
+/// match what the embedder will count.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tokenizer {
+    /// text-embedding-3-*, text-embedding-ada-002, gpt-4, gpt-3.5-turbo
+    Cl100kBase,
+    /// gpt-4o and later OpenAI models
+    O200kBase,
+    P50kBase,
+    R50kBase,
+}
+
+impl Tokenizer {
+    pub fn bpe(&self) -> &'static CoreBPE {
+        match self {
+            Tokenizer::Cl100kBase => tiktoken_rs::cl100k_base_singleton(),
+            Tokenizer::O200kBase => tiktoken_rs::o200k_base_singleton(),
+            Tokenizer::P50kBase => tiktoken_rs::p50k_base_singleton(),
+            Tokenizer::R50kBase => tiktoken_rs::r50k_base_singleton(),
+        }
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        self.bpe().encode_ordinary(text).len()
+    }
+}
+
+/// Unit that `chunk_size`, `chunk_overlap` and `min_chunk_size` are measured in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChunkSizing {
+    /// Measured by `length_function`, the byte length unless overridden.
+    Characters,
+    Tokens(Tokenizer),
+}
 
 #[derive(Clone)]
 pub struct ChunkerConfig {
     pub chunk_size: usize,
-    chunk_overlap: usize,
-    length_function: fn(String) -> usize,
+    pub(crate) chunk_overlap: usize,
+    pub(crate) length_function: fn(String) -> usize,
     pub min_chunk_size: usize,
+    pub(crate) sizing: ChunkSizing,
+    pub(crate) strategy: ChunkingStrategy,
+}
+
+impl ChunkerConfig {
+    pub fn length(&self, text: &str) -> usize {
+        match self.sizing {
+            ChunkSizing::Characters => (self.length_function)(text.to_string()),
+            ChunkSizing::Tokens(tokenizer) => tokenizer.count(text),
+        }
+    }
+}
+
+impl ChunkSizer for ChunkerConfig {
+    fn size(&self, chunk: &str) -> usize {
+        self.length(chunk)
+    }
 }
 
 impl Default for ChunkerConfig {
@@ -25,6 +117,8 @@ pub struct ChunkerConfigBuilder {
     chunk_overlap: usize,
     length_function: Option<fn(String) -> usize>,
     min_chunk_size: usize,
+    sizing: ChunkSizing,
+    strategy: ChunkingStrategy,
 }
 
 impl ChunkerConfigBuilder {
@@ -34,6 +128,8 @@ impl ChunkerConfigBuilder {
             chunk_overlap: 0,
             length_function: None,
             min_chunk_size: 0,
+            sizing: ChunkSizing::Tokens(Tokenizer::Cl100kBase),
+            strategy: ChunkingStrategy::DataType,
         }
     }
 
@@ -47,8 +143,20 @@ impl ChunkerConfigBuilder {
         self
     }
 
+    /// Measures chunks with `func` instead of a tokenizer.
     pub fn length_function(mut self, func: fn(String) -> usize) -> Self {
         self.length_function = Some(func);
+        self.sizing = ChunkSizing::Characters;
+        self
+    }
+
+    pub fn sizing(mut self, sizing: ChunkSizing) -> Self {
+        self.sizing = sizing;
+        self
+    }
+
+    pub fn tokenizer(mut self, tokenizer: Tokenizer) -> Self {
+        self.sizing = ChunkSizing::Tokens(tokenizer);
         self
     }
 
@@ -57,6 +165,11 @@ impl ChunkerConfigBuilder {
         self
     }
 
+    pub fn strategy(mut self, strategy: ChunkingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
     pub fn build(self) -> Result<ChunkerConfig> {
         if self.min_chunk_size >= self.chunk_size {
             return Err(anyhow!(
@@ -66,6 +179,19 @@ impl ChunkerConfigBuilder {
             ));
         }
 
+        if let ChunkingStrategy::Semantic {
+            breakpoint_percentile,
+            ..
+        } = &self.strategy
+        {
+            if !(0.0..=100.0).contains(breakpoint_percentile) {
+                return Err(anyhow!(
+                    "breakpoint_percentile {} should be between 0 and 100",
+                    breakpoint_percentile
+                ));
+            }
+        }
+
         if self.chunk_overlap > self.min_chunk_size {
             return Err(anyhow!(
                 "min_chunk_size {} should be greater than or equal to chunk_overlap {}, otherwise its redundant",
@@ -84,6 +210,8 @@ impl ChunkerConfigBuilder {
             chunk_overlap: self.chunk_overlap,
             length_function,
             min_chunk_size: self.min_chunk_size,
+            sizing: self.sizing,
+            strategy: self.strategy,
         })
     }
 }
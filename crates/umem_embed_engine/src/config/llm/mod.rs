@@ -8,52 +8,61 @@ pub enum QueryType {}
 
 pub struct LLMConfig {
     /// Number of documents to pull from the database for context
-    documents_to_pull_from_db: usize,
+    pub(crate) documents_to_pull_from_db: usize,
     /// The system message to use for the LLM, default is None
-    system_prompt: Option<String>,
+    pub(crate) system_prompt: Option<String>,
     /// The prompt to use for the LLM, default is None
-    prompt: Option<String>,
+    pub(crate) prompt: Option<String>,
     /// Controls the randomness of the LLM's output
     /// Higher values like 0.8 will make the output more random,
     /// while lower values like 0.2 will make it more focused and deterministic
-    temperature: f32,
+    pub(crate) temperature: f32,
     /// Maximum number of tokens to generate in the LLM's response
     /// Defaults to 1024
-    max_tokens: usize,
+    pub(crate) max_tokens: usize,
     // The LLM model to use
-    model: String,
+    pub(crate) model: String,
     /// Controls the diversity of the LLM's output
     /// Values range from 0.0 to 1.0, Defaults to 1.0
-    top_p: f32,
+    pub(crate) top_p: f32,
     /// Whether to use online resources (google search) to enhance responses
     /// Defaults to false
-    online: bool,
+    pub(crate) online: bool,
     /// If True, the model will be run locally, defaults to False (for huggingface provider)
-    local: bool,
+    pub(crate) local: bool,
     /// Whether to output token usage information for debugging
-    debug_token_usage: bool,
+    pub(crate) debug_token_usage: bool,
     // The type of query to perform
-    query_type: Option<QueryType>,
+    pub(crate) query_type: Option<QueryType>,
     /// Additional model-specific arguments
-    model_args: HashMap<String, Value>,
+    pub(crate) model_args: HashMap<String, Value>,
     /// Additional filters to apply when querying the vector database to narrow down context documents
-    where_filters: Vec<(String, Value)>,
+    pub(crate) where_filters: Vec<(String, Value)>,
     /// ONLY used for OpenAI compliant APIs, if provided will override the model_id while sending
     /// the requests
-    deployment_name: Option<String>,
+    pub(crate) deployment_name: Option<String>,
     ///  Provider specific configuration
-    provider_config: Option<ProviderConfig>,
+    pub(crate) provider_config: Option<ProviderConfig>,
 }
 
 impl LLMConfig {
     pub fn new() -> Self {
         LLMConfig {
             documents_to_pull_from_db: 1,
-            debug_token_usage: false,
+            system_prompt: None,
+            prompt: None,
             temperature: 0.2,
-            top_p: 1.0,
             max_tokens: 1024,
-            ..Default::default()
+            model: String::new(),
+            top_p: 1.0,
+            online: false,
+            local: false,
+            debug_token_usage: false,
+            query_type: None,
+            model_args: HashMap::default(),
+            where_filters: vec![],
+            deployment_name: None,
+            provider_config: None,
         }
     }
 
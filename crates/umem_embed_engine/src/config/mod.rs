@@ -5,6 +5,10 @@ mod llm;
 mod loader_config;
 
 pub use add_config::AddConfig;
-pub use chunker_config::ChunkerConfig;
+pub use chunker_config::{
+    ChunkSizing, ChunkerConfig, ChunkerConfigBuilder, ChunkingStrategy, DEFAULT_SEPARATORS,
+    Tokenizer,
+};
 pub use engine_config::EngineConfig;
+pub use llm::{LLMConfig, providers};
 pub use loader_config::LoaderConfig;
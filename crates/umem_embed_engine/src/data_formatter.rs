@@ -2,14 +2,26 @@ use std::collections::HashSet;
 
 use crate::{
     HashMap,
-    chunkers::{Chunker, pdf_chunker::PdfChunker},
-    config::{AddConfig, ChunkerConfig},
+    chunkers::{
+        Chunker, docx_chunker::DocxChunker, image_chunker::ImageChunker, mdx_chunker::MdxChunker,
+        pdf_chunker::PdfChunker, recursive_character_chunker::RecursiveCharacterChunker,
+        rss_feed_chunker::RssFeedChunker, semantic_chunker::SemanticChunker,
+        table_chunker::TableChunker,
+    },
+    config::{AddConfig, ChunkerConfig, ChunkingStrategy},
     data_type::DataType,
     engine::AddSource,
-    loaders::{LoadDataResult, Loader, pdf_loader::PdfLoader},
+    loaders::{
+        LoadDataResult, Loader, csv_loader::CsvLoader, excel_loader::ExcelLoader,
+        image_loader::ImageLoader, mdx_loader::MdxLoader, pdf_loader::PdfLoader,
+        rss_feed_loader::RssFeedLoader,
+    },
 };
 use anyhow::Result;
 
+#[cfg(feature = "docx")]
+use crate::loaders::docx_loader::DocxLoader;
+
 pub(crate) struct DataFormatter {
     pub(crate) loader: Box<dyn Loader>,
     pub(crate) chunker: Box<dyn Chunker>,
@@ -18,6 +30,8 @@ pub(crate) struct DataFormatter {
 pub(crate) struct CreateChunksResponse {
     pub doc_id: String,
     pub chunk_ids: HashSet<String>,
+    /// Ids of `documents`, in the same order.
+    pub ids: Vec<String>,
     pub documents: Vec<String>,
     pub metadatas: Vec<HashMap<String, String>>,
 }
@@ -46,13 +60,11 @@ impl DataFormatter {
         chunker_config: Option<ChunkerConfig>,
     ) -> Result<CreateChunksResponse> {
         let mut chunk_ids: HashSet<String> = HashSet::default();
+        let mut ids = vec![];
         let mut documents = vec![];
         let mut metadatas = vec![];
 
-        let min_chunk_size = match chunker_config {
-            Some(config) => config.min_chunk_size,
-            None => 0,
-        };
+        let chunker_config = chunker_config.unwrap_or_default();
 
         let LoadDataResult {
             mut doc_id,
@@ -75,7 +87,7 @@ impl DataFormatter {
                 Some(u) => u.clone(),
                 None => source.to_string(),
             };
-            let mut chunks = self.chunker.get_chunks(content);
+            let mut chunks = self.chunker.get_chunks(content).await?;
 
             for chunk in chunks.iter_mut() {
                 chunk.push_str(&url);
@@ -87,8 +99,12 @@ impl DataFormatter {
                     None => chunk_id,
                 };
 
-                if !chunk_ids.contains(&chunk_id) && chunk.len() >= min_chunk_size {
+                if !chunk_ids.contains(&chunk_id)
+                    && (chunker_config.min_chunk_size == 0
+                        || chunker_config.length(chunk) >= chunker_config.min_chunk_size)
+                {
                     chunk_ids.insert(chunk_id.clone());
+                    ids.push(chunk_id);
                     documents.push(std::mem::take(chunk));
                     metadatas.push(std::mem::take(&mut metadata));
                 }
@@ -98,6 +114,7 @@ impl DataFormatter {
         Ok(CreateChunksResponse {
             doc_id,
             chunk_ids,
+            ids,
             documents,
             metadatas,
         })
@@ -115,6 +132,19 @@ fn get_loader(data_type: &DataType, loader: Option<Box<dyn Loader>>) -> Result<B
             None => Err(anyhow::anyhow!("Custom data type requires a loader")),
         },
         DataType::PdfFile => Ok(Box::new(PdfLoader::new())),
+        DataType::Csv => Ok(Box::new(CsvLoader::new())),
+        DataType::ExcelFile => Ok(Box::new(ExcelLoader::new())),
+        #[cfg(feature = "docx")]
+        DataType::Docx => Ok(Box::new(DocxLoader::new())),
+        #[cfg(not(feature = "docx"))]
+        DataType::Docx => Err(anyhow::anyhow!(
+            "Docx data type requires umem_embed_engine's `docx` feature"
+        )),
+        DataType::Mdx => Ok(Box::new(MdxLoader::new())),
+        // NOTE: a configured `ImageLoader` (e.g. with an OCR language) may be passed in
+        DataType::Image => Ok(loader.unwrap_or_else(|| Box::new(ImageLoader::new()))),
+        DataType::RssFeed => Ok(Box::new(RssFeedLoader::new())),
+        DataType::Substack => Ok(Box::new(RssFeedLoader::substack())),
         _ => unimplemented!(),
     }
 }
@@ -128,8 +158,38 @@ fn get_chunker(
         return Ok(c);
     }
 
+    let config = add_config.chunker.clone().unwrap_or_default();
+    match config.strategy.clone() {
+        ChunkingStrategy::RecursiveCharacter { separators } => {
+            return Ok(Box::new(RecursiveCharacterChunker::new(
+                *data_type, config, separators,
+            )));
+        }
+        ChunkingStrategy::Semantic {
+            embedder,
+            breakpoint_percentile,
+        } => {
+            return Ok(Box::new(SemanticChunker::new(
+                *data_type,
+                config,
+                embedder,
+                breakpoint_percentile,
+            )));
+        }
+        ChunkingStrategy::DataType => {}
+    }
+
     match data_type {
         DataType::PdfFile => Ok(Box::new(PdfChunker::try_new(add_config)?)),
+        DataType::Docx => Ok(Box::new(DocxChunker::try_new(add_config)?)),
+        DataType::Mdx => Ok(Box::new(MdxChunker::try_new(add_config)?)),
+        DataType::Image => Ok(Box::new(ImageChunker::try_new(add_config)?)),
+        DataType::Csv | DataType::ExcelFile => {
+            Ok(Box::new(TableChunker::try_new(*data_type, add_config)?))
+        }
+        DataType::RssFeed | DataType::Substack => {
+            Ok(Box::new(RssFeedChunker::try_new(*data_type, add_config)?))
+        }
         _ => unimplemented!(),
     }
 }
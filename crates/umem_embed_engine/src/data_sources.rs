@@ -0,0 +1,152 @@
+use crate::{HashMap, engine::AddSource};
+use anyhow::{Result, anyhow};
+use sqlx::{
+    Pool, Row, Sqlite, query,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteRow},
+};
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A source that was added to the engine. `hash` is the source hash every chunk of the
+/// source carries in its `hash` metadata.
+#[derive(Debug, Clone)]
+pub struct DataSource {
+    pub hash: String,
+    pub app_id: Option<String>,
+    pub data_type: String,
+    pub source: String,
+    pub is_url: bool,
+    pub metadata: HashMap<String, String>,
+    pub created_at: i64,
+    /// When the source's chunks were last brought up to date by an add or a resync.
+    pub last_synced_at: i64,
+}
+
+impl DataSource {
+    pub fn add_source(&self) -> AddSource {
+        if self.is_url {
+            AddSource::Url(self.source.clone())
+        } else {
+            AddSource::LocalFile(self.source.clone())
+        }
+    }
+}
+
+/// Registry of the sources added to the engine, kept in a small sqlite database so it
+/// survives restarts. Re-adding a source refreshes its row instead of adding another.
+pub struct DataSourceStore {
+    client: Pool<Sqlite>,
+}
+
+impl DataSourceStore {
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+        let client = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
+            .await?;
+
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS data_sources (
+                hash TEXT NOT NULL,
+                app_id TEXT NOT NULL DEFAULT '',
+                data_type TEXT NOT NULL,
+                source TEXT NOT NULL,
+                is_url INTEGER NOT NULL,
+                metadata TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                last_synced_at INTEGER NOT NULL,
+                PRIMARY KEY (hash, app_id)
+            )
+            "#,
+        )
+        .execute(&client)
+        .await?;
+
+        Ok(DataSourceStore { client })
+    }
+
+    pub async fn upsert(&self, data_source: &DataSource) -> Result<()> {
+        query(
+            r#"INSERT INTO data_sources
+                (hash, app_id, data_type, source, is_url, metadata, created_at, last_synced_at)
+                VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (hash, app_id) DO UPDATE SET
+                data_type = excluded.data_type,
+                metadata = excluded.metadata,
+                last_synced_at = excluded.last_synced_at"#,
+        )
+        .bind(&data_source.hash)
+        .bind(data_source.app_id.clone().unwrap_or_default())
+        .bind(&data_source.data_type)
+        .bind(&data_source.source)
+        .bind(data_source.is_url)
+        .bind(serde_json::to_string(&data_source.metadata)?)
+        .bind(data_source.created_at)
+        .bind(data_source.last_synced_at)
+        .execute(&self.client)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn list(&self, app_id: Option<&str>) -> Result<Vec<DataSource>> {
+        query(r#"SELECT * FROM data_sources WHERE app_id = $1 ORDER BY created_at"#)
+            .bind(app_id.unwrap_or_default())
+            .fetch_all(&self.client)
+            .await?
+            .iter()
+            .map(Self::map_row)
+            .collect()
+    }
+
+    pub async fn get(&self, app_id: Option<&str>, hash: &str) -> Result<Option<DataSource>> {
+        query(r#"SELECT * FROM data_sources WHERE app_id = $1 AND hash = $2"#)
+            .bind(app_id.unwrap_or_default())
+            .bind(hash)
+            .fetch_optional(&self.client)
+            .await?
+            .as_ref()
+            .map(Self::map_row)
+            .transpose()
+    }
+
+    /// Removes a source's row, returning whether it existed.
+    pub async fn delete(&self, app_id: Option<&str>, hash: &str) -> Result<bool> {
+        let result = query(r#"DELETE FROM data_sources WHERE app_id = $1 AND hash = $2"#)
+            .bind(app_id.unwrap_or_default())
+            .bind(hash)
+            .execute(&self.client)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn map_row(row: &SqliteRow) -> Result<DataSource> {
+        let app_id: String = row.try_get("app_id")?;
+        let metadata: String = row.try_get("metadata")?;
+
+        Ok(DataSource {
+            hash: row.try_get("hash")?,
+            app_id: (!app_id.is_empty()).then_some(app_id),
+            data_type: row.try_get("data_type")?,
+            source: row.try_get("source")?,
+            is_url: row.try_get("is_url")?,
+            metadata: serde_json::from_str(&metadata)
+                .map_err(|e| anyhow!("stored data source metadata is invalid: {e}"))?,
+            created_at: row.try_get("created_at")?,
+            last_synced_at: row.try_get("last_synced_at")?,
+        })
+    }
+}
+
+pub(crate) fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
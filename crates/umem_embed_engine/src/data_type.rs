@@ -4,7 +4,7 @@ use anyhow::{Result, anyhow};
 use core::fmt;
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::fs;
+use std::{fs, str::FromStr};
 use yaml_rust2::YamlLoader;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -45,7 +45,17 @@ pub enum DataType {
 pub const DIRECT_DATA_TYPES: [DataType; 1] = [DataType::Text];
 
 // TODO: add all indirect data types
-pub const INDIRECT_DATA_TYPES: [DataType; 2] = [DataType::PdfFile, DataType::Json];
+pub const INDIRECT_DATA_TYPES: [DataType; 9] = [
+    DataType::PdfFile,
+    DataType::Json,
+    DataType::Csv,
+    DataType::ExcelFile,
+    DataType::Docx,
+    DataType::Mdx,
+    DataType::Image,
+    DataType::RssFeed,
+    DataType::Substack,
+];
 
 impl fmt::Display for DataType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -85,6 +95,48 @@ impl fmt::Display for DataType {
     }
 }
 
+impl FromStr for DataType {
+    type Err = anyhow::Error;
+
+    /// Parses the names written by `Display`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "youtube_channel" => Ok(DataType::YoutubeChannel),
+            "google_drive" => Ok(DataType::GoogleDrive),
+            "docs_site" => Ok(DataType::DocsSite),
+            "pdf_file" => Ok(DataType::PdfFile),
+            "text_file" => Ok(DataType::TextFile),
+            "web_page" => Ok(DataType::WebPage),
+            "sitemap" => Ok(DataType::Sitemap),
+            "csv" => Ok(DataType::Csv),
+            "mdx" => Ok(DataType::Mdx),
+            "docx" => Ok(DataType::Docx),
+            "json" => Ok(DataType::Json),
+            "openapi" => Ok(DataType::OpenApi),
+            "audio" => Ok(DataType::Audio),
+            "text" => Ok(DataType::Text),
+            "youtube_video" => Ok(DataType::YoutubeVideo),
+            "xml" => Ok(DataType::Xml),
+            "notion" => Ok(DataType::Notion),
+            "qna_pair" => Ok(DataType::QnaPair),
+            "image" => Ok(DataType::Image),
+            "unstructured" => Ok(DataType::Unstructured),
+            "gmail" => Ok(DataType::Gmail),
+            "substack" => Ok(DataType::Substack),
+            "discord" => Ok(DataType::Discord),
+            "custom" => Ok(DataType::Custom),
+            "rss_feed" => Ok(DataType::RssFeed),
+            "beehiiv" => Ok(DataType::Beehiiv),
+            "directory" => Ok(DataType::Directory),
+            "slack" => Ok(DataType::Slack),
+            "dropbox" => Ok(DataType::Dropbox),
+            "excel_file" => Ok(DataType::ExcelFile),
+            "github" => Ok(DataType::Github),
+            _ => Err(anyhow!("unknown data type: {}", s)),
+        }
+    }
+}
+
 impl DataType {
     pub async fn try_from_source(source: &AddSource) -> Result<Self> {
         match source {
@@ -98,10 +150,15 @@ fn detect_data_type_from_file(local_path: &str) -> Result<DataType> {
     match local_path.rsplit('.').next() {
         Some("docx") => Ok(DataType::Docx),
         Some("csv") => Ok(DataType::Csv),
+        Some("xlsx") | Some("xlsm") | Some("xlsb") | Some("xls") | Some("ods") => {
+            Ok(DataType::ExcelFile)
+        }
         Some("xml") => Ok(DataType::Xml),
         Some("md") | Some("mdx") => Ok(DataType::Mdx),
         Some("txt") => Ok(DataType::TextFile),
         Some("pdf") => Ok(DataType::PdfFile),
+        Some("png") | Some("jpg") | Some("jpeg") | Some("tif") | Some("tiff") | Some("bmp")
+        | Some("webp") => Ok(DataType::Image),
         Some("json") => Ok(DataType::Json),
         Some("yaml") | Some("yml") => {
             let file_content = fs::read_to_string(local_path).map_err(|_| {
@@ -136,6 +193,20 @@ const YOUTUBE_ALLOWED_HOSTNAMES: [&str; 6] = [
 
 const NOTION_ALLOWED_HOSTNAMES: [&str; 2] = ["www.notion.so", "notion.so"];
 
+const EXCEL_FILE_EXTENSIONS: [&str; 5] = [".xlsx", ".xlsm", ".xlsb", ".xls", ".ods"];
+
+const FEED_PATH_SUFFIXES: [&str; 6] = [
+    ".rss",
+    "/rss",
+    "/feed",
+    "/rss.xml",
+    "/atom.xml",
+    "/feed.xml",
+];
+
+const IMAGE_FILE_EXTENSIONS: [&str; 7] =
+    [".png", ".jpg", ".jpeg", ".tif", ".tiff", ".bmp", ".webp"];
+
 const SUPPORTED_AUDIO_FILE_EXTENSIONS: [&str; 11] = [
     ".mp3", ".mp4", ".mp2", ".aac", ".wav", ".flac", ".pcm", ".m4a", ".ogg", ".opus", ".webm",
 ];
@@ -188,16 +259,27 @@ async fn detect_data_type_from_url(url: &str) -> Result<DataType> {
         .any(|&hn| host_name.contains(hn))
     {
         Ok(DataType::Notion)
+    } else if host_name.ends_with("substack.com") {
+        Ok(DataType::Substack)
+    } else if FEED_PATH_SUFFIXES
+        .iter()
+        .any(|&suffix| path.trim_end_matches('/').ends_with(suffix))
+    {
+        Ok(DataType::RssFeed)
     } else if path.ends_with(".pdf") {
         Ok(DataType::PdfFile)
     } else if path.ends_with(".xml") {
         Ok(DataType::Sitemap)
     } else if path.ends_with(".csv") {
         Ok(DataType::Csv)
+    } else if EXCEL_FILE_EXTENSIONS.iter().any(|&ext| path.ends_with(ext)) {
+        Ok(DataType::ExcelFile)
     } else if path.ends_with(".mdx") || path.ends_with(".md") {
         Ok(DataType::Mdx)
     } else if path.ends_with(".docx") {
-        Ok(DataType::Json)
+        Ok(DataType::Docx)
+    } else if IMAGE_FILE_EXTENSIONS.iter().any(|&ext| path.ends_with(ext)) {
+        Ok(DataType::Image)
     } else if SUPPORTED_AUDIO_FILE_EXTENSIONS
         .iter()
         .any(|&ext| path.ends_with(ext))
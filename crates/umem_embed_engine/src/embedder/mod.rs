@@ -0,0 +1,7 @@
+use anyhow::Result;
+
+#[async_trait::async_trait]
+pub trait EmbeddingModel {
+    /// Embeds every input, returning one vector per input in the same order.
+    async fn embed(&self, input: &[String]) -> Result<Vec<Vec<f32>>>;
+}
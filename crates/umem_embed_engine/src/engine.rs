@@ -1,13 +1,34 @@
 use crate::{
-    HashMap,
+    HashMap, HashSet,
     chunkers::Chunker,
-    config::{AddConfig, ChunkerConfig, EngineConfig},
+    config::{AddConfig, ChunkerConfig, EngineConfig, LLMConfig},
     data_formatter::{CreateChunksResponse, DataFormatter},
+    data_sources::{DataSource, DataSourceStore, now},
     data_type::{DIRECT_DATA_TYPES, DataType, INDIRECT_DATA_TYPES},
+    embedder::EmbeddingModel,
+    estimate::{DryRunReport, Pricing},
+    llm::LanguageModel,
     loaders::Loader,
-    vectordb::{VectorDB, VectorDBGetResponse},
+    vectordb::{VectorDB, VectorDBGetResponse, VectorDBQueryResponse},
 };
 use anyhow::Result;
+use futures::stream::BoxStream;
+use serde_json::Value;
+
+/// Used when `LLMConfig::prompt` is unset. Custom prompts may use the same
+/// `{context}` and `{query}` placeholders.
+const DEFAULT_PROMPT: &str = "You are a Q&A expert system. Your responses must always be rooted in the context provided for each query. Here are some guidelines to follow:
+1. Refrain from explicitly mentioning the context provided in your response.
+2. The context should silently guide your answers without being directly acknowledged.
+3. Do not use phrases such as 'According to the context provided', 'Based on the context, ...' etc.
+
+Context information:
+----------------------
+{context}
+----------------------
+
+Query: {query}
+Answer:";
 
 pub struct Engine<LLM, VectorDB, Embedder> {
     config: EngineConfig,
@@ -17,6 +38,8 @@ pub struct Engine<LLM, VectorDB, Embedder> {
     embedder: Embedder,
     system_prompt: Option<String>,
     user_asks: Vec<UserAsk>,
+    data_sources: Option<DataSourceStore>,
+    pricing: Option<Pricing>,
 }
 
 pub struct AddRequest {
@@ -29,6 +52,54 @@ pub struct AddRequest {
     dry_run: bool,
 }
 
+impl AddRequest {
+    pub fn new(source: AddSource) -> Self {
+        AddRequest {
+            source,
+            data_type: None,
+            metadata: HashMap::default(),
+            add_config: None,
+            chunker: None,
+            loader: None,
+            dry_run: false,
+        }
+    }
+
+    pub fn data_type(mut self, data_type: DataType) -> Self {
+        self.data_type = Some(data_type);
+        self
+    }
+
+    pub fn metadata(mut self, metadata: HashMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn add_config(mut self, add_config: AddConfig) -> Self {
+        self.add_config = Some(add_config);
+        self
+    }
+
+    pub fn chunker(mut self, chunker: Box<dyn Chunker>) -> Self {
+        self.chunker = Some(chunker);
+        self
+    }
+
+    pub fn loader(mut self, loader: Box<dyn Loader>) -> Self {
+        self.loader = Some(loader);
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
 pub struct UserAsk {
     source: AddSource,
     data_type: DataType,
@@ -56,6 +127,47 @@ impl AddSource {
     }
 }
 
+/// A retrieved chunk that was handed to the LLM as context.
+pub struct Citation {
+    pub id: String,
+    pub document: String,
+    pub metadata: HashMap<String, String>,
+}
+
+pub struct QueryResponse {
+    pub answer: String,
+    pub citations: Vec<Citation>,
+}
+
+/// Citations are known before the first token, so callers can render them while
+/// `answer` is still streaming.
+pub struct QueryStreamResponse {
+    pub citations: Vec<Citation>,
+    pub answer: BoxStream<'static, Result<String>>,
+}
+
+pub enum AddOutcome {
+    /// The source was added; holds its source hash.
+    Added(String),
+    DryRun(DryRunReport),
+}
+
+pub struct AddResponse {
+    pub source_hash: String,
+    pub chunks: LoadAndEmbedResponse,
+}
+
+pub struct ResyncResponse {
+    pub source_hash: String,
+    /// Chunks that were new or changed and got embedded.
+    pub added_ids: Vec<String>,
+    /// Text of the `added_ids` chunks, in the same order.
+    pub added_documents: Vec<String>,
+    /// Chunks that no longer appear in the source and were deleted.
+    pub removed_ids: Vec<String>,
+    pub unchanged_count: usize,
+}
+
 pub struct LoadAndEmbedResponse {
     pub documents: Vec<String>,
     pub metadatas: Vec<HashMap<String, String>>,
@@ -83,10 +195,57 @@ where
             system_prompt,
             chunker,
             user_asks: vec![],
+            data_sources: None,
+            pricing: None,
+        }
+    }
+
+    /// Records every added source in `store`, enabling `data_sources` and
+    /// `delete_data_source`.
+    pub fn with_data_sources(mut self, store: DataSourceStore) -> Self {
+        self.data_sources = Some(store);
+        self
+    }
+
+    /// Prices dry run reports with `pricing`.
+    pub fn with_pricing(mut self, pricing: Pricing) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
+    pub fn pricing(&self) -> Option<&Pricing> {
+        self.pricing.as_ref()
+    }
+
+    pub async fn add(&mut self, request: AddRequest) -> Result<AddOutcome> {
+        let dry_run = request.dry_run;
+        let AddResponse {
+            source_hash,
+            chunks,
+        } = self.add_chunks(request).await?;
+
+        if dry_run {
+            return Ok(AddOutcome::DryRun(
+                self.dry_run_report(source_hash, &chunks),
+            ));
         }
+
+        Ok(AddOutcome::Added(source_hash))
     }
 
-    pub async fn add(&mut self, request: AddRequest) -> Result<String> {
+    /// Estimates the embedding work for chunks returned by a dry run of `add_chunks`.
+    pub fn dry_run_report(
+        &self,
+        source_hash: String,
+        chunks: &LoadAndEmbedResponse,
+    ) -> DryRunReport {
+        DryRunReport::new(source_hash, &chunks.documents).priced(self.pricing.as_ref())
+    }
+
+    /// Like `add`, but returns the chunks that were stored so callers can build on
+    /// them. Chunks already stored for the source are not returned again; on a dry
+    /// run nothing is stored and the chunks that would have been are returned.
+    pub async fn add_chunks(&mut self, request: AddRequest) -> Result<AddResponse> {
         let add_config = match request.add_config {
             Some(config) => config,
             None => AddConfig {
@@ -103,12 +262,7 @@ where
         let data_formatter =
             DataFormatter::try_new(data_type, request.loader, request.chunker, &add_config)?;
 
-        let LoadAndEmbedResponse {
-            documents,
-            metadatas,
-            ids,
-            added_chunks_count,
-        } = self
+        let chunks = self
             .load_and_embed(
                 &data_formatter,
                 &request.source,
@@ -118,26 +272,180 @@ where
                 request.dry_run,
             )
             .await?;
+
+        if let Some(store) = self.data_sources.as_ref().filter(|_| !request.dry_run) {
+            store
+                .upsert(&DataSource {
+                    hash: source_hash.clone(),
+                    app_id: self.config.id.clone(),
+                    data_type: data_type.to_string(),
+                    source: request.source.to_string(),
+                    is_url: matches!(request.source, AddSource::Url(_)),
+                    metadata: request.metadata.clone(),
+                    created_at: now(),
+                    last_synced_at: now(),
+                })
+                .await?;
+        }
+
         let _: () = self.user_asks.push(UserAsk {
             source: request.source,
             data_type,
             metadata: request.metadata,
         });
 
-        // TODO: make a data sources table and insert source_hash, app_id, data_type, source, metadata
+        if self.config.enable_telemetry {
+            // TODO: log it to some telemetry system
+        }
+
+        Ok(AddResponse {
+            source_hash,
+            chunks,
+        })
+    }
+
+    /// Lists the sources added under this engine's app id.
+    pub async fn data_sources(&self) -> Result<Vec<DataSource>> {
+        self.data_source_store()?
+            .list(self.config.id.as_deref())
+            .await
+    }
+
+    /// Looks up the registry entry of `source`, if it was added.
+    pub async fn data_source(&self, source: &AddSource) -> Result<Option<DataSource>> {
+        let source_hash = blake3::hash(source.as_bytes()).to_hex().to_string();
+        self.data_source_store()?
+            .get(self.config.id.as_deref(), &source_hash)
+            .await
+    }
+
+    /// Removes a source and every chunk stored for it, returning whether the source
+    /// was registered.
+    pub async fn delete_data_source(&mut self, source_hash: &str) -> Result<bool> {
+        self.data_source_store()?;
+        let app_id = self.config.id.clone();
+
+        let mut filters = vec![("hash".to_string(), source_hash.to_string())];
+        if let Some(app_id) = &app_id {
+            filters.push(("app_id".to_string(), app_id.clone()));
+        }
+
+        let VectorDBGetResponse { metadatas, .. } =
+            self.db.get(None, Some(filters.as_slice()), None).await?;
+        let mut doc_ids: Vec<String> = metadatas
+            .iter()
+            .filter_map(|metadata| metadata.get("doc_id").cloned())
+            .collect();
+        doc_ids.sort();
+        doc_ids.dedup();
+        if !doc_ids.is_empty() {
+            let _: () = self.db.delete(&doc_ids).await?;
+        }
+
+        self.user_asks
+            .retain(|ask| blake3::hash(ask.source.as_bytes()).to_hex().as_str() != source_hash);
+
+        self.data_source_store()?
+            .delete(app_id.as_deref(), source_hash)
+            .await
+    }
 
-        if request.dry_run {
-            return Ok(format!(
-                "Dry run: {} chunks would be added.",
-                added_chunks_count
+    /// Re-fetches a registered indirect source (typically a URL) and brings its chunks
+    /// up to date: chunks whose content hash is already stored are kept as they are,
+    /// only new or changed chunks are embedded, and chunks gone from the source are
+    /// deleted. Unchanged chunks keep the `doc_id` of the load that stored them.
+    pub async fn resync(&mut self, source: AddSource) -> Result<ResyncResponse> {
+        let app_id = self.config.id.clone();
+        let source_hash = blake3::hash(source.as_bytes()).to_hex().to_string();
+        let mut data_source = self
+            .data_source_store()?
+            .get(app_id.as_deref(), &source_hash)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("{} was never added", source.to_string()))?;
+
+        let data_type: DataType = data_source.data_type.parse()?;
+        if !INDIRECT_DATA_TYPES.contains(&data_type) {
+            return Err(anyhow::anyhow!(
+                "DataType {:?} can't be resynced",
+                data_type
             ));
         }
 
-        if self.config.enable_telemetry {
-            // TODO: log it to some telemetry system
+        let add_config = AddConfig {
+            chunker: self.chunker.clone(),
+            loader: None,
+        };
+        let data_formatter = DataFormatter::try_new(data_type, None, None, &add_config)?;
+        let CreateChunksResponse {
+            chunk_ids,
+            ids,
+            documents,
+            metadatas,
+            ..
+        } = data_formatter
+            .create_chunks(&source, app_id.clone(), add_config.chunker)
+            .await?;
+
+        let mut filters = vec![("hash".to_string(), source_hash.clone())];
+        if let Some(app_id) = &app_id {
+            filters.push(("app_id".to_string(), app_id.clone()));
+        }
+        let VectorDBGetResponse {
+            ids: stored_ids, ..
+        } = self.db.get(None, Some(filters.as_slice()), None).await?;
+        let stored_ids: HashSet<String> = stored_ids.into_iter().collect();
+
+        let removed_ids: Vec<String> = stored_ids
+            .iter()
+            .filter(|id| !chunk_ids.contains(*id))
+            .cloned()
+            .collect();
+        if !removed_ids.is_empty() {
+            let _: () = self.db.delete_chunks(&removed_ids).await?;
         }
 
-        Ok(source_hash)
+        let mut added_ids = vec![];
+        let mut added_documents = vec![];
+        let mut added_metadatas = vec![];
+        for ((id, document), mut metadata) in ids.into_iter().zip(documents).zip(metadatas) {
+            if stored_ids.contains(&id) {
+                continue;
+            }
+            metadata.insert("app_id".to_string(), app_id.clone().unwrap_or_default());
+            metadata.insert("hash".to_string(), source_hash.clone());
+            metadata.extend(
+                data_source
+                    .metadata
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone())),
+            );
+            added_ids.push(id);
+            added_documents.push(document);
+            added_metadatas.push(metadata);
+        }
+        if !added_ids.is_empty() {
+            let _: () = self
+                .db
+                .add(&added_documents, &added_metadatas, &added_ids)
+                .await?;
+        }
+
+        data_source.last_synced_at = now();
+        self.data_source_store()?.upsert(&data_source).await?;
+
+        Ok(ResyncResponse {
+            source_hash,
+            unchanged_count: chunk_ids.len() - added_ids.len(),
+            added_ids,
+            added_documents,
+            removed_ids,
+        })
+    }
+
+    fn data_source_store(&self) -> Result<&DataSourceStore> {
+        self.data_sources
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("no data source store is configured"))
     }
 
     async fn load_and_embed(
@@ -159,6 +467,7 @@ where
             mut metadatas,
             chunk_ids,
             doc_id,
+            ..
         } = data_formatter
             .create_chunks(source, app_id, add_config.chunker)
             .await?;
@@ -303,6 +612,112 @@ where
             ))
         }
     }
+}
+
+impl<LLM, DB, Embedder> Engine<LLM, DB, Embedder>
+where
+    LLM: LanguageModel,
+    DB: VectorDB,
+    Embedder: EmbeddingModel,
+{
+    /// Answers `input_query` from the stored chunks: the query is embedded, the
+    /// `documents_to_pull_from_db` nearest chunks matching `where_filters` become the
+    /// prompt's context, and the LLM's answer comes back with the chunks it was given.
+    pub async fn query(
+        &self,
+        input_query: String,
+        config: Option<LLMConfig>,
+    ) -> Result<QueryResponse> {
+        let config = config.unwrap_or_default();
+        let citations = self.retrieve(&input_query, &config).await?;
+        let prompt = self.generate_prompt(&input_query, &citations, &config);
+
+        let answer = self
+            .llm
+            .generate(self.system_prompt_for(&config), &prompt, &config)
+            .await?;
+
+        Ok(QueryResponse { answer, citations })
+    }
 
-    async fn query(&self, input_query: String, config: Option<BaseLLMConfig>) -> Result<()> {}
+    /// Streaming form of `query`: retrieval happens up front and the answer is
+    /// streamed from the LLM as it is generated.
+    pub async fn query_stream(
+        &self,
+        input_query: String,
+        config: Option<LLMConfig>,
+    ) -> Result<QueryStreamResponse> {
+        let config = config.unwrap_or_default();
+        let citations = self.retrieve(&input_query, &config).await?;
+        let prompt = self.generate_prompt(&input_query, &citations, &config);
+
+        let answer = self
+            .llm
+            .generate_stream(self.system_prompt_for(&config), &prompt, &config)
+            .await?;
+
+        Ok(QueryStreamResponse { citations, answer })
+    }
+
+    async fn retrieve(&self, input_query: &str, config: &LLMConfig) -> Result<Vec<Citation>> {
+        let embedding = self
+            .embedder
+            .embed(&[input_query.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("embedder returned no embedding for the query"))?;
+
+        let mut filters = config.where_filters.clone();
+        if let Some(app_id) = &self.config.id {
+            filters.push(("app_id".to_string(), Value::String(app_id.clone())));
+        }
+
+        let VectorDBQueryResponse {
+            ids,
+            documents,
+            metadatas,
+        } = self
+            .db
+            .query(&embedding, config.documents_to_pull_from_db, &filters)
+            .await?;
+
+        Ok(ids
+            .into_iter()
+            .zip(documents)
+            .zip(metadatas)
+            .map(|((id, document), metadata)| Citation {
+                id,
+                document,
+                metadata,
+            })
+            .collect())
+    }
+
+    fn system_prompt_for<'a>(&'a self, config: &'a LLMConfig) -> Option<&'a str> {
+        config
+            .system_prompt
+            .as_deref()
+            .or(self.system_prompt.as_deref())
+    }
+
+    fn generate_prompt(
+        &self,
+        input_query: &str,
+        citations: &[Citation],
+        config: &LLMConfig,
+    ) -> String {
+        let context = citations
+            .iter()
+            .map(|citation| citation.document.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n\n");
+
+        config
+            .prompt
+            .as_deref()
+            .unwrap_or(DEFAULT_PROMPT)
+            .replace("{context}", &context)
+            .replace("{query}", input_query)
+    }
 }
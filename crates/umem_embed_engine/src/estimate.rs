@@ -0,0 +1,60 @@
+use crate::config::Tokenizer;
+
+/// Provider prices in US dollars per million tokens, used to estimate what an
+/// ingestion would cost.
+#[derive(Debug, Clone, Default)]
+pub struct Pricing {
+    pub embedding: f64,
+    pub llm_input: f64,
+    pub llm_output: f64,
+}
+
+/// What a dry run would have done. Token counts use the cl100k tokenizer, so they are
+/// close to, but not exactly, what other providers bill.
+#[derive(Debug, Clone)]
+pub struct DryRunReport {
+    pub source_hash: String,
+    pub chunk_count: usize,
+    pub embedding_tokens: usize,
+    pub annotation_calls: usize,
+    pub annotation_input_tokens: usize,
+    pub annotation_output_tokens: usize,
+    /// Set when pricing is known.
+    pub estimated_cost: Option<f64>,
+}
+
+impl DryRunReport {
+    pub fn new(source_hash: String, documents: &[String]) -> Self {
+        DryRunReport {
+            source_hash,
+            chunk_count: documents.len(),
+            embedding_tokens: documents
+                .iter()
+                .map(|document| Tokenizer::Cl100kBase.count(document))
+                .sum(),
+            annotation_calls: 0,
+            annotation_input_tokens: 0,
+            annotation_output_tokens: 0,
+            estimated_cost: None,
+        }
+    }
+
+    /// Accounts for one annotation call per chunk, each sending the chunk with
+    /// `prompt_tokens` of instructions and answering with about `output_tokens`.
+    pub fn with_annotation(mut self, prompt_tokens: usize, output_tokens: usize) -> Self {
+        self.annotation_calls = self.chunk_count;
+        self.annotation_input_tokens = self.embedding_tokens + self.chunk_count * prompt_tokens;
+        self.annotation_output_tokens = self.chunk_count * output_tokens;
+        self
+    }
+
+    pub fn priced(mut self, pricing: Option<&Pricing>) -> Self {
+        self.estimated_cost = pricing.map(|pricing| {
+            (self.embedding_tokens as f64 * pricing.embedding
+                + self.annotation_input_tokens as f64 * pricing.llm_input
+                + self.annotation_output_tokens as f64 * pricing.llm_output)
+                / 1_000_000.0
+        });
+        self
+    }
+}
@@ -2,8 +2,12 @@ pub mod chunkers;
 pub mod client;
 pub mod config;
 pub mod data_formatter;
+pub mod data_sources;
 pub mod data_type;
+pub mod embedder;
 pub mod engine;
+pub mod estimate;
+pub mod llm;
 pub mod loaders;
 pub mod utils;
 pub mod vectordb;
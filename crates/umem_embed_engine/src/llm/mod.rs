@@ -0,0 +1,23 @@
+use crate::config::LLMConfig;
+use anyhow::Result;
+use futures::stream::BoxStream;
+
+#[async_trait::async_trait]
+pub trait LanguageModel {
+    /// Completes `prompt`, honouring the sampling settings (model, temperature,
+    /// top_p, max_tokens, model_args) of `config`.
+    async fn generate(
+        &self,
+        system_prompt: Option<&str>,
+        prompt: &str,
+        config: &LLMConfig,
+    ) -> Result<String>;
+
+    /// Like `generate`, but yields the answer as it is produced, one text delta per item.
+    async fn generate_stream(
+        &self,
+        system_prompt: Option<&str>,
+        prompt: &str,
+        config: &LLMConfig,
+    ) -> Result<BoxStream<'static, Result<String>>>;
+}
@@ -0,0 +1,41 @@
+use super::{LoadDataResult, Loader, format_row, read_source_bytes, row_metadata, tabular_doc_id};
+use crate::engine::AddSource;
+use anyhow::Result;
+
+/// Loads one record per CSV row as `header: value` text, with the 1-based data row
+/// number in the metadata. The first row is taken as the header.
+pub struct CsvLoader;
+
+#[async_trait::async_trait]
+impl Loader for CsvLoader {
+    async fn load_data(&self, source: &AddSource) -> Result<LoadDataResult> {
+        let bytes = read_source_bytes(source).await?;
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(bytes.as_slice());
+
+        let headers: Vec<String> = reader
+            .headers()?
+            .iter()
+            .map(|header| header.trim().to_string())
+            .collect();
+
+        let mut data = Vec::new();
+        for (index, record) in reader.records().enumerate() {
+            let text = format_row(&headers, record?.iter());
+            if !text.is_empty() {
+                data.push((text, row_metadata(source, index + 1)));
+            }
+        }
+
+        let doc_id = tabular_doc_id(&data, source);
+
+        Ok(LoadDataResult { doc_id, data })
+    }
+}
+
+impl CsvLoader {
+    pub fn new() -> Self {
+        CsvLoader
+    }
+}
@@ -0,0 +1,100 @@
+use super::{LoadDataResult, Loader, read_source_bytes};
+use crate::{HashMap, engine::AddSource, utils};
+use anyhow::{Result, anyhow};
+use docx_rs::{
+    DocumentChild, Paragraph, ParagraphChild, RunChild, Table, TableCellContent, TableChild,
+    TableRowChild,
+};
+
+/// Loads the body text of a Word document: one line per paragraph, and table rows
+/// with their cells separated by ` | `.
+pub struct DocxLoader;
+
+#[async_trait::async_trait]
+impl Loader for DocxLoader {
+    async fn load_data(&self, source: &AddSource) -> Result<LoadDataResult> {
+        let bytes = read_source_bytes(source).await?;
+        let docx = docx_rs::read_docx(&bytes)
+            .map_err(|e| anyhow!("failed to read docx {}: {}", source.to_string(), e))?;
+
+        let mut lines = Vec::new();
+        for child in &docx.document.children {
+            match child {
+                DocumentChild::Paragraph(paragraph) => lines.push(paragraph_text(paragraph)),
+                DocumentChild::Table(table) => lines.extend(table_lines(table)),
+                _ => {}
+            }
+        }
+        lines.retain(|line| !line.is_empty());
+        let content = lines.join("\n");
+
+        let mut metadata: HashMap<String, String> = HashMap::default();
+        metadata.insert("url".to_string(), source.to_string());
+
+        let mut hash_content = content.clone();
+        hash_content.push_str(&source.to_string());
+        let doc_id = blake3::hash(hash_content.as_bytes()).to_hex().to_string();
+
+        Ok(LoadDataResult {
+            doc_id,
+            data: vec![(content, metadata)],
+        })
+    }
+}
+
+fn paragraph_text(paragraph: &Paragraph) -> String {
+    let mut text = String::new();
+    push_paragraph_children(&paragraph.children, &mut text);
+    utils::clean_string(text)
+}
+
+fn push_paragraph_children(children: &[ParagraphChild], text: &mut String) {
+    for child in children {
+        match child {
+            ParagraphChild::Run(run) => {
+                for run_child in &run.children {
+                    match run_child {
+                        RunChild::Text(t) => text.push_str(&t.text),
+                        RunChild::Tab(_) | RunChild::Break(_) => text.push(' '),
+                        _ => {}
+                    }
+                }
+            }
+            ParagraphChild::Hyperlink(hyperlink) => {
+                push_paragraph_children(&hyperlink.children, text)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn table_lines(table: &Table) -> Vec<String> {
+    table
+        .rows
+        .iter()
+        .map(|TableChild::TableRow(row)| {
+            row.cells
+                .iter()
+                .map(|TableRowChild::TableCell(cell)| {
+                    cell.children
+                        .iter()
+                        .filter_map(|content| match content {
+                            TableCellContent::Paragraph(paragraph) => {
+                                Some(paragraph_text(paragraph))
+                            }
+                            _ => None,
+                        })
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                })
+                .collect::<Vec<String>>()
+                .join(" | ")
+        })
+        .collect()
+}
+
+impl DocxLoader {
+    pub fn new() -> Self {
+        DocxLoader
+    }
+}
@@ -0,0 +1,54 @@
+use super::{LoadDataResult, Loader, format_row, read_source_bytes, row_metadata, tabular_doc_id};
+use crate::engine::AddSource;
+use anyhow::Result;
+use calamine::{Reader, open_workbook_auto_from_rs};
+use std::io::Cursor;
+
+/// Loads every sheet of a workbook (xlsx, xls, xlsb, ods) row by row, like
+/// `CsvLoader`, taking each sheet's first row as its header. The sheet name is
+/// added to each row's metadata.
+pub struct ExcelLoader;
+
+#[async_trait::async_trait]
+impl Loader for ExcelLoader {
+    async fn load_data(&self, source: &AddSource) -> Result<LoadDataResult> {
+        let bytes = read_source_bytes(source).await?;
+        let mut workbook = open_workbook_auto_from_rs(Cursor::new(bytes))?;
+
+        let mut data = Vec::new();
+        for sheet_name in workbook.sheet_names() {
+            let range = workbook.worksheet_range(&sheet_name)?;
+            let mut rows = range.rows();
+
+            let Some(header_row) = rows.next() else {
+                continue;
+            };
+            let headers: Vec<String> = header_row
+                .iter()
+                .map(|cell| cell.to_string().trim().to_string())
+                .collect();
+
+            for (index, row) in rows.enumerate() {
+                let cells: Vec<String> = row.iter().map(|cell| cell.to_string()).collect();
+                let text = format_row(&headers, cells.iter().map(String::as_str));
+                if text.is_empty() {
+                    continue;
+                }
+
+                let mut metadata = row_metadata(source, index + 1);
+                metadata.insert("sheet".to_string(), sheet_name.clone());
+                data.push((text, metadata));
+            }
+        }
+
+        let doc_id = tabular_doc_id(&data, source);
+
+        Ok(LoadDataResult { doc_id, data })
+    }
+}
+
+impl ExcelLoader {
+    pub fn new() -> Self {
+        ExcelLoader
+    }
+}
@@ -0,0 +1,90 @@
+use super::{LoadDataResult, Loader, read_source_bytes};
+use crate::{HashMap, engine::AddSource, utils};
+use anyhow::{Result, anyhow};
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// Extracts the text of screenshots and scanned pages with the `tesseract` OCR binary,
+/// which has to be installed and on `PATH` (or set with `command`).
+pub struct ImageLoader {
+    command: String,
+    language: Option<String>,
+}
+
+impl ImageLoader {
+    pub fn new() -> Self {
+        ImageLoader {
+            command: "tesseract".to_string(),
+            language: None,
+        }
+    }
+
+    pub fn command(mut self, command: impl Into<String>) -> Self {
+        self.command = command.into();
+        self
+    }
+
+    /// Tesseract language codes, e.g. `eng+deu`. Tesseract reads English by default.
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    fn recognize(&self, image: &[u8]) -> Result<String> {
+        let mut command = Command::new(&self.command);
+        command.args(["stdin", "stdout"]);
+        if let Some(language) = &self.language {
+            command.args(["-l", language]);
+        }
+
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow!("failed to run {}: {}", self.command, e))?;
+        child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("failed to open stdin of {}", self.command))?
+            .write_all(image)?;
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "{} failed: {}",
+                self.command,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+#[async_trait::async_trait]
+impl Loader for ImageLoader {
+    async fn load_data(&self, source: &AddSource) -> Result<LoadDataResult> {
+        let bytes = read_source_bytes(source).await?;
+        let content = utils::clean_string(self.recognize(&bytes)?);
+        if content.is_empty() {
+            return Err(anyhow!("no text recognized in {}", source.to_string()));
+        }
+
+        let mut metadata: HashMap<String, String> = HashMap::default();
+        metadata.insert("url".to_string(), source.to_string());
+        metadata.insert("image_source".to_string(), source.to_string());
+        metadata.insert("ocr".to_string(), "tesseract".to_string());
+
+        let mut hash_content = content.clone();
+        hash_content.push_str(&source.to_string());
+        let doc_id = blake3::hash(hash_content.as_bytes()).to_hex().to_string();
+
+        Ok(LoadDataResult {
+            doc_id,
+            data: vec![(content, metadata)],
+        })
+    }
+}
@@ -0,0 +1,103 @@
+use super::{LoadDataResult, Loader, read_source_bytes};
+use crate::{HashMap, engine::AddSource};
+use anyhow::Result;
+
+/// Loads markdown/MDX as one record per heading section. Each record keeps its
+/// heading line and carries the heading path (`Install > Linux`) in its metadata.
+/// Lines inside fenced code blocks are never read as headings.
+pub struct MdxLoader;
+
+#[async_trait::async_trait]
+impl Loader for MdxLoader {
+    async fn load_data(&self, source: &AddSource) -> Result<LoadDataResult> {
+        let content = String::from_utf8(read_source_bytes(source).await?)?;
+
+        let data: Vec<(String, HashMap<String, String>)> = split_sections(&content)
+            .into_iter()
+            .map(|(heading, text)| {
+                let mut metadata: HashMap<String, String> = HashMap::default();
+                metadata.insert("url".to_string(), source.to_string());
+                if !heading.is_empty() {
+                    metadata.insert("heading".to_string(), heading);
+                }
+                (text, metadata)
+            })
+            .collect();
+
+        let mut hash_content = content;
+        hash_content.push_str(&source.to_string());
+        let doc_id = blake3::hash(hash_content.as_bytes()).to_hex().to_string();
+
+        Ok(LoadDataResult { doc_id, data })
+    }
+}
+
+/// Returns `(heading path, section text)` pairs, dropping sections with no text.
+fn split_sections(content: &str) -> Vec<(String, String)> {
+    let mut sections = Vec::new();
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    let mut current = String::new();
+    let mut fence: Option<&str> = None;
+
+    let heading_path = |headings: &[(usize, String)]| {
+        headings
+            .iter()
+            .map(|(_, title)| title.as_str())
+            .collect::<Vec<&str>>()
+            .join(" > ")
+    };
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+
+        match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+            }
+            None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => {
+                fence = Some(&trimmed[..3]);
+            }
+            None => {
+                if let Some((level, title)) = parse_heading(trimmed) {
+                    if !current.trim().is_empty() {
+                        sections.push((heading_path(&headings), current.trim().to_string()));
+                    }
+                    current.clear();
+                    headings.retain(|(l, _)| *l < level);
+                    headings.push((level, title.to_string()));
+                }
+            }
+        }
+
+        current.push_str(line);
+        current.push('\n');
+    }
+
+    if !current.trim().is_empty() {
+        sections.push((heading_path(&headings), current.trim().to_string()));
+    }
+
+    sections
+}
+
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+
+    Some((level, rest.trim().trim_end_matches('#').trim()))
+}
+
+impl MdxLoader {
+    pub fn new() -> Self {
+        MdxLoader
+    }
+}
@@ -1,7 +1,17 @@
-use crate::{HashMap, engine::AddSource};
+use crate::{HashMap, engine::AddSource, reqwest_client};
 use anyhow::Result;
 
+pub mod csv_loader;
+#[cfg(feature = "docx")]
+pub mod docx_loader;
+pub mod excel_loader;
+pub mod image_loader;
+pub mod mdx_loader;
 pub mod pdf_loader;
+pub mod rss_feed_loader;
+
+/// Joins the `header: value` pairs of a tabular row.
+pub(crate) const ROW_FIELD_SEPARATOR: &str = ", ";
 
 pub struct LoadDataResult {
     pub(crate) doc_id: String,
@@ -12,3 +22,53 @@ pub struct LoadDataResult {
 pub trait Loader {
     async fn load_data(&self, source: &AddSource) -> Result<LoadDataResult>;
 }
+
+pub(crate) async fn read_source_bytes(source: &AddSource) -> Result<Vec<u8>> {
+    match source {
+        AddSource::LocalFile(path) => Ok(std::fs::read(path)?),
+        AddSource::Url(url) => Ok(reqwest_client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?
+            .to_vec()),
+    }
+}
+
+/// Renders a row as `header: value` pairs, skipping empty cells. Cells past the last
+/// header are named by their 1-based column.
+pub(crate) fn format_row<'a>(headers: &[String], values: impl Iterator<Item = &'a str>) -> String {
+    values
+        .enumerate()
+        .map(|(index, value)| (index, value.trim()))
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(index, value)| match headers.get(index) {
+            Some(header) if !header.is_empty() => format!("{}: {}", header, value),
+            _ => format!("column_{}: {}", index + 1, value),
+        })
+        .collect::<Vec<String>>()
+        .join(ROW_FIELD_SEPARATOR)
+}
+
+pub(crate) fn row_metadata(source: &AddSource, row: usize) -> HashMap<String, String> {
+    let mut metadata: HashMap<String, String> = HashMap::default();
+    metadata.insert("url".to_string(), source.to_string());
+    metadata.insert("row".to_string(), row.to_string());
+    metadata
+}
+
+pub(crate) fn tabular_doc_id(
+    rows: &[(String, HashMap<String, String>)],
+    source: &AddSource,
+) -> String {
+    let mut hash_content = rows
+        .iter()
+        .map(|(text, _)| text.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    hash_content.push_str(&source.to_string());
+
+    blake3::hash(hash_content.as_bytes()).to_hex().to_string()
+}
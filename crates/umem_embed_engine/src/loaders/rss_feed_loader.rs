@@ -0,0 +1,110 @@
+use super::{LoadDataResult, Loader, read_source_bytes};
+use crate::{HashMap, engine::AddSource, utils};
+use anyhow::{Result, anyhow};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref HTML_TAG_RE: Regex = Regex::new(r"<[^>]*>").unwrap();
+}
+
+/// One entry of an RSS or Atom feed, with its HTML reduced to text.
+pub struct FeedItem {
+    pub id: String,
+    pub title: String,
+    pub link: Option<String>,
+    pub published: Option<i64>,
+    pub content: String,
+}
+
+/// Loads the items of an RSS or Atom feed, one record per item. Substack publications
+/// are read from their `/feed` endpoint, so the publication URL can be added directly.
+pub struct RssFeedLoader {
+    substack: bool,
+}
+
+impl RssFeedLoader {
+    pub fn new() -> Self {
+        RssFeedLoader { substack: false }
+    }
+
+    pub fn substack() -> Self {
+        RssFeedLoader { substack: true }
+    }
+
+    pub async fn fetch_items(&self, source: &AddSource) -> Result<Vec<FeedItem>> {
+        let source = match source {
+            AddSource::Url(url)
+                if self.substack && !url.trim_end_matches('/').ends_with("/feed") =>
+            {
+                AddSource::Url(format!("{}/feed", url.trim_end_matches('/')))
+            }
+            AddSource::Url(url) => AddSource::Url(url.clone()),
+            AddSource::LocalFile(path) => AddSource::LocalFile(path.clone()),
+        };
+        let bytes = read_source_bytes(&source).await?;
+        let feed = feed_rs::parser::parse(bytes.as_slice())
+            .map_err(|e| anyhow!("failed to parse feed {}: {}", source.to_string(), e))?;
+
+        Ok(feed
+            .entries
+            .into_iter()
+            .map(|entry| {
+                let body = entry
+                    .content
+                    .and_then(|content| content.body)
+                    .or(entry.summary.map(|summary| summary.content))
+                    .unwrap_or_default();
+
+                FeedItem {
+                    id: entry.id,
+                    title: entry.title.map(|title| title.content).unwrap_or_default(),
+                    link: entry.links.into_iter().next().map(|link| link.href),
+                    published: entry
+                        .published
+                        .or(entry.updated)
+                        .map(|time| time.timestamp()),
+                    content: utils::clean_string(HTML_TAG_RE.replace_all(&body, " ").into_owned()),
+                }
+            })
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Loader for RssFeedLoader {
+    async fn load_data(&self, source: &AddSource) -> Result<LoadDataResult> {
+        let items = self.fetch_items(source).await?;
+
+        let mut hash_content = String::new();
+        let mut data = Vec::with_capacity(items.len());
+        for item in items {
+            hash_content.push_str(&item.id);
+
+            let content = match item.title.is_empty() {
+                true => item.content,
+                false => format!("{}\n{}", item.title, item.content),
+            };
+            if content.trim().is_empty() {
+                continue;
+            }
+
+            let mut metadata: HashMap<String, String> = HashMap::default();
+            metadata.insert(
+                "url".to_string(),
+                item.link.unwrap_or_else(|| source.to_string()),
+            );
+            metadata.insert("feed".to_string(), source.to_string());
+            metadata.insert("item_id".to_string(), item.id);
+            metadata.insert("title".to_string(), item.title);
+            if let Some(published) = item.published {
+                metadata.insert("published".to_string(), published.to_string());
+            }
+            data.push((content, metadata));
+        }
+        hash_content.push_str(&source.to_string());
+        let doc_id = blake3::hash(hash_content.as_bytes()).to_hex().to_string();
+
+        Ok(LoadDataResult { doc_id, data })
+    }
+}
@@ -1,7 +1,12 @@
+mod vector_store;
+
 use anyhow::Result;
+use serde_json::Value;
 
 use crate::HashMap;
 
+pub use vector_store::VectorStoreDB;
+
 #[async_trait::async_trait]
 pub trait VectorDB {
     async fn get(
@@ -11,6 +16,8 @@ pub trait VectorDB {
         limit: Option<usize>,
     ) -> Result<VectorDBGetResponse>;
     async fn delete(&self, document_ids: &[String]) -> Result<()>;
+    /// Deletes single chunks by chunk id, leaving the rest of their document.
+    async fn delete_chunks(&self, ids: &[String]) -> Result<()>;
     async fn count(&self) -> Result<usize>;
     async fn add(
         &self,
@@ -18,9 +25,23 @@ pub trait VectorDB {
         metadatas: &[HashMap<String, String>],
         ids: &[String],
     ) -> Result<()>;
+    /// Returns the `n_results` chunks nearest to `embedding` that match every filter,
+    /// closest first.
+    async fn query(
+        &self,
+        embedding: &[f32],
+        n_results: usize,
+        filter: &[(String, Value)],
+    ) -> Result<VectorDBQueryResponse>;
 }
 
 pub struct VectorDBGetResponse {
     pub ids: Vec<String>,
     pub metadatas: Vec<HashMap<String, String>>,
 }
+
+pub struct VectorDBQueryResponse {
+    pub ids: Vec<String>,
+    pub documents: Vec<String>,
+    pub metadatas: Vec<HashMap<String, String>>,
+}
@@ -0,0 +1,141 @@
+use super::{VectorDB, VectorDBGetResponse, VectorDBQueryResponse};
+use crate::{HashMap, embedder::EmbeddingModel};
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::{iter::zip, sync::Arc};
+use umem_core::DocumentChunk;
+use umem_vector_store::VectorStoreBase;
+
+/// Stores the engine's chunks in the memory vector store (Qdrant or pgvector), in the
+/// `<collection>_documents` collection beside the memories, so one deployment serves
+/// both. Chunks are embedded with `embedder` on insert.
+pub struct VectorStoreDB {
+    store: Arc<dyn VectorStoreBase + Send + Sync>,
+    embedder: Arc<dyn EmbeddingModel + Send + Sync>,
+}
+
+impl VectorStoreDB {
+    pub async fn new(
+        store: Arc<dyn VectorStoreBase + Send + Sync>,
+        embedder: Arc<dyn EmbeddingModel + Send + Sync>,
+    ) -> Result<Self> {
+        store.create_document_collection().await?;
+        Ok(VectorStoreDB { store, embedder })
+    }
+}
+
+#[async_trait::async_trait]
+impl VectorDB for VectorStoreDB {
+    async fn get(
+        &self,
+        ids: Option<&[String]>,
+        filter: Option<&[(String, String)]>,
+        limit: Option<usize>,
+    ) -> Result<VectorDBGetResponse> {
+        let chunks = self
+            .store
+            .get_documents(
+                ids,
+                filter.unwrap_or_default(),
+                limit.map(|limit| limit as u32),
+            )
+            .await?;
+
+        let (ids, metadatas) = chunks
+            .into_iter()
+            .map(|chunk| (chunk.id, chunk.metadata.into_iter().collect()))
+            .unzip();
+
+        Ok(VectorDBGetResponse { ids, metadatas })
+    }
+
+    /// Deletes every chunk of each document; the engine passes `doc_id`s here.
+    async fn delete(&self, document_ids: &[String]) -> Result<()> {
+        for document_id in document_ids {
+            self.store
+                .delete_documents(None, &[("doc_id".to_string(), document_id.clone())])
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_chunks(&self, ids: &[String]) -> Result<()> {
+        Ok(self.store.delete_documents(Some(ids), &[]).await?)
+    }
+
+    async fn count(&self) -> Result<usize> {
+        Ok(self.store.count_documents().await?)
+    }
+
+    async fn add(
+        &self,
+        documents: &[String],
+        metadatas: &[HashMap<String, String>],
+        ids: &[String],
+    ) -> Result<()> {
+        if documents.is_empty() {
+            return Ok(());
+        }
+
+        let embeddings = self.embedder.embed(documents).await?;
+        if embeddings.len() != documents.len() {
+            return Err(anyhow!(
+                "embedder returned {} embeddings for {} documents",
+                embeddings.len(),
+                documents.len()
+            ));
+        }
+
+        let chunks: Vec<DocumentChunk> = zip(zip(ids, documents), metadatas)
+            .map(|((id, document), metadata)| DocumentChunk {
+                id: id.clone(),
+                document: document.clone(),
+                metadata: metadata.clone().into_iter().collect(),
+            })
+            .collect();
+
+        self.store
+            .insert_documents(
+                &embeddings.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+                &chunks.iter().collect::<Vec<_>>(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn query(
+        &self,
+        embedding: &[f32],
+        n_results: usize,
+        filter: &[(String, Value)],
+    ) -> Result<VectorDBQueryResponse> {
+        // NOTE: chunk metadata is stored as strings, so filter values are compared as strings
+        let filter: Vec<(String, String)> = filter
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(value) => (key.clone(), value.clone()),
+                value => (key.clone(), value.to_string()),
+            })
+            .collect();
+
+        let chunks = self
+            .store
+            .search_documents(embedding, &filter, n_results as u32)
+            .await?;
+
+        let mut response = VectorDBQueryResponse {
+            ids: Vec::with_capacity(chunks.len()),
+            documents: Vec::with_capacity(chunks.len()),
+            metadatas: Vec::with_capacity(chunks.len()),
+        };
+        for chunk in chunks {
+            response.ids.push(chunk.id);
+            response.documents.push(chunk.document);
+            response
+                .metadatas
+                .push(chunk.metadata.into_iter().collect());
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,43 @@
+use crate::EvalError;
+use serde::Deserialize;
+use std::path::Path;
+use uuid::Uuid;
+
+/// A memory seeded into the store before a benchmark runs.
+#[derive(Debug, Deserialize, Clone)]
+pub struct FixtureMemory {
+    pub id: Uuid,
+    pub summary: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A query and the ids of the memories it should find.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EvalCase {
+    pub query: String,
+    pub expected: Vec<Uuid>,
+}
+
+/// A retrieval benchmark over the memories of one user, read from JSON:
+///
+/// ```json
+/// {
+///   "user_id": "eval",
+///   "memories": [{ "id": "5f0c…", "summary": "Prefers window seats", "tags": ["travel"] }],
+///   "cases": [{ "query": "how does the user like to fly?", "expected": ["5f0c…"] }]
+/// }
+/// ```
+#[derive(Debug, Deserialize, Clone)]
+pub struct Fixture {
+    pub user_id: String,
+    #[serde(default)]
+    pub memories: Vec<FixtureMemory>,
+    pub cases: Vec<EvalCase>,
+}
+
+impl Fixture {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, EvalError> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+}
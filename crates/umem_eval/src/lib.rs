@@ -0,0 +1,37 @@
+use thiserror::Error;
+use umem_ai::EmbeddingModelError;
+use umem_controller::MemoryControllerError;
+use umem_core::{MemoryContextError, MemoryError};
+use umem_vector_store::VectorStoreError;
+
+mod fixture;
+mod metrics;
+mod runner;
+
+pub use fixture::*;
+pub use metrics::*;
+pub use runner::*;
+
+#[derive(Debug, Error)]
+pub enum EvalError {
+    #[error("reading fixture failed with: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("parsing fixture failed with: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("search failed with: {0}")]
+    MemoryControllerError(#[from] MemoryControllerError),
+
+    #[error("vector store action failed with: {0}")]
+    VectorStoreError(#[from] VectorStoreError),
+
+    #[error("embedding action failed with: {0}")]
+    EmbeddingModelError(#[from] EmbeddingModelError),
+
+    #[error("invalid fixture memory: {0}")]
+    MemoryError(#[from] MemoryError),
+
+    #[error("invalid fixture context: {0}")]
+    MemoryContextError(#[from] MemoryContextError),
+}
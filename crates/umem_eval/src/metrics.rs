@@ -0,0 +1,63 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Share of `expected` found in the first `k` of `retrieved`. A case with nothing
+/// expected counts as fully recalled.
+pub fn recall_at_k(retrieved: &[Uuid], expected: &[Uuid], k: usize) -> f64 {
+    if expected.is_empty() {
+        return 1.0;
+    }
+
+    let found = expected
+        .iter()
+        .filter(|id| retrieved.iter().take(k).any(|retrieved| retrieved == *id))
+        .count();
+    found as f64 / expected.len() as f64
+}
+
+/// `1 / rank` of the first expected id in `retrieved`, or 0 when none was found.
+pub fn reciprocal_rank(retrieved: &[Uuid], expected: &[Uuid]) -> f64 {
+    retrieved
+        .iter()
+        .position(|id| expected.contains(id))
+        .map_or(0.0, |index| 1.0 / (index + 1) as f64)
+}
+
+/// Metrics of one variant, averaged over every case of a fixture.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EvalReport {
+    pub variant: String,
+    pub cases: usize,
+    pub k: usize,
+    pub recall_at_k: f64,
+    pub mrr: f64,
+    pub mean_latency_ms: f64,
+    /// Cases answered without reranking because the reranker failed.
+    pub rerank_skipped: usize,
+    /// Cases cut short by the search deadline.
+    pub timed_out: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recall_counts_expected_ids_within_k() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let retrieved = [ids[0], ids[1], ids[2]];
+
+        assert_eq!(recall_at_k(&retrieved, &[ids[1], ids[3]], 3), 0.5);
+        assert_eq!(recall_at_k(&retrieved, &[ids[2]], 2), 0.0);
+        assert_eq!(recall_at_k(&retrieved, &[], 2), 1.0);
+    }
+
+    #[test]
+    fn reciprocal_rank_uses_first_expected_hit() {
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let retrieved = [ids[0], ids[1], ids[2]];
+
+        assert_eq!(reciprocal_rank(&retrieved, &[ids[2], ids[1]]), 0.5);
+        assert_eq!(reciprocal_rank(&retrieved, &[Uuid::new_v4()]), 0.0);
+    }
+}
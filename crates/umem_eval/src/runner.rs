@@ -0,0 +1,172 @@
+use crate::{recall_at_k, reciprocal_rank, EvalError, EvalReport, Fixture, FixtureMemory};
+use chrono::Utc;
+use serde::Deserialize;
+use std::{sync::Arc, time::Instant};
+use tracing::info;
+use umem_ai::embed::{embed, EmbeddingRequest};
+use umem_config::ScoreFusion;
+use umem_controller::{MemoryController, SearchMemoryOptions};
+use umem_core::{
+    credence::Credence, LifecycleState, Memory, MemoryContent, MemoryContext, MemoryError,
+    MemoryKind, MemorySignals, Provenance, TemporalMetadata,
+};
+
+/// One search pipeline configuration to measure. Unset fields keep the controller's.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct EvalVariant {
+    pub name: String,
+    /// Model of the configured embedding provider used for queries. Stored vectors
+    /// stay whatever seeded them, so compare embedders on separately seeded stores.
+    pub embedding_model: Option<String>,
+    /// Model of the configured reranking provider.
+    pub reranking_model: Option<String>,
+    pub fusion: Option<ScoreFusion>,
+}
+
+pub struct Evaluator {
+    controller: MemoryController,
+    fixture: Fixture,
+    k: usize,
+}
+
+impl Evaluator {
+    pub fn new(controller: MemoryController, fixture: Fixture, k: usize) -> Self {
+        Evaluator {
+            controller,
+            fixture,
+            k,
+        }
+    }
+
+    /// Embeds and stores the fixture memories that are not stored yet, returning how
+    /// many were added. Point the configuration at a scratch collection first.
+    pub async fn seed(&self) -> Result<usize, EvalError> {
+        let ids: Vec<_> = self
+            .fixture
+            .memories
+            .iter()
+            .map(|memory| memory.id)
+            .collect();
+        let existing = self.controller.vector_store.existing_ids(&ids).await?;
+        let pending: Vec<_> = self
+            .fixture
+            .memories
+            .iter()
+            .filter(|memory| !existing.contains(&memory.id))
+            .collect();
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let mut memories = pending
+            .iter()
+            .map(|memory| fixture_memory(memory, &self.fixture.user_id))
+            .collect::<Result<Vec<_>, MemoryError>>()?;
+        for memory in &mut memories {
+            memory.set_embedding_model(self.controller.embedding_model.model_name.as_str());
+        }
+
+        let request = EmbeddingRequest::builder()
+            .model(Arc::clone(&self.controller.embedding_model))
+            .input(
+                pending
+                    .iter()
+                    .map(|memory| memory.summary.clone())
+                    .collect(),
+            )
+            .build();
+        let embedding_response = embed(request).await?;
+
+        let vectors: Vec<&[f32]> = embedding_response
+            .embeddings
+            .iter()
+            .map(|inner| inner.as_slice())
+            .collect();
+        let payloads: Vec<&Memory> = memories.iter().collect();
+        self.controller
+            .vector_store
+            .insert(&vectors, &payloads)
+            .await?;
+
+        info!("seeded {} fixture memories", memories.len());
+        Ok(memories.len())
+    }
+
+    /// Runs every case of the fixture through multi-search with `variant` applied.
+    pub async fn run(&self, variant: &EvalVariant) -> Result<EvalReport, EvalError> {
+        let controller = self.controller_for(variant);
+        let context = MemoryContext::for_user(&self.fixture.user_id)?;
+        let mut report = EvalReport {
+            variant: variant.name.clone(),
+            cases: self.fixture.cases.len(),
+            k: self.k,
+            ..Default::default()
+        };
+
+        for case in &self.fixture.cases {
+            let options = SearchMemoryOptions::builder()
+                .embedding_model_name(variant.embedding_model.clone())
+                .build();
+
+            let start = Instant::now();
+            let results = controller
+                .multi_search_with_context_results(
+                    context.clone(),
+                    case.query.clone(),
+                    Some(options),
+                )
+                .await?;
+            report.mean_latency_ms += start.elapsed().as_secs_f64() * 1000.0;
+
+            let retrieved: Vec<_> = results
+                .memories
+                .iter()
+                .map(|memory| *memory.get_id())
+                .collect();
+            report.recall_at_k += recall_at_k(&retrieved, &case.expected, self.k);
+            report.mrr += reciprocal_rank(&retrieved, &case.expected);
+            report.rerank_skipped += results.rerank_skipped as usize;
+            report.timed_out += results.timed_out as usize;
+        }
+
+        if report.cases > 0 {
+            let cases = report.cases as f64;
+            report.recall_at_k /= cases;
+            report.mrr /= cases;
+            report.mean_latency_ms /= cases;
+        }
+
+        Ok(report)
+    }
+
+    fn controller_for(&self, variant: &EvalVariant) -> MemoryController {
+        let mut controller = self.controller.clone();
+        if let Some(model_name) = &variant.reranking_model {
+            controller.reranking_model =
+                Arc::new(controller.reranking_model.with_model_name(model_name));
+        }
+        if let Some(fusion) = &variant.fusion {
+            let mut search_config = (*controller.search_config).clone();
+            search_config.fusion = Some(fusion.clone());
+            controller.search_config = Arc::new(search_config);
+        }
+        controller
+    }
+}
+
+fn fixture_memory(memory: &FixtureMemory, user_id: &str) -> Result<Memory, MemoryError> {
+    Memory::builder()
+        .id(memory.id)
+        .content(MemoryContent::new(&memory.summary, memory.tags.clone())?)
+        .context(MemoryContext::for_user(user_id)?)
+        .kind(MemoryKind::Semantic)
+        .signals(MemorySignals::new(
+            Credence::new(1.0)?,
+            Credence::new(0.5)?,
+        )?)
+        .provenance(Provenance::direct_user())
+        .lifecycle(LifecycleState::Active)
+        .temporal(TemporalMetadata::new(Utc::now()))
+        .build()
+        .finish()
+}
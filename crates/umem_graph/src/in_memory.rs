@@ -0,0 +1,200 @@
+use crate::{scope_of, GraphStoreBase, Relation, Result};
+use async_trait::async_trait;
+use petgraph::{
+    stable_graph::{EdgeIndex, NodeIndex, StableDiGraph},
+    visit::EdgeRef,
+    Direction,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use tokio::sync::RwLock;
+use umem_core::MemoryContext;
+use uuid::Uuid;
+
+type NodeKey = (String, String);
+
+#[derive(Default)]
+struct Inner {
+    graph: StableDiGraph<NodeKey, Relation>,
+    nodes: FxHashMap<NodeKey, NodeIndex>,
+}
+
+impl Inner {
+    fn node(&mut self, scope: &str, entity: &str) -> NodeIndex {
+        let key = (scope.to_string(), entity.to_string());
+
+        if let Some(index) = self.nodes.get(&key) {
+            return *index;
+        }
+
+        let index = self.graph.add_node(key.clone());
+        self.nodes.insert(key, index);
+        index
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryGraph {
+    inner: RwLock<Inner>,
+}
+
+#[async_trait]
+impl GraphStoreBase for InMemoryGraph {
+    async fn insert(&self, relations: &[Relation]) -> Result<()> {
+        let mut inner = self.inner.write().await;
+
+        for relation in relations {
+            let subject = inner.node(relation.scope(), relation.subject());
+            let object = inner.node(relation.scope(), relation.object());
+
+            let exists = inner
+                .graph
+                .edges_connecting(subject, object)
+                .any(|edge| edge.weight() == relation);
+
+            if !exists {
+                inner.graph.add_edge(subject, object, relation.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn delete_memory(&self, memory_id: &Uuid) -> Result<()> {
+        let mut inner = self.inner.write().await;
+
+        let edges: Vec<EdgeIndex> = inner
+            .graph
+            .edge_indices()
+            .filter(|edge| inner.graph[*edge].memory_id() == memory_id)
+            .collect();
+
+        for edge in edges {
+            inner.graph.remove_edge(edge);
+        }
+
+        let orphans: Vec<NodeIndex> = inner
+            .graph
+            .node_indices()
+            .filter(|node| inner.graph.neighbors_undirected(*node).next().is_none())
+            .collect();
+
+        for node in orphans {
+            if let Some(key) = inner.graph.remove_node(node) {
+                inner.nodes.remove(&key);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn entities(&self, context: &MemoryContext) -> Result<Vec<String>> {
+        let scope = scope_of(context);
+        let inner = self.inner.read().await;
+
+        Ok(inner
+            .nodes
+            .keys()
+            .filter(|(node_scope, _)| *node_scope == scope)
+            .map(|(_, entity)| entity.clone())
+            .collect())
+    }
+
+    async fn traverse(
+        &self,
+        context: &MemoryContext,
+        entities: &[String],
+        hops: usize,
+    ) -> Result<Vec<Relation>> {
+        let scope = scope_of(context);
+        let inner = self.inner.read().await;
+
+        let mut frontier: Vec<NodeIndex> = entities
+            .iter()
+            .filter_map(|entity| {
+                inner
+                    .nodes
+                    .get(&(scope.clone(), entity.trim().to_lowercase()))
+                    .copied()
+            })
+            .collect();
+
+        let mut visited: FxHashSet<NodeIndex> = frontier.iter().copied().collect();
+        let mut seen_edges: FxHashSet<EdgeIndex> = FxHashSet::default();
+        let mut relations = Vec::new();
+
+        for _ in 0..hops {
+            let mut next = Vec::new();
+
+            for node in frontier {
+                let edges = inner
+                    .graph
+                    .edges_directed(node, Direction::Outgoing)
+                    .chain(inner.graph.edges_directed(node, Direction::Incoming));
+
+                for edge in edges {
+                    if seen_edges.insert(edge.id()) {
+                        relations.push(edge.weight().clone());
+                    }
+
+                    let neighbor = if edge.source() == node {
+                        edge.target()
+                    } else {
+                        edge.source()
+                    };
+
+                    if visited.insert(neighbor) {
+                        next.push(neighbor);
+                    }
+                }
+            }
+
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        Ok(relations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn traverse_follows_relations_across_hops() {
+        let graph = InMemoryGraph::default();
+        let context = MemoryContext::for_user("user").unwrap();
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        graph
+            .insert(&[
+                Relation::new(&context, "Alice", "works with", "Bob", first).unwrap(),
+                Relation::new(&context, "Bob", "works at", "Acme", second).unwrap(),
+            ])
+            .await
+            .unwrap();
+
+        let one_hop = graph
+            .traverse(&context, &["alice".to_string()], 1)
+            .await
+            .unwrap();
+        assert_eq!(one_hop.len(), 1);
+        assert_eq!(one_hop[0].object(), "bob");
+
+        let two_hops = graph
+            .traverse(&context, &["alice".to_string()], 2)
+            .await
+            .unwrap();
+        assert_eq!(two_hops.len(), 2);
+
+        graph.delete_memory(&second).await.unwrap();
+        assert!(!graph
+            .entities(&context)
+            .await
+            .unwrap()
+            .contains(&"acme".to_string()));
+    }
+}
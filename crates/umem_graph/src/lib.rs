@@ -0,0 +1,110 @@
+mod in_memory;
+
+pub use in_memory::*;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use thiserror::Error;
+use umem_core::MemoryContext;
+use uuid::Uuid;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    #[error("relation {field} cannot be empty or whitespace")]
+    EmptyField { field: &'static str },
+}
+
+type Result<T> = std::result::Result<T, GraphError>;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Relation {
+    scope: String,
+    subject: String,
+    predicate: String,
+    object: String,
+    memory_id: Uuid,
+}
+
+impl Relation {
+    pub fn new(
+        context: &MemoryContext,
+        subject: impl Into<String>,
+        predicate: impl Into<String>,
+        object: impl Into<String>,
+        memory_id: Uuid,
+    ) -> Result<Self> {
+        Ok(Self {
+            scope: scope_of(context),
+            subject: normalize("subject", subject.into())?,
+            predicate: normalize("predicate", predicate.into())?,
+            object: normalize("object", object.into())?,
+            memory_id,
+        })
+    }
+
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn predicate(&self) -> &str {
+        &self.predicate
+    }
+
+    pub fn object(&self) -> &str {
+        &self.object
+    }
+
+    pub fn memory_id(&self) -> &Uuid {
+        &self.memory_id
+    }
+}
+
+fn normalize(field: &'static str, value: String) -> Result<String> {
+    let value = value.trim().to_lowercase();
+
+    if value.is_empty() {
+        return Err(GraphError::EmptyField { field });
+    }
+
+    Ok(value)
+}
+
+pub fn scope_of(context: &MemoryContext) -> String {
+    format!(
+        "{}/{}/{}",
+        context.user_id().unwrap_or_default(),
+        context.agent_id().unwrap_or_default(),
+        context.run_id().unwrap_or_default()
+    )
+}
+
+#[async_trait]
+pub trait GraphStoreBase {
+    async fn insert(&self, relations: &[Relation]) -> Result<()>;
+
+    async fn delete_memory(&self, memory_id: &Uuid) -> Result<()>;
+
+    async fn entities(&self, context: &MemoryContext) -> Result<Vec<String>>;
+
+    async fn traverse(
+        &self,
+        context: &MemoryContext,
+        entities: &[String],
+        hops: usize,
+    ) -> Result<Vec<Relation>>;
+}
+
+pub struct GraphStore;
+
+impl GraphStore {
+    pub fn from_config(config: &umem_config::Graph) -> Arc<dyn GraphStoreBase + Send + Sync> {
+        match config {
+            umem_config::Graph::InMemory => Arc::new(InMemoryGraph::default()),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use tonic::{Code, Request, Response, Status};
+use umem_controller::{
+    MemoryController, MemoryControllerError, SummarizeSessionError, SummarizeSessionRequest,
+};
+use umem_proto::admin_v1::{
+    admin_service_server::AdminService, FlushCachesResponse, MetricsResponse, ReindexResponse,
+    ResetCollectionRequest, RunConsolidationRequest, RunConsolidationResponse,
+};
+
+pub struct AdminServiceImpl {
+    memory_controller: MemoryController,
+    token: String,
+}
+
+impl AdminServiceImpl {
+    pub fn new(memory_controller: MemoryController, token: String) -> Self {
+        Self {
+            memory_controller,
+            token,
+        }
+    }
+
+    /// Checks the `[admin] token` was passed as a bearer token. An empty token never
+    /// authenticates, even should the configured one be empty.
+    fn authenticate<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .filter(|token| !token.trim().is_empty())
+            .ok_or_else(|| Status::new(Code::Unauthenticated, "admin token must be passed"))?;
+
+        if !constant_time_eq(token.as_bytes(), self.token.as_bytes()) {
+            return Err(Status::new(Code::Unauthenticated, "invalid admin token"));
+        }
+        Ok(())
+    }
+
+    fn map_controller_error(e: MemoryControllerError) -> Status {
+        if let MemoryControllerError::SummarizeSessionError(
+            SummarizeSessionError::MemoryContextError(_),
+        ) = e
+        {
+            return Status::new(Code::InvalidArgument, e.to_string());
+        }
+        if let MemoryControllerError::SummarizeSessionError(
+            SummarizeSessionError::BudgetExhausted,
+        ) = e
+        {
+            return Status::new(Code::ResourceExhausted, e.to_string());
+        }
+        Status::new(Code::Internal, e.to_string())
+    }
+}
+
+/// Compares without returning early, so response times don't reveal how much of a
+/// guessed token was right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[tonic::async_trait]
+impl AdminService for AdminServiceImpl {
+    async fn reset_collection(
+        &self,
+        request: Request<ResetCollectionRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.authenticate(&request)?;
+        if !request.get_ref().confirm {
+            return Err(Status::new(
+                Code::FailedPrecondition,
+                "confirm must be set to reset the collection",
+            ));
+        }
+
+        self.memory_controller
+            .reset_collection()
+            .await
+            .map_err(Self::map_controller_error)?;
+        Ok(Response::new(()))
+    }
+
+    async fn reindex(&self, request: Request<()>) -> Result<Response<ReindexResponse>, Status> {
+        self.authenticate(&request)?;
+        let upgraded = self
+            .memory_controller
+            .reindex()
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(ReindexResponse {
+            upgraded: upgraded as u64,
+        }))
+    }
+
+    async fn run_consolidation(
+        &self,
+        request: Request<RunConsolidationRequest>,
+    ) -> Result<Response<RunConsolidationResponse>, Status> {
+        self.authenticate(&request)?;
+        let request = request.into_inner();
+        if request.run_id.trim().is_empty() {
+            return Err(Status::new(Code::InvalidArgument, "run_id must be set"));
+        }
+
+        let summary = self
+            .memory_controller
+            .summarize_session(
+                SummarizeSessionRequest::builder()
+                    .user_id(request.user_id)
+                    .agent_id(request.agent_id)
+                    .run_id(request.run_id)
+                    .build(),
+                None,
+            )
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(RunConsolidationResponse {
+            memory_id: summary.map(|memory| memory.get_id().to_string()),
+        }))
+    }
+
+    async fn dump_metrics(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<MetricsResponse>, Status> {
+        self.authenticate(&request)?;
+        let metrics = self
+            .memory_controller
+            .metrics()
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(MetricsResponse {
+            memory_count: metrics.memory_count as u64,
+            pending_journal_entries: metrics.pending_journal_entries.map(|count| count as u64),
+        }))
+    }
+
+    async fn flush_caches(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<FlushCachesResponse>, Status> {
+        self.authenticate(&request)?;
+        // NOTE: reads are served straight from the stores; the journal is the only
+        // state held back from them
+        let flushed = match self.memory_controller.journal {
+            Some(_) => self
+                .memory_controller
+                .flush_journal()
+                .await
+                .map_err(Self::map_controller_error)?,
+            None => 0,
+        };
+
+        Ok(Response::new(FlushCachesResponse {
+            flushed: flushed as u64,
+        }))
+    }
+}
@@ -1,26 +1,173 @@
-use anyhow::Result;
-use tonic::transport::Server;
-use tracing::info;
+use std::{os::unix::fs::FileTypeExt, path::Path, sync::Arc, time::Duration};
+
+use anyhow::{bail, Result};
+use tokio::net::UnixListener;
+use tonic::{
+    server::NamedService,
+    transport::{server::TcpIncoming, Server},
+};
+use tonic_health::{server::HealthReporter, ServingStatus};
+use tracing::{info, warn};
+use umem_auth::ApiKeyStore;
 use umem_controller::MemoryController;
-use umem_proto::memory_service_server::MemoryServiceServer;
+use umem_proto::{
+    admin_v1::admin_service_server::AdminServiceServer, memory_service_server::MemoryServiceServer,
+    memory_v2,
+};
 
+mod admin;
 mod service;
+mod v1;
+mod validation;
+use admin::AdminServiceImpl;
 use service::ServiceImpl;
+use v1::V1Service;
+
+const DEFAULT_HEALTH_INTERVAL_SECS: u64 = 10;
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
 
 pub struct MemoryServiceGrpc;
 
 impl MemoryServiceGrpc {
-    pub async fn run_server(config: umem_config::Grpc, controller: MemoryController) -> Result<()> {
-        let addr = config.server_addr;
-        info!("Memory gRPC Server listening on {}", addr);
+    pub async fn run_server(
+        config: umem_config::Grpc,
+        controller: MemoryController,
+        api_keys: Option<Arc<ApiKeyStore>>,
+    ) -> Result<()> {
+        let keepalive = config.keepalive.clone().unwrap_or_default();
 
         let reflection_service = tonic_reflection::server::Builder::configure()
             .register_encoded_file_descriptor_set(umem_proto::FILE_DESCRIPTOR_SET)
             .build_v1()?;
 
-        Server::builder()
+        let (health_reporter, health_service) = tonic_health::server::health_reporter();
+        tokio::spawn(report_health(
+            health_reporter,
+            controller.clone(),
+            Duration::from_secs(
+                config
+                    .health_interval_secs
+                    .unwrap_or(DEFAULT_HEALTH_INTERVAL_SECS),
+            ),
+        ));
+
+        // NOTE: memory_v1 stays served next to memory_v2 until its clients have moved
+        let service = Arc::new(ServiceImpl::new(controller, api_keys));
+
+        // NOTE: tonic drops a call's future when its deadline passes or the client
+        // goes away, which cancels the store and model calls it was waiting on
+        let mut server = Server::builder()
+            .http2_keepalive_interval(keepalive.interval_secs.map(Duration::from_secs))
+            .http2_keepalive_timeout(keepalive.timeout_secs.map(Duration::from_secs));
+        if let Some(timeout_ms) = config.request_timeout_ms {
+            server = server.timeout(Duration::from_millis(timeout_ms));
+        }
+        if let Some(max_connection_age_secs) = keepalive.max_connection_age_secs {
+            server = server.max_connection_age(Duration::from_secs(max_connection_age_secs));
+        }
+        let router = server
+            .add_service(health_service)
             .add_service(reflection_service)
-            .add_service(MemoryServiceServer::new(ServiceImpl::new(controller)))
+            .add_service(MemoryServiceServer::new(V1Service::new(Arc::clone(
+                &service,
+            ))))
+            .add_service(memory_v2::memory_service_server::MemoryServiceServer::from_arc(service));
+
+        match config.socket_path.as_deref() {
+            Some(socket_path) => {
+                let listener = bind_unix(socket_path)?;
+                info!("Memory gRPC Server listening on {}", socket_path.display());
+                let incoming = futures::stream::unfold(listener, |listener| async move {
+                    let stream = listener.accept().await.map(|(stream, _)| stream);
+                    Some((stream, listener))
+                });
+                router.serve_with_incoming(incoming).await?;
+            }
+            None => {
+                let addrs: Vec<_> = std::iter::once(config.server_addr)
+                    .chain(config.extra_addrs.iter().copied())
+                    .collect();
+                let incoming = addrs
+                    .iter()
+                    .map(|addr| {
+                        Ok(TcpIncoming::bind(*addr)?
+                            .with_nodelay(Some(true))
+                            .with_keepalive(keepalive.tcp_secs.map(Duration::from_secs)))
+                    })
+                    .collect::<std::io::Result<Vec<TcpIncoming>>>()?;
+                info!("Memory gRPC Server listening on {:?}", addrs);
+                router
+                    .serve_with_incoming(futures::stream::select_all(incoming))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Binds `path`, replacing a socket a previous run left behind but nothing else that
+/// may be there.
+fn bind_unix(path: &Path) -> std::io::Result<UnixListener> {
+    if std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        std::fs::remove_file(path)?;
+    }
+    UnixListener::bind(path)
+}
+
+/// Keeps the `grpc.health.v1` status of the server and of each memory service in line
+/// with whether the vector store and embedder answer, so load balancers stop routing to
+/// an instance that cannot serve.
+async fn report_health(reporter: HealthReporter, controller: MemoryController, every: Duration) {
+    let services = [
+        "",
+        <MemoryServiceServer<V1Service> as NamedService>::NAME,
+        <memory_v2::memory_service_server::MemoryServiceServer<ServiceImpl> as NamedService>::NAME,
+    ];
+    let mut ready = None;
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        let readiness = controller.readiness(HEALTH_CHECK_TIMEOUT, None).await;
+        if ready != Some(readiness.ready) {
+            match readiness.ready {
+                true => info!("gRPC services are serving"),
+                false => warn!("gRPC services are not serving: {:?}", readiness.checks),
+            }
+            ready = Some(readiness.ready);
+        }
+
+        let status = match readiness.ready {
+            true => ServingStatus::Serving,
+            false => ServingStatus::NotServing,
+        };
+        for service in services {
+            reporter.set_service_status(service, status).await;
+        }
+    }
+}
+
+pub struct AdminServiceGrpc;
+
+impl AdminServiceGrpc {
+    /// Serves admin_v1 on its own address, so it can be kept off the network the
+    /// memory services are exposed on. Refuses to start without an `[admin] token`.
+    pub async fn run_server(
+        config: umem_config::Admin,
+        controller: MemoryController,
+    ) -> Result<()> {
+        if config.token.trim().is_empty() {
+            bail!("[admin] token is empty; set one to serve the admin service");
+        }
+
+        let addr = config.server_addr;
+        info!("Admin gRPC Server listening on {}", addr);
+
+        Server::builder()
+            .add_service(AdminServiceServer::new(AdminServiceImpl::new(
+                controller,
+                config.token,
+            )))
             .serve(addr)
             .await?;
 
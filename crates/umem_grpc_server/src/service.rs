@@ -1,26 +1,40 @@
+use chrono::Utc;
+use std::{sync::Arc, time::Duration};
+
+use crate::validation::Validate;
 use tonic::{Code, Request, Response, Status};
-use umem_controller::MemoryController;
+use umem_auth::{Access, ApiKey, ApiKeyStore, AuthError};
+use umem_controller::{
+    BuildContextOptions, ContextPolicy, CreateMemoryError, CreateMemoryRequestError,
+    GetMemoryError, MemoryController, MemoryControllerError, ModerationError, SavedViewError,
+    SearchMemoryError, SearchMemoryOptions, SearchResults, TenancyError, UpdateMemoryError,
+};
+use umem_core::{MemoryId, MemoryScope, Recurrence};
 use umem_proto::{
-    memory_service_server::MemoryService,
-    // provenance_method,
-    ContextFilter,
-    CreateMemoryRequest,
-    DeleteMemoryRequest,
-    GetMemoryRequest,
-    ListMemoriesRequest,
-    Memory,
-    MemoryListResponse,
-    MemoryResponse,
-    SearchMemoriesRequest,
+    memory_v2::{
+        self, memory_service_server::MemoryService, ChangesResponse, ContextPackResponse,
+        CreateMemoryResponse, DeleteViewRequest, GetChangesRequest, GetMemoryStatsRequest,
+        ListViewsRequest, Memory, MemoryListResponse, MemoryResponse, MemoryStatsResponse,
+        RunViewRequest, SaveViewRequest, SavedView, UpdateMemoryRequest, ViewListResponse,
+    },
+    provenance_method, BuildContextRequest, ContextFilter, CreateMemoriesRequest,
+    CreateMemoryRequest, CreateStructuredMemoryRequest, DeleteMemoryRequest, ExplainedCandidate,
+    FacetCount, FeedbackRequest, GetMemoryRequest, ListMemoriesRequest, ListTemplatesRequest,
+    MarkUsedRequest, MemoryFacets, SearchExplanation, SearchMemoriesRequest, SearchTimings,
+    TemplateListResponse,
 };
 
 pub struct ServiceImpl {
     memory_controller: MemoryController,
+    api_keys: Option<Arc<ApiKeyStore>>,
 }
 
 impl ServiceImpl {
-    pub fn new(memory_controller: MemoryController) -> Self {
-        Self { memory_controller }
+    pub fn new(memory_controller: MemoryController, api_keys: Option<Arc<ApiKeyStore>>) -> Self {
+        Self {
+            memory_controller,
+            api_keys,
+        }
     }
 }
 
@@ -29,39 +43,27 @@ impl MemoryService for ServiceImpl {
     async fn create_memory(
         &self,
         request: Request<CreateMemoryRequest>,
-    ) -> Result<Response<()>, Status> {
-        let CreateMemoryRequest {
-            user_id,
-            raw_content,
-            agent_id,
-            run_id,
-        } = request.into_inner();
-
-        self.memory_controller
-            .create(
-                umem_controller::CreateMemoryRequest::builder()
-                    .raw_content(raw_content)
-                    .user_id(user_id)
-                    .agent_id(agent_id)
-                    .run_id(run_id)
-                    .build(),
-                None,
-            )
-            .await
-            .map_err(|e| Status::new(Code::Internal, e.to_string()))?;
+    ) -> Result<Response<CreateMemoryResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
 
-        Ok(Response::new(()))
+        self.authorize_create(&request, api_key.as_ref())?;
+        Ok(Response::new(self.create(request, api_key.as_ref()).await?))
     }
 
     async fn delete_memory(
         &self,
         request: Request<DeleteMemoryRequest>,
     ) -> Result<Response<()>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
         let request = request.into_inner();
-        self.memory_controller
-            .delete(request.id)
+
+        self.controller(api_key.as_ref())
+            .delete(Self::map_memory_id(&request.id)?)
             .await
-            .map_err(|e| Status::new(Code::Internal, e.to_string()))?;
+            .map_err(Self::map_controller_error)?;
 
         Ok(Response::new(()))
     }
@@ -70,15 +72,17 @@ impl MemoryService for ServiceImpl {
         &self,
         request: Request<GetMemoryRequest>,
     ) -> Result<Response<MemoryResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
         let request = request.into_inner();
         let memory = self
-            .memory_controller
-            .get(request.id)
+            .controller(api_key.as_ref())
+            .get(Self::map_memory_id(&request.id)?)
             .await
-            .map_err(|e| Status::new(Code::Internal, e.to_string()))?;
+            .map_err(Self::map_controller_error)?;
 
         Ok(Response::new(MemoryResponse {
-            memory: Some(Self::map_memory(memory)),
+            memory: self.present(vec![memory], api_key.as_ref()).pop(),
         }))
     }
 
@@ -86,23 +90,30 @@ impl MemoryService for ServiceImpl {
         &self,
         request: Request<ListMemoriesRequest>,
     ) -> Result<Response<MemoryListResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
         let request = request.into_inner();
 
-        if request.context.is_none() {
-            return Err(Status::new(Code::InvalidArgument, "context must be passed"));
-        }
+        let context = Self::map_context(request.context.unwrap_or_default())
+            .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        Self::authorize(api_key.as_ref(), &context, Access::Read)?;
 
+        let facets = self
+            .facets_for(&context, request.include_facets, api_key.as_ref())
+            .await?;
         let memories = self
-            .memory_controller
-            .list_with_context(
-                Self::map_context(request.context.unwrap())
-                    .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?,
-            )
+            .controller(api_key.as_ref())
+            .list_with_context(context)
             .await
-            .map_err(|e| Status::new(Code::Internal, e.to_string()))?;
+            .map_err(Self::map_controller_error)?;
 
         Ok(Response::new(MemoryListResponse {
-            memories: memories.into_iter().map(Self::map_memory).collect(),
+            memories: self.present(memories, api_key.as_ref()),
+            facets,
+            rerank_skipped: false,
+            timed_out: false,
+            timings: None,
+            explanation: None,
         }))
     }
 
@@ -110,42 +121,741 @@ impl MemoryService for ServiceImpl {
         &self,
         request: Request<SearchMemoriesRequest>,
     ) -> Result<Response<MemoryListResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let timeout = Self::timeout(&request);
         let request = request.into_inner();
 
-        if request.context.is_none() {
-            return Err(Status::new(Code::InvalidArgument, "context must be passed"));
+        let context = Self::map_context(request.context.unwrap_or_default())
+            .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        Self::authorize(api_key.as_ref(), &context, Access::Read)?;
+
+        let facets = self
+            .facets_for(&context, request.include_facets, api_key.as_ref())
+            .await?;
+        let results = self
+            .controller(api_key.as_ref())
+            .multi_search_with_context_results(
+                context,
+                request.query,
+                Some(
+                    SearchMemoryOptions::builder()
+                        .timeout(timeout)
+                        .explain(request.explain)
+                        .build(),
+                ),
+            )
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(self.map_results(
+            results,
+            facets,
+            api_key.as_ref(),
+        )))
+    }
+
+    async fn build_context(
+        &self,
+        request: Request<BuildContextRequest>,
+    ) -> Result<Response<ContextPackResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
+
+        let policy = match umem_proto::ContextPolicy::try_from(request.policy) {
+            Ok(umem_proto::ContextPolicy::Recency) => ContextPolicy::Recency,
+            Ok(umem_proto::ContextPolicy::Importance) => ContextPolicy::Importance,
+            _ => ContextPolicy::Relevance,
+        };
+
+        let context = Self::map_context(request.context.unwrap_or_default())
+            .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        Self::authorize(api_key.as_ref(), &context, Access::Read)?;
+
+        let pack = self
+            .controller(api_key.as_ref())
+            .build_context(
+                context,
+                request.query,
+                request.token_budget as usize,
+                Some(BuildContextOptions::builder().policy(policy).build()),
+            )
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(ContextPackResponse {
+            text: pack.text,
+            tokens: pack.tokens as u32,
+            memories: self.present(pack.memories, api_key.as_ref()),
+        }))
+    }
+
+    async fn mark_used(&self, request: Request<MarkUsedRequest>) -> Result<Response<()>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
+
+        let ids = request
+            .ids
+            .iter()
+            .map(|id| Self::map_memory_id(id))
+            .collect::<Result<_, _>>()?;
+        self.controller(api_key.as_ref())
+            .mark_used(ids, Utc::now())
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn feedback(&self, request: Request<FeedbackRequest>) -> Result<Response<()>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
+
+        self.controller(api_key.as_ref())
+            .feedback(
+                Self::map_memory_id(&request.memory_id)?,
+                request.query,
+                request.helpful,
+            )
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn create_memories(
+        &self,
+        request: Request<CreateMemoriesRequest>,
+    ) -> Result<Response<CreateMemoryResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let requests = request.into_inner().requests;
+
+        // NOTE: every request is authorized before the first is created, so a
+        // forbidden context does not leave the batch half stored
+        for request in &requests {
+            self.authorize_create(request, api_key.as_ref())?;
+        }
+
+        let mut response = CreateMemoryResponse::default();
+        for request in requests {
+            let created = self.create(request, api_key.as_ref()).await?;
+            response.memories.extend(created.memories);
+            response.queued |= created.queued;
         }
 
+        Ok(Response::new(response))
+    }
+
+    async fn create_structured_memory(
+        &self,
+        request: Request<CreateStructuredMemoryRequest>,
+    ) -> Result<Response<CreateMemoryResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
+
+        let context = umem_core::MemoryContext::new(
+            request.user_id.clone(),
+            request.agent_id.clone(),
+            request.run_id.clone(),
+        )
+        .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        Self::authorize(api_key.as_ref(), &context, Access::ReadWrite)?;
+
+        let request = umem_controller::CreateStructuredMemoryRequest::builder()
+            .user_id(request.user_id)
+            .agent_id(request.agent_id)
+            .run_id(request.run_id)
+            .summary(request.summary)
+            .tags(request.tags)
+            .kind(Self::parse_kind(request.kind))
+            .fields(request.fields.into_iter().collect())
+            .build();
         let memories = self
+            .controller(api_key.as_ref())
+            .create_structured(vec![request], None)
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(CreateMemoryResponse {
+            memories: self.present(memories, api_key.as_ref()),
+            queued: false,
+        }))
+    }
+
+    async fn list_templates(
+        &self,
+        _request: Request<ListTemplatesRequest>,
+    ) -> Result<Response<TemplateListResponse>, Status> {
+        let templates = self
             .memory_controller
-            .multi_search_with_context(
-                Self::map_context(request.context.unwrap())
-                    .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?,
-                request.query,
-                None,
+            .templates
+            .all()
+            .iter()
+            .map(|template| umem_proto::MemoryTemplate {
+                kind: Self::map_kind(&template.kind) as i32,
+                fields: template
+                    .fields
+                    .iter()
+                    .map(|field| umem_proto::TemplateField {
+                        name: field.name.clone(),
+                        required: field.required,
+                        description: field.description.clone(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Ok(Response::new(TemplateListResponse { templates }))
+    }
+
+    async fn get_memory_stats(
+        &self,
+        request: Request<GetMemoryStatsRequest>,
+    ) -> Result<Response<MemoryStatsResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
+
+        let context = Self::map_context(request.context.unwrap_or_default())
+            .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        Self::authorize(api_key.as_ref(), &context, Access::Read)?;
+
+        let stats = self
+            .controller(api_key.as_ref())
+            .stats(context)
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(Self::map_stats(stats)))
+    }
+
+    async fn save_view(
+        &self,
+        request: Request<SaveViewRequest>,
+    ) -> Result<Response<SavedView>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
+
+        let context = Self::map_context(request.context.unwrap_or_default())
+            .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        Self::authorize(api_key.as_ref(), &context, Access::ReadWrite)?;
+
+        let view = self
+            .controller(api_key.as_ref())
+            .save_view(context, request.name, request.query)
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(Self::map_view(view)))
+    }
+
+    async fn list_views(
+        &self,
+        request: Request<ListViewsRequest>,
+    ) -> Result<Response<ViewListResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
+
+        let context = Self::map_context(request.context.unwrap_or_default())
+            .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        Self::authorize(api_key.as_ref(), &context, Access::Read)?;
+
+        let views = self
+            .controller(api_key.as_ref())
+            .list_views(context)
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(ViewListResponse {
+            views: views.into_iter().map(Self::map_view).collect(),
+        }))
+    }
+
+    async fn run_view(
+        &self,
+        request: Request<RunViewRequest>,
+    ) -> Result<Response<MemoryListResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let timeout = Self::timeout(&request);
+        let request = request.into_inner();
+
+        let context = Self::map_context(request.context.unwrap_or_default())
+            .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        Self::authorize(api_key.as_ref(), &context, Access::Read)?;
+
+        let results = self
+            .controller(api_key.as_ref())
+            .run_view(
+                context,
+                &request.name,
+                Some(
+                    SearchMemoryOptions::builder()
+                        .timeout(timeout)
+                        .explain(request.explain)
+                        .build(),
+                ),
             )
             .await
-            .map_err(|e| Status::new(Code::Internal, e.to_string()))?;
+            .map_err(Self::map_controller_error)?;
 
-        Ok(Response::new(MemoryListResponse {
-            memories: memories.into_iter().map(Self::map_memory).collect(),
+        Ok(Response::new(self.map_results(
+            results,
+            None,
+            api_key.as_ref(),
+        )))
+    }
+
+    async fn delete_view(
+        &self,
+        request: Request<DeleteViewRequest>,
+    ) -> Result<Response<()>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
+
+        let context = Self::map_context(request.context.unwrap_or_default())
+            .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        Self::authorize(api_key.as_ref(), &context, Access::ReadWrite)?;
+
+        let deleted = self
+            .controller(api_key.as_ref())
+            .delete_view(context, &request.name)
+            .await
+            .map_err(Self::map_controller_error)?;
+        if !deleted {
+            return Err(Status::new(
+                Code::NotFound,
+                format!("no view named {}", request.name),
+            ));
+        }
+
+        Ok(Response::new(()))
+    }
+
+    async fn get_changes(
+        &self,
+        request: Request<GetChangesRequest>,
+    ) -> Result<Response<ChangesResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
+
+        let context = Self::map_context(request.context.unwrap_or_default())
+            .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        Self::authorize(api_key.as_ref(), &context, Access::Read)?;
+
+        let changes = self
+            .controller(api_key.as_ref())
+            .changes(context, request.since)
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(ChangesResponse {
+            memories: self.present(changes.memories, api_key.as_ref()),
+            until: changes.until,
+        }))
+    }
+
+    async fn update_memory(
+        &self,
+        request: Request<UpdateMemoryRequest>,
+    ) -> Result<Response<MemoryResponse>, Status> {
+        let api_key = self.api_key(&request).await?;
+        request.get_ref().validate()?;
+        let request = request.into_inner();
+
+        let memory = self
+            .controller(api_key.as_ref())
+            .edit(
+                Self::map_memory_id(&request.id)?,
+                request.summary,
+                request.expected_version,
+            )
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Response::new(MemoryResponse {
+            memory: self.present(vec![memory], api_key.as_ref()).pop(),
         }))
     }
 }
 
 impl ServiceImpl {
+    fn authorize_create(
+        &self,
+        request: &CreateMemoryRequest,
+        api_key: Option<&ApiKey>,
+    ) -> Result<(), Status> {
+        let Some(api_key) = api_key else {
+            return Ok(());
+        };
+        let context = umem_core::MemoryContext::new(
+            request.user_id.clone(),
+            request.agent_id.clone(),
+            request.run_id.clone(),
+        )
+        .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+        api_key
+            .authorize(&context, Access::ReadWrite)
+            .map_err(Self::map_auth_error)
+    }
+
+    /// Creates the memories for one request, or journals it when a journal is
+    /// configured.
+    async fn create(
+        &self,
+        request: CreateMemoryRequest,
+        api_key: Option<&ApiKey>,
+    ) -> Result<CreateMemoryResponse, Status> {
+        let CreateMemoryRequest {
+            user_id,
+            raw_content,
+            agent_id,
+            run_id,
+        } = request;
+
+        let controller = self.controller(api_key);
+        let request = umem_controller::CreateMemoryRequest::builder()
+            .raw_content(raw_content)
+            .user_id(user_id)
+            .agent_id(agent_id)
+            .run_id(run_id)
+            .build();
+
+        // NOTE: with a journal the memory is created by the next flush, so clients can
+        // keep recording while the vector store is down
+        if controller.journal.is_some() {
+            controller
+                .enqueue_create(request)
+                .await
+                .map_err(Self::map_controller_error)?;
+            return Ok(CreateMemoryResponse {
+                memories: Vec::new(),
+                queued: true,
+            });
+        }
+
+        let memories = controller
+            .create(request, None)
+            .await
+            .map_err(Self::map_controller_error)?;
+        Ok(CreateMemoryResponse {
+            memories: self.present(memories, api_key),
+            queued: false,
+        })
+    }
+
+    /// Resolves the `authorization: Bearer <key>` metadata. Returns `None` when api keys
+    /// are not configured, in which case every request is allowed.
+    async fn api_key<T>(&self, request: &Request<T>) -> Result<Option<ApiKey>, Status> {
+        let Some(api_keys) = self.api_keys.as_ref() else {
+            return Ok(None);
+        };
+
+        let secret = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::new(Code::Unauthenticated, "api key must be passed"))?;
+
+        let api_key = api_keys
+            .verify(secret)
+            .await
+            .map_err(Self::map_auth_error)?;
+        Ok(Some(api_key))
+    }
+
+    /// The client's deadline, sent as `grpc-timeout` metadata such as `500m` (an amount
+    /// followed by one of the units H, M, S, m, u and n).
+    fn timeout<T>(request: &Request<T>) -> Option<Duration> {
+        let value = request.metadata().get("grpc-timeout")?.to_str().ok()?;
+        let (amount, unit) = value.split_at(value.len().checked_sub(1)?);
+        let amount: u64 = amount.parse().ok()?;
+
+        match unit {
+            "H" => Some(Duration::from_secs(amount * 3600)),
+            "M" => Some(Duration::from_secs(amount * 60)),
+            "S" => Some(Duration::from_secs(amount)),
+            "m" => Some(Duration::from_millis(amount)),
+            "u" => Some(Duration::from_micros(amount)),
+            "n" => Some(Duration::from_nanos(amount)),
+            _ => None,
+        }
+    }
+
+    fn authorize(
+        api_key: Option<&ApiKey>,
+        context: &umem_core::MemoryContext,
+        access: Access,
+    ) -> Result<(), Status> {
+        match api_key {
+            Some(api_key) => api_key
+                .authorize(context, access)
+                .map_err(Self::map_auth_error),
+            None => Ok(()),
+        }
+    }
+
+    /// The controller scoped to the caller, so it re-checks every context it touches.
+    fn controller(&self, api_key: Option<&ApiKey>) -> MemoryController {
+        self.memory_controller.for_principal(api_key)
+    }
+
+    /// Applies the caller's response policy and converts for the wire.
+    fn present(
+        &self,
+        mut memories: Vec<umem_core::Memory>,
+        api_key: Option<&ApiKey>,
+    ) -> Vec<Memory> {
+        self.memory_controller
+            .redact(&mut memories, api_key.map(|api_key| api_key.access));
+        memories.into_iter().map(Self::map_memory).collect()
+    }
+
+    fn map_memory_id(id: &str) -> Result<MemoryId, Status> {
+        MemoryId::parse(id).map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))
+    }
+
+    /// Reports the controller's tenancy checks like the server's own api key checks.
+    fn map_controller_error(e: MemoryControllerError) -> Status {
+        if let MemoryControllerError::GetMemoryError(GetMemoryError::Deleted(_)) = e {
+            return Status::new(Code::NotFound, e.to_string());
+        }
+        if let MemoryControllerError::CreateMemoryError(
+            CreateMemoryError::MemoryTemplateError(_)
+            | CreateMemoryError::ModerationError(ModerationError::Rejected(_))
+            | CreateMemoryError::CreateMemoryRequestError(
+                CreateMemoryRequestError::MemoryScheduleError(_),
+            ),
+        ) = e
+        {
+            return Status::new(Code::InvalidArgument, e.to_string());
+        }
+        match &e {
+            MemoryControllerError::UpdateMemoryError(UpdateMemoryError::VersionConflict {
+                ..
+            }) => return Status::new(Code::Aborted, e.to_string()),
+            MemoryControllerError::UpdateMemoryError(
+                UpdateMemoryError::MemoryError(_)
+                | UpdateMemoryError::ModerationError(ModerationError::Rejected(_)),
+            ) => return Status::new(Code::InvalidArgument, e.to_string()),
+            _ => {}
+        }
+        if let MemoryControllerError::SavedViewError(error) = &e {
+            match error {
+                SavedViewError::NotFound(_) => return Status::new(Code::NotFound, e.to_string()),
+                SavedViewError::NotConfigured => {
+                    return Status::new(Code::FailedPrecondition, e.to_string())
+                }
+                SavedViewError::InvalidName
+                | SavedViewError::QuerySyntaxError(_)
+                | SavedViewError::SearchMemoryError(SearchMemoryError::QueryError(_)) => {
+                    return Status::new(Code::InvalidArgument, e.to_string())
+                }
+                _ => {}
+            }
+        }
+        let mut source: Option<&(dyn std::error::Error + 'static)> = Some(&e);
+        while let Some(error) = source {
+            match error.downcast_ref::<TenancyError>() {
+                Some(TenancyError::AuthError(_) | TenancyError::CrossTenant) => {
+                    return Status::new(Code::PermissionDenied, e.to_string());
+                }
+                Some(TenancyError::MemoryContextError(_)) => {
+                    return Status::new(Code::InvalidArgument, e.to_string());
+                }
+                _ => source = error.source(),
+            }
+        }
+        Status::new(Code::Internal, e.to_string())
+    }
+
+    fn map_auth_error(e: AuthError) -> Status {
+        let code = match e {
+            AuthError::InvalidKey => Code::Unauthenticated,
+            AuthError::ReadOnly | AuthError::OutOfScope { .. } => Code::PermissionDenied,
+            _ => Code::Internal,
+        };
+        Status::new(code, e.to_string())
+    }
+
+    async fn facets_for(
+        &self,
+        context: &umem_core::MemoryContext,
+        include_facets: bool,
+        api_key: Option<&ApiKey>,
+    ) -> Result<Option<MemoryFacets>, Status> {
+        if !include_facets {
+            return Ok(None);
+        }
+
+        let facets = self
+            .controller(api_key)
+            .facets_with_context(context.clone())
+            .await
+            .map_err(Self::map_controller_error)?;
+
+        Ok(Some(Self::map_facets(facets)))
+    }
+
+    fn map_counts(counts: Vec<umem_core::FacetCount>) -> Vec<FacetCount> {
+        counts
+            .into_iter()
+            .map(|count| FacetCount {
+                value: count.value,
+                count: count.count,
+            })
+            .collect()
+    }
+
+    fn map_explanation(explanation: umem_controller::SearchExplanation) -> SearchExplanation {
+        SearchExplanation {
+            sub_queries: explanation.sub_queries,
+            filter: explanation.filter,
+            candidates: explanation
+                .candidates
+                .into_iter()
+                .map(|candidate| ExplainedCandidate {
+                    memory_id: candidate.memory_id,
+                    sub_query: candidate.sub_query as u32,
+                    vector_score: candidate.vector_score,
+                    rerank_score: candidate.rerank_score,
+                    fused_score: candidate.fused_score,
+                    rank: candidate.rank.map(|rank| rank as u32),
+                })
+                .collect(),
+        }
+    }
+
+    fn map_facets(facets: umem_core::MemoryFacets) -> MemoryFacets {
+        MemoryFacets {
+            kinds: Self::map_counts(facets.kinds),
+            tags: Self::map_counts(facets.tags),
+            months: Self::map_counts(facets.months),
+            topics: Self::map_counts(facets.topics),
+        }
+    }
+
+    /// Converts search results, applying the caller's response policy to the memories.
+    fn map_results(
+        &self,
+        results: SearchResults,
+        facets: Option<MemoryFacets>,
+        api_key: Option<&ApiKey>,
+    ) -> MemoryListResponse {
+        MemoryListResponse {
+            memories: self.present(results.memories, api_key),
+            facets,
+            rerank_skipped: results.rerank_skipped,
+            timed_out: results.timed_out,
+            timings: Some(SearchTimings {
+                embed_micros: results.timings.embed.as_micros() as u64,
+                store_micros: results.timings.store.as_micros() as u64,
+                rerank_micros: results.timings.rerank.as_micros() as u64,
+            }),
+            explanation: results.explanation.map(Self::map_explanation),
+        }
+    }
+
+    fn map_view(view: umem_controller::SavedView) -> SavedView {
+        SavedView {
+            name: view.name,
+            query: view.query,
+            created_at: view.created_at,
+            updated_at: view.updated_at,
+        }
+    }
+
+    fn map_stats(stats: umem_controller::MemoryStats) -> MemoryStatsResponse {
+        MemoryStatsResponse {
+            total: stats.total,
+            kinds: Self::map_counts(stats.kinds),
+            lifecycles: Self::map_counts(stats.lifecycles),
+            storage_bytes: stats.storage_bytes,
+            months: Self::map_counts(stats.months),
+            last_activity_at: stats.last_activity_at,
+        }
+    }
+
+    /// A filter with only an org_id names the organization pool; otherwise the pool is
+    /// matched next to the personal context.
     fn map_context(
         context: ContextFilter,
     ) -> Result<umem_core::MemoryContext, umem_core::MemoryContextError> {
-        umem_core::MemoryContext::new(context.user_id, context.agent_id, context.run_id)
+        if context.user_id.is_none() && context.agent_id.is_none() && context.run_id.is_none() {
+            if let Some(org_id) = context.org_id {
+                return umem_core::MemoryContext::for_org(org_id);
+            }
+        }
+        umem_core::MemoryContext::new(context.user_id, context.agent_id, context.run_id)?
+            .with_org(context.org_id)
+    }
+
+    fn map_kind(kind: &umem_core::MemoryKind) -> umem_proto::MemoryKind {
+        match kind {
+            umem_core::MemoryKind::Semantic => umem_proto::MemoryKind::Semantic,
+            umem_core::MemoryKind::Episodic => umem_proto::MemoryKind::Episodic,
+            umem_core::MemoryKind::Procedural => umem_proto::MemoryKind::Procedural,
+            umem_core::MemoryKind::Instruction => umem_proto::MemoryKind::Instruction,
+            umem_core::MemoryKind::Relational => umem_proto::MemoryKind::Relational,
+            umem_core::MemoryKind::Working => umem_proto::MemoryKind::Working,
+            umem_core::MemoryKind::Prospective => umem_proto::MemoryKind::Prospective,
+        }
+    }
+
+    /// Validation rejects an unset kind, so the default is never reached from a request.
+    fn parse_kind(kind: i32) -> umem_core::MemoryKind {
+        match umem_proto::MemoryKind::try_from(kind) {
+            Ok(umem_proto::MemoryKind::Episodic) => umem_core::MemoryKind::Episodic,
+            Ok(umem_proto::MemoryKind::Procedural) => umem_core::MemoryKind::Procedural,
+            Ok(umem_proto::MemoryKind::Instruction) => umem_core::MemoryKind::Instruction,
+            Ok(umem_proto::MemoryKind::Relational) => umem_core::MemoryKind::Relational,
+            Ok(umem_proto::MemoryKind::Working) => umem_core::MemoryKind::Working,
+            Ok(umem_proto::MemoryKind::Prospective) => umem_core::MemoryKind::Prospective,
+            _ => umem_core::MemoryKind::Semantic,
+        }
+    }
+
+    fn map_provenance_method(method: &umem_core::ProvenanceMethod) -> umem_proto::ProvenanceMethod {
+        umem_proto::ProvenanceMethod {
+            method: Some(match method {
+                umem_core::ProvenanceMethod::Direct => provenance_method::Method::Direct(true),
+                umem_core::ProvenanceMethod::Extracted { model, prompt } => {
+                    provenance_method::Method::Extracted(umem_proto::ExtractedMethod {
+                        model: model.clone(),
+                        prompt: prompt.clone(),
+                    })
+                }
+                umem_core::ProvenanceMethod::Summarized { model } => {
+                    provenance_method::Method::Summarized(umem_proto::SummarizedMethod {
+                        model: model.clone(),
+                    })
+                }
+                umem_core::ProvenanceMethod::Imported { source } => {
+                    provenance_method::Method::Imported(umem_proto::ImportedMethod {
+                        source: source.clone(),
+                    })
+                }
+            }),
+        }
     }
 
     fn map_memory(memory: umem_core::Memory) -> Memory {
         let context = memory.context();
         let content = memory.content();
-        // let signals = memory.signals();
+        let signals = memory.signals();
         let temporal = memory.temporal();
-        // let provenance = memory.provenance();
+        let provenance = memory.provenance();
+        let language = memory.language();
 
         Memory {
             id: memory.get_id().to_string(),
@@ -153,69 +863,99 @@ impl ServiceImpl {
                 user_id: context.user_id().map(|s| s.to_string()),
                 agent_id: context.agent_id().map(|s| s.to_string()),
                 run_id: context.run_id().map(|s| s.to_string()),
+                org_id: context.org_id().map(|s| s.to_string()),
             }),
             lifecycle: match memory.lifecycle() {
                 umem_core::LifecycleState::Active => umem_proto::LifecycleState::Active as i32,
                 umem_core::LifecycleState::Archived => umem_proto::LifecycleState::Archived as i32,
+                umem_core::LifecycleState::Deleted => umem_proto::LifecycleState::Deleted as i32,
+                umem_core::LifecycleState::Superseded => {
+                    umem_proto::LifecycleState::Superseded as i32
+                }
+                umem_core::LifecycleState::Suppressed => {
+                    umem_proto::LifecycleState::Suppressed as i32
+                }
             },
-            kind: match memory.kind() {
-                umem_core::MemoryKind::Semantic => umem_proto::MemoryKind::Semantic as i32,
-                umem_core::MemoryKind::Episodic => umem_proto::MemoryKind::Episodic as i32,
-                umem_core::MemoryKind::Procedural => umem_proto::MemoryKind::Procedural as i32,
-                umem_core::MemoryKind::Instruction => umem_proto::MemoryKind::Instruction as i32,
-                umem_core::MemoryKind::Relational => umem_proto::MemoryKind::Relational as i32,
-                umem_core::MemoryKind::Working => umem_proto::MemoryKind::Working as i32,
-                umem_core::MemoryKind::Prospective => umem_proto::MemoryKind::Prospective as i32,
-            },
+            kind: Self::map_kind(memory.kind()) as i32,
             content: Some(umem_proto::MemoryContent {
                 summary: content.summary().clone(),
                 tags: content.tags().clone(),
             }),
-            signals: Some(umem_proto::MemorySignals {
-                // certainty: signals.get_certainty() as u32,
-                // salience: signals.get_salience() as u32,
-                certainty: 0,
-                salience: 0,
-            }),
-            temporal: Some(umem_proto::TemporalMetadata {
+            temporal: Some(memory_v2::TemporalMetadata {
                 created_at: temporal.created_at(),
                 updated_at: temporal.updated_at(),
                 archived_at: temporal.archived_at(),
+                deleted_at: temporal.deleted_at(),
+                suppressed_at: temporal.suppressed_at(),
             }),
             provenance: Some(umem_proto::Provenance {
-                origin: umem_proto::ProvenanceOrigin::User as i32,
-                method: Some(umem_proto::ProvenanceMethod {
-                    method: Some(umem_proto::provenance_method::Method::Direct(true)),
+                origin: match &provenance.origin {
+                    umem_core::ProvenanceOrigin::User => umem_proto::ProvenanceOrigin::User as i32,
+                    umem_core::ProvenanceOrigin::Agent => {
+                        umem_proto::ProvenanceOrigin::Agent as i32
+                    }
+                },
+                method: Some(Self::map_provenance_method(provenance.method())),
+                chain: provenance
+                    .chain
+                    .iter()
+                    .map(Self::map_provenance_method)
+                    .collect(),
+                moderation: provenance.moderation.as_ref().map(|moderation| {
+                    umem_proto::Moderation {
+                        moderator: moderation.moderator.clone(),
+                        flagged: moderation.flagged.clone(),
+                    }
                 }),
-                // origin: match &provenance.origin {
-                //     umem_core::ProvenanceOrigin::User => umem_proto::ProvenanceOrigin::User as i32,
-                //     umem_core::ProvenanceOrigin::Agent => {
-                //         umem_proto::ProvenanceOrigin::Agent as i32
-                //     }
-                // },
-                // method: Some(umem_proto::ProvenanceMethod {
-                //     method: Some(match &provenance.method {
-                //         umem_core::ProvenanceMethod::Direct => {
-                //             umem_proto::provenance_method::Method::Direct(true)
-                //         }
-                //         umem_core::ProvenanceMethod::Extracted { model, prompt } => {
-                //             umem_proto::provenance_method::Method::Extracted(
-                //                 umem_proto::ExtractedMethod {
-                //                     model: model.clone(),
-                //                     prompt: prompt.clone(),
-                //                 },
-                //             )
-                //         }
-                //         umem_core::ProvenanceMethod::Summarized { model } => {
-                //             umem_proto::provenance_method::Method::Summarized(
-                //                 umem_proto::SummarizedMethod {
-                //                     model: model.clone(),
-                //                 },
-                //             )
-                //         }
-                //     }),
-                // }),
             }),
+            metadata: Some(memory_v2::MemoryMetadata {
+                language: language.map(|language| language.language().to_string()),
+                original_summary: language
+                    .and_then(|language| language.original_summary())
+                    .map(|summary| summary.to_string()),
+                sources: memory.sources().to_vec(),
+                schedule: memory.schedule().map(|schedule| memory_v2::MemorySchedule {
+                    due_at: schedule.due_at(),
+                    recurrence: match schedule.recurrence() {
+                        None => memory_v2::Recurrence::Unspecified as i32,
+                        Some(Recurrence::Daily) => memory_v2::Recurrence::Daily as i32,
+                        Some(Recurrence::Weekly) => memory_v2::Recurrence::Weekly as i32,
+                        Some(Recurrence::Monthly) => memory_v2::Recurrence::Monthly as i32,
+                        Some(Recurrence::Yearly) => memory_v2::Recurrence::Yearly as i32,
+                    },
+                }),
+                use_count: memory.usage().use_count(),
+                last_used_at: memory.usage().last_used_at(),
+                fields: memory
+                    .fields()
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect(),
+            }),
+            relations: memory
+                .contradicts()
+                .iter()
+                .map(|id| (memory_v2::RelationKind::Contradicts, id))
+                .chain(
+                    memory
+                        .superseded_by()
+                        .map(|id| (memory_v2::RelationKind::SupersededBy, id)),
+                )
+                .map(|(kind, id)| memory_v2::MemoryRelation {
+                    kind: kind as i32,
+                    memory_id: id.to_string(),
+                })
+                .collect(),
+            scores: Some(memory_v2::MemoryScores {
+                certainty: signals.get_certainty(),
+                salience: signals.get_salience(),
+                score: memory.score(),
+            }),
+            scope: match memory.scope() {
+                MemoryScope::Personal => memory_v2::MemoryScope::Personal as i32,
+                MemoryScope::Org => memory_v2::MemoryScope::Org as i32,
+            },
+            version: memory.version(),
         }
     }
 }
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use crate::service::ServiceImpl;
+use tonic::{Request, Response, Status};
+use umem_proto::{
+    memory_service_server::MemoryService, memory_v2::memory_service_server::MemoryService as _,
+    BuildContextRequest, ContextPackResponse, CreateMemoriesRequest, CreateMemoryRequest,
+    CreateMemoryResponse, CreateStructuredMemoryRequest, DeleteMemoryRequest, FeedbackRequest,
+    GetMemoryRequest, ListMemoriesRequest, ListTemplatesRequest, MarkUsedRequest,
+    MemoryListResponse, MemoryResponse, SearchMemoriesRequest, TemplateListResponse,
+};
+
+/// Serves memory_v1 by running each call through the memory_v2 service and
+/// downgrading its response. Requests are the same messages in both versions.
+pub struct V1Service {
+    inner: Arc<ServiceImpl>,
+}
+
+impl V1Service {
+    pub fn new(inner: Arc<ServiceImpl>) -> Self {
+        Self { inner }
+    }
+}
+
+#[tonic::async_trait]
+impl MemoryService for V1Service {
+    async fn create_memory(
+        &self,
+        request: Request<CreateMemoryRequest>,
+    ) -> Result<Response<CreateMemoryResponse>, Status> {
+        Ok(self.inner.create_memory(request).await?.map(Into::into))
+    }
+
+    async fn delete_memory(
+        &self,
+        request: Request<DeleteMemoryRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.inner.delete_memory(request).await
+    }
+
+    async fn get_memory(
+        &self,
+        request: Request<GetMemoryRequest>,
+    ) -> Result<Response<MemoryResponse>, Status> {
+        Ok(self.inner.get_memory(request).await?.map(Into::into))
+    }
+
+    async fn list_memories(
+        &self,
+        request: Request<ListMemoriesRequest>,
+    ) -> Result<Response<MemoryListResponse>, Status> {
+        Ok(self.inner.list_memories(request).await?.map(Into::into))
+    }
+
+    async fn search_memories(
+        &self,
+        request: Request<SearchMemoriesRequest>,
+    ) -> Result<Response<MemoryListResponse>, Status> {
+        Ok(self.inner.search_memories(request).await?.map(Into::into))
+    }
+
+    async fn build_context(
+        &self,
+        request: Request<BuildContextRequest>,
+    ) -> Result<Response<ContextPackResponse>, Status> {
+        Ok(self.inner.build_context(request).await?.map(Into::into))
+    }
+
+    async fn mark_used(&self, request: Request<MarkUsedRequest>) -> Result<Response<()>, Status> {
+        self.inner.mark_used(request).await
+    }
+
+    async fn feedback(&self, request: Request<FeedbackRequest>) -> Result<Response<()>, Status> {
+        self.inner.feedback(request).await
+    }
+
+    async fn create_memories(
+        &self,
+        request: Request<CreateMemoriesRequest>,
+    ) -> Result<Response<CreateMemoryResponse>, Status> {
+        Ok(self.inner.create_memories(request).await?.map(Into::into))
+    }
+
+    async fn create_structured_memory(
+        &self,
+        request: Request<CreateStructuredMemoryRequest>,
+    ) -> Result<Response<CreateMemoryResponse>, Status> {
+        Ok(self
+            .inner
+            .create_structured_memory(request)
+            .await?
+            .map(Into::into))
+    }
+
+    async fn list_templates(
+        &self,
+        request: Request<ListTemplatesRequest>,
+    ) -> Result<Response<TemplateListResponse>, Status> {
+        self.inner.list_templates(request).await
+    }
+}
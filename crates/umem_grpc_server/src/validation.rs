@@ -0,0 +1,285 @@
+use tonic::{Code, Status};
+use umem_proto::{
+    memory_v2::{
+        DeleteViewRequest, GetChangesRequest, GetMemoryStatsRequest, ListViewsRequest,
+        RunViewRequest, SaveViewRequest, UpdateMemoryRequest,
+    },
+    BuildContextRequest, ContextFilter, CreateMemoriesRequest, CreateMemoryRequest,
+    CreateStructuredMemoryRequest, DeleteMemoryRequest, FeedbackRequest, GetMemoryRequest,
+    ListMemoriesRequest, MarkUsedRequest, MemoryKind, SearchMemoriesRequest,
+};
+use uuid::Uuid;
+
+const MAX_RAW_CONTENT_CHARS: usize = 32_000;
+const MAX_SUMMARY_CHARS: usize = 4_000;
+const MAX_QUERY_CHARS: usize = 2_000;
+const MAX_ID_CHARS: usize = 256;
+const MAX_TOKEN_BUDGET: u32 = 200_000;
+const MAX_MARK_USED_IDS: usize = 256;
+const MAX_CREATE_BATCH: usize = 100;
+const MAX_VIEW_NAME_CHARS: usize = 100;
+
+/// Problems found in one request, each tied to the field it is about.
+#[derive(Debug, Default)]
+pub(crate) struct Violations(Vec<(String, String)>);
+
+impl Violations {
+    fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.0.push((field.into(), message.into()));
+    }
+
+    fn text(&mut self, field: &str, value: &str, max_chars: usize) {
+        if value.trim().is_empty() {
+            self.add(field, "must not be empty");
+        } else if value.chars().count() > max_chars {
+            self.add(field, format!("must be at most {max_chars} characters"));
+        }
+    }
+
+    fn uuid(&mut self, field: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.add(field, "must not be empty");
+        } else if Uuid::parse_str(value).is_err() {
+            self.add(field, "must be a uuid");
+        }
+    }
+
+    fn context_ids(
+        &mut self,
+        field: &str,
+        user_id: Option<&str>,
+        agent_id: Option<&str>,
+        run_id: Option<&str>,
+    ) {
+        let ids = [
+            ("user_id", user_id),
+            ("agent_id", agent_id),
+            ("run_id", run_id),
+        ];
+        if ids.iter().all(|(_, id)| id.is_none()) {
+            self.add(
+                field,
+                "must set at least one of user_id, agent_id or run_id",
+            );
+        }
+        for (name, id) in ids {
+            if let Some(id) = id {
+                self.text(&format!("{field}.{name}"), id, MAX_ID_CHARS);
+            }
+        }
+    }
+
+    /// Like `context_ids`, except that an org_id on its own names an organization pool.
+    fn context(&mut self, context: Option<&ContextFilter>) {
+        let Some(context) = context else {
+            return self.add("context", "must be set");
+        };
+        if let Some(org_id) = context.org_id.as_deref() {
+            self.text("context.org_id", org_id, MAX_ID_CHARS);
+        }
+        let is_org_pool = context.org_id.is_some()
+            && context.user_id.is_none()
+            && context.agent_id.is_none()
+            && context.run_id.is_none();
+        if !is_org_pool {
+            self.context_ids(
+                "context",
+                context.user_id.as_deref(),
+                context.agent_id.as_deref(),
+                context.run_id.as_deref(),
+            );
+        }
+    }
+
+    /// Checks a message nested in the request, prefixing its fields with `field`.
+    fn nested(&mut self, field: &str, value: &impl Validate) {
+        let mut nested = Violations::default();
+        value.check(&mut nested);
+        for (name, message) in nested.0 {
+            self.add(format!("{field}.{name}"), message);
+        }
+    }
+
+    fn into_result(self) -> Result<(), Status> {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        let message = self
+            .0
+            .iter()
+            .map(|(field, message)| format!("{field}: {message}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Err(Status::new(
+            Code::InvalidArgument,
+            format!("invalid request: {message}"),
+        ))
+    }
+}
+
+/// Checks a request's fields before it reaches the controller, answering malformed
+/// ones with `InvalidArgument` and a message per offending field.
+pub(crate) trait Validate {
+    fn check(&self, violations: &mut Violations);
+
+    fn validate(&self) -> Result<(), Status> {
+        let mut violations = Violations::default();
+        self.check(&mut violations);
+        violations.into_result()
+    }
+}
+
+impl Validate for CreateMemoryRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.text("raw_content", &self.raw_content, MAX_RAW_CONTENT_CHARS);
+        violations.context_ids(
+            "request",
+            self.user_id.as_deref(),
+            self.agent_id.as_deref(),
+            self.run_id.as_deref(),
+        );
+    }
+}
+
+impl Validate for CreateStructuredMemoryRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.text("summary", &self.summary, MAX_SUMMARY_CHARS);
+        violations.context_ids(
+            "request",
+            self.user_id.as_deref(),
+            self.agent_id.as_deref(),
+            self.run_id.as_deref(),
+        );
+        match MemoryKind::try_from(self.kind) {
+            Ok(MemoryKind::Unspecified) | Err(_) => violations.add("kind", "must be set"),
+            Ok(_) => {}
+        }
+        for name in self.fields.keys() {
+            violations.text("fields", name, MAX_ID_CHARS);
+        }
+    }
+}
+
+impl Validate for CreateMemoriesRequest {
+    fn check(&self, violations: &mut Violations) {
+        if self.requests.is_empty() {
+            violations.add("requests", "must not be empty");
+        } else if self.requests.len() > MAX_CREATE_BATCH {
+            violations.add(
+                "requests",
+                format!("must hold at most {MAX_CREATE_BATCH} requests"),
+            );
+        }
+        for (index, request) in self.requests.iter().enumerate() {
+            violations.nested(&format!("requests[{index}]"), request);
+        }
+    }
+}
+
+impl Validate for DeleteMemoryRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.uuid("id", &self.id);
+    }
+}
+
+impl Validate for GetMemoryRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.uuid("id", &self.id);
+    }
+}
+
+impl Validate for UpdateMemoryRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.uuid("id", &self.id);
+        violations.text("summary", &self.summary, MAX_SUMMARY_CHARS);
+    }
+}
+
+impl Validate for ListMemoriesRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.context(self.context.as_ref());
+    }
+}
+
+impl Validate for GetMemoryStatsRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.context(self.context.as_ref());
+    }
+}
+
+impl Validate for SaveViewRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.context(self.context.as_ref());
+        violations.text("name", &self.name, MAX_VIEW_NAME_CHARS);
+        violations.text("query", &self.query, MAX_QUERY_CHARS);
+    }
+}
+
+impl Validate for ListViewsRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.context(self.context.as_ref());
+    }
+}
+
+impl Validate for RunViewRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.context(self.context.as_ref());
+        violations.text("name", &self.name, MAX_VIEW_NAME_CHARS);
+    }
+}
+
+impl Validate for DeleteViewRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.context(self.context.as_ref());
+        violations.text("name", &self.name, MAX_VIEW_NAME_CHARS);
+    }
+}
+
+impl Validate for GetChangesRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.context(self.context.as_ref());
+        if self.since < 0 {
+            violations.add("since", "must not be negative");
+        }
+    }
+}
+
+impl Validate for SearchMemoriesRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.text("query", &self.query, MAX_QUERY_CHARS);
+        violations.context(self.context.as_ref());
+    }
+}
+
+impl Validate for BuildContextRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.text("query", &self.query, MAX_QUERY_CHARS);
+        violations.context(self.context.as_ref());
+        if self.token_budget == 0 || self.token_budget > MAX_TOKEN_BUDGET {
+            violations.add(
+                "token_budget",
+                format!("must be between 1 and {MAX_TOKEN_BUDGET}"),
+            );
+        }
+    }
+}
+
+impl Validate for MarkUsedRequest {
+    fn check(&self, violations: &mut Violations) {
+        if self.ids.is_empty() {
+            violations.add("ids", "must not be empty");
+        } else if self.ids.len() > MAX_MARK_USED_IDS {
+            violations.add("ids", format!("must hold at most {MAX_MARK_USED_IDS} ids"));
+        }
+        for (index, id) in self.ids.iter().enumerate() {
+            violations.uuid(&format!("ids[{index}]"), id);
+        }
+    }
+}
+
+impl Validate for FeedbackRequest {
+    fn check(&self, violations: &mut Violations) {
+        violations.uuid("memory_id", &self.memory_id);
+        violations.text("query", &self.query, MAX_QUERY_CHARS);
+    }
+}
@@ -0,0 +1,121 @@
+use std::iter::zip;
+use thiserror::Error;
+use tracing::info;
+use umem_controller::{CreateMemoryRequest, MemoryController, MemoryControllerError};
+use umem_core::Memory;
+use umem_embed_engine_core::{
+    engine::{AddRequest, AddResponse, Engine},
+    estimate::DryRunReport,
+    vectordb::VectorDB,
+};
+
+// NOTE: rough per-call figures for the annotation prompt and its JSON answer, used only
+// for dry run estimates
+const ANNOTATION_PROMPT_TOKENS: usize = 1_500;
+const ANNOTATION_OUTPUT_TOKENS: usize = 300;
+
+#[derive(Debug, Error)]
+pub enum IngestError {
+    #[error("document ingestion failed with: {0}")]
+    EngineError(#[from] anyhow::Error),
+
+    #[error("memory distillation failed with: {0}")]
+    MemoryControllerError(#[from] MemoryControllerError),
+
+    #[error("At least one of user_id, agent_id, or run_id must be set")]
+    MissingContext,
+}
+
+pub struct IngestResponse {
+    pub source_hash: String,
+    /// Ids of the chunks stored by this ingestion.
+    pub chunk_ids: Vec<String>,
+    pub memories: Vec<Memory>,
+    /// Set on a dry run, with the embedding and annotation work the ingestion would do.
+    pub dry_run: Option<DryRunReport>,
+}
+
+/// Ingests a source through the embed engine, then distills each newly stored chunk
+/// into memories through the controller's annotation path. Every memory records the
+/// chunk it came from in `Memory::sources`, so answers built on a memory can cite the
+/// document. Re-ingesting an unchanged source stores no chunks and distills nothing,
+/// and a dry run only reports the chunks with an estimate of the work and its cost.
+pub struct IngestPipeline<LLM, DB, Embedder> {
+    engine: Engine<LLM, DB, Embedder>,
+    memory_controller: MemoryController,
+}
+
+impl<LLM, DB, Embedder> IngestPipeline<LLM, DB, Embedder>
+where
+    DB: VectorDB,
+{
+    pub fn new(engine: Engine<LLM, DB, Embedder>, memory_controller: MemoryController) -> Self {
+        Self {
+            engine,
+            memory_controller,
+        }
+    }
+
+    pub async fn ingest(
+        &mut self,
+        request: AddRequest,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+    ) -> Result<IngestResponse, IngestError> {
+        if user_id.is_none() && agent_id.is_none() && run_id.is_none() {
+            return Err(IngestError::MissingContext);
+        }
+
+        let dry_run = request.is_dry_run();
+        let AddResponse {
+            source_hash,
+            chunks,
+        } = self.engine.add_chunks(request).await?;
+
+        if dry_run {
+            let report = self
+                .engine
+                .dry_run_report(source_hash.clone(), &chunks)
+                .with_annotation(ANNOTATION_PROMPT_TOKENS, ANNOTATION_OUTPUT_TOKENS)
+                .priced(self.engine.pricing());
+
+            return Ok(IngestResponse {
+                source_hash,
+                chunk_ids: chunks.ids,
+                memories: vec![],
+                dry_run: Some(report),
+            });
+        }
+
+        let mut memories = Vec::new();
+        for (chunk_id, document) in zip(&chunks.ids, chunks.documents) {
+            let request = CreateMemoryRequest::builder()
+                .user_id(user_id.clone())
+                .agent_id(agent_id.clone())
+                .run_id(run_id.clone())
+                .raw_content(document)
+                .sources(vec![chunk_id.clone()])
+                .build();
+            memories.extend(self.memory_controller.create(request, None).await?);
+        }
+
+        info!(
+            "ingested {} chunks from {} into {} memories",
+            chunks.ids.len(),
+            source_hash,
+            memories.len()
+        );
+
+        Ok(IngestResponse {
+            source_hash,
+            chunk_ids: chunks.ids,
+            memories,
+            dry_run: None,
+        })
+    }
+
+    pub fn engine(&self) -> &Engine<LLM, DB, Embedder> {
+        &self.engine
+    }
+}
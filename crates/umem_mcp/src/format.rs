@@ -0,0 +1,124 @@
+use anyhow::bail;
+use chrono::DateTime;
+use rmcp::schemars;
+use serde::Serialize;
+use std::{collections::BTreeMap, str::FromStr};
+use umem_core::{Memory, MemoryScope};
+
+/// How tools that return memories write them into the model's context.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Every stored field, one JSON object per line.
+    #[default]
+    Full,
+    /// Id, kind, scope, date, summary, tags, fields and conflicts, one JSON object per line.
+    Json,
+    /// A markdown list for reading, one entry per memory.
+    Markdown,
+}
+
+impl FromStr for ResponseFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "full" => Ok(Self::Full),
+            "json" => Ok(Self::Json),
+            "markdown" | "md" => Ok(Self::Markdown),
+            s => bail!("invalid response format: {s} (expected full, json or markdown)"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CompactMemory<'a> {
+    id: String,
+    kind: &'static str,
+    scope: &'static str,
+    date: String,
+    summary: &'a str,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    fields: &'a BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    contradicts: Vec<String>,
+}
+
+fn created_date(memory: &Memory) -> String {
+    DateTime::from_timestamp(memory.temporal().created_at(), 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn contradicts(memory: &Memory) -> Vec<String> {
+    memory
+        .contradicts()
+        .iter()
+        .map(|id| id.to_string())
+        .collect()
+}
+
+impl ResponseFormat {
+    pub fn render(self, memories: &[Memory]) -> String {
+        if memories.is_empty() && self == ResponseFormat::Markdown {
+            return "No memories found.".to_string();
+        }
+
+        memories
+            .iter()
+            .map(|memory| self.render_entry(memory))
+            .collect()
+    }
+
+    /// One memory as `render` writes it, including its line break.
+    pub fn render_entry(self, memory: &Memory) -> String {
+        let line = match self {
+            ResponseFormat::Full => serde_json::to_string(memory),
+            ResponseFormat::Json => serde_json::to_string(&CompactMemory {
+                id: memory.get_id().to_string(),
+                kind: memory.kind().as_str(),
+                scope: memory.scope().as_str(),
+                date: created_date(memory),
+                summary: memory.get_summary().trim(),
+                tags: memory.content().tags(),
+                fields: memory.fields(),
+                contradicts: contradicts(memory),
+            }),
+            ResponseFormat::Markdown => return markdown_entry(memory),
+        };
+        line.expect("serializing memory should never fail") + "\n"
+    }
+}
+
+fn markdown_entry(memory: &Memory) -> String {
+    let mut entry = format!(
+        "- {}\n  - {}, {}, id `{}`\n",
+        memory.get_summary().trim(),
+        memory.kind().as_str(),
+        created_date(memory),
+        memory.get_id()
+    );
+    if memory.scope() == MemoryScope::Org {
+        entry.push_str(&format!(
+            "  - shared by org `{}`\n",
+            memory.context().org_id().unwrap_or_default()
+        ));
+    }
+    let tags = memory.content().tags();
+    if !tags.is_empty() {
+        entry.push_str(&format!("  - tags: {}\n", tags.join(", ")));
+    }
+    for (name, value) in memory.fields() {
+        entry.push_str(&format!("  - {name}: {value}\n"));
+    }
+    let contradicts = contradicts(memory);
+    if !contradicts.is_empty() {
+        entry.push_str(&format!(
+            "  - conflicts with: `{}`\n",
+            contradicts.join("`, `")
+        ));
+    }
+    entry
+}
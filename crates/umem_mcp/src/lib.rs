@@ -1,6 +1,9 @@
+mod format;
 pub mod service;
 mod token;
 
+pub use format::ResponseFormat;
+
 use anyhow::Result;
 use axum::{
     body::Body,
@@ -16,13 +19,14 @@ use rmcp::transport::{
     SseServer, StreamableHttpServerConfig, StreamableHttpService,
 };
 use serde_json::json;
-use std::{net::SocketAddr, sync::Arc, time::Duration};
+use std::{net::SocketAddr, os::unix::fs::FileTypeExt, path::Path, sync::Arc, time::Duration};
 use tokio_util::sync::CancellationToken;
 use tower_http::{
     cors::{Any, CorsLayer},
     trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
 };
 use tracing::{error, info, Level};
+use umem_auth::{ApiKeyStore, API_KEY_PREFIX};
 use umem_controller::MemoryController;
 
 pub const USER_ID_HEADER: &str = "x-evenscribe-header";
@@ -34,10 +38,11 @@ struct McpAppState {
     workos_authkit_url: String,
     workos_client_id: String,
     jwks: Arc<token::Jwks>,
+    api_keys: Option<Arc<ApiKeyStore>>,
 }
 
 impl McpAppState {
-    async fn new(config: umem_config::Mcp) -> Self {
+    async fn new(config: umem_config::Mcp, api_keys: Option<Arc<ApiKeyStore>>) -> Self {
         let jwks = token::get_jwks(&config.jwks_url)
             .await
             .unwrap_or_else(|e| panic!("jwks_url parse token error {}", e));
@@ -48,6 +53,7 @@ impl McpAppState {
             workos_authkit_url: config.work_os.authkit_url,
             workos_client_id: config.work_os.client_id,
             jwks: Arc::new(jwks),
+            api_keys,
         }
     }
 }
@@ -73,6 +79,28 @@ async fn validate_token_middleware(
         }
     };
 
+    if let (Some(api_keys), true) = (
+        app_state.api_keys.as_ref(),
+        token.starts_with(API_KEY_PREFIX),
+    ) {
+        let api_key = match api_keys.verify(token).await {
+            Ok(api_key) => api_key,
+            Err(_) => return StatusCode::UNAUTHORIZED.into_response(),
+        };
+
+        // NOTE: MCP tools act on a single user's memories, so the key must name one
+        let Some(user_id) = api_key.scope.user_id.as_deref() else {
+            return StatusCode::FORBIDDEN.into_response();
+        };
+        let Ok(user_id) = HeaderValue::from_str(user_id) else {
+            return StatusCode::FORBIDDEN.into_response();
+        };
+
+        let _ = request.headers_mut().insert(USER_ID_HEADER, user_id);
+        request.extensions_mut().insert(api_key);
+        return next.run(request).await;
+    }
+
     let token_data =
         match token::check_token(token, &app_state.jwks, &app_state.workos_client_id).await {
             Ok(claims) => claims,
@@ -130,9 +158,20 @@ async fn oauth_authorization_server(
         .unwrap_or_else(|e| panic!("{}", e))
 }
 
-fn build_stream_http(app_state: Arc<McpAppState>, memory_controller: MemoryController) -> Router {
+fn build_stream_http(
+    app_state: Arc<McpAppState>,
+    memory_controller: MemoryController,
+    response_format: ResponseFormat,
+    clarify_ambiguous: bool,
+) -> Router {
     let streamable_service = StreamableHttpService::new(
-        move || Ok(service::McpService::new(memory_controller.clone())),
+        move || {
+            Ok(service::McpService::new(
+                memory_controller.clone(),
+                response_format,
+                clarify_ambiguous,
+            ))
+        },
         LocalSessionManager::default().into(),
         StreamableHttpServerConfig::default(),
     );
@@ -159,7 +198,12 @@ fn build_stream_http(app_state: Arc<McpAppState>, memory_controller: MemoryContr
         )
 }
 
-fn build_sse(app_state: Arc<McpAppState>, memory_controller: MemoryController) -> Router {
+fn build_sse(
+    app_state: Arc<McpAppState>,
+    memory_controller: MemoryController,
+    response_format: ResponseFormat,
+    clarify_ambiguous: bool,
+) -> Router {
     let sse_config = SseServerConfig {
         bind: app_state.server_addr,
         sse_path: "/mcp/sse".to_string(),
@@ -169,7 +213,13 @@ fn build_sse(app_state: Arc<McpAppState>, memory_controller: MemoryController) -
     };
 
     let (sse_server, sse_router) = SseServer::new(sse_config);
-    sse_server.with_service(move || service::McpService::new(memory_controller.clone()));
+    sse_server.with_service(move || {
+        service::McpService::new(
+            memory_controller.clone(),
+            response_format,
+            clarify_ambiguous,
+        )
+    });
     sse_router
         .layer(middleware::from_fn_with_state(
             app_state,
@@ -217,25 +267,61 @@ impl MemoryServiceMcp {
     pub async fn run_server(
         config: umem_config::Mcp,
         memory_controller: MemoryController,
+        api_keys: Option<Arc<ApiKeyStore>>,
     ) -> Result<()> {
         let addr = config.server_addr;
-        let app_state = Arc::new(McpAppState::new(config).await);
+        let socket_path = config.socket_path.clone();
+        let response_format = config
+            .response_format
+            .as_deref()
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or_default();
+        let clarify_ambiguous = config.clarify_ambiguous.unwrap_or(false);
+        let app_state = Arc::new(McpAppState::new(config, api_keys).await);
 
-        let protected_sse_router = build_sse(Arc::clone(&app_state), memory_controller.clone());
-        let streamable_router = build_stream_http(Arc::clone(&app_state), memory_controller);
+        let protected_sse_router = build_sse(
+            Arc::clone(&app_state),
+            memory_controller.clone(),
+            response_format,
+            clarify_ambiguous,
+        );
+        let streamable_router = build_stream_http(
+            Arc::clone(&app_state),
+            memory_controller,
+            response_format,
+            clarify_ambiguous,
+        );
         let oauth_server_router = build_auth_router(Arc::clone(&app_state));
 
         let app = Router::new().merge(oauth_server_router);
         let app = app.merge(protected_sse_router).merge(streamable_router);
 
-        info!("MCP OAuth Server started on {}", addr);
-        let listener = tokio::net::TcpListener::bind(addr).await?;
-        let server = axum::serve(listener, app);
+        let served = match socket_path {
+            Some(socket_path) => {
+                let listener = bind_unix(&socket_path)?;
+                info!("MCP OAuth Server started on {}", socket_path.display());
+                axum::serve(listener, app).await
+            }
+            None => {
+                info!("MCP OAuth Server started on {}", addr);
+                let listener = tokio::net::TcpListener::bind(addr).await?;
+                axum::serve(listener, app).await
+            }
+        };
 
-        if let Err(e) = server.await {
+        if let Err(e) = served {
             error!("Server error: {}", e);
         }
 
         Ok(())
     }
 }
+
+/// Binds `path`, first removing a socket left there by an earlier run.
+fn bind_unix(path: &Path) -> std::io::Result<tokio::net::UnixListener> {
+    if std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        std::fs::remove_file(path)?;
+    }
+    tokio::net::UnixListener::bind(path)
+}
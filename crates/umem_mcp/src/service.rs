@@ -1,26 +1,90 @@
-use crate::USER_ID_HEADER;
+use crate::{ResponseFormat, USER_ID_HEADER};
 use anyhow::Result;
 use axum::http::request::Parts;
+use chrono::{DateTime, Utc};
 use rmcp::{
     handler::server::{
         router::tool::ToolRouter,
         tool::{Extension, Parameters},
     },
     model::{ErrorData as McpError, *},
-    schemars, tool, tool_handler, tool_router,
+    schemars, tool, tool_handler, tool_router, Peer, RoleServer,
 };
-use std::future::Future;
-use tracing::debug;
-use umem_controller::{CreateMemoryRequest, MemoryController};
+use std::{collections::BTreeMap, future::Future};
+use tracing::{debug, warn};
+use umem_auth::{Access, ApiKey};
+use umem_controller::{
+    ContextPack, CreateMemoryRequest, CreateStructuredMemoryRequest, MemoryController,
+    MemoryControllerError, MemoryTemplate, SavedViewError, SearchMemoryError, SearchMemoryOptions,
+};
+use umem_core::{
+    Memory, MemoryContext, MemoryId, MemoryKind, MemoryVisibility, ParsedQuery, QuerySyntaxError,
+};
+
+const DEFAULT_CONTEXT_TOKEN_BUDGET: usize = 1000;
+
+const CLARIFY_SYSTEM_PROMPT: &str = "You are helping a memory store record what the user said. Answer the question about the content from the conversation so far, in one or two sentences. If the conversation does not answer it, reply with UNKNOWN and nothing else.";
+const CLARIFY_MAX_TOKENS: u32 = 200;
+const UNKNOWN_ANSWER: &str = "UNKNOWN";
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct AddMemoryRequest {
     pub content: String,
+    pub format: Option<ResponseFormat>,
+    pub shared: Option<bool>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct AddStructuredMemoryRequest {
+    pub kind: MemoryKind,
+    pub summary: String,
+    pub tags: Option<Vec<String>>,
+    pub fields: Option<BTreeMap<String, String>>,
+    pub format: Option<ResponseFormat>,
+    pub shared: Option<bool>,
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct GetMemoriesByQueryRequest {
     pub query: String,
+    pub format: Option<ResponseFormat>,
+    pub max_tokens: Option<usize>,
+    /// RFC 3339 timestamp; searches the memories as they stood at that time.
+    pub as_of: Option<String>,
+    /// Read query as the query language, e.g.
+    /// `kind:procedural tag:deploy after:2024-01-01 "exact phrase" rollback`.
+    pub query_language: Option<bool>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct SaveViewRequest {
+    pub name: String,
+    /// In the query language, e.g. `kind:procedural tag:deploy rollback`.
+    pub query: String,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct RunViewRequest {
+    pub name: String,
+    pub format: Option<ResponseFormat>,
+    pub max_tokens: Option<usize>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct DeleteViewRequest {
+    pub name: String,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct BuildContextRequest {
+    pub query: String,
+    pub token_budget: Option<usize>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct SummarizeSessionRequest {
+    pub run_id: String,
+    pub format: Option<ResponseFormat>,
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
@@ -32,12 +96,67 @@ pub struct ExtendMemoryRequest {
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct GetMemoriesByIdRequest {
     pub memory_id: String,
+    pub format: Option<ResponseFormat>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct FormatRequest {
+    pub format: Option<ResponseFormat>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct MarkUsedRequest {
+    pub memory_ids: Vec<String>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct AcknowledgeRemindersRequest {
+    pub memory_ids: Vec<String>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ShareMemoryRequest {
+    pub memory_id: String,
+    pub shared: bool,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct PublishMemoryRequest {
+    pub memory_id: String,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct FeedbackRequest {
+    pub memory_id: String,
+    pub query: String,
+    pub helpful: bool,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct AdjustSignalsRequest {
+    pub memory_id: String,
+    /// How much to change certainty, from -1.0 to 1.0.
+    #[serde(default)]
+    pub delta_certainty: f32,
+    /// How much to change salience, from -1.0 to 1.0.
+    #[serde(default)]
+    pub delta_salience: f32,
 }
 
 #[derive(Clone)]
 pub struct McpService {
     tool_router: ToolRouter<Self>,
     memory_controller: MemoryController,
+    response_format: ResponseFormat,
+    clarify_ambiguous: bool,
+}
+
+/// What `add_memory` content turned out to be once checked for ambiguity.
+enum Clarification {
+    /// Content to store, with the answer to the clarifying question when one was asked.
+    Clear(String),
+    /// The question the host model could not answer.
+    Unanswered(String),
 }
 
 fn extract_user_id(parts: Parts) -> String {
@@ -50,8 +169,99 @@ fn extract_user_id(parts: Parts) -> String {
         .to_owned()
 }
 
+/// The api key the request authenticated with; `None` for OAuth sessions, which have
+/// full access to the user's memories.
+fn extract_api_key(parts: &Parts) -> Option<&ApiKey> {
+    parts.extensions.get::<ApiKey>()
+}
+
+fn extract_agent_id(parts: &Parts) -> Option<String> {
+    extract_api_key(parts).and_then(|api_key| api_key.scope.agent_id.clone())
+}
+
+fn extract_org_id(parts: &Parts) -> Option<String> {
+    extract_api_key(parts).and_then(|api_key| api_key.scope.org_id.clone())
+}
+
+/// The user's context, narrowed to the api key's agent when the key is agent-scoped
+/// and merged with the pool of the key's organization when it names one.
+fn extract_context(parts: &Parts) -> Result<MemoryContext, McpError> {
+    let agent_id = extract_agent_id(parts);
+    let user_id = extract_user_id(parts.clone());
+    MemoryContext::new(Some(user_id), agent_id, None)
+        .and_then(|context| context.with_org(extract_org_id(parts)))
+        .map_err(|e| McpError::new(ErrorCode::INVALID_REQUEST, e.to_string(), None))
+}
+
+fn parse_memory_id(id: &str) -> Result<MemoryId, McpError> {
+    MemoryId::parse(id).map_err(|e| McpError::new(ErrorCode::INVALID_REQUEST, e.to_string(), None))
+}
+
+fn authorize(parts: &Parts, context: &MemoryContext, access: Access) -> Result<(), McpError> {
+    match extract_api_key(parts) {
+        Some(api_key) => api_key
+            .authorize(context, access)
+            .map_err(|e| McpError::new(ErrorCode::INVALID_REQUEST, e.to_string(), None)),
+        None => Ok(()),
+    }
+}
+
+/// Like `authorize` for reading `memory`, which the user's other agents may also read
+/// once it is shared.
+fn authorize_read(parts: &Parts, memory: &Memory) -> Result<(), McpError> {
+    let user_id = extract_user_id(parts.clone());
+    if memory.is_shared() && memory.context().user_id() == Some(user_id.as_str()) {
+        return Ok(());
+    }
+    authorize(parts, memory.context(), Access::Read)
+}
+
+/// Reports a saved view that is missing, badly named or does not parse as the caller's
+/// mistake.
+fn map_view_error(e: MemoryControllerError) -> McpError {
+    let code = match &e {
+        MemoryControllerError::SavedViewError(
+            SavedViewError::NotFound(_)
+            | SavedViewError::InvalidName
+            | SavedViewError::QuerySyntaxError(_)
+            | SavedViewError::SearchMemoryError(SearchMemoryError::QueryError(_)),
+        ) => ErrorCode::INVALID_REQUEST,
+        _ => ErrorCode::INTERNAL_ERROR,
+    };
+    McpError::new(code, e.to_string(), None)
+}
+
+fn describe_template(template: &MemoryTemplate) -> String {
+    let mut text = format!("- {}\n", template.kind.as_str());
+    for field in &template.fields {
+        let required = if field.required {
+            "required"
+        } else {
+            "optional"
+        };
+        text.push_str(&format!("  - `{}` ({required})", field.name));
+        if !field.description.is_empty() {
+            text.push_str(&format!(": {}", field.description));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn visibility(shared: bool) -> MemoryVisibility {
+    if shared {
+        MemoryVisibility::Shared
+    } else {
+        MemoryVisibility::Private
+    }
+}
+
 impl McpService {
-    pub fn new(memory_controller: MemoryController) -> Self {
+    pub fn new(
+        memory_controller: MemoryController,
+        response_format: ResponseFormat,
+        clarify_ambiguous: bool,
+    ) -> Self {
         debug!("Creating new McpService instance");
         let tool_router = Self::tool_router();
         let tools = tool_router.list_all();
@@ -62,7 +272,117 @@ impl McpService {
         Self {
             tool_router,
             memory_controller,
+            response_format,
+            clarify_ambiguous,
+        }
+    }
+
+    /// With `[mcp] clarify_ambiguous`, has ambiguous `content` cleared up before it is
+    /// stored: the clarifying question goes to the host model through MCP sampling, with
+    /// the conversation as context. Hosts without sampling get the question back.
+    async fn clarify(
+        &self,
+        controller: &MemoryController,
+        peer: &Peer<RoleServer>,
+        content: String,
+    ) -> Result<Clarification, McpError> {
+        if !self.clarify_ambiguous {
+            return Ok(Clarification::Clear(content));
+        }
+        let Some(question) = controller
+            .clarifying_question(&content)
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?
+        else {
+            return Ok(Clarification::Clear(content));
+        };
+
+        let samples = peer
+            .peer_info()
+            .is_some_and(|info| info.capabilities.sampling.is_some());
+        if !samples {
+            return Ok(Clarification::Unanswered(question));
+        }
+
+        let request = CreateMessageRequestParam {
+            messages: vec![SamplingMessage {
+                role: Role::User,
+                content: Content::text(format!("Content: {content}\n\nQuestion: {question}")),
+            }],
+            model_preferences: None,
+            system_prompt: Some(CLARIFY_SYSTEM_PROMPT.to_string()),
+            include_context: Some(ContextInclusion::AllServers),
+            temperature: None,
+            max_tokens: CLARIFY_MAX_TOKENS,
+            stop_sequences: None,
+            metadata: None,
+        };
+        let answer = match peer.create_message(request).await {
+            Ok(result) => result
+                .message
+                .content
+                .as_text()
+                .map(|text| text.text.trim().to_string()),
+            Err(e) => {
+                warn!("could not ask the host model to clarify a memory: {}", e);
+                None
+            }
+        };
+
+        Ok(match answer {
+            Some(answer) if !answer.is_empty() && answer != UNKNOWN_ANSWER => {
+                Clarification::Clear(format!("{content}\n{question} {answer}"))
+            }
+            _ => Clarification::Unanswered(question),
+        })
+    }
+
+    /// The controller scoped to the caller: the api key, or for OAuth sessions the
+    /// signed-in user.
+    fn controller(&self, parts: &Parts) -> MemoryController {
+        let principal = match extract_api_key(parts) {
+            Some(api_key) => api_key.clone(),
+            None => ApiKey::for_user(extract_user_id(parts.clone())),
+        };
+        self.memory_controller.for_principal(Some(&principal))
+    }
+
+    /// Applies the caller's response policy and renders the memories in the requested
+    /// format, or the deployment's `[mcp] response_format`.
+    fn present(
+        &self,
+        parts: &Parts,
+        mut memories: Vec<Memory>,
+        format: Option<ResponseFormat>,
+    ) -> String {
+        self.memory_controller.redact(
+            &mut memories,
+            extract_api_key(parts).map(|api_key| api_key.access),
+        );
+
+        format.unwrap_or(self.response_format).render(&memories)
+    }
+
+    /// Like `present`, but keeps only the memories that fit into `max_tokens`, in
+    /// order, skipping ones too long for what is left.
+    fn present_within(
+        &self,
+        parts: &Parts,
+        mut memories: Vec<Memory>,
+        format: Option<ResponseFormat>,
+        max_tokens: usize,
+    ) -> String {
+        self.memory_controller.redact(
+            &mut memories,
+            extract_api_key(parts).map(|api_key| api_key.access),
+        );
+
+        let format = format.unwrap_or(self.response_format);
+        let pack = ContextPack::fit(memories, max_tokens, |memory| format.render_entry(memory));
+        if pack.memories.is_empty() {
+            return format.render(&[]);
         }
+        pack.text
     }
 }
 
@@ -75,10 +395,16 @@ impl McpService {
     async fn add_memory(
         &self,
         Extension(parts): Extension<Parts>,
-        Parameters(AddMemoryRequest { content }): Parameters<AddMemoryRequest>,
+        peer: Peer<RoleServer>,
+        Parameters(AddMemoryRequest {
+            content,
+            format,
+            shared,
+        }): Parameters<AddMemoryRequest>,
     ) -> Result<CallToolResult, McpError> {
         debug!("add_memory tool called with text: {}", content);
-        let user_id = extract_user_id(parts);
+        let context = extract_context(&parts)?;
+        authorize(&parts, &context, Access::ReadWrite)?;
         if content.is_empty() {
             return Err(McpError::new(
                 ErrorCode::INVALID_REQUEST,
@@ -87,22 +413,110 @@ impl McpService {
             ));
         }
 
-        let memory = self
-            .memory_controller
-            .create(
-                CreateMemoryRequest::builder()
-                    .user_id(Some(user_id))
-                    .raw_content(content)
-                    .build(),
+        let controller = self.controller(&parts);
+        let content = match self.clarify(&controller, &peer, content).await? {
+            Clarification::Clear(content) => content,
+            Clarification::Unanswered(question) => {
+                return Ok(CallToolResult::success(vec![Annotated::new(
+                    RawContent::Text(RawTextContent {
+                        text: format!(
+                            "Memory not stored, the content is ambiguous. {question} Ask the user if needed, then call add_memory again with the answer included."
+                        ),
+                    }),
+                    None,
+                )]));
+            }
+        };
+        let request = CreateMemoryRequest::builder()
+            .user_id(context.user_id().map(str::to_owned))
+            .agent_id(context.agent_id().map(str::to_owned))
+            .raw_content(content)
+            .visibility(visibility(shared.unwrap_or(false)))
+            .build();
+
+        let memory_bulk = if controller.journal.is_some() {
+            controller
+                .enqueue_create(request)
+                .await
+                .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+            "Memory recorded; it will be searchable shortly.".to_string()
+        } else {
+            let memories = controller
+                .create(request, None)
+                .await
+                .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+            self.present(&parts, memories, format)
+        };
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent { text: memory_bulk }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "add_structured_memory",
+        description = "Add a memory whose kind and fields you already know, without having the content annotated. Kinds with a template need its required fields: call get_memory_templates to see them. For example a Relational memory needs subject and object, and a Prospective memory needs due_at as an RFC 3339 timestamp. WHEN TO USE: When you can state the memory as one summary sentence with its structured fields, such as who relates to whom or when a plan is due; use add_memory for free-form content."
+    )]
+    async fn add_structured_memory(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(AddStructuredMemoryRequest {
+            kind,
+            summary,
+            tags,
+            fields,
+            format,
+            shared,
+        }): Parameters<AddStructuredMemoryRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let context = extract_context(&parts)?;
+        authorize(&parts, &context, Access::ReadWrite)?;
+        if summary.trim().is_empty() {
+            return Err(McpError::new(
+                ErrorCode::INVALID_REQUEST,
+                "Memory summary cannot be empty",
                 None,
-            )
+            ));
+        }
+
+        let request = CreateStructuredMemoryRequest::builder()
+            .user_id(context.user_id().map(str::to_owned))
+            .agent_id(context.agent_id().map(str::to_owned))
+            .summary(summary)
+            .tags(tags.unwrap_or_default())
+            .kind(kind)
+            .fields(fields.unwrap_or_default())
+            .visibility(visibility(shared.unwrap_or(false)))
+            .build();
+        let memories = self
+            .controller(&parts)
+            .create_structured(vec![request], None)
             .await
-            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+            .map_err(|e| McpError::new(ErrorCode::INVALID_REQUEST, e.to_string(), None))?;
+        let memory_bulk = self.present(&parts, memories, format);
 
         Ok(CallToolResult::success(vec![Annotated::new(
-            RawContent::Text(RawTextContent {
-                text: serde_json::to_string(&memory).expect("serializing memory should never fail"),
-            }),
+            RawContent::Text(RawTextContent { text: memory_bulk }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "get_memory_templates",
+        description = "List the fields each structured memory kind is created with, and which of them are required. Kinds not listed take any fields. WHEN TO USE: Before calling add_structured_memory for a kind you have not stored yet."
+    )]
+    async fn get_memory_templates(&self) -> Result<CallToolResult, McpError> {
+        let text = self
+            .memory_controller
+            .templates
+            .all()
+            .iter()
+            .map(describe_template)
+            .collect();
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent { text }),
             None,
         )]))
     }
@@ -114,17 +528,15 @@ impl McpService {
     async fn get_all_memory(
         &self,
         Extension(parts): Extension<Parts>,
+        Parameters(FormatRequest { format }): Parameters<FormatRequest>,
     ) -> Result<CallToolResult, McpError> {
-        let user_id = extract_user_id(parts);
-        let memory_bulk: String = self
-            .memory_controller
-            .list_for_user(user_id)
+        let context = extract_context(&parts)?;
+        let memories = self
+            .controller(&parts)
+            .list_with_context(context)
             .await
-            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?
-            .iter()
-            .map(|mem| serde_json::to_string(mem).expect("serializing memory should never fail"))
-            .collect::<Vec<String>>()
-            .join("\n");
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+        let memory_bulk = self.present(&parts, memories, format);
 
         Ok(CallToolResult::success(vec![Annotated::new(
             RawContent::Text(RawTextContent { text: memory_bulk }),
@@ -138,15 +550,19 @@ impl McpService {
     )]
     async fn get_memory_by_id(
         &self,
-        Parameters(GetMemoriesByIdRequest { memory_id }): Parameters<GetMemoriesByIdRequest>,
+        Extension(parts): Extension<Parts>,
+        Parameters(GetMemoriesByIdRequest { memory_id, format }): Parameters<
+            GetMemoriesByIdRequest,
+        >,
     ) -> Result<CallToolResult, McpError> {
         let memory = self
-            .memory_controller
-            .get(memory_id)
+            .controller(&parts)
+            .get(parse_memory_id(&memory_id)?)
             .await
             .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+        authorize_read(&parts, &memory)?;
 
-        let text = serde_json::to_string(&memory).expect("serializing memory should never fail");
+        let text = self.present(&parts, vec![memory], format);
         Ok(CallToolResult::success(vec![Annotated::new(
             RawContent::Text(RawTextContent { text }),
             None,
@@ -155,28 +571,459 @@ impl McpService {
 
     #[tool(
         name = "search",
-        description = "Get memories for the current user related to a query. This tool enables targeted retrieval of specific memories from the persistence layer using semantic search capabilities. WHEN TO USE: (1) When responding to questions that may benefit from past context, (2) Before generating responses that should consider historical preferences or interactions, (3) When references to previous conversations are made, or (4) When topic-specific context would improve response quality. IMPLEMENTATION: The query parameter accepts natural language or keywords—umem automatically performs hybrid semantic and keyword matching to retrieve the most relevant memories. BEST PRACTICE: Use focused, specific queries rather than generic ones for better results. After retrieving memories, consider saving new insights with add_memory to maintain an up-to-date persistence layer."
+        description = "Get memories for the current user related to a query. This tool enables targeted retrieval of specific memories from the persistence layer using semantic search capabilities. WHEN TO USE: (1) When responding to questions that may benefit from past context, (2) Before generating responses that should consider historical preferences or interactions, (3) When references to previous conversations are made, or (4) When topic-specific context would improve response quality. IMPLEMENTATION: The query parameter accepts natural language or keywords—umem automatically performs hybrid semantic and keyword matching to retrieve the most relevant memories. CONFLICTS: A memory with a non-empty contradicts list disagrees with the memories whose ids it lists; ask the user which one is current before relying on either. BEST PRACTICE: Use focused, specific queries rather than generic ones for better results. To check what was known at an earlier time, e.g. when a past decision was made, pass as_of. For exact filtering, set query_language and write the query as filters plus text, e.g. kind:procedural tag:deploy after:2024-01-01 \"exact phrase\" rollback (filters: kind, tag, topic, after, before, updated_after, updated_before, certainty, salience, as_of, limit, is:archived; quoted phrases must appear in the memory). When your context is tight, pass max_tokens to get only the most relevant memories that fit into that many tokens. After retrieving memories, consider saving new insights with add_memory to maintain an up-to-date persistence layer."
     )]
     async fn search(
         &self,
         Extension(parts): Extension<Parts>,
-        Parameters(GetMemoriesByQueryRequest { query }): Parameters<GetMemoriesByQueryRequest>,
+        Parameters(GetMemoriesByQueryRequest {
+            query,
+            format,
+            max_tokens,
+            as_of,
+            query_language,
+        }): Parameters<GetMemoriesByQueryRequest>,
     ) -> Result<CallToolResult, McpError> {
-        let user_id = extract_user_id(parts);
-        let memory_bulk: String = self
-            .memory_controller
-            .search_for_user(user_id, query, None)
-            .await
+        let as_of = as_of
+            .map(|as_of| DateTime::parse_from_rfc3339(&as_of).map(|as_of| as_of.to_utc()))
+            .transpose()
+            .map_err(|e| {
+                McpError::new(
+                    ErrorCode::INVALID_REQUEST,
+                    format!("as_of is not an RFC 3339 timestamp: {e}"),
+                    None,
+                )
+            })?;
+        let options = SearchMemoryOptions::builder().as_of(as_of).build();
+        let memories = match query_language.unwrap_or(false) {
+            true => {
+                let parsed: ParsedQuery = query.parse().map_err(|e: QuerySyntaxError| {
+                    McpError::new(ErrorCode::INVALID_REQUEST, e.to_string(), None)
+                })?;
+                self.controller(&parts)
+                    .parsed_search_with_context(extract_context(&parts)?, parsed, Some(options))
+                    .await
+                    .map(|results| results.memories)
+            }
+            false => {
+                self.controller(&parts)
+                    .search_with_context(extract_context(&parts)?, query, Some(options))
+                    .await
+            }
+        };
+        let memories: Vec<Memory> = memories
             .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?
+            .into_iter()
+            .filter(|mem| authorize_read(&parts, mem).is_ok())
+            .collect();
+        let memory_bulk = match max_tokens {
+            Some(max_tokens) => self.present_within(&parts, memories, format, max_tokens),
+            None => self.present(&parts, memories, format),
+        };
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent { text: memory_bulk }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "build_context",
+        description = "Build a prompt-ready block of the current user's memories relevant to a query, trimmed to a token budget. Memories are searched, reranked and deduplicated, then listed one per line with their kind and date. WHEN TO USE: When you need compact background on the user for a task and want to paste it directly into context instead of reading raw search results. token_budget defaults to 1000 tokens."
+    )]
+    async fn build_context(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(BuildContextRequest {
+            query,
+            token_budget,
+        }): Parameters<BuildContextRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let context = extract_context(&parts)?;
+        let pack = self
+            .controller(&parts)
+            .build_context(
+                context,
+                query,
+                token_budget.unwrap_or(DEFAULT_CONTEXT_TOKEN_BUDGET),
+                None,
+            )
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent { text: pack.text }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "get_reminders",
+        description = "Get the prospective memories (reminders, scheduled commitments, plans with a due date) of the current user that are due now. WHEN TO USE: At the start of a conversation or task, and whenever the user asks what they need to do, so you can act on requests like \"remind me next Tuesday\" at the right time. Recurring reminders keep being returned until you pass them to acknowledge_reminders; one-off reminders keep being returned until they are deleted."
+    )]
+    async fn get_reminders(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(FormatRequest { format }): Parameters<FormatRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let context = extract_context(&parts)?;
+        let memories = self
+            .controller(&parts)
+            .due_prospective(context, Utc::now())
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+        let memory_bulk = self.present(&parts, memories, format);
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent { text: memory_bulk }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "acknowledge_reminders",
+        description = "Move recurring reminders returned by get_reminders on to their next occurrence, so they are not returned again until then. WHEN TO USE: After you have told the user about, or acted on, the reminders from get_reminders, pass their ids. One-off reminders are not affected; delete them once they are done."
+    )]
+    async fn acknowledge_reminders(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(AcknowledgeRemindersRequest { memory_ids }): Parameters<
+            AcknowledgeRemindersRequest,
+        >,
+    ) -> Result<CallToolResult, McpError> {
+        let memory_ids = memory_ids
             .iter()
-            .map(|mem| serde_json::to_string(mem).expect("serializing memory should never fail"))
-            .collect::<Vec<String>>()
-            .join("\n");
+            .map(|id| parse_memory_id(id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let count = memory_ids.len();
+        self.controller(&parts)
+            .acknowledge_due(memory_ids, Utc::now())
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent {
+                text: format!("acknowledged {count} reminders"),
+            }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "memory_stats",
+        description = "Report what is stored about the current user: how many memories there are by kind, lifecycle state and month created, how much space they take up, and when they were last created, updated or used. Returned as JSON. WHEN TO USE: When the user asks what you remember about them or how much, so you can answer with real numbers instead of guessing."
+    )]
+    async fn memory_stats(
+        &self,
+        Extension(parts): Extension<Parts>,
+    ) -> Result<CallToolResult, McpError> {
+        let context = extract_context(&parts)?;
+        let stats = self
+            .controller(&parts)
+            .stats(context)
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+        let text = serde_json::to_string_pretty(&stats)
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent { text }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "save_view",
+        description = "Save a search under a name so it can be run again with run_view, replacing an earlier view of that name. The query is written in the query language: filters such as kind:procedural, tag:deploy, topic:, after:2024-01-01, before:, certainty:, salience:, limit: and is:archived next to the text to search for, and \"quoted phrases\" that must appear in the memory. WHEN TO USE: When the user or a recurring task keeps asking for the same slice of memories, e.g. \"open action items\" or \"deploy procedures\"."
+    )]
+    async fn save_view(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(SaveViewRequest { name, query }): Parameters<SaveViewRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let context = extract_context(&parts)?;
+        authorize(&parts, &context, Access::ReadWrite)?;
+        let view = self
+            .controller(&parts)
+            .save_view(context, name, query)
+            .await
+            .map_err(map_view_error)?;
+        let text = serde_json::to_string_pretty(&view)
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent { text }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "list_views",
+        description = "List the current user's saved views with their queries, as JSON. WHEN TO USE: Before run_view, to find the name of a view, or when the user asks which searches they have saved."
+    )]
+    async fn list_views(
+        &self,
+        Extension(parts): Extension<Parts>,
+    ) -> Result<CallToolResult, McpError> {
+        let context = extract_context(&parts)?;
+        let views = self
+            .controller(&parts)
+            .list_views(context)
+            .await
+            .map_err(map_view_error)?;
+        let text = serde_json::to_string_pretty(&views)
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent { text }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "run_view",
+        description = "Run a saved view by name and return the memories it finds, like search. When your context is tight, pass max_tokens to get only the most relevant memories that fit into that many tokens."
+    )]
+    async fn run_view(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(RunViewRequest {
+            name,
+            format,
+            max_tokens,
+        }): Parameters<RunViewRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let context = extract_context(&parts)?;
+        let memories: Vec<Memory> = self
+            .controller(&parts)
+            .run_view(context, &name, None)
+            .await
+            .map_err(map_view_error)?
+            .memories
+            .into_iter()
+            .filter(|mem| authorize_read(&parts, mem).is_ok())
+            .collect();
+        let memory_bulk = match max_tokens {
+            Some(max_tokens) => self.present_within(&parts, memories, format, max_tokens),
+            None => self.present(&parts, memories, format),
+        };
         Ok(CallToolResult::success(vec![Annotated::new(
             RawContent::Text(RawTextContent { text: memory_bulk }),
             None,
         )]))
     }
+
+    #[tool(
+        name = "delete_view",
+        description = "Delete a saved view by name. The memories it found are not affected."
+    )]
+    async fn delete_view(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(DeleteViewRequest { name }): Parameters<DeleteViewRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let context = extract_context(&parts)?;
+        authorize(&parts, &context, Access::ReadWrite)?;
+        let deleted = self
+            .controller(&parts)
+            .delete_view(context, &name)
+            .await
+            .map_err(map_view_error)?;
+        if !deleted {
+            return Err(McpError::new(
+                ErrorCode::INVALID_REQUEST,
+                format!("no view named {name}"),
+                None,
+            ));
+        }
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent {
+                text: format!("deleted view {name}"),
+            }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "summarize_session",
+        description = "Close out a session by consolidating all working memories recorded under a run_id into a single episodic summary memory. The working memories are archived and the summary is returned. WHEN TO USE: At the end of a task or conversation that stored scratch notes as working memories, so later sessions see one concise account instead of many fragments. Returns an empty result when the run has no working memories."
+    )]
+    async fn summarize_session(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(SummarizeSessionRequest { run_id, format }): Parameters<SummarizeSessionRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let context = extract_context(&parts)?;
+        authorize(&parts, &context, Access::ReadWrite)?;
+        let summary = self
+            .controller(&parts)
+            .summarize_session(
+                umem_controller::SummarizeSessionRequest::builder()
+                    .user_id(context.user_id().map(str::to_owned))
+                    .agent_id(context.agent_id().map(str::to_owned))
+                    .run_id(run_id)
+                    .build(),
+                None,
+            )
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        let text = self.present(&parts, summary.into_iter().collect(), format);
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent { text }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "mark_used",
+        description = "Report which retrieved memories actually helped with the current answer or task. Memories marked as used become more salient and rank higher in later searches; memories that are never used slowly fade. WHEN TO USE: After relying on memories returned by search, build_context or get_memory_by_id, pass the ids of the ones you used. Do not pass memories you only skimmed."
+    )]
+    async fn mark_used(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(MarkUsedRequest { memory_ids }): Parameters<MarkUsedRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let memory_ids = memory_ids
+            .iter()
+            .map(|id| parse_memory_id(id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let count = memory_ids.len();
+        self.controller(&parts)
+            .mark_used(memory_ids, Utc::now())
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent {
+                text: format!("marked {count} memories as used"),
+            }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "feedback",
+        description = "Rate whether a memory returned for a query was actually helpful. Ratings are stored with the memory and used to tune retrieval. WHEN TO USE: After a search, when a returned memory clearly answered the query (helpful = true) or was irrelevant or misleading (helpful = false). Pass the query exactly as you searched it."
+    )]
+    async fn feedback(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(FeedbackRequest {
+            memory_id,
+            query,
+            helpful,
+        }): Parameters<FeedbackRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        self.controller(&parts)
+            .feedback(parse_memory_id(&memory_id)?, query, helpful)
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent {
+                text: "feedback recorded".to_string(),
+            }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "adjust_signals",
+        description = "Strengthen or weaken a memory when new evidence bears on it. Certainty is how likely the memory is to be true; salience is how much it matters. Both stay between 0 and 1, and every adjustment is recorded with the memory. WHEN TO USE: When the user confirms or contradicts a memory without restating it (raise or lower delta_certainty), or when a memory turns out to matter more or less than it seemed (delta_salience). Use small steps such as 0.1 or 0.2; each delta must be between -1 and 1."
+    )]
+    async fn adjust_signals(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(AdjustSignalsRequest {
+            memory_id,
+            delta_certainty,
+            delta_salience,
+        }): Parameters<AdjustSignalsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let memory = self
+            .controller(&parts)
+            .adjust_signals(
+                parse_memory_id(&memory_id)?,
+                delta_certainty,
+                delta_salience,
+            )
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent {
+                text: format!(
+                    "memory {} now has certainty {:.2} and salience {:.2}",
+                    memory.get_id(),
+                    memory.signals().get_certainty(),
+                    memory.signals().get_salience()
+                ),
+            }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "publish_memory",
+        description = "Publish one of your instruction or semantic memories to your organization's shared pool, where every member's searches find it next to their own memories. WHEN TO USE: When a memory holds a team convention, policy or fact everyone in the organization should rely on, not something personal to this user. Only works with an api key that belongs to an organization. Published memories are marked with the org scope in results."
+    )]
+    async fn publish_memory(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(PublishMemoryRequest { memory_id }): Parameters<PublishMemoryRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let Some(org_id) = extract_org_id(&parts) else {
+            return Err(McpError::new(
+                ErrorCode::INVALID_REQUEST,
+                "Publishing needs an api key that belongs to an organization",
+                None,
+            ));
+        };
+        let memory = self
+            .controller(&parts)
+            .publish(parse_memory_id(&memory_id)?, org_id)
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent {
+                text: format!(
+                    "memory {} is now in the pool of {}",
+                    memory.get_id(),
+                    memory.context().org_id().unwrap_or_default()
+                ),
+            }),
+            None,
+        )]))
+    }
+
+    #[tool(
+        name = "share_memory",
+        description = "Share one of your memories with the user's other agents, or make it private again. Memories are private to the agent that saved them unless saved with shared = true. WHEN TO USE: When a memory holds knowledge other agents working for the same user should also rely on, such as a user preference; set shared = false to take it back."
+    )]
+    async fn share_memory(
+        &self,
+        Extension(parts): Extension<Parts>,
+        Parameters(ShareMemoryRequest { memory_id, shared }): Parameters<ShareMemoryRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let memory = self
+            .controller(&parts)
+            .set_visibility(parse_memory_id(&memory_id)?, visibility(shared))
+            .await
+            .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Annotated::new(
+            RawContent::Text(RawTextContent {
+                text: format!(
+                    "memory {} is now {}",
+                    memory.get_id(),
+                    memory.visibility().as_str().to_lowercase()
+                ),
+            }),
+            None,
+        )]))
+    }
 }
 
 #[tool_handler]
@@ -199,3 +1046,128 @@ impl rmcp::ServerHandler for McpService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use std::sync::Arc;
+    use umem_ai::{
+        embed::{embed, EmbeddingRequest},
+        AIProvider, EmbeddingModel, LanguageModel, MockProvider, RerankingModel, SpendTracker,
+    };
+    use umem_annotations::AnnotationPrompt;
+    use umem_auth::KeyScope;
+    use umem_controller::{MemoryTemplates, ResponsePolicy};
+    use umem_core::{LifecycleState, MemoryContent, TemporalMetadata};
+    use umem_vector_store::{InMemoryVectorStore, VectorStoreBase};
+    use uuid::Uuid;
+
+    fn controller(vector_store: Arc<InMemoryVectorStore>) -> MemoryController {
+        let provider = Arc::new(AIProvider::Mock(MockProvider::new()));
+        MemoryController {
+            vector_store,
+            embedding_model: Arc::new(EmbeddingModel {
+                provider: Arc::clone(&provider),
+                model_name: "mock".to_string(),
+            }),
+            reranking_model: Arc::new(RerankingModel {
+                provider: Arc::clone(&provider),
+                model_name: "mock".to_string(),
+            }),
+            language_model: Arc::new(LanguageModel {
+                provider,
+                model_name: "mock".to_string(),
+            }),
+            annotation_prompt: Arc::new(AnnotationPrompt::default()),
+            translator: None,
+            keep_raw_content: false,
+            blob_store: None,
+            graph: None,
+            response_policy: Arc::new(ResponsePolicy::default()),
+            search_config: Arc::default(),
+            salience_config: Arc::default(),
+            trash_config: Arc::default(),
+            topics_config: Arc::default(),
+            budget: Arc::default(),
+            spend: Arc::new(SpendTracker::default()),
+            templates: Arc::new(MemoryTemplates::default()),
+            moderation: None,
+            journal: None,
+            views: None,
+            principal: None,
+        }
+    }
+
+    async fn store(controller: &MemoryController, agent_id: &str, summary: &str) {
+        let memory = Memory::builder()
+            .id(Uuid::new_v4())
+            .content(MemoryContent::with_summary(summary).unwrap())
+            .context(
+                MemoryContext::new(Some("alice".to_string()), Some(agent_id.to_string()), None)
+                    .unwrap(),
+            )
+            .kind(MemoryKind::Semantic)
+            .lifecycle(LifecycleState::Active)
+            .temporal(TemporalMetadata::new(Utc::now()))
+            .build()
+            .finish()
+            .unwrap();
+        let request = EmbeddingRequest::builder()
+            .model(Arc::clone(&controller.embedding_model))
+            .input(vec![summary.to_string()])
+            .build();
+        let vector = embed(request).await.unwrap().embeddings.remove(0);
+        controller
+            .vector_store
+            .insert(&[vector.as_slice()], &[&memory])
+            .await
+            .unwrap();
+    }
+
+    /// Request parts as the api key middleware leaves them for `api_key`.
+    fn parts_for(api_key: ApiKey) -> Parts {
+        let (mut parts, _) = Request::builder()
+            .header(USER_ID_HEADER, "alice")
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts.extensions.insert(api_key);
+        parts
+    }
+
+    #[tokio::test]
+    async fn search_with_an_agent_scoped_key_returns_the_agents_memories() {
+        let controller = controller(Arc::new(InMemoryVectorStore::default()));
+        store(&controller, "planner", "User prefers green tea").await;
+        store(&controller, "coder", "User prefers black coffee").await;
+        let service = McpService::new(controller, ResponseFormat::Json, false);
+
+        let api_key = ApiKey {
+            scope: KeyScope {
+                user_id: Some("alice".to_string()),
+                agent_id: Some("planner".to_string()),
+                org_id: None,
+            },
+            access: Access::Read,
+            ..ApiKey::for_user("alice")
+        };
+        let result = service
+            .search(
+                Extension(parts_for(api_key)),
+                Parameters(GetMemoriesByQueryRequest {
+                    query: "what does the user drink".to_string(),
+                    format: None,
+                    max_tokens: None,
+                    as_of: None,
+                    query_language: None,
+                }),
+            )
+            .await
+            .unwrap();
+
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.contains("green tea"));
+        assert!(!text.contains("black coffee"));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::Mem0State;
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use umem_auth::{Access, ApiKey, AuthError};
+use umem_controller::CreateMemoryRequest;
+use umem_core::{Memory, MemoryContext, MemoryId, ParsedQuery};
+
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl ToString) -> Self {
+        Self {
+            status,
+            message: message.to_string(),
+        }
+    }
+
+    fn internal(message: impl ToString) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+impl From<AuthError> for ApiError {
+    fn from(e: AuthError) -> Self {
+        let status = match e {
+            AuthError::InvalidKey => StatusCode::UNAUTHORIZED,
+            AuthError::ReadOnly | AuthError::OutOfScope { .. } => StatusCode::FORBIDDEN,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self::new(status, e)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+type Result<T> = std::result::Result<T, ApiError>;
+
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+}
+
+/// mem0 accepts either a plain string or a chat transcript.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Messages {
+    Text(String),
+    Chat(Vec<Message>),
+}
+
+impl Messages {
+    fn into_raw_content(self) -> String {
+        match self {
+            Messages::Text(text) => text,
+            Messages::Chat(messages) => messages
+                .iter()
+                .map(|message| format!("{}: {}", message.role, message.content))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddRequest {
+    pub messages: Messages,
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchRequest {
+    pub query: String,
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+    pub limit: Option<usize>,
+    /// Not part of mem0's API: read `query` as enfinyte's query language, e.g.
+    /// `kind:procedural tag:deploy after:2024-01-01 "exact phrase" rollback`.
+    #[serde(default)]
+    pub query_language: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContextParams {
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+}
+
+/// A memory in mem0's response shape; tags are reported as categories.
+#[derive(Debug, Serialize)]
+pub struct Mem0Memory {
+    pub id: String,
+    pub memory: String,
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+    pub categories: Vec<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl From<&Memory> for Mem0Memory {
+    fn from(memory: &Memory) -> Self {
+        let rfc3339 =
+            |timestamp: i64| DateTime::from_timestamp(timestamp, 0).map(|time| time.to_rfc3339());
+
+        Self {
+            id: memory.get_id().to_string(),
+            memory: memory.get_summary().clone(),
+            user_id: memory.context().user_id().map(str::to_owned),
+            agent_id: memory.context().agent_id().map(str::to_owned),
+            run_id: memory.context().run_id().map(str::to_owned),
+            categories: memory.content().tags().clone(),
+            created_at: rfc3339(memory.temporal().created_at()),
+            updated_at: memory.temporal().updated_at().and_then(rfc3339),
+        }
+    }
+}
+
+/// Resolves `Authorization: Token <key>` (mem0's scheme) or `Bearer <key>`. Returns
+/// `None` when api keys are not configured, in which case every request is allowed.
+async fn authenticate(state: &Mem0State, headers: &HeaderMap) -> Result<Option<ApiKey>> {
+    let Some(api_keys) = state.api_keys.as_ref() else {
+        return Ok(None);
+    };
+
+    let secret = headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value
+                .strip_prefix("Token ")
+                .or_else(|| value.strip_prefix("Bearer "))
+        })
+        .ok_or_else(|| ApiError::new(StatusCode::UNAUTHORIZED, "api key must be passed"))?;
+
+    Ok(Some(api_keys.verify(secret).await?))
+}
+
+fn authorize(api_key: Option<&ApiKey>, context: &MemoryContext, access: Access) -> Result<()> {
+    match api_key {
+        Some(api_key) => Ok(api_key.authorize(context, access)?),
+        None => Ok(()),
+    }
+}
+
+fn context(
+    user_id: Option<String>,
+    agent_id: Option<String>,
+    run_id: Option<String>,
+) -> Result<MemoryContext> {
+    MemoryContext::new(user_id, agent_id, run_id)
+        .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))
+}
+
+fn present(
+    state: &Mem0State,
+    api_key: Option<&ApiKey>,
+    mut memories: Vec<Memory>,
+) -> Vec<Mem0Memory> {
+    state
+        .memory_controller
+        .redact(&mut memories, api_key.map(|api_key| api_key.access));
+    memories.iter().map(Mem0Memory::from).collect()
+}
+
+pub async fn add(
+    State(state): State<Arc<Mem0State>>,
+    headers: HeaderMap,
+    Json(request): Json<AddRequest>,
+) -> Result<Json<Value>> {
+    let api_key = authenticate(&state, &headers).await?;
+    let context = context(
+        request.user_id.clone(),
+        request.agent_id.clone(),
+        request.run_id.clone(),
+    )?;
+    authorize(api_key.as_ref(), &context, Access::ReadWrite)?;
+
+    let memories = state
+        .memory_controller
+        .for_principal(api_key.as_ref())
+        .create(
+            CreateMemoryRequest::builder()
+                .user_id(request.user_id)
+                .agent_id(request.agent_id)
+                .run_id(request.run_id)
+                .raw_content(request.messages.into_raw_content())
+                .build(),
+            None,
+        )
+        .await
+        .map_err(ApiError::internal)?;
+
+    let results: Vec<Value> = present(&state, api_key.as_ref(), memories)
+        .into_iter()
+        .map(|memory| json!({ "id": memory.id, "memory": memory.memory, "event": "ADD" }))
+        .collect();
+
+    Ok(Json(json!({ "results": results })))
+}
+
+pub async fn get_all(
+    State(state): State<Arc<Mem0State>>,
+    headers: HeaderMap,
+    Query(params): Query<ContextParams>,
+) -> Result<Json<Vec<Mem0Memory>>> {
+    let api_key = authenticate(&state, &headers).await?;
+    let context = context(params.user_id, params.agent_id, params.run_id)?;
+    authorize(api_key.as_ref(), &context, Access::Read)?;
+
+    let memories = state
+        .memory_controller
+        .for_principal(api_key.as_ref())
+        .list_with_context(context)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(present(&state, api_key.as_ref(), memories)))
+}
+
+pub async fn search(
+    State(state): State<Arc<Mem0State>>,
+    headers: HeaderMap,
+    Json(request): Json<SearchRequest>,
+) -> Result<Json<Vec<Mem0Memory>>> {
+    let api_key = authenticate(&state, &headers).await?;
+    let context = context(request.user_id, request.agent_id, request.run_id)?;
+    authorize(api_key.as_ref(), &context, Access::Read)?;
+
+    let controller = state.memory_controller.for_principal(api_key.as_ref());
+    let mut memories = match request.query_language {
+        true => {
+            let parsed: ParsedQuery = request
+                .query
+                .parse()
+                .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))?;
+            controller
+                .parsed_search_with_context(context, parsed, None)
+                .await
+                .map(|results| results.memories)
+        }
+        false => {
+            controller
+                .search_with_context(context, request.query, None)
+                .await
+        }
+    }
+    .map_err(ApiError::internal)?;
+    if let Some(limit) = request.limit {
+        memories.truncate(limit);
+    }
+
+    Ok(Json(present(&state, api_key.as_ref(), memories)))
+}
+
+pub async fn get(
+    State(state): State<Arc<Mem0State>>,
+    headers: HeaderMap,
+    Path(id): Path<MemoryId>,
+) -> Result<Json<Mem0Memory>> {
+    let api_key = authenticate(&state, &headers).await?;
+    let memory = state
+        .memory_controller
+        .for_principal(api_key.as_ref())
+        .get(id)
+        .await
+        .map_err(|e| ApiError::new(StatusCode::NOT_FOUND, e))?;
+    authorize(api_key.as_ref(), memory.context(), Access::Read)?;
+
+    Ok(Json(
+        present(&state, api_key.as_ref(), vec![memory]).remove(0),
+    ))
+}
+
+pub async fn delete(
+    State(state): State<Arc<Mem0State>>,
+    headers: HeaderMap,
+    Path(id): Path<MemoryId>,
+) -> Result<Json<Value>> {
+    let api_key = authenticate(&state, &headers).await?;
+    let memory = state
+        .memory_controller
+        .for_principal(api_key.as_ref())
+        .get(id)
+        .await
+        .map_err(|e| ApiError::new(StatusCode::NOT_FOUND, e))?;
+    authorize(api_key.as_ref(), memory.context(), Access::ReadWrite)?;
+
+    state
+        .memory_controller
+        .for_principal(api_key.as_ref())
+        .delete(id)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(json!({ "message": "Memory deleted successfully!" })))
+}
@@ -0,0 +1,82 @@
+mod handlers;
+
+use anyhow::Result;
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use std::{os::unix::fs::FileTypeExt, path::Path, sync::Arc};
+use tracing::info;
+use umem_auth::ApiKeyStore;
+use umem_controller::MemoryController;
+
+#[derive(Clone)]
+struct Mem0State {
+    memory_controller: MemoryController,
+    api_keys: Option<Arc<ApiKeyStore>>,
+}
+
+/// HTTP surface shaped like mem0's `/v1/memories` REST API, so apps built on a mem0
+/// client can switch to enfinyte by changing the host and key.
+pub struct MemoryServiceMem0;
+
+impl MemoryServiceMem0 {
+    pub fn router(
+        memory_controller: MemoryController,
+        api_keys: Option<Arc<ApiKeyStore>>,
+    ) -> Router {
+        let state = Arc::new(Mem0State {
+            memory_controller,
+            api_keys,
+        });
+
+        // NOTE: mem0 clients always send the trailing slash; accept both spellings
+        Router::new()
+            .route("/v1/memories/", post(handlers::add).get(handlers::get_all))
+            .route("/v1/memories", post(handlers::add).get(handlers::get_all))
+            .route("/v1/memories/search/", post(handlers::search))
+            .route("/v1/memories/search", post(handlers::search))
+            .route(
+                "/v1/memories/{id}/",
+                get(handlers::get).delete(handlers::delete),
+            )
+            .route(
+                "/v1/memories/{id}",
+                get(handlers::get).delete(handlers::delete),
+            )
+            .with_state(state)
+    }
+
+    pub async fn run_server(
+        config: umem_config::Mem0,
+        memory_controller: MemoryController,
+        api_keys: Option<Arc<ApiKeyStore>>,
+    ) -> Result<()> {
+        let router = Self::router(memory_controller, api_keys);
+        match config.socket_path {
+            Some(socket_path) => {
+                let listener = bind_unix(&socket_path)?;
+                info!(
+                    "mem0 compatible server listening on {}",
+                    socket_path.display()
+                );
+                axum::serve(listener, router).await?;
+            }
+            None => {
+                info!("mem0 compatible server listening on {}", config.server_addr);
+                let listener = tokio::net::TcpListener::bind(config.server_addr).await?;
+                axum::serve(listener, router).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Binds `path`; a socket already there is stale, since only one server may own it.
+fn bind_unix(path: &Path) -> std::io::Result<tokio::net::UnixListener> {
+    if std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        std::fs::remove_file(path)?;
+    }
+    tokio::net::UnixListener::bind(path)
+}
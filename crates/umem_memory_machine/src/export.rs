@@ -0,0 +1,73 @@
+use crate::MemoryMachine;
+use anyhow::{anyhow, bail};
+use chrono::Utc;
+use object_store::{
+    aws::AmazonS3Builder, gcp::GoogleCloudStorageBuilder, local::LocalFileSystem, ObjectStore,
+};
+use std::{str::FromStr, sync::Arc, time::Duration};
+use tracing::warn;
+use umem_controller::{ExportFormat, ExportRequest};
+
+const DEFAULT_EXPORT_INTERVAL_SECS: u64 = 60 * 60;
+const DEFAULT_EXPORT_BATCH_SIZE: u32 = 1000;
+
+/// Opens the bucket named by `[export] bucket`.
+fn object_store(bucket: &str) -> anyhow::Result<Arc<dyn ObjectStore>> {
+    match bucket.split_once("://") {
+        Some(("s3", name)) => Ok(Arc::new(
+            AmazonS3Builder::from_env().with_bucket_name(name).build()?,
+        )),
+        Some(("gs", name)) => Ok(Arc::new(
+            GoogleCloudStorageBuilder::from_env()
+                .with_bucket_name(name)
+                .build()?,
+        )),
+        Some(("file", directory)) => {
+            std::fs::create_dir_all(directory)?;
+            Ok(Arc::new(LocalFileSystem::new_with_prefix(directory)?))
+        }
+        _ => bail!("invalid export bucket: {bucket} (expected s3://, gs:// or file://)"),
+    }
+}
+
+impl MemoryMachine {
+    /// Writes the memories changed since the previous run to `[export] bucket` every
+    /// `[export] interval_secs`.
+    pub async fn run_export(&self) -> anyhow::Result<()> {
+        let config = self
+            .config
+            .export
+            .as_ref()
+            .ok_or_else(|| anyhow!("[export] is not configured"))?;
+        let target = object_store(&config.bucket)?;
+        let format = match config.format.as_deref() {
+            Some(format) => ExportFormat::from_str(format)?,
+            None => ExportFormat::default(),
+        };
+        let prefix = config
+            .prefix
+            .as_deref()
+            .unwrap_or_default()
+            .trim_matches('/')
+            .to_string();
+        let interval_secs = config.interval_secs.unwrap_or(DEFAULT_EXPORT_INTERVAL_SECS);
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+        loop {
+            interval.tick().await;
+            let request = ExportRequest::builder()
+                .target(Arc::clone(&target))
+                .prefix(prefix.clone())
+                .format(format)
+                .batch_size(config.batch_size.unwrap_or(DEFAULT_EXPORT_BATCH_SIZE))
+                .build();
+            if let Err(e) = self
+                .memory_controller
+                .export_changed(request, Utc::now())
+                .await
+            {
+                warn!("export failed: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::MemoryMachine;
+use anyhow::anyhow;
+use std::{iter::zip, time::Duration};
+use tracing::{info, warn};
+use umem_config::Feed;
+use umem_controller::CreateMemoryRequest;
+use umem_embed_engine_core::{
+    data_type::DataType,
+    engine::{AddRequest, AddResponse, AddSource, Engine, ResyncResponse},
+    vectordb::VectorDB,
+};
+
+impl MemoryMachine {
+    /// Polls every `[feeds]` source each `poll_interval_secs`, storing new items in
+    /// `engine` and, for feeds with `distill`, turning them into memories tagged per
+    /// feed. `engine` needs a data source store: it remembers which feeds were added, so
+    /// later polls only embed new items. Items that drop out of a feed are removed from
+    /// the engine; memories distilled from them stay.
+    pub async fn run_feed_poller<LLM, DB, Embedder>(
+        &self,
+        mut engine: Engine<LLM, DB, Embedder>,
+    ) -> anyhow::Result<()>
+    where
+        DB: VectorDB,
+    {
+        let config = self
+            .config
+            .feeds
+            .clone()
+            .ok_or_else(|| anyhow!("[feeds] is not configured"))?;
+        let mut interval = tokio::time::interval(Duration::from_secs(config.poll_interval_secs));
+
+        loop {
+            interval.tick().await;
+            for feed in &config.sources {
+                match self.poll_feed(&mut engine, feed).await {
+                    Ok(count) => info!("polled feed {}: {} new chunks", feed.url, count),
+                    Err(e) => warn!("polling feed {} failed: {}", feed.url, e),
+                }
+            }
+        }
+    }
+
+    async fn poll_feed<LLM, DB, Embedder>(
+        &self,
+        engine: &mut Engine<LLM, DB, Embedder>,
+        feed: &Feed,
+    ) -> anyhow::Result<usize>
+    where
+        DB: VectorDB,
+    {
+        if feed.distill && feed.user_id.is_none() && feed.agent_id.is_none() {
+            return Err(anyhow!("a distilled feed needs a user_id or agent_id"));
+        }
+
+        let source = AddSource::Url(feed.url.clone());
+        let (chunk_ids, documents) = if engine.data_source(&source).await?.is_some() {
+            let ResyncResponse {
+                added_ids,
+                added_documents,
+                ..
+            } = engine.resync(source).await?;
+            (added_ids, added_documents)
+        } else {
+            let data_type = match DataType::try_from_source(&source).await? {
+                DataType::Substack => DataType::Substack,
+                _ => DataType::RssFeed,
+            };
+            let AddResponse { chunks, .. } = engine
+                .add_chunks(AddRequest::new(source).data_type(data_type))
+                .await?;
+            (chunks.ids, chunks.documents)
+        };
+
+        if feed.distill {
+            let tag = feed.tag.clone().unwrap_or_else(|| feed_host(&feed.url));
+            for (chunk_id, document) in zip(&chunk_ids, documents) {
+                let request = CreateMemoryRequest::builder()
+                    .user_id(feed.user_id.clone())
+                    .agent_id(feed.agent_id.clone())
+                    .raw_content(document)
+                    .sources(vec![chunk_id.clone()])
+                    .tags(vec![tag.clone()])
+                    .build();
+                self.memory_controller.create(request, None).await?;
+            }
+        }
+
+        Ok(chunk_ids.len())
+    }
+}
+
+fn feed_host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', '?', '#'])
+        .next()
+        .unwrap_or(rest)
+        .to_string()
+}
@@ -0,0 +1,67 @@
+use crate::MemoryMachine;
+use anyhow::anyhow;
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use std::{sync::Arc, time::Duration};
+use tracing::info;
+use umem_controller::{Readiness, ReadinessCheck};
+
+const DEFAULT_HEALTH_TIMEOUT_MS: u64 = 2000;
+
+impl MemoryMachine {
+    /// Serves `/healthz`, which answers as long as the process runs, and `/readyz`,
+    /// which answers 503 while the vector store or embedder is unreachable or the
+    /// configuration is inconsistent, on `[health] server_addr`.
+    pub async fn run_health(self: Arc<Self>) -> anyhow::Result<()> {
+        let config = self
+            .config
+            .health
+            .clone()
+            .ok_or_else(|| anyhow!("[health] is not configured"))?;
+
+        let router = Router::new()
+            .route("/healthz", get(|| async { "ok" }))
+            .route("/readyz", get(readyz))
+            .with_state(self);
+
+        info!("health endpoints listening on {}", config.server_addr);
+        let listener = tokio::net::TcpListener::bind(config.server_addr).await?;
+        axum::serve(listener, router).await?;
+        Ok(())
+    }
+
+    pub async fn readiness(&self) -> Readiness {
+        let timeout = Duration::from_millis(
+            self.config
+                .health
+                .as_ref()
+                .and_then(|health| health.timeout_ms)
+                .unwrap_or(DEFAULT_HEALTH_TIMEOUT_MS),
+        );
+
+        // NOTE: the dimensions the vector store was created with, which embeddings must match
+        let vector_store =
+            umem_ai::resolve_vector_store(&self.config.vector_store, &self.config.embedding_model);
+        let dimensions = vector_store
+            .as_ref()
+            .ok()
+            .and_then(|vector_store| vector_store.embedding_model_dimensions())
+            .map(usize::from);
+
+        let mut readiness = self.memory_controller.readiness(timeout, dimensions).await;
+        readiness.checks.push(ReadinessCheck::new(
+            "config",
+            vector_store.map(|_| ()).map_err(|e| e.to_string()),
+        ));
+        Readiness::new(readiness.checks)
+    }
+}
+
+async fn readyz(State(machine): State<Arc<MemoryMachine>>) -> (StatusCode, Json<Readiness>) {
+    let readiness = machine.readiness().await;
+    let status = if readiness.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness))
+}
@@ -0,0 +1,29 @@
+use crate::MemoryMachine;
+use anyhow::anyhow;
+use std::time::Duration;
+use tracing::warn;
+
+const DEFAULT_FLUSH_INTERVAL_SECS: u64 = 30;
+
+impl MemoryMachine {
+    /// Replays journaled create requests into the stores every `[journal]
+    /// flush_interval_secs`.
+    pub async fn run_journal_flush(&self) -> anyhow::Result<()> {
+        let config = self
+            .config
+            .journal
+            .as_ref()
+            .ok_or_else(|| anyhow!("[journal] is not configured"))?;
+        let interval_secs = config
+            .flush_interval_secs
+            .unwrap_or(DEFAULT_FLUSH_INTERVAL_SECS);
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.memory_controller.flush_journal().await {
+                warn!("journal flush failed: {}", e);
+            }
+        }
+    }
+}
@@ -1,17 +1,37 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use thiserror::Error;
+use tracing::info;
 use typed_builder::TypedBuilder;
 use umem_ai::{
     EmbeddingModel, EmbeddingModelError, LanguageModel, LanguageModelError, RerankingModel,
-    RerankingModelError,
+    RerankingModelError, SpendTracker,
 };
-use umem_config::CONFIG;
-use umem_controller::MemoryController;
-use umem_grpc_server::MemoryServiceGrpc;
+use umem_annotations::{AnnotationError, AnnotationPrompt, Translator};
+use umem_auth::ApiKeyStore;
+use umem_blob::{BlobStore, BlobStoreBase, BlobStoreError};
+use umem_config::{AppConfig, CONFIG};
+use umem_controller::{
+    Journal, MemoryController, MemoryControllerError, MemoryTemplateError, MemoryTemplates,
+    ModerationError, ModerationPolicy, ResponsePolicy, ResponsePolicyError, SavedViewError,
+    ViewStore,
+};
+use umem_graph::{GraphStore, GraphStoreBase};
+use umem_grpc_server::{AdminServiceGrpc, MemoryServiceGrpc};
 use umem_mcp::MemoryServiceMcp;
+use umem_mem0::MemoryServiceMem0;
 use umem_vector_store::{VectorStore, VectorStoreBase, VectorStoreError};
 
+mod export;
+mod feeds;
+mod health;
+mod journal;
+mod salience;
+mod topics;
+mod trash;
+
+const DEFAULT_KEEP_RAW_CONTENT: bool = true;
+
 #[derive(Debug, Error)]
 pub enum MemoryMachineError {
     #[error("memory machine vector_store failed : {0}")]
@@ -25,64 +45,220 @@ pub enum MemoryMachineError {
 
     #[error("memory machine embedding failed : {0}")]
     EmbeddingModelError(#[from] EmbeddingModelError),
+
+    #[error("memory machine annotation prompt failed : {0}")]
+    AnnotationError(#[from] AnnotationError),
+
+    #[error("memory machine response policy failed : {0}")]
+    ResponsePolicyError(#[from] ResponsePolicyError),
+
+    #[error("memory machine templates failed : {0}")]
+    MemoryTemplateError(#[from] MemoryTemplateError),
+
+    #[error("memory machine moderation failed : {0}")]
+    ModerationError(#[from] ModerationError),
+
+    #[error("memory machine blob store failed : {0}")]
+    BlobStoreError(#[from] BlobStoreError),
+
+    #[error("memory machine saved views failed : {0}")]
+    SavedViewError(#[from] SavedViewError),
+
+    #[error("memory machine graph rebuild failed : {0}")]
+    GraphRebuildError(#[source] MemoryControllerError),
+
+    #[error("memory machine spend tracking at {0} failed : {1}")]
+    SpendError(PathBuf, #[source] std::io::Error),
+}
+
+/// Continues from the spend saved at `[budget] path`, so the caps hold across restarts.
+fn spend_tracker(budget: &umem_config::Budget) -> Result<Arc<SpendTracker>, MemoryMachineError> {
+    let spend = Arc::new(SpendTracker::default());
+    if let Some(path) = budget.path.as_ref() {
+        spend
+            .persist_to(path)
+            .map_err(|e| MemoryMachineError::SpendError(path.clone(), e))?;
+    }
+    Ok(spend)
 }
 
 #[derive(TypedBuilder)]
 pub struct MemoryMachine {
     pub memory_controller: MemoryController,
+    pub config: AppConfig,
 }
 
 #[derive(TypedBuilder)]
 pub struct MemoryMachineOptions {
+    #[builder(default = None)]
     vector_store: Option<Arc<dyn VectorStoreBase + Send + Sync>>,
+    #[builder(default = None)]
     embedder: Option<Arc<EmbeddingModel>>,
+    #[builder(default = None)]
     reranking_model: Option<Arc<RerankingModel>>,
+    #[builder(default = None)]
     language_model: Option<Arc<LanguageModel>>,
+    #[builder(default = None)]
+    annotation_prompt: Option<Arc<AnnotationPrompt>>,
+    #[builder(default = None)]
+    graph: Option<Arc<dyn GraphStoreBase + Send + Sync>>,
+    #[builder(default = None)]
+    blob_store: Option<Arc<dyn BlobStoreBase + Send + Sync>>,
 }
 
 impl MemoryMachine {
     pub async fn new() -> Result<Self, MemoryMachineError> {
-        Ok(Self {
-            memory_controller: MemoryController {
-                embedding_model: EmbeddingModel::get_model().await?,
-                vector_store: VectorStore::get_store().await?,
-                reranking_model: RerankingModel::get_model().await?,
-                language_model: LanguageModel::get_model().await?,
-            },
-        })
+        Self::build(&CONFIG, MemoryMachineOptions::builder().build()).await
+    }
+
+    pub async fn from_config(config: AppConfig) -> Result<Self, MemoryMachineError> {
+        Self::build(&config, MemoryMachineOptions::builder().build()).await
+    }
+
+    pub async fn new_with(
+        config: &AppConfig,
+        options: MemoryMachineOptions,
+    ) -> Result<Self, MemoryMachineError> {
+        Self::build(config, options).await
     }
 
-    pub async fn new_with(options: MemoryMachineOptions) -> Result<Self, MemoryMachineError> {
-        let embedding_model = options
-            .embedder
-            .unwrap_or(EmbeddingModel::get_model().await?);
-        let vector_store = options
-            .vector_store
-            .unwrap_or(VectorStore::get_store().await?);
-        let reranking_model = options
-            .reranking_model
-            .unwrap_or(RerankingModel::get_model().await?);
-        let language_model = options
-            .language_model
-            .unwrap_or(LanguageModel::get_model().await?);
-
-        Ok(Self {
+    /// Builds everything from `config` except the dependencies `overrides` injects.
+    /// Injected models record their spend wherever they were built to, not against
+    /// `[budget]`.
+    async fn build(
+        config: &AppConfig,
+        overrides: MemoryMachineOptions,
+    ) -> Result<Self, MemoryMachineError> {
+        let spend = spend_tracker(&config.budget)?;
+        let embedding_model = match overrides.embedder {
+            Some(embedding_model) => embedding_model,
+            None => EmbeddingModel::from_config(&config.embedding_model, &spend).await?,
+        };
+        let vector_store = match overrides.vector_store {
+            Some(vector_store) => vector_store,
+            None => VectorStore::from_app_config(config).await?,
+        };
+        let reranking_model = match overrides.reranking_model {
+            Some(reranking_model) => reranking_model,
+            None => RerankingModel::from_config(&config.reranking_model, &spend).await?,
+        };
+        let language_model = match overrides.language_model {
+            Some(language_model) => language_model,
+            None => LanguageModel::from_config(&config.language_model, &spend).await?,
+        };
+        let annotation_prompt = match overrides.annotation_prompt {
+            Some(annotation_prompt) => annotation_prompt,
+            None => Arc::new(AnnotationPrompt::from_config(&config.annotation)?),
+        };
+        let graph = overrides
+            .graph
+            .or_else(|| config.graph.as_ref().map(GraphStore::from_config));
+        let blob_store = match (overrides.blob_store, config.blob_store.as_ref()) {
+            (Some(blob_store), _) => Some(blob_store),
+            (None, Some(blob_store)) => Some(BlobStore::from_config(blob_store)?),
+            (None, None) => None,
+        };
+
+        let machine = Self {
             memory_controller: MemoryController {
                 embedding_model,
                 vector_store,
                 reranking_model,
                 language_model,
+                annotation_prompt,
+                translator: Translator::from_config(&config.annotation)?,
+                keep_raw_content: config
+                    .annotation
+                    .keep_raw_content
+                    .unwrap_or(DEFAULT_KEEP_RAW_CONTENT),
+                graph,
+                response_policy: Arc::new(ResponsePolicy::from_config(&config.redaction)?),
+                search_config: Arc::new(config.search.clone()),
+                salience_config: Arc::new(config.salience.clone()),
+                trash_config: Arc::new(config.trash.clone()),
+                topics_config: Arc::new(config.topics.clone()),
+                budget: Arc::new(config.budget.clone()),
+                spend,
+                templates: Arc::new(MemoryTemplates::from_config(&config.templates)?),
+                moderation: match config.moderation.as_ref() {
+                    Some(moderation) => Some(Arc::new(ModerationPolicy::from_config(moderation)?)),
+                    None => None,
+                },
+                blob_store,
+                journal: config.journal.as_ref().map(Journal::from_config),
+                views: match config.views.as_ref() {
+                    Some(views) => Some(ViewStore::from_config(views).await?),
+                    None => None,
+                },
+                principal: None,
             },
+            config: config.clone(),
+        };
+
+        // NOTE: the graph may be kept in memory only, so restore it from the stored links
+        if machine.memory_controller.graph.is_some() {
+            let relations = machine
+                .memory_controller
+                .rebuild_graph()
+                .await
+                .map_err(MemoryMachineError::GraphRebuildError)?;
+            info!("restored {relations} relations to the graph");
+        }
+
+        Ok(machine)
+    }
+
+    async fn api_keys(&self) -> anyhow::Result<Option<Arc<ApiKeyStore>>> {
+        Ok(match self.config.api_keys.as_ref() {
+            Some(config) => Some(Arc::new(ApiKeyStore::from_config(config).await?)),
+            None => None,
         })
     }
 
     pub async fn run_grpc(&self) -> anyhow::Result<()> {
-        MemoryServiceGrpc::run_server(CONFIG.grpc.clone(), self.memory_controller.clone()).await?;
+        MemoryServiceGrpc::run_server(
+            self.config.grpc.clone(),
+            self.memory_controller.clone(),
+            self.api_keys().await?,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Serves admin_v1 on `[admin] server_addr`, behind `[admin] token` instead of the
+    /// api keys the memory services take.
+    pub async fn run_admin(&self) -> anyhow::Result<()> {
+        let config = self
+            .config
+            .admin
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("[admin] is not configured"))?;
+        AdminServiceGrpc::run_server(config, self.memory_controller.clone()).await?;
         Ok(())
     }
 
     pub async fn run_mcp(&self) -> anyhow::Result<()> {
-        MemoryServiceMcp::run_server(CONFIG.mcp.clone(), self.memory_controller.clone()).await?;
+        MemoryServiceMcp::run_server(
+            self.config.mcp.clone(),
+            self.memory_controller.clone(),
+            self.api_keys().await?,
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn run_mem0(&self) -> anyhow::Result<()> {
+        let config = self
+            .config
+            .mem0
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("[mem0] is not configured"))?;
+        MemoryServiceMem0::run_server(
+            config,
+            self.memory_controller.clone(),
+            self.api_keys().await?,
+        )
+        .await?;
         Ok(())
     }
 }
@@ -0,0 +1,26 @@
+use crate::MemoryMachine;
+use chrono::Utc;
+use std::time::Duration;
+use tracing::warn;
+
+const DEFAULT_DECAY_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+impl MemoryMachine {
+    /// Lowers the salience of memories nobody marked as used, every `[salience]
+    /// decay_interval_secs`.
+    pub async fn run_salience_decay(&self) -> anyhow::Result<()> {
+        let interval_secs = self
+            .config
+            .salience
+            .decay_interval_secs
+            .unwrap_or(DEFAULT_DECAY_INTERVAL_SECS);
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.memory_controller.decay_unused(Utc::now()).await {
+                warn!("salience decay failed: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::MemoryMachine;
+use std::time::Duration;
+use tracing::warn;
+
+const DEFAULT_TOPIC_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+impl MemoryMachine {
+    /// Reclusters every user's memories into labeled topics, every `[topics]
+    /// interval_secs`.
+    pub async fn run_topic_clustering(&self) -> anyhow::Result<()> {
+        let interval_secs = self
+            .config
+            .topics
+            .interval_secs
+            .unwrap_or(DEFAULT_TOPIC_INTERVAL_SECS);
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.memory_controller.cluster_all_topics().await {
+                warn!("topic clustering failed: {}", e);
+            }
+        }
+    }
+}
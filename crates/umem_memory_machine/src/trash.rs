@@ -0,0 +1,26 @@
+use crate::MemoryMachine;
+use chrono::Utc;
+use std::time::Duration;
+use tracing::warn;
+
+const DEFAULT_PURGE_INTERVAL_SECS: u64 = 60 * 60;
+
+impl MemoryMachine {
+    /// Permanently removes memories whose `[trash] retention_days` ran out, every
+    /// `[trash] purge_interval_secs`.
+    pub async fn run_trash_purge(&self) -> anyhow::Result<()> {
+        let interval_secs = self
+            .config
+            .trash
+            .purge_interval_secs
+            .unwrap_or(DEFAULT_PURGE_INTERVAL_SECS);
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.memory_controller.purge_expired(Utc::now()).await {
+                warn!("trash purge failed: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Conversions between the memory_v1 and memory_v2 messages. The server answers in
+//! memory_v2 and downgrades for memory_v1 clients; upgrading lets memory_v1 data be
+//! carried in memory_v2 messages.
+
+use crate::{memory_v1, memory_v2};
+
+fn percent(value: f32) -> u32 {
+    (value * 100.0).round() as u32
+}
+
+fn fraction(percent: u32) -> f32 {
+    percent as f32 / 100.0
+}
+
+/// Pool memories carry only an org_id; memory_v1 has no scope of its own.
+fn scope(context: Option<&memory_v1::MemoryContext>) -> memory_v2::MemoryScope {
+    match context {
+        Some(memory_v1::MemoryContext {
+            user_id: None,
+            agent_id: None,
+            run_id: None,
+            org_id: Some(_),
+        }) => memory_v2::MemoryScope::Org,
+        _ => memory_v2::MemoryScope::Personal,
+    }
+}
+
+impl From<memory_v2::TemporalMetadata> for memory_v1::TemporalMetadata {
+    fn from(temporal: memory_v2::TemporalMetadata) -> Self {
+        Self {
+            created_at: temporal.created_at,
+            updated_at: temporal.updated_at,
+            archived_at: temporal.archived_at,
+        }
+    }
+}
+
+impl From<memory_v1::TemporalMetadata> for memory_v2::TemporalMetadata {
+    fn from(temporal: memory_v1::TemporalMetadata) -> Self {
+        Self {
+            created_at: temporal.created_at,
+            updated_at: temporal.updated_at,
+            archived_at: temporal.archived_at,
+            deleted_at: None,
+            suppressed_at: None,
+        }
+    }
+}
+
+impl From<memory_v2::MemoryScores> for memory_v1::MemorySignals {
+    fn from(scores: memory_v2::MemoryScores) -> Self {
+        Self {
+            certainty: percent(scores.certainty),
+            salience: percent(scores.salience),
+        }
+    }
+}
+
+impl From<memory_v1::MemorySignals> for memory_v2::MemoryScores {
+    fn from(signals: memory_v1::MemorySignals) -> Self {
+        let certainty = fraction(signals.certainty);
+        let salience = fraction(signals.salience);
+        Self {
+            certainty,
+            salience,
+            score: certainty * salience,
+        }
+    }
+}
+
+impl From<memory_v2::Memory> for memory_v1::Memory {
+    fn from(memory: memory_v2::Memory) -> Self {
+        let related = |kind: memory_v2::RelationKind| {
+            memory
+                .relations
+                .iter()
+                .filter(move |relation| relation.kind == kind as i32)
+                .map(|relation| relation.memory_id.clone())
+        };
+        let contradicts = related(memory_v2::RelationKind::Contradicts).collect();
+        let superseded_by = related(memory_v2::RelationKind::SupersededBy).next();
+
+        Self {
+            id: memory.id,
+            context: memory.context,
+            lifecycle: memory.lifecycle,
+            kind: memory.kind,
+            content: memory.content,
+            signals: memory.scores.map(Into::into),
+            temporal: memory.temporal.map(Into::into),
+            provenance: memory.provenance,
+            contradicts,
+            superseded_by,
+        }
+    }
+}
+
+impl From<memory_v1::Memory> for memory_v2::Memory {
+    fn from(memory: memory_v1::Memory) -> Self {
+        let relation =
+            |kind: memory_v2::RelationKind, memory_id: String| memory_v2::MemoryRelation {
+                kind: kind as i32,
+                memory_id,
+            };
+        let relations = memory
+            .contradicts
+            .into_iter()
+            .map(|id| relation(memory_v2::RelationKind::Contradicts, id))
+            .chain(
+                memory
+                    .superseded_by
+                    .map(|id| relation(memory_v2::RelationKind::SupersededBy, id)),
+            )
+            .collect();
+        let scope = scope(memory.context.as_ref());
+
+        Self {
+            id: memory.id,
+            context: memory.context,
+            lifecycle: memory.lifecycle,
+            kind: memory.kind,
+            content: memory.content,
+            temporal: memory.temporal.map(Into::into),
+            provenance: memory.provenance,
+            metadata: None,
+            relations,
+            scores: memory.signals.map(Into::into),
+            scope: scope as i32,
+            version: 0,
+        }
+    }
+}
+
+fn downgrade(memories: Vec<memory_v2::Memory>) -> Vec<memory_v1::Memory> {
+    memories.into_iter().map(Into::into).collect()
+}
+
+impl From<memory_v2::MemoryResponse> for memory_v1::MemoryResponse {
+    fn from(response: memory_v2::MemoryResponse) -> Self {
+        Self {
+            memory: response.memory.map(Into::into),
+        }
+    }
+}
+
+impl From<memory_v2::CreateMemoryResponse> for memory_v1::CreateMemoryResponse {
+    fn from(response: memory_v2::CreateMemoryResponse) -> Self {
+        Self {
+            memories: downgrade(response.memories),
+            queued: response.queued,
+        }
+    }
+}
+
+impl From<memory_v2::MemoryListResponse> for memory_v1::MemoryListResponse {
+    fn from(response: memory_v2::MemoryListResponse) -> Self {
+        Self {
+            memories: downgrade(response.memories),
+            facets: response.facets,
+            rerank_skipped: response.rerank_skipped,
+            timed_out: response.timed_out,
+            timings: response.timings,
+            explanation: response.explanation,
+        }
+    }
+}
+
+impl From<memory_v2::ContextPackResponse> for memory_v1::ContextPackResponse {
+    fn from(response: memory_v2::ContextPackResponse) -> Self {
+        Self {
+            text: response.text,
+            tokens: response.tokens,
+            memories: downgrade(response.memories),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    fn v1_memory() -> memory_v1::Memory {
+        memory_v1::Memory {
+            id: "0b6f3a52-6f0e-4d5b-9f4e-1b1f0c7a9d21".to_string(),
+            context: Some(memory_v1::MemoryContext {
+                user_id: Some("alice".to_string()),
+                agent_id: Some("planner".to_string()),
+                run_id: None,
+                org_id: None,
+            }),
+            lifecycle: memory_v1::LifecycleState::Superseded as i32,
+            kind: memory_v1::MemoryKind::Semantic as i32,
+            content: Some(memory_v1::MemoryContent {
+                summary: "Alice prefers tea over coffee".to_string(),
+                tags: vec!["preferences".to_string()],
+            }),
+            signals: Some(memory_v1::MemorySignals {
+                certainty: 87,
+                salience: 33,
+            }),
+            temporal: Some(memory_v1::TemporalMetadata {
+                created_at: 1_700_000_000,
+                updated_at: Some(1_700_000_100),
+                archived_at: None,
+            }),
+            provenance: Some(memory_v1::Provenance {
+                origin: memory_v1::ProvenanceOrigin::User as i32,
+                method: Some(memory_v1::ProvenanceMethod {
+                    method: Some(memory_v1::provenance_method::Method::Direct(true)),
+                }),
+                moderation: None,
+                chain: vec![memory_v1::ProvenanceMethod {
+                    method: Some(memory_v1::provenance_method::Method::Direct(true)),
+                }],
+            }),
+            contradicts: vec!["5d1c7e0a-2b9f-4c59-8d43-0f6a1e2b3c4d".to_string()],
+            superseded_by: Some("9a8b7c6d-5e4f-4a3b-8c2d-1e0f9a8b7c6d".to_string()),
+        }
+    }
+
+    #[test]
+    fn v1_memory_survives_a_round_trip_through_v2() {
+        let memory = v1_memory();
+
+        let upgraded = memory_v2::Memory::from(memory.clone());
+        assert_eq!(upgraded.relations.len(), 2);
+        assert_eq!(memory_v1::Memory::from(upgraded), memory);
+    }
+
+    #[test]
+    fn every_v1_percentage_survives_the_v2_scores() {
+        for value in 0..=100 {
+            let signals = memory_v1::MemorySignals {
+                certainty: value,
+                salience: 100 - value,
+            };
+            let scores = memory_v2::MemoryScores::from(signals);
+            assert_eq!(memory_v1::MemorySignals::from(scores), signals);
+        }
+    }
+
+    #[test]
+    fn shared_fields_keep_their_wire_format() {
+        let memory = v1_memory();
+        let upgraded = memory_v2::Memory::from(memory.clone());
+
+        let decoded = memory_v2::Memory::decode(memory.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded.id, upgraded.id);
+        assert_eq!(decoded.context, upgraded.context);
+        assert_eq!(decoded.lifecycle, upgraded.lifecycle);
+        assert_eq!(decoded.kind, upgraded.kind);
+        assert_eq!(decoded.content, upgraded.content);
+        assert_eq!(decoded.temporal, upgraded.temporal);
+        assert_eq!(decoded.provenance, upgraded.provenance);
+
+        let mut upgraded = upgraded;
+        if let Some(temporal) = upgraded.temporal.as_mut() {
+            temporal.deleted_at = Some(1_700_000_200);
+        }
+        let decoded = memory_v1::Memory::decode(upgraded.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded.temporal, memory.temporal);
+        assert_eq!(decoded.content, memory.content);
+        assert_eq!(decoded.provenance, memory.provenance);
+    }
+
+    #[test]
+    fn downgrades_list_responses() {
+        let response = memory_v2::MemoryListResponse {
+            memories: vec![v1_memory().into()],
+            facets: None,
+            rerank_skipped: true,
+            timed_out: false,
+            timings: Some(memory_v1::SearchTimings {
+                embed_micros: 10,
+                store_micros: 20,
+                rerank_micros: 30,
+            }),
+            explanation: None,
+        };
+
+        let downgraded = memory_v1::MemoryListResponse::from(response.clone());
+        assert_eq!(downgraded.memories, vec![v1_memory()]);
+        assert_eq!(downgraded.rerank_skipped, response.rerank_skipped);
+        assert_eq!(downgraded.timings, response.timings);
+    }
+}
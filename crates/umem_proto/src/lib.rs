@@ -1,9 +1,9 @@
-mod memory_v1;
+pub mod admin_v1;
+pub mod memory_v1;
+pub mod memory_v2;
 
-pub const FILE_DESCRIPTOR_SET: &[u8] = include_bytes!("./memory_service_descriptor.bin");
+mod compat;
 
-mod generated {
-    include!("./memory_v1.rs");
-}
+pub const FILE_DESCRIPTOR_SET: &[u8] = include_bytes!("./memory_service_descriptor.bin");
 
-pub use generated::*;
+pub use memory_v1::*;
@@ -7,6 +7,9 @@ pub struct MemoryContext {
     pub agent_id: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(string, optional, tag = "3")]
     pub run_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// Set on memories published to an organization's pool, which carry no other ids.
+    #[prost(string, optional, tag = "4")]
+    pub org_id: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct MemoryContent {
@@ -15,6 +18,7 @@ pub struct MemoryContent {
     #[prost(string, repeated, tag = "2")]
     pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
+/// Signals are percentages in the range \[0, 100\].
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct MemorySignals {
     #[prost(uint32, tag = "1")]
@@ -44,8 +48,14 @@ pub struct SummarizedMethod {
     pub model: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ImportedMethod {
+    /// The memory store it was migrated from, e.g. "mem0".
+    #[prost(string, tag = "1")]
+    pub source: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ProvenanceMethod {
-    #[prost(oneof = "provenance_method::Method", tags = "1, 2, 3")]
+    #[prost(oneof = "provenance_method::Method", tags = "1, 2, 3, 4")]
     pub method: ::core::option::Option<provenance_method::Method>,
 }
 /// Nested message and enum types in `ProvenanceMethod`.
@@ -58,14 +68,32 @@ pub mod provenance_method {
         Extracted(super::ExtractedMethod),
         #[prost(message, tag = "3")]
         Summarized(super::SummarizedMethod),
+        #[prost(message, tag = "4")]
+        Imported(super::ImportedMethod),
     }
 }
+/// Set when the memory was moderated before it was stored.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Moderation {
+    /// What judged the content, e.g. "rules" or "omni-moderation-latest".
+    #[prost(string, tag = "1")]
+    pub moderator: ::prost::alloc::string::String,
+    /// Categories the content was flagged for but stored anyway.
+    #[prost(string, repeated, tag = "2")]
+    pub flagged: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct Provenance {
     #[prost(enumeration = "ProvenanceOrigin", tag = "1")]
     pub origin: i32,
+    /// The last step of `chain`, which produced the current content.
     #[prost(message, optional, tag = "2")]
     pub method: ::core::option::Option<ProvenanceMethod>,
+    #[prost(message, optional, tag = "3")]
+    pub moderation: ::core::option::Option<Moderation>,
+    /// Every step that produced the memory's current content, oldest first.
+    #[prost(message, repeated, tag = "4")]
+    pub chain: ::prost::alloc::vec::Vec<ProvenanceMethod>,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct Memory {
@@ -85,6 +113,12 @@ pub struct Memory {
     pub temporal: ::core::option::Option<TemporalMetadata>,
     #[prost(message, optional, tag = "8")]
     pub provenance: ::core::option::Option<Provenance>,
+    /// Ids of stored memories this memory contradicts.
+    #[prost(string, repeated, tag = "9")]
+    pub contradicts: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Id of the memory that replaced this one, set when it is superseded.
+    #[prost(string, optional, tag = "10")]
+    pub superseded_by: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ContextFilter {
@@ -94,6 +128,9 @@ pub struct ContextFilter {
     pub agent_id: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(string, optional, tag = "3")]
     pub run_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// Also match the pool of this organization, or only the pool when no other id is set.
+    #[prost(string, optional, tag = "4")]
+    pub org_id: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct TemporalFilter {
@@ -124,6 +161,12 @@ pub struct CreateMemoryRequest {
     #[prost(string, tag = "4")]
     pub raw_content: ::prost::alloc::string::String,
 }
+/// Creates the memories for each request; the requests may belong to different contexts.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct CreateMemoriesRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub requests: ::prost::alloc::vec::Vec<CreateMemoryRequest>,
+}
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct DeleteMemoryRequest {
     #[prost(string, tag = "1")]
@@ -138,6 +181,9 @@ pub struct GetMemoryRequest {
 pub struct ListMemoriesRequest {
     #[prost(message, optional, tag = "1")]
     pub context: ::core::option::Option<ContextFilter>,
+    /// Also return kind/tag/month counts over the whole context.
+    #[prost(bool, tag = "2")]
+    pub include_facets: bool,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct SearchMemoriesRequest {
@@ -145,16 +191,193 @@ pub struct SearchMemoriesRequest {
     pub query: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub context: ::core::option::Option<ContextFilter>,
+    /// Also return kind/tag/month counts over the whole context.
+    #[prost(bool, tag = "3")]
+    pub include_facets: bool,
+    /// Also return how the search arrived at its results.
+    #[prost(bool, tag = "4")]
+    pub explain: bool,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct BuildContextRequest {
+    #[prost(string, tag = "1")]
+    pub query: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub context: ::core::option::Option<ContextFilter>,
+    #[prost(uint32, tag = "3")]
+    pub token_budget: u32,
+    #[prost(enumeration = "ContextPolicy", tag = "4")]
+    pub policy: i32,
+}
+/// Reports that retrieved memories were used, raising their salience.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MarkUsedRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Whether a memory retrieved for query helped.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FeedbackRequest {
+    #[prost(string, tag = "1")]
+    pub memory_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub query: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub helpful: bool,
+}
+/// Stores one memory as given, without annotation. ListTemplates says which fields
+/// its kind needs.
+#[derive(Clone, PartialEq, Eq, ::prost::Message)]
+pub struct CreateStructuredMemoryRequest {
+    #[prost(string, optional, tag = "1")]
+    pub user_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "2")]
+    pub agent_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub run_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "4")]
+    pub summary: ::prost::alloc::string::String,
+    #[prost(enumeration = "MemoryKind", tag = "5")]
+    pub kind: i32,
+    #[prost(string, repeated, tag = "6")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Values for the fields of the kind's template; due_at is an RFC 3339 timestamp.
+    #[prost(map = "string, string", tag = "7")]
+    pub fields: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ListTemplatesRequest {}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct MemoryResponse {
     #[prost(message, optional, tag = "1")]
     pub memory: ::core::option::Option<Memory>,
 }
+/// The memories a create call stored, with their generated ids, summaries and kinds.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct CreateMemoryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub memories: ::prost::alloc::vec::Vec<Memory>,
+    /// Set when the requests were journaled instead; memories is then empty and the
+    /// memories become searchable after the next journal flush.
+    #[prost(bool, tag = "2")]
+    pub queued: bool,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FacetCount {
+    #[prost(string, tag = "1")]
+    pub value: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MemoryFacets {
+    #[prost(message, repeated, tag = "1")]
+    pub kinds: ::prost::alloc::vec::Vec<FacetCount>,
+    #[prost(message, repeated, tag = "2")]
+    pub tags: ::prost::alloc::vec::Vec<FacetCount>,
+    /// Creation month as YYYY-MM (UTC), newest first.
+    #[prost(message, repeated, tag = "3")]
+    pub months: ::prost::alloc::vec::Vec<FacetCount>,
+    /// Topic labels assigned by clustering.
+    #[prost(message, repeated, tag = "4")]
+    pub topics: ::prost::alloc::vec::Vec<FacetCount>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MemoryListResponse {
     #[prost(message, repeated, tag = "1")]
     pub memories: ::prost::alloc::vec::Vec<Memory>,
+    /// Set only when include_facets was requested.
+    #[prost(message, optional, tag = "2")]
+    pub facets: ::core::option::Option<MemoryFacets>,
+    /// Set when the reranker failed and memories are in vector-similarity order.
+    #[prost(bool, tag = "3")]
+    pub rerank_skipped: bool,
+    /// Set when the search deadline ran out and memories are what was found in time.
+    #[prost(bool, tag = "4")]
+    pub timed_out: bool,
+    /// Server-side time per search stage. Not set for listings.
+    #[prost(message, optional, tag = "5")]
+    pub timings: ::core::option::Option<SearchTimings>,
+    /// Set only when explain was requested.
+    #[prost(message, optional, tag = "6")]
+    pub explanation: ::core::option::Option<SearchExplanation>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SearchTimings {
+    #[prost(uint64, tag = "1")]
+    pub embed_micros: u64,
+    #[prost(uint64, tag = "2")]
+    pub store_micros: u64,
+    #[prost(uint64, tag = "3")]
+    pub rerank_micros: u64,
+}
+/// How a search arrived at its results; timings are in SearchTimings.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchExplanation {
+    /// The queries that were embedded and searched; the original query is last.
+    #[prost(string, repeated, tag = "1")]
+    pub sub_queries: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// The vector store's translation of the search's filters.
+    #[prost(string, tag = "2")]
+    pub filter: ::prost::alloc::string::String,
+    /// Everything the vector stage found, in the order it was found.
+    #[prost(message, repeated, tag = "3")]
+    pub candidates: ::prost::alloc::vec::Vec<ExplainedCandidate>,
+}
+/// One memory the vector stage found, with its score at each later stage.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExplainedCandidate {
+    #[prost(string, tag = "1")]
+    pub memory_id: ::prost::alloc::string::String,
+    /// Index into SearchExplanation.sub_queries of the query that found it.
+    #[prost(uint32, tag = "2")]
+    pub sub_query: u32,
+    #[prost(float, tag = "3")]
+    pub vector_score: f32,
+    /// Unset when the memory was not reranked: it was already found by another
+    /// sub-query, cut by the candidate cap, or the reranker was skipped.
+    #[prost(float, optional, tag = "4")]
+    pub rerank_score: ::core::option::Option<f32>,
+    /// The score results were ordered by, when \[search.fusion\] is configured.
+    #[prost(float, optional, tag = "5")]
+    pub fused_score: ::core::option::Option<f32>,
+    /// Position in memories; unset when the memory was not returned.
+    #[prost(uint32, optional, tag = "6")]
+    pub rank: ::core::option::Option<u32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContextPackResponse {
+    #[prost(string, tag = "1")]
+    pub text: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub tokens: u32,
+    #[prost(message, repeated, tag = "3")]
+    pub memories: ::prost::alloc::vec::Vec<Memory>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TemplateField {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub required: bool,
+    #[prost(string, tag = "3")]
+    pub description: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MemoryTemplate {
+    #[prost(enumeration = "MemoryKind", tag = "1")]
+    pub kind: i32,
+    #[prost(message, repeated, tag = "2")]
+    pub fields: ::prost::alloc::vec::Vec<TemplateField>,
+}
+/// Kinds without a template take any fields.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TemplateListResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub templates: ::prost::alloc::vec::Vec<MemoryTemplate>,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -162,6 +385,9 @@ pub enum LifecycleState {
     Unspecified = 0,
     Active = 1,
     Archived = 2,
+    Deleted = 3,
+    Superseded = 4,
+    Suppressed = 5,
 }
 impl LifecycleState {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -173,6 +399,9 @@ impl LifecycleState {
             Self::Unspecified => "LIFECYCLE_STATE_UNSPECIFIED",
             Self::Active => "LIFECYCLE_STATE_ACTIVE",
             Self::Archived => "LIFECYCLE_STATE_ARCHIVED",
+            Self::Deleted => "LIFECYCLE_STATE_DELETED",
+            Self::Superseded => "LIFECYCLE_STATE_SUPERSEDED",
+            Self::Suppressed => "LIFECYCLE_STATE_SUPPRESSED",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -181,6 +410,9 @@ impl LifecycleState {
             "LIFECYCLE_STATE_UNSPECIFIED" => Some(Self::Unspecified),
             "LIFECYCLE_STATE_ACTIVE" => Some(Self::Active),
             "LIFECYCLE_STATE_ARCHIVED" => Some(Self::Archived),
+            "LIFECYCLE_STATE_DELETED" => Some(Self::Deleted),
+            "LIFECYCLE_STATE_SUPERSEDED" => Some(Self::Superseded),
+            "LIFECYCLE_STATE_SUPPRESSED" => Some(Self::Suppressed),
             _ => None,
         }
     }
@@ -231,6 +463,38 @@ impl MemoryKind {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum ContextPolicy {
+    Unspecified = 0,
+    Relevance = 1,
+    Recency = 2,
+    Importance = 3,
+}
+impl ContextPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "CONTEXT_POLICY_UNSPECIFIED",
+            Self::Relevance => "CONTEXT_POLICY_RELEVANCE",
+            Self::Recency => "CONTEXT_POLICY_RECENCY",
+            Self::Importance => "CONTEXT_POLICY_IMPORTANCE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CONTEXT_POLICY_UNSPECIFIED" => Some(Self::Unspecified),
+            "CONTEXT_POLICY_RELEVANCE" => Some(Self::Relevance),
+            "CONTEXT_POLICY_RECENCY" => Some(Self::Recency),
+            "CONTEXT_POLICY_IMPORTANCE" => Some(Self::Importance),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum ProvenanceOrigin {
     Unspecified = 0,
     User = 1,
@@ -351,7 +615,7 @@ pub mod memory_service_client {
         pub async fn create_memory(
             &mut self,
             request: impl tonic::IntoRequest<super::CreateMemoryRequest>,
-        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status> {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
             })?;
@@ -424,6 +688,92 @@ pub mod memory_service_client {
                 .insert(GrpcMethod::new("memory_v1.MemoryService", "SearchMemories"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn build_context(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BuildContextRequest>,
+        ) -> std::result::Result<tonic::Response<super::ContextPackResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/memory_v1.MemoryService/BuildContext");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v1.MemoryService", "BuildContext"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn mark_used(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MarkUsedRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v1.MemoryService/MarkUsed");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v1.MemoryService", "MarkUsed"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn feedback(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FeedbackRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v1.MemoryService/Feedback");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v1.MemoryService", "Feedback"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn create_memories(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateMemoriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v1.MemoryService/CreateMemories");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v1.MemoryService", "CreateMemories"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn create_structured_memory(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateStructuredMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v1.MemoryService/CreateStructuredMemory");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v1.MemoryService", "CreateStructuredMemory"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_templates(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListTemplatesRequest>,
+        ) -> std::result::Result<tonic::Response<super::TemplateListResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v1.MemoryService/ListTemplates");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v1.MemoryService", "ListTemplates"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -442,7 +792,7 @@ pub mod memory_service_server {
         async fn create_memory(
             &self,
             request: tonic::Request<super::CreateMemoryRequest>,
-        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status>;
         async fn delete_memory(
             &self,
             request: tonic::Request<super::DeleteMemoryRequest>,
@@ -459,6 +809,30 @@ pub mod memory_service_server {
             &self,
             request: tonic::Request<super::SearchMemoriesRequest>,
         ) -> std::result::Result<tonic::Response<super::MemoryListResponse>, tonic::Status>;
+        async fn build_context(
+            &self,
+            request: tonic::Request<super::BuildContextRequest>,
+        ) -> std::result::Result<tonic::Response<super::ContextPackResponse>, tonic::Status>;
+        async fn mark_used(
+            &self,
+            request: tonic::Request<super::MarkUsedRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn feedback(
+            &self,
+            request: tonic::Request<super::FeedbackRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn create_memories(
+            &self,
+            request: tonic::Request<super::CreateMemoriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status>;
+        async fn create_structured_memory(
+            &self,
+            request: tonic::Request<super::CreateStructuredMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status>;
+        async fn list_templates(
+            &self,
+            request: tonic::Request<super::ListTemplatesRequest>,
+        ) -> std::result::Result<tonic::Response<super::TemplateListResponse>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct MemoryServiceServer<T> {
@@ -539,7 +913,7 @@ pub mod memory_service_server {
                     impl<T: MemoryService> tonic::server::UnaryService<super::CreateMemoryRequest>
                         for CreateMemorySvc<T>
                     {
-                        type Response = ();
+                        type Response = super::CreateMemoryResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
@@ -736,6 +1110,252 @@ pub mod memory_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/memory_v1.MemoryService/BuildContext" => {
+                    #[allow(non_camel_case_types)]
+                    struct BuildContextSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::BuildContextRequest>
+                        for BuildContextSvc<T>
+                    {
+                        type Response = super::ContextPackResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BuildContextRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::build_context(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = BuildContextSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v1.MemoryService/MarkUsed" => {
+                    #[allow(non_camel_case_types)]
+                    struct MarkUsedSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::MarkUsedRequest>
+                        for MarkUsedSvc<T>
+                    {
+                        type Response = ();
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MarkUsedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::mark_used(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = MarkUsedSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v1.MemoryService/Feedback" => {
+                    #[allow(non_camel_case_types)]
+                    struct FeedbackSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::FeedbackRequest>
+                        for FeedbackSvc<T>
+                    {
+                        type Response = ();
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FeedbackRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::feedback(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FeedbackSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v1.MemoryService/CreateMemories" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateMemoriesSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::CreateMemoriesRequest>
+                        for CreateMemoriesSvc<T>
+                    {
+                        type Response = super::CreateMemoryResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateMemoriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::create_memories(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateMemoriesSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v1.MemoryService/CreateStructuredMemory" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateStructuredMemorySvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::CreateStructuredMemoryRequest>
+                        for CreateStructuredMemorySvc<T>
+                    {
+                        type Response = super::CreateMemoryResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateStructuredMemoryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::create_structured_memory(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateStructuredMemorySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v1.MemoryService/ListTemplates" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListTemplatesSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::ListTemplatesRequest>
+                        for ListTemplatesSvc<T>
+                    {
+                        type Response = super::TemplateListResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListTemplatesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::list_templates(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListTemplatesSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => Box::pin(async move {
                     let mut response = http::Response::new(tonic::body::Body::default());
                     let headers = response.headers_mut();
@@ -0,0 +1,1635 @@
+// This file is @generated by prost-build.
+/// Fields 1-3 match memory_v1.TemporalMetadata.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TemporalMetadata {
+    #[prost(int64, tag = "1")]
+    pub created_at: i64,
+    #[prost(int64, optional, tag = "2")]
+    pub updated_at: ::core::option::Option<i64>,
+    #[prost(int64, optional, tag = "3")]
+    pub archived_at: ::core::option::Option<i64>,
+    #[prost(int64, optional, tag = "4")]
+    pub deleted_at: ::core::option::Option<i64>,
+    #[prost(int64, optional, tag = "5")]
+    pub suppressed_at: ::core::option::Option<i64>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MemorySchedule {
+    #[prost(int64, tag = "1")]
+    pub due_at: i64,
+    #[prost(enumeration = "Recurrence", tag = "2")]
+    pub recurrence: i32,
+}
+#[derive(Clone, PartialEq, Eq, ::prost::Message)]
+pub struct MemoryMetadata {
+    /// Language the summary was written in, when it was detected.
+    #[prost(string, optional, tag = "1")]
+    pub language: ::core::option::Option<::prost::alloc::string::String>,
+    /// The summary before it was translated, when it was.
+    #[prost(string, optional, tag = "2")]
+    pub original_summary: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag = "3")]
+    pub sources: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "4")]
+    pub schedule: ::core::option::Option<MemorySchedule>,
+    #[prost(uint32, tag = "5")]
+    pub use_count: u32,
+    #[prost(int64, optional, tag = "6")]
+    pub last_used_at: ::core::option::Option<i64>,
+    /// Values of the kind's template fields; the schedule holds due_at.
+    #[prost(map = "string, string", tag = "7")]
+    pub fields: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MemoryRelation {
+    #[prost(enumeration = "RelationKind", tag = "1")]
+    pub kind: i32,
+    #[prost(string, tag = "2")]
+    pub memory_id: ::prost::alloc::string::String,
+}
+/// Scores are in the range \[0, 1\], unlike the percentages of memory_v1.MemorySignals.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct MemoryScores {
+    #[prost(float, tag = "1")]
+    pub certainty: f32,
+    #[prost(float, tag = "2")]
+    pub salience: f32,
+    /// What memories are ranked by outside of a search: certainty * salience.
+    #[prost(float, tag = "3")]
+    pub score: f32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Memory {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub context: ::core::option::Option<super::memory_v1::MemoryContext>,
+    #[prost(enumeration = "super::memory_v1::LifecycleState", tag = "3")]
+    pub lifecycle: i32,
+    #[prost(enumeration = "super::memory_v1::MemoryKind", tag = "4")]
+    pub kind: i32,
+    #[prost(message, optional, tag = "5")]
+    pub content: ::core::option::Option<super::memory_v1::MemoryContent>,
+    #[prost(message, optional, tag = "7")]
+    pub temporal: ::core::option::Option<TemporalMetadata>,
+    #[prost(message, optional, tag = "8")]
+    pub provenance: ::core::option::Option<super::memory_v1::Provenance>,
+    #[prost(message, optional, tag = "11")]
+    pub metadata: ::core::option::Option<MemoryMetadata>,
+    #[prost(message, repeated, tag = "12")]
+    pub relations: ::prost::alloc::vec::Vec<MemoryRelation>,
+    #[prost(message, optional, tag = "13")]
+    pub scores: ::core::option::Option<MemoryScores>,
+    #[prost(enumeration = "MemoryScope", tag = "14")]
+    pub scope: i32,
+    /// Bumped by every stored change; pass it as UpdateMemoryRequest.expected_version.
+    #[prost(uint64, tag = "15")]
+    pub version: u64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetMemoryStatsRequest {
+    #[prost(message, optional, tag = "1")]
+    pub context: ::core::option::Option<super::memory_v1::ContextFilter>,
+}
+/// Saves query, written in the query language (e.g. `kind:procedural tag:deploy rollback`),
+/// as the view name of context, replacing an earlier view of that name.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SaveViewRequest {
+    #[prost(message, optional, tag = "1")]
+    pub context: ::core::option::Option<super::memory_v1::ContextFilter>,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub query: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ListViewsRequest {
+    #[prost(message, optional, tag = "1")]
+    pub context: ::core::option::Option<super::memory_v1::ContextFilter>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct RunViewRequest {
+    #[prost(message, optional, tag = "1")]
+    pub context: ::core::option::Option<super::memory_v1::ContextFilter>,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// Also return how the results were found, as for SearchMemories.
+    #[prost(bool, tag = "3")]
+    pub explain: bool,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct DeleteViewRequest {
+    #[prost(message, optional, tag = "1")]
+    pub context: ::core::option::Option<super::memory_v1::ContextFilter>,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+}
+/// Replaces the memory's summary, keeping its tags, and embeds it again.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UpdateMemoryRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub summary: ::prost::alloc::string::String,
+    /// When set, the update fails with ABORTED unless the memory is still at this version.
+    #[prost(uint64, optional, tag = "3")]
+    pub expected_version: ::core::option::Option<u64>,
+}
+/// Memories of context created, updated, archived, deleted or suppressed after since.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetChangesRequest {
+    #[prost(message, optional, tag = "1")]
+    pub context: ::core::option::Option<super::memory_v1::ContextFilter>,
+    /// Unix seconds. 0 returns every memory; afterwards pass the previous response's until.
+    #[prost(int64, tag = "2")]
+    pub since: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MemoryResponse {
+    #[prost(message, optional, tag = "1")]
+    pub memory: ::core::option::Option<Memory>,
+}
+/// The memories a create call stored, with their generated ids, summaries and kinds.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateMemoryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub memories: ::prost::alloc::vec::Vec<Memory>,
+    /// Set when the requests were journaled instead; memories is then empty and the
+    /// memories become searchable after the next journal flush.
+    #[prost(bool, tag = "2")]
+    pub queued: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MemoryListResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub memories: ::prost::alloc::vec::Vec<Memory>,
+    /// Set only when include_facets was requested.
+    #[prost(message, optional, tag = "2")]
+    pub facets: ::core::option::Option<super::memory_v1::MemoryFacets>,
+    /// Set when the reranker failed and memories are in vector-similarity order.
+    #[prost(bool, tag = "3")]
+    pub rerank_skipped: bool,
+    /// Set when the search deadline ran out and memories are what was found in time.
+    #[prost(bool, tag = "4")]
+    pub timed_out: bool,
+    /// Server-side time per search stage. Not set for listings.
+    #[prost(message, optional, tag = "5")]
+    pub timings: ::core::option::Option<super::memory_v1::SearchTimings>,
+    /// Set only when explain was requested.
+    #[prost(message, optional, tag = "6")]
+    pub explanation: ::core::option::Option<super::memory_v1::SearchExplanation>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContextPackResponse {
+    #[prost(string, tag = "1")]
+    pub text: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub tokens: u32,
+    #[prost(message, repeated, tag = "3")]
+    pub memories: ::prost::alloc::vec::Vec<Memory>,
+}
+/// What is stored for a context. Deleted and suppressed memories are not counted.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MemoryStatsResponse {
+    #[prost(uint64, tag = "1")]
+    pub total: u64,
+    /// Most frequent first.
+    #[prost(message, repeated, tag = "2")]
+    pub kinds: ::prost::alloc::vec::Vec<super::memory_v1::FacetCount>,
+    /// Most frequent first.
+    #[prost(message, repeated, tag = "3")]
+    pub lifecycles: ::prost::alloc::vec::Vec<super::memory_v1::FacetCount>,
+    /// Size of the stored memories, not counting embeddings or blob store content.
+    #[prost(uint64, tag = "4")]
+    pub storage_bytes: u64,
+    /// Memories created per month as YYYY-MM (UTC), newest first.
+    #[prost(message, repeated, tag = "5")]
+    pub months: ::prost::alloc::vec::Vec<super::memory_v1::FacetCount>,
+    /// When a memory was last created, updated, archived or used.
+    #[prost(int64, optional, tag = "6")]
+    pub last_activity_at: ::core::option::Option<i64>,
+}
+/// A named search saved for a context.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SavedView {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub query: ::prost::alloc::string::String,
+    #[prost(int64, tag = "3")]
+    pub created_at: i64,
+    #[prost(int64, tag = "4")]
+    pub updated_at: i64,
+}
+/// Sorted by name.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ViewListResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub views: ::prost::alloc::vec::Vec<SavedView>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangesResponse {
+    /// Deleted and suppressed memories carry temporal.deleted_at or temporal.suppressed_at,
+    /// so caches can drop them.
+    #[prost(message, repeated, tag = "1")]
+    pub memories: ::prost::alloc::vec::Vec<Memory>,
+    /// Pass as since next time. Memories changed during that second may be returned again.
+    #[prost(int64, tag = "2")]
+    pub until: i64,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RelationKind {
+    Unspecified = 0,
+    /// The related memory is a stored memory this one contradicts.
+    Contradicts = 1,
+    /// The related memory replaced this one.
+    SupersededBy = 2,
+}
+impl RelationKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "RELATION_KIND_UNSPECIFIED",
+            Self::Contradicts => "RELATION_KIND_CONTRADICTS",
+            Self::SupersededBy => "RELATION_KIND_SUPERSEDED_BY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RELATION_KIND_UNSPECIFIED" => Some(Self::Unspecified),
+            "RELATION_KIND_CONTRADICTS" => Some(Self::Contradicts),
+            "RELATION_KIND_SUPERSEDED_BY" => Some(Self::SupersededBy),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Recurrence {
+    /// A one-off schedule.
+    Unspecified = 0,
+    Daily = 1,
+    Weekly = 2,
+    Monthly = 3,
+    Yearly = 4,
+}
+impl Recurrence {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "RECURRENCE_UNSPECIFIED",
+            Self::Daily => "RECURRENCE_DAILY",
+            Self::Weekly => "RECURRENCE_WEEKLY",
+            Self::Monthly => "RECURRENCE_MONTHLY",
+            Self::Yearly => "RECURRENCE_YEARLY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RECURRENCE_UNSPECIFIED" => Some(Self::Unspecified),
+            "RECURRENCE_DAILY" => Some(Self::Daily),
+            "RECURRENCE_WEEKLY" => Some(Self::Weekly),
+            "RECURRENCE_MONTHLY" => Some(Self::Monthly),
+            "RECURRENCE_YEARLY" => Some(Self::Yearly),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MemoryScope {
+    Unspecified = 0,
+    /// Stored for a user, agent or run.
+    Personal = 1,
+    /// Published to the pool of the organization in context.org_id.
+    Org = 2,
+}
+impl MemoryScope {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "MEMORY_SCOPE_UNSPECIFIED",
+            Self::Personal => "MEMORY_SCOPE_PERSONAL",
+            Self::Org => "MEMORY_SCOPE_ORG",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "MEMORY_SCOPE_UNSPECIFIED" => Some(Self::Unspecified),
+            "MEMORY_SCOPE_PERSONAL" => Some(Self::Personal),
+            "MEMORY_SCOPE_ORG" => Some(Self::Org),
+            _ => None,
+        }
+    }
+}
+/// Generated client implementations.
+pub mod memory_service_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value
+    )]
+    use tonic::codegen::http::Uri;
+    use tonic::codegen::*;
+    #[derive(Debug, Clone)]
+    pub struct MemoryServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl MemoryServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> MemoryServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::Body>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> MemoryServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<http::Request<tonic::body::Body>>>::Error:
+                Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            MemoryServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn create_memory(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::CreateMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/CreateMemory");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "CreateMemory"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_memory(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::DeleteMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/DeleteMemory");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "DeleteMemory"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_memory(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::GetMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/GetMemory");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "GetMemory"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_memories(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::ListMemoriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryListResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/ListMemories");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "ListMemories"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn search_memories(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::SearchMemoriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryListResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/SearchMemories");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "SearchMemories"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn build_context(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::BuildContextRequest>,
+        ) -> std::result::Result<tonic::Response<super::ContextPackResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/BuildContext");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "BuildContext"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn mark_used(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::MarkUsedRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/MarkUsed");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "MarkUsed"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn feedback(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::FeedbackRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/Feedback");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "Feedback"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn create_memories(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::CreateMemoriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/CreateMemories");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "CreateMemories"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn create_structured_memory(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::CreateStructuredMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/CreateStructuredMemory");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "CreateStructuredMemory"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_templates(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::memory_v1::ListTemplatesRequest>,
+        ) -> std::result::Result<tonic::Response<super::super::memory_v1::TemplateListResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/ListTemplates");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "ListTemplates"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_memory_stats(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetMemoryStatsRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryStatsResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/GetMemoryStats");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "GetMemoryStats"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn save_view(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SaveViewRequest>,
+        ) -> std::result::Result<tonic::Response<super::SavedView>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/SaveView");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "SaveView"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_views(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListViewsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ViewListResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/ListViews");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "ListViews"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn run_view(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RunViewRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryListResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/RunView");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "RunView"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_view(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteViewRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/DeleteView");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "DeleteView"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_changes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetChangesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ChangesResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/GetChanges");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "GetChanges"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn update_memory(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/memory_v2.MemoryService/UpdateMemory");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("memory_v2.MemoryService", "UpdateMemory"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod memory_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with MemoryServiceServer.
+    #[async_trait]
+    pub trait MemoryService: std::marker::Send + std::marker::Sync + 'static {
+        async fn create_memory(
+            &self,
+            request: tonic::Request<super::super::memory_v1::CreateMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status>;
+        async fn delete_memory(
+            &self,
+            request: tonic::Request<super::super::memory_v1::DeleteMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn get_memory(
+            &self,
+            request: tonic::Request<super::super::memory_v1::GetMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryResponse>, tonic::Status>;
+        async fn list_memories(
+            &self,
+            request: tonic::Request<super::super::memory_v1::ListMemoriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryListResponse>, tonic::Status>;
+        async fn search_memories(
+            &self,
+            request: tonic::Request<super::super::memory_v1::SearchMemoriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryListResponse>, tonic::Status>;
+        async fn build_context(
+            &self,
+            request: tonic::Request<super::super::memory_v1::BuildContextRequest>,
+        ) -> std::result::Result<tonic::Response<super::ContextPackResponse>, tonic::Status>;
+        async fn mark_used(
+            &self,
+            request: tonic::Request<super::super::memory_v1::MarkUsedRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn feedback(
+            &self,
+            request: tonic::Request<super::super::memory_v1::FeedbackRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn create_memories(
+            &self,
+            request: tonic::Request<super::super::memory_v1::CreateMemoriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status>;
+        async fn create_structured_memory(
+            &self,
+            request: tonic::Request<super::super::memory_v1::CreateStructuredMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateMemoryResponse>, tonic::Status>;
+        async fn list_templates(
+            &self,
+            request: tonic::Request<super::super::memory_v1::ListTemplatesRequest>,
+        ) -> std::result::Result<tonic::Response<super::super::memory_v1::TemplateListResponse>, tonic::Status>;
+        async fn get_memory_stats(
+            &self,
+            request: tonic::Request<super::GetMemoryStatsRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryStatsResponse>, tonic::Status>;
+        async fn save_view(
+            &self,
+            request: tonic::Request<super::SaveViewRequest>,
+        ) -> std::result::Result<tonic::Response<super::SavedView>, tonic::Status>;
+        async fn list_views(
+            &self,
+            request: tonic::Request<super::ListViewsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ViewListResponse>, tonic::Status>;
+        async fn run_view(
+            &self,
+            request: tonic::Request<super::RunViewRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryListResponse>, tonic::Status>;
+        async fn delete_view(
+            &self,
+            request: tonic::Request<super::DeleteViewRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn get_changes(
+            &self,
+            request: tonic::Request<super::GetChangesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ChangesResponse>, tonic::Status>;
+        async fn update_memory(
+            &self,
+            request: tonic::Request<super::UpdateMemoryRequest>,
+        ) -> std::result::Result<tonic::Response<super::MemoryResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct MemoryServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> MemoryServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for MemoryServiceServer<T>
+    where
+        T: MemoryService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/memory_v2.MemoryService/CreateMemory" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateMemorySvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService>
+                        tonic::server::UnaryService<super::super::memory_v1::CreateMemoryRequest>
+                        for CreateMemorySvc<T>
+                    {
+                        type Response = super::CreateMemoryResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::CreateMemoryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::create_memory(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateMemorySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/DeleteMemory" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteMemorySvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService>
+                        tonic::server::UnaryService<super::super::memory_v1::DeleteMemoryRequest>
+                        for DeleteMemorySvc<T>
+                    {
+                        type Response = ();
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::DeleteMemoryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::delete_memory(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteMemorySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/GetMemory" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetMemorySvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService>
+                        tonic::server::UnaryService<super::super::memory_v1::GetMemoryRequest>
+                        for GetMemorySvc<T>
+                    {
+                        type Response = super::MemoryResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::GetMemoryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::get_memory(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetMemorySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/ListMemories" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListMemoriesSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService>
+                        tonic::server::UnaryService<super::super::memory_v1::ListMemoriesRequest>
+                        for ListMemoriesSvc<T>
+                    {
+                        type Response = super::MemoryListResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::ListMemoriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::list_memories(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListMemoriesSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/SearchMemories" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchMemoriesSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService>
+                        tonic::server::UnaryService<super::super::memory_v1::SearchMemoriesRequest>
+                        for SearchMemoriesSvc<T>
+                    {
+                        type Response = super::MemoryListResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::SearchMemoriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::search_memories(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SearchMemoriesSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/BuildContext" => {
+                    #[allow(non_camel_case_types)]
+                    struct BuildContextSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService>
+                        tonic::server::UnaryService<super::super::memory_v1::BuildContextRequest>
+                        for BuildContextSvc<T>
+                    {
+                        type Response = super::ContextPackResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::BuildContextRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::build_context(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = BuildContextSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/MarkUsed" => {
+                    #[allow(non_camel_case_types)]
+                    struct MarkUsedSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService>
+                        tonic::server::UnaryService<super::super::memory_v1::MarkUsedRequest>
+                        for MarkUsedSvc<T>
+                    {
+                        type Response = ();
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::MarkUsedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::mark_used(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = MarkUsedSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/Feedback" => {
+                    #[allow(non_camel_case_types)]
+                    struct FeedbackSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService>
+                        tonic::server::UnaryService<super::super::memory_v1::FeedbackRequest>
+                        for FeedbackSvc<T>
+                    {
+                        type Response = ();
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::FeedbackRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::feedback(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FeedbackSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/CreateMemories" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateMemoriesSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService>
+                        tonic::server::UnaryService<super::super::memory_v1::CreateMemoriesRequest>
+                        for CreateMemoriesSvc<T>
+                    {
+                        type Response = super::CreateMemoryResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::CreateMemoriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::create_memories(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateMemoriesSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/CreateStructuredMemory" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateStructuredMemorySvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::super::memory_v1::CreateStructuredMemoryRequest>
+                        for CreateStructuredMemorySvc<T>
+                    {
+                        type Response = super::CreateMemoryResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::CreateStructuredMemoryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::create_structured_memory(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateStructuredMemorySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/ListTemplates" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListTemplatesSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::super::memory_v1::ListTemplatesRequest>
+                        for ListTemplatesSvc<T>
+                    {
+                        type Response = super::super::memory_v1::TemplateListResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::memory_v1::ListTemplatesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::list_templates(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListTemplatesSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/GetMemoryStats" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetMemoryStatsSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::GetMemoryStatsRequest>
+                        for GetMemoryStatsSvc<T>
+                    {
+                        type Response = super::MemoryStatsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetMemoryStatsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::get_memory_stats(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetMemoryStatsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/SaveView" => {
+                    #[allow(non_camel_case_types)]
+                    struct SaveViewSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::SaveViewRequest>
+                        for SaveViewSvc<T>
+                    {
+                        type Response = super::SavedView;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SaveViewRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::save_view(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SaveViewSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/ListViews" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListViewsSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::ListViewsRequest>
+                        for ListViewsSvc<T>
+                    {
+                        type Response = super::ViewListResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListViewsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::list_views(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListViewsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/RunView" => {
+                    #[allow(non_camel_case_types)]
+                    struct RunViewSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::RunViewRequest>
+                        for RunViewSvc<T>
+                    {
+                        type Response = super::MemoryListResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RunViewRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::run_view(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RunViewSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/DeleteView" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteViewSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::DeleteViewRequest>
+                        for DeleteViewSvc<T>
+                    {
+                        type Response = ();
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteViewRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::delete_view(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteViewSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/GetChanges" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetChangesSvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::GetChangesRequest>
+                        for GetChangesSvc<T>
+                    {
+                        type Response = super::ChangesResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetChangesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::get_changes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetChangesSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/memory_v2.MemoryService/UpdateMemory" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateMemorySvc<T: MemoryService>(pub Arc<T>);
+                    impl<T: MemoryService> tonic::server::UnaryService<super::UpdateMemoryRequest>
+                        for UpdateMemorySvc<T>
+                    {
+                        type Response = super::MemoryResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateMemoryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as MemoryService>::update_memory(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateMemorySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    let mut response = http::Response::new(tonic::body::Body::default());
+                    let headers = response.headers_mut();
+                    headers.insert(
+                        tonic::Status::GRPC_STATUS,
+                        (tonic::Code::Unimplemented as i32).into(),
+                    );
+                    headers.insert(
+                        http::header::CONTENT_TYPE,
+                        tonic::metadata::GRPC_CONTENT_TYPE,
+                    );
+                    Ok(response)
+                }),
+            }
+        }
+    }
+    impl<T> Clone for MemoryServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "memory_v2.MemoryService";
+    impl<T> tonic::server::NamedService for MemoryServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
@@ -0,0 +1,160 @@
+use std::future::Future;
+
+use lazy_static::lazy_static;
+use pyo3::{create_exception, exceptions::PyException, prelude::*};
+use serde::Serialize;
+use tokio::runtime::Runtime;
+use umem_config::AppConfig;
+use umem_controller::{CreateMemoryRequest, MemoryController, SummarizeSessionRequest};
+use umem_core::{MemoryContext, MemoryId};
+use umem_memory_machine::MemoryMachine;
+
+create_exception!(enfinyte, EnfinyteError, PyException);
+
+lazy_static! {
+    static ref RUNTIME: Runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("failed to start the enfinyte tokio runtime");
+}
+
+fn to_py_err(e: impl ToString) -> PyErr {
+    EnfinyteError::new_err(e.to_string())
+}
+
+fn to_py_object(py: Python<'_>, value: &impl Serialize) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(to_py_err)?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+fn block_on<F>(py: Python<'_>, future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    py.allow_threads(|| RUNTIME.block_on(future))
+}
+
+fn context(
+    user_id: Option<String>,
+    agent_id: Option<String>,
+    run_id: Option<String>,
+) -> PyResult<MemoryContext> {
+    MemoryContext::new(user_id, agent_id, run_id).map_err(to_py_err)
+}
+
+#[pyclass(name = "MemoryMachine", module = "enfinyte", frozen)]
+struct PyMemoryMachine {
+    memory_controller: MemoryController,
+}
+
+#[pymethods]
+impl PyMemoryMachine {
+    #[new]
+    fn new(py: Python<'_>) -> PyResult<Self> {
+        let machine = block_on(py, MemoryMachine::new()).map_err(to_py_err)?;
+        Ok(Self {
+            memory_controller: machine.memory_controller,
+        })
+    }
+
+    #[staticmethod]
+    fn from_config(py: Python<'_>, path: String) -> PyResult<Self> {
+        let config = AppConfig::from_path(path).map_err(to_py_err)?;
+        let machine = block_on(py, MemoryMachine::from_config(config)).map_err(to_py_err)?;
+        Ok(Self {
+            memory_controller: machine.memory_controller,
+        })
+    }
+
+    #[pyo3(signature = (content, user_id=None, agent_id=None, run_id=None))]
+    fn add(
+        &self,
+        py: Python<'_>,
+        content: String,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+    ) -> PyResult<PyObject> {
+        let request = CreateMemoryRequest::builder()
+            .user_id(user_id)
+            .agent_id(agent_id)
+            .run_id(run_id)
+            .raw_content(content)
+            .build();
+
+        let memories =
+            block_on(py, self.memory_controller.create(request, None)).map_err(to_py_err)?;
+        to_py_object(py, &memories)
+    }
+
+    fn get(&self, py: Python<'_>, id: String) -> PyResult<PyObject> {
+        let id = MemoryId::parse(&id).map_err(to_py_err)?;
+        let memory = block_on(py, self.memory_controller.get(id)).map_err(to_py_err)?;
+        to_py_object(py, &memory)
+    }
+
+    fn delete(&self, py: Python<'_>, id: String) -> PyResult<()> {
+        let id = MemoryId::parse(&id).map_err(to_py_err)?;
+        block_on(py, self.memory_controller.delete(id)).map_err(to_py_err)
+    }
+
+    #[pyo3(signature = (user_id=None, agent_id=None, run_id=None))]
+    fn list(
+        &self,
+        py: Python<'_>,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+    ) -> PyResult<PyObject> {
+        let context = context(user_id, agent_id, run_id)?;
+        let memories =
+            block_on(py, self.memory_controller.list_with_context(context)).map_err(to_py_err)?;
+        to_py_object(py, &memories)
+    }
+
+    #[pyo3(signature = (query, user_id=None, agent_id=None, run_id=None))]
+    fn search(
+        &self,
+        py: Python<'_>,
+        query: String,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+    ) -> PyResult<PyObject> {
+        let context = context(user_id, agent_id, run_id)?;
+        let memories = block_on(
+            py,
+            self.memory_controller
+                .search_with_context(context, query, None),
+        )
+        .map_err(to_py_err)?;
+        to_py_object(py, &memories)
+    }
+
+    #[pyo3(signature = (run_id, user_id=None, agent_id=None))]
+    fn summarize_session(
+        &self,
+        py: Python<'_>,
+        run_id: String,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+    ) -> PyResult<PyObject> {
+        let request = SummarizeSessionRequest::builder()
+            .user_id(user_id)
+            .agent_id(agent_id)
+            .run_id(run_id)
+            .build();
+
+        let summary = block_on(py, self.memory_controller.summarize_session(request, None))
+            .map_err(to_py_err)?;
+        to_py_object(py, &summary)
+    }
+}
+
+#[pymodule]
+fn enfinyte(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyMemoryMachine>()?;
+    m.add("EnfinyteError", m.py().get_type::<EnfinyteError>())?;
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::{rank_score, MemoryRetriever};
+use async_trait::async_trait;
+use langchain_rust::schemas::{Document, Retriever};
+use serde_json::{json, Value};
+use std::{collections::HashMap, error::Error};
+
+#[async_trait]
+impl Retriever for MemoryRetriever {
+    /// Each memory becomes a document whose content is the summary; id, kind and
+    /// tags travel in the metadata.
+    async fn get_relevant_documents(&self, query: &str) -> Result<Vec<Document>, Box<dyn Error>> {
+        let memories = self.retrieve(query, self.limit).await?;
+
+        Ok(memories
+            .iter()
+            .enumerate()
+            .map(|(rank, memory)| {
+                let metadata = HashMap::<String, Value>::from([
+                    ("id".to_string(), json!(memory.get_id().to_string())),
+                    ("kind".to_string(), json!(memory.kind().as_str())),
+                    ("tags".to_string(), json!(memory.content().tags())),
+                ]);
+                Document::new(memory.get_summary().clone())
+                    .with_metadata(metadata)
+                    .with_score(rank_score(rank))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(feature = "langchain")]
+mod langchain_retriever;
+#[cfg(feature = "rig")]
+mod rig_index;
+
+use typed_builder::TypedBuilder;
+use umem_controller::{MemoryController, MemoryControllerError};
+use umem_core::{Memory, MemoryContext};
+
+/// Exposes `MemoryController::search` to agent frameworks: rig's `VectorStoreIndex`
+/// (feature `rig`) and langchain-rust's `Retriever` (feature `langchain`). Every
+/// lookup is confined to `context`.
+#[derive(TypedBuilder)]
+pub struct MemoryRetriever {
+    memory_controller: MemoryController,
+    context: MemoryContext,
+    #[builder(default = 5)]
+    limit: usize,
+}
+
+impl MemoryRetriever {
+    async fn retrieve(&self, query: &str, n: usize) -> Result<Vec<Memory>, MemoryControllerError> {
+        let mut memories = self
+            .memory_controller
+            .search_with_context(self.context.clone(), query.to_string(), None)
+            .await?;
+        memories.truncate(n);
+        Ok(memories)
+    }
+}
+
+/// Search returns memories ranked but unscored, so frameworks that want a
+/// similarity get one derived from the rank: 1.0 for the best hit, decreasing.
+fn rank_score(rank: usize) -> f64 {
+    1.0 / (rank as f64 + 1.0)
+}
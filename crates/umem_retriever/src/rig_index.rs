@@ -0,0 +1,44 @@
+use crate::{rank_score, MemoryRetriever};
+use rig::vector_store::{VectorStoreError, VectorStoreIndex};
+use serde::Deserialize;
+
+impl VectorStoreIndex for MemoryRetriever {
+    /// Documents are the memory's JSON form, so `T` can be `Memory` itself or any
+    /// struct picking out the fields it needs (e.g. `content.summary`).
+    async fn top_n<T: for<'a> Deserialize<'a> + Send>(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
+        let memories = self
+            .retrieve(query, n)
+            .await
+            .map_err(|e| VectorStoreError::DatastoreError(Box::new(e)))?;
+
+        memories
+            .iter()
+            .enumerate()
+            .map(|(rank, memory)| {
+                let document = serde_json::from_value(serde_json::to_value(memory)?)?;
+                Ok((rank_score(rank), memory.get_id().to_string(), document))
+            })
+            .collect()
+    }
+
+    async fn top_n_ids(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        let memories = self
+            .retrieve(query, n)
+            .await
+            .map_err(|e| VectorStoreError::DatastoreError(Box::new(e)))?;
+
+        Ok(memories
+            .iter()
+            .enumerate()
+            .map(|(rank, memory)| (rank_score(rank), memory.get_id().to_string()))
+            .collect())
+    }
+}
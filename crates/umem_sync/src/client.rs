@@ -0,0 +1,248 @@
+use crate::{ChangeKind, PendingChange, Replica, Result, SyncError};
+use tonic::{
+    metadata::{Ascii, MetadataValue},
+    transport::Channel,
+    Code, Request,
+};
+use tracing::info;
+use umem_proto::{
+    memory_v2::{
+        memory_service_client::MemoryServiceClient, GetChangesRequest, Memory, UpdateMemoryRequest,
+    },
+    ContextFilter, CreateMemoryRequest, DeleteMemoryRequest, GetMemoryRequest,
+};
+
+/// Which change is kept when a memory changed on the server after the replica read it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// The local change is dropped and the replica takes the server's memory.
+    #[default]
+    ServerWins,
+    /// The local change is applied over the server's.
+    ClientWins,
+}
+
+/// A local change made on a memory version the server had already moved past.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub change: PendingChange,
+    /// The server's memory when the change was pushed; `None` when it was deleted there.
+    pub server: Option<Memory>,
+    /// Whether the local change was applied anyway.
+    pub applied: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// Local changes the server took, including those applied over a conflict.
+    pub pushed: usize,
+    /// Server changes written to the replica.
+    pub pulled: usize,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Keeps a `Replica` in sync with the memories of `context` on a memory_v2 server.
+pub struct SyncClient {
+    client: MemoryServiceClient<Channel>,
+    replica: Replica,
+    context: ContextFilter,
+    api_key: Option<String>,
+    policy: ConflictPolicy,
+}
+
+impl SyncClient {
+    pub fn new(
+        client: MemoryServiceClient<Channel>,
+        replica: Replica,
+        context: ContextFilter,
+    ) -> Self {
+        Self {
+            client,
+            replica,
+            context,
+            api_key: None,
+            policy: ConflictPolicy::default(),
+        }
+    }
+
+    /// Sent as `authorization: Bearer <key>` with every call.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn with_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn replica(&self) -> &Replica {
+        &self.replica
+    }
+
+    /// Pushes the replica's pending changes in the order they were made, then pulls what
+    /// changed on the server since the last sync. When a call fails, the changes not
+    /// pushed yet stay pending for the next sync.
+    pub async fn sync(&mut self) -> Result<SyncReport> {
+        let mut report = SyncReport::default();
+        for change in self.replica.pending().await? {
+            self.push(change, &mut report).await?;
+        }
+        self.pull(&mut report).await?;
+
+        info!(
+            pushed = report.pushed,
+            pulled = report.pulled,
+            conflicts = report.conflicts.len(),
+            "replica synced"
+        );
+        Ok(report)
+    }
+
+    async fn push(&mut self, change: PendingChange, report: &mut SyncReport) -> Result<()> {
+        match &change.kind {
+            ChangeKind::Create { raw_content } => {
+                let request = self.request(CreateMemoryRequest {
+                    user_id: self.context.user_id.clone(),
+                    agent_id: self.context.agent_id.clone(),
+                    run_id: self.context.run_id.clone(),
+                    raw_content: raw_content.clone(),
+                })?;
+                let created = self.client.create_memory(request).await?.into_inner();
+                self.replica.resolve(change.seq).await?;
+                for memory in &created.memories {
+                    self.replica.apply(memory).await?;
+                }
+                report.pushed += 1;
+            }
+            ChangeKind::Edit {
+                memory_id,
+                base_version,
+                summary,
+            } => match self.update(memory_id, summary, Some(*base_version)).await {
+                Ok(memory) => {
+                    self.replica.resolve(change.seq).await?;
+                    self.settle(memory_id, memory).await?;
+                    report.pushed += 1;
+                }
+                Err(SyncError::ServerError(status))
+                    if matches!(status.code(), Code::Aborted | Code::NotFound) =>
+                {
+                    let server = self.server_copy(memory_id).await?;
+                    let applied = server.is_some() && self.policy == ConflictPolicy::ClientWins;
+                    let memory = if applied {
+                        self.update(memory_id, summary, None).await?
+                    } else {
+                        server.clone()
+                    };
+                    self.replica.resolve(change.seq).await?;
+                    self.settle(memory_id, memory).await?;
+                    if applied {
+                        report.pushed += 1;
+                    }
+                    report.conflicts.push(Conflict {
+                        change,
+                        server,
+                        applied,
+                    });
+                }
+                Err(e) => return Err(e),
+            },
+            ChangeKind::Delete {
+                memory_id,
+                base_version,
+            } => {
+                // NOTE: deletes carry no expected version, so the check is a read just
+                // before; an edit landing in between is deleted with the memory
+                let server = self.server_copy(memory_id).await?;
+                let stale = server
+                    .as_ref()
+                    .is_some_and(|memory| memory.version != *base_version);
+                let applied =
+                    server.is_some() && (!stale || self.policy == ConflictPolicy::ClientWins);
+                if applied {
+                    let request = self.request(DeleteMemoryRequest {
+                        id: memory_id.clone(),
+                    })?;
+                    self.client.delete_memory(request).await?;
+                    report.pushed += 1;
+                }
+                self.replica.resolve(change.seq).await?;
+                if stale {
+                    if !applied {
+                        self.settle(memory_id, server.clone()).await?;
+                    }
+                    report.conflicts.push(Conflict {
+                        change,
+                        server,
+                        applied,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn pull(&mut self, report: &mut SyncReport) -> Result<()> {
+        let request = self.request(GetChangesRequest {
+            context: Some(self.context.clone()),
+            since: self.replica.since().await?,
+        })?;
+        let changes = self.client.get_changes(request).await?.into_inner();
+        for memory in &changes.memories {
+            if self.replica.apply(memory).await? {
+                report.pulled += 1;
+            }
+        }
+        self.replica.set_since(changes.until).await
+    }
+
+    async fn update(
+        &mut self,
+        id: &str,
+        summary: &str,
+        expected_version: Option<u64>,
+    ) -> Result<Option<Memory>> {
+        let request = self.request(UpdateMemoryRequest {
+            id: id.to_string(),
+            summary: summary.to_string(),
+            expected_version,
+        })?;
+        Ok(self
+            .client
+            .update_memory(request)
+            .await?
+            .into_inner()
+            .memory)
+    }
+
+    /// The server's memory `id`, or `None` when it is gone.
+    async fn server_copy(&mut self, id: &str) -> Result<Option<Memory>> {
+        let request = self.request(GetMemoryRequest { id: id.to_string() })?;
+        match self.client.get_memory(request).await {
+            Ok(response) => Ok(response.into_inner().memory),
+            Err(status) if status.code() == Code::NotFound => Ok(None),
+            Err(status) => Err(status.into()),
+        }
+    }
+
+    /// Writes the server's outcome for memory `id` to the replica.
+    async fn settle(&self, id: &str, memory: Option<Memory>) -> Result<()> {
+        match memory {
+            Some(memory) => self.replica.apply(&memory).await?,
+            None => self.replica.forget(id).await?,
+        };
+        Ok(())
+    }
+
+    fn request<T>(&self, message: T) -> Result<Request<T>> {
+        let mut request = Request::new(message);
+        if let Some(api_key) = &self.api_key {
+            let value: MetadataValue<Ascii> = format!("Bearer {api_key}")
+                .parse()
+                .map_err(|_| SyncError::InvalidApiKey)?;
+            request.metadata_mut().insert("authorization", value);
+        }
+        Ok(request)
+    }
+}
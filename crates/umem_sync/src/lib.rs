@@ -0,0 +1,40 @@
+//! A local sqlite replica of one context's memories for offline-capable clients. Reads
+//! and edits go to the replica; `SyncClient::sync` pushes the edits made since the last
+//! sync over gRPC and pulls the server's changes feed.
+//!
+//! Edits are pushed with the version they were made on and reported as conflicts when
+//! the server has moved past it. Servers on Postgres or Qdrant check that version in
+//! the write itself. Other vector stores only check it when the memory is read, so an
+//! edit landing on the server between that read and the write is overwritten without
+//! a conflict.
+
+mod client;
+mod replica;
+
+pub use client::*;
+pub use replica::*;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SyncError {
+    #[error("replica database action failed with: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    #[error("stored memory could not be decoded: {0}")]
+    DecodeError(#[from] prost::DecodeError),
+
+    #[error("server call failed with: {0}")]
+    ServerError(#[from] tonic::Status),
+
+    #[error("api key cannot be sent as metadata")]
+    InvalidApiKey,
+
+    #[error("no memory {0} in the replica")]
+    NotFound(String),
+
+    #[error("summary must not be empty")]
+    EmptySummary,
+}
+
+type Result<T> = std::result::Result<T, SyncError>;
@@ -0,0 +1,406 @@
+use crate::{Result, SyncError};
+use prost::Message;
+use sqlx::{
+    query,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteRow},
+    Pool, Row, Sqlite,
+};
+use std::str::FromStr;
+use umem_proto::{memory_v2::Memory, LifecycleState};
+
+/// A change made on the replica that the server has not seen yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingChange {
+    pub seq: i64,
+    pub kind: ChangeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Content to create memories from. The server annotates it, so its memories only
+    /// show up in the replica once synced.
+    Create { raw_content: String },
+    /// A new summary for a memory read at `base_version`.
+    Edit {
+        memory_id: String,
+        base_version: u64,
+        summary: String,
+    },
+    /// Deletion of a memory read at `base_version`.
+    Delete {
+        memory_id: String,
+        base_version: u64,
+    },
+}
+
+/// One context's memories in a sqlite database, with the local changes waiting to be
+/// pushed and the point the server's changes feed was last read up to.
+pub struct Replica {
+    client: Pool<Sqlite>,
+}
+
+impl Replica {
+    pub async fn open(database_url: &str) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+        // NOTE: a single connection, so an in-memory replica is one database and local
+        // writes never wait on each other
+        let client = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await?;
+
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS memories (
+                id TEXT PRIMARY KEY,
+                version INTEGER NOT NULL,
+                summary TEXT NOT NULL,
+                memory BLOB NOT NULL
+            )
+            "#,
+        )
+        .execute(&client)
+        .await?;
+
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS pending_changes (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                memory_id TEXT,
+                base_version INTEGER,
+                text TEXT
+            )
+            "#,
+        )
+        .execute(&client)
+        .await?;
+
+        query(
+            r#"
+            CREATE TABLE IF NOT EXISTS sync_state (
+                id INTEGER PRIMARY KEY CHECK (id = 0),
+                since INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&client)
+        .await?;
+
+        Ok(Self { client })
+    }
+
+    /// Every memory in the replica, including local edits not pushed yet.
+    pub async fn memories(&self) -> Result<Vec<Memory>> {
+        query(r#"SELECT memory FROM memories ORDER BY id"#)
+            .fetch_all(&self.client)
+            .await?
+            .iter()
+            .map(Self::memory)
+            .collect()
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<Memory>> {
+        let row = query(r#"SELECT memory FROM memories WHERE id = $1"#)
+            .bind(id)
+            .fetch_optional(&self.client)
+            .await?;
+        row.as_ref().map(Self::memory).transpose()
+    }
+
+    /// Memories whose summary contains `text`, ignoring ASCII case.
+    pub async fn search(&self, text: &str) -> Result<Vec<Memory>> {
+        query(
+            r#"SELECT memory FROM memories WHERE instr(lower(summary), lower($1)) > 0
+                ORDER BY id"#,
+        )
+        .bind(text)
+        .fetch_all(&self.client)
+        .await?
+        .iter()
+        .map(Self::memory)
+        .collect()
+    }
+
+    /// Queues `raw_content` to be turned into memories on the next sync.
+    pub async fn create(&self, raw_content: &str) -> Result<()> {
+        query(r#"INSERT INTO pending_changes (kind, text) VALUES ('create', $1)"#)
+            .bind(raw_content)
+            .execute(&self.client)
+            .await?;
+        Ok(())
+    }
+
+    /// Replaces the summary of memory `id` and queues the edit. A later edit of the same
+    /// memory replaces this one before it is pushed.
+    pub async fn edit(&self, id: &str, summary: &str) -> Result<Memory> {
+        let summary = summary.trim();
+        if summary.is_empty() {
+            return Err(SyncError::EmptySummary);
+        }
+        let mut memory = self
+            .get(id)
+            .await?
+            .ok_or_else(|| SyncError::NotFound(id.to_string()))?;
+        if let Some(content) = memory.content.as_mut() {
+            content.summary = summary.to_string();
+        }
+
+        let mut tx = self.client.begin().await?;
+        query(r#"UPDATE memories SET summary = $2, memory = $3 WHERE id = $1"#)
+            .bind(id)
+            .bind(summary)
+            .bind(memory.encode_to_vec())
+            .execute(&mut *tx)
+            .await?;
+        query(r#"DELETE FROM pending_changes WHERE kind = 'edit' AND memory_id = $1"#)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        query(
+            r#"INSERT INTO pending_changes (kind, memory_id, base_version, text)
+                VALUES ('edit', $1, $2, $3)"#,
+        )
+        .bind(id)
+        .bind(memory.version as i64)
+        .bind(summary)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(memory)
+    }
+
+    /// Removes memory `id` from the replica and queues its deletion, dropping an edit of
+    /// it that was not pushed yet.
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        let memory = self
+            .get(id)
+            .await?
+            .ok_or_else(|| SyncError::NotFound(id.to_string()))?;
+
+        let mut tx = self.client.begin().await?;
+        query(r#"DELETE FROM memories WHERE id = $1"#)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        query(r#"DELETE FROM pending_changes WHERE kind = 'edit' AND memory_id = $1"#)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        query(
+            r#"INSERT INTO pending_changes (kind, memory_id, base_version)
+                VALUES ('delete', $1, $2)"#,
+        )
+        .bind(id)
+        .bind(memory.version as i64)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Local changes not pushed yet, oldest first.
+    pub async fn pending(&self) -> Result<Vec<PendingChange>> {
+        query(
+            r#"SELECT seq, kind, memory_id, base_version, text FROM pending_changes ORDER BY seq"#,
+        )
+        .fetch_all(&self.client)
+        .await?
+        .iter()
+        .map(Self::change)
+        .collect()
+    }
+
+    /// Takes the server's copy of a memory, or drops it when it was deleted or
+    /// suppressed there. Memories with a change waiting to be pushed and copies older
+    /// than the replica's are left alone. Returns whether the replica changed.
+    pub(crate) async fn apply(&self, memory: &Memory) -> Result<bool> {
+        let pending: bool =
+            query(r#"SELECT count(*) > 0 FROM pending_changes WHERE memory_id = $1"#)
+                .bind(&memory.id)
+                .fetch_one(&self.client)
+                .await?
+                .try_get(0)?;
+        if pending {
+            return Ok(false);
+        }
+
+        if matches!(
+            memory.lifecycle(),
+            LifecycleState::Deleted | LifecycleState::Suppressed
+        ) {
+            return self.forget(&memory.id).await;
+        }
+
+        let summary = memory
+            .content
+            .as_ref()
+            .map(|content| content.summary.as_str())
+            .unwrap_or_default();
+        let changed = query(
+            r#"INSERT INTO memories (id, version, summary, memory) VALUES ($1, $2, $3, $4)
+                ON CONFLICT (id) DO UPDATE
+                SET version = excluded.version, summary = excluded.summary, memory = excluded.memory
+                WHERE excluded.version >= memories.version"#,
+        )
+        .bind(&memory.id)
+        .bind(memory.version as i64)
+        .bind(summary)
+        .bind(memory.encode_to_vec())
+        .execute(&self.client)
+        .await?
+        .rows_affected();
+        Ok(changed > 0)
+    }
+
+    /// Drops memory `id` from the replica, returning whether it was there.
+    pub(crate) async fn forget(&self, id: &str) -> Result<bool> {
+        let deleted = query(r#"DELETE FROM memories WHERE id = $1"#)
+            .bind(id)
+            .execute(&self.client)
+            .await?
+            .rows_affected();
+        Ok(deleted > 0)
+    }
+
+    /// Marks a pending change as dealt with.
+    pub(crate) async fn resolve(&self, seq: i64) -> Result<()> {
+        query(r#"DELETE FROM pending_changes WHERE seq = $1"#)
+            .bind(seq)
+            .execute(&self.client)
+            .await?;
+        Ok(())
+    }
+
+    /// Where the next read of the changes feed starts; 0 before the first sync.
+    pub async fn since(&self) -> Result<i64> {
+        let row = query(r#"SELECT since FROM sync_state WHERE id = 0"#)
+            .fetch_optional(&self.client)
+            .await?;
+        Ok(row
+            .map(|row| row.try_get("since"))
+            .transpose()?
+            .unwrap_or(0))
+    }
+
+    pub(crate) async fn set_since(&self, since: i64) -> Result<()> {
+        query(
+            r#"INSERT INTO sync_state (id, since) VALUES (0, $1)
+                ON CONFLICT (id) DO UPDATE SET since = excluded.since"#,
+        )
+        .bind(since)
+        .execute(&self.client)
+        .await?;
+        Ok(())
+    }
+
+    fn memory(row: &SqliteRow) -> Result<Memory> {
+        let bytes: Vec<u8> = row.try_get("memory")?;
+        Ok(Memory::decode(bytes.as_slice())?)
+    }
+
+    fn change(row: &SqliteRow) -> Result<PendingChange> {
+        let kind: String = row.try_get("kind")?;
+        let memory_id = || -> Result<String> { Ok(row.try_get("memory_id")?) };
+        let base_version = || -> Result<u64> { Ok(row.try_get::<i64, _>("base_version")? as u64) };
+        let text = || -> Result<String> { Ok(row.try_get("text")?) };
+
+        let kind = match kind.as_str() {
+            "create" => ChangeKind::Create {
+                raw_content: text()?,
+            },
+            "edit" => ChangeKind::Edit {
+                memory_id: memory_id()?,
+                base_version: base_version()?,
+                summary: text()?,
+            },
+            _ => ChangeKind::Delete {
+                memory_id: memory_id()?,
+                base_version: base_version()?,
+            },
+        };
+        Ok(PendingChange {
+            seq: row.try_get("seq")?,
+            kind,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use umem_proto::MemoryContent;
+
+    fn memory(id: &str, version: u64, summary: &str) -> Memory {
+        Memory {
+            id: id.to_string(),
+            lifecycle: LifecycleState::Active as i32,
+            content: Some(MemoryContent {
+                summary: summary.to_string(),
+                tags: vec![],
+            }),
+            version,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn local_edits_are_queued_against_the_version_read() {
+        let replica = Replica::open("sqlite::memory:").await.unwrap();
+        assert!(replica
+            .apply(&memory("a", 2, "User likes tea"))
+            .await
+            .unwrap());
+
+        replica.edit("a", "User likes green tea").await.unwrap();
+        replica.edit("a", "User likes oolong").await.unwrap();
+        let pending = replica.pending().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(
+            pending[0].kind,
+            ChangeKind::Edit {
+                memory_id: "a".to_string(),
+                base_version: 2,
+                summary: "User likes oolong".to_string(),
+            }
+        );
+        assert_eq!(replica.search("OOLONG").await.unwrap().len(), 1);
+
+        // NOTE: the server's copy does not replace an edit waiting to be pushed
+        assert!(!replica
+            .apply(&memory("a", 3, "User likes coffee"))
+            .await
+            .unwrap());
+        replica.resolve(pending[0].seq).await.unwrap();
+        assert!(replica
+            .apply(&memory("a", 3, "User likes coffee"))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn apply_skips_stale_copies_and_drops_deleted_memories() {
+        let replica = Replica::open("sqlite::memory:").await.unwrap();
+        replica
+            .apply(&memory("a", 4, "User likes tea"))
+            .await
+            .unwrap();
+
+        assert!(!replica
+            .apply(&memory("a", 3, "User likes coffee"))
+            .await
+            .unwrap());
+        let stored = replica.get("a").await.unwrap().unwrap();
+        assert_eq!(stored.content.unwrap().summary, "User likes tea");
+
+        let mut deleted = memory("a", 5, "User likes tea");
+        deleted.lifecycle = LifecycleState::Deleted as i32;
+        assert!(replica.apply(&deleted).await.unwrap());
+        assert!(replica.memories().await.unwrap().is_empty());
+
+        replica.set_since(1_700_000_000).await.unwrap();
+        assert_eq!(replica.since().await.unwrap(), 1_700_000_000);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::migrations::{CREATED_MONTH_FIELD, SCHEMA_VERSION_FIELD};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{Map, Value};
+use std::io;
+
+/// Holds the compressed part of a payload: base64 encoded in a Qdrant payload, and a
+/// bytea column of the same name in Postgres.
+pub const COMPRESSED_FIELD: &str = "compressed";
+
+/// Payload fields queries filter, sort, facet or search on, or conditional updates
+/// check. They stay readable when the rest of a payload is compressed.
+const QUERIED_FIELDS: &[&str] = &[
+    "context",
+    "lifecycle",
+    "kind",
+    "content",
+    "signals",
+    "temporal",
+    "visibility",
+    "topic",
+    "version",
+    CREATED_MONTH_FIELD,
+    SCHEMA_VERSION_FIELD,
+];
+
+/// Content fields no query reads. The raw content is by far the largest part of a
+/// payload.
+const UNQUERIED_CONTENT_FIELDS: &[&str] = &["raw_content"];
+
+const ZSTD_LEVEL: i32 = 3;
+
+/// Moves the fields of `payload` no query reads into a zstd-compressed JSON object,
+/// returned unless there was nothing to move.
+pub fn compress(payload: &mut Value) -> io::Result<Option<Vec<u8>>> {
+    let Value::Object(fields) = payload else {
+        return Ok(None);
+    };
+
+    let unqueried: Vec<String> = fields
+        .keys()
+        .filter(|key| !QUERIED_FIELDS.contains(&key.as_str()))
+        .cloned()
+        .collect();
+    let mut rest: Map<String, Value> = unqueried
+        .into_iter()
+        .filter_map(|key| fields.remove(&key).map(|value| (key, value)))
+        .collect();
+
+    if let Some(Value::Object(content)) = fields.get_mut("content") {
+        let content_rest: Map<String, Value> = UNQUERIED_CONTENT_FIELDS
+            .iter()
+            .filter_map(|key| content.remove(*key).map(|value| (key.to_string(), value)))
+            .collect();
+        if !content_rest.is_empty() {
+            rest.insert("content".to_string(), Value::Object(content_rest));
+        }
+    }
+
+    if rest.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(zstd::encode_all(
+        serde_json::to_vec(&rest)?.as_slice(),
+        ZSTD_LEVEL,
+    )?))
+}
+
+/// Puts the fields `compress` moved back into `payload`.
+pub fn decompress(payload: &mut Value, compressed: &[u8]) -> io::Result<()> {
+    let rest: Map<String, Value> = serde_json::from_slice(&zstd::decode_all(compressed)?)?;
+    let Value::Object(fields) = payload else {
+        return Ok(());
+    };
+
+    for (key, value) in rest {
+        match (fields.get_mut(&key), value) {
+            (Some(Value::Object(field)), Value::Object(value)) => field.extend(value),
+            (_, value) => {
+                fields.insert(key, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `compress`, keeping the compressed part in the payload itself.
+pub fn compress_inline(payload: &mut Value) -> io::Result<()> {
+    if let Some(compressed) = compress(payload)? {
+        if let Value::Object(fields) = payload {
+            fields.insert(
+                COMPRESSED_FIELD.to_string(),
+                Value::String(STANDARD.encode(compressed)),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Undoes `compress_inline`; payloads stored uncompressed are left as they are.
+pub fn decompress_inline(payload: &mut Value) -> io::Result<()> {
+    let Some(Value::String(compressed)) = payload
+        .as_object_mut()
+        .and_then(|fields| fields.remove(COMPRESSED_FIELD))
+    else {
+        return Ok(());
+    };
+
+    let compressed = STANDARD
+        .decode(compressed)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    decompress(payload, &compressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migrations::{from_payload, to_payload};
+    use umem_core::{Memory, RawContent};
+
+    #[test]
+    fn compressed_payloads_keep_queried_fields_readable() {
+        let mut memory = Memory::gen_dummy().unwrap();
+        memory.set_raw_content(Some(RawContent::new(&"a long transcript ".repeat(100))));
+        let mut payload = to_payload(&memory).unwrap();
+
+        compress_inline(&mut payload).unwrap();
+        assert!(payload[COMPRESSED_FIELD].is_string());
+        assert!(payload["content"]["summary"].is_string());
+        assert!(payload["content"].get("raw_content").is_none());
+        assert!(payload.get("provenance").is_none());
+
+        decompress_inline(&mut payload).unwrap();
+        let (decoded, _) = from_payload(payload).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&memory).unwrap()
+        );
+    }
+
+    #[test]
+    fn compressed_payloads_keep_topic_filterable() {
+        let mut memory = Memory::gen_dummy().unwrap();
+        memory.set_topic(Some("travel".to_string()), chrono::Utc::now());
+        memory.set_raw_content(Some(RawContent::new(&"a long transcript ".repeat(100))));
+        let mut payload = to_payload(&memory).unwrap();
+
+        // Qdrant matches `topic` and Postgres reads `payload->>'topic'` on the stored payload
+        compress_inline(&mut payload).unwrap();
+        assert!(payload[COMPRESSED_FIELD].is_string());
+        assert_eq!(payload["topic"], "travel");
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{Result, VectorStoreBase, VectorStoreError};
+use async_trait::async_trait;
+use std::sync::Arc;
+use umem_core::{DocumentChunk, Memory, MemoryContext, MemoryFacets, MemoryId, Query, TagCount};
+use uuid::Uuid;
+
+/// Migrates between two stores without downtime: every write goes to both, reads are
+/// served by one of them. Start reading from `old` while `new` is backfilled, then
+/// flip to reading from `new` before retiring `old`.
+///
+/// Updates and deletes skip the store that is not read from when it does not hold the
+/// memory yet; the backfill copies its latest state from the other store.
+pub struct DualWriteVectorStore {
+    primary: Arc<dyn VectorStoreBase + Send + Sync>,
+    secondary: Arc<dyn VectorStoreBase + Send + Sync>,
+}
+
+impl DualWriteVectorStore {
+    pub fn new(
+        old: Arc<dyn VectorStoreBase + Send + Sync>,
+        new: Arc<dyn VectorStoreBase + Send + Sync>,
+        read_from_new: bool,
+    ) -> Self {
+        let (primary, secondary) = match read_from_new {
+            true => (new, old),
+            false => (old, new),
+        };
+        Self { primary, secondary }
+    }
+
+    async fn secondary_holds(&self, id: &MemoryId) -> Result<bool> {
+        Ok(!self
+            .secondary
+            .existing_ids(&[*id.as_uuid()])
+            .await?
+            .is_empty())
+    }
+}
+
+#[async_trait]
+impl VectorStoreBase for DualWriteVectorStore {
+    async fn create_collection(&self) -> Result<()> {
+        self.primary.create_collection().await?;
+        self.secondary.create_collection().await
+    }
+
+    async fn delete_collection(&self) -> Result<()> {
+        self.primary.delete_collection().await?;
+        self.secondary.delete_collection().await
+    }
+
+    async fn reset(&self) -> Result<()> {
+        self.primary.reset().await?;
+        self.secondary.reset().await
+    }
+
+    async fn ping(&self) -> Result<()> {
+        self.primary.ping().await?;
+        self.secondary.ping().await
+    }
+
+    async fn insert(&self, vectors: &[&[f32]], payloads: &[&Memory]) -> Result<()> {
+        self.primary.insert(vectors, payloads).await?;
+        self.secondary.insert(vectors, payloads).await
+    }
+
+    async fn get(&self, id: &MemoryId) -> Result<Memory> {
+        self.primary.get(id).await
+    }
+
+    async fn update(
+        &self,
+        id: &MemoryId,
+        vector: Option<&[f32]>,
+        payload: Option<&Memory>,
+    ) -> Result<()> {
+        self.primary.update(id, vector, payload).await?;
+        if self.secondary_holds(id).await? {
+            self.secondary.update(id, vector, payload).await?;
+        }
+        Ok(())
+    }
+
+    /// Conditional on the store read from; the other store takes the write as is.
+    async fn update_if_version(
+        &self,
+        id: &MemoryId,
+        vector: Option<&[f32]>,
+        payload: &Memory,
+        version: u64,
+    ) -> Result<bool> {
+        if !self
+            .primary
+            .update_if_version(id, vector, payload, version)
+            .await?
+        {
+            return Ok(false);
+        }
+        if self.secondary_holds(id).await? {
+            self.secondary.update(id, vector, Some(payload)).await?;
+        }
+        Ok(true)
+    }
+
+    async fn delete(&self, id: &MemoryId) -> Result<()> {
+        self.primary.delete(id).await?;
+        if self.secondary_holds(id).await? {
+            self.secondary.delete(id).await?;
+        }
+        Ok(())
+    }
+
+    async fn list(&self, query: Query) -> Result<Vec<Memory>> {
+        self.primary.list(query).await
+    }
+
+    async fn search_scored(&self, query: Query) -> Result<Vec<(Memory, f32)>> {
+        self.primary.search_scored(query).await
+    }
+
+    async fn search_scored_batch(&self, queries: &[Query]) -> Result<Vec<Vec<(Memory, f32)>>> {
+        self.primary.search_scored_batch(queries).await
+    }
+
+    fn describe_filter(&self, query: &Query) -> String {
+        self.primary.describe_filter(query)
+    }
+
+    async fn facets(&self, query: Query) -> Result<MemoryFacets> {
+        self.primary.facets(query).await
+    }
+
+    async fn tag_counts(&self, context: &MemoryContext) -> Result<Vec<TagCount>> {
+        self.primary.tag_counts(context).await
+    }
+
+    async fn replace_tags(
+        &self,
+        context: &MemoryContext,
+        from: &[String],
+        to: &str,
+    ) -> Result<usize> {
+        let replaced = self.primary.replace_tags(context, from, to).await?;
+        self.secondary.replace_tags(context, from, to).await?;
+        Ok(replaced)
+    }
+
+    async fn scroll(
+        &self,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> Result<(Vec<Memory>, Option<Uuid>)> {
+        self.primary.scroll(cursor, limit).await
+    }
+
+    async fn scroll_changed(
+        &self,
+        context: Option<&MemoryContext>,
+        since: i64,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> Result<(Vec<Memory>, Option<Uuid>)> {
+        self.primary
+            .scroll_changed(context, since, cursor, limit)
+            .await
+    }
+
+    async fn existing_ids(&self, ids: &[Uuid]) -> Result<Vec<Uuid>> {
+        self.primary.existing_ids(ids).await
+    }
+
+    /// Promoting swaps one store's collection; finish the migration first.
+    async fn promote(&self, _collection: &str) -> Result<()> {
+        Err(VectorStoreError::Unsupported("promote"))
+    }
+
+    async fn reindex(&self) -> Result<usize> {
+        let upgraded = self.primary.reindex().await?;
+        self.secondary.reindex().await?;
+        Ok(upgraded)
+    }
+
+    async fn create_document_collection(&self) -> Result<()> {
+        self.primary.create_document_collection().await?;
+        self.secondary.create_document_collection().await
+    }
+
+    async fn insert_documents(&self, vectors: &[&[f32]], chunks: &[&DocumentChunk]) -> Result<()> {
+        self.primary.insert_documents(vectors, chunks).await?;
+        self.secondary.insert_documents(vectors, chunks).await
+    }
+
+    async fn get_documents(
+        &self,
+        ids: Option<&[String]>,
+        filter: &[(String, String)],
+        limit: Option<u32>,
+    ) -> Result<Vec<DocumentChunk>> {
+        self.primary.get_documents(ids, filter, limit).await
+    }
+
+    async fn search_documents(
+        &self,
+        vector: &[f32],
+        filter: &[(String, String)],
+        limit: u32,
+    ) -> Result<Vec<DocumentChunk>> {
+        self.primary.search_documents(vector, filter, limit).await
+    }
+
+    async fn delete_documents(
+        &self,
+        ids: Option<&[String]>,
+        filter: &[(String, String)],
+    ) -> Result<()> {
+        self.primary.delete_documents(ids, filter).await?;
+        self.secondary.delete_documents(ids, filter).await
+    }
+
+    async fn count_documents(&self) -> Result<usize> {
+        self.primary.count_documents().await
+    }
+
+    async fn text_search(&self, query: Query, text: &str) -> Result<Vec<Memory>> {
+        self.primary.text_search(query, text).await
+    }
+}
@@ -0,0 +1,313 @@
+use crate::{Result, VectorStoreBase, VectorStoreError};
+use async_trait::async_trait;
+use std::{collections::BTreeMap, ops::Bound};
+use tokio::sync::RwLock;
+use umem_core::{
+    DocumentChunk, LifecycleState, Memory, MemoryContext, MemoryFacets, MemoryId, MemoryVisibility,
+    Query, TagCount,
+};
+use uuid::Uuid;
+
+/// Keeps memories in a map, for tests and single-process setups that need no
+/// persistence. Matches queries on context, lifecycle, kind, tags and topic, and ranks
+/// searches by cosine similarity. Documents, facets, tag management, the changes feed
+/// and collection promotion are unsupported.
+#[derive(Default)]
+pub struct InMemoryVectorStore {
+    memories: RwLock<BTreeMap<Uuid, (Vec<f32>, Memory)>>,
+}
+
+impl InMemoryVectorStore {
+    /// Mirrors the context filter of the persistent stores: an organization's pool, or
+    /// the personal context merged with it when the context names an organization.
+    fn matches_context(memory: &Memory, context: &MemoryContext, shared: bool) -> bool {
+        let org_id = memory.context().org_id();
+        match context.org_id() {
+            Some(pool) if context.is_org_pool() => org_id == Some(pool),
+            Some(pool) => {
+                org_id == Some(pool) || Self::matches_personal_context(memory, context, shared)
+            }
+            None => Self::matches_personal_context(memory, context, shared),
+        }
+    }
+
+    fn matches_personal_context(memory: &Memory, context: &MemoryContext, shared: bool) -> bool {
+        let stored = memory.context();
+        if context
+            .user_id()
+            .is_some_and(|user_id| stored.user_id() != Some(user_id))
+        {
+            return false;
+        }
+        if let Some(agent_id) = context.agent_id() {
+            let visible = shared
+                && context.user_id().is_some()
+                && memory.visibility() == MemoryVisibility::Shared;
+            if stored.agent_id() != Some(agent_id) && !visible {
+                return false;
+            }
+        }
+        context.run_id().is_none() || stored.run_id() == context.run_id()
+    }
+
+    fn matches_lifecycle(memory: &Memory, query: &Query) -> bool {
+        if let Some(as_of) = query.as_of() {
+            return memory.temporal().created_at() <= as_of.timestamp();
+        }
+        if query.include_archived() {
+            LifecycleState::RETRIEVABLE.contains(memory.lifecycle())
+        } else {
+            memory.lifecycle().is_active()
+        }
+    }
+
+    fn matches(memory: &Memory, query: &Query) -> bool {
+        Self::matches_lifecycle(memory, query)
+            && Self::matches_context(memory, query.context(), query.include_shared())
+            && query
+                .kinds()
+                .is_none_or(|kinds| kinds.contains(memory.kind()))
+            && query
+                .tags()
+                .is_none_or(|tags| tags.iter().any(|tag| memory.content().tags().contains(tag)))
+            && query.topics().is_none_or(|topics| {
+                memory
+                    .topic()
+                    .is_some_and(|topic| topics.iter().any(|t| t == topic))
+            })
+    }
+
+    fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+        let dot: f32 = a.iter().zip(b).map(|(a, b)| a * b).sum();
+        let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let norms = norm(a) * norm(b);
+        if norms == 0.0 {
+            0.0
+        } else {
+            dot / norms
+        }
+    }
+}
+
+#[async_trait]
+impl VectorStoreBase for InMemoryVectorStore {
+    async fn create_collection(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_collection(&self) -> Result<()> {
+        self.memories.write().await.clear();
+        Ok(())
+    }
+
+    async fn reset(&self) -> Result<()> {
+        self.memories.write().await.clear();
+        Ok(())
+    }
+
+    async fn ping(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn insert(&self, vectors: &[&[f32]], payloads: &[&Memory]) -> Result<()> {
+        let mut memories = self.memories.write().await;
+        for (vector, memory) in vectors.iter().zip(payloads) {
+            memories.insert(*memory.get_id(), (vector.to_vec(), (*memory).clone()));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, id: &MemoryId) -> Result<Memory> {
+        self.memories
+            .read()
+            .await
+            .get(id.as_uuid())
+            .map(|(_, memory)| memory.clone())
+            .ok_or(VectorStoreError::NotFound(*id.as_uuid()))
+    }
+
+    async fn update(
+        &self,
+        id: &MemoryId,
+        vector: Option<&[f32]>,
+        payload: Option<&Memory>,
+    ) -> Result<()> {
+        let mut memories = self.memories.write().await;
+        let (stored_vector, stored) = memories
+            .get_mut(id.as_uuid())
+            .ok_or(VectorStoreError::NotFound(*id.as_uuid()))?;
+        if let Some(vector) = vector {
+            *stored_vector = vector.to_vec();
+        }
+        if let Some(payload) = payload {
+            *stored = payload.clone();
+        }
+        Ok(())
+    }
+
+    async fn update_if_version(
+        &self,
+        id: &MemoryId,
+        vector: Option<&[f32]>,
+        payload: &Memory,
+        version: u64,
+    ) -> Result<bool> {
+        let mut memories = self.memories.write().await;
+        let (stored_vector, stored) = memories
+            .get_mut(id.as_uuid())
+            .ok_or(VectorStoreError::NotFound(*id.as_uuid()))?;
+        if stored.version() != version {
+            return Ok(false);
+        }
+        if let Some(vector) = vector {
+            *stored_vector = vector.to_vec();
+        }
+        *stored = payload.clone();
+        Ok(true)
+    }
+
+    async fn delete(&self, id: &MemoryId) -> Result<()> {
+        self.memories.write().await.remove(id.as_uuid());
+        Ok(())
+    }
+
+    async fn list(&self, query: Query) -> Result<Vec<Memory>> {
+        Ok(self
+            .memories
+            .read()
+            .await
+            .values()
+            .filter(|(_, memory)| Self::matches(memory, &query))
+            .take(query.limit() as usize)
+            .map(|(_, memory)| memory.clone())
+            .collect())
+    }
+
+    async fn search_scored(&self, query: Query) -> Result<Vec<(Memory, f32)>> {
+        let Some(target) = query.vector() else {
+            return Err(VectorStoreError::Unsupported("search without a vector"));
+        };
+
+        let mut scored: Vec<(Memory, f32)> = self
+            .memories
+            .read()
+            .await
+            .values()
+            .filter(|(_, memory)| Self::matches(memory, &query))
+            .map(|(vector, memory)| (memory.clone(), Self::cosine_similarity(vector, target)))
+            .collect();
+        scored.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        scored.truncate(query.limit() as usize);
+        Ok(scored)
+    }
+
+    fn describe_filter(&self, query: &Query) -> String {
+        format!("{:?}", query.context())
+    }
+
+    async fn facets(&self, _query: Query) -> Result<MemoryFacets> {
+        Err(VectorStoreError::Unsupported("facets"))
+    }
+
+    async fn tag_counts(&self, _context: &MemoryContext) -> Result<Vec<TagCount>> {
+        Err(VectorStoreError::Unsupported("tag_counts"))
+    }
+
+    async fn replace_tags(
+        &self,
+        _context: &MemoryContext,
+        _from: &[String],
+        _to: &str,
+    ) -> Result<usize> {
+        Err(VectorStoreError::Unsupported("replace_tags"))
+    }
+
+    async fn scroll(
+        &self,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> Result<(Vec<Memory>, Option<Uuid>)> {
+        let memories = self.memories.read().await;
+        let start = cursor.map_or(Bound::Unbounded, Bound::Excluded);
+        let page: Vec<Memory> = memories
+            .range((start, Bound::Unbounded))
+            .take(limit as usize)
+            .map(|(_, (_, memory))| memory.clone())
+            .collect();
+        let next = if page.len() == limit as usize {
+            page.last().map(|memory| *memory.get_id())
+        } else {
+            None
+        };
+        Ok((page, next))
+    }
+
+    async fn scroll_changed(
+        &self,
+        _context: Option<&MemoryContext>,
+        _since: i64,
+        _cursor: Option<Uuid>,
+        _limit: u32,
+    ) -> Result<(Vec<Memory>, Option<Uuid>)> {
+        Err(VectorStoreError::Unsupported("scroll_changed"))
+    }
+
+    async fn existing_ids(&self, ids: &[Uuid]) -> Result<Vec<Uuid>> {
+        let memories = self.memories.read().await;
+        Ok(ids
+            .iter()
+            .filter(|id| memories.contains_key(id))
+            .copied()
+            .collect())
+    }
+
+    async fn promote(&self, _collection: &str) -> Result<()> {
+        Err(VectorStoreError::Unsupported("promote"))
+    }
+
+    async fn reindex(&self) -> Result<usize> {
+        Ok(0)
+    }
+
+    async fn create_document_collection(&self) -> Result<()> {
+        Err(VectorStoreError::Unsupported("documents"))
+    }
+
+    async fn insert_documents(
+        &self,
+        _vectors: &[&[f32]],
+        _chunks: &[&DocumentChunk],
+    ) -> Result<()> {
+        Err(VectorStoreError::Unsupported("documents"))
+    }
+
+    async fn get_documents(
+        &self,
+        _ids: Option<&[String]>,
+        _filter: &[(String, String)],
+        _limit: Option<u32>,
+    ) -> Result<Vec<DocumentChunk>> {
+        Err(VectorStoreError::Unsupported("documents"))
+    }
+
+    async fn search_documents(
+        &self,
+        _vector: &[f32],
+        _filter: &[(String, String)],
+        _limit: u32,
+    ) -> Result<Vec<DocumentChunk>> {
+        Err(VectorStoreError::Unsupported("documents"))
+    }
+
+    async fn delete_documents(
+        &self,
+        _ids: Option<&[String]>,
+        _filter: &[(String, String)],
+    ) -> Result<()> {
+        Err(VectorStoreError::Unsupported("documents"))
+    }
+
+    async fn count_documents(&self) -> Result<usize> {
+        Err(VectorStoreError::Unsupported("documents"))
+    }
+}
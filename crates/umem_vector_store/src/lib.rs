@@ -1,14 +1,30 @@
+mod compression;
+mod dual_write;
+mod in_memory;
+pub mod migrations;
 mod pgvector;
 mod qdrant;
 
 use async_trait::async_trait;
+pub use dual_write::DualWriteVectorStore;
+pub use in_memory::InMemoryVectorStore;
 use pgvector::{PgError, PgVector};
 use qdrant::{Qdrant, QdrantError};
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::OnceCell;
-use umem_config::CONFIG;
-use umem_core::{Memory, Query};
+use umem_config::{AppConfig, CONFIG};
+use umem_core::{DocumentChunk, Memory, MemoryContext, MemoryFacets, MemoryId, Query, TagCount};
+use uuid::Uuid;
+
+/// Timestamps under `temporal` that `scroll_changed` compares against. Each is indexed.
+const CHANGE_TIMESTAMPS: [&str; 5] = [
+    "created_at",
+    "updated_at",
+    "archived_at",
+    "deleted_at",
+    "suppressed_at",
+];
 
 #[derive(Error, Debug)]
 pub enum VectorStoreError {
@@ -23,6 +39,21 @@ pub enum VectorStoreError {
 
     #[error("serde action failed: {0}")]
     SerdeError(#[from] serde_json::Error),
+
+    #[error("payload compression failed with: {0}")]
+    CompressionError(#[from] std::io::Error),
+
+    #[error("{0} is not supported by this vector store")]
+    Unsupported(&'static str),
+
+    #[error("memory {0} is not stored")]
+    NotFound(Uuid),
+
+    #[error("embedding model dimensions failed with: {0}")]
+    ModelRegistryError(#[from] umem_ai::ModelRegistryError),
+
+    #[error("embedding_model_dimensions is not set")]
+    MissingDimensions,
 }
 
 type Result<T> = std::result::Result<T, VectorStoreError>;
@@ -34,23 +65,59 @@ pub struct VectorStore;
 impl VectorStore {
     pub async fn get_store() -> Result<Arc<dyn VectorStoreBase + Send + Sync>> {
         VECTOR_STORE
-            .get_or_try_init(|| async {
-                match CONFIG.vector_store.clone() {
-                    umem_config::VectorStore::Qdrant(qdrant) => {
-                        let qdrant = Qdrant::new(qdrant).await?;
-                        qdrant.create_collection().await?;
-                        Ok(Arc::new(qdrant) as Arc<dyn VectorStoreBase + Send + Sync>)
-                    }
-                    umem_config::VectorStore::PgVector(pgvector) => {
-                        let pgvector = PgVector::new(pgvector).await?;
-                        pgvector.create_collection().await?;
-                        Ok(Arc::new(pgvector) as Arc<dyn VectorStoreBase + Send + Sync>)
-                    }
-                }
-            })
+            .get_or_try_init(|| Self::from_app_config(&CONFIG))
             .await
             .cloned()
     }
+
+    /// Opens `[vector_store]`, writing through to `[dual_write]`'s store as well while
+    /// a migration is configured.
+    pub async fn from_app_config(
+        config: &AppConfig,
+    ) -> Result<Arc<dyn VectorStoreBase + Send + Sync>> {
+        let store = Self::from_config(&umem_ai::resolve_vector_store(
+            &config.vector_store,
+            &config.embedding_model,
+        )?)
+        .await?;
+        let Some(dual_write) = config.dual_write.as_ref() else {
+            return Ok(store);
+        };
+
+        let target = Self::from_config(&umem_ai::resolve_vector_store(
+            &dual_write.vector_store,
+            &config.embedding_model,
+        )?)
+        .await?;
+        Ok(Arc::new(DualWriteVectorStore::new(
+            store,
+            target,
+            dual_write.read_from_target.unwrap_or(false),
+        )))
+    }
+
+    /// Opens the store described by `config`, whose dimensions must already be set
+    /// (see `umem_ai::resolve_vector_store`).
+    pub async fn from_config(
+        config: &umem_config::VectorStore,
+    ) -> Result<Arc<dyn VectorStoreBase + Send + Sync>> {
+        let dimensions = config
+            .embedding_model_dimensions()
+            .ok_or(VectorStoreError::MissingDimensions)?;
+
+        match config.clone() {
+            umem_config::VectorStore::Qdrant(qdrant) => {
+                let qdrant = Qdrant::new(qdrant, dimensions).await?;
+                qdrant.create_collection().await?;
+                Ok(Arc::new(qdrant) as Arc<dyn VectorStoreBase + Send + Sync>)
+            }
+            umem_config::VectorStore::PgVector(pgvector) => {
+                let pgvector = PgVector::new(pgvector, dimensions).await?;
+                pgvector.create_collection().await?;
+                Ok(Arc::new(pgvector) as Arc<dyn VectorStoreBase + Send + Sync>)
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -61,20 +128,177 @@ pub trait VectorStoreBase {
 
     async fn reset(&self) -> Result<()>;
 
+    /// Fails unless the store can be reached and its collection exists.
+    async fn ping(&self) -> Result<()>;
+
+    /// Stores memories, replacing any memory with the same id, so a retried insert is
+    /// harmless.
     async fn insert(&self, vectors: &[&[f32]], payloads: &[&Memory]) -> Result<()>;
 
-    async fn get(&self, vector_id: &str) -> Result<Memory>;
+    async fn get(&self, id: &MemoryId) -> Result<Memory>;
 
     async fn update(
         &self,
-        vector_id: &str,
+        id: &MemoryId,
         vector: Option<&[f32]>,
         payload: Option<&Memory>,
     ) -> Result<()>;
 
-    async fn delete(&self, vector_id: &str) -> Result<()>;
+    /// Like `update` with a payload, applied only while the stored memory is still at
+    /// `version`. The version is checked by the write itself, so a write landing after
+    /// the caller read the memory is not overwritten. Returns whether it was applied;
+    /// stores that cannot make the write conditional return `Unsupported`.
+    async fn update_if_version(
+        &self,
+        _id: &MemoryId,
+        _vector: Option<&[f32]>,
+        _payload: &Memory,
+        _version: u64,
+    ) -> Result<bool> {
+        Err(VectorStoreError::Unsupported("update_if_version"))
+    }
+
+    async fn delete(&self, id: &MemoryId) -> Result<()>;
 
     async fn list(&self, query: Query) -> Result<Vec<Memory>>;
 
-    async fn search(&self, query: Query) -> Result<Vec<Memory>>;
+    async fn search(&self, query: Query) -> Result<Vec<Memory>> {
+        Ok(self
+            .search_scored(query)
+            .await?
+            .into_iter()
+            .map(|(memory, _)| memory)
+            .collect())
+    }
+
+    /// Like `search`, paired with each memory's cosine similarity to the query vector.
+    async fn search_scored(&self, query: Query) -> Result<Vec<(Memory, f32)>>;
+
+    /// Runs every query in one request, returning one result list per query in order.
+    /// Stores without a batch API return `Unsupported`; search them one query at a time.
+    async fn search_scored_batch(&self, _queries: &[Query]) -> Result<Vec<Vec<(Memory, f32)>>> {
+        Err(VectorStoreError::Unsupported("search_scored_batch"))
+    }
+
+    /// How the store translates the query's filters, for explaining a search.
+    fn describe_filter(&self, query: &Query) -> String;
+
+    /// Counts memories by kind, tag and creation month over everything matching the
+    /// query's filters; the vector and limit are ignored.
+    async fn facets(&self, query: Query) -> Result<MemoryFacets>;
+
+    /// Counts how many memories in `context` use each tag, most used first.
+    async fn tag_counts(&self, context: &MemoryContext) -> Result<Vec<TagCount>>;
+
+    /// Replaces every tag in `from` with the normalized tag `to` on the memories in
+    /// `context`, returning how many memories changed.
+    async fn replace_tags(
+        &self,
+        context: &MemoryContext,
+        from: &[String],
+        to: &str,
+    ) -> Result<usize>;
+
+    /// Pages through every stored memory, archived ones included, in id order. Pass the
+    /// returned cursor back in to continue; `None` means the end was reached.
+    async fn scroll(&self, cursor: Option<Uuid>, limit: u32)
+        -> Result<(Vec<Memory>, Option<Uuid>)>;
+
+    /// Like `scroll`, limited to memories created, updated, archived, deleted or
+    /// suppressed after `since` (unix seconds), and to the memories `context` can read
+    /// when it is given. Deleted and suppressed memories are included, so callers can
+    /// tell they are gone.
+    async fn scroll_changed(
+        &self,
+        context: Option<&MemoryContext>,
+        since: i64,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> Result<(Vec<Memory>, Option<Uuid>)>;
+
+    /// Returns the subset of `ids` that is already stored.
+    async fn existing_ids(&self, ids: &[Uuid]) -> Result<Vec<Uuid>>;
+
+    /// Atomically serves `collection` under this store's collection name and drops the
+    /// collection that was served before.
+    async fn promote(&self, collection: &str) -> Result<()>;
+
+    /// Migrates every stored payload to the current schema version, returning how many
+    /// were upgraded.
+    async fn reindex(&self) -> Result<usize>;
+
+    /// Creates the collection that holds ingested document chunks, named after this
+    /// store's collection with a `_documents` suffix. Kept separate from
+    /// `create_collection` so stores opened only for memories never create it.
+    async fn create_document_collection(&self) -> Result<()>;
+
+    /// Stores document chunks, replacing any chunk with the same id.
+    async fn insert_documents(&self, vectors: &[&[f32]], chunks: &[&DocumentChunk]) -> Result<()>;
+
+    /// Returns the chunks whose metadata matches every `filter` pair, restricted to
+    /// `ids` when given.
+    async fn get_documents(
+        &self,
+        ids: Option<&[String]>,
+        filter: &[(String, String)],
+        limit: Option<u32>,
+    ) -> Result<Vec<DocumentChunk>>;
+
+    /// Returns the `limit` chunks nearest to `vector` whose metadata matches every
+    /// `filter` pair, closest first.
+    async fn search_documents(
+        &self,
+        vector: &[f32],
+        filter: &[(String, String)],
+        limit: u32,
+    ) -> Result<Vec<DocumentChunk>>;
+
+    /// Deletes every chunk whose metadata matches every `filter` pair, restricted to
+    /// `ids` when given.
+    async fn delete_documents(
+        &self,
+        ids: Option<&[String]>,
+        filter: &[(String, String)],
+    ) -> Result<()>;
+
+    async fn count_documents(&self) -> Result<usize>;
+
+    /// Keyword search over summaries and tags, without embeddings.
+    async fn text_search(&self, _query: Query, _text: &str) -> Result<Vec<Memory>> {
+        Err(VectorStoreError::Unsupported("text_search"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use umem_core::MemoryVisibility;
+
+    /// Whether `scroll_changed` returns the stored `memory` for `since`.
+    fn changed_since(memory: &Memory, since: i64) -> bool {
+        let payload = migrations::to_payload(memory).unwrap();
+        CHANGE_TIMESTAMPS.iter().any(|field| {
+            payload["temporal"][field]
+                .as_i64()
+                .is_some_and(|at| at > since)
+        })
+    }
+
+    #[test]
+    fn visibility_and_signal_changes_reach_the_changes_feed() {
+        let mut memory = Memory::gen_dummy().unwrap();
+        let since = memory.temporal().created_at();
+        assert!(!changed_since(&memory, since));
+
+        let later = Utc::now() + Duration::seconds(10);
+        memory.set_visibility(MemoryVisibility::Shared, later);
+        assert!(changed_since(&memory, since));
+
+        let since = later.timestamp();
+        memory
+            .adjust_signals(0.1, 0.0, None, later + Duration::seconds(10))
+            .unwrap();
+        assert!(changed_since(&memory, since));
+    }
 }
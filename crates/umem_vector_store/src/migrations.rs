@@ -0,0 +1,129 @@
+use serde_json::{Map, Value};
+use umem_core::{Memory, TemporalMetadata};
+
+pub const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// Derived `YYYY-MM` creation month, stored so backends can facet on it.
+pub const CREATED_MONTH_FIELD: &str = "created_month";
+
+/// Version stamped on every payload written by this build.
+pub const CURRENT_SCHEMA_VERSION: u64 = 3;
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[n]` upgrades a payload from version `n` to `n + 1`.
+const MIGRATIONS: &[Migration] = &[v0_to_v1, v1_to_v2, v2_to_v3];
+
+/// Payloads written before versioning. Every field added since (signals, provenance,
+/// language, contradicts, schedule) has a serde default, so only the version is stamped.
+fn v0_to_v1(_payload: &mut Map<String, Value>) {}
+
+/// Adds the derived creation month used by month facets.
+fn v1_to_v2(payload: &mut Map<String, Value>) {
+    let created_at = payload
+        .get("temporal")
+        .and_then(|temporal| temporal.get("created_at"))
+        .and_then(Value::as_i64)
+        .unwrap_or_default();
+    let month = TemporalMetadata::with_times(created_at, None, None)
+        .map(|temporal| temporal.created_month())
+        .unwrap_or_default();
+    payload.insert(CREATED_MONTH_FIELD.to_string(), Value::from(month));
+}
+
+/// Turns the single provenance method into a chain of one step.
+fn v2_to_v3(payload: &mut Map<String, Value>) {
+    let Some(Value::Object(provenance)) = payload.get_mut("provenance") else {
+        return;
+    };
+    if provenance.contains_key("chain") {
+        return;
+    }
+    let method = provenance
+        .remove("method")
+        .unwrap_or_else(|| Value::from("Direct"));
+    provenance.insert("chain".to_string(), Value::Array(vec![method]));
+}
+
+pub fn schema_version(payload: &Value) -> u64 {
+    payload
+        .get(SCHEMA_VERSION_FIELD)
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+pub fn to_payload(memory: &Memory) -> serde_json::Result<Value> {
+    let mut payload = serde_json::to_value(memory)?;
+    if let Value::Object(fields) = &mut payload {
+        fields.insert(
+            SCHEMA_VERSION_FIELD.to_string(),
+            Value::from(CURRENT_SCHEMA_VERSION),
+        );
+        fields.insert(
+            CREATED_MONTH_FIELD.to_string(),
+            Value::from(memory.temporal().created_month()),
+        );
+    }
+    Ok(payload)
+}
+
+/// Upgrades `payload` to the current schema and decodes it. The flag is `true` when
+/// the payload was migrated and should be written back.
+pub fn from_payload(mut payload: Value) -> serde_json::Result<(Memory, bool)> {
+    let version = schema_version(&payload);
+    let outdated = version < CURRENT_SCHEMA_VERSION;
+
+    if let (true, Value::Object(fields)) = (outdated, &mut payload) {
+        for migration in MIGRATIONS.iter().skip(version as usize) {
+            migration(fields);
+        }
+        fields.insert(
+            SCHEMA_VERSION_FIELD.to_string(),
+            Value::from(CURRENT_SCHEMA_VERSION),
+        );
+    }
+
+    Ok((serde_json::from_value(payload)?, outdated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upgrades_unversioned_payloads() {
+        let memory = Memory::gen_dummy().unwrap();
+        let mut payload = serde_json::to_value(&memory).unwrap();
+        payload.as_object_mut().unwrap().remove("contradicts");
+
+        let (migrated, outdated) = from_payload(payload).unwrap();
+        assert!(outdated);
+        assert_eq!(migrated.get_id(), memory.get_id());
+
+        let payload = to_payload(&migrated).unwrap();
+        assert_eq!(
+            payload[CREATED_MONTH_FIELD],
+            memory.temporal().created_month()
+        );
+
+        let (_, outdated) = from_payload(payload).unwrap();
+        assert!(!outdated);
+    }
+
+    #[test]
+    fn moves_provenance_methods_into_chains() {
+        let memory = Memory::gen_dummy().unwrap();
+        let mut payload = to_payload(&memory).unwrap();
+        payload[SCHEMA_VERSION_FIELD] = Value::from(2);
+        let provenance = payload["provenance"].as_object_mut().unwrap();
+        provenance.remove("chain");
+        provenance.insert("method".to_string(), Value::from("Direct"));
+
+        let (migrated, outdated) = from_payload(payload).unwrap();
+        assert!(outdated);
+        assert_eq!(
+            migrated.provenance().chain,
+            vec![umem_core::ProvenanceMethod::Direct]
+        );
+    }
+}
@@ -1,19 +1,48 @@
-use std::iter::zip;
+use std::{collections::HashSet, iter::zip};
 
-use crate::{VectorStoreBase, VectorStoreError};
+use crate::{compression, migrations, VectorStoreBase, VectorStoreError, CHANGE_TIMESTAMPS};
 use async_trait::async_trait;
-use serde_json::json;
-use sqlx::{postgres::PgPoolOptions, query, Pool, Postgres, QueryBuilder, Row};
+use chrono::Utc;
+use sqlx::{
+    postgres::{PgPoolOptions, PgRow},
+    query, Pool, Postgres, QueryBuilder, Row,
+};
 use thiserror::Error;
+use umem_core::DocumentChunk;
+use umem_core::FacetCount;
 use umem_core::LifecycleState;
 use umem_core::Memory;
+use umem_core::MemoryContext;
+use umem_core::MemoryFacets;
+use umem_core::MemoryId;
+use umem_core::MemoryVisibility;
 use umem_core::Query;
+use umem_core::TagCount;
 use uuid::Uuid;
 
+const FACET_LIMIT: u32 = 1000;
+/// Rows per multi-row INSERT, well under Postgres' 65535 bind parameters. Full batches
+/// share one statement text, so sqlx prepares it once per connection.
+const INSERT_BATCH_SIZE: usize = 1000;
+
+/// Keeps only the last row of each id, in order, since one `INSERT … ON CONFLICT DO
+/// UPDATE` fails when it would update the same row twice.
+fn last_per_id<T>(rows: Vec<(Uuid, T)>) -> Vec<(Uuid, T)> {
+    let mut seen = HashSet::new();
+    let mut rows: Vec<(Uuid, T)> = rows
+        .into_iter()
+        .rev()
+        .filter(|(id, _)| seen.insert(*id))
+        .collect();
+    rows.reverse();
+    rows
+}
+
 pub struct PgVector {
     client: Pool<Postgres>,
     collection_name: String,
     embedding_model_dimensions: u16,
+    compress_payloads: bool,
 }
 
 #[derive(Error, Debug)]
@@ -34,7 +63,10 @@ impl From<sqlx::Error> for VectorStoreError {
 type Result<T> = std::result::Result<T, PgError>;
 
 impl PgVector {
-    pub async fn new(pgvector: umem_config::PgVector) -> Result<Self> {
+    pub async fn new(
+        pgvector: umem_config::PgVector,
+        embedding_model_dimensions: u16,
+    ) -> Result<Self> {
         let pool = PgPoolOptions::new()
             .max_connections(5)
             .connect(&pgvector.url)
@@ -42,31 +74,193 @@ impl PgVector {
 
         Ok(Self {
             client: pool,
-            embedding_model_dimensions: pgvector.embedding_model_dimensions,
+            embedding_model_dimensions,
             collection_name: pgvector.collection_name,
+            compress_payloads: pgvector.compress_payloads.unwrap_or_default(),
+        })
+    }
+
+    /// The payload of `memory` and, when payloads are compressed, its compressed part.
+    fn payload_of(&self, memory: &Memory) -> crate::Result<(serde_json::Value, Option<Vec<u8>>)> {
+        let mut payload = migrations::to_payload(memory)?;
+        let compressed = if self.compress_payloads {
+            compression::compress(&mut payload)?
+        } else {
+            None
+        };
+        Ok((payload, compressed))
+    }
+
+    /// Decodes a stored payload, writing it back when it had to be migrated.
+    async fn decode(&self, row: &PgRow) -> crate::Result<(Memory, bool)> {
+        let mut payload: serde_json::Value = row.try_get("payload")?;
+        if let Some(compressed) =
+            row.try_get::<Option<Vec<u8>>, _>(compression::COMPRESSED_FIELD)?
+        {
+            compression::decompress(&mut payload, &compressed)?;
+        }
+        let (memory, outdated) = migrations::from_payload(payload)?;
+        if outdated {
+            self.update(&memory.memory_id(), None, Some(&memory))
+                .await?;
+        }
+        Ok((memory, outdated))
+    }
+
+    /// Counts the filtered rows grouped by the SQL expression `value`; `source` may add a
+    /// lateral join (e.g. to unnest tags).
+    async fn facet_counts(
+        &self,
+        value: &str,
+        source: &str,
+        query: &Query,
+        order_by: &str,
+    ) -> crate::Result<Vec<FacetCount>> {
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+            " SELECT {} AS value, count(*) FROM {}{} WHERE 1=1 ",
+            value, self.collection_name, source
+        ));
+        PgVector::filter_query(&mut query_builder, query);
+        query_builder.push(format!(
+            " GROUP BY value ORDER BY {} LIMIT {} ",
+            order_by, FACET_LIMIT
+        ));
+
+        query_builder
+            .build()
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(FacetCount {
+                    value: row.try_get(0)?,
+                    count: row.try_get::<i64, _>(1)? as u64,
+                })
+            })
+            .collect()
+    }
+
+    fn documents_table(&self) -> String {
+        format!("{}_documents", self.collection_name)
+    }
+
+    fn decode_document(row: &PgRow) -> crate::Result<DocumentChunk> {
+        Ok(DocumentChunk {
+            id: row.try_get(0)?,
+            document: row.try_get(1)?,
+            metadata: serde_json::from_value(row.try_get(2)?)?,
         })
     }
 
+    /// `metadata @> {...}` matches rows whose metadata contains every filter pair; an
+    /// empty filter matches everything.
+    fn filter_documents(builder: &mut QueryBuilder<'_, Postgres>, filter: &[(String, String)]) {
+        let filter: serde_json::Map<String, serde_json::Value> = filter
+            .iter()
+            .map(|(key, value)| (key.clone(), serde_json::Value::String(value.clone())))
+            .collect();
+        builder.push(" metadata @> ");
+        builder.push_bind(serde_json::Value::Object(filter));
+        builder.push(" ");
+    }
+
+    async fn decode_rows(&self, rows: Vec<PgRow>) -> crate::Result<Vec<Memory>> {
+        let mut memories = Vec::with_capacity(rows.len());
+        for row in rows {
+            memories.push(self.decode(&row).await?.0);
+        }
+        Ok(memories)
+    }
+
     fn filter_include_archived(builder: &mut QueryBuilder<'_, Postgres>, query: &umem_core::Query) {
-        if !query.include_archived() {
+        if let Some(as_of) = query.as_of() {
+            Self::filter_as_of(builder, as_of.timestamp(), query.include_archived());
+        } else if !query.include_archived() {
             builder.push(format!(
                 "AND payload->>'lifecycle'='{}' ",
                 LifecycleState::Active.as_str()
             ));
+        } else {
+            Self::filter_retrievable(builder);
         }
     }
 
-    fn filter_context(builder: &mut QueryBuilder<'_, Postgres>, query: &umem_core::Query) {
-        if let Some(user_id) = query.context().user_id() {
-            builder.push(format!("AND payload->'context'->>'user_id'='{}' ", user_id));
+    /// Matches on the lifecycle timestamps instead of the lifecycle, which only tells
+    /// the present state.
+    fn filter_as_of(builder: &mut QueryBuilder<'_, Postgres>, as_of: i64, include_archived: bool) {
+        builder.push(" AND (payload->'temporal'->>'created_at')::bigint <= ");
+        builder.push_bind(as_of);
+
+        let mut ended = vec!["deleted_at", "suppressed_at"];
+        if !include_archived {
+            ended.extend(["archived_at", "superseded_at"]);
         }
-        if let Some(agent_id) = query.context().agent_id() {
+        for field in ended {
             builder.push(format!(
-                "AND payload->'context'->>'agent_id'='{}' ",
-                agent_id
+                " AND (payload->'temporal'->>'{field}' IS NULL OR (payload->'temporal'->>'{field}')::bigint > "
             ));
+            builder.push_bind(as_of);
+            builder.push(")");
         }
-        if let Some(run_id) = query.context().run_id() {
+    }
+
+    /// Deleted and suppressed memories never show up in queries, archived or not.
+    fn filter_retrievable(builder: &mut QueryBuilder<'_, Postgres>) {
+        builder.push(" AND payload->>'lifecycle'=ANY(");
+        builder.push_bind(
+            LifecycleState::RETRIEVABLE
+                .iter()
+                .map(|state| state.as_str())
+                .collect::<Vec<&str>>(),
+        );
+        builder.push(") ");
+    }
+
+    /// A personal context that names an organization also matches that organization's
+    /// pool; see `filter_personal_context` for `shared`.
+    fn filter_context(
+        builder: &mut QueryBuilder<'_, Postgres>,
+        context: &MemoryContext,
+        shared: bool,
+    ) {
+        match context.org_id() {
+            Some(org_id) if context.is_org_pool() => {
+                builder.push(format!("AND payload->'context'->>'org_id'='{}' ", org_id));
+            }
+            Some(org_id) => {
+                builder.push("AND ((TRUE ");
+                Self::filter_personal_context(builder, context, shared);
+                builder.push(format!(") OR payload->'context'->>'org_id'='{}') ", org_id));
+            }
+            None => Self::filter_personal_context(builder, context, shared),
+        }
+    }
+
+    /// With `shared`, memories other agents of the same user shared are matched as
+    /// well as the agent's own.
+    fn filter_personal_context(
+        builder: &mut QueryBuilder<'_, Postgres>,
+        context: &MemoryContext,
+        shared: bool,
+    ) {
+        if let Some(user_id) = context.user_id() {
+            builder.push(format!("AND payload->'context'->>'user_id'='{}' ", user_id));
+        }
+        if let Some(agent_id) = context.agent_id() {
+            if shared && context.user_id().is_some() {
+                builder.push(format!(
+                    "AND (payload->'context'->>'agent_id'='{}' OR payload->>'visibility'='{}') ",
+                    agent_id,
+                    MemoryVisibility::Shared.as_str()
+                ));
+            } else {
+                builder.push(format!(
+                    "AND payload->'context'->>'agent_id'='{}' ",
+                    agent_id
+                ));
+            }
+        }
+        if let Some(run_id) = context.run_id() {
             builder.push(format!("AND payload->'context'->>'run_id'='{}' ", run_id));
         }
     }
@@ -95,6 +289,14 @@ impl PgVector {
         }
     }
 
+    fn filter_topics(builder: &mut QueryBuilder<'_, Postgres>, query: &umem_core::Query) {
+        if let Some(topics) = query.topics() {
+            builder.push(" AND payload->>'topic'=ANY(");
+            builder.push_bind(topics.to_vec());
+            builder.push(") ");
+        }
+    }
+
     fn filter_temporal(builder: &mut QueryBuilder<'_, Postgres>, query: &umem_core::Query) {
         if let Some(temporal) = query.temporal() {
             if temporal.has_created_range() {
@@ -133,16 +335,25 @@ impl PgVector {
         }
     }
 
-    fn create_filter(builder: &mut QueryBuilder<'_, Postgres>, query: &Query) {
+    fn filter_query(builder: &mut QueryBuilder<'_, Postgres>, query: &Query) {
         Self::filter_include_archived(builder, query);
-        Self::filter_context(builder, query);
+        Self::filter_context(builder, query.context(), query.include_shared());
         Self::filter_kinds(builder, query);
         Self::filter_tags(builder, query);
+        Self::filter_topics(builder, query);
         Self::filter_temporal(builder, query);
         Self::filter_signals(builder, query);
+    }
+
+    fn create_filter(
+        builder: &mut QueryBuilder<'_, Postgres>,
+        query: &Query,
+        order_by: Option<&str>,
+    ) {
+        Self::filter_query(builder, query);
 
-        if query.vector().is_some() {
-            builder.push(" ORDER by distance ");
+        if let Some(order_by) = order_by {
+            builder.push(format!(" ORDER by {} ", order_by));
         }
         builder.push(format!(" LIMIT {} ", query.limit()));
     }
@@ -168,6 +379,14 @@ impl VectorStoreBase for PgVector {
         .execute(&self.client)
         .await?;
 
+        query(&format!(
+            r#"ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} BYTEA"#,
+            self.collection_name,
+            compression::COMPRESSED_FIELD
+        ))
+        .execute(&self.client)
+        .await?;
+
         query(&format!(
             r#"
             CREATE INDEX IF NOT EXISTS {}_hnsw_idx
@@ -179,6 +398,44 @@ impl VectorStoreBase for PgVector {
         .execute(&self.client)
         .await?;
 
+        // NOTE: expression indexes for scroll_changed, which compares the same casts
+        for field in CHANGE_TIMESTAMPS {
+            query(&format!(
+                r#"
+                CREATE INDEX IF NOT EXISTS {}_{field}_idx
+                    ON {} (((payload->'temporal'->>'{field}')::bigint))
+                "#,
+                &self.collection_name, &self.collection_name
+            ))
+            .execute(&self.client)
+            .await?;
+        }
+
+        // NOTE: 'simple' config so identifiers and error codes are matched verbatim, not stemmed
+        query(&format!(
+            r#"
+            ALTER TABLE {} ADD COLUMN IF NOT EXISTS search_text tsvector
+                GENERATED ALWAYS AS (
+                    setweight(to_tsvector('simple', coalesce(payload->'content'->>'summary', '')), 'A') ||
+                    setweight(jsonb_to_tsvector('simple', coalesce(payload->'content'->'tags', '[]'::jsonb), '["string"]'), 'B')
+                ) STORED
+            "#,
+            &self.collection_name
+        ))
+        .execute(&self.client)
+        .await?;
+
+        query(&format!(
+            r#"
+            CREATE INDEX IF NOT EXISTS {}_search_text_idx
+                ON {}
+                USING gin (search_text)
+            "#,
+            &self.collection_name, &self.collection_name
+        ))
+        .execute(&self.client)
+        .await?;
+
         Ok(())
     }
 
@@ -195,77 +452,151 @@ impl VectorStoreBase for PgVector {
         self.create_collection().await
     }
 
+    async fn ping(&self) -> crate::Result<()> {
+        query(&format!(
+            r#"SELECT 1 FROM {} LIMIT 1"#,
+            self.collection_name
+        ))
+        .fetch_optional(&self.client)
+        .await?;
+        Ok(())
+    }
+
     async fn insert(&self, vectors: &[&[f32]], payloads: &[&Memory]) -> crate::Result<()> {
-        for (vector, payload) in zip(vectors, payloads) {
-            query(&format!(
-                r#"INSERT INTO {}
-                    (id, vector, payload)
-                    VALUES
-                    ($1, $2, $3)"#,
-                self.collection_name
-            ))
-            .bind(payload.get_id())
-            .bind(vector)
-            .bind(json!(payload))
-            .execute(&self.client)
-            .await?;
+        let rows = zip(vectors, payloads)
+            .map(|(vector, memory)| {
+                let (payload, compressed) = self.payload_of(memory)?;
+                Ok((*memory.get_id(), (*vector, payload, compressed)))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        let rows = last_per_id(rows);
+
+        let mut transaction = self.client.begin().await?;
+
+        for batch in rows.chunks(INSERT_BATCH_SIZE) {
+            let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+                "INSERT INTO {} (id, vector, payload, {}) ",
+                self.collection_name,
+                compression::COMPRESSED_FIELD
+            ));
+            query_builder.push_values(batch, |mut row, (id, (vector, payload, compressed))| {
+                row.push_bind(id)
+                    .push_bind(vector)
+                    .push_bind(payload)
+                    .push_bind(compressed);
+            });
+            query_builder.push(format!(
+                " ON CONFLICT (id) DO UPDATE SET vector = EXCLUDED.vector, payload = EXCLUDED.payload, {0} = EXCLUDED.{0}",
+                compression::COMPRESSED_FIELD
+            ));
+
+            query_builder.build().execute(&mut *transaction).await?;
         }
 
+        transaction.commit().await?;
         Ok(())
     }
 
-    async fn get(&self, vector_id: &str) -> crate::Result<Memory> {
+    async fn get(&self, id: &MemoryId) -> crate::Result<Memory> {
         let result = query(&format!(
-            r#"SELECT payload FROM {} WHERE id = $1"#,
+            r#"SELECT payload, {} FROM {} WHERE id = $1"#,
+            compression::COMPRESSED_FIELD,
             self.collection_name,
         ))
-        .bind(Uuid::parse_str(vector_id)?)
+        .bind(*id.as_uuid())
         .fetch_one(&self.client)
         .await?;
 
-        let payload: serde_json::Value = result.try_get(0)?;
-        let memory: Memory = serde_json::from_value(payload)?;
-
+        let (memory, _) = self.decode(&result).await?;
         Ok(memory)
     }
 
     async fn update(
         &self,
-        vector_id: &str,
+        id: &MemoryId,
         vector: Option<&[f32]>,
         payload: Option<&Memory>,
     ) -> crate::Result<()> {
+        let mut transaction = self.client.begin().await?;
+
         if let Some(vector) = vector {
             query(&format!(
                 r#"UPDATE {} SET vector = $1 WHERE id = $2"#,
                 self.collection_name,
             ))
             .bind(vector)
-            .bind(Uuid::parse_str(vector_id)?)
-            .execute(&self.client)
+            .bind(*id.as_uuid())
+            .execute(&mut *transaction)
             .await?;
         }
 
         if let Some(payload) = payload {
+            let (payload, compressed) = self.payload_of(payload)?;
             query(&format!(
-                r#"UPDATE {} SET payload = $1 WHERE id = $2"#,
+                r#"UPDATE {} SET payload = $1, {} = $2 WHERE id = $3"#,
                 self.collection_name,
+                compression::COMPRESSED_FIELD,
             ))
-            .bind(json!(payload))
-            .bind(Uuid::parse_str(vector_id)?)
-            .execute(&self.client)
+            .bind(payload)
+            .bind(compressed)
+            .bind(*id.as_uuid())
+            .execute(&mut *transaction)
             .await?;
         }
 
+        transaction.commit().await?;
         Ok(())
     }
 
-    async fn delete(&self, vector_id: &str) -> crate::Result<()> {
+    async fn update_if_version(
+        &self,
+        id: &MemoryId,
+        vector: Option<&[f32]>,
+        payload: &Memory,
+        version: u64,
+    ) -> crate::Result<bool> {
+        let (payload, compressed) = self.payload_of(payload)?;
+        let mut transaction = self.client.begin().await?;
+
+        // NOTE: payloads stored before versions were recorded have none and are at 0
+        let updated = query(&format!(
+            r#"UPDATE {} SET payload = $1, {} = $2
+                WHERE id = $3 AND COALESCE((payload->>'version')::bigint, 0) = $4"#,
+            self.collection_name,
+            compression::COMPRESSED_FIELD,
+        ))
+        .bind(payload)
+        .bind(compressed)
+        .bind(*id.as_uuid())
+        .bind(version as i64)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Ok(false);
+        }
+
+        if let Some(vector) = vector {
+            query(&format!(
+                r#"UPDATE {} SET vector = $1 WHERE id = $2"#,
+                self.collection_name,
+            ))
+            .bind(vector)
+            .bind(*id.as_uuid())
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(true)
+    }
+
+    async fn delete(&self, id: &MemoryId) -> crate::Result<()> {
         query(&format!(
             r#"DELETE FROM {} WHERE id = $1"#,
             self.collection_name,
         ))
-        .bind(Uuid::parse_str(vector_id)?)
+        .bind(*id.as_uuid())
         .execute(&self.client)
         .await?;
 
@@ -274,37 +605,260 @@ impl VectorStoreBase for PgVector {
 
     async fn list(&self, query: umem_core::Query) -> crate::Result<Vec<Memory>> {
         let mut query_builder = QueryBuilder::<Postgres>::new(format!(
-            " SELECT payload FROM {} WHERE 1=1 ",
+            " SELECT payload, {} FROM {} WHERE 1=1 ",
+            compression::COMPRESSED_FIELD,
             self.collection_name
         ));
 
-        PgVector::create_filter(&mut query_builder, &query);
+        PgVector::create_filter(&mut query_builder, &query, None);
 
         let q = query_builder.build();
 
-        q.fetch_all(&self.client)
-            .await?
-            .into_iter()
-            .map(|row| {
-                let payload: serde_json::Value = row.try_get(0)?;
-                let memory: Memory = serde_json::from_value(payload)?;
-                Ok(memory)
-            })
-            .collect()
+        self.decode_rows(q.fetch_all(&self.client).await?).await
     }
 
-    async fn search(&self, query: umem_core::Query) -> crate::Result<Vec<Memory>> {
+    fn describe_filter(&self, query: &umem_core::Query) -> String {
+        let mut builder = QueryBuilder::<Postgres>::new("WHERE 1=1");
+        PgVector::filter_query(&mut builder, query);
+        builder.into_sql()
+    }
+
+    async fn search_scored(&self, query: umem_core::Query) -> crate::Result<Vec<(Memory, f32)>> {
         if query.vector().is_none() {
             return Err(PgError::VectorNotSupplied)?;
         }
 
         let mut query_builder = QueryBuilder::<Postgres>::new(format!(
-            " SELECT payload, vector<=>'{:?}'::vector AS distance FROM {} WHERE 1=1 ",
+            " SELECT payload, vector<=>'{:?}'::vector AS distance, {} FROM {} WHERE 1=1 ",
             query.vector().unwrap(),
+            compression::COMPRESSED_FIELD,
             self.collection_name
         ));
 
-        PgVector::create_filter(&mut query_builder, &query);
+        PgVector::create_filter(&mut query_builder, &query, Some("distance"));
+
+        let rows = query_builder.build().fetch_all(&self.client).await?;
+        let mut memories = Vec::with_capacity(rows.len());
+        for row in rows {
+            let distance: f64 = row.try_get(1)?;
+            memories.push((self.decode(&row).await?.0, 1.0 - distance as f32));
+        }
+        Ok(memories)
+    }
+
+    async fn create_document_collection(&self) -> crate::Result<()> {
+        query(&format!(
+            r#"
+            CREATE TABLE IF NOT EXISTS {} (
+                id TEXT PRIMARY KEY,
+                vector vector({}),
+                document TEXT NOT NULL,
+                metadata JSONB NOT NULL
+            )
+            "#,
+            self.documents_table(),
+            self.embedding_model_dimensions
+        ))
+        .execute(&self.client)
+        .await?;
+
+        query(&format!(
+            r#"
+            CREATE INDEX IF NOT EXISTS {}_hnsw_idx
+                ON {}
+                USING hnsw (vector vector_cosine_ops)
+            "#,
+            self.documents_table(),
+            self.documents_table()
+        ))
+        .execute(&self.client)
+        .await?;
+
+        query(&format!(
+            r#"
+            CREATE INDEX IF NOT EXISTS {}_metadata_idx
+                ON {}
+                USING gin (metadata)
+            "#,
+            self.documents_table(),
+            self.documents_table()
+        ))
+        .execute(&self.client)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_documents(
+        &self,
+        vectors: &[&[f32]],
+        chunks: &[&DocumentChunk],
+    ) -> crate::Result<()> {
+        for (vector, chunk) in zip(vectors, chunks) {
+            query(&format!(
+                r#"INSERT INTO {}
+                    (id, vector, document, metadata)
+                    VALUES
+                    ($1, $2, $3, $4)
+                    ON CONFLICT (id) DO UPDATE
+                    SET vector = EXCLUDED.vector, document = EXCLUDED.document, metadata = EXCLUDED.metadata"#,
+                self.documents_table()
+            ))
+            .bind(&chunk.id)
+            .bind(vector)
+            .bind(&chunk.document)
+            .bind(serde_json::to_value(&chunk.metadata)?)
+            .execute(&self.client)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_documents(
+        &self,
+        ids: Option<&[String]>,
+        filter: &[(String, String)],
+        limit: Option<u32>,
+    ) -> crate::Result<Vec<DocumentChunk>> {
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+            " SELECT id, document, metadata FROM {} WHERE ",
+            self.documents_table()
+        ));
+        PgVector::filter_documents(&mut query_builder, filter);
+        if let Some(ids) = ids {
+            query_builder.push(" AND id = ANY(");
+            query_builder.push_bind(ids.to_vec());
+            query_builder.push(") ");
+        }
+        if let Some(limit) = limit {
+            query_builder.push(format!(" LIMIT {} ", limit));
+        }
+
+        query_builder
+            .build()
+            .fetch_all(&self.client)
+            .await?
+            .iter()
+            .map(PgVector::decode_document)
+            .collect()
+    }
+
+    async fn search_documents(
+        &self,
+        vector: &[f32],
+        filter: &[(String, String)],
+        limit: u32,
+    ) -> crate::Result<Vec<DocumentChunk>> {
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+            " SELECT id, document, metadata FROM {} WHERE ",
+            self.documents_table()
+        ));
+        PgVector::filter_documents(&mut query_builder, filter);
+        query_builder.push(format!(
+            " ORDER BY vector<=>'{:?}'::vector LIMIT {} ",
+            vector, limit
+        ));
+
+        query_builder
+            .build()
+            .fetch_all(&self.client)
+            .await?
+            .iter()
+            .map(PgVector::decode_document)
+            .collect()
+    }
+
+    async fn delete_documents(
+        &self,
+        ids: Option<&[String]>,
+        filter: &[(String, String)],
+    ) -> crate::Result<()> {
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+            " DELETE FROM {} WHERE ",
+            self.documents_table()
+        ));
+        PgVector::filter_documents(&mut query_builder, filter);
+        if let Some(ids) = ids {
+            query_builder.push(" AND id = ANY(");
+            query_builder.push_bind(ids.to_vec());
+            query_builder.push(") ");
+        }
+
+        query_builder.build().execute(&self.client).await?;
+        Ok(())
+    }
+
+    async fn count_documents(&self) -> crate::Result<usize> {
+        let row = query(&format!(
+            r#"SELECT count(*) FROM {}"#,
+            self.documents_table()
+        ))
+        .fetch_one(&self.client)
+        .await?;
+
+        Ok(row.try_get::<i64, _>(0)? as usize)
+    }
+
+    async fn text_search(&self, query: umem_core::Query, text: &str) -> crate::Result<Vec<Memory>> {
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+            " SELECT payload, {}, ts_rank(search_text, websearch_to_tsquery('simple', ",
+            compression::COMPRESSED_FIELD
+        ));
+        query_builder.push_bind(text.to_owned());
+        query_builder.push(format!(
+            ")) AS rank FROM {} WHERE search_text @@ websearch_to_tsquery('simple', ",
+            self.collection_name
+        ));
+        query_builder.push_bind(text.to_owned());
+        query_builder.push(") ");
+
+        PgVector::create_filter(&mut query_builder, &query, Some("rank DESC"));
+
+        let rows = query_builder.build().fetch_all(&self.client).await?;
+        self.decode_rows(rows).await
+    }
+
+    async fn facets(&self, query: umem_core::Query) -> crate::Result<MemoryFacets> {
+        Ok(MemoryFacets {
+            kinds: self
+                .facet_counts("payload->>'kind'", "", &query, "count(*) DESC, value")
+                .await?,
+            tags: self
+                .facet_counts(
+                    "tag",
+                    ", jsonb_array_elements_text(payload->'content'->'tags') AS tag",
+                    &query,
+                    "count(*) DESC, value",
+                )
+                .await?,
+            months: self
+                .facet_counts(
+                    "to_char(to_timestamp((payload->'temporal'->>'created_at')::bigint) AT TIME ZONE 'UTC', 'YYYY-MM')",
+                    "",
+                    &query,
+                    "value DESC",
+                )
+                .await?,
+            topics: self
+                .facet_counts(
+                    "topic #>> '{}'",
+                    ", jsonb_path_query(payload, '$.topic ? (@ != null)') AS topic",
+                    &query,
+                    "count(*) DESC, value",
+                )
+                .await?,
+        })
+    }
+
+    async fn tag_counts(&self, context: &MemoryContext) -> crate::Result<Vec<TagCount>> {
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+            " SELECT tag, count(*) FROM {}, jsonb_array_elements_text(payload->'content'->'tags') AS tag WHERE 1=1 ",
+            self.collection_name
+        ));
+        PgVector::filter_retrievable(&mut query_builder);
+        PgVector::filter_context(&mut query_builder, context, false);
+        query_builder.push(" GROUP BY tag ORDER BY count(*) DESC, tag ");
 
         query_builder
             .build()
@@ -312,10 +866,180 @@ impl VectorStoreBase for PgVector {
             .await?
             .into_iter()
             .map(|row| {
-                let payload: serde_json::Value = row.try_get(0)?;
-                let memory: Memory = serde_json::from_value(payload)?;
-                Ok(memory)
+                Ok(TagCount {
+                    tag: row.try_get(0)?,
+                    count: row.try_get::<i64, _>(1)? as u64,
+                })
             })
             .collect()
     }
+
+    async fn replace_tags(
+        &self,
+        context: &MemoryContext,
+        from: &[String],
+        to: &str,
+    ) -> crate::Result<usize> {
+        // NOTE: rewritten one by one through `Memory::replace_tags`, which stamps the
+        // change and bumps the version, as the Qdrant store does
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+            " SELECT payload, {} FROM {} WHERE payload->'content'->'tags' ?| ",
+            compression::COMPRESSED_FIELD,
+            self.collection_name
+        ));
+        query_builder.push_bind(from.to_vec());
+        query_builder.push(" ");
+        PgVector::filter_retrievable(&mut query_builder);
+        PgVector::filter_context(&mut query_builder, context, false);
+
+        let rows = query_builder.build().fetch_all(&self.client).await?;
+        let now = Utc::now();
+        let mut replaced = 0;
+        for mut memory in self.decode_rows(rows).await? {
+            if memory.replace_tags(from, to, now) {
+                self.update(&memory.memory_id(), None, Some(&memory))
+                    .await?;
+                replaced += 1;
+            }
+        }
+        Ok(replaced)
+    }
+
+    async fn scroll(
+        &self,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> crate::Result<(Vec<Memory>, Option<Uuid>)> {
+        let rows = query(&format!(
+            r#"SELECT payload, id, {} FROM {} WHERE $1::uuid IS NULL OR id > $1 ORDER BY id LIMIT $2"#,
+            compression::COMPRESSED_FIELD,
+            self.collection_name,
+        ))
+        .bind(cursor)
+        .bind(i64::from(limit))
+        .fetch_all(&self.client)
+        .await?;
+
+        let next = if rows.len() == limit as usize {
+            rows.last()
+                .map(|row| row.try_get::<Uuid, _>(1))
+                .transpose()?
+        } else {
+            None
+        };
+
+        Ok((self.decode_rows(rows).await?, next))
+    }
+
+    async fn scroll_changed(
+        &self,
+        context: Option<&MemoryContext>,
+        since: i64,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> crate::Result<(Vec<Memory>, Option<Uuid>)> {
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!(
+            " SELECT payload, id, {} FROM {} WHERE (FALSE ",
+            compression::COMPRESSED_FIELD,
+            self.collection_name,
+        ));
+        for field in CHANGE_TIMESTAMPS {
+            query_builder.push(format!(" OR (payload->'temporal'->>'{field}')::bigint > "));
+            query_builder.push_bind(since);
+        }
+        query_builder.push(") ");
+        if let Some(cursor) = cursor {
+            query_builder.push(" AND id > ");
+            query_builder.push_bind(cursor);
+            query_builder.push(" ");
+        }
+        if let Some(context) = context {
+            PgVector::filter_context(&mut query_builder, context, true);
+        }
+        query_builder.push(" ORDER BY id LIMIT ");
+        query_builder.push_bind(i64::from(limit));
+        let rows = query_builder.build().fetch_all(&self.client).await?;
+
+        let next = if rows.len() == limit as usize {
+            rows.last()
+                .map(|row| row.try_get::<Uuid, _>(1))
+                .transpose()?
+        } else {
+            None
+        };
+
+        Ok((self.decode_rows(rows).await?, next))
+    }
+
+    async fn existing_ids(&self, ids: &[Uuid]) -> crate::Result<Vec<Uuid>> {
+        let rows = query(&format!(
+            r#"SELECT id FROM {} WHERE id = ANY($1)"#,
+            self.collection_name,
+        ))
+        .bind(ids)
+        .fetch_all(&self.client)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| row.try_get::<Uuid, _>(0))
+            .collect::<std::result::Result<Vec<Uuid>, sqlx::Error>>()?)
+    }
+
+    async fn promote(&self, collection: &str) -> crate::Result<()> {
+        let mut transaction = self.client.begin().await?;
+
+        query(&format!(r#"DROP TABLE IF EXISTS {}"#, self.collection_name))
+            .execute(&mut *transaction)
+            .await?;
+        query(&format!(
+            r#"ALTER TABLE {} RENAME TO {}"#,
+            collection, self.collection_name
+        ))
+        .execute(&mut *transaction)
+        .await?;
+
+        for index in ["hnsw_idx", "search_text_idx"] {
+            query(&format!(
+                r#"ALTER INDEX IF EXISTS {}_{} RENAME TO {}_{}"#,
+                collection, index, self.collection_name, index
+            ))
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn reindex(&self) -> crate::Result<usize> {
+        let rows = query(&format!(
+            r#"SELECT payload, {} FROM {} WHERE coalesce((payload->>'{}')::bigint, 0) < $1"#,
+            compression::COMPRESSED_FIELD,
+            self.collection_name,
+            migrations::SCHEMA_VERSION_FIELD,
+        ))
+        .bind(migrations::CURRENT_SCHEMA_VERSION as i64)
+        .fetch_all(&self.client)
+        .await?;
+
+        let upgraded = rows.len();
+        self.decode_rows(rows).await?;
+        Ok(upgraded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_ids_keep_the_last_row() {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        let rows = last_per_id(vec![(first, "old"), (second, "only"), (first, "new")]);
+
+        assert_eq!(rows, vec![(second, "only"), (first, "new")]);
+    }
 }
@@ -1,27 +1,45 @@
-use crate::{VectorStoreBase, VectorStoreError};
+use crate::{compression, migrations, VectorStoreBase, VectorStoreError, CHANGE_TIMESTAMPS};
 use async_trait::async_trait;
 use chrono::{TimeZone, Utc};
 use qdrant_client::{
     qdrant::{
-        Condition, CreateCollectionBuilder, CreateFieldIndexCollectionBuilder, DatetimeRange,
-        DeletePointsBuilder, Distance, FieldType, Filter, GetPointsBuilder, PointStruct,
-        PointVectors, PointsIdsList, Query, QueryPointsBuilder, Range, RetrievedPoint,
-        ScalarQuantizationBuilder, ScoredPoint, ScrollPointsBuilder, SetPayloadPointsBuilder,
-        UpdatePointVectorsBuilder, UpsertPointsBuilder, UuidIndexParamsBuilder,
-        VectorParamsBuilder,
+        facet_value, point_id::PointIdOptions, Condition, CountPointsBuilder, CreateAliasBuilder,
+        CreateCollectionBuilder, CreateFieldIndexCollectionBuilder, DatetimeRange,
+        DeletePointsBuilder, Distance, FacetCountsBuilder, FieldType, Filter, GetPointsBuilder,
+        PointId, PointStruct, PointVectors, PointsIdsList, Query, QueryBatchPointsBuilder,
+        QueryPoints, QueryPointsBuilder, Range, RetrievedPoint, ScalarQuantizationBuilder,
+        ScoredPoint, ScrollPointsBuilder, SetPayloadPointsBuilder, UpdatePointVectorsBuilder,
+        UpsertPointsBuilder, UuidIndexParamsBuilder, Value, VectorParamsBuilder,
     },
     Payload,
 };
-use serde_json::json;
-use std::{iter::zip, time};
+use std::{collections::HashMap, iter::zip, time};
 use thiserror::Error;
-use umem_core::{LifecycleState, Memory};
+use umem_core::{
+    DocumentChunk, FacetCount, LifecycleState, Memory, MemoryContext, MemoryFacets, MemoryId,
+    MemoryVisibility, TagCount,
+};
+use uuid::Uuid;
+
+const FACET_LIMIT: u64 = 1000;
+
+/// Keyword-indexed payload fields, required for facet counts.
+const FACET_FIELDS: &[&str] = &[
+    "kind",
+    "content.tags",
+    "topic",
+    migrations::CREATED_MONTH_FIELD,
+];
+
+/// Chunk metadata the embed engine filters on when re-ingesting a source.
+const DOCUMENT_INDEX_FIELDS: &[&str] = &["metadata.doc_id", "metadata.app_id", "metadata.url"];
 
 pub struct Qdrant {
     client: qdrant_client::Qdrant,
     collection_name: String,
     embedding_model_dims: u16,
     chunk_size: u16,
+    compress_payloads: bool,
 }
 
 #[derive(Error, Debug)]
@@ -45,7 +63,7 @@ impl From<qdrant_client::QdrantError> for VectorStoreError {
 type Result<T> = std::result::Result<T, QdrantError>;
 
 impl Qdrant {
-    pub async fn new(qdrant: umem_config::Qdrant) -> Result<Self> {
+    pub async fn new(qdrant: umem_config::Qdrant, embedding_model_dims: u16) -> Result<Self> {
         let client = qdrant_client::Qdrant::from_url(&qdrant.url)
             .api_key(qdrant.key)
             .build()?;
@@ -53,8 +71,9 @@ impl Qdrant {
         Ok(Self {
             client,
             collection_name: qdrant.collection_name,
-            embedding_model_dims: qdrant.embedding_model_dimensions,
+            embedding_model_dims,
             chunk_size: qdrant.chunk_size,
+            compress_payloads: qdrant.compress_payloads.unwrap_or_default(),
         })
     }
 
@@ -69,26 +88,164 @@ impl Qdrant {
                 .field_index_params(UuidIndexParamsBuilder::default().is_tenant(true)),
             )
             .await?;
+        self.create_facet_indexes().await?;
+        self.create_change_indexes().await
+    }
+
+    async fn create_facet_indexes(&self) -> Result<()> {
+        for field in FACET_FIELDS {
+            self.client
+                .create_field_index(CreateFieldIndexCollectionBuilder::new(
+                    &self.collection_name,
+                    *field,
+                    FieldType::Keyword,
+                ))
+                .await?;
+        }
         Ok(())
     }
 
+    /// Range indexes for `scroll_changed`, so change feeds do not scan the collection.
+    async fn create_change_indexes(&self) -> Result<()> {
+        for field in CHANGE_TIMESTAMPS {
+            self.client
+                .create_field_index(CreateFieldIndexCollectionBuilder::new(
+                    &self.collection_name,
+                    format!("temporal.{field}"),
+                    FieldType::Integer,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn facet_counts(&self, key: &str, filter: Filter) -> Result<Vec<FacetCount>> {
+        let hits = self
+            .client
+            .facet(
+                FacetCountsBuilder::new(&self.collection_name, key)
+                    .filter(filter)
+                    .limit(FACET_LIMIT)
+                    .exact(true),
+            )
+            .await?
+            .hits;
+
+        Ok(hits
+            .into_iter()
+            .filter_map(|hit| match hit.value?.variant? {
+                facet_value::Variant::StringValue(value) => Some(FacetCount {
+                    value,
+                    count: hit.count,
+                }),
+                _ => None,
+            })
+            .collect())
+    }
+
     fn filter_include_archived(conds: &mut Vec<Condition>, query: &umem_core::Query) {
-        if !query.include_archived() {
+        if let Some(as_of) = query.as_of() {
+            Self::filter_as_of(conds, as_of.timestamp(), query.include_archived());
+        } else if !query.include_archived() {
             conds.push(Condition::matches(
                 "lifecycle",
                 LifecycleState::Active.as_str().to_owned(),
             ));
+        } else {
+            Self::filter_retrievable(conds);
+        }
+    }
+
+    /// Matches on the lifecycle timestamps instead of the lifecycle, which only tells
+    /// the present state.
+    fn filter_as_of(conds: &mut Vec<Condition>, as_of: i64, include_archived: bool) {
+        conds.push(Condition::range(
+            "temporal.created_at",
+            Range {
+                lte: Some(as_of as f64),
+                ..Default::default()
+            },
+        ));
+
+        let mut ended = vec!["deleted_at", "suppressed_at"];
+        if !include_archived {
+            ended.extend(["archived_at", "superseded_at"]);
+        }
+        for field in ended {
+            let field = format!("temporal.{field}");
+            conds.push(
+                Filter::should([
+                    Condition::is_empty(field.clone()),
+                    Condition::range(
+                        field,
+                        Range {
+                            gt: Some(as_of as f64),
+                            ..Default::default()
+                        },
+                    ),
+                ])
+                .into(),
+            );
         }
     }
 
-    fn filter_context(conds: &mut Vec<Condition>, query: &umem_core::Query) {
-        if let Some(user_id) = query.context().user_id() {
+    /// Deleted and suppressed memories never show up in queries, archived or not.
+    fn filter_retrievable(conds: &mut Vec<Condition>) {
+        conds.push(Condition::matches(
+            "lifecycle",
+            LifecycleState::RETRIEVABLE
+                .iter()
+                .map(|state| state.as_str().to_owned())
+                .collect::<Vec<String>>(),
+        ));
+    }
+
+    /// A personal context that names an organization also matches that organization's
+    /// pool; see `filter_personal_context` for `shared`.
+    fn filter_context(conds: &mut Vec<Condition>, context: &MemoryContext, shared: bool) {
+        match context.org_id() {
+            Some(org_id) if context.is_org_pool() => {
+                conds.push(Condition::matches("context.org_id", org_id.to_string()));
+            }
+            Some(org_id) => {
+                let mut personal = vec![];
+                Self::filter_personal_context(&mut personal, context, shared);
+                conds.push(
+                    Filter::should([
+                        Filter::must(personal).into(),
+                        Condition::matches("context.org_id", org_id.to_string()),
+                    ])
+                    .into(),
+                );
+            }
+            None => Self::filter_personal_context(conds, context, shared),
+        }
+    }
+
+    /// With `shared`, memories other agents of the same user shared are matched as
+    /// well as the agent's own.
+    fn filter_personal_context(conds: &mut Vec<Condition>, context: &MemoryContext, shared: bool) {
+        if let Some(user_id) = context.user_id() {
             conds.push(Condition::matches("context.user_id", user_id.to_string()));
         }
-        if let Some(agent_id) = query.context().agent_id() {
-            conds.push(Condition::matches("context.agent_id", agent_id.to_string()));
+        if let Some(agent_id) = context.agent_id() {
+            let own = Condition::matches("context.agent_id", agent_id.to_string());
+            if shared && context.user_id().is_some() {
+                conds.push(
+                    Filter::should([
+                        own,
+                        Condition::matches(
+                            "visibility",
+                            MemoryVisibility::Shared.as_str().to_string(),
+                        ),
+                    ])
+                    .into(),
+                );
+            } else {
+                conds.push(own);
+            }
         }
-        if let Some(run_id) = query.context().run_id() {
+        if let Some(run_id) = context.run_id() {
             conds.push(Condition::matches("context.run_id", run_id.to_string()));
         }
     }
@@ -105,6 +262,12 @@ impl Qdrant {
         }
     }
 
+    fn filter_topics(conds: &mut Vec<Condition>, query: &umem_core::Query) {
+        if let Some(topics) = query.topics() {
+            conds.push(Condition::matches("topic", topics.to_vec()));
+        }
+    }
+
     fn filter_tags(conds: &mut Vec<Condition>, query: &umem_core::Query) {
         if let Some(tags) = query.tags() {
             conds.push(Condition::matches(
@@ -187,13 +350,90 @@ impl Qdrant {
         }
     }
 
+    fn documents_collection(&self) -> String {
+        format!("{}_documents", self.collection_name)
+    }
+
+    /// Chunk ids are arbitrary strings but qdrant points need a uuid, so points are keyed
+    /// by a uuid derived from the chunk id, which stays in the payload.
+    fn document_point_id(chunk_id: &str) -> PointId {
+        Uuid::new_v5(&Uuid::NAMESPACE_OID, chunk_id.as_bytes())
+            .to_string()
+            .into()
+    }
+
+    fn document_filter(ids: Option<&[String]>, filter: &[(String, String)]) -> Filter {
+        let mut conds: Vec<Condition> = filter
+            .iter()
+            .map(|(key, value)| Condition::matches(format!("metadata.{}", key), value.clone()))
+            .collect();
+        if let Some(ids) = ids {
+            conds.push(Condition::has_id(
+                ids.iter().map(|id| Self::document_point_id(id)),
+            ));
+        }
+        Filter::must(conds)
+    }
+
+    fn decode_document(payload: HashMap<String, Value>) -> crate::Result<DocumentChunk> {
+        Ok(serde_json::from_value(serde_json::to_value(&payload)?)?)
+    }
+
+    fn point_uuid(id: Option<PointId>) -> Option<Uuid> {
+        match id?.point_id_options? {
+            PointIdOptions::Uuid(uuid) => Uuid::parse_str(&uuid).ok(),
+            PointIdOptions::Num(_) => None,
+        }
+    }
+
+    fn payload_of(&self, memory: &Memory) -> crate::Result<Payload> {
+        let mut payload = migrations::to_payload(memory)?;
+        if self.compress_payloads {
+            compression::compress_inline(&mut payload)?;
+        }
+        Ok(Payload::try_from(payload)?)
+    }
+
+    /// Decodes a stored payload, writing it back when it had to be migrated.
+    async fn decode(&self, payload: HashMap<String, Value>) -> crate::Result<(Memory, bool)> {
+        let mut payload = serde_json::to_value(&payload)?;
+        compression::decompress_inline(&mut payload)?;
+        let (memory, outdated) = migrations::from_payload(payload)?;
+        if outdated {
+            self.update(&memory.memory_id(), None, Some(&memory))
+                .await?;
+        }
+        Ok((memory, outdated))
+    }
+
+    fn nearest(&self, query: &umem_core::Query) -> crate::Result<QueryPointsBuilder> {
+        let Some(vector) = query.vector() else {
+            return Err(QdrantError::VectorNotSupplied)?;
+        };
+
+        Ok(QueryPointsBuilder::new(&self.collection_name)
+            .query(Query::new_nearest(vector.to_vec()))
+            .limit(query.limit().into())
+            .with_payload(true)
+            .filter(Qdrant::create_filter(query)))
+    }
+
+    async fn decode_scored(&self, points: Vec<ScoredPoint>) -> crate::Result<Vec<(Memory, f32)>> {
+        let mut memories = Vec::with_capacity(points.len());
+        for ScoredPoint { payload, score, .. } in points {
+            memories.push((self.decode(payload).await?.0, score));
+        }
+        Ok(memories)
+    }
+
     fn create_filter(query: &umem_core::Query) -> Filter {
         let mut conds = vec![];
 
         Self::filter_include_archived(&mut conds, query);
-        Self::filter_context(&mut conds, query);
+        Self::filter_context(&mut conds, query.context(), query.include_shared());
         Self::filter_kinds(&mut conds, query);
         Self::filter_tags(&mut conds, query);
+        Self::filter_topics(&mut conds, query);
         Self::filter_temporal(&mut conds, query);
         Self::filter_signals(&mut conds, query);
 
@@ -205,6 +445,8 @@ impl Qdrant {
 impl VectorStoreBase for Qdrant {
     async fn create_collection(&self) -> crate::Result<()> {
         if self.client.collection_exists(&self.collection_name).await? {
+            self.create_facet_indexes().await?;
+            self.create_change_indexes().await?;
             return Ok(());
         }
 
@@ -233,11 +475,18 @@ impl VectorStoreBase for Qdrant {
         Ok(())
     }
 
+    async fn ping(&self) -> crate::Result<()> {
+        self.client
+            .collection_info(self.collection_name.as_str())
+            .await?;
+        Ok(())
+    }
+
     async fn insert(&self, vectors: &[&[f32]], payloads: &[&Memory]) -> crate::Result<()> {
         let mut points: Vec<PointStruct> = Vec::with_capacity(vectors.len());
         for (vector, payload) in zip(vectors, payloads) {
             let point_id = payload.get_id();
-            let payload = Payload::try_from(json!(payload))?;
+            let payload = self.payload_of(payload)?;
             points.push(PointStruct::new(point_id.to_string(), *vector, payload));
         }
 
@@ -250,66 +499,115 @@ impl VectorStoreBase for Qdrant {
         Ok(())
     }
 
-    async fn get(&self, vector_id: &str) -> crate::Result<Memory> {
+    async fn get(&self, id: &MemoryId) -> crate::Result<Memory> {
         let result = self
             .client
             .get_points(
-                GetPointsBuilder::new(&self.collection_name, vec![vector_id.into()])
+                GetPointsBuilder::new(&self.collection_name, vec![id.to_string().into()])
                     .with_payload(true),
             )
             .await?
             .result;
 
-        if result.is_empty() {
-            return Err(QdrantError::PointNotFound(vector_id.to_string()))?;
-        }
-
-        let string = serde_json::to_string(&result[0].payload)?;
-        let memory: Memory = serde_json::from_str(string.as_str())?;
+        let Some(point) = result.into_iter().next() else {
+            return Err(QdrantError::PointNotFound(id.to_string()))?;
+        };
 
+        let (memory, _) = self.decode(point.payload).await?;
         Ok(memory)
     }
 
     async fn update(
         &self,
-        vector_id: &str,
+        id: &MemoryId,
         vector: Option<&[f32]>,
         payload: Option<&Memory>,
     ) -> crate::Result<()> {
+        // NOTE: one upsert so the vector and payload can't be left out of step
+        if let (Some(vector), Some(payload)) = (vector, payload) {
+            let payload = self.payload_of(payload)?;
+            self.client
+                .upsert_points(
+                    UpsertPointsBuilder::new(
+                        &self.collection_name,
+                        vec![PointStruct::new(id.to_string(), vector, payload)],
+                    )
+                    .wait(true),
+                )
+                .await?;
+            return Ok(());
+        }
+
         if let Some(vector) = vector {
             self.client
                 .update_vectors(UpdatePointVectorsBuilder::new(
                     &self.collection_name,
                     vec![PointVectors {
-                        id: Some(vector_id.into()),
+                        id: Some(id.to_string().into()),
                         vectors: Some(vector.into()),
                     }],
                 ))
                 .await?;
         }
 
+        // NOTE: overwritten rather than merged, so fields that moved into or out of the
+        // compressed part of the payload don't linger
         if let Some(payload) = payload {
             self.client
-                .set_payload(
-                    SetPayloadPointsBuilder::new(
-                        &self.collection_name,
-                        Payload::try_from(json!(payload))?,
-                    )
-                    .points_selector(PointsIdsList {
-                        ids: vec![vector_id.into()],
-                    }),
+                .overwrite_payload(
+                    SetPayloadPointsBuilder::new(&self.collection_name, self.payload_of(payload)?)
+                        .points_selector(PointsIdsList {
+                            ids: vec![id.to_string().into()],
+                        }),
                 )
                 .await?;
         }
         Ok(())
     }
 
-    async fn delete(&self, vector_id: &str) -> crate::Result<()> {
+    async fn update_if_version(
+        &self,
+        id: &MemoryId,
+        vector: Option<&[f32]>,
+        payload: &Memory,
+        version: u64,
+    ) -> crate::Result<bool> {
+        // NOTE: payloads stored before versions were recorded have none and are at 0
+        let mut at_version = vec![Condition::matches("version", version as i64)];
+        if version == 0 {
+            at_version.push(Condition::is_empty("version"));
+        }
+        let filter = Filter::must([
+            Condition::has_id([id.to_string()]),
+            Filter::should(at_version).into(),
+        ]);
+
+        // NOTE: Qdrant reports no count of the points a filtered write matched, so the
+        // stored payload is read back to tell whether this write took
+        self.client
+            .overwrite_payload(
+                SetPayloadPointsBuilder::new(&self.collection_name, self.payload_of(payload)?)
+                    .points_selector(filter)
+                    .wait(true),
+            )
+            .await?;
+        let stored = self.get(id).await?;
+        if migrations::to_payload(&stored)? != migrations::to_payload(payload)? {
+            return Ok(false);
+        }
+
+        if let Some(vector) = vector {
+            self.update(id, Some(vector), None).await?;
+        }
+        Ok(true)
+    }
+
+    async fn delete(&self, id: &MemoryId) -> crate::Result<()> {
         self.client
             .delete_points(
                 DeletePointsBuilder::new(&self.collection_name)
                     .points(PointsIdsList {
-                        ids: vec![vector_id.into()],
+                        ids: vec![id.to_string().into()],
                     })
                     .wait(true),
             )
@@ -325,41 +623,394 @@ impl VectorStoreBase for Qdrant {
 
         scroll = scroll.filter(Qdrant::create_filter(&query));
 
-        self.client
-            .scroll(scroll)
+        let points = self.client.scroll(scroll).await?.result;
+        let mut memories = Vec::with_capacity(points.len());
+        for RetrievedPoint { payload, .. } in points {
+            memories.push(self.decode(payload).await?.0);
+        }
+        Ok(memories)
+    }
+
+    fn describe_filter(&self, query: &umem_core::Query) -> String {
+        format!("{:?}", Self::create_filter(query))
+    }
+
+    async fn search_scored(&self, query: umem_core::Query) -> crate::Result<Vec<(Memory, f32)>> {
+        let points = self.client.query(self.nearest(&query)?).await?.result;
+        self.decode_scored(points).await
+    }
+
+    async fn search_scored_batch(
+        &self,
+        queries: &[umem_core::Query],
+    ) -> crate::Result<Vec<Vec<(Memory, f32)>>> {
+        let query_points = queries
+            .iter()
+            .map(|query| Ok(self.nearest(query)?.build()))
+            .collect::<crate::Result<Vec<QueryPoints>>>()?;
+
+        let batches = self
+            .client
+            .query_batch(QueryBatchPointsBuilder::new(
+                &self.collection_name,
+                query_points,
+            ))
+            .await?
+            .result;
+
+        let mut results = Vec::with_capacity(batches.len());
+        for batch in batches {
+            results.push(self.decode_scored(batch.result).await?);
+        }
+        Ok(results)
+    }
+
+    async fn facets(&self, query: umem_core::Query) -> crate::Result<MemoryFacets> {
+        let filter = Qdrant::create_filter(&query);
+
+        let mut months = self
+            .facet_counts(migrations::CREATED_MONTH_FIELD, filter.clone())
+            .await?;
+        months.sort_by(|a, b| b.value.cmp(&a.value));
+
+        Ok(MemoryFacets {
+            kinds: self.facet_counts("kind", filter.clone()).await?,
+            tags: self.facet_counts("content.tags", filter.clone()).await?,
+            months,
+            topics: self.facet_counts("topic", filter).await?,
+        })
+    }
+
+    async fn tag_counts(&self, context: &MemoryContext) -> crate::Result<Vec<TagCount>> {
+        let mut conds = vec![];
+        Self::filter_retrievable(&mut conds);
+        Self::filter_context(&mut conds, context, false);
+
+        Ok(self
+            .facet_counts("content.tags", Filter::must(conds))
             .await?
-            .result
             .into_iter()
-            .map(|RetrievedPoint { payload, .. }| {
-                let string = serde_json::to_string(&payload)?;
-                let memory: Memory = serde_json::from_str(&string)?;
-                Ok(memory)
-            })
-            .collect()
+            .map(|FacetCount { value, count }| TagCount { tag: value, count })
+            .collect())
     }
 
-    async fn search(&self, query: umem_core::Query) -> crate::Result<Vec<Memory>> {
-        if query.vector().is_none() {
-            return Err(QdrantError::VectorNotSupplied)?;
+    async fn replace_tags(
+        &self,
+        context: &MemoryContext,
+        from: &[String],
+        to: &str,
+    ) -> crate::Result<usize> {
+        let mut conds = vec![Condition::matches("content.tags[]", from.to_vec())];
+        Self::filter_retrievable(&mut conds);
+        Self::filter_context(&mut conds, context, false);
+        let filter = Filter::must(conds);
+
+        let mut replaced = 0;
+        let mut offset = None;
+        loop {
+            let mut scroll = ScrollPointsBuilder::new(&self.collection_name)
+                .filter(filter.clone())
+                .limit(self.chunk_size.into())
+                .with_payload(true);
+            if let Some(offset) = offset {
+                scroll = scroll.offset(offset);
+            }
+
+            let response = self.client.scroll(scroll).await?;
+            for RetrievedPoint { payload, .. } in response.result {
+                let (mut memory, _) = self.decode(payload).await?;
+                if memory.replace_tags(from, to, Utc::now()) {
+                    self.update(&memory.memory_id(), None, Some(&memory))
+                        .await?;
+                    replaced += 1;
+                }
+            }
+
+            match response.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
         }
 
-        let mut query_builder = QueryPointsBuilder::new(&self.collection_name)
-            .query(Query::new_nearest(query.vector().unwrap().to_vec()))
-            .limit(query.limit().into())
+        Ok(replaced)
+    }
+
+    async fn scroll(
+        &self,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> crate::Result<(Vec<Memory>, Option<Uuid>)> {
+        let mut scroll = ScrollPointsBuilder::new(&self.collection_name)
+            .limit(limit)
             .with_payload(true);
+        if let Some(cursor) = cursor {
+            scroll = scroll.offset(cursor.to_string().as_str());
+        }
+
+        let response = self.client.scroll(scroll).await?;
+        let mut memories = Vec::with_capacity(response.result.len());
+        for RetrievedPoint { payload, .. } in response.result {
+            memories.push(self.decode(payload).await?.0);
+        }
+
+        Ok((memories, Self::point_uuid(response.next_page_offset)))
+    }
+
+    async fn scroll_changed(
+        &self,
+        context: Option<&MemoryContext>,
+        since: i64,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> crate::Result<(Vec<Memory>, Option<Uuid>)> {
+        let changed = CHANGE_TIMESTAMPS.map(|field| {
+            Condition::range(
+                format!("temporal.{field}"),
+                Range {
+                    lt: None,
+                    gt: Some(since as f64),
+                    gte: None,
+                    lte: None,
+                },
+            )
+        });
+        let mut conds = vec![Filter::should(changed).into()];
+        if let Some(context) = context {
+            Self::filter_context(&mut conds, context, true);
+        }
+        let mut scroll = ScrollPointsBuilder::new(&self.collection_name)
+            .filter(Filter::must(conds))
+            .limit(limit)
+            .with_payload(true);
+        if let Some(cursor) = cursor {
+            scroll = scroll.offset(cursor.to_string().as_str());
+        }
+
+        let response = self.client.scroll(scroll).await?;
+        let mut memories = Vec::with_capacity(response.result.len());
+        for RetrievedPoint { payload, .. } in response.result {
+            memories.push(self.decode(payload).await?.0);
+        }
+
+        Ok((memories, Self::point_uuid(response.next_page_offset)))
+    }
+
+    async fn existing_ids(&self, ids: &[Uuid]) -> crate::Result<Vec<Uuid>> {
+        let ids: Vec<String> = ids.iter().map(Uuid::to_string).collect();
+        let points = self
+            .client
+            .get_points(
+                GetPointsBuilder::new(
+                    &self.collection_name,
+                    ids.iter()
+                        .map(|id| id.as_str().into())
+                        .collect::<Vec<PointId>>(),
+                )
+                .with_payload(false),
+            )
+            .await?
+            .result;
+
+        Ok(points
+            .into_iter()
+            .filter_map(|point| Self::point_uuid(point.id))
+            .collect())
+    }
+
+    async fn promote(&self, collection: &str) -> crate::Result<()> {
+        let previous = self
+            .client
+            .list_aliases()
+            .await?
+            .aliases
+            .into_iter()
+            .find(|alias| alias.alias_name == self.collection_name)
+            .map(|alias| alias.collection_name);
+
+        if previous.is_none() && self.client.collection_exists(&self.collection_name).await? {
+            // NOTE: the first swap has to drop the real collection before the alias can take
+            // its name; every later swap re-points the alias atomically
+            self.client.delete_collection(&self.collection_name).await?;
+        }
+
+        self.client
+            .create_alias(CreateAliasBuilder::new(collection, &self.collection_name))
+            .await?;
+
+        if let Some(previous) = previous.filter(|previous| previous != collection) {
+            self.client.delete_collection(previous).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn create_document_collection(&self) -> crate::Result<()> {
+        let collection = self.documents_collection();
+        if self.client.collection_exists(&collection).await? {
+            return Ok(());
+        }
+
+        self.client
+            .create_collection(
+                CreateCollectionBuilder::new(&collection)
+                    .vectors_config(VectorParamsBuilder::new(
+                        self.embedding_model_dims.into(),
+                        Distance::Cosine,
+                    ))
+                    .quantization_config(ScalarQuantizationBuilder::default()),
+            )
+            .await?;
 
-        query_builder = query_builder.filter(Qdrant::create_filter(&query));
+        for field in DOCUMENT_INDEX_FIELDS {
+            self.client
+                .create_field_index(CreateFieldIndexCollectionBuilder::new(
+                    &collection,
+                    *field,
+                    FieldType::Keyword,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn insert_documents(
+        &self,
+        vectors: &[&[f32]],
+        chunks: &[&DocumentChunk],
+    ) -> crate::Result<()> {
+        let mut points: Vec<PointStruct> = Vec::with_capacity(vectors.len());
+        for (vector, chunk) in zip(vectors, chunks) {
+            let payload = Payload::try_from(serde_json::to_value(chunk)?)?;
+            points.push(PointStruct::new(
+                Self::document_point_id(&chunk.id),
+                *vector,
+                payload,
+            ));
+        }
+
+        self.client
+            .upsert_points_chunked(
+                UpsertPointsBuilder::new(self.documents_collection(), points).wait(true),
+                self.chunk_size.into(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get_documents(
+        &self,
+        ids: Option<&[String]>,
+        filter: &[(String, String)],
+        limit: Option<u32>,
+    ) -> crate::Result<Vec<DocumentChunk>> {
+        let filter = Self::document_filter(ids, filter);
+        let mut chunks = vec![];
+        let mut offset = None;
+
+        loop {
+            let remaining = limit.map(|limit| limit - chunks.len() as u32);
+            let page_size = remaining.map_or(u32::from(self.chunk_size), |remaining| {
+                remaining.min(self.chunk_size.into())
+            });
+            if page_size == 0 {
+                break;
+            }
+
+            let mut scroll = ScrollPointsBuilder::new(self.documents_collection())
+                .filter(filter.clone())
+                .limit(page_size)
+                .with_payload(true);
+            if let Some(offset) = offset {
+                scroll = scroll.offset(offset);
+            }
+
+            let response = self.client.scroll(scroll).await?;
+            for RetrievedPoint { payload, .. } in response.result {
+                chunks.push(Self::decode_document(payload)?);
+            }
+
+            match response.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(chunks)
+    }
+
+    async fn search_documents(
+        &self,
+        vector: &[f32],
+        filter: &[(String, String)],
+        limit: u32,
+    ) -> crate::Result<Vec<DocumentChunk>> {
+        let query_builder = QueryPointsBuilder::new(self.documents_collection())
+            .query(Query::new_nearest(vector.to_vec()))
+            .filter(Self::document_filter(None, filter))
+            .limit(limit.into())
+            .with_payload(true);
 
         self.client
             .query(query_builder)
             .await?
             .result
             .into_iter()
-            .map(|ScoredPoint { payload, .. }| {
-                let string = serde_json::to_string(&payload)?;
-                let memory: Memory = serde_json::from_str(&string)?;
-                Ok(memory)
-            })
+            .map(|ScoredPoint { payload, .. }| Self::decode_document(payload))
             .collect()
     }
+
+    async fn delete_documents(
+        &self,
+        ids: Option<&[String]>,
+        filter: &[(String, String)],
+    ) -> crate::Result<()> {
+        self.client
+            .delete_points(
+                DeletePointsBuilder::new(self.documents_collection())
+                    .points(Self::document_filter(ids, filter))
+                    .wait(true),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn count_documents(&self) -> crate::Result<usize> {
+        let count = self
+            .client
+            .count(CountPointsBuilder::new(self.documents_collection()).exact(true))
+            .await?
+            .result
+            .map_or(0, |result| result.count);
+
+        Ok(count as usize)
+    }
+
+    async fn reindex(&self) -> crate::Result<usize> {
+        let mut upgraded = 0;
+        let mut offset = None;
+
+        loop {
+            let mut scroll = ScrollPointsBuilder::new(&self.collection_name)
+                .limit(self.chunk_size.into())
+                .with_payload(true);
+            if let Some(offset) = offset {
+                scroll = scroll.offset(offset);
+            }
+
+            let response = self.client.scroll(scroll).await?;
+            for RetrievedPoint { payload, .. } in response.result {
+                if self.decode(payload).await?.1 {
+                    upgraded += 1;
+                }
+            }
+
+            match response.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(upgraded)
+    }
 }
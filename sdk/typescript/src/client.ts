@@ -0,0 +1,171 @@
+import { Code, ConnectError, createClient, type Client, type Interceptor, type Transport } from "@connectrpc/connect";
+import type { ContextPolicy, MemoryKind, TemplateListResponse } from "./gen/memory_pb.js";
+import {
+  MemoryService,
+  type ChangesResponse,
+  type ContextPackResponse,
+  type CreateMemoryResponse,
+  type Memory,
+  type MemoryListResponse,
+  type MemoryStatsResponse,
+  type SavedView,
+} from "./gen/memory_v2_pb.js";
+
+/** Whose memories a call reads or writes. At least one id must be set. */
+export interface ContextIds {
+  userId?: string;
+  agentId?: string;
+  runId?: string;
+  /** Only read: also match this organization's pool, or only the pool when no other id is set. */
+  orgId?: string;
+}
+
+export interface MemoryClientOptions {
+  /** Deadline for every call, sent as `grpc-timeout`. The server's `request_timeout_ms` still applies. */
+  timeoutMs?: number;
+}
+
+/** Sends `key` as `authorization: Bearer <key>`, for servers configured with `[api_keys]`. */
+export function apiKeyInterceptor(key: string): Interceptor {
+  return (next) => async (request) => {
+    request.header.set("authorization", `Bearer ${key}`);
+    return next(request);
+  };
+}
+
+/**
+ * Calls `memory_v2.MemoryService` with plain arguments instead of request messages. Every
+ * rpc, with every request field, stays available on `rpc`.
+ */
+export class MemoryClient {
+  readonly rpc: Client<typeof MemoryService>;
+  private readonly timeoutMs?: number;
+
+  constructor(transport: Transport, options: MemoryClientOptions = {}) {
+    this.rpc = createClient(MemoryService, transport);
+    this.timeoutMs = options.timeoutMs;
+  }
+
+  /** Annotates `rawContent` into memories and stores them. */
+  async add(rawContent: string, context: ContextIds): Promise<CreateMemoryResponse> {
+    const { userId, agentId, runId } = context;
+    return this.rpc.createMemory({ userId, agentId, runId, rawContent }, this.callOptions());
+  }
+
+  /** Stores one memory as given, without annotation; see `templates` for the fields each kind needs. */
+  async addStructured(
+    summary: string,
+    kind: MemoryKind,
+    context: ContextIds,
+    extra: { tags?: string[]; fields?: Record<string, string> } = {},
+  ): Promise<CreateMemoryResponse> {
+    const { userId, agentId, runId } = context;
+    return this.rpc.createStructuredMemory(
+      { userId, agentId, runId, summary, kind, tags: extra.tags, fields: extra.fields },
+      this.callOptions(),
+    );
+  }
+
+  /** The memory with `id`, or `undefined` when there is none. */
+  async get(id: string): Promise<Memory | undefined> {
+    try {
+      return (await this.rpc.getMemory({ id }, this.callOptions())).memory;
+    } catch (e) {
+      if (ConnectError.from(e).code === Code.NotFound) {
+        return undefined;
+      }
+      throw e;
+    }
+  }
+
+  async list(context: ContextIds, options: { includeFacets?: boolean } = {}): Promise<MemoryListResponse> {
+    return this.rpc.listMemories({ context, includeFacets: options.includeFacets }, this.callOptions());
+  }
+
+  async search(
+    query: string,
+    context: ContextIds,
+    options: { includeFacets?: boolean; explain?: boolean } = {},
+  ): Promise<MemoryListResponse> {
+    return this.rpc.searchMemories(
+      { query, context, includeFacets: options.includeFacets, explain: options.explain },
+      this.callOptions(),
+    );
+  }
+
+  /** Prompt-ready text of the memories most relevant to `query`, within `tokenBudget` tokens. */
+  async buildContext(
+    query: string,
+    context: ContextIds,
+    options: { tokenBudget?: number; policy?: ContextPolicy } = {},
+  ): Promise<ContextPackResponse> {
+    return this.rpc.buildContext(
+      { query, context, tokenBudget: options.tokenBudget, policy: options.policy },
+      this.callOptions(),
+    );
+  }
+
+  /**
+   * Replaces the memory's summary. With `expectedVersion`, fails with `Code.Aborted` when the
+   * memory changed since it was read at that version.
+   */
+  async update(id: string, summary: string, options: { expectedVersion?: bigint } = {}): Promise<Memory | undefined> {
+    const request = { id, summary, expectedVersion: options.expectedVersion };
+    return (await this.rpc.updateMemory(request, this.callOptions())).memory;
+  }
+
+  async delete(id: string): Promise<void> {
+    await this.rpc.deleteMemory({ id }, this.callOptions());
+  }
+
+  /** Reports that retrieved memories were used, raising their salience. */
+  async markUsed(ids: string[]): Promise<void> {
+    await this.rpc.markUsed({ ids }, this.callOptions());
+  }
+
+  /** Records whether the memory retrieved for `query` helped. */
+  async feedback(memoryId: string, query: string, helpful: boolean): Promise<void> {
+    await this.rpc.feedback({ memoryId, query, helpful }, this.callOptions());
+  }
+
+  async stats(context: ContextIds): Promise<MemoryStatsResponse> {
+    return this.rpc.getMemoryStats({ context }, this.callOptions());
+  }
+
+  /**
+   * Saves `query`, written in the query language (e.g. `kind:procedural tag:deploy rollback`),
+   * as the view `name`, replacing an earlier view of that name.
+   */
+  async saveView(name: string, query: string, context: ContextIds): Promise<SavedView> {
+    return this.rpc.saveView({ context, name, query }, this.callOptions());
+  }
+
+  /** The context's saved views, by name. */
+  async listViews(context: ContextIds): Promise<SavedView[]> {
+    return (await this.rpc.listViews({ context }, this.callOptions())).views;
+  }
+
+  async runView(name: string, context: ContextIds, options: { explain?: boolean } = {}): Promise<MemoryListResponse> {
+    return this.rpc.runView({ context, name, explain: options.explain }, this.callOptions());
+  }
+
+  async deleteView(name: string, context: ContextIds): Promise<void> {
+    await this.rpc.deleteView({ context, name }, this.callOptions());
+  }
+
+  /**
+   * Memories created, updated, archived, deleted or suppressed after `since` (unix seconds), for
+   * keeping a local cache in sync. Pass the response's `until` as `since` on the next call.
+   */
+  async changes(context: ContextIds, since: bigint = 0n): Promise<ChangesResponse> {
+    return this.rpc.getChanges({ context, since }, this.callOptions());
+  }
+
+  async templates(): Promise<TemplateListResponse> {
+    return this.rpc.listTemplates({}, this.callOptions());
+  }
+
+  private callOptions() {
+    return { timeoutMs: this.timeoutMs };
+  }
+}
@@ -0,0 +1,201 @@
+// @generated by protoc-gen-es v2.2.3 with parameter "target=ts,import_extension=js"
+// @generated from file admin.proto (package admin_v1, syntax proto3)
+/* eslint-disable */
+
+import type { GenFile, GenMessage, GenService } from "@bufbuild/protobuf/codegenv1";
+import { fileDesc, messageDesc, serviceDesc } from "@bufbuild/protobuf/codegenv1";
+import { file_google_protobuf_empty } from "@bufbuild/protobuf/wkt";
+import type { Message } from "@bufbuild/protobuf";
+import type { EmptySchema } from "@bufbuild/protobuf/wkt";
+
+/**
+ * Describes the file admin.proto.
+ */
+export const file_admin: GenFile = /*@__PURE__*/
+  fileDesc("CgthZG1pbi5wcm90bxIIYWRtaW5fdjEiKQoWUmVzZXRDb2xsZWN0aW9uUmVxdWVzdBIPCgdjb25maXJtGAEgASgIIiMKD1JlaW5kZXhSZXNwb25zZRIQCgh1cGdyYWRlZBgBIAEoBCJvChdSdW5Db25zb2xpZGF0aW9uUmVxdWVzdBIUCgd1c2VyX2lkGAEgASgJSACIAQESFQoIYWdlbnRfaWQYAiABKAlIAYgBARIOCgZydW5faWQYAyABKAlCCgoIX3VzZXJfaWRCCwoJX2FnZW50X2lkIkAKGFJ1bkNvbnNvbGlkYXRpb25SZXNwb25zZRIWCgltZW1vcnlfaWQYASABKAlIAIgBAUIMCgpfbWVtb3J5X2lkImkKD01ldHJpY3NSZXNwb25zZRIUCgxtZW1vcnlfY291bnQYASABKAQSJAoXcGVuZGluZ19qb3VybmFsX2VudHJpZXMYAiABKARIAIgBAUIaChhfcGVuZGluZ19qb3VybmFsX2VudHJpZXMiJgoTRmx1c2hDYWNoZXNSZXNwb25zZRIPCgdmbHVzaGVkGAEgASgEMvwCCgxBZG1pblNlcnZpY2USSwoPUmVzZXRDb2xsZWN0aW9uEiAuYWRtaW5fdjEuUmVzZXRDb2xsZWN0aW9uUmVxdWVzdBoWLmdvb2dsZS5wcm90b2J1Zi5FbXB0eRI8CgdSZWluZGV4EhYuZ29vZ2xlLnByb3RvYnVmLkVtcHR5GhkuYWRtaW5fdjEuUmVpbmRleFJlc3BvbnNlElkKEFJ1bkNvbnNvbGlkYXRpb24SIS5hZG1pbl92MS5SdW5Db25zb2xpZGF0aW9uUmVxdWVzdBoiLmFkbWluX3YxLlJ1bkNvbnNvbGlkYXRpb25SZXNwb25zZRJACgtEdW1wTWV0cmljcxIWLmdvb2dsZS5wcm90b2J1Zi5FbXB0eRoZLmFkbWluX3YxLk1ldHJpY3NSZXNwb25zZRJECgtGbHVzaENhY2hlcxIWLmdvb2dsZS5wcm90b2J1Zi5FbXB0eRodLmFkbWluX3YxLkZsdXNoQ2FjaGVzUmVzcG9uc2ViBnByb3RvMw", [file_google_protobuf_empty]);
+
+/**
+ * @generated from message admin_v1.ResetCollectionRequest
+ */
+export type ResetCollectionRequest = Message<"admin_v1.ResetCollectionRequest"> & {
+  /**
+   * Must be set; guards against resetting the collection by accident.
+   *
+   * @generated from field: bool confirm = 1;
+   */
+  confirm: boolean;
+};
+
+/**
+ * Describes the message admin_v1.ResetCollectionRequest.
+ * Use `create(ResetCollectionRequestSchema)` to create a new message.
+ */
+export const ResetCollectionRequestSchema: GenMessage<ResetCollectionRequest> = /*@__PURE__*/
+  messageDesc(file_admin, 0);
+
+/**
+ * @generated from message admin_v1.ReindexResponse
+ */
+export type ReindexResponse = Message<"admin_v1.ReindexResponse"> & {
+  /**
+   * Payloads upgraded to the current schema version.
+   *
+   * @generated from field: uint64 upgraded = 1;
+   */
+  upgraded: bigint;
+};
+
+/**
+ * Describes the message admin_v1.ReindexResponse.
+ * Use `create(ReindexResponseSchema)` to create a new message.
+ */
+export const ReindexResponseSchema: GenMessage<ReindexResponse> = /*@__PURE__*/
+  messageDesc(file_admin, 1);
+
+/**
+ * Consolidates the working memories of one run, like closing out its session.
+ *
+ * @generated from message admin_v1.RunConsolidationRequest
+ */
+export type RunConsolidationRequest = Message<"admin_v1.RunConsolidationRequest"> & {
+  /**
+   * @generated from field: optional string user_id = 1;
+   */
+  userId?: string;
+
+  /**
+   * @generated from field: optional string agent_id = 2;
+   */
+  agentId?: string;
+
+  /**
+   * @generated from field: string run_id = 3;
+   */
+  runId: string;
+};
+
+/**
+ * Describes the message admin_v1.RunConsolidationRequest.
+ * Use `create(RunConsolidationRequestSchema)` to create a new message.
+ */
+export const RunConsolidationRequestSchema: GenMessage<RunConsolidationRequest> = /*@__PURE__*/
+  messageDesc(file_admin, 2);
+
+/**
+ * @generated from message admin_v1.RunConsolidationResponse
+ */
+export type RunConsolidationResponse = Message<"admin_v1.RunConsolidationResponse"> & {
+  /**
+   * The episodic memory the run was consolidated into; unset when the run had no
+   * active working memories.
+   *
+   * @generated from field: optional string memory_id = 1;
+   */
+  memoryId?: string;
+};
+
+/**
+ * Describes the message admin_v1.RunConsolidationResponse.
+ * Use `create(RunConsolidationResponseSchema)` to create a new message.
+ */
+export const RunConsolidationResponseSchema: GenMessage<RunConsolidationResponse> = /*@__PURE__*/
+  messageDesc(file_admin, 3);
+
+/**
+ * @generated from message admin_v1.MetricsResponse
+ */
+export type MetricsResponse = Message<"admin_v1.MetricsResponse"> & {
+  /**
+   * Memories in the collection, including archived ones and ones in the trash.
+   *
+   * @generated from field: uint64 memory_count = 1;
+   */
+  memoryCount: bigint;
+
+  /**
+   * Create requests waiting in the journal; unset when no journal is configured.
+   *
+   * @generated from field: optional uint64 pending_journal_entries = 2;
+   */
+  pendingJournalEntries?: bigint;
+};
+
+/**
+ * Describes the message admin_v1.MetricsResponse.
+ * Use `create(MetricsResponseSchema)` to create a new message.
+ */
+export const MetricsResponseSchema: GenMessage<MetricsResponse> = /*@__PURE__*/
+  messageDesc(file_admin, 4);
+
+/**
+ * @generated from message admin_v1.FlushCachesResponse
+ */
+export type FlushCachesResponse = Message<"admin_v1.FlushCachesResponse"> & {
+  /**
+   * Journaled create requests written to the stores.
+   *
+   * @generated from field: uint64 flushed = 1;
+   */
+  flushed: bigint;
+};
+
+/**
+ * Describes the message admin_v1.FlushCachesResponse.
+ * Use `create(FlushCachesResponseSchema)` to create a new message.
+ */
+export const FlushCachesResponseSchema: GenMessage<FlushCachesResponse> = /*@__PURE__*/
+  messageDesc(file_admin, 5);
+
+/**
+ * @generated from service admin_v1.AdminService
+ */
+export const AdminService: GenService<{
+  /**
+   * Drops every memory in the collection and recreates it empty.
+   *
+   * @generated from rpc admin_v1.AdminService.ResetCollection
+   */
+  resetCollection: {
+    methodKind: "unary";
+    input: typeof ResetCollectionRequestSchema;
+    output: typeof EmptySchema;
+  },
+  /**
+   * Migrates every stored payload to the current schema version.
+   *
+   * @generated from rpc admin_v1.AdminService.Reindex
+   */
+  reindex: {
+    methodKind: "unary";
+    input: typeof EmptySchema;
+    output: typeof ReindexResponseSchema;
+  },
+  /**
+   * @generated from rpc admin_v1.AdminService.RunConsolidation
+   */
+  runConsolidation: {
+    methodKind: "unary";
+    input: typeof RunConsolidationRequestSchema;
+    output: typeof RunConsolidationResponseSchema;
+  },
+  /**
+   * @generated from rpc admin_v1.AdminService.DumpMetrics
+   */
+  dumpMetrics: {
+    methodKind: "unary";
+    input: typeof EmptySchema;
+    output: typeof MetricsResponseSchema;
+  },
+  /**
+   * Writes buffered state out to the stores. The server keeps no read caches, so
+   * this replays the create journal.
+   *
+   * @generated from rpc admin_v1.AdminService.FlushCaches
+   */
+  flushCaches: {
+    methodKind: "unary";
+    input: typeof EmptySchema;
+    output: typeof FlushCachesResponseSchema;
+  },
+}> = /*@__PURE__*/
+  serviceDesc(file_admin, 0);
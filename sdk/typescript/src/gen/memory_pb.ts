@@ -0,0 +1,1337 @@
+// @generated by protoc-gen-es v2.2.3 with parameter "target=ts,import_extension=js"
+// @generated from file memory.proto (package memory_v1, syntax proto3)
+/* eslint-disable */
+
+import type { GenEnum, GenFile, GenMessage, GenService } from "@bufbuild/protobuf/codegenv1";
+import { enumDesc, fileDesc, messageDesc, serviceDesc } from "@bufbuild/protobuf/codegenv1";
+import { file_google_protobuf_empty } from "@bufbuild/protobuf/wkt";
+import type { Message } from "@bufbuild/protobuf";
+import type { EmptySchema } from "@bufbuild/protobuf/wkt";
+
+/**
+ * Describes the file memory.proto.
+ */
+export const file_memory: GenFile = /*@__PURE__*/
+  fileDesc("CgxtZW1vcnkucHJvdG8SCW1lbW9yeV92MSKVAQoNTWVtb3J5Q29udGV4dBIUCgd1c2VyX2lkGAEgASgJSACIAQESFQoIYWdlbnRfaWQYAiABKAlIAYgBARITCgZydW5faWQYAyABKAlIAogBARITCgZvcmdfaWQYBCABKAlIA4gBAUIKCghfdXNlcl9pZEILCglfYWdlbnRfaWRCCQoHX3J1bl9pZEIJCgdfb3JnX2lkIi4KDU1lbW9yeUNvbnRlbnQSDwoHc3VtbWFyeRgBIAEoCRIMCgR0YWdzGAIgAygJIjQKDU1lbW9yeVNpZ25hbHMSEQoJY2VydGFpbnR5GAEgASgNEhAKCHNhbGllbmNlGAIgASgNIngKEFRlbXBvcmFsTWV0YWRhdGESEgoKY3JlYXRlZF9hdBgBIAEoAxIXCgp1cGRhdGVkX2F0GAIgASgDSACIAQESGAoLYXJjaGl2ZWRfYXQYAyABKANIAYgBAUINCgtfdXBkYXRlZF9hdEIOCgxfYXJjaGl2ZWRfYXQiMAoPRXh0cmFjdGVkTWV0aG9kEg0KBW1vZGVsGAEgASgJEg4KBnByb21wdBgCIAEoCSIhChBTdW1tYXJpemVkTWV0aG9kEg0KBW1vZGVsGAEgASgJIiAKDkltcG9ydGVkTWV0aG9kEg4KBnNvdXJjZRgBIAEoCSLBAQoQUHJvdmVuYW5jZU1ldGhvZBIQCgZkaXJlY3QYASABKAhIABIvCglleHRyYWN0ZWQYAiABKAsyGi5tZW1vcnlfdjEuRXh0cmFjdGVkTWV0aG9kSAASMQoKc3VtbWFyaXplZBgDIAEoCzIbLm1lbW9yeV92MS5TdW1tYXJpemVkTWV0aG9kSAASLQoIaW1wb3J0ZWQYBCABKAsyGS5tZW1vcnlfdjEuSW1wb3J0ZWRNZXRob2RIAEIICgZtZXRob2QiMAoKTW9kZXJhdGlvbhIRCgltb2RlcmF0b3IYASABKAkSDwoHZmxhZ2dlZBgCIAMoCSK9AQoKUHJvdmVuYW5jZRIrCgZvcmlnaW4YASABKA4yGy5tZW1vcnlfdjEuUHJvdmVuYW5jZU9yaWdpbhIrCgZtZXRob2QYAiABKAsyGy5tZW1vcnlfdjEuUHJvdmVuYW5jZU1ldGhvZBIpCgptb2RlcmF0aW9uGAMgASgLMhUubWVtb3J5X3YxLk1vZGVyYXRpb24SKgoFY2hhaW4YBCADKAsyGy5tZW1vcnlfdjEuUHJvdmVuYW5jZU1ldGhvZCKFAwoGTWVtb3J5EgoKAmlkGAEgASgJEikKB2NvbnRleHQYAiABKAsyGC5tZW1vcnlfdjEuTWVtb3J5Q29udGV4dBIsCglsaWZlY3ljbGUYAyABKA4yGS5tZW1vcnlfdjEuTGlmZWN5Y2xlU3RhdGUSIwoEa2luZBgEIAEoDjIVLm1lbW9yeV92MS5NZW1vcnlLaW5kEikKB2NvbnRlbnQYBSABKAsyGC5tZW1vcnlfdjEuTWVtb3J5Q29udGVudBIpCgdzaWduYWxzGAYgASgLMhgubWVtb3J5X3YxLk1lbW9yeVNpZ25hbHMSLQoIdGVtcG9yYWwYByABKAsyGy5tZW1vcnlfdjEuVGVtcG9yYWxNZXRhZGF0YRIpCgpwcm92ZW5hbmNlGAggASgLMhUubWVtb3J5X3YxLlByb3ZlbmFuY2USEwoLY29udHJhZGljdHMYCSADKAkSGgoNc3VwZXJzZWRlZF9ieRgKIAEoCUgAiAEBQhAKDl9zdXBlcnNlZGVkX2J5IpUBCg1Db250ZXh0RmlsdGVyEhQKB3VzZXJfaWQYASABKAlIAIgBARIVCghhZ2VudF9pZBgCIAEoCUgBiAEBEhMKBnJ1bl9pZBgDIAEoCUgCiAEBEhMKBm9yZ19pZBgEIAEoCUgDiAEBQgoKCF91c2VyX2lkQgsKCV9hZ2VudF9pZEIJCgdfcnVuX2lkQgkKB19vcmdfaWQizAEKDlRlbXBvcmFsRmlsdGVyEhoKDWNyZWF0ZWRfYWZ0ZXIYASABKANIAIgBARIbCg5jcmVhdGVkX2JlZm9yZRgCIAEoA0gBiAEBEhoKDXVwZGF0ZWRfYWZ0ZXIYAyABKANIAogBARIbCg51cGRhdGVkX2JlZm9yZRgEIAEoA0gDiAEBQhAKDl9jcmVhdGVkX2FmdGVyQhEKD19jcmVhdGVkX2JlZm9yZUIQCg5fdXBkYXRlZF9hZnRlckIRCg9fdXBkYXRlZF9iZWZvcmUiaAoMU2lnbmFsRmlsdGVyEhoKDW1pbl9jZXJ0YWludHkYASABKAJIAIgBARIZCgxtaW5fc2FsaWVuY2UYAiABKAJIAYgBAUIQCg5fbWluX2NlcnRhaW50eUIPCg1fbWluX3NhbGllbmNlIpABChNDcmVhdGVNZW1vcnlSZXF1ZXN0EhQKB3VzZXJfaWQYASABKAlIAIgBARIVCghhZ2VudF9pZBgCIAEoCUgBiAEBEhMKBnJ1bl9pZBgDIAEoCUgCiAEBEhMKC3Jhd19jb250ZW50GAQgASgJQgoKCF91c2VyX2lkQgsKCV9hZ2VudF9pZEIJCgdfcnVuX2lkIkkKFUNyZWF0ZU1lbW9yaWVzUmVxdWVzdBIwCghyZXF1ZXN0cxgBIAMoCzIeLm1lbW9yeV92MS5DcmVhdGVNZW1vcnlSZXF1ZXN0IiEKE0RlbGV0ZU1lbW9yeVJlcXVlc3QSCgoCaWQYASABKAkiHgoQR2V0TWVtb3J5UmVxdWVzdBIKCgJpZBgBIAEoCSJYChNMaXN0TWVtb3JpZXNSZXF1ZXN0EikKB2NvbnRleHQYASABKAsyGC5tZW1vcnlfdjEuQ29udGV4dEZpbHRlchIWCg5pbmNsdWRlX2ZhY2V0cxgCIAEoCCJ6ChVTZWFyY2hNZW1vcmllc1JlcXVlc3QSDQoFcXVlcnkYASABKAkSKQoHY29udGV4dBgCIAEoCzIYLm1lbW9yeV92MS5Db250ZXh0RmlsdGVyEhYKDmluY2x1ZGVfZmFjZXRzGAMgASgIEg8KB2V4cGxhaW4YBCABKAgijwEKE0J1aWxkQ29udGV4dFJlcXVlc3QSDQoFcXVlcnkYASABKAkSKQoHY29udGV4dBgCIAEoCzIYLm1lbW9yeV92MS5Db250ZXh0RmlsdGVyEhQKDHRva2VuX2J1ZGdldBgDIAEoDRIoCgZwb2xpY3kYBCABKA4yGC5tZW1vcnlfdjEuQ29udGV4dFBvbGljeSIeCg9NYXJrVXNlZFJlcXVlc3QSCwoDaWRzGAEgAygJIkQKD0ZlZWRiYWNrUmVxdWVzdBIRCgltZW1vcnlfaWQYASABKAkSDQoFcXVlcnkYAiABKAkSDwoHaGVscGZ1bBgDIAEoCCK+AgodQ3JlYXRlU3RydWN0dXJlZE1lbW9yeVJlcXVlc3QSFAoHdXNlcl9pZBgBIAEoCUgAiAEBEhUKCGFnZW50X2lkGAIgASgJSAGIAQESEwoGcnVuX2lkGAMgASgJSAKIAQESDwoHc3VtbWFyeRgEIAEoCRIjCgRraW5kGAUgASgOMhUubWVtb3J5X3YxLk1lbW9yeUtpbmQSDAoEdGFncxgGIAMoCRJECgZmaWVsZHMYByADKAsyNC5tZW1vcnlfdjEuQ3JlYXRlU3RydWN0dXJlZE1lbW9yeVJlcXVlc3QuRmllbGRzRW50cnkaLQoLRmllbGRzRW50cnkSCwoDa2V5GAEgASgJEg0KBXZhbHVlGAIgASgJOgI4AUIKCghfdXNlcl9pZEILCglfYWdlbnRfaWRCCQoHX3J1bl9pZCIWChRMaXN0VGVtcGxhdGVzUmVxdWVzdCIzCg5NZW1vcnlSZXNwb25zZRIhCgZtZW1vcnkYASABKAsyES5tZW1vcnlfdjEuTWVtb3J5IksKFENyZWF0ZU1lbW9yeVJlc3BvbnNlEiMKCG1lbW9yaWVzGAEgAygLMhEubWVtb3J5X3YxLk1lbW9yeRIOCgZxdWV1ZWQYAiABKAgiKgoKRmFjZXRDb3VudBINCgV2YWx1ZRgBIAEoCRINCgVjb3VudBgCIAEoBCKnAQoMTWVtb3J5RmFjZXRzEiQKBWtpbmRzGAEgAygLMhUubWVtb3J5X3YxLkZhY2V0Q291bnQSIwoEdGFncxgCIAMoCzIVLm1lbW9yeV92MS5GYWNldENvdW50EiUKBm1vbnRocxgDIAMoCzIVLm1lbW9yeV92MS5GYWNldENvdW50EiUKBnRvcGljcxgEIAMoCzIVLm1lbW9yeV92MS5GYWNldENvdW50IusBChJNZW1vcnlMaXN0UmVzcG9uc2USIwoIbWVtb3JpZXMYASADKAsyES5tZW1vcnlfdjEuTWVtb3J5EicKBmZhY2V0cxgCIAEoCzIXLm1lbW9yeV92MS5NZW1vcnlGYWNldHMSFgoOcmVyYW5rX3NraXBwZWQYAyABKAgSEQoJdGltZWRfb3V0GAQgASgIEikKB3RpbWluZ3MYBSABKAsyGC5tZW1vcnlfdjEuU2VhcmNoVGltaW5ncxIxCgtleHBsYW5hdGlvbhgGIAEoCzIcLm1lbW9yeV92MS5TZWFyY2hFeHBsYW5hdGlvbiJSCg1TZWFyY2hUaW1pbmdzEhQKDGVtYmVkX21pY3JvcxgBIAEoBBIUCgxzdG9yZV9taWNyb3MYAiABKAQSFQoNcmVyYW5rX21pY3JvcxgDIAEoBCJrChFTZWFyY2hFeHBsYW5hdGlvbhITCgtzdWJfcXVlcmllcxgBIAMoCRIOCgZmaWx0ZXIYAiABKAkSMQoKY2FuZGlkYXRlcxgDIAMoCzIdLm1lbW9yeV92MS5FeHBsYWluZWRDYW5kaWRhdGUiwgEKEkV4cGxhaW5lZENhbmRpZGF0ZRIRCgltZW1vcnlfaWQYASABKAkSEQoJc3ViX3F1ZXJ5GAIgASgNEhQKDHZlY3Rvcl9zY29yZRgDIAEoAhIZCgxyZXJhbmtfc2NvcmUYBCABKAJIAIgBARIYCgtmdXNlZF9zY29yZRgFIAEoAkgBiAEBEhEKBHJhbmsYBiABKA1IAogBAUIPCg1fcmVyYW5rX3Njb3JlQg4KDF9mdXNlZF9zY29yZUIHCgVfcmFuayJYChNDb250ZXh0UGFja1Jlc3BvbnNlEgwKBHRleHQYASABKAkSDgoGdG9rZW5zGAIgASgNEiMKCG1lbW9yaWVzGAMgAygLMhEubWVtb3J5X3YxLk1lbW9yeSJECg1UZW1wbGF0ZUZpZWxkEgwKBG5hbWUYASABKAkSEAoIcmVxdWlyZWQYAiABKAgSEwoLZGVzY3JpcHRpb24YAyABKAkiXwoOTWVtb3J5VGVtcGxhdGUSIwoEa2luZBgBIAEoDjIVLm1lbW9yeV92MS5NZW1vcnlLaW5kEigKBmZpZWxkcxgCIAMoCzIYLm1lbW9yeV92MS5UZW1wbGF0ZUZpZWxkIkQKFFRlbXBsYXRlTGlzdFJlc3BvbnNlEiwKCXRlbXBsYXRlcxgBIAMoCzIZLm1lbW9yeV92MS5NZW1vcnlUZW1wbGF0ZSrIAQoOTGlmZWN5Y2xlU3RhdGUSHwobTElGRUNZQ0xFX1NUQVRFX1VOU1BFQ0lGSUVEEAASGgoWTElGRUNZQ0xFX1NUQVRFX0FDVElWRRABEhwKGExJRkVDWUNMRV9TVEFURV9BUkNISVZFRBACEhsKF0xJRkVDWUNMRV9TVEFURV9ERUxFVEVEEAMSHgoaTElGRUNZQ0xFX1NUQVRFX1NVUEVSU0VERUQQBBIeChpMSUZFQ1lDTEVfU1RBVEVfU1VQUFJFU1NFRBAFKugBCgpNZW1vcnlLaW5kEhsKF01FTU9SWV9LSU5EX1VOU1BFQ0lGSUVEEAASGAoUTUVNT1JZX0tJTkRfU0VNQU5USUMQARIYChRNRU1PUllfS0lORF9FUElTT0RJQxACEhoKFk1FTU9SWV9LSU5EX1BST0NFRFVSQUwQAxIbChdNRU1PUllfS0lORF9JTlNUUlVDVElPThAEEhoKFk1FTU9SWV9LSU5EX1JFTEFUSU9OQUwQBRIXChNNRU1PUllfS0lORF9XT1JLSU5HEAYSGwoXTUVNT1JZX0tJTkRfUFJPU1BFQ1RJVkUQByqIAQoNQ29udGV4dFBvbGljeRIeChpDT05URVhUX1BPTElDWV9VTlNQRUNJRklFRBAAEhwKGENPTlRFWFRfUE9MSUNZX1JFTEVWQU5DRRABEhoKFkNPTlRFWFRfUE9MSUNZX1JFQ0VOQ1kQAhIdChlDT05URVhUX1BPTElDWV9JTVBPUlRBTkNFEAMqbgoQUHJvdmVuYW5jZU9yaWdpbhIhCh1QUk9WRU5BTkNFX09SSUdJTl9VTlNQRUNJRklFRBAAEhoKFlBST1ZFTkFOQ0VfT1JJR0lOX1VTRVIQARIbChdQUk9WRU5BTkNFX09SSUdJTl9BR0VOVBACMuwGCg1NZW1vcnlTZXJ2aWNlEk8KDENyZWF0ZU1lbW9yeRIeLm1lbW9yeV92MS5DcmVhdGVNZW1vcnlSZXF1ZXN0Gh8ubWVtb3J5X3YxLkNyZWF0ZU1lbW9yeVJlc3BvbnNlEkYKDERlbGV0ZU1lbW9yeRIeLm1lbW9yeV92MS5EZWxldGVNZW1vcnlSZXF1ZXN0GhYuZ29vZ2xlLnByb3RvYnVmLkVtcHR5EkMKCUdldE1lbW9yeRIbLm1lbW9yeV92MS5HZXRNZW1vcnlSZXF1ZXN0GhkubWVtb3J5X3YxLk1lbW9yeVJlc3BvbnNlEk0KDExpc3RNZW1vcmllcxIeLm1lbW9yeV92MS5MaXN0TWVtb3JpZXNSZXF1ZXN0Gh0ubWVtb3J5X3YxLk1lbW9yeUxpc3RSZXNwb25zZRJRCg5TZWFyY2hNZW1vcmllcxIgLm1lbW9yeV92MS5TZWFyY2hNZW1vcmllc1JlcXVlc3QaHS5tZW1vcnlfdjEuTWVtb3J5TGlzdFJlc3BvbnNlEk4KDEJ1aWxkQ29udGV4dBIeLm1lbW9yeV92MS5CdWlsZENvbnRleHRSZXF1ZXN0Gh4ubWVtb3J5X3YxLkNvbnRleHRQYWNrUmVzcG9uc2USPgoITWFya1VzZWQSGi5tZW1vcnlfdjEuTWFya1VzZWRSZXF1ZXN0GhYuZ29vZ2xlLnByb3RvYnVmLkVtcHR5Ej4KCEZlZWRiYWNrEhoubWVtb3J5X3YxLkZlZWRiYWNrUmVxdWVzdBoWLmdvb2dsZS5wcm90b2J1Zi5FbXB0eRJTCg5DcmVhdGVNZW1vcmllcxIgLm1lbW9yeV92MS5DcmVhdGVNZW1vcmllc1JlcXVlc3QaHy5tZW1vcnlfdjEuQ3JlYXRlTWVtb3J5UmVzcG9uc2USYwoWQ3JlYXRlU3RydWN0dXJlZE1lbW9yeRIoLm1lbW9yeV92MS5DcmVhdGVTdHJ1Y3R1cmVkTWVtb3J5UmVxdWVzdBofLm1lbW9yeV92MS5DcmVhdGVNZW1vcnlSZXNwb25zZRJRCg1MaXN0VGVtcGxhdGVzEh8ubWVtb3J5X3YxLkxpc3RUZW1wbGF0ZXNSZXF1ZXN0Gh8ubWVtb3J5X3YxLlRlbXBsYXRlTGlzdFJlc3BvbnNlYgZwcm90bzM", [file_google_protobuf_empty]);
+
+/**
+ * @generated from message memory_v1.MemoryContext
+ */
+export type MemoryContext = Message<"memory_v1.MemoryContext"> & {
+  /**
+   * @generated from field: optional string user_id = 1;
+   */
+  userId?: string;
+
+  /**
+   * @generated from field: optional string agent_id = 2;
+   */
+  agentId?: string;
+
+  /**
+   * @generated from field: optional string run_id = 3;
+   */
+  runId?: string;
+
+  /**
+   * Set on memories published to an organization's pool, which carry no other ids.
+   *
+   * @generated from field: optional string org_id = 4;
+   */
+  orgId?: string;
+};
+
+/**
+ * Describes the message memory_v1.MemoryContext.
+ * Use `create(MemoryContextSchema)` to create a new message.
+ */
+export const MemoryContextSchema: GenMessage<MemoryContext> = /*@__PURE__*/
+  messageDesc(file_memory, 0);
+
+/**
+ * @generated from message memory_v1.MemoryContent
+ */
+export type MemoryContent = Message<"memory_v1.MemoryContent"> & {
+  /**
+   * @generated from field: string summary = 1;
+   */
+  summary: string;
+
+  /**
+   * @generated from field: repeated string tags = 2;
+   */
+  tags: string[];
+};
+
+/**
+ * Describes the message memory_v1.MemoryContent.
+ * Use `create(MemoryContentSchema)` to create a new message.
+ */
+export const MemoryContentSchema: GenMessage<MemoryContent> = /*@__PURE__*/
+  messageDesc(file_memory, 1);
+
+/**
+ * Signals are percentages in the range [0, 100].
+ *
+ * @generated from message memory_v1.MemorySignals
+ */
+export type MemorySignals = Message<"memory_v1.MemorySignals"> & {
+  /**
+   * @generated from field: uint32 certainty = 1;
+   */
+  certainty: number;
+
+  /**
+   * @generated from field: uint32 salience = 2;
+   */
+  salience: number;
+};
+
+/**
+ * Describes the message memory_v1.MemorySignals.
+ * Use `create(MemorySignalsSchema)` to create a new message.
+ */
+export const MemorySignalsSchema: GenMessage<MemorySignals> = /*@__PURE__*/
+  messageDesc(file_memory, 2);
+
+/**
+ * @generated from message memory_v1.TemporalMetadata
+ */
+export type TemporalMetadata = Message<"memory_v1.TemporalMetadata"> & {
+  /**
+   * @generated from field: int64 created_at = 1;
+   */
+  createdAt: bigint;
+
+  /**
+   * @generated from field: optional int64 updated_at = 2;
+   */
+  updatedAt?: bigint;
+
+  /**
+   * @generated from field: optional int64 archived_at = 3;
+   */
+  archivedAt?: bigint;
+};
+
+/**
+ * Describes the message memory_v1.TemporalMetadata.
+ * Use `create(TemporalMetadataSchema)` to create a new message.
+ */
+export const TemporalMetadataSchema: GenMessage<TemporalMetadata> = /*@__PURE__*/
+  messageDesc(file_memory, 3);
+
+/**
+ * @generated from message memory_v1.ExtractedMethod
+ */
+export type ExtractedMethod = Message<"memory_v1.ExtractedMethod"> & {
+  /**
+   * @generated from field: string model = 1;
+   */
+  model: string;
+
+  /**
+   * @generated from field: string prompt = 2;
+   */
+  prompt: string;
+};
+
+/**
+ * Describes the message memory_v1.ExtractedMethod.
+ * Use `create(ExtractedMethodSchema)` to create a new message.
+ */
+export const ExtractedMethodSchema: GenMessage<ExtractedMethod> = /*@__PURE__*/
+  messageDesc(file_memory, 4);
+
+/**
+ * @generated from message memory_v1.SummarizedMethod
+ */
+export type SummarizedMethod = Message<"memory_v1.SummarizedMethod"> & {
+  /**
+   * @generated from field: string model = 1;
+   */
+  model: string;
+};
+
+/**
+ * Describes the message memory_v1.SummarizedMethod.
+ * Use `create(SummarizedMethodSchema)` to create a new message.
+ */
+export const SummarizedMethodSchema: GenMessage<SummarizedMethod> = /*@__PURE__*/
+  messageDesc(file_memory, 5);
+
+/**
+ * @generated from message memory_v1.ImportedMethod
+ */
+export type ImportedMethod = Message<"memory_v1.ImportedMethod"> & {
+  /**
+   * The memory store it was migrated from, e.g. "mem0".
+   *
+   * @generated from field: string source = 1;
+   */
+  source: string;
+};
+
+/**
+ * Describes the message memory_v1.ImportedMethod.
+ * Use `create(ImportedMethodSchema)` to create a new message.
+ */
+export const ImportedMethodSchema: GenMessage<ImportedMethod> = /*@__PURE__*/
+  messageDesc(file_memory, 6);
+
+/**
+ * @generated from message memory_v1.ProvenanceMethod
+ */
+export type ProvenanceMethod = Message<"memory_v1.ProvenanceMethod"> & {
+  /**
+   * @generated from oneof memory_v1.ProvenanceMethod.method
+   */
+  method: {
+    /**
+     * @generated from field: bool direct = 1;
+     */
+    value: boolean;
+    case: "direct";
+  } | {
+    /**
+     * @generated from field: memory_v1.ExtractedMethod extracted = 2;
+     */
+    value: ExtractedMethod;
+    case: "extracted";
+  } | {
+    /**
+     * @generated from field: memory_v1.SummarizedMethod summarized = 3;
+     */
+    value: SummarizedMethod;
+    case: "summarized";
+  } | {
+    /**
+     * @generated from field: memory_v1.ImportedMethod imported = 4;
+     */
+    value: ImportedMethod;
+    case: "imported";
+  } | { case: undefined; value?: undefined };
+};
+
+/**
+ * Describes the message memory_v1.ProvenanceMethod.
+ * Use `create(ProvenanceMethodSchema)` to create a new message.
+ */
+export const ProvenanceMethodSchema: GenMessage<ProvenanceMethod> = /*@__PURE__*/
+  messageDesc(file_memory, 7);
+
+/**
+ * Set when the memory was moderated before it was stored.
+ *
+ * @generated from message memory_v1.Moderation
+ */
+export type Moderation = Message<"memory_v1.Moderation"> & {
+  /**
+   * What judged the content, e.g. "rules" or "omni-moderation-latest".
+   *
+   * @generated from field: string moderator = 1;
+   */
+  moderator: string;
+
+  /**
+   * Categories the content was flagged for but stored anyway.
+   *
+   * @generated from field: repeated string flagged = 2;
+   */
+  flagged: string[];
+};
+
+/**
+ * Describes the message memory_v1.Moderation.
+ * Use `create(ModerationSchema)` to create a new message.
+ */
+export const ModerationSchema: GenMessage<Moderation> = /*@__PURE__*/
+  messageDesc(file_memory, 8);
+
+/**
+ * @generated from message memory_v1.Provenance
+ */
+export type Provenance = Message<"memory_v1.Provenance"> & {
+  /**
+   * @generated from field: memory_v1.ProvenanceOrigin origin = 1;
+   */
+  origin: ProvenanceOrigin;
+
+  /**
+   * The last step of `chain`, which produced the current content.
+   *
+   * @generated from field: memory_v1.ProvenanceMethod method = 2;
+   */
+  method?: ProvenanceMethod;
+
+  /**
+   * @generated from field: memory_v1.Moderation moderation = 3;
+   */
+  moderation?: Moderation;
+
+  /**
+   * Every step that produced the memory's current content, oldest first.
+   *
+   * @generated from field: repeated memory_v1.ProvenanceMethod chain = 4;
+   */
+  chain: ProvenanceMethod[];
+};
+
+/**
+ * Describes the message memory_v1.Provenance.
+ * Use `create(ProvenanceSchema)` to create a new message.
+ */
+export const ProvenanceSchema: GenMessage<Provenance> = /*@__PURE__*/
+  messageDesc(file_memory, 9);
+
+/**
+ * @generated from message memory_v1.Memory
+ */
+export type Memory = Message<"memory_v1.Memory"> & {
+  /**
+   * @generated from field: string id = 1;
+   */
+  id: string;
+
+  /**
+   * @generated from field: memory_v1.MemoryContext context = 2;
+   */
+  context?: MemoryContext;
+
+  /**
+   * @generated from field: memory_v1.LifecycleState lifecycle = 3;
+   */
+  lifecycle: LifecycleState;
+
+  /**
+   * @generated from field: memory_v1.MemoryKind kind = 4;
+   */
+  kind: MemoryKind;
+
+  /**
+   * @generated from field: memory_v1.MemoryContent content = 5;
+   */
+  content?: MemoryContent;
+
+  /**
+   * @generated from field: memory_v1.MemorySignals signals = 6;
+   */
+  signals?: MemorySignals;
+
+  /**
+   * @generated from field: memory_v1.TemporalMetadata temporal = 7;
+   */
+  temporal?: TemporalMetadata;
+
+  /**
+   * @generated from field: memory_v1.Provenance provenance = 8;
+   */
+  provenance?: Provenance;
+
+  /**
+   * Ids of stored memories this memory contradicts.
+   *
+   * @generated from field: repeated string contradicts = 9;
+   */
+  contradicts: string[];
+
+  /**
+   * Id of the memory that replaced this one, set when it is superseded.
+   *
+   * @generated from field: optional string superseded_by = 10;
+   */
+  supersededBy?: string;
+};
+
+/**
+ * Describes the message memory_v1.Memory.
+ * Use `create(MemorySchema)` to create a new message.
+ */
+export const MemorySchema: GenMessage<Memory> = /*@__PURE__*/
+  messageDesc(file_memory, 10);
+
+/**
+ * @generated from message memory_v1.ContextFilter
+ */
+export type ContextFilter = Message<"memory_v1.ContextFilter"> & {
+  /**
+   * @generated from field: optional string user_id = 1;
+   */
+  userId?: string;
+
+  /**
+   * @generated from field: optional string agent_id = 2;
+   */
+  agentId?: string;
+
+  /**
+   * @generated from field: optional string run_id = 3;
+   */
+  runId?: string;
+
+  /**
+   * Also match the pool of this organization, or only the pool when no other id is set.
+   *
+   * @generated from field: optional string org_id = 4;
+   */
+  orgId?: string;
+};
+
+/**
+ * Describes the message memory_v1.ContextFilter.
+ * Use `create(ContextFilterSchema)` to create a new message.
+ */
+export const ContextFilterSchema: GenMessage<ContextFilter> = /*@__PURE__*/
+  messageDesc(file_memory, 11);
+
+/**
+ * @generated from message memory_v1.TemporalFilter
+ */
+export type TemporalFilter = Message<"memory_v1.TemporalFilter"> & {
+  /**
+   * @generated from field: optional int64 created_after = 1;
+   */
+  createdAfter?: bigint;
+
+  /**
+   * @generated from field: optional int64 created_before = 2;
+   */
+  createdBefore?: bigint;
+
+  /**
+   * @generated from field: optional int64 updated_after = 3;
+   */
+  updatedAfter?: bigint;
+
+  /**
+   * @generated from field: optional int64 updated_before = 4;
+   */
+  updatedBefore?: bigint;
+};
+
+/**
+ * Describes the message memory_v1.TemporalFilter.
+ * Use `create(TemporalFilterSchema)` to create a new message.
+ */
+export const TemporalFilterSchema: GenMessage<TemporalFilter> = /*@__PURE__*/
+  messageDesc(file_memory, 12);
+
+/**
+ * @generated from message memory_v1.SignalFilter
+ */
+export type SignalFilter = Message<"memory_v1.SignalFilter"> & {
+  /**
+   * @generated from field: optional float min_certainty = 1;
+   */
+  minCertainty?: number;
+
+  /**
+   * @generated from field: optional float min_salience = 2;
+   */
+  minSalience?: number;
+};
+
+/**
+ * Describes the message memory_v1.SignalFilter.
+ * Use `create(SignalFilterSchema)` to create a new message.
+ */
+export const SignalFilterSchema: GenMessage<SignalFilter> = /*@__PURE__*/
+  messageDesc(file_memory, 13);
+
+/**
+ * @generated from message memory_v1.CreateMemoryRequest
+ */
+export type CreateMemoryRequest = Message<"memory_v1.CreateMemoryRequest"> & {
+  /**
+   * @generated from field: optional string user_id = 1;
+   */
+  userId?: string;
+
+  /**
+   * @generated from field: optional string agent_id = 2;
+   */
+  agentId?: string;
+
+  /**
+   * @generated from field: optional string run_id = 3;
+   */
+  runId?: string;
+
+  /**
+   * @generated from field: string raw_content = 4;
+   */
+  rawContent: string;
+};
+
+/**
+ * Describes the message memory_v1.CreateMemoryRequest.
+ * Use `create(CreateMemoryRequestSchema)` to create a new message.
+ */
+export const CreateMemoryRequestSchema: GenMessage<CreateMemoryRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 14);
+
+/**
+ * Creates the memories for each request; the requests may belong to different contexts.
+ *
+ * @generated from message memory_v1.CreateMemoriesRequest
+ */
+export type CreateMemoriesRequest = Message<"memory_v1.CreateMemoriesRequest"> & {
+  /**
+   * @generated from field: repeated memory_v1.CreateMemoryRequest requests = 1;
+   */
+  requests: CreateMemoryRequest[];
+};
+
+/**
+ * Describes the message memory_v1.CreateMemoriesRequest.
+ * Use `create(CreateMemoriesRequestSchema)` to create a new message.
+ */
+export const CreateMemoriesRequestSchema: GenMessage<CreateMemoriesRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 15);
+
+/**
+ * @generated from message memory_v1.DeleteMemoryRequest
+ */
+export type DeleteMemoryRequest = Message<"memory_v1.DeleteMemoryRequest"> & {
+  /**
+   * @generated from field: string id = 1;
+   */
+  id: string;
+};
+
+/**
+ * Describes the message memory_v1.DeleteMemoryRequest.
+ * Use `create(DeleteMemoryRequestSchema)` to create a new message.
+ */
+export const DeleteMemoryRequestSchema: GenMessage<DeleteMemoryRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 16);
+
+/**
+ * @generated from message memory_v1.GetMemoryRequest
+ */
+export type GetMemoryRequest = Message<"memory_v1.GetMemoryRequest"> & {
+  /**
+   * @generated from field: string id = 1;
+   */
+  id: string;
+};
+
+/**
+ * Describes the message memory_v1.GetMemoryRequest.
+ * Use `create(GetMemoryRequestSchema)` to create a new message.
+ */
+export const GetMemoryRequestSchema: GenMessage<GetMemoryRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 17);
+
+/**
+ * @generated from message memory_v1.ListMemoriesRequest
+ */
+export type ListMemoriesRequest = Message<"memory_v1.ListMemoriesRequest"> & {
+  /**
+   * @generated from field: memory_v1.ContextFilter context = 1;
+   */
+  context?: ContextFilter;
+
+  /**
+   * Also return kind/tag/month counts over the whole context.
+   *
+   * @generated from field: bool include_facets = 2;
+   */
+  includeFacets: boolean;
+};
+
+/**
+ * Describes the message memory_v1.ListMemoriesRequest.
+ * Use `create(ListMemoriesRequestSchema)` to create a new message.
+ */
+export const ListMemoriesRequestSchema: GenMessage<ListMemoriesRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 18);
+
+/**
+ * @generated from message memory_v1.SearchMemoriesRequest
+ */
+export type SearchMemoriesRequest = Message<"memory_v1.SearchMemoriesRequest"> & {
+  /**
+   * @generated from field: string query = 1;
+   */
+  query: string;
+
+  /**
+   * @generated from field: memory_v1.ContextFilter context = 2;
+   */
+  context?: ContextFilter;
+
+  /**
+   * Also return kind/tag/month counts over the whole context.
+   *
+   * @generated from field: bool include_facets = 3;
+   */
+  includeFacets: boolean;
+
+  /**
+   * Also return how the search arrived at its results.
+   *
+   * @generated from field: bool explain = 4;
+   */
+  explain: boolean;
+};
+
+/**
+ * Describes the message memory_v1.SearchMemoriesRequest.
+ * Use `create(SearchMemoriesRequestSchema)` to create a new message.
+ */
+export const SearchMemoriesRequestSchema: GenMessage<SearchMemoriesRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 19);
+
+/**
+ * @generated from message memory_v1.BuildContextRequest
+ */
+export type BuildContextRequest = Message<"memory_v1.BuildContextRequest"> & {
+  /**
+   * @generated from field: string query = 1;
+   */
+  query: string;
+
+  /**
+   * @generated from field: memory_v1.ContextFilter context = 2;
+   */
+  context?: ContextFilter;
+
+  /**
+   * @generated from field: uint32 token_budget = 3;
+   */
+  tokenBudget: number;
+
+  /**
+   * @generated from field: memory_v1.ContextPolicy policy = 4;
+   */
+  policy: ContextPolicy;
+};
+
+/**
+ * Describes the message memory_v1.BuildContextRequest.
+ * Use `create(BuildContextRequestSchema)` to create a new message.
+ */
+export const BuildContextRequestSchema: GenMessage<BuildContextRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 20);
+
+/**
+ * Reports that retrieved memories were used, raising their salience.
+ *
+ * @generated from message memory_v1.MarkUsedRequest
+ */
+export type MarkUsedRequest = Message<"memory_v1.MarkUsedRequest"> & {
+  /**
+   * @generated from field: repeated string ids = 1;
+   */
+  ids: string[];
+};
+
+/**
+ * Describes the message memory_v1.MarkUsedRequest.
+ * Use `create(MarkUsedRequestSchema)` to create a new message.
+ */
+export const MarkUsedRequestSchema: GenMessage<MarkUsedRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 21);
+
+/**
+ * Whether a memory retrieved for query helped.
+ *
+ * @generated from message memory_v1.FeedbackRequest
+ */
+export type FeedbackRequest = Message<"memory_v1.FeedbackRequest"> & {
+  /**
+   * @generated from field: string memory_id = 1;
+   */
+  memoryId: string;
+
+  /**
+   * @generated from field: string query = 2;
+   */
+  query: string;
+
+  /**
+   * @generated from field: bool helpful = 3;
+   */
+  helpful: boolean;
+};
+
+/**
+ * Describes the message memory_v1.FeedbackRequest.
+ * Use `create(FeedbackRequestSchema)` to create a new message.
+ */
+export const FeedbackRequestSchema: GenMessage<FeedbackRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 22);
+
+/**
+ * Stores one memory as given, without annotation. ListTemplates says which fields
+ * its kind needs.
+ *
+ * @generated from message memory_v1.CreateStructuredMemoryRequest
+ */
+export type CreateStructuredMemoryRequest = Message<"memory_v1.CreateStructuredMemoryRequest"> & {
+  /**
+   * @generated from field: optional string user_id = 1;
+   */
+  userId?: string;
+
+  /**
+   * @generated from field: optional string agent_id = 2;
+   */
+  agentId?: string;
+
+  /**
+   * @generated from field: optional string run_id = 3;
+   */
+  runId?: string;
+
+  /**
+   * @generated from field: string summary = 4;
+   */
+  summary: string;
+
+  /**
+   * @generated from field: memory_v1.MemoryKind kind = 5;
+   */
+  kind: MemoryKind;
+
+  /**
+   * @generated from field: repeated string tags = 6;
+   */
+  tags: string[];
+
+  /**
+   * Values for the fields of the kind's template; due_at is an RFC 3339 timestamp.
+   *
+   * @generated from field: map<string, string> fields = 7;
+   */
+  fields: { [key: string]: string };
+};
+
+/**
+ * Describes the message memory_v1.CreateStructuredMemoryRequest.
+ * Use `create(CreateStructuredMemoryRequestSchema)` to create a new message.
+ */
+export const CreateStructuredMemoryRequestSchema: GenMessage<CreateStructuredMemoryRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 23);
+
+/**
+ * @generated from message memory_v1.ListTemplatesRequest
+ */
+export type ListTemplatesRequest = Message<"memory_v1.ListTemplatesRequest"> & {
+};
+
+/**
+ * Describes the message memory_v1.ListTemplatesRequest.
+ * Use `create(ListTemplatesRequestSchema)` to create a new message.
+ */
+export const ListTemplatesRequestSchema: GenMessage<ListTemplatesRequest> = /*@__PURE__*/
+  messageDesc(file_memory, 24);
+
+/**
+ * @generated from message memory_v1.MemoryResponse
+ */
+export type MemoryResponse = Message<"memory_v1.MemoryResponse"> & {
+  /**
+   * @generated from field: memory_v1.Memory memory = 1;
+   */
+  memory?: Memory;
+};
+
+/**
+ * Describes the message memory_v1.MemoryResponse.
+ * Use `create(MemoryResponseSchema)` to create a new message.
+ */
+export const MemoryResponseSchema: GenMessage<MemoryResponse> = /*@__PURE__*/
+  messageDesc(file_memory, 25);
+
+/**
+ * The memories a create call stored, with their generated ids, summaries and kinds.
+ *
+ * @generated from message memory_v1.CreateMemoryResponse
+ */
+export type CreateMemoryResponse = Message<"memory_v1.CreateMemoryResponse"> & {
+  /**
+   * @generated from field: repeated memory_v1.Memory memories = 1;
+   */
+  memories: Memory[];
+
+  /**
+   * Set when the requests were journaled instead; memories is then empty and the
+   * memories become searchable after the next journal flush.
+   *
+   * @generated from field: bool queued = 2;
+   */
+  queued: boolean;
+};
+
+/**
+ * Describes the message memory_v1.CreateMemoryResponse.
+ * Use `create(CreateMemoryResponseSchema)` to create a new message.
+ */
+export const CreateMemoryResponseSchema: GenMessage<CreateMemoryResponse> = /*@__PURE__*/
+  messageDesc(file_memory, 26);
+
+/**
+ * @generated from message memory_v1.FacetCount
+ */
+export type FacetCount = Message<"memory_v1.FacetCount"> & {
+  /**
+   * @generated from field: string value = 1;
+   */
+  value: string;
+
+  /**
+   * @generated from field: uint64 count = 2;
+   */
+  count: bigint;
+};
+
+/**
+ * Describes the message memory_v1.FacetCount.
+ * Use `create(FacetCountSchema)` to create a new message.
+ */
+export const FacetCountSchema: GenMessage<FacetCount> = /*@__PURE__*/
+  messageDesc(file_memory, 27);
+
+/**
+ * @generated from message memory_v1.MemoryFacets
+ */
+export type MemoryFacets = Message<"memory_v1.MemoryFacets"> & {
+  /**
+   * @generated from field: repeated memory_v1.FacetCount kinds = 1;
+   */
+  kinds: FacetCount[];
+
+  /**
+   * @generated from field: repeated memory_v1.FacetCount tags = 2;
+   */
+  tags: FacetCount[];
+
+  /**
+   * Creation month as YYYY-MM (UTC), newest first.
+   *
+   * @generated from field: repeated memory_v1.FacetCount months = 3;
+   */
+  months: FacetCount[];
+
+  /**
+   * Topic labels assigned by clustering.
+   *
+   * @generated from field: repeated memory_v1.FacetCount topics = 4;
+   */
+  topics: FacetCount[];
+};
+
+/**
+ * Describes the message memory_v1.MemoryFacets.
+ * Use `create(MemoryFacetsSchema)` to create a new message.
+ */
+export const MemoryFacetsSchema: GenMessage<MemoryFacets> = /*@__PURE__*/
+  messageDesc(file_memory, 28);
+
+/**
+ * @generated from message memory_v1.MemoryListResponse
+ */
+export type MemoryListResponse = Message<"memory_v1.MemoryListResponse"> & {
+  /**
+   * @generated from field: repeated memory_v1.Memory memories = 1;
+   */
+  memories: Memory[];
+
+  /**
+   * Set only when include_facets was requested.
+   *
+   * @generated from field: memory_v1.MemoryFacets facets = 2;
+   */
+  facets?: MemoryFacets;
+
+  /**
+   * Set when the reranker failed and memories are in vector-similarity order.
+   *
+   * @generated from field: bool rerank_skipped = 3;
+   */
+  rerankSkipped: boolean;
+
+  /**
+   * Set when the search deadline ran out and memories are what was found in time.
+   *
+   * @generated from field: bool timed_out = 4;
+   */
+  timedOut: boolean;
+
+  /**
+   * Server-side time per search stage. Not set for listings.
+   *
+   * @generated from field: memory_v1.SearchTimings timings = 5;
+   */
+  timings?: SearchTimings;
+
+  /**
+   * Set only when explain was requested.
+   *
+   * @generated from field: memory_v1.SearchExplanation explanation = 6;
+   */
+  explanation?: SearchExplanation;
+};
+
+/**
+ * Describes the message memory_v1.MemoryListResponse.
+ * Use `create(MemoryListResponseSchema)` to create a new message.
+ */
+export const MemoryListResponseSchema: GenMessage<MemoryListResponse> = /*@__PURE__*/
+  messageDesc(file_memory, 29);
+
+/**
+ * @generated from message memory_v1.SearchTimings
+ */
+export type SearchTimings = Message<"memory_v1.SearchTimings"> & {
+  /**
+   * @generated from field: uint64 embed_micros = 1;
+   */
+  embedMicros: bigint;
+
+  /**
+   * @generated from field: uint64 store_micros = 2;
+   */
+  storeMicros: bigint;
+
+  /**
+   * @generated from field: uint64 rerank_micros = 3;
+   */
+  rerankMicros: bigint;
+};
+
+/**
+ * Describes the message memory_v1.SearchTimings.
+ * Use `create(SearchTimingsSchema)` to create a new message.
+ */
+export const SearchTimingsSchema: GenMessage<SearchTimings> = /*@__PURE__*/
+  messageDesc(file_memory, 30);
+
+/**
+ * How a search arrived at its results; timings are in SearchTimings.
+ *
+ * @generated from message memory_v1.SearchExplanation
+ */
+export type SearchExplanation = Message<"memory_v1.SearchExplanation"> & {
+  /**
+   * The queries that were embedded and searched; the original query is last.
+   *
+   * @generated from field: repeated string sub_queries = 1;
+   */
+  subQueries: string[];
+
+  /**
+   * The vector store's translation of the search's filters.
+   *
+   * @generated from field: string filter = 2;
+   */
+  filter: string;
+
+  /**
+   * Everything the vector stage found, in the order it was found.
+   *
+   * @generated from field: repeated memory_v1.ExplainedCandidate candidates = 3;
+   */
+  candidates: ExplainedCandidate[];
+};
+
+/**
+ * Describes the message memory_v1.SearchExplanation.
+ * Use `create(SearchExplanationSchema)` to create a new message.
+ */
+export const SearchExplanationSchema: GenMessage<SearchExplanation> = /*@__PURE__*/
+  messageDesc(file_memory, 31);
+
+/**
+ * One memory the vector stage found, with its score at each later stage.
+ *
+ * @generated from message memory_v1.ExplainedCandidate
+ */
+export type ExplainedCandidate = Message<"memory_v1.ExplainedCandidate"> & {
+  /**
+   * @generated from field: string memory_id = 1;
+   */
+  memoryId: string;
+
+  /**
+   * Index into SearchExplanation.sub_queries of the query that found it.
+   *
+   * @generated from field: uint32 sub_query = 2;
+   */
+  subQuery: number;
+
+  /**
+   * @generated from field: float vector_score = 3;
+   */
+  vectorScore: number;
+
+  /**
+   * Unset when the memory was not reranked: it was already found by another
+   * sub-query, cut by the candidate cap, or the reranker was skipped.
+   *
+   * @generated from field: optional float rerank_score = 4;
+   */
+  rerankScore?: number;
+
+  /**
+   * The score results were ordered by, when [search.fusion] is configured.
+   *
+   * @generated from field: optional float fused_score = 5;
+   */
+  fusedScore?: number;
+
+  /**
+   * Position in memories; unset when the memory was not returned.
+   *
+   * @generated from field: optional uint32 rank = 6;
+   */
+  rank?: number;
+};
+
+/**
+ * Describes the message memory_v1.ExplainedCandidate.
+ * Use `create(ExplainedCandidateSchema)` to create a new message.
+ */
+export const ExplainedCandidateSchema: GenMessage<ExplainedCandidate> = /*@__PURE__*/
+  messageDesc(file_memory, 32);
+
+/**
+ * @generated from message memory_v1.ContextPackResponse
+ */
+export type ContextPackResponse = Message<"memory_v1.ContextPackResponse"> & {
+  /**
+   * @generated from field: string text = 1;
+   */
+  text: string;
+
+  /**
+   * @generated from field: uint32 tokens = 2;
+   */
+  tokens: number;
+
+  /**
+   * @generated from field: repeated memory_v1.Memory memories = 3;
+   */
+  memories: Memory[];
+};
+
+/**
+ * Describes the message memory_v1.ContextPackResponse.
+ * Use `create(ContextPackResponseSchema)` to create a new message.
+ */
+export const ContextPackResponseSchema: GenMessage<ContextPackResponse> = /*@__PURE__*/
+  messageDesc(file_memory, 33);
+
+/**
+ * @generated from message memory_v1.TemplateField
+ */
+export type TemplateField = Message<"memory_v1.TemplateField"> & {
+  /**
+   * @generated from field: string name = 1;
+   */
+  name: string;
+
+  /**
+   * @generated from field: bool required = 2;
+   */
+  required: boolean;
+
+  /**
+   * @generated from field: string description = 3;
+   */
+  description: string;
+};
+
+/**
+ * Describes the message memory_v1.TemplateField.
+ * Use `create(TemplateFieldSchema)` to create a new message.
+ */
+export const TemplateFieldSchema: GenMessage<TemplateField> = /*@__PURE__*/
+  messageDesc(file_memory, 34);
+
+/**
+ * @generated from message memory_v1.MemoryTemplate
+ */
+export type MemoryTemplate = Message<"memory_v1.MemoryTemplate"> & {
+  /**
+   * @generated from field: memory_v1.MemoryKind kind = 1;
+   */
+  kind: MemoryKind;
+
+  /**
+   * @generated from field: repeated memory_v1.TemplateField fields = 2;
+   */
+  fields: TemplateField[];
+};
+
+/**
+ * Describes the message memory_v1.MemoryTemplate.
+ * Use `create(MemoryTemplateSchema)` to create a new message.
+ */
+export const MemoryTemplateSchema: GenMessage<MemoryTemplate> = /*@__PURE__*/
+  messageDesc(file_memory, 35);
+
+/**
+ * Kinds without a template take any fields.
+ *
+ * @generated from message memory_v1.TemplateListResponse
+ */
+export type TemplateListResponse = Message<"memory_v1.TemplateListResponse"> & {
+  /**
+   * @generated from field: repeated memory_v1.MemoryTemplate templates = 1;
+   */
+  templates: MemoryTemplate[];
+};
+
+/**
+ * Describes the message memory_v1.TemplateListResponse.
+ * Use `create(TemplateListResponseSchema)` to create a new message.
+ */
+export const TemplateListResponseSchema: GenMessage<TemplateListResponse> = /*@__PURE__*/
+  messageDesc(file_memory, 36);
+
+/**
+ * @generated from enum memory_v1.LifecycleState
+ */
+export enum LifecycleState {
+  /**
+   * @generated from enum value: LIFECYCLE_STATE_UNSPECIFIED = 0;
+   */
+  UNSPECIFIED = 0,
+
+  /**
+   * @generated from enum value: LIFECYCLE_STATE_ACTIVE = 1;
+   */
+  ACTIVE = 1,
+
+  /**
+   * @generated from enum value: LIFECYCLE_STATE_ARCHIVED = 2;
+   */
+  ARCHIVED = 2,
+
+  /**
+   * @generated from enum value: LIFECYCLE_STATE_DELETED = 3;
+   */
+  DELETED = 3,
+
+  /**
+   * @generated from enum value: LIFECYCLE_STATE_SUPERSEDED = 4;
+   */
+  SUPERSEDED = 4,
+
+  /**
+   * @generated from enum value: LIFECYCLE_STATE_SUPPRESSED = 5;
+   */
+  SUPPRESSED = 5,
+}
+
+/**
+ * Describes the enum memory_v1.LifecycleState.
+ */
+export const LifecycleStateSchema: GenEnum<LifecycleState> = /*@__PURE__*/
+  enumDesc(file_memory, 0);
+
+/**
+ * @generated from enum memory_v1.MemoryKind
+ */
+export enum MemoryKind {
+  /**
+   * @generated from enum value: MEMORY_KIND_UNSPECIFIED = 0;
+   */
+  UNSPECIFIED = 0,
+
+  /**
+   * @generated from enum value: MEMORY_KIND_SEMANTIC = 1;
+   */
+  SEMANTIC = 1,
+
+  /**
+   * @generated from enum value: MEMORY_KIND_EPISODIC = 2;
+   */
+  EPISODIC = 2,
+
+  /**
+   * @generated from enum value: MEMORY_KIND_PROCEDURAL = 3;
+   */
+  PROCEDURAL = 3,
+
+  /**
+   * @generated from enum value: MEMORY_KIND_INSTRUCTION = 4;
+   */
+  INSTRUCTION = 4,
+
+  /**
+   * @generated from enum value: MEMORY_KIND_RELATIONAL = 5;
+   */
+  RELATIONAL = 5,
+
+  /**
+   * @generated from enum value: MEMORY_KIND_WORKING = 6;
+   */
+  WORKING = 6,
+
+  /**
+   * @generated from enum value: MEMORY_KIND_PROSPECTIVE = 7;
+   */
+  PROSPECTIVE = 7,
+}
+
+/**
+ * Describes the enum memory_v1.MemoryKind.
+ */
+export const MemoryKindSchema: GenEnum<MemoryKind> = /*@__PURE__*/
+  enumDesc(file_memory, 1);
+
+/**
+ * @generated from enum memory_v1.ContextPolicy
+ */
+export enum ContextPolicy {
+  /**
+   * @generated from enum value: CONTEXT_POLICY_UNSPECIFIED = 0;
+   */
+  UNSPECIFIED = 0,
+
+  /**
+   * @generated from enum value: CONTEXT_POLICY_RELEVANCE = 1;
+   */
+  RELEVANCE = 1,
+
+  /**
+   * @generated from enum value: CONTEXT_POLICY_RECENCY = 2;
+   */
+  RECENCY = 2,
+
+  /**
+   * @generated from enum value: CONTEXT_POLICY_IMPORTANCE = 3;
+   */
+  IMPORTANCE = 3,
+}
+
+/**
+ * Describes the enum memory_v1.ContextPolicy.
+ */
+export const ContextPolicySchema: GenEnum<ContextPolicy> = /*@__PURE__*/
+  enumDesc(file_memory, 2);
+
+/**
+ * @generated from enum memory_v1.ProvenanceOrigin
+ */
+export enum ProvenanceOrigin {
+  /**
+   * @generated from enum value: PROVENANCE_ORIGIN_UNSPECIFIED = 0;
+   */
+  UNSPECIFIED = 0,
+
+  /**
+   * @generated from enum value: PROVENANCE_ORIGIN_USER = 1;
+   */
+  USER = 1,
+
+  /**
+   * @generated from enum value: PROVENANCE_ORIGIN_AGENT = 2;
+   */
+  AGENT = 2,
+}
+
+/**
+ * Describes the enum memory_v1.ProvenanceOrigin.
+ */
+export const ProvenanceOriginSchema: GenEnum<ProvenanceOrigin> = /*@__PURE__*/
+  enumDesc(file_memory, 3);
+
+/**
+ * @generated from service memory_v1.MemoryService
+ */
+export const MemoryService: GenService<{
+  /**
+   * @generated from rpc memory_v1.MemoryService.CreateMemory
+   */
+  createMemory: {
+    methodKind: "unary";
+    input: typeof CreateMemoryRequestSchema;
+    output: typeof CreateMemoryResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v1.MemoryService.DeleteMemory
+   */
+  deleteMemory: {
+    methodKind: "unary";
+    input: typeof DeleteMemoryRequestSchema;
+    output: typeof EmptySchema;
+  },
+  /**
+   * @generated from rpc memory_v1.MemoryService.GetMemory
+   */
+  getMemory: {
+    methodKind: "unary";
+    input: typeof GetMemoryRequestSchema;
+    output: typeof MemoryResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v1.MemoryService.ListMemories
+   */
+  listMemories: {
+    methodKind: "unary";
+    input: typeof ListMemoriesRequestSchema;
+    output: typeof MemoryListResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v1.MemoryService.SearchMemories
+   */
+  searchMemories: {
+    methodKind: "unary";
+    input: typeof SearchMemoriesRequestSchema;
+    output: typeof MemoryListResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v1.MemoryService.BuildContext
+   */
+  buildContext: {
+    methodKind: "unary";
+    input: typeof BuildContextRequestSchema;
+    output: typeof ContextPackResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v1.MemoryService.MarkUsed
+   */
+  markUsed: {
+    methodKind: "unary";
+    input: typeof MarkUsedRequestSchema;
+    output: typeof EmptySchema;
+  },
+  /**
+   * @generated from rpc memory_v1.MemoryService.Feedback
+   */
+  feedback: {
+    methodKind: "unary";
+    input: typeof FeedbackRequestSchema;
+    output: typeof EmptySchema;
+  },
+  /**
+   * @generated from rpc memory_v1.MemoryService.CreateMemories
+   */
+  createMemories: {
+    methodKind: "unary";
+    input: typeof CreateMemoriesRequestSchema;
+    output: typeof CreateMemoryResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v1.MemoryService.CreateStructuredMemory
+   */
+  createStructuredMemory: {
+    methodKind: "unary";
+    input: typeof CreateStructuredMemoryRequestSchema;
+    output: typeof CreateMemoryResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v1.MemoryService.ListTemplates
+   */
+  listTemplates: {
+    methodKind: "unary";
+    input: typeof ListTemplatesRequestSchema;
+    output: typeof TemplateListResponseSchema;
+  },
+}> = /*@__PURE__*/
+  serviceDesc(file_memory, 0);
@@ -0,0 +1,936 @@
+// @generated by protoc-gen-es v2.2.3 with parameter "target=ts,import_extension=js"
+// @generated from file memory_v2.proto (package memory_v2, syntax proto3)
+/* eslint-disable */
+
+import type { GenEnum, GenFile, GenMessage, GenService } from "@bufbuild/protobuf/codegenv1";
+import { enumDesc, fileDesc, messageDesc, serviceDesc } from "@bufbuild/protobuf/codegenv1";
+import { file_google_protobuf_empty } from "@bufbuild/protobuf/wkt";
+import { file_memory } from "./memory_pb.js";
+import type { Message } from "@bufbuild/protobuf";
+import type { BuildContextRequestSchema, ContextFilter, CreateMemoriesRequestSchema, CreateMemoryRequestSchema, CreateStructuredMemoryRequestSchema, DeleteMemoryRequestSchema, FacetCount, FeedbackRequestSchema, GetMemoryRequestSchema, LifecycleState, ListMemoriesRequestSchema, ListTemplatesRequestSchema, MarkUsedRequestSchema, MemoryContent, MemoryContext, MemoryFacets, MemoryKind, Provenance, SearchExplanation, SearchMemoriesRequestSchema, SearchTimings, TemplateListResponseSchema } from "./memory_pb.js";
+import type { EmptySchema } from "@bufbuild/protobuf/wkt";
+
+/**
+ * Describes the file memory_v2.proto.
+ */
+export const file_memory_v2: GenFile = /*@__PURE__*/
+  fileDesc("Cg9tZW1vcnlfdjIucHJvdG8SCW1lbW9yeV92MiLOAQoQVGVtcG9yYWxNZXRhZGF0YRISCgpjcmVhdGVkX2F0GAEgASgDEhcKCnVwZGF0ZWRfYXQYAiABKANIAIgBARIYCgthcmNoaXZlZF9hdBgDIAEoA0gBiAEBEhcKCmRlbGV0ZWRfYXQYBCABKANIAogBARIaCg1zdXBwcmVzc2VkX2F0GAUgASgDSAOIAQFCDQoLX3VwZGF0ZWRfYXRCDgoMX2FyY2hpdmVkX2F0Qg0KC19kZWxldGVkX2F0QhAKDl9zdXBwcmVzc2VkX2F0IksKDk1lbW9yeVNjaGVkdWxlEg4KBmR1ZV9hdBgBIAEoAxIpCgpyZWN1cnJlbmNlGAIgASgOMhUubWVtb3J5X3YyLlJlY3VycmVuY2UiywIKDk1lbW9yeU1ldGFkYXRhEhUKCGxhbmd1YWdlGAEgASgJSACIAQESHQoQb3JpZ2luYWxfc3VtbWFyeRgCIAEoCUgBiAEBEg8KB3NvdXJjZXMYAyADKAkSKwoIc2NoZWR1bGUYBCABKAsyGS5tZW1vcnlfdjIuTWVtb3J5U2NoZWR1bGUSEQoJdXNlX2NvdW50GAUgASgNEhkKDGxhc3RfdXNlZF9hdBgGIAEoA0gCiAEBEjUKBmZpZWxkcxgHIAMoCzIlLm1lbW9yeV92Mi5NZW1vcnlNZXRhZGF0YS5GaWVsZHNFbnRyeRotCgtGaWVsZHNFbnRyeRILCgNrZXkYASABKAkSDQoFdmFsdWUYAiABKAk6AjgBQgsKCV9sYW5ndWFnZUITChFfb3JpZ2luYWxfc3VtbWFyeUIPCg1fbGFzdF91c2VkX2F0IkoKDk1lbW9yeVJlbGF0aW9uEiUKBGtpbmQYASABKA4yFy5tZW1vcnlfdjIuUmVsYXRpb25LaW5kEhEKCW1lbW9yeV9pZBgCIAEoCSJCCgxNZW1vcnlTY29yZXMSEQoJY2VydGFpbnR5GAEgASgCEhAKCHNhbGllbmNlGAIgASgCEg0KBXNjb3JlGAMgASgCIuUDCgZNZW1vcnkSCgoCaWQYASABKAkSKQoHY29udGV4dBgCIAEoCzIYLm1lbW9yeV92MS5NZW1vcnlDb250ZXh0EiwKCWxpZmVjeWNsZRgDIAEoDjIZLm1lbW9yeV92MS5MaWZlY3ljbGVTdGF0ZRIjCgRraW5kGAQgASgOMhUubWVtb3J5X3YxLk1lbW9yeUtpbmQSKQoHY29udGVudBgFIAEoCzIYLm1lbW9yeV92MS5NZW1vcnlDb250ZW50Ei0KCHRlbXBvcmFsGAcgASgLMhsubWVtb3J5X3YyLlRlbXBvcmFsTWV0YWRhdGESKQoKcHJvdmVuYW5jZRgIIAEoCzIVLm1lbW9yeV92MS5Qcm92ZW5hbmNlEisKCG1ldGFkYXRhGAsgASgLMhkubWVtb3J5X3YyLk1lbW9yeU1ldGFkYXRhEiwKCXJlbGF0aW9ucxgMIAMoCzIZLm1lbW9yeV92Mi5NZW1vcnlSZWxhdGlvbhInCgZzY29yZXMYDSABKAsyFy5tZW1vcnlfdjIuTWVtb3J5U2NvcmVzEiUKBXNjb3BlGA4gASgOMhYubWVtb3J5X3YyLk1lbW9yeVNjb3BlEg8KB3ZlcnNpb24YDyABKARKBAgGEAdKBAgJEApKBAgKEAsiQgoVR2V0TWVtb3J5U3RhdHNSZXF1ZXN0EikKB2NvbnRleHQYASABKAsyGC5tZW1vcnlfdjEuQ29udGV4dEZpbHRlciJZCg9TYXZlVmlld1JlcXVlc3QSKQoHY29udGV4dBgBIAEoCzIYLm1lbW9yeV92MS5Db250ZXh0RmlsdGVyEgwKBG5hbWUYAiABKAkSDQoFcXVlcnkYAyABKAkiPQoQTGlzdFZpZXdzUmVxdWVzdBIpCgdjb250ZXh0GAEgASgLMhgubWVtb3J5X3YxLkNvbnRleHRGaWx0ZXIiWgoOUnVuVmlld1JlcXVlc3QSKQoHY29udGV4dBgBIAEoCzIYLm1lbW9yeV92MS5Db250ZXh0RmlsdGVyEgwKBG5hbWUYAiABKAkSDwoHZXhwbGFpbhgDIAEoCCJMChFEZWxldGVWaWV3UmVxdWVzdBIpCgdjb250ZXh0GAEgASgLMhgubWVtb3J5X3YxLkNvbnRleHRGaWx0ZXISDAoEbmFtZRgCIAEoCSJmChNVcGRhdGVNZW1vcnlSZXF1ZXN0EgoKAmlkGAEgASgJEg8KB3N1bW1hcnkYAiABKAkSHQoQZXhwZWN0ZWRfdmVyc2lvbhgDIAEoBEgAiAEBQhMKEV9leHBlY3RlZF92ZXJzaW9uIk0KEUdldENoYW5nZXNSZXF1ZXN0EikKB2NvbnRleHQYASABKAsyGC5tZW1vcnlfdjEuQ29udGV4dEZpbHRlchINCgVzaW5jZRgCIAEoAyIzCg5NZW1vcnlSZXNwb25zZRIhCgZtZW1vcnkYASABKAsyES5tZW1vcnlfdjIuTWVtb3J5IksKFENyZWF0ZU1lbW9yeVJlc3BvbnNlEiMKCG1lbW9yaWVzGAEgAygLMhEubWVtb3J5X3YyLk1lbW9yeRIOCgZxdWV1ZWQYAiABKAgi6wEKEk1lbW9yeUxpc3RSZXNwb25zZRIjCghtZW1vcmllcxgBIAMoCzIRLm1lbW9yeV92Mi5NZW1vcnkSJwoGZmFjZXRzGAIgASgLMhcubWVtb3J5X3YxLk1lbW9yeUZhY2V0cxIWCg5yZXJhbmtfc2tpcHBlZBgDIAEoCBIRCgl0aW1lZF9vdXQYBCABKAgSKQoHdGltaW5ncxgFIAEoCzIYLm1lbW9yeV92MS5TZWFyY2hUaW1pbmdzEjEKC2V4cGxhbmF0aW9uGAYgASgLMhwubWVtb3J5X3YxLlNlYXJjaEV4cGxhbmF0aW9uIlgKE0NvbnRleHRQYWNrUmVzcG9uc2USDAoEdGV4dBgBIAEoCRIOCgZ0b2tlbnMYAiABKA0SIwoIbWVtb3JpZXMYAyADKAsyES5tZW1vcnlfdjIuTWVtb3J5IucBChNNZW1vcnlTdGF0c1Jlc3BvbnNlEg0KBXRvdGFsGAEgASgEEiQKBWtpbmRzGAIgAygLMhUubWVtb3J5X3YxLkZhY2V0Q291bnQSKQoKbGlmZWN5Y2xlcxgDIAMoCzIVLm1lbW9yeV92MS5GYWNldENvdW50EhUKDXN0b3JhZ2VfYnl0ZXMYBCABKAQSJQoGbW9udGhzGAUgAygLMhUubWVtb3J5X3YxLkZhY2V0Q291bnQSHQoQbGFzdF9hY3Rpdml0eV9hdBgGIAEoA0gAiAEBQhMKEV9sYXN0X2FjdGl2aXR5X2F0IlAKCVNhdmVkVmlldxIMCgRuYW1lGAEgASgJEg0KBXF1ZXJ5GAIgASgJEhIKCmNyZWF0ZWRfYXQYAyABKAMSEgoKdXBkYXRlZF9hdBgEIAEoAyI3ChBWaWV3TGlzdFJlc3BvbnNlEiMKBXZpZXdzGAEgAygLMhQubWVtb3J5X3YyLlNhdmVkVmlldyJFCg9DaGFuZ2VzUmVzcG9uc2USIwoIbWVtb3JpZXMYASADKAsyES5tZW1vcnlfdjIuTWVtb3J5Eg0KBXVudGlsGAIgASgDKm0KDFJlbGF0aW9uS2luZBIdChlSRUxBVElPTl9LSU5EX1VOU1BFQ0lGSUVEEAASHQoZUkVMQVRJT05fS0lORF9DT05UUkFESUNUUxABEh8KG1JFTEFUSU9OX0tJTkRfU1VQRVJTRURFRF9CWRACKoQBCgpSZWN1cnJlbmNlEhoKFlJFQ1VSUkVOQ0VfVU5TUEVDSUZJRUQQABIUChBSRUNVUlJFTkNFX0RBSUxZEAESFQoRUkVDVVJSRU5DRV9XRUVLTFkQAhIWChJSRUNVUlJFTkNFX01PTlRITFkQAxIVChFSRUNVUlJFTkNFX1lFQVJMWRAEKlwKC01lbW9yeVNjb3BlEhwKGE1FTU9SWV9TQ09QRV9VTlNQRUNJRklFRBAAEhkKFU1FTU9SWV9TQ09QRV9QRVJTT05BTBABEhQKEE1FTU9SWV9TQ09QRV9PUkcQAjLhCgoNTWVtb3J5U2VydmljZRJPCgxDcmVhdGVNZW1vcnkSHi5tZW1vcnlfdjEuQ3JlYXRlTWVtb3J5UmVxdWVzdBofLm1lbW9yeV92Mi5DcmVhdGVNZW1vcnlSZXNwb25zZRJGCgxEZWxldGVNZW1vcnkSHi5tZW1vcnlfdjEuRGVsZXRlTWVtb3J5UmVxdWVzdBoWLmdvb2dsZS5wcm90b2J1Zi5FbXB0eRJDCglHZXRNZW1vcnkSGy5tZW1vcnlfdjEuR2V0TWVtb3J5UmVxdWVzdBoZLm1lbW9yeV92Mi5NZW1vcnlSZXNwb25zZRJNCgxMaXN0TWVtb3JpZXMSHi5tZW1vcnlfdjEuTGlzdE1lbW9yaWVzUmVxdWVzdBodLm1lbW9yeV92Mi5NZW1vcnlMaXN0UmVzcG9uc2USUQoOU2VhcmNoTWVtb3JpZXMSIC5tZW1vcnlfdjEuU2VhcmNoTWVtb3JpZXNSZXF1ZXN0Gh0ubWVtb3J5X3YyLk1lbW9yeUxpc3RSZXNwb25zZRJOCgxCdWlsZENvbnRleHQSHi5tZW1vcnlfdjEuQnVpbGRDb250ZXh0UmVxdWVzdBoeLm1lbW9yeV92Mi5Db250ZXh0UGFja1Jlc3BvbnNlEj4KCE1hcmtVc2VkEhoubWVtb3J5X3YxLk1hcmtVc2VkUmVxdWVzdBoWLmdvb2dsZS5wcm90b2J1Zi5FbXB0eRI+CghGZWVkYmFjaxIaLm1lbW9yeV92MS5GZWVkYmFja1JlcXVlc3QaFi5nb29nbGUucHJvdG9idWYuRW1wdHkSUwoOQ3JlYXRlTWVtb3JpZXMSIC5tZW1vcnlfdjEuQ3JlYXRlTWVtb3JpZXNSZXF1ZXN0Gh8ubWVtb3J5X3YyLkNyZWF0ZU1lbW9yeVJlc3BvbnNlEmMKFkNyZWF0ZVN0cnVjdHVyZWRNZW1vcnkSKC5tZW1vcnlfdjEuQ3JlYXRlU3RydWN0dXJlZE1lbW9yeVJlcXVlc3QaHy5tZW1vcnlfdjIuQ3JlYXRlTWVtb3J5UmVzcG9uc2USUQoNTGlzdFRlbXBsYXRlcxIfLm1lbW9yeV92MS5MaXN0VGVtcGxhdGVzUmVxdWVzdBofLm1lbW9yeV92MS5UZW1wbGF0ZUxpc3RSZXNwb25zZRJSCg5HZXRNZW1vcnlTdGF0cxIgLm1lbW9yeV92Mi5HZXRNZW1vcnlTdGF0c1JlcXVlc3QaHi5tZW1vcnlfdjIuTWVtb3J5U3RhdHNSZXNwb25zZRI8CghTYXZlVmlldxIaLm1lbW9yeV92Mi5TYXZlVmlld1JlcXVlc3QaFC5tZW1vcnlfdjIuU2F2ZWRWaWV3EkUKCUxpc3RWaWV3cxIbLm1lbW9yeV92Mi5MaXN0Vmlld3NSZXF1ZXN0GhsubWVtb3J5X3YyLlZpZXdMaXN0UmVzcG9uc2USQwoHUnVuVmlldxIZLm1lbW9yeV92Mi5SdW5WaWV3UmVxdWVzdBodLm1lbW9yeV92Mi5NZW1vcnlMaXN0UmVzcG9uc2USQgoKRGVsZXRlVmlldxIcLm1lbW9yeV92Mi5EZWxldGVWaWV3UmVxdWVzdBoWLmdvb2dsZS5wcm90b2J1Zi5FbXB0eRJGCgpHZXRDaGFuZ2VzEhwubWVtb3J5X3YyLkdldENoYW5nZXNSZXF1ZXN0GhoubWVtb3J5X3YyLkNoYW5nZXNSZXNwb25zZRJJCgxVcGRhdGVNZW1vcnkSHi5tZW1vcnlfdjIuVXBkYXRlTWVtb3J5UmVxdWVzdBoZLm1lbW9yeV92Mi5NZW1vcnlSZXNwb25zZWIGcHJvdG8z", [file_google_protobuf_empty, file_memory]);
+
+/**
+ * Fields 1-3 match memory_v1.TemporalMetadata.
+ *
+ * @generated from message memory_v2.TemporalMetadata
+ */
+export type TemporalMetadata = Message<"memory_v2.TemporalMetadata"> & {
+  /**
+   * @generated from field: int64 created_at = 1;
+   */
+  createdAt: bigint;
+
+  /**
+   * @generated from field: optional int64 updated_at = 2;
+   */
+  updatedAt?: bigint;
+
+  /**
+   * @generated from field: optional int64 archived_at = 3;
+   */
+  archivedAt?: bigint;
+
+  /**
+   * @generated from field: optional int64 deleted_at = 4;
+   */
+  deletedAt?: bigint;
+
+  /**
+   * @generated from field: optional int64 suppressed_at = 5;
+   */
+  suppressedAt?: bigint;
+};
+
+/**
+ * Describes the message memory_v2.TemporalMetadata.
+ * Use `create(TemporalMetadataSchema)` to create a new message.
+ */
+export const TemporalMetadataSchema: GenMessage<TemporalMetadata> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 0);
+
+/**
+ * @generated from message memory_v2.MemorySchedule
+ */
+export type MemorySchedule = Message<"memory_v2.MemorySchedule"> & {
+  /**
+   * @generated from field: int64 due_at = 1;
+   */
+  dueAt: bigint;
+
+  /**
+   * @generated from field: memory_v2.Recurrence recurrence = 2;
+   */
+  recurrence: Recurrence;
+};
+
+/**
+ * Describes the message memory_v2.MemorySchedule.
+ * Use `create(MemoryScheduleSchema)` to create a new message.
+ */
+export const MemoryScheduleSchema: GenMessage<MemorySchedule> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 1);
+
+/**
+ * @generated from message memory_v2.MemoryMetadata
+ */
+export type MemoryMetadata = Message<"memory_v2.MemoryMetadata"> & {
+  /**
+   * Language the summary was written in, when it was detected.
+   *
+   * @generated from field: optional string language = 1;
+   */
+  language?: string;
+
+  /**
+   * The summary before it was translated, when it was.
+   *
+   * @generated from field: optional string original_summary = 2;
+   */
+  originalSummary?: string;
+
+  /**
+   * @generated from field: repeated string sources = 3;
+   */
+  sources: string[];
+
+  /**
+   * @generated from field: memory_v2.MemorySchedule schedule = 4;
+   */
+  schedule?: MemorySchedule;
+
+  /**
+   * @generated from field: uint32 use_count = 5;
+   */
+  useCount: number;
+
+  /**
+   * @generated from field: optional int64 last_used_at = 6;
+   */
+  lastUsedAt?: bigint;
+
+  /**
+   * Values of the kind's template fields; the schedule holds due_at.
+   *
+   * @generated from field: map<string, string> fields = 7;
+   */
+  fields: { [key: string]: string };
+};
+
+/**
+ * Describes the message memory_v2.MemoryMetadata.
+ * Use `create(MemoryMetadataSchema)` to create a new message.
+ */
+export const MemoryMetadataSchema: GenMessage<MemoryMetadata> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 2);
+
+/**
+ * @generated from message memory_v2.MemoryRelation
+ */
+export type MemoryRelation = Message<"memory_v2.MemoryRelation"> & {
+  /**
+   * @generated from field: memory_v2.RelationKind kind = 1;
+   */
+  kind: RelationKind;
+
+  /**
+   * @generated from field: string memory_id = 2;
+   */
+  memoryId: string;
+};
+
+/**
+ * Describes the message memory_v2.MemoryRelation.
+ * Use `create(MemoryRelationSchema)` to create a new message.
+ */
+export const MemoryRelationSchema: GenMessage<MemoryRelation> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 3);
+
+/**
+ * Scores are in the range [0, 1], unlike the percentages of memory_v1.MemorySignals.
+ *
+ * @generated from message memory_v2.MemoryScores
+ */
+export type MemoryScores = Message<"memory_v2.MemoryScores"> & {
+  /**
+   * @generated from field: float certainty = 1;
+   */
+  certainty: number;
+
+  /**
+   * @generated from field: float salience = 2;
+   */
+  salience: number;
+
+  /**
+   * What memories are ranked by outside of a search: certainty * salience.
+   *
+   * @generated from field: float score = 3;
+   */
+  score: number;
+};
+
+/**
+ * Describes the message memory_v2.MemoryScores.
+ * Use `create(MemoryScoresSchema)` to create a new message.
+ */
+export const MemoryScoresSchema: GenMessage<MemoryScores> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 4);
+
+/**
+ * @generated from message memory_v2.Memory
+ */
+export type Memory = Message<"memory_v2.Memory"> & {
+  /**
+   * @generated from field: string id = 1;
+   */
+  id: string;
+
+  /**
+   * @generated from field: memory_v1.MemoryContext context = 2;
+   */
+  context?: MemoryContext;
+
+  /**
+   * @generated from field: memory_v1.LifecycleState lifecycle = 3;
+   */
+  lifecycle: LifecycleState;
+
+  /**
+   * @generated from field: memory_v1.MemoryKind kind = 4;
+   */
+  kind: MemoryKind;
+
+  /**
+   * @generated from field: memory_v1.MemoryContent content = 5;
+   */
+  content?: MemoryContent;
+
+  /**
+   * @generated from field: memory_v2.TemporalMetadata temporal = 7;
+   */
+  temporal?: TemporalMetadata;
+
+  /**
+   * @generated from field: memory_v1.Provenance provenance = 8;
+   */
+  provenance?: Provenance;
+
+  /**
+   * @generated from field: memory_v2.MemoryMetadata metadata = 11;
+   */
+  metadata?: MemoryMetadata;
+
+  /**
+   * @generated from field: repeated memory_v2.MemoryRelation relations = 12;
+   */
+  relations: MemoryRelation[];
+
+  /**
+   * @generated from field: memory_v2.MemoryScores scores = 13;
+   */
+  scores?: MemoryScores;
+
+  /**
+   * @generated from field: memory_v2.MemoryScope scope = 14;
+   */
+  scope: MemoryScope;
+
+  /**
+   * Bumped by every stored change; pass it as UpdateMemoryRequest.expected_version.
+   *
+   * @generated from field: uint64 version = 15;
+   */
+  version: bigint;
+};
+
+/**
+ * Describes the message memory_v2.Memory.
+ * Use `create(MemorySchema)` to create a new message.
+ */
+export const MemorySchema: GenMessage<Memory> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 5);
+
+/**
+ * @generated from message memory_v2.GetMemoryStatsRequest
+ */
+export type GetMemoryStatsRequest = Message<"memory_v2.GetMemoryStatsRequest"> & {
+  /**
+   * @generated from field: memory_v1.ContextFilter context = 1;
+   */
+  context?: ContextFilter;
+};
+
+/**
+ * Describes the message memory_v2.GetMemoryStatsRequest.
+ * Use `create(GetMemoryStatsRequestSchema)` to create a new message.
+ */
+export const GetMemoryStatsRequestSchema: GenMessage<GetMemoryStatsRequest> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 6);
+
+/**
+ * Saves query, written in the query language (e.g. `kind:procedural tag:deploy rollback`),
+ * as the view name of context, replacing an earlier view of that name.
+ *
+ * @generated from message memory_v2.SaveViewRequest
+ */
+export type SaveViewRequest = Message<"memory_v2.SaveViewRequest"> & {
+  /**
+   * @generated from field: memory_v1.ContextFilter context = 1;
+   */
+  context?: ContextFilter;
+
+  /**
+   * @generated from field: string name = 2;
+   */
+  name: string;
+
+  /**
+   * @generated from field: string query = 3;
+   */
+  query: string;
+};
+
+/**
+ * Describes the message memory_v2.SaveViewRequest.
+ * Use `create(SaveViewRequestSchema)` to create a new message.
+ */
+export const SaveViewRequestSchema: GenMessage<SaveViewRequest> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 7);
+
+/**
+ * @generated from message memory_v2.ListViewsRequest
+ */
+export type ListViewsRequest = Message<"memory_v2.ListViewsRequest"> & {
+  /**
+   * @generated from field: memory_v1.ContextFilter context = 1;
+   */
+  context?: ContextFilter;
+};
+
+/**
+ * Describes the message memory_v2.ListViewsRequest.
+ * Use `create(ListViewsRequestSchema)` to create a new message.
+ */
+export const ListViewsRequestSchema: GenMessage<ListViewsRequest> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 8);
+
+/**
+ * @generated from message memory_v2.RunViewRequest
+ */
+export type RunViewRequest = Message<"memory_v2.RunViewRequest"> & {
+  /**
+   * @generated from field: memory_v1.ContextFilter context = 1;
+   */
+  context?: ContextFilter;
+
+  /**
+   * @generated from field: string name = 2;
+   */
+  name: string;
+
+  /**
+   * Also return how the results were found, as for SearchMemories.
+   *
+   * @generated from field: bool explain = 3;
+   */
+  explain: boolean;
+};
+
+/**
+ * Describes the message memory_v2.RunViewRequest.
+ * Use `create(RunViewRequestSchema)` to create a new message.
+ */
+export const RunViewRequestSchema: GenMessage<RunViewRequest> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 9);
+
+/**
+ * @generated from message memory_v2.DeleteViewRequest
+ */
+export type DeleteViewRequest = Message<"memory_v2.DeleteViewRequest"> & {
+  /**
+   * @generated from field: memory_v1.ContextFilter context = 1;
+   */
+  context?: ContextFilter;
+
+  /**
+   * @generated from field: string name = 2;
+   */
+  name: string;
+};
+
+/**
+ * Describes the message memory_v2.DeleteViewRequest.
+ * Use `create(DeleteViewRequestSchema)` to create a new message.
+ */
+export const DeleteViewRequestSchema: GenMessage<DeleteViewRequest> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 10);
+
+/**
+ * Replaces the memory's summary, keeping its tags, and embeds it again.
+ *
+ * @generated from message memory_v2.UpdateMemoryRequest
+ */
+export type UpdateMemoryRequest = Message<"memory_v2.UpdateMemoryRequest"> & {
+  /**
+   * @generated from field: string id = 1;
+   */
+  id: string;
+
+  /**
+   * @generated from field: string summary = 2;
+   */
+  summary: string;
+
+  /**
+   * When set, the update fails with ABORTED unless the memory is still at this version.
+   *
+   * @generated from field: optional uint64 expected_version = 3;
+   */
+  expectedVersion?: bigint;
+};
+
+/**
+ * Describes the message memory_v2.UpdateMemoryRequest.
+ * Use `create(UpdateMemoryRequestSchema)` to create a new message.
+ */
+export const UpdateMemoryRequestSchema: GenMessage<UpdateMemoryRequest> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 11);
+
+/**
+ * Memories of context created, updated, archived, deleted or suppressed after since.
+ *
+ * @generated from message memory_v2.GetChangesRequest
+ */
+export type GetChangesRequest = Message<"memory_v2.GetChangesRequest"> & {
+  /**
+   * @generated from field: memory_v1.ContextFilter context = 1;
+   */
+  context?: ContextFilter;
+
+  /**
+   * Unix seconds. 0 returns every memory; afterwards pass the previous response's until.
+   *
+   * @generated from field: int64 since = 2;
+   */
+  since: bigint;
+};
+
+/**
+ * Describes the message memory_v2.GetChangesRequest.
+ * Use `create(GetChangesRequestSchema)` to create a new message.
+ */
+export const GetChangesRequestSchema: GenMessage<GetChangesRequest> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 12);
+
+/**
+ * @generated from message memory_v2.MemoryResponse
+ */
+export type MemoryResponse = Message<"memory_v2.MemoryResponse"> & {
+  /**
+   * @generated from field: memory_v2.Memory memory = 1;
+   */
+  memory?: Memory;
+};
+
+/**
+ * Describes the message memory_v2.MemoryResponse.
+ * Use `create(MemoryResponseSchema)` to create a new message.
+ */
+export const MemoryResponseSchema: GenMessage<MemoryResponse> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 13);
+
+/**
+ * The memories a create call stored, with their generated ids, summaries and kinds.
+ *
+ * @generated from message memory_v2.CreateMemoryResponse
+ */
+export type CreateMemoryResponse = Message<"memory_v2.CreateMemoryResponse"> & {
+  /**
+   * @generated from field: repeated memory_v2.Memory memories = 1;
+   */
+  memories: Memory[];
+
+  /**
+   * Set when the requests were journaled instead; memories is then empty and the
+   * memories become searchable after the next journal flush.
+   *
+   * @generated from field: bool queued = 2;
+   */
+  queued: boolean;
+};
+
+/**
+ * Describes the message memory_v2.CreateMemoryResponse.
+ * Use `create(CreateMemoryResponseSchema)` to create a new message.
+ */
+export const CreateMemoryResponseSchema: GenMessage<CreateMemoryResponse> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 14);
+
+/**
+ * @generated from message memory_v2.MemoryListResponse
+ */
+export type MemoryListResponse = Message<"memory_v2.MemoryListResponse"> & {
+  /**
+   * @generated from field: repeated memory_v2.Memory memories = 1;
+   */
+  memories: Memory[];
+
+  /**
+   * Set only when include_facets was requested.
+   *
+   * @generated from field: memory_v1.MemoryFacets facets = 2;
+   */
+  facets?: MemoryFacets;
+
+  /**
+   * Set when the reranker failed and memories are in vector-similarity order.
+   *
+   * @generated from field: bool rerank_skipped = 3;
+   */
+  rerankSkipped: boolean;
+
+  /**
+   * Set when the search deadline ran out and memories are what was found in time.
+   *
+   * @generated from field: bool timed_out = 4;
+   */
+  timedOut: boolean;
+
+  /**
+   * Server-side time per search stage. Not set for listings.
+   *
+   * @generated from field: memory_v1.SearchTimings timings = 5;
+   */
+  timings?: SearchTimings;
+
+  /**
+   * Set only when explain was requested.
+   *
+   * @generated from field: memory_v1.SearchExplanation explanation = 6;
+   */
+  explanation?: SearchExplanation;
+};
+
+/**
+ * Describes the message memory_v2.MemoryListResponse.
+ * Use `create(MemoryListResponseSchema)` to create a new message.
+ */
+export const MemoryListResponseSchema: GenMessage<MemoryListResponse> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 15);
+
+/**
+ * @generated from message memory_v2.ContextPackResponse
+ */
+export type ContextPackResponse = Message<"memory_v2.ContextPackResponse"> & {
+  /**
+   * @generated from field: string text = 1;
+   */
+  text: string;
+
+  /**
+   * @generated from field: uint32 tokens = 2;
+   */
+  tokens: number;
+
+  /**
+   * @generated from field: repeated memory_v2.Memory memories = 3;
+   */
+  memories: Memory[];
+};
+
+/**
+ * Describes the message memory_v2.ContextPackResponse.
+ * Use `create(ContextPackResponseSchema)` to create a new message.
+ */
+export const ContextPackResponseSchema: GenMessage<ContextPackResponse> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 16);
+
+/**
+ * What is stored for a context. Deleted and suppressed memories are not counted.
+ *
+ * @generated from message memory_v2.MemoryStatsResponse
+ */
+export type MemoryStatsResponse = Message<"memory_v2.MemoryStatsResponse"> & {
+  /**
+   * @generated from field: uint64 total = 1;
+   */
+  total: bigint;
+
+  /**
+   * Most frequent first.
+   *
+   * @generated from field: repeated memory_v1.FacetCount kinds = 2;
+   */
+  kinds: FacetCount[];
+
+  /**
+   * Most frequent first.
+   *
+   * @generated from field: repeated memory_v1.FacetCount lifecycles = 3;
+   */
+  lifecycles: FacetCount[];
+
+  /**
+   * Size of the stored memories, not counting embeddings or blob store content.
+   *
+   * @generated from field: uint64 storage_bytes = 4;
+   */
+  storageBytes: bigint;
+
+  /**
+   * Memories created per month as YYYY-MM (UTC), newest first.
+   *
+   * @generated from field: repeated memory_v1.FacetCount months = 5;
+   */
+  months: FacetCount[];
+
+  /**
+   * When a memory was last created, updated, archived or used.
+   *
+   * @generated from field: optional int64 last_activity_at = 6;
+   */
+  lastActivityAt?: bigint;
+};
+
+/**
+ * Describes the message memory_v2.MemoryStatsResponse.
+ * Use `create(MemoryStatsResponseSchema)` to create a new message.
+ */
+export const MemoryStatsResponseSchema: GenMessage<MemoryStatsResponse> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 17);
+
+/**
+ * A named search saved for a context.
+ *
+ * @generated from message memory_v2.SavedView
+ */
+export type SavedView = Message<"memory_v2.SavedView"> & {
+  /**
+   * @generated from field: string name = 1;
+   */
+  name: string;
+
+  /**
+   * @generated from field: string query = 2;
+   */
+  query: string;
+
+  /**
+   * @generated from field: int64 created_at = 3;
+   */
+  createdAt: bigint;
+
+  /**
+   * @generated from field: int64 updated_at = 4;
+   */
+  updatedAt: bigint;
+};
+
+/**
+ * Describes the message memory_v2.SavedView.
+ * Use `create(SavedViewSchema)` to create a new message.
+ */
+export const SavedViewSchema: GenMessage<SavedView> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 18);
+
+/**
+ * Sorted by name.
+ *
+ * @generated from message memory_v2.ViewListResponse
+ */
+export type ViewListResponse = Message<"memory_v2.ViewListResponse"> & {
+  /**
+   * @generated from field: repeated memory_v2.SavedView views = 1;
+   */
+  views: SavedView[];
+};
+
+/**
+ * Describes the message memory_v2.ViewListResponse.
+ * Use `create(ViewListResponseSchema)` to create a new message.
+ */
+export const ViewListResponseSchema: GenMessage<ViewListResponse> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 19);
+
+/**
+ * @generated from message memory_v2.ChangesResponse
+ */
+export type ChangesResponse = Message<"memory_v2.ChangesResponse"> & {
+  /**
+   * Deleted and suppressed memories carry temporal.deleted_at or temporal.suppressed_at,
+   * so caches can drop them.
+   *
+   * @generated from field: repeated memory_v2.Memory memories = 1;
+   */
+  memories: Memory[];
+
+  /**
+   * Pass as since next time. Memories changed during that second may be returned again.
+   *
+   * @generated from field: int64 until = 2;
+   */
+  until: bigint;
+};
+
+/**
+ * Describes the message memory_v2.ChangesResponse.
+ * Use `create(ChangesResponseSchema)` to create a new message.
+ */
+export const ChangesResponseSchema: GenMessage<ChangesResponse> = /*@__PURE__*/
+  messageDesc(file_memory_v2, 20);
+
+/**
+ * @generated from enum memory_v2.RelationKind
+ */
+export enum RelationKind {
+  /**
+   * @generated from enum value: RELATION_KIND_UNSPECIFIED = 0;
+   */
+  UNSPECIFIED = 0,
+
+  /**
+   * The related memory is a stored memory this one contradicts.
+   *
+   * @generated from enum value: RELATION_KIND_CONTRADICTS = 1;
+   */
+  CONTRADICTS = 1,
+
+  /**
+   * The related memory replaced this one.
+   *
+   * @generated from enum value: RELATION_KIND_SUPERSEDED_BY = 2;
+   */
+  SUPERSEDED_BY = 2,
+}
+
+/**
+ * Describes the enum memory_v2.RelationKind.
+ */
+export const RelationKindSchema: GenEnum<RelationKind> = /*@__PURE__*/
+  enumDesc(file_memory_v2, 0);
+
+/**
+ * @generated from enum memory_v2.Recurrence
+ */
+export enum Recurrence {
+  /**
+   * A one-off schedule.
+   *
+   * @generated from enum value: RECURRENCE_UNSPECIFIED = 0;
+   */
+  UNSPECIFIED = 0,
+
+  /**
+   * @generated from enum value: RECURRENCE_DAILY = 1;
+   */
+  DAILY = 1,
+
+  /**
+   * @generated from enum value: RECURRENCE_WEEKLY = 2;
+   */
+  WEEKLY = 2,
+
+  /**
+   * @generated from enum value: RECURRENCE_MONTHLY = 3;
+   */
+  MONTHLY = 3,
+
+  /**
+   * @generated from enum value: RECURRENCE_YEARLY = 4;
+   */
+  YEARLY = 4,
+}
+
+/**
+ * Describes the enum memory_v2.Recurrence.
+ */
+export const RecurrenceSchema: GenEnum<Recurrence> = /*@__PURE__*/
+  enumDesc(file_memory_v2, 1);
+
+/**
+ * @generated from enum memory_v2.MemoryScope
+ */
+export enum MemoryScope {
+  /**
+   * @generated from enum value: MEMORY_SCOPE_UNSPECIFIED = 0;
+   */
+  UNSPECIFIED = 0,
+
+  /**
+   * Stored for a user, agent or run.
+   *
+   * @generated from enum value: MEMORY_SCOPE_PERSONAL = 1;
+   */
+  PERSONAL = 1,
+
+  /**
+   * Published to the pool of the organization in context.org_id.
+   *
+   * @generated from enum value: MEMORY_SCOPE_ORG = 2;
+   */
+  ORG = 2,
+}
+
+/**
+ * Describes the enum memory_v2.MemoryScope.
+ */
+export const MemoryScopeSchema: GenEnum<MemoryScope> = /*@__PURE__*/
+  enumDesc(file_memory_v2, 2);
+
+/**
+ * @generated from service memory_v2.MemoryService
+ */
+export const MemoryService: GenService<{
+  /**
+   * @generated from rpc memory_v2.MemoryService.CreateMemory
+   */
+  createMemory: {
+    methodKind: "unary";
+    input: typeof CreateMemoryRequestSchema;
+    output: typeof CreateMemoryResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.DeleteMemory
+   */
+  deleteMemory: {
+    methodKind: "unary";
+    input: typeof DeleteMemoryRequestSchema;
+    output: typeof EmptySchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.GetMemory
+   */
+  getMemory: {
+    methodKind: "unary";
+    input: typeof GetMemoryRequestSchema;
+    output: typeof MemoryResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.ListMemories
+   */
+  listMemories: {
+    methodKind: "unary";
+    input: typeof ListMemoriesRequestSchema;
+    output: typeof MemoryListResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.SearchMemories
+   */
+  searchMemories: {
+    methodKind: "unary";
+    input: typeof SearchMemoriesRequestSchema;
+    output: typeof MemoryListResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.BuildContext
+   */
+  buildContext: {
+    methodKind: "unary";
+    input: typeof BuildContextRequestSchema;
+    output: typeof ContextPackResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.MarkUsed
+   */
+  markUsed: {
+    methodKind: "unary";
+    input: typeof MarkUsedRequestSchema;
+    output: typeof EmptySchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.Feedback
+   */
+  feedback: {
+    methodKind: "unary";
+    input: typeof FeedbackRequestSchema;
+    output: typeof EmptySchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.CreateMemories
+   */
+  createMemories: {
+    methodKind: "unary";
+    input: typeof CreateMemoriesRequestSchema;
+    output: typeof CreateMemoryResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.CreateStructuredMemory
+   */
+  createStructuredMemory: {
+    methodKind: "unary";
+    input: typeof CreateStructuredMemoryRequestSchema;
+    output: typeof CreateMemoryResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.ListTemplates
+   */
+  listTemplates: {
+    methodKind: "unary";
+    input: typeof ListTemplatesRequestSchema;
+    output: typeof TemplateListResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.GetMemoryStats
+   */
+  getMemoryStats: {
+    methodKind: "unary";
+    input: typeof GetMemoryStatsRequestSchema;
+    output: typeof MemoryStatsResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.SaveView
+   */
+  saveView: {
+    methodKind: "unary";
+    input: typeof SaveViewRequestSchema;
+    output: typeof SavedViewSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.ListViews
+   */
+  listViews: {
+    methodKind: "unary";
+    input: typeof ListViewsRequestSchema;
+    output: typeof ViewListResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.RunView
+   */
+  runView: {
+    methodKind: "unary";
+    input: typeof RunViewRequestSchema;
+    output: typeof MemoryListResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.DeleteView
+   */
+  deleteView: {
+    methodKind: "unary";
+    input: typeof DeleteViewRequestSchema;
+    output: typeof EmptySchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.GetChanges
+   */
+  getChanges: {
+    methodKind: "unary";
+    input: typeof GetChangesRequestSchema;
+    output: typeof ChangesResponseSchema;
+  },
+  /**
+   * @generated from rpc memory_v2.MemoryService.UpdateMemory
+   */
+  updateMemory: {
+    methodKind: "unary";
+    input: typeof UpdateMemoryRequestSchema;
+    output: typeof MemoryResponseSchema;
+  },
+}> = /*@__PURE__*/
+  serviceDesc(file_memory_v2, 0);
@@ -0,0 +1,5 @@
+export { apiKeyInterceptor, MemoryClient, type ContextIds, type MemoryClientOptions } from "./client.js";
+export * as admin_v1 from "./gen/admin_pb.js";
+export * as memory_v1 from "./gen/memory_pb.js";
+export * as memory_v2 from "./gen/memory_v2_pb.js";
+export { ContextPolicy, MemoryKind } from "./gen/memory_pb.js";
@@ -0,0 +1,18 @@
+import { createGrpcTransport } from "@connectrpc/connect-node";
+import { apiKeyInterceptor, MemoryClient, type MemoryClientOptions } from "./client.js";
+
+export interface ConnectOptions extends MemoryClientOptions {
+  /** Where `[grpc] server_addr` is reachable, e.g. `http://localhost:5051`. */
+  baseUrl: string;
+  /** Required when the server is configured with `[api_keys]`. */
+  apiKey?: string;
+}
+
+/** A client speaking gRPC over HTTP/2, as the server does. */
+export function connect(options: ConnectOptions): MemoryClient {
+  const transport = createGrpcTransport({
+    baseUrl: options.baseUrl,
+    interceptors: options.apiKey === undefined ? [] : [apiKeyInterceptor(options.apiKey)],
+  });
+  return new MemoryClient(transport, options);
+}
@@ -0,0 +1,70 @@
+use anyhow::{anyhow, bail, Result};
+use dotenv::dotenv;
+use umem_auth::{Access, ApiKeyStore, KeyScope};
+use umem_config::CONFIG;
+use uuid::Uuid;
+
+const USAGE: &str = "usage:
+  api_key create <name> [--user <user_id>] [--agent <agent_id>] [--org <org_id>] [--read-only]
+  api_key list
+  api_key revoke <id>";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let config = CONFIG
+        .api_keys
+        .as_ref()
+        .ok_or_else(|| anyhow!("[api_keys] is not configured"))?;
+    let store = ApiKeyStore::from_config(config).await?;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("create") => {
+            let name = args.get(1).ok_or_else(|| anyhow!(USAGE))?;
+            let mut scope = KeyScope::default();
+            let mut access = Access::ReadWrite;
+
+            let mut flags = args[2..].iter();
+            while let Some(flag) = flags.next() {
+                match flag.as_str() {
+                    "--user" => scope.user_id = flags.next().cloned(),
+                    "--agent" => scope.agent_id = flags.next().cloned(),
+                    "--org" => scope.org_id = flags.next().cloned(),
+                    "--read-only" => access = Access::Read,
+                    other => bail!("unknown flag {other}\n{USAGE}"),
+                }
+            }
+
+            let (key, secret) = store.create(name, scope, access).await?;
+            println!("created key {} ({})", key.id, key.access.as_str());
+            println!("{secret}");
+            println!("store it now, it cannot be shown again");
+        }
+        Some("list") => {
+            for key in store.list().await? {
+                println!(
+                    "{}\t{}\tuser={}\tagent={}\torg={}\t{}{}",
+                    key.id,
+                    key.name,
+                    key.scope.user_id.as_deref().unwrap_or("*"),
+                    key.scope.agent_id.as_deref().unwrap_or("*"),
+                    key.scope.org_id.as_deref().unwrap_or("-"),
+                    key.access.as_str(),
+                    if key.is_revoked() { "\trevoked" } else { "" }
+                );
+            }
+        }
+        Some("revoke") => {
+            let id = args.get(1).ok_or_else(|| anyhow!(USAGE))?;
+            if !store.revoke(&Uuid::parse_str(id)?).await? {
+                bail!("no active key with id {id}");
+            }
+            println!("revoked key {id}");
+        }
+        _ => bail!(USAGE),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,260 @@
+use anyhow::{anyhow, bail, Result};
+use dotenv::dotenv;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tonic::Request;
+use tracing::{info, warn};
+use umem::tracing_conf;
+use umem_ai::embed::{embed, EmbeddingRequest};
+use umem_controller::MemoryController;
+use umem_core::{Memory, MemoryGenerator};
+use umem_memory_machine::MemoryMachine;
+use umem_proto::{
+    memory_service_client::MemoryServiceClient, ContextFilter, MemoryListResponse,
+    SearchMemoriesRequest,
+};
+
+const DEFAULT_BATCH: usize = 100;
+const DEFAULT_CONCURRENCY: usize = 8;
+const DEFAULT_REQUESTS: usize = 200;
+
+const USAGE: &str = "usage:
+  bench seed [--count <n>] [--users <n>] [--days <n>] [--seed <n>] [--batch <n>]
+  bench search [--addr <url>] [--api-key <key>] [--concurrency <n>] [--requests <n>] [--users <n>] [--seed <n>]";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+    let _guard = tracing_conf::init_tracing()?;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("seed") => seed(&args[1..]).await,
+        Some("search") => search(&args[1..]).await,
+        _ => bail!(USAGE),
+    }
+}
+
+/// Fills the configured store with generated memories. Point the config at a scratch
+/// collection first. The same `--seed` produces the same ids, so a rerun only adds
+/// the memories that are missing.
+async fn seed(args: &[String]) -> Result<()> {
+    let mut generator = MemoryGenerator::default();
+    let mut batch = DEFAULT_BATCH;
+
+    let mut flags = args.iter();
+    while let Some(flag) = flags.next() {
+        let mut value = || flags.next().ok_or_else(|| anyhow!(USAGE));
+        match flag.as_str() {
+            "--count" => generator.count = value()?.parse()?,
+            "--users" => generator.users = value()?.parse()?,
+            "--days" => generator.days = value()?.parse()?,
+            "--seed" => generator.seed = value()?.parse()?,
+            "--batch" => batch = value()?.parse()?,
+            other => bail!("unknown flag {other}\n{USAGE}"),
+        }
+    }
+    if batch == 0 {
+        bail!("--batch must be at least 1");
+    }
+
+    let machine = MemoryMachine::new().await?;
+    let memories = generator.generate()?;
+    let mut stored = 0;
+    for chunk in memories.chunks(batch) {
+        stored += insert_batch(&machine.memory_controller, chunk).await?;
+        info!("seeded {stored} new memories of {}", memories.len());
+    }
+
+    println!(
+        "seeded {stored} new memories for {} users",
+        generator.users.max(1)
+    );
+    Ok(())
+}
+
+async fn insert_batch(controller: &MemoryController, memories: &[Memory]) -> Result<usize> {
+    let ids: Vec<_> = memories.iter().map(|memory| *memory.get_id()).collect();
+    let existing = controller.vector_store.existing_ids(&ids).await?;
+    let memories: Vec<&Memory> = memories
+        .iter()
+        .filter(|memory| !existing.contains(memory.get_id()))
+        .collect();
+    if memories.is_empty() {
+        return Ok(0);
+    }
+
+    let request = EmbeddingRequest::builder()
+        .model(Arc::clone(&controller.embedding_model))
+        .input(
+            memories
+                .iter()
+                .map(|memory| memory.get_summary().clone())
+                .collect(),
+        )
+        .build();
+    let embedding_response = embed(request).await?;
+
+    let vectors: Vec<&[f32]> = embedding_response
+        .embeddings
+        .iter()
+        .map(|inner| inner.as_slice())
+        .collect();
+    controller.vector_store.insert(&vectors, &memories).await?;
+    Ok(memories.len())
+}
+
+/// Fires generated queries at a running gRPC server from `--concurrency` workers and
+/// reports p50/p95/p99 latency end to end and for each stage the server timed. Use the
+/// same `--users` and `--seed` as `bench seed` so the queries hit seeded users.
+async fn search(args: &[String]) -> Result<()> {
+    let mut generator = MemoryGenerator::default();
+    let mut addr = None;
+    let mut api_key = None;
+    let mut concurrency = DEFAULT_CONCURRENCY;
+    let mut requests = DEFAULT_REQUESTS;
+
+    let mut flags = args.iter();
+    while let Some(flag) = flags.next() {
+        let mut value = || flags.next().ok_or_else(|| anyhow!(USAGE));
+        match flag.as_str() {
+            "--addr" => addr = Some(value()?.clone()),
+            "--api-key" => api_key = Some(value()?.clone()),
+            "--concurrency" => concurrency = value()?.parse()?,
+            "--requests" => requests = value()?.parse()?,
+            "--users" => generator.users = value()?.parse()?,
+            "--seed" => generator.seed = value()?.parse()?,
+            other => bail!("unknown flag {other}\n{USAGE}"),
+        }
+    }
+    let addr = addr.unwrap_or_else(|| format!("http://{}", umem_config::CONFIG.grpc.server_addr));
+    let client = MemoryServiceClient::connect(addr).await?;
+    let queries = Arc::new(generator.queries(requests));
+    let next = Arc::new(AtomicUsize::new(0));
+
+    let start = Instant::now();
+    let mut workers = Vec::new();
+    for _ in 0..concurrency.max(1) {
+        let mut client = client.clone();
+        let queries = Arc::clone(&queries);
+        let next = Arc::clone(&next);
+        let api_key = api_key.clone();
+
+        workers.push(tokio::spawn(async move {
+            let mut samples = Samples::default();
+            while let Some((user_id, query)) = queries.get(next.fetch_add(1, Ordering::Relaxed)) {
+                let mut request = Request::new(SearchMemoriesRequest {
+                    query: query.clone(),
+                    context: Some(ContextFilter {
+                        user_id: Some(user_id.clone()),
+                        agent_id: None,
+                        run_id: None,
+                        org_id: None,
+                    }),
+                    include_facets: false,
+                    explain: false,
+                });
+                if let Some(api_key) = &api_key {
+                    request
+                        .metadata_mut()
+                        .insert("authorization", format!("Bearer {api_key}").parse()?);
+                }
+
+                let sent = Instant::now();
+                match client.search_memories(request).await {
+                    Ok(response) => samples.record(sent.elapsed(), response.into_inner()),
+                    Err(status) => {
+                        warn!("search failed: {}", status.message());
+                        samples.errors += 1;
+                    }
+                }
+            }
+            Ok::<_, anyhow::Error>(samples)
+        }));
+    }
+
+    let mut samples = Samples::default();
+    for worker in workers {
+        samples.merge(worker.await??);
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{} requests, {} concurrent, {:.1} req/s, {} errors, {} timed out, {} rerank skipped",
+        requests,
+        concurrency.max(1),
+        requests as f64 / elapsed.as_secs_f64(),
+        samples.errors,
+        samples.timed_out,
+        samples.rerank_skipped
+    );
+    println!("stage\tp50_ms\tp95_ms\tp99_ms");
+    for (stage, latencies) in [
+        ("total", &mut samples.total),
+        ("embed", &mut samples.embed),
+        ("store", &mut samples.store),
+        ("rerank", &mut samples.rerank),
+    ] {
+        latencies.sort();
+        println!(
+            "{}\t{:.1}\t{:.1}\t{:.1}",
+            stage,
+            percentile_ms(latencies, 0.50),
+            percentile_ms(latencies, 0.95),
+            percentile_ms(latencies, 0.99)
+        );
+    }
+
+    Ok(())
+}
+
+/// Latencies of successful searches. `total` is what the client saw, the stages are
+/// what the server reported.
+#[derive(Default)]
+struct Samples {
+    total: Vec<Duration>,
+    embed: Vec<Duration>,
+    store: Vec<Duration>,
+    rerank: Vec<Duration>,
+    errors: usize,
+    timed_out: usize,
+    rerank_skipped: usize,
+}
+
+impl Samples {
+    fn record(&mut self, total: Duration, response: MemoryListResponse) {
+        self.total.push(total);
+        if let Some(timings) = response.timings {
+            self.embed.push(Duration::from_micros(timings.embed_micros));
+            self.store.push(Duration::from_micros(timings.store_micros));
+            self.rerank
+                .push(Duration::from_micros(timings.rerank_micros));
+        }
+        self.timed_out += response.timed_out as usize;
+        self.rerank_skipped += response.rerank_skipped as usize;
+    }
+
+    fn merge(&mut self, other: Samples) {
+        self.total.extend(other.total);
+        self.embed.extend(other.embed);
+        self.store.extend(other.store);
+        self.rerank.extend(other.rerank);
+        self.errors += other.errors;
+        self.timed_out += other.timed_out;
+        self.rerank_skipped += other.rerank_skipped;
+    }
+}
+
+/// Nearest-rank percentile of `sorted`, in milliseconds.
+fn percentile_ms(sorted: &[Duration], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len());
+    sorted[rank - 1].as_secs_f64() * 1000.0
+}
@@ -0,0 +1,73 @@
+use anyhow::{anyhow, bail, Result};
+use dotenv::dotenv;
+use umem::tracing_conf;
+use umem_eval::{EvalVariant, Evaluator, Fixture};
+use umem_memory_machine::MemoryMachine;
+
+const DEFAULT_K: usize = 5;
+
+const USAGE: &str = "usage:
+  eval <fixture.json> [--variants <variants.json>] [--k <k>] [--seed] [--json]";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+    let _guard = tracing_conf::init_tracing()?;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let fixture_path = args.first().ok_or_else(|| anyhow!(USAGE))?;
+    let mut variants = vec![EvalVariant {
+        name: "baseline".to_string(),
+        ..Default::default()
+    }];
+    let mut k = DEFAULT_K;
+    let mut seed = false;
+    let mut json = false;
+
+    let mut flags = args[1..].iter();
+    while let Some(flag) = flags.next() {
+        match flag.as_str() {
+            "--variants" => {
+                let path = flags.next().ok_or_else(|| anyhow!(USAGE))?;
+                variants = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+            }
+            "--k" => k = flags.next().ok_or_else(|| anyhow!(USAGE))?.parse()?,
+            "--seed" => seed = true,
+            "--json" => json = true,
+            other => bail!("unknown flag {other}\n{USAGE}"),
+        }
+    }
+
+    let machine = MemoryMachine::new().await?;
+    let evaluator = Evaluator::new(
+        machine.memory_controller,
+        Fixture::from_path(fixture_path)?,
+        k,
+    );
+    if seed {
+        println!("seeded {} fixture memories", evaluator.seed().await?);
+    }
+
+    if !json {
+        println!("variant\tcases\trecall@{k}\tmrr\tlatency_ms\trerank_skipped\ttimed_out");
+    }
+    for variant in &variants {
+        let report = evaluator.run(variant).await?;
+        if json {
+            println!("{}", serde_json::to_string(&report)?);
+        } else {
+            println!(
+                "{}\t{}\t{:.3}\t{:.3}\t{:.1}\t{}\t{}",
+                report.variant,
+                report.cases,
+                report.recall_at_k,
+                report.mrr,
+                report.mean_latency_ms,
+                report.rerank_skipped,
+                report.timed_out
+            );
+        }
+    }
+
+    Ok(())
+}
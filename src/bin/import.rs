@@ -0,0 +1,177 @@
+use anyhow::{anyhow, bail, Result};
+use dotenv::dotenv;
+use std::{str::FromStr, time::Duration};
+use tracing::info;
+use umem::tracing_conf;
+use umem_controller::{
+    BatchImportRequest, ConversationFormat, DrainImportQueueRequest, ImportFormat, ImportQueue,
+    QueuedWork,
+};
+use umem_memory_machine::MemoryMachine;
+
+const DEFAULT_QUEUE_URL: &str = "sqlite://import_queue.db";
+const DEFAULT_BATCH_SIZE: u32 = 64;
+const DEFAULT_SESSION_BATCH_SIZE: u32 = 4;
+const DEFAULT_POLL_SECS: u64 = 60;
+const SHOWN_FAILURES: u32 = 10;
+
+const USAGE: &str = "usage:
+  import --format mem0|zep|langchain <file> [--user <user_id>] [--batch-size <n>]
+  import --format chatgpt|claude <conversations.json> --user <user_id> [--batch-size <n>]
+         [--requests-per-minute <n>] [--session-chars <n>] [--batch-api [--poll-secs <n>]]
+  import resume [--retry-failed] [--batch-size <n>] [--requests-per-minute <n>]
+         [--batch-api [--poll-secs <n>]]
+  import status
+every command takes [--queue <sqlite url>] (default sqlite://import_queue.db)
+--batch-api annotates sessions through the OpenAI Batch API: half the price, answered
+within 24 hours";
+
+/// Memory store exports are stored as they are; conversation exports are annotated.
+enum Format {
+    Memories(ImportFormat),
+    Conversations(ConversationFormat),
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Ok(format) = ConversationFormat::from_str(s) {
+            return Ok(Self::Conversations(format));
+        }
+        Ok(Self::Memories(ImportFormat::from_str(s)?))
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+    let _guard = tracing_conf::init_tracing()?;
+
+    let mut command = None;
+    let mut format = None;
+    let mut path = None;
+    let mut user_id = None;
+    let mut batch_size = None;
+    let mut requests_per_minute = None;
+    let mut session_chars = None;
+    let mut retry_failed = false;
+    let mut batch_api = false;
+    let mut poll_secs = DEFAULT_POLL_SECS;
+    let mut queue_url = DEFAULT_QUEUE_URL.to_string();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value = args.next().ok_or_else(|| anyhow!(USAGE))?;
+                format = Some(Format::from_str(value)?);
+            }
+            "--user" => user_id = args.next().cloned(),
+            "--batch-size" => {
+                batch_size = Some(args.next().ok_or_else(|| anyhow!(USAGE))?.parse()?);
+            }
+            "--requests-per-minute" => {
+                requests_per_minute = Some(args.next().ok_or_else(|| anyhow!(USAGE))?.parse()?);
+            }
+            "--session-chars" => {
+                session_chars = Some(args.next().ok_or_else(|| anyhow!(USAGE))?.parse()?);
+            }
+            "--retry-failed" => retry_failed = true,
+            "--batch-api" => batch_api = true,
+            "--poll-secs" => poll_secs = args.next().ok_or_else(|| anyhow!(USAGE))?.parse()?,
+            "--queue" => queue_url = args.next().ok_or_else(|| anyhow!(USAGE))?.clone(),
+            flag if flag.starts_with("--") => bail!("unknown flag {flag}\n{USAGE}"),
+            "resume" | "status" if command.is_none() && path.is_none() => {
+                command = Some(arg.clone())
+            }
+            file => path = Some(file.to_string()),
+        }
+    }
+
+    let queue = ImportQueue::open(&queue_url).await?;
+    match (command.as_deref(), format, path) {
+        (Some("status"), ..) => {
+            let status = queue.status().await?;
+            println!(
+                "pending: {}\nsubmitted: {}\ndone: {}\nfailed: {}",
+                status.pending, status.submitted, status.done, status.failed
+            );
+            for (key, attempts, error) in queue.failures(SHOWN_FAILURES).await? {
+                println!("  {key} ({attempts} attempts): {error}");
+            }
+            return Ok(());
+        }
+        (Some("resume"), ..) => {
+            if retry_failed {
+                info!("retrying {} failed items", queue.retry_failed().await?);
+            }
+        }
+        (None, Some(Format::Memories(format)), Some(path)) => {
+            let input = std::fs::read_to_string(&path)?;
+            let memories = format.parse(&input, user_id.as_deref())?;
+            info!("read {} memories from {path}", memories.len());
+            let total = memories.len();
+            let queued = queue
+                .enqueue(memories.into_iter().map(QueuedWork::Memory))
+                .await?;
+            info!(
+                "queued {queued} memories ({} queued before)",
+                total as u64 - queued
+            );
+            batch_size = batch_size.or(Some(DEFAULT_BATCH_SIZE));
+        }
+        (None, Some(Format::Conversations(format)), Some(path)) => {
+            let Some(user_id) = user_id else {
+                bail!("{} exports need --user\n{USAGE}", format.as_str());
+            };
+            let input = std::fs::read_to_string(&path)?;
+            let sessions = format.parse(&input, session_chars)?;
+            info!("read {} sessions from {path}", sessions.len());
+            let total = sessions.len();
+            let queued = queue
+                .enqueue(sessions.into_iter().map(|session| QueuedWork::Session {
+                    user_id: user_id.clone(),
+                    session,
+                }))
+                .await?;
+            info!(
+                "queued {queued} sessions ({} queued before)",
+                total as u64 - queued
+            );
+            batch_size = batch_size.or(Some(DEFAULT_SESSION_BATCH_SIZE));
+        }
+        _ => bail!(USAGE),
+    }
+
+    let machine = MemoryMachine::new().await?;
+    let progress = if batch_api {
+        let request = BatchImportRequest::builder()
+            .batch_size(batch_size.unwrap_or(DEFAULT_BATCH_SIZE))
+            .poll_interval(Duration::from_secs(poll_secs))
+            .build();
+        machine
+            .memory_controller
+            .drain_import_queue_batched(&queue, request)
+            .await?
+    } else {
+        let request = DrainImportQueueRequest::builder()
+            .batch_size(batch_size.unwrap_or(DEFAULT_SESSION_BATCH_SIZE))
+            .requests_per_minute(requests_per_minute)
+            .build();
+        machine
+            .memory_controller
+            .drain_import_queue(&queue, request)
+            .await?
+    };
+    info!(
+        "import finished: {} items done, {} failed, {} memories stored",
+        progress.done, progress.failed, progress.memories
+    );
+    if progress.failed > 0 {
+        info!("see `import status` for the errors and `import resume --retry-failed` to retry");
+    }
+
+    Ok(())
+}
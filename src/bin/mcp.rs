@@ -1,5 +1,7 @@
 use anyhow::Result;
 use dotenv::dotenv;
+use std::sync::Arc;
+use tracing::error;
 use umem::tracing_conf;
 use umem_memory_machine::MemoryMachine;
 
@@ -8,7 +10,15 @@ async fn main() -> Result<()> {
     dotenv().ok();
     let _guard = tracing_conf::init_tracing()?;
 
-    let machine = MemoryMachine::new().await?;
+    let machine = Arc::new(MemoryMachine::new().await?);
+    if machine.config.health.is_some() {
+        let health = Arc::clone(&machine);
+        tokio::spawn(async move {
+            if let Err(e) = health.run_health().await {
+                error!("health endpoints failed: {}", e);
+            }
+        });
+    }
     let mcp_handle = tokio::spawn(async move { machine.run_grpc().await });
     mcp_handle.await??;
 
@@ -0,0 +1,125 @@
+use anyhow::{anyhow, bail, Result};
+use dotenv::dotenv;
+use tracing::info;
+use umem::tracing_conf;
+use umem_config::CONFIG;
+use umem_controller::ReembedRequest;
+use umem_memory_machine::MemoryMachine;
+use umem_vector_store::{migrations::CURRENT_SCHEMA_VERSION, VectorStore};
+
+/// Collection the configured embedder writes into; deterministic so an interrupted
+/// `--new-embedder` run resumes into the same collection.
+fn reembed_collection_name(vector_store: &umem_config::VectorStore) -> String {
+    let model: String = CONFIG
+        .embedding_model
+        .model
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!(
+        "{}_{}_{}",
+        vector_store.collection_name(),
+        model,
+        vector_store
+            .embedding_model_dimensions()
+            .unwrap_or_default()
+    )
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+    let _guard = tracing_conf::init_tracing()?;
+
+    if std::env::args().any(|arg| arg == "--drift") {
+        let machine = MemoryMachine::new().await?;
+        let drift = machine.memory_controller.embedding_drift().await?;
+        for (model, count) in &drift.models {
+            info!(
+                "{count} vectors embedded with {}",
+                model.as_deref().unwrap_or("an unrecorded model")
+            );
+        }
+        info!(
+            "{} of {} vectors ({:.1}%) are stale against {}; run reindex --new-embedder to replace them",
+            drift.stale,
+            drift.total,
+            drift.stale_fraction() * 100.0,
+            drift.current_model
+        );
+
+        return Ok(());
+    }
+
+    if std::env::args().any(|arg| arg == "--backfill") {
+        let dual_write = CONFIG
+            .dual_write
+            .as_ref()
+            .ok_or_else(|| anyhow!("[dual_write] is not configured"))?;
+        if dual_write.read_from_target.unwrap_or(false) {
+            bail!("backfill reads from the store being replaced; unset read_from_target first");
+        }
+        let vector_store =
+            umem_ai::resolve_vector_store(&dual_write.vector_store, &CONFIG.embedding_model)?;
+        let target_collection = vector_store.collection_name().to_string();
+        let target = VectorStore::from_config(&vector_store).await?;
+
+        let machine = MemoryMachine::new().await?;
+        let progress = machine
+            .memory_controller
+            .reembed(
+                ReembedRequest::builder()
+                    .target(target)
+                    .target_collection(target_collection)
+                    .promote(false)
+                    .build(),
+            )
+            .await?;
+        info!(
+            "backfill finished: {} copied, {} already present",
+            progress.embedded, progress.skipped
+        );
+
+        return Ok(());
+    }
+
+    if std::env::args().any(|arg| arg == "--new-embedder") {
+        let vector_store =
+            umem_ai::resolve_vector_store(&CONFIG.vector_store, &CONFIG.embedding_model)?;
+        let target_collection = reembed_collection_name(&vector_store);
+        let target = VectorStore::from_config(
+            &vector_store.with_collection_name(target_collection.as_str()),
+        )
+        .await?;
+
+        let machine = MemoryMachine::new().await?;
+        let progress = machine
+            .memory_controller
+            .reembed(
+                ReembedRequest::builder()
+                    .target(target)
+                    .target_collection(target_collection)
+                    .build(),
+            )
+            .await?;
+        info!(
+            "re-embedding finished: {} embedded, {} resumed",
+            progress.embedded, progress.skipped
+        );
+
+        return Ok(());
+    }
+
+    let vector_store = VectorStore::get_store().await?;
+    let upgraded = vector_store.reindex().await?;
+    info!("reindex upgraded {upgraded} payloads to schema version {CURRENT_SCHEMA_VERSION}");
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anyhow::{anyhow, bail, Result};
+use dotenv::dotenv;
+use umem::tracing_conf;
+use umem_controller::SearchMemoryOptions;
+use umem_core::{MemoryContext, ParsedQuery};
+use umem_memory_machine::MemoryMachine;
+
+const USAGE: &str = r#"usage:
+  search [--user <user_id>] [--agent <agent_id>] [--run <run_id>] [--explain] <query>
+the query takes filters next to its text, e.g.
+  search --user alice 'kind:procedural tag:deploy after:2024-01-01 "exact phrase" rollback'
+filters: kind: tag: topic: after: before: updated_after: updated_before: certainty:
+         salience: as_of: limit: is:archived"#;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+    let _guard = tracing_conf::init_tracing()?;
+
+    let mut user_id = None;
+    let mut agent_id = None;
+    let mut run_id = None;
+    let mut explain = false;
+    let mut words = Vec::new();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--user" => user_id = Some(args.next().ok_or_else(|| anyhow!(USAGE))?.clone()),
+            "--agent" => agent_id = Some(args.next().ok_or_else(|| anyhow!(USAGE))?.clone()),
+            "--run" => run_id = Some(args.next().ok_or_else(|| anyhow!(USAGE))?.clone()),
+            "--explain" => explain = true,
+            flag if flag.starts_with("--") => bail!("unknown flag {flag}\n{USAGE}"),
+            word => words.push(word),
+        }
+    }
+    if words.is_empty() {
+        bail!(USAGE);
+    }
+
+    let query: ParsedQuery = words.join(" ").parse()?;
+    let context = MemoryContext::new(user_id, agent_id, run_id)?;
+
+    let machine = MemoryMachine::new().await?;
+    let results = machine
+        .memory_controller
+        .parsed_search_with_context(
+            context,
+            query,
+            Some(SearchMemoryOptions::builder().explain(explain).build()),
+        )
+        .await?;
+
+    for memory in &results.memories {
+        println!(
+            "{}\t{}\t{}",
+            memory.get_id(),
+            memory.kind().as_str(),
+            memory.get_summary()
+        );
+    }
+    if let Some(explanation) = results.explanation {
+        println!("filter: {}", explanation.filter);
+        for candidate in explanation.candidates {
+            println!(
+                "  {} vector={:.3} rerank={:?} rank={:?}",
+                candidate.memory_id, candidate.vector_score, candidate.rerank_score, candidate.rank
+            );
+        }
+    }
+    if results.rerank_skipped {
+        println!("(reranking was skipped; results are in vector order)");
+    }
+
+    Ok(())
+}